@@ -0,0 +1,29 @@
+//! Records what `patingin version --verbose` reports about the build itself: the commit it
+//! was built from, the target and the enabled cargo features.
+
+use std::process::Command;
+
+fn main() {
+    let git_sha = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|sha| sha.trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=PATINGIN_GIT_SHA={git_sha}");
+
+    let target = std::env::var("TARGET").unwrap_or_default();
+    println!("cargo:rustc-env=PATINGIN_TARGET={target}");
+
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(name, _)| name.strip_prefix("CARGO_FEATURE_").map(str::to_string))
+        .map(|feature| feature.to_lowercase().replace('_', "-"))
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=PATINGIN_FEATURES={}", features.join(","));
+
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+}
@@ -0,0 +1,106 @@
+use anyhow::{anyhow, Result};
+use clap::{Args, Subcommand};
+use colored::*;
+use std::path::PathBuf;
+
+use crate::core::audit::AuditLog;
+
+#[derive(Args)]
+pub struct AuditArgs {
+    #[command(subcommand)]
+    pub action: AuditAction,
+
+    /// Audit log to read instead of the one configured in patingin.yml
+    #[arg(long, value_name = "PATH", global = true)]
+    pub log: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum AuditAction {
+    /// List recorded review runs, newest last
+    Show {
+        /// Show only the last N runs
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Output the records in JSON format
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check that no record was edited, removed or reordered
+    Verify,
+}
+
+pub async fn run(args: AuditArgs) -> Result<()> {
+    let audit_log = match args.log {
+        Some(path) => AuditLog::new(path),
+        None => AuditLog::for_project()?.ok_or_else(|| {
+            anyhow!("No audit log configured; set `audit: {{ log: <path> }}` in patingin.yml")
+        })?,
+    };
+
+    match args.action {
+        AuditAction::Show { limit, json } => show(&audit_log, limit, json),
+        AuditAction::Verify => verify(&audit_log),
+    }
+}
+
+fn show(audit_log: &AuditLog, limit: Option<usize>, json: bool) -> Result<()> {
+    let records = audit_log.read()?;
+    let skip = limit.map_or(0, |limit| records.len().saturating_sub(limit));
+    let records = &records[skip..];
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(records)?);
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        println!("📭 No runs recorded in {}", audit_log.path().display());
+        return Ok(());
+    }
+
+    println!("{} {}", "📜 Audit log:".bold(), audit_log.path().display());
+    println!();
+    for record in records {
+        let decision = match record.decision.as_str() {
+            "fail" => "❌ fail".red(),
+            _ => "✅ pass".green(),
+        };
+        println!(
+            "{} {} {} — {}",
+            record.timestamp.dimmed(),
+            decision,
+            record.command.bold(),
+            record.scope
+        );
+        println!(
+            "   {} violations ({} critical, {} major, {} warning) by {}",
+            record.total_violations, record.critical, record.major, record.warning, record.user
+        );
+        let head = record.head.as_deref().map_or("-", |head| &head[..head.len().min(7)]);
+        println!(
+            "   head {} · rules {}",
+            head.cyan(),
+            record.ruleset[..record.ruleset.len().min(12)].dimmed()
+        );
+    }
+    Ok(())
+}
+
+fn verify(audit_log: &AuditLog) -> Result<()> {
+    let (checked, problems) = audit_log.verify()?;
+    if problems.is_empty() {
+        println!("✅ {checked} record(s) verified in {}", audit_log.path().display());
+        return Ok(());
+    }
+
+    for problem in &problems {
+        eprintln!("❌ {}:{}: {}", audit_log.path().display(), problem.line, problem.reason);
+    }
+    eprintln!(
+        "❌ Audit log failed verification: {} problem(s) in {checked} record(s)",
+        problems.len()
+    );
+    std::process::exit(1);
+}
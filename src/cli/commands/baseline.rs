@@ -0,0 +1,28 @@
+use anyhow::Result;
+use std::collections::BTreeSet;
+
+use super::review::{build_review_engine, scan_project_tree};
+use crate::core::baseline::Baseline;
+
+/// Record every violation in the project as accepted, replacing the previous baseline
+pub async fn run() -> Result<()> {
+    let review_result = build_review_engine()?.review_git_diff(&scan_project_tree()?)?;
+
+    let path = Baseline::project_path()?;
+    let mut baseline = Baseline::from_violations(&review_result.violations);
+    baseline.keep_accepted(&Baseline::load(&path)?);
+    baseline.save(&path)?;
+
+    let files: BTreeSet<&str> =
+        baseline.violations.iter().map(|entry| entry.file.as_str()).collect();
+    println!(
+        "📌 Recorded {} violation(s) in {} file(s) to {}",
+        baseline.violations.len(),
+        files.len(),
+        path.display()
+    );
+    println!("💡 patingin review now only reports violations that are not in the baseline");
+    println!("   Commit the file to share it; drop fixed entries with:");
+    println!("   patingin review --all --update-baseline");
+    Ok(())
+}
@@ -0,0 +1,593 @@
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use serde_json::Value;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::review::{
+    apply_branch_policy, build_review_engine, describe_scope, ensure_scope_references,
+    failing_violations, filter_diff_by_language, publish_commit_status, record_audit,
+    report_budgets, select_branch_policy, skip_baselined, skip_format_only,
+};
+use crate::config::Config;
+use crate::core::coverage::FileCoverage;
+use crate::core::review_engine::{ReviewEngine, ReviewSummary};
+use crate::core::{Language, ReviewViolation, Severity};
+use crate::external::github::{render_summary_comment, CommentAction};
+use crate::external::GitHubIntegration;
+use crate::git::{DiffScope, GitDiffParser, GitIntegration};
+use crate::report::markdown::render_markdown;
+use crate::report::mentions::MentionsConfig;
+use crate::report::{report, ReportContext, ReporterRegistry};
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct CiArgs {
+    #[command(subcommand)]
+    pub provider: Option<CiProvider>,
+
+    #[command(flatten)]
+    pub defaults: DefaultCiArgs,
+}
+
+#[derive(Subcommand)]
+pub enum CiProvider {
+    /// Run inside GitHub Actions: scope from the event payload, annotations, job summary
+    /// and step outputs
+    Github(GithubCiArgs),
+}
+
+/// `patingin ci` without a provider: review a pull or merge request since its merge base on
+/// any CI system
+#[derive(Args)]
+pub struct DefaultCiArgs {
+    #[command(flatten)]
+    pub review: CiReviewArgs,
+
+    /// Branch the changes go into; defaults to GITHUB_BASE_REF,
+    /// CI_MERGE_REQUEST_TARGET_BRANCH_NAME or BITBUCKET_PR_DESTINATION_BRANCH, then to
+    /// `review.base` in patingin.yml
+    #[arg(long, value_name = "BRANCH")]
+    pub base: Option<String>,
+
+    /// Also write a SARIF report to this file, e.g. for code scanning uploads
+    #[arg(long, value_name = "PATH")]
+    pub sarif: Option<PathBuf>,
+
+    /// Also write a JUnit XML report to this file, for CI test report tabs
+    #[arg(long, value_name = "PATH")]
+    pub junit: Option<PathBuf>,
+
+    /// Append a markdown summary to this file; defaults to GITHUB_STEP_SUMMARY
+    #[arg(long, value_name = "PATH")]
+    pub summary: Option<PathBuf>,
+
+    /// Publish a pass/fail/neutral `patingin` status on the head commit
+    #[arg(long)]
+    pub set_commit_status: bool,
+}
+
+#[derive(Args)]
+pub struct GithubCiArgs {
+    #[command(flatten)]
+    pub review: CiReviewArgs,
+
+    /// Also post or update a summary comment on the pull request (needs GITHUB_TOKEN)
+    #[arg(long)]
+    pub comment: bool,
+
+    /// Publish a pass/fail/neutral `patingin` check run on the head commit
+    #[arg(long)]
+    pub set_commit_status: bool,
+
+    /// Event payload to read instead of GITHUB_EVENT_PATH
+    #[arg(long, value_name = "PATH")]
+    pub event_path: Option<PathBuf>,
+}
+
+/// What to review and when to fail, shared by every CI mode
+#[derive(Args)]
+pub struct CiReviewArgs {
+    /// Minimum severity to report
+    #[arg(long, value_name = "LEVEL")]
+    pub severity: Option<Severity>,
+
+    /// Fail the job when a violation at or above this severity is found; tag gates in
+    /// patingin.yml take precedence for the tags they cover
+    #[arg(long, value_name = "LEVEL")]
+    pub fail_on: Option<Severity>,
+
+    /// Tolerate up to N failing violations (every violation, without --fail-on or tag
+    /// gates) before failing the job
+    #[arg(long, value_name = "N")]
+    pub max_violations: Option<usize>,
+
+    /// Check only specific language files
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<Language>,
+
+    /// Also review lines whose change only re-indents, re-spaces or re-wraps existing code
+    #[arg(long)]
+    pub include_format_only: bool,
+}
+
+/// The CI system running us, from the variables each one sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiEnvironment {
+    GitHubActions,
+    GitLabCi,
+    BitbucketPipelines,
+    Other,
+}
+
+impl CiEnvironment {
+    pub fn detect(var: impl Fn(&str) -> Option<String>) -> Self {
+        let set = |name: &str| var(name).is_some_and(|value| !value.is_empty());
+        if set("GITHUB_ACTIONS") {
+            CiEnvironment::GitHubActions
+        } else if set("GITLAB_CI") {
+            CiEnvironment::GitLabCi
+        } else if set("BITBUCKET_BUILD_NUMBER") {
+            CiEnvironment::BitbucketPipelines
+        } else {
+            CiEnvironment::Other
+        }
+    }
+}
+
+impl std::fmt::Display for CiEnvironment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CiEnvironment::GitHubActions => write!(f, "GitHub Actions"),
+            CiEnvironment::GitLabCi => write!(f, "GitLab CI"),
+            CiEnvironment::BitbucketPipelines => write!(f, "Bitbucket Pipelines"),
+            CiEnvironment::Other => write!(f, "CI"),
+        }
+    }
+}
+
+/// Variables naming the branch a pull or merge request targets, by provider
+const TARGET_BRANCH_VARIABLES: [&str; 3] =
+    ["GITHUB_BASE_REF", "CI_MERGE_REQUEST_TARGET_BRANCH_NAME", "BITBUCKET_PR_DESTINATION_BRANCH"];
+
+/// Branch the request being built targets; `None` for push and scheduled builds
+pub fn target_branch(var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    TARGET_BRANCH_VARIABLES.iter().find_map(|name| var(name).filter(|value| !value.is_empty()))
+}
+
+/// What patingin needs from the workflow's event payload
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GithubEvent {
+    pub pr_number: Option<u64>,
+    pub base_sha: Option<String>,
+    pub head_sha: Option<String>,
+}
+
+impl GithubEvent {
+    /// Pull request events carry base and head commits; push events carry `before` and
+    /// `after`, where `before` is all zeros for a newly created branch.
+    pub fn from_payload(payload: &Value) -> Self {
+        let sha = |value: &Value| {
+            value.as_str().filter(|sha| !sha.chars().all(|c| c == '0')).map(String::from)
+        };
+
+        if let Some(pull_request) = payload.get("pull_request") {
+            return Self {
+                pr_number: pull_request["number"].as_u64(),
+                base_sha: sha(&pull_request["base"]["sha"]),
+                head_sha: sha(&pull_request["head"]["sha"]),
+            };
+        }
+        Self {
+            pr_number: None,
+            base_sha: sha(&payload["before"]),
+            head_sha: sha(&payload["after"]),
+        }
+    }
+
+    /// Read the payload GitHub Actions writes for the running workflow
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read event payload {}", path.display()))?;
+        let payload: Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse event payload {}", path.display()))?;
+        Ok(Self::from_payload(&payload))
+    }
+}
+
+pub async fn run(args: CiArgs) -> Result<()> {
+    match args.provider {
+        Some(CiProvider::Github(args)) => run_github(args).await,
+        None => run_default(args.defaults).await,
+    }
+}
+
+/// A review of one scope with the project's settings and gates
+struct CiReview {
+    scope: String,
+    violations: Vec<ReviewViolation>,
+    files: Vec<FileCoverage>,
+    failing: usize,
+    review_engine: ReviewEngine,
+}
+
+fn review_in_ci(diff_scope: &DiffScope, args: &CiReviewArgs, config: &Config) -> Result<CiReview> {
+    let mut git_diff = GitDiffParser::parse(&GitDiffParser::execute_git_diff(diff_scope)?)?;
+    if let Some(language) = &args.language {
+        git_diff = filter_diff_by_language(git_diff, language);
+    }
+    let ignored = config.remove_ignored(&mut git_diff);
+    let branch_policy = select_branch_policy()?;
+    let fail_on = args
+        .fail_on
+        .or(branch_policy.as_ref().and_then(|policy| policy.fail_on))
+        .or(config.review.fail_on);
+    let max_violations = args
+        .max_violations
+        .or(branch_policy.as_ref().and_then(|policy| policy.max_violations))
+        .or(config.review.max_violations);
+    if !args.include_format_only {
+        skip_format_only(&mut git_diff);
+    }
+    let review_engine = apply_branch_policy(build_review_engine()?, branch_policy.as_ref());
+    let mut review_result = review_engine.review_git_diff(&git_diff)?;
+    skip_baselined(&mut review_result, false)?;
+    let violations: Vec<ReviewViolation> = review_result
+        .violations
+        .into_iter()
+        .filter(|v| args.severity.map_or(true, |min| v.severity.is_at_least(min)))
+        .collect();
+
+    let failing = failing_violations(
+        review_engine.gates(),
+        review_engine.budgets(),
+        &violations,
+        fail_on,
+        max_violations,
+    );
+    Ok(CiReview {
+        scope: describe_scope(diff_scope),
+        files: review_engine.file_coverage(&git_diff, &ignored, &violations),
+        violations,
+        failing,
+        review_engine,
+    })
+}
+
+async fn run_default(args: DefaultCiArgs) -> Result<()> {
+    let var = |name: &str| std::env::var(name).ok();
+    let environment = CiEnvironment::detect(var);
+    let project_config = Config::load_for_project()?;
+    let base =
+        args.base.clone().or_else(|| target_branch(var)).or(project_config.review.base.clone());
+
+    let diff_scope = merge_base_scope(base.as_deref())?;
+    let review = review_in_ci(&diff_scope, &args.review, &project_config)?;
+    eprintln!("🔍 patingin ci ({environment}): {}", review.scope);
+
+    let context = ReportContext {
+        scope: review.scope.clone(),
+        files: review.files.clone(),
+        mentions: MentionsConfig::load_for_project()?,
+        ..Default::default()
+    };
+    let summary = review.review_engine.create_review_summary(&review.violations);
+    let registry = ReporterRegistry::new();
+    let mut human = registry.create("human", Box::new(std::io::stdout()))?;
+    report(human.as_mut(), &context, &review.violations, &summary)?;
+    if environment == CiEnvironment::GitHubActions {
+        let mut stdout = std::io::stdout();
+        for violation in &review.violations {
+            writeln!(stdout, "{}", annotation(violation))?;
+        }
+        append_to_file(
+            env_path("GITHUB_OUTPUT").as_deref(),
+            &render_outputs(&review.violations, review.failing > 0),
+        )?;
+    }
+    for (format, path) in [("sarif", &args.sarif), ("junit", &args.junit)] {
+        let Some(path) = path else { continue };
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut reporter = registry.create(format, Box::new(std::io::BufWriter::new(file)))?;
+        report(reporter.as_mut(), &context, &review.violations, &summary)?;
+        eprintln!("📄 Wrote the {format} report to {}", path.display());
+    }
+    append_to_file(
+        args.summary.clone().or_else(|| env_path("GITHUB_STEP_SUMMARY")).as_deref(),
+        &render_markdown(&review.violations, &review.scope),
+    )?;
+
+    if args.set_commit_status {
+        let head_sha = GitIntegration::new(".").and_then(|git| git.head_sha())?;
+        publish_commit_status(
+            &review.violations,
+            review.failing,
+            &review.scope,
+            None,
+            &head_sha,
+            None,
+        )
+        .await?;
+    }
+
+    finish("ci", review, summary)
+}
+
+/// Record the run and fail the job when violations failed their gate
+fn finish(command: &str, review: CiReview, summary: ReviewSummary) -> Result<()> {
+    report_budgets(review.review_engine.budgets(), &review.violations);
+    record_audit(command, &review.scope, &review.review_engine, summary, review.failing)?;
+    if review.failing > 0 {
+        eprintln!(
+            "❌ {} violation(s) failed --fail-on, --max-violations, their tag gate or budget",
+            review.failing
+        );
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Changes since the merge base with `base`, fetched first since CI clones are often
+/// shallow. Builds without a target branch review the branch since it left the default
+/// branch, or else the last commit.
+fn merge_base_scope(base: Option<&str>) -> Result<DiffScope> {
+    let git = GitIntegration::new(".")?;
+    let head = git.head_sha()?;
+    let Some(base) = base else {
+        let from = git
+            .default_branch_merge_base(&head)
+            .filter(|merge_base| *merge_base != head)
+            .or_else(|| git.resolves("HEAD~1").then(|| "HEAD~1".to_string()));
+        return Ok(from.map_or(DiffScope::All, |from| DiffScope::Between(from, head)));
+    };
+
+    let branch = base.strip_prefix("origin/").unwrap_or(base);
+    let remote_branch = format!("origin/{branch}");
+    if git.is_shallow() || !git.resolves(&remote_branch) {
+        // Without a remote, e.g. when trying it out locally, the local branch will do
+        if let Err(error) = git.fetch_branch(branch) {
+            if !git.resolves(&remote_branch) && !git.resolves(branch) {
+                return Err(error);
+            }
+        }
+    }
+    let reference = if git.resolves(&remote_branch) { remote_branch } else { branch.to_string() };
+    let from = git
+        .merge_base_between(&reference, &head)
+        .with_context(|| format!("No merge base between {reference} and HEAD"))?;
+    Ok(DiffScope::Between(from, head))
+}
+
+async fn run_github(args: GithubCiArgs) -> Result<()> {
+    let event_path = args.event_path.clone().or_else(|| env_path("GITHUB_EVENT_PATH"));
+    let event = match event_path {
+        Some(path) => GithubEvent::load(&path)?,
+        None => GithubEvent::default(),
+    };
+
+    let diff_scope = select_scope(&event)?;
+    eprintln!("🔍 patingin ci github: {}", describe_scope(&diff_scope));
+    let review = review_in_ci(&diff_scope, &args.review, &Config::load_for_project()?)?;
+    let (violations, failing, scope) = (&review.violations, review.failing, &review.scope);
+
+    let mut stdout = std::io::stdout();
+    for violation in violations {
+        writeln!(stdout, "{}", annotation(violation))?;
+    }
+
+    append_to_file(
+        env_path("GITHUB_STEP_SUMMARY").as_deref(),
+        &render_markdown(violations, scope),
+    )?;
+    append_to_file(env_path("GITHUB_OUTPUT").as_deref(), &render_outputs(violations, failing > 0))?;
+
+    if args.comment {
+        match event.pr_number {
+            Some(pr_number) => {
+                let github = GitHubIntegration::new();
+                let repo = GitHubIntegration::resolve_repository(None)?;
+                let body = render_summary_comment(
+                    violations,
+                    event.head_sha.as_deref(),
+                    &MentionsConfig::load_for_project()?,
+                );
+                let action = github.upsert_summary_comment(&repo, pr_number, &body).await?;
+                let verb = match action {
+                    CommentAction::Created => "Posted",
+                    CommentAction::Updated => "Updated",
+                };
+                eprintln!("💬 {verb} summary comment on {repo}#{pr_number}");
+            }
+            None => eprintln!("💡 Not a pull request event; skipping the summary comment"),
+        }
+    }
+
+    if args.set_commit_status {
+        let head_sha = match event.head_sha.clone() {
+            Some(sha) => sha,
+            None => GitIntegration::new(".").and_then(|git| git.head_sha())?,
+        };
+        publish_commit_status(violations, failing, scope, None, &head_sha, None).await?;
+    }
+
+    eprintln!("📊 {} violation(s) found", violations.len());
+    let summary = review.review_engine.create_review_summary(violations);
+    finish("ci github", review, summary)
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from)
+}
+
+/// Pull requests are reviewed from the merge base, so commits that landed on the base
+/// branch meanwhile are not blamed on the PR. Events without a usable base (manual runs,
+/// the first push of a branch) review the whole repository.
+fn select_scope(event: &GithubEvent) -> Result<DiffScope> {
+    let (Some(base), Some(head)) = (&event.base_sha, &event.head_sha) else {
+        return Ok(DiffScope::All);
+    };
+
+    // actions/checkout fetches a single commit by default
+    ensure_scope_references(&DiffScope::Between(base.clone(), head.clone()), true)?;
+
+    let from = match event.pr_number {
+        Some(_) => GitIntegration::new(".")
+            .and_then(|git| git.merge_base_between(base, head))
+            .unwrap_or_else(|_| base.clone()),
+        None => base.clone(),
+    };
+    Ok(DiffScope::Between(from, head.clone()))
+}
+
+/// A workflow command that shows the violation on the diff in the pull request UI
+pub fn annotation(violation: &ReviewViolation) -> String {
+    let level = match violation.severity {
+        Severity::Blocker | Severity::Critical => "error",
+        Severity::Major => "warning",
+        Severity::Warning | Severity::Info => "notice",
+    };
+    format!(
+        "::{level} file={},line={},title={}::{}",
+        escape_property(&violation.file_path),
+        violation.line_number,
+        escape_property(&format!("{} ({})", violation.rule.name, violation.rule.id)),
+        escape_data(&violation.fix_suggestion)
+    )
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// `name=value` lines for `$GITHUB_OUTPUT`, usable as `steps.<id>.outputs.<name>`
+pub fn render_outputs(violations: &[ReviewViolation], failed: bool) -> String {
+    let count = |severity| violations.iter().filter(|v| v.severity == severity).count();
+    format!(
+        "violations={}\nblocker={}\ncritical={}\nmajor={}\nwarning={}\ninfo={}\nfailed={failed}\n",
+        violations.len(),
+        count(Severity::Blocker),
+        count(Severity::Critical),
+        count(Severity::Major),
+        count(Severity::Warning),
+        count(Severity::Info)
+    )
+}
+
+/// Append to one of the files GitHub Actions collects after the step; a no-op outside
+/// Actions where the variable is unset
+fn append_to_file(path: Option<&Path>, content: &str) -> Result<()> {
+    let Some(path) = path else { return Ok(()) };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod ci_tests {
+    use super::*;
+    use crate::report::test_support::violation;
+    use serde_json::json;
+
+    #[test]
+    fn test_pull_request_event() {
+        let payload = json!({
+            "action": "synchronize",
+            "pull_request": {
+                "number": 42,
+                "base": { "sha": "aaa111" },
+                "head": { "sha": "bbb222" }
+            }
+        });
+
+        assert_eq!(
+            GithubEvent::from_payload(&payload),
+            GithubEvent {
+                pr_number: Some(42),
+                base_sha: Some("aaa111".to_string()),
+                head_sha: Some("bbb222".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_push_events_and_new_branches() {
+        let push = GithubEvent::from_payload(&json!({ "before": "ccc333", "after": "ddd444" }));
+        assert_eq!(push.pr_number, None);
+        assert_eq!(push.base_sha.as_deref(), Some("ccc333"));
+
+        let new_branch = GithubEvent::from_payload(&json!({
+            "before": "0000000000000000000000000000000000000000",
+            "after": "ddd444"
+        }));
+        assert_eq!(new_branch.base_sha, None);
+        assert_eq!(select_scope(&new_branch).unwrap(), DiffScope::All);
+
+        let dispatch = GithubEvent::from_payload(&json!({ "inputs": {} }));
+        assert_eq!(dispatch, GithubEvent::default());
+    }
+
+    #[test]
+    fn test_target_branch_and_environment() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+            }
+        };
+
+        let gitlab =
+            env(&[("GITLAB_CI", "true"), ("CI_MERGE_REQUEST_TARGET_BRANCH_NAME", "develop")]);
+        assert_eq!(target_branch(gitlab).as_deref(), Some("develop"));
+        assert_eq!(CiEnvironment::detect(gitlab), CiEnvironment::GitLabCi);
+
+        // GitHub sets GITHUB_BASE_REF to an empty string on push builds
+        let push = env(&[("GITHUB_ACTIONS", "true"), ("GITHUB_BASE_REF", "")]);
+        assert_eq!(target_branch(push), None);
+        assert_eq!(CiEnvironment::detect(push), CiEnvironment::GitHubActions);
+
+        let bitbucket =
+            env(&[("BITBUCKET_BUILD_NUMBER", "7"), ("BITBUCKET_PR_DESTINATION_BRANCH", "main")]);
+        assert_eq!(target_branch(bitbucket).as_deref(), Some("main"));
+        assert_eq!(CiEnvironment::detect(env(&[])), CiEnvironment::Other);
+    }
+
+    #[test]
+    fn test_annotation_levels_and_escaping() {
+        let mut critical = violation("lib/a,b.ex", 7, Severity::Critical);
+        critical.fix_suggestion = "Use 100% safe calls\nnot this".to_string();
+
+        assert_eq!(
+            annotation(&critical),
+            format!(
+                "::error file=lib/a%2Cb.ex,line=7,title={} ({})::Use 100%25 safe calls%0Anot this",
+                critical.rule.name, critical.rule.id
+            )
+        );
+        assert!(annotation(&violation("a.ex", 1, Severity::Warning)).starts_with("::notice "));
+    }
+
+    #[test]
+    fn test_outputs_are_appended() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let output_path = temp_dir.path().join("output");
+        std::fs::write(&output_path, "earlier=1\n").unwrap();
+        let violations =
+            vec![violation("a.ex", 1, Severity::Critical), violation("a.ex", 2, Severity::Warning)];
+
+        append_to_file(Some(&output_path), &render_outputs(&violations, true)).unwrap();
+        append_to_file(None, "ignored").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&output_path).unwrap(),
+            "earlier=1\nviolations=2\nblocker=0\ncritical=1\nmajor=0\nwarning=1\ninfo=0\nfailed=true\n"
+        );
+    }
+}
@@ -1,78 +1,191 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::{Args, Subcommand};
+use colored::*;
+use serde_json::json;
+use std::io::Write;
+
+use crate::config::resolve::{global_config_path, ConfigSource, ResolvedConfig};
+
+/// How the sources rank, for the show output
+const PRECEDENCE: &str = "command line > PATINGIN_* variables > patingin.yml > \
+                          ~/.config/patingin/config.yml > git config";
 
 #[derive(Args)]
-pub struct ConfigCommand {
+pub struct ConfigArgs {
     #[command(subcommand)]
-    pub subcommand: ConfigSubcommand,
+    pub action: ConfigAction,
 }
 
 #[derive(Subcommand)]
-pub enum ConfigSubcommand {
-    /// Show current configuration
+pub enum ConfigAction {
+    /// Show every setting with its value and the source it came from
     Show {
-        /// Show configuration in JSON format
+        /// Output the resolved configuration in JSON format
         #[arg(long)]
         json: bool,
     },
-    
-    /// Get a specific configuration value
+    /// Print the resolved value of one `review:` setting, e.g. `review.fail_on`
     Get {
-        /// Configuration key to retrieve
+        /// Setting to print
         key: String,
     },
-    
-    /// Set a configuration value
-    Set {
-        /// Configuration key to set
-        key: String,
-        /// Value to set
-        value: String,
-    },
-    
-    /// List all available configuration options
-    List {
-        /// Show descriptions for each option
-        #[arg(long)]
-        verbose: bool,
-    },
-    
-    /// Reset configuration to defaults
-    Reset {
-        /// Reset without confirmation prompt
-        #[arg(long)]
-        force: bool,
-    },
 }
 
-pub async fn run(args: ConfigCommand) -> Result<()> {
-    match args.subcommand {
-        ConfigSubcommand::Show { json } => {
-            println!("Config show command not yet implemented");
-            if json {
-                println!("JSON output requested");
-            }
-        }
-        ConfigSubcommand::Get { key } => {
-            println!("Config get command not yet implemented");
-            println!("Key: {}", key);
+pub async fn run(args: ConfigArgs) -> Result<()> {
+    let resolved = ResolvedConfig::load_current()?;
+    let mut out = std::io::stdout();
+    match args.action {
+        ConfigAction::Show { json: true } => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&show_json(&resolved))?)?
         }
-        ConfigSubcommand::Set { key, value } => {
-            println!("Config set command not yet implemented");
-            println!("Key: {}, Value: {}", key, value);
-        }
-        ConfigSubcommand::List { verbose } => {
-            println!("Config list command not yet implemented");
-            if verbose {
-                println!("Verbose output requested");
+        ConfigAction::Show { json: false } => show(&resolved, &mut out)?,
+        ConfigAction::Get { key } => writeln!(out, "{}", get(&resolved, &key)?)?,
+    }
+    Ok(())
+}
+
+fn show(resolved: &ResolvedConfig, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "{}", "⚙️  Resolved configuration".bold())?;
+    writeln!(out, "   {}", format!("Precedence: {PRECEDENCE}").dimmed())?;
+    writeln!(out)?;
+
+    writeln!(out, "📄 Config files")?;
+    for source in [ConfigSource::Project, ConfigSource::Global] {
+        let path = match (resolved.layer(source).and_then(|layer| layer.path.as_ref()), source) {
+            (Some(path), _) => path.display().to_string(),
+            (None, ConfigSource::Global) => {
+                format!("{} (not found)", global_config_path().display())
             }
-        }
-        ConfigSubcommand::Reset { force } => {
-            println!("Config reset command not yet implemented");
-            if force {
-                println!("Force reset requested");
+            (None, _) => "(not found)".to_string(),
+        };
+        writeln!(out, "  {:<8} {path}", source.to_string())?;
+    }
+    writeln!(out)?;
+
+    writeln!(out, "🔧 review")?;
+    for (key, setting) in resolved.review_settings() {
+        match setting {
+            Some((value, source)) => {
+                writeln!(out, "  {key:<15} {value:<12} {}", format!("({source})").dimmed())?
             }
+            None => writeln!(out, "  {key:<15} {}", "-".dimmed())?,
         }
     }
+
+    let config = resolved.config();
+    writeln!(out)?;
+    writeln!(
+        out,
+        "📏 rules: {} disabled, {} enabled, {} severity and {} threshold override(s)",
+        config.rules.disabled.len(),
+        config.rules.enabled.len(),
+        config.rules.severity.len(),
+        config.rules.thresholds.len()
+    )?;
+    if !config.ignore_paths.is_empty() {
+        writeln!(out, "🙈 ignore_paths: {}", config.ignore_paths.join(", "))?;
+    }
+    if !config.include_paths.is_empty() {
+        writeln!(out, "🎯 include_paths: {}", config.include_paths.join(", "))?;
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+fn show_json(resolved: &ResolvedConfig) -> serde_json::Value {
+    let review: serde_json::Map<String, serde_json::Value> = resolved
+        .review_settings()
+        .into_iter()
+        .map(|(key, setting)| {
+            let value = match setting {
+                Some((value, source)) => json!({ "value": value, "source": source }),
+                None => serde_json::Value::Null,
+            };
+            (key.to_string(), value)
+        })
+        .collect();
+    let files: Vec<serde_json::Value> = resolved
+        .layers()
+        .iter()
+        .filter_map(|layer| {
+            let path = layer.path.as_ref()?;
+            Some(json!({ "source": layer.source, "path": path }))
+        })
+        .collect();
+    let config = resolved.config();
+
+    json!({
+        "files": files,
+        "review": review,
+        "rules": config.rules,
+        "ignore_paths": config.ignore_paths,
+        "include_paths": config.include_paths,
+    })
+}
+
+/// The value of `key`, given with or without its `review.` prefix
+fn get(resolved: &ResolvedConfig, key: &str) -> Result<String> {
+    let name = key.strip_prefix("review.").unwrap_or(key);
+    let settings = resolved.review_settings();
+    let Some((_, setting)) = settings.iter().find(|(setting_key, _)| *setting_key == name) else {
+        let keys: Vec<String> = settings.iter().map(|(key, _)| format!("review.{key}")).collect();
+        return Err(anyhow!("Unknown setting '{key}'. Available settings: {}", keys.join(", ")));
+    };
+    match setting {
+        Some((value, _)) => Ok(value.clone()),
+        None => Err(anyhow!("review.{name} is not set")),
+    }
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    use crate::config::resolve::ConfigLayer;
+    use std::path::PathBuf;
+
+    fn resolved() -> ResolvedConfig {
+        ResolvedConfig::new(vec![
+            ConfigLayer {
+                source: ConfigSource::Project,
+                path: Some(PathBuf::from("/repo/patingin.yml")),
+                config: serde_yaml::from_str(
+                    "review: {fail_on: major, scope: staged}\nrules: {disabled: [long_line]}",
+                )
+                .unwrap(),
+            },
+            ConfigLayer {
+                source: ConfigSource::Env,
+                path: None,
+                config: serde_yaml::from_str("review: {fail_on: critical}").unwrap(),
+            },
+        ])
+    }
+
+    #[test]
+    fn test_show_lists_values_with_their_source() {
+        colored::control::set_override(false);
+        let mut out = Vec::new();
+        show(&resolved(), &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("project  /repo/patingin.yml"), "{out}");
+        assert!(out.contains("global   ") && out.contains("(not found)"));
+        assert!(out.contains("fail_on         critical     (env)"));
+        assert!(out.contains("scope           staged       (project)"));
+        assert!(out.contains("base            -"));
+        assert!(out.contains("📏 rules: 1 disabled, 0 enabled"));
+
+        let json = show_json(&resolved());
+        assert_eq!(json["review"]["fail_on"]["source"], "env");
+        assert_eq!(json["review"]["base"], serde_json::Value::Null);
+        assert_eq!(json["files"][0]["source"], "project");
+    }
+
+    #[test]
+    fn test_get() {
+        assert_eq!(get(&resolved(), "review.fail_on").unwrap(), "critical");
+        assert_eq!(get(&resolved(), "scope").unwrap(), "staged");
+        assert_eq!(get(&resolved(), "base").unwrap_err().to_string(), "review.base is not set");
+        let error = get(&resolved(), "review.colour").unwrap_err().to_string();
+        assert!(error.contains("Available settings: review.preset, review.scope"), "{error}");
+    }
+}
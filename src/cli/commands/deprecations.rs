@@ -0,0 +1,48 @@
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+
+use crate::config::deprecations::{DeprecationPolicy, DEPRECATIONS};
+
+#[derive(Args)]
+pub struct DeprecationsArgs {
+    /// Output the deprecations in JSON format
+    #[arg(long)]
+    pub json: bool,
+}
+
+pub async fn run(args: DeprecationsArgs) -> Result<()> {
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(DEPRECATIONS)?);
+        return Ok(());
+    }
+
+    if DEPRECATIONS.is_empty() {
+        println!("✅ No flags are deprecated");
+        return Ok(());
+    }
+
+    println!("{}", "🗓️  Deprecated flags".bold());
+    println!();
+    for deprecation in DEPRECATIONS {
+        println!(
+            "  {} {} → {}",
+            format!("patingin {}", deprecation.command).dimmed(),
+            deprecation.flag.yellow().bold(),
+            deprecation.replacement.green()
+        );
+        println!(
+            "    Deprecated in {}, removed in {}",
+            deprecation.deprecated_in, deprecation.removal_in
+        );
+        println!("    {}", deprecation.note);
+        println!();
+    }
+
+    if DeprecationPolicy::load_for_project()?.allow_deprecated {
+        println!("💡 Set `allow_deprecated: false` in patingin.yml to make these flags errors now");
+    } else {
+        println!("🚫 This project sets `allow_deprecated: false`: these flags are errors");
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+
+use super::review::project_root;
+use crate::core::fix_backup::SavedFixBackup;
+
+#[derive(Args)]
+pub struct FixArgs {
+    /// Restore the files the last fix run changed to how they were before it
+    #[arg(long)]
+    pub rollback: bool,
+
+    /// With --rollback, restore files even if they were edited after the fixes
+    #[arg(long, requires = "rollback")]
+    pub force: bool,
+}
+
+/// Show the backup of the last `review` fix run, or with `--rollback` restore it
+pub async fn run(args: FixArgs) -> Result<()> {
+    let Some(backup) = SavedFixBackup::load(&project_root())? else {
+        println!("📭 No fix backup; one is kept of the files each fix run changes");
+        return Ok(());
+    };
+
+    if args.rollback {
+        let command = backup.command().to_string();
+        let restored = backup.restore(args.force)?;
+        println!("↩️  Rolled back {} file(s) changed by `{command}`:", restored.len());
+        for file in &restored {
+            let action = if file.existed { "restored" } else { "removed" };
+            println!("   {} {}", file.path, action.dimmed());
+        }
+        return Ok(());
+    }
+
+    println!("{} {} at {}", "🗂️  Last fixes:".bold(), backup.command(), backup.created_at());
+    let edited = backup.edited_since();
+    for file in backup.files() {
+        let note = match (edited.contains(&file.path.as_str()), file.existed) {
+            (true, _) => "edited since".yellow(),
+            (false, false) => "created".dimmed(),
+            (false, true) => "".normal(),
+        };
+        println!("   {} {}", file.path, note);
+    }
+    println!();
+    println!("💡 Undo them with: patingin fix --rollback");
+    Ok(())
+}
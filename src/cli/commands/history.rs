@@ -0,0 +1,290 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use colored::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use super::review::{build_review_engine, filter_diff_by_language};
+use crate::config::Config;
+use crate::core::{Language, ReviewEngine, ReviewViolation};
+use crate::git::{CommitInfo, GitDiff, GitIntegration};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum HistoryFormat {
+    /// Per-commit and per-author tables for the terminal
+    #[default]
+    Table,
+    Json,
+}
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    /// Report the commits after this reference (branch, tag or commit), or a `base..head`
+    /// range
+    #[arg(long, value_name = "REF")]
+    pub since: String,
+
+    /// Check only specific language files
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<Language>,
+
+    /// Output format
+    #[arg(long, value_name = "FORMAT", default_value = "table")]
+    pub format: HistoryFormat,
+}
+
+/// Violations one commit introduced and fixed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommitTrend {
+    pub sha: String,
+    pub summary: String,
+    pub author: String,
+    pub introduced: usize,
+    pub fixed: usize,
+}
+
+/// One author's commits in the inspected history, summed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthorTrend {
+    pub author: String,
+    pub commits: usize,
+    pub introduced: usize,
+    pub fixed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryReport {
+    pub since: String,
+    pub commits: Vec<CommitTrend>,
+    /// Ordered by net violations added, worst first
+    pub authors: Vec<AuthorTrend>,
+    pub introduced: usize,
+    pub fixed: usize,
+}
+
+pub async fn run(args: HistoryArgs) -> Result<()> {
+    let git = GitIntegration::new(".")?;
+    for reference in args.since.split("..").filter(|reference| !reference.is_empty()) {
+        git.ensure_reference(reference, false)?;
+    }
+    let commits = git.non_merge_commits(&args.since)?;
+
+    let project_config = Config::load_for_project()?;
+    let review_engine = build_review_engine()?;
+
+    let mut trends = Vec::with_capacity(commits.len());
+    for commit in commits {
+        let prepare = |mut git_diff: GitDiff| {
+            if let Some(language) = &args.language {
+                git_diff = filter_diff_by_language(git_diff, language);
+            }
+            project_config.remove_ignored(&mut git_diff);
+            git_diff
+        };
+
+        let added = prepare(git.diff_between(commit.parent.as_deref(), &commit.sha)?);
+        let introduced = review(&review_engine, &added)?;
+        // Reversing the commit turns its removed lines into added ones. A root commit
+        // removes nothing.
+        let fixed = match &commit.parent {
+            Some(parent) => {
+                review(&review_engine, &prepare(git.diff_between(Some(&commit.sha), parent)?))?
+            }
+            None => Vec::new(),
+        };
+
+        trends.push(commit_trend(commit, &introduced, &fixed));
+    }
+
+    let report = build_report(&args.since, trends);
+    match args.format {
+        HistoryFormat::Table => output_table(&report),
+        HistoryFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    Ok(())
+}
+
+fn review(review_engine: &ReviewEngine, git_diff: &GitDiff) -> Result<Vec<ReviewViolation>> {
+    Ok(review_engine.review_git_diff(git_diff)?.violations)
+}
+
+/// Net the violations on a commit's added lines against those on its removed lines, rule
+/// by rule, so that moved, renamed and edited lines still matching the same rule count as
+/// neither introduced nor fixed
+pub fn commit_trend(
+    commit: CommitInfo,
+    on_added_lines: &[ReviewViolation],
+    on_removed_lines: &[ReviewViolation],
+) -> CommitTrend {
+    let mut net_by_rule: HashMap<&str, i64> = HashMap::new();
+    for violation in on_added_lines {
+        *net_by_rule.entry(&violation.rule.id).or_default() += 1;
+    }
+    for violation in on_removed_lines {
+        *net_by_rule.entry(&violation.rule.id).or_default() -= 1;
+    }
+
+    CommitTrend {
+        introduced: net_by_rule.values().filter(|net| **net > 0).sum::<i64>() as usize,
+        fixed: net_by_rule.values().filter(|net| **net < 0).map(|net| -net).sum::<i64>() as usize,
+        sha: commit.sha,
+        summary: commit.summary,
+        author: commit.author,
+    }
+}
+
+/// Totals and per-author sums of the commit trends
+pub fn build_report(since: &str, commits: Vec<CommitTrend>) -> HistoryReport {
+    let mut by_author: BTreeMap<&str, AuthorTrend> = BTreeMap::new();
+    for commit in &commits {
+        let author = by_author.entry(&commit.author).or_insert_with(|| AuthorTrend {
+            author: commit.author.clone(),
+            commits: 0,
+            introduced: 0,
+            fixed: 0,
+        });
+        author.commits += 1;
+        author.introduced += commit.introduced;
+        author.fixed += commit.fixed;
+    }
+
+    let net = |introduced: usize, fixed: usize| introduced as i64 - fixed as i64;
+    let mut authors: Vec<AuthorTrend> = by_author.into_values().collect();
+    authors.sort_by_key(|author| std::cmp::Reverse(net(author.introduced, author.fixed)));
+
+    HistoryReport {
+        since: since.to_string(),
+        introduced: commits.iter().map(|commit| commit.introduced).sum(),
+        fixed: commits.iter().map(|commit| commit.fixed).sum(),
+        commits,
+        authors,
+    }
+}
+
+/// `+2` in red for added violations, `-1` in green for removed ones
+fn colored_net(introduced: usize, fixed: usize) -> ColoredString {
+    let net = introduced as i64 - fixed as i64;
+    match net {
+        net if net > 0 => format!("+{net}").red(),
+        net if net < 0 => net.to_string().green(),
+        _ => "0".normal(),
+    }
+}
+
+fn output_table(report: &HistoryReport) {
+    println!(
+        "📈 Violation history since {} ({} commit(s), merges skipped)",
+        report.since,
+        report.commits.len()
+    );
+    println!();
+
+    if report.commits.is_empty() {
+        println!("✅ No commits to report");
+        return;
+    }
+
+    println!(
+        "{:<7}  {:>5}  {:>5}  {:>4}  {:<20}  Summary",
+        "Commit", "Intro", "Fixed", "Net", "Author"
+    );
+    for commit in &report.commits {
+        println!(
+            "{:<7}  {:>5}  {:>5}  {:>4}  {:<20}  {}",
+            &commit.sha[..commit.sha.len().min(7)],
+            commit.introduced,
+            commit.fixed,
+            colored_net(commit.introduced, commit.fixed),
+            truncate(&commit.author, 20),
+            commit.summary
+        );
+    }
+
+    println!();
+    println!("👥 By author");
+    println!("{:<20}  {:>7}  {:>5}  {:>5}  {:>4}", "Author", "Commits", "Intro", "Fixed", "Net");
+    for author in &report.authors {
+        println!(
+            "{:<20}  {:>7}  {:>5}  {:>5}  {:>4}",
+            truncate(&author.author, 20),
+            author.commits,
+            author.introduced,
+            author.fixed,
+            colored_net(author.introduced, author.fixed)
+        );
+    }
+
+    println!();
+    println!(
+        "📊 {} introduced, {} fixed, net {}",
+        report.introduced,
+        report.fixed,
+        colored_net(report.introduced, report.fixed)
+    );
+    println!("💡 Counts are netted per rule, so moved or edited lines still matching are neither");
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(width - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::*;
+    use crate::core::Severity;
+    use crate::report::test_support::violation;
+
+    fn commit(sha: &str, author: &str) -> CommitInfo {
+        CommitInfo {
+            sha: sha.to_string(),
+            summary: format!("Commit {sha}"),
+            author: author.to_string(),
+            parent: None,
+        }
+    }
+
+    fn with_rule(rule_id: &str) -> ReviewViolation {
+        let mut violation = violation("lib/a.ex", 1, Severity::Major);
+        violation.rule.id = rule_id.to_string();
+        violation
+    }
+
+    #[test]
+    fn test_commit_trend_nets_each_rule() {
+        // One atom call moved elsewhere, one added; one debug print removed
+        let added = [with_rule("dynamic_atom"), with_rule("dynamic_atom")];
+        let removed = [with_rule("dynamic_atom"), with_rule("io_inspect")];
+
+        let trend = commit_trend(commit("abc", "Ana"), &added, &removed);
+        assert_eq!((trend.introduced, trend.fixed), (1, 1));
+
+        let refactor = commit_trend(commit("def", "Ana"), &removed, &removed);
+        assert_eq!((refactor.introduced, refactor.fixed), (0, 0));
+    }
+
+    #[test]
+    fn test_build_report_sums_by_author() {
+        let trend = |sha: &str, author: &str, introduced: usize, fixed: usize| CommitTrend {
+            introduced,
+            fixed,
+            ..commit_trend(commit(sha, author), &[], &[])
+        };
+        let report = build_report(
+            "v1.0",
+            vec![trend("a", "Ana", 3, 0), trend("b", "Ben", 0, 2), trend("c", "Ana", 0, 1)],
+        );
+
+        assert_eq!((report.introduced, report.fixed), (3, 3));
+        let authors: Vec<(&str, usize, usize, usize)> = report
+            .authors
+            .iter()
+            .map(|a| (a.author.as_str(), a.commits, a.introduced, a.fixed))
+            .collect();
+        assert_eq!(authors, [("Ana", 2, 3, 1), ("Ben", 1, 0, 2)]);
+    }
+}
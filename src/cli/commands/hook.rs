@@ -0,0 +1,245 @@
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand};
+use std::path::{Path, PathBuf};
+
+use crate::core::Severity;
+use crate::git::GitIntegration;
+
+const HOOK_NAME: &str = "pre-commit";
+
+/// Identifies hooks written by patingin, so they are updated rather than chained to
+const MARKER: &str = "# patingin pre-commit hook";
+
+/// A hook that was already installed is moved here and run before the checks
+const CHAINED_SUFFIX: &str = ".pre-patingin";
+
+#[derive(Args)]
+pub struct HookArgs {
+    #[command(subcommand)]
+    pub action: HookAction,
+}
+
+#[derive(Subcommand)]
+pub enum HookAction {
+    /// Install a pre-commit hook that blocks commits whose staged changes have violations
+    Install {
+        /// Block commits with violations at or above this severity
+        #[arg(long, value_name = "LEVEL", default_value = "critical")]
+        severity: Severity,
+
+        /// Remove the hook instead, restoring the one it chained to
+        #[arg(long)]
+        uninstall: bool,
+    },
+}
+
+/// What `install` did with the hook slot
+#[derive(Debug, PartialEq, Eq)]
+enum Installed {
+    New,
+    /// Another hook was there; it now runs first
+    Chained,
+    /// A patingin hook was there and was rewritten
+    Updated,
+}
+
+/// What `uninstall` did with the hook slot
+#[derive(Debug, PartialEq, Eq)]
+enum Uninstalled {
+    /// No patingin hook was installed; nothing was touched
+    NotInstalled,
+    Removed,
+    /// The chained hook was moved back into place
+    Restored,
+}
+
+pub async fn run(args: HookArgs) -> Result<()> {
+    let hooks_dir = GitIntegration::new(".")
+        .context("patingin hook must be run inside a git repository")?
+        .hooks_dir();
+
+    match args.action {
+        HookAction::Install { uninstall: true, .. } => match uninstall(&hooks_dir)? {
+            Uninstalled::NotInstalled => {
+                println!("📭 No patingin hook installed in {}", hooks_dir.display())
+            }
+            Uninstalled::Removed => println!("🗑️  Removed the patingin pre-commit hook"),
+            Uninstalled::Restored => println!(
+                "🗑️  Removed the patingin pre-commit hook and restored the previous {HOOK_NAME} hook"
+            ),
+        },
+        HookAction::Install { severity, .. } => {
+            let hook = hooks_dir.join(HOOK_NAME);
+            match install(&hooks_dir, severity)? {
+                Installed::New => println!("✅ Installed {}", hook.display()),
+                Installed::Updated => println!("🔄 Updated {}", hook.display()),
+                Installed::Chained => println!(
+                    "🔗 Installed {} and kept the existing hook as {HOOK_NAME}{CHAINED_SUFFIX}; \
+                     it runs first",
+                    hook.display()
+                ),
+            }
+            println!("🚦 Commits with staged {severity} violations or worse will be blocked");
+            println!("💡 Skip once with: git commit --no-verify");
+        }
+    }
+    Ok(())
+}
+
+fn chained_path(hook: &Path) -> PathBuf {
+    hook.with_file_name(format!("{HOOK_NAME}{CHAINED_SUFFIX}"))
+}
+
+/// Hooks can be any executable, so look for the marker in raw bytes
+fn is_patingin_hook(hook: &Path) -> bool {
+    std::fs::read(hook)
+        .map(|content| content.windows(MARKER.len()).any(|window| window == MARKER.as_bytes()))
+        .unwrap_or(false)
+}
+
+fn install(hooks_dir: &Path, severity: Severity) -> Result<Installed> {
+    let hook = hooks_dir.join(HOOK_NAME);
+    let installed = if hook.symlink_metadata().is_err() {
+        Installed::New
+    } else if is_patingin_hook(&hook) {
+        Installed::Updated
+    } else {
+        let chained = chained_path(&hook);
+        if chained.symlink_metadata().is_ok() {
+            bail!(
+                "{} already exists; remove it or merge it into {} before installing",
+                chained.display(),
+                hook.display()
+            );
+        }
+        std::fs::rename(&hook, &chained)
+            .with_context(|| format!("Failed to move {} aside", hook.display()))?;
+        Installed::Chained
+    };
+
+    std::fs::create_dir_all(hooks_dir)?;
+    std::fs::write(&hook, hook_script(severity))
+        .with_context(|| format!("Failed to write {}", hook.display()))?;
+    make_executable(&hook)?;
+    Ok(installed)
+}
+
+fn uninstall(hooks_dir: &Path) -> Result<Uninstalled> {
+    let hook = hooks_dir.join(HOOK_NAME);
+    if !is_patingin_hook(&hook) {
+        return Ok(Uninstalled::NotInstalled);
+    }
+
+    std::fs::remove_file(&hook).with_context(|| format!("Failed to remove {}", hook.display()))?;
+    let chained = chained_path(&hook);
+    if chained.symlink_metadata().is_err() {
+        return Ok(Uninstalled::Removed);
+    }
+    std::fs::rename(&chained, &hook)
+        .with_context(|| format!("Failed to restore {}", chained.display()))?;
+    Ok(Uninstalled::Restored)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn hook_script(severity: Severity) -> String {
+    format!(
+        r#"#!/bin/sh
+{MARKER}
+# Blocks commits whose staged changes have anti-patterns at or above {severity} severity.
+# Skip once with `git commit --no-verify`; remove with `patingin hook install --uninstall`.
+
+# The hook that was installed before patingin runs first
+if [ -x "$0{CHAINED_SUFFIX}" ]; then
+    "$0{CHAINED_SUFFIX}" "$@" || exit $?
+fi
+
+if ! command -v patingin >/dev/null 2>&1; then
+    echo "patingin not found; skipping anti-pattern checks" >&2
+    exit 0
+fi
+
+if ! patingin review --staged --severity {severity} --fail-on {severity} --no-color --no-state; then
+    echo "" >&2
+    echo "❌ Commit blocked by patingin. Fix with: patingin review --staged --fix" >&2
+    echo "💡 Or skip once with: git commit --no-verify" >&2
+    exit 1
+fi
+"#
+    )
+}
+
+#[cfg(test)]
+mod hook_tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_script() {
+        let script = hook_script(Severity::Major);
+        assert!(script.starts_with("#!/bin/sh\n# patingin pre-commit hook\n"));
+        assert!(script.contains(
+            "patingin review --staged --severity major --fail-on major --no-color --no-state"
+        ));
+    }
+
+    #[test]
+    fn test_install_updates_its_own_hook() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hooks_dir = temp_dir.path().join("hooks");
+        let hook = hooks_dir.join(HOOK_NAME);
+
+        assert_eq!(install(&hooks_dir, Severity::Critical).unwrap(), Installed::New);
+        assert_eq!(install(&hooks_dir, Severity::Major).unwrap(), Installed::Updated);
+        assert!(std::fs::read_to_string(&hook).unwrap().contains("--severity major"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&hook).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+
+        assert_eq!(uninstall(&hooks_dir).unwrap(), Uninstalled::Removed);
+        assert!(!hook.exists());
+        assert_eq!(uninstall(&hooks_dir).unwrap(), Uninstalled::NotInstalled);
+    }
+
+    #[test]
+    fn test_existing_hook_is_chained_and_restored() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hooks_dir = temp_dir.path();
+        let hook = hooks_dir.join(HOOK_NAME);
+        std::fs::write(&hook, "#!/bin/sh\nnpx lint-staged\n").unwrap();
+
+        assert_eq!(install(hooks_dir, Severity::Critical).unwrap(), Installed::Chained);
+        assert!(is_patingin_hook(&hook));
+        assert_eq!(
+            std::fs::read_to_string(chained_path(&hook)).unwrap(),
+            "#!/bin/sh\nnpx lint-staged\n"
+        );
+
+        assert_eq!(uninstall(hooks_dir).unwrap(), Uninstalled::Restored);
+        assert_eq!(std::fs::read_to_string(&hook).unwrap(), "#!/bin/sh\nnpx lint-staged\n");
+        assert!(!chained_path(&hook).exists());
+    }
+
+    #[test]
+    fn test_install_refuses_to_overwrite_a_chained_hook() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let hook = temp_dir.path().join(HOOK_NAME);
+        std::fs::write(&hook, "#!/bin/sh\necho one\n").unwrap();
+        std::fs::write(chained_path(&hook), "#!/bin/sh\necho two\n").unwrap();
+
+        assert!(install(temp_dir.path(), Severity::Critical).is_err());
+        assert_eq!(std::fs::read_to_string(&hook).unwrap(), "#!/bin/sh\necho one\n");
+    }
+}
@@ -0,0 +1,405 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use colored::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use super::review::{build_review_engine, filter_diff_by_language};
+use crate::core::{Language, ReviewViolation, Severity};
+use crate::git::{DiffScope, GitDiffParser, GitIntegration};
+use crate::report::html::escape_html;
+use crate::report::summary_severities;
+
+/// Files shorter than this are treated as this long, so a one-line file with a single
+/// violation does not outrank genuinely troubled modules
+const MIN_DENSITY_LINES: usize = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum HotspotFormat {
+    /// Ranked table for the terminal
+    #[default]
+    Table,
+    Json,
+    /// Self-contained HTML treemap sized by score and colored by worst severity
+    Html,
+}
+
+#[derive(Args)]
+pub struct HotspotsArgs {
+    /// Number of files to show
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    pub limit: usize,
+
+    /// How many recent commits to count churn over
+    #[arg(long, value_name = "COMMITS", default_value_t = 1000)]
+    pub history: usize,
+
+    /// Check only specific language files
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<Language>,
+
+    /// Output format
+    #[arg(long, value_name = "FORMAT", default_value = "table")]
+    pub format: HotspotFormat,
+}
+
+/// A file ranked by how badly and how often-changed its code is
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Hotspot {
+    pub file_path: String,
+    pub lines: usize,
+    pub blocker: usize,
+    pub critical: usize,
+    pub major: usize,
+    pub warning: usize,
+    pub info: usize,
+    /// Commits touching the file within the inspected history
+    pub churn: usize,
+    /// Severity-weighted violations per 100 lines
+    pub density: f64,
+    /// `density × churn`: dense problems in files that keep changing come first
+    pub score: f64,
+}
+
+impl Hotspot {
+    fn count(&self, severity: Severity) -> usize {
+        match severity {
+            Severity::Blocker => self.blocker,
+            Severity::Critical => self.critical,
+            Severity::Major => self.major,
+            Severity::Warning => self.warning,
+            Severity::Info => self.info,
+        }
+    }
+
+    fn worst_severity(&self) -> Severity {
+        Severity::ALL
+            .into_iter()
+            .find(|severity| self.count(*severity) > 0)
+            .unwrap_or(Severity::Info)
+    }
+}
+
+/// Informational findings do not make a file a hotspot on their own
+fn severity_weight(severity: Severity) -> usize {
+    match severity {
+        Severity::Blocker => 8,
+        Severity::Critical => 5,
+        Severity::Major => 3,
+        Severity::Warning => 1,
+        Severity::Info => 0,
+    }
+}
+
+pub async fn run(args: HotspotsArgs) -> Result<()> {
+    let git = GitIntegration::new(".")?;
+
+    let mut git_diff = GitDiffParser::parse(&GitDiffParser::execute_git_diff(&DiffScope::All)?)?;
+    if let Some(language) = &args.language {
+        git_diff = filter_diff_by_language(git_diff, language);
+    }
+    let line_counts: HashMap<String, usize> =
+        git_diff.files.iter().map(|file| (file.path.clone(), file.added_lines.len())).collect();
+
+    let review_result = build_review_engine()?.review_git_diff(&git_diff)?;
+    let churn = git.file_churn(args.history)?;
+
+    let mut hotspots = compute_hotspots(&review_result.violations, &line_counts, &churn);
+    hotspots.truncate(args.limit);
+
+    match args.format {
+        HotspotFormat::Table => output_table(&hotspots, args.history),
+        HotspotFormat::Json => println!("{}", serde_json::to_string_pretty(&hotspots)?),
+        HotspotFormat::Html => print!("{}", render_html_treemap(&hotspots)),
+    }
+
+    Ok(())
+}
+
+/// Rank files with violations by severity-weighted density times churn, highest first
+pub fn compute_hotspots(
+    violations: &[ReviewViolation],
+    line_counts: &HashMap<String, usize>,
+    churn: &HashMap<String, usize>,
+) -> Vec<Hotspot> {
+    let mut by_file: BTreeMap<&str, BTreeMap<Severity, usize>> = BTreeMap::new();
+    for violation in violations {
+        *by_file.entry(&violation.file_path).or_default().entry(violation.severity).or_default() +=
+            1;
+    }
+
+    let mut hotspots: Vec<Hotspot> = by_file
+        .into_iter()
+        .map(|(file_path, counts)| {
+            let count = |severity| counts.get(&severity).copied().unwrap_or_default();
+            let lines = line_counts.get(file_path).copied().unwrap_or_default();
+            let weighted: usize =
+                counts.iter().map(|(severity, count)| count * severity_weight(*severity)).sum();
+            let density = weighted as f64 * 100.0 / lines.max(MIN_DENSITY_LINES) as f64;
+            // Uncommitted files have no history yet but still deserve a place in the list
+            let churn = churn.get(file_path).copied().unwrap_or_default();
+            Hotspot {
+                file_path: file_path.to_string(),
+                lines,
+                blocker: count(Severity::Blocker),
+                critical: count(Severity::Critical),
+                major: count(Severity::Major),
+                warning: count(Severity::Warning),
+                info: count(Severity::Info),
+                churn,
+                density,
+                score: density * churn.max(1) as f64,
+            }
+        })
+        .collect();
+
+    hotspots.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.file_path.cmp(&b.file_path)));
+    hotspots
+}
+
+fn output_table(hotspots: &[Hotspot], history: usize) {
+    println!("🔥 Hotspots: files most in need of refactoring (churn over {history} commits)");
+    println!();
+
+    if hotspots.is_empty() {
+        println!("✅ No anti-pattern violations found!");
+        return;
+    }
+
+    println!(
+        "{:>3}  {:>8}  {:>7}  {:>5}  {:>3} {:>3} {:>3} {:>3} {:>3}  File",
+        "#", "Score", "Density", "Churn", "⛔", "🔴", "🟡", "🔵", "⚪"
+    );
+    for (rank, hotspot) in hotspots.iter().enumerate() {
+        let file = match hotspot.worst_severity() {
+            Severity::Blocker => hotspot.file_path.red().bold(),
+            Severity::Critical => hotspot.file_path.red(),
+            Severity::Major => hotspot.file_path.yellow(),
+            Severity::Warning => hotspot.file_path.blue(),
+            Severity::Info => hotspot.file_path.normal(),
+        };
+        println!(
+            "{:>3}  {:>8.1}  {:>7.1}  {:>5}  {:>3} {:>3} {:>3} {:>3} {:>3}  {}",
+            rank + 1,
+            hotspot.score,
+            hotspot.density,
+            hotspot.churn,
+            hotspot.blocker,
+            hotspot.critical,
+            hotspot.major,
+            hotspot.warning,
+            hotspot.info,
+            file
+        );
+    }
+    println!();
+    println!(
+        "💡 Density is severity-weighted violations per 100 lines \
+         (blocker 8, critical 5, major 3, warning 1, info 0)"
+    );
+}
+
+/// Rectangle in percent of the treemap area
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Tile {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Split `area` between `weights` (sorted descending) by recursively halving the total
+/// weight along the longer side, which keeps tiles reasonably square.
+fn layout_treemap(weights: &[f64], area: Tile) -> Vec<Tile> {
+    if weights.len() <= 1 {
+        return weights.iter().map(|_| area).collect();
+    }
+
+    let total: f64 = weights.iter().sum();
+    let mut split = 1;
+    let mut first_sum = weights[0];
+    while split < weights.len() - 1 && first_sum + weights[split] <= total / 2.0 {
+        first_sum += weights[split];
+        split += 1;
+    }
+    let ratio = if total > 0.0 { first_sum / total } else { split as f64 / weights.len() as f64 };
+
+    let (first, second) = if area.width >= area.height {
+        let first_width = area.width * ratio;
+        (
+            Tile { width: first_width, ..area },
+            Tile { x: area.x + first_width, width: area.width - first_width, ..area },
+        )
+    } else {
+        let first_height = area.height * ratio;
+        (
+            Tile { height: first_height, ..area },
+            Tile { y: area.y + first_height, height: area.height - first_height, ..area },
+        )
+    };
+
+    let mut tiles = layout_treemap(&weights[..split], first);
+    tiles.extend(layout_treemap(&weights[split..], second));
+    tiles
+}
+
+fn render_html_treemap(hotspots: &[Hotspot]) -> String {
+    let weights: Vec<f64> = hotspots.iter().map(|hotspot| hotspot.score).collect();
+    let tiles = layout_treemap(&weights, Tile { x: 0.0, y: 0.0, width: 100.0, height: 100.0 });
+
+    let mut html = String::from(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>patingin hotspots</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 2rem; }
+.treemap { position: relative; width: 100%; height: 70vh; }
+.tile { position: absolute; box-sizing: border-box; border: 2px solid #fff; padding: 4px;
+        overflow: hidden; color: #fff; font-size: 12px; }
+.blocker { background: #86181d; } .critical { background: #d73a49; }
+.major { background: #b08800; } .warning { background: #0366d6; } .info { background: #6a737d; }
+</style>
+</head>
+<body>
+<h1>🔥 patingin hotspots</h1>
+<p>Tile area is density × churn; color is the worst severity in the file.</p>
+<div class="treemap">
+"#,
+    );
+
+    for (hotspot, tile) in hotspots.iter().zip(tiles) {
+        let class = hotspot.worst_severity();
+        let path = escape_html(&hotspot.file_path);
+        let counts = summary_severities(|severity| hotspot.count(severity))
+            .into_iter()
+            .map(|severity| format!("{} {severity}", hotspot.count(severity)))
+            .collect::<Vec<_>>()
+            .join(" / ");
+        html.push_str(&format!(
+            "<div class=\"tile {class}\" style=\"left:{:.2}%;top:{:.2}%;width:{:.2}%;height:{:.2}%\" title=\"{path}: score {:.1}, density {:.1}, churn {}, {counts}\">{path}</div>\n",
+            tile.x,
+            tile.y,
+            tile.width,
+            tile.height,
+            hotspot.score,
+            hotspot.density,
+            hotspot.churn
+        ));
+    }
+
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod hotspots_tests {
+    use super::*;
+    use crate::core::{AntiPattern, DetectionMethod};
+
+    fn violation(file_path: &str, severity: Severity) -> ReviewViolation {
+        ReviewViolation {
+            rule: AntiPattern {
+                id: "test_rule".to_string(),
+                name: "Test Rule".to_string(),
+                language: Language::Elixir,
+                severity,
+                description: "Test description".to_string(),
+                detection_method: DetectionMethod::Regex { pattern: "test".to_string() },
+                fix_suggestion: "Fix this test issue".to_string(),
+                source_url: None,
+                claude_code_fixable: false,
+                examples: vec![],
+                tags: vec![],
+                ai_hint: None,
+                auto_fix: None,
+                skip_comments: false,
+                skip_strings: false,
+                enabled: true,
+            },
+            file_path: file_path.to_string(),
+            line_number: 1,
+            content: "test()".to_string(),
+            severity,
+            language: Language::Elixir,
+            fix_suggestion: "Fix this test issue".to_string(),
+            auto_fixable: false,
+            context_before: vec![],
+            context_after: vec![],
+            confidence: 0.85,
+            span: None,
+            blame: None,
+            captures: BTreeMap::new(),
+            owners: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_compute_hotspots_combines_density_and_churn() {
+        let violations = vec![
+            violation("lib/stable.ex", Severity::Critical),
+            violation("lib/stable.ex", Severity::Critical),
+            violation("lib/busy.ex", Severity::Major),
+            violation("lib/huge.ex", Severity::Critical),
+        ];
+        let line_counts = HashMap::from([
+            ("lib/stable.ex".to_string(), 100),
+            ("lib/busy.ex".to_string(), 100),
+            ("lib/huge.ex".to_string(), 1000),
+        ]);
+        let churn = HashMap::from([
+            ("lib/stable.ex".to_string(), 1),
+            ("lib/busy.ex".to_string(), 10),
+            ("lib/huge.ex".to_string(), 10),
+        ]);
+
+        let hotspots = compute_hotspots(&violations, &line_counts, &churn);
+        let order: Vec<&str> = hotspots.iter().map(|h| h.file_path.as_str()).collect();
+
+        assert_eq!(order, ["lib/busy.ex", "lib/stable.ex", "lib/huge.ex"]);
+        assert_eq!(hotspots[0].density, 3.0);
+        assert_eq!(hotspots[0].score, 30.0);
+        assert_eq!(hotspots[1].critical, 2);
+        assert_eq!(hotspots[2].density, 0.5);
+    }
+
+    #[test]
+    fn test_small_and_uncommitted_files_are_damped() {
+        let violations = vec![violation("new.ex", Severity::Warning)];
+        let line_counts = HashMap::from([("new.ex".to_string(), 1)]);
+
+        let hotspots = compute_hotspots(&violations, &line_counts, &HashMap::new());
+
+        assert_eq!(hotspots[0].density, 4.0);
+        assert_eq!(hotspots[0].churn, 0);
+        assert_eq!(hotspots[0].score, 4.0);
+    }
+
+    #[test]
+    fn test_layout_treemap_covers_area_proportionally() {
+        let area = Tile { x: 0.0, y: 0.0, width: 100.0, height: 100.0 };
+        let tiles = layout_treemap(&[50.0, 25.0, 25.0], area);
+
+        assert_eq!(tiles.len(), 3);
+        assert_eq!(tiles[0], Tile { x: 0.0, y: 0.0, width: 50.0, height: 100.0 });
+        let total_area: f64 = tiles.iter().map(|tile| tile.width * tile.height).sum();
+        assert!((total_area - 10_000.0).abs() < 1e-9);
+        assert!((tiles[2].width * tiles[2].height - 2_500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_render_html_treemap_escapes_paths() {
+        let hotspots = compute_hotspots(
+            &[violation("lib/<weird>.ex", Severity::Critical)],
+            &HashMap::new(),
+            &HashMap::new(),
+        );
+        let html = render_html_treemap(&hotspots);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("class=\"tile critical\""));
+        assert!(html.contains("lib/&lt;weird&gt;.ex"));
+        assert!(!html.contains("<weird>"));
+    }
+}
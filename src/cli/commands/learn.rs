@@ -0,0 +1,348 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use colored::*;
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+
+use super::review::{build_review_engine, filter_diff_by_language};
+use crate::core::registry::PatternRegistry;
+use crate::core::{AntiPattern, Language, ReviewViolation, Severity};
+use crate::git::{DiffScope, GitDiffParser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum QuizSource {
+    /// The bad/good examples shipped with each rule
+    #[default]
+    Examples,
+    /// Violations currently present in this repository, shown without file names
+    Repo,
+}
+
+#[derive(Args)]
+pub struct LearnArgs {
+    /// Where quiz questions come from
+    #[arg(long, value_name = "SOURCE", default_value = "examples")]
+    pub source: QuizSource,
+
+    /// Quiz on rules for one language only
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<Language>,
+
+    /// Number of questions
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    pub count: usize,
+
+    /// Shuffle seed, to replay the same quiz
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+}
+
+/// One piece of bad code to fix, plus what to reveal afterwards
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuizQuestion {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub language: Language,
+    pub severity: Severity,
+    pub bad_code: String,
+    pub good_code: Option<String>,
+    pub explanation: String,
+    pub source_url: Option<String>,
+}
+
+impl QuizQuestion {
+    fn from_example(rule: &AntiPattern, example: &crate::core::CodeExample) -> Self {
+        Self {
+            rule_id: rule.id.clone(),
+            rule_name: rule.name.clone(),
+            language: rule.language.clone(),
+            severity: rule.severity,
+            bad_code: example.bad.clone(),
+            good_code: Some(example.good.clone()),
+            explanation: example.explanation.clone(),
+            source_url: rule.source_url.clone(),
+        }
+    }
+
+    /// A real violation, stripped of its file name, line number and authorship
+    fn from_violation(violation: &ReviewViolation) -> Self {
+        let rule = &violation.rule;
+        let mut bad_code: Vec<&str> = violation.context_before.iter().map(String::as_str).collect();
+        bad_code.push(&violation.content);
+        bad_code.extend(violation.context_after.iter().map(String::as_str));
+
+        Self {
+            rule_id: rule.id.clone(),
+            rule_name: rule.name.clone(),
+            language: violation.language.clone(),
+            severity: violation.severity,
+            bad_code: bad_code.join("\n"),
+            good_code: rule.examples.first().map(|example| example.good.clone()),
+            explanation: rule
+                .examples
+                .first()
+                .map_or_else(|| rule.description.clone(), |example| example.explanation.clone()),
+            source_url: rule.source_url.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct QuizScore {
+    pub asked: usize,
+    pub correct: usize,
+}
+
+pub async fn run(args: LearnArgs) -> Result<()> {
+    let mut registry = PatternRegistry::new();
+    registry.load_built_in_patterns()?;
+
+    let mut questions = match args.source {
+        QuizSource::Examples => example_questions(&registry, args.language.as_ref()),
+        QuizSource::Repo => repo_questions(args.language.as_ref())?,
+    };
+    if questions.is_empty() {
+        println!("🎓 Nothing to quiz on yet: no matching rule examples or violations found");
+        return Ok(());
+    }
+
+    let seed = args.seed.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(1, |elapsed| elapsed.as_nanos() as u64)
+    });
+    shuffle(&mut questions, seed);
+    questions.truncate(args.count);
+
+    let stdin = std::io::stdin();
+    let score = run_quiz(&questions, &registry, &mut stdin.lock(), &mut std::io::stdout())?;
+
+    println!();
+    println!("🎓 Score: {}/{} (replay with --seed {seed})", score.correct, score.asked);
+    Ok(())
+}
+
+fn example_questions(registry: &PatternRegistry, language: Option<&Language>) -> Vec<QuizQuestion> {
+    registry
+        .all_patterns()
+        .into_iter()
+        .filter(|rule| rule.enabled && language.map_or(true, |language| rule.language == *language))
+        .flat_map(|rule| {
+            rule.examples.iter().map(|example| QuizQuestion::from_example(rule, example))
+        })
+        .collect()
+}
+
+fn repo_questions(language: Option<&Language>) -> Result<Vec<QuizQuestion>> {
+    let mut git_diff = GitDiffParser::parse(&GitDiffParser::execute_git_diff(&DiffScope::All)?)?;
+    if let Some(language) = language {
+        git_diff = filter_diff_by_language(git_diff, language);
+    }
+    let review_result = build_review_engine()?.review_git_diff(&git_diff)?;
+
+    // The same offending line copied across files makes for a boring quiz
+    let mut seen = HashSet::new();
+    Ok(review_result
+        .violations
+        .iter()
+        .filter(|violation| {
+            seen.insert((violation.rule.id.clone(), violation.content.trim().to_string()))
+        })
+        .map(QuizQuestion::from_violation)
+        .collect())
+}
+
+/// Deterministic Fisher-Yates shuffle driven by xorshift, so `--seed` replays a quiz
+fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed.max(1);
+    for index in (1..items.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        items.swap(index, (state % (index as u64 + 1)) as usize);
+    }
+}
+
+fn prompt<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    text: &str,
+) -> Result<Option<String>> {
+    write!(output, "{text}")?;
+    output.flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+/// Ask each question, reveal the rule's answer and let the developer grade themselves.
+/// Stops early when input ends.
+pub fn run_quiz<R: BufRead, W: Write>(
+    questions: &[QuizQuestion],
+    registry: &PatternRegistry,
+    input: &mut R,
+    output: &mut W,
+) -> Result<QuizScore> {
+    let mut score = QuizScore::default();
+
+    for (index, question) in questions.iter().enumerate() {
+        let severity = format!("{} {}", question.severity.icon(), question.severity);
+        writeln!(
+            output,
+            "\n📝 Question {}/{} — {} · {}",
+            index + 1,
+            questions.len(),
+            question.language,
+            severity
+        )?;
+        writeln!(output, "   What is wrong here, and how would you write it instead?\n")?;
+        for line in question.bad_code.lines() {
+            writeln!(output, "     {}", line.red())?;
+        }
+        writeln!(output)?;
+
+        let Some(answer) = prompt(input, output, "✏️  Your fix (enter to skip): ")? else {
+            break;
+        };
+        score.asked += 1;
+
+        if !answer.is_empty() {
+            if let Some(pattern) =
+                registry.get_compiled_variant(&question.rule_id, &question.language)
+            {
+                if pattern.is_match(&answer) {
+                    writeln!(output, "   ⚠️  Your fix would still trigger this rule")?;
+                } else {
+                    writeln!(output, "   ✅ Your fix no longer triggers this rule")?;
+                }
+            }
+        }
+
+        writeln!(output, "\n💡 {} ({})", question.rule_name.bold(), question.rule_id.dimmed())?;
+        if let Some(good_code) = &question.good_code {
+            for line in good_code.lines() {
+                writeln!(output, "   ✅ {}", line.green())?;
+            }
+        }
+        writeln!(output, "   📖 {}", question.explanation)?;
+        if let Some(source_url) = &question.source_url {
+            writeln!(output, "   🔗 {source_url}")?;
+        }
+
+        if answer.is_empty() {
+            continue;
+        }
+        match prompt(input, output, "\n🤔 Did you get it right? [y/N]: ")? {
+            Some(grade) if matches!(grade.to_lowercase().as_str(), "y" | "yes") => {
+                score.correct += 1
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    Ok(score)
+}
+
+#[cfg(test)]
+mod learn_tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn registry() -> PatternRegistry {
+        let mut registry = PatternRegistry::new();
+        registry.load_built_in_patterns().unwrap();
+        registry
+    }
+
+    fn atom_question(registry: &PatternRegistry) -> QuizQuestion {
+        let rule = registry.get_pattern("dynamic_atom_creation").unwrap();
+        QuizQuestion::from_example(rule, &rule.examples[0])
+    }
+
+    #[test]
+    fn test_example_questions_filter_by_language() {
+        let registry = registry();
+        let questions = example_questions(&registry, Some(&Language::Elixir));
+
+        assert!(!questions.is_empty());
+        assert!(questions.iter().all(|question| question.language == Language::Elixir));
+        assert!(questions.iter().any(|question| question.rule_id == "dynamic_atom_creation"));
+    }
+
+    #[test]
+    fn test_shuffle_is_deterministic_per_seed() {
+        let mut first: Vec<u32> = (0..20).collect();
+        let mut second = first.clone();
+        shuffle(&mut first, 42);
+        shuffle(&mut second, 42);
+
+        assert_eq!(first, second);
+        assert_ne!(first, (0..20).collect::<Vec<u32>>());
+        first.sort();
+        assert_eq!(first, (0..20).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_quiz_checks_answer_and_counts_self_grade() {
+        colored::control::set_override(false);
+        let registry = registry();
+        let questions = vec![atom_question(&registry), atom_question(&registry)];
+        let mut input =
+            std::io::Cursor::new("String.to_existing_atom(input)\ny\nString.to_atom(x)\nn\n");
+        let mut output = Vec::new();
+
+        let score = run_quiz(&questions, &registry, &mut input, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+
+        assert_eq!(score, QuizScore { asked: 2, correct: 1 });
+        assert!(text.contains("📝 Question 1/2 — elixir · 🔴 critical"));
+        assert!(text.contains("String.to_atom(user_input)"));
+        assert!(text.contains("✅ Your fix no longer triggers this rule"));
+        assert!(text.contains("⚠️  Your fix would still trigger this rule"));
+        assert!(text.contains("✅ String.to_existing_atom(user_input)"));
+    }
+
+    #[test]
+    fn test_quiz_stops_at_end_of_input_and_skips_are_not_graded() {
+        let registry = registry();
+        let questions = vec![atom_question(&registry), atom_question(&registry)];
+        let mut input = std::io::Cursor::new("\n");
+        let mut output = Vec::new();
+
+        let score = run_quiz(&questions, &registry, &mut input, &mut output).unwrap();
+
+        assert_eq!(score, QuizScore { asked: 1, correct: 0 });
+        assert!(!String::from_utf8(output).unwrap().contains("Did you get it right"));
+    }
+
+    #[test]
+    fn test_repo_questions_are_anonymized() {
+        let registry = registry();
+        let violation = ReviewViolation {
+            rule: registry.get_pattern("dynamic_atom_creation").unwrap().clone(),
+            file_path: "lib/secret_project/billing.ex".to_string(),
+            line_number: 42,
+            content: "String.to_atom(params[\"kind\"])".to_string(),
+            severity: Severity::Critical,
+            language: Language::Elixir,
+            fix_suggestion: String::new(),
+            auto_fixable: true,
+            context_before: vec!["def kind(params) do".to_string()],
+            context_after: vec!["end".to_string()],
+            confidence: 0.85,
+            span: None,
+            blame: None,
+            captures: BTreeMap::new(),
+            owners: Vec::new(),
+        };
+
+        let question = QuizQuestion::from_violation(&violation);
+
+        assert_eq!(question.bad_code, "def kind(params) do\nString.to_atom(params[\"kind\"])\nend");
+        assert!(!format!("{question:?}").contains("billing.ex"));
+        assert_eq!(question.good_code.as_deref(), Some("String.to_existing_atom(user_input)"));
+    }
+}
@@ -1,3 +1,21 @@
+pub mod audit;
+pub mod baseline;
+pub mod ci;
+pub mod config;
+pub mod deprecations;
+pub mod fix;
+pub mod history;
+pub mod hook;
+pub mod hotspots;
+pub mod learn;
+pub mod pre_receive;
+pub mod report;
 pub mod review;
+pub mod rule_editor;
+pub mod rule_wizard;
 pub mod rules;
+pub mod self_update;
 pub mod setup;
+pub mod stats;
+pub mod suggest_reviewers;
+pub mod version;
@@ -0,0 +1,216 @@
+use anyhow::Result;
+use clap::Args;
+use std::io::BufRead;
+
+use crate::core::{ReviewEngine, ReviewViolation, Severity};
+use crate::git::GitIntegration;
+
+/// All-zero object id git uses for created and deleted refs
+const ZERO_SHA: &str = "0000000000000000000000000000000000000000";
+
+#[derive(Args)]
+pub struct PreReceiveArgs {
+    /// Print a pre-receive hook script instead of checking pushed refs
+    #[arg(long)]
+    pub template: bool,
+
+    /// Reject the push when violations at or above this severity are introduced
+    #[arg(long, value_name = "LEVEL", default_value = "critical")]
+    pub fail_on: Severity,
+
+    /// Report only issues of specified severity and above
+    #[arg(long, value_name = "LEVEL")]
+    pub severity: Option<Severity>,
+}
+
+/// One `<old-sha> <new-sha> <refname>` line from the pre-receive hook's stdin
+#[derive(Debug, PartialEq)]
+struct RefUpdate {
+    old_sha: String,
+    new_sha: String,
+    refname: String,
+}
+
+impl RefUpdate {
+    fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+        let update = Self {
+            old_sha: parts.next()?.to_string(),
+            new_sha: parts.next()?.to_string(),
+            refname: parts.next()?.to_string(),
+        };
+        parts.next().is_none().then_some(update)
+    }
+
+    fn is_deletion(&self) -> bool {
+        self.new_sha == ZERO_SHA
+    }
+
+    fn is_creation(&self) -> bool {
+        self.old_sha == ZERO_SHA
+    }
+
+    /// Commit to diff the pushed tip against: the old tip, or for a new branch its merge
+    /// base with the default branch
+    fn diff_base(&self, git: &GitIntegration) -> Option<String> {
+        match self.is_creation() {
+            true => git.default_branch_merge_base(&self.new_sha),
+            false => Some(self.old_sha.clone()),
+        }
+    }
+}
+
+pub async fn run(args: PreReceiveArgs) -> Result<()> {
+    if args.template {
+        print!("{}", hook_template(args.fail_on));
+        return Ok(());
+    }
+
+    let git = GitIntegration::from_env()?;
+    let review_engine = ReviewEngine::new();
+    let mut blocking = 0;
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        let Some(update) = RefUpdate::parse(&line) else {
+            continue;
+        };
+        // Only branch pushes carry reviewable changes
+        if update.is_deletion() || !update.refname.starts_with("refs/heads/") {
+            continue;
+        }
+
+        let git_diff = git.diff_between(update.diff_base(&git).as_deref(), &update.new_sha)?;
+        let review_result = review_engine.review_git_diff(&git_diff)?;
+        let violations: Vec<&ReviewViolation> = match args.severity {
+            Some(min_severity) => {
+                review_engine.filter_violations_by_severity(&review_result.violations, min_severity)
+            }
+            None => review_result.violations.iter().collect(),
+        };
+
+        report_ref(&update, &violations);
+        blocking += violations.iter().filter(|v| v.severity.is_at_least(args.fail_on)).count();
+    }
+
+    if blocking > 0 {
+        eprintln!(
+            "❌ patingin: push rejected, {blocking} violation(s) at or above {} severity",
+            args.fail_on
+        );
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Output goes to the pusher's terminal prefixed with `remote:`, so keep it plain
+fn report_ref(update: &RefUpdate, violations: &[&ReviewViolation]) {
+    let branch = update.refname.trim_start_matches("refs/heads/");
+    if violations.is_empty() {
+        println!("✅ patingin: {branch} has no anti-pattern violations");
+        return;
+    }
+
+    println!("🔍 patingin: {} violation(s) in {branch}", violations.len());
+    for violation in violations {
+        println!(
+            "  [{}] {}:{} {} ({})",
+            violation.severity,
+            violation.file_path,
+            violation.line_number,
+            violation.rule.name,
+            violation.rule.id
+        );
+    }
+}
+
+fn hook_template(fail_on: Severity) -> String {
+    format!(
+        r#"#!/bin/sh
+# patingin pre-receive hook
+# Rejects pushes that introduce anti-patterns at or above {fail_on} severity.
+# Diffs are read from the object database, so this works in bare repositories.
+#
+# Install: patingin pre-receive --template > hooks/pre-receive && chmod +x hooks/pre-receive
+
+if ! command -v patingin >/dev/null 2>&1; then
+    echo "patingin not found on the server; skipping anti-pattern checks" >&2
+    cat >/dev/null
+    exit 0
+fi
+
+exec patingin pre-receive --fail-on {fail_on}
+"#
+    )
+}
+
+#[cfg(test)]
+mod pre_receive_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ref_update() {
+        let update = RefUpdate::parse(&format!("{ZERO_SHA} abc123 refs/heads/feature")).unwrap();
+        assert!(update.is_creation());
+        assert!(!update.is_deletion());
+        assert_eq!(update.refname, "refs/heads/feature");
+
+        let deletion = RefUpdate::parse(&format!("abc123 {ZERO_SHA} refs/heads/old")).unwrap();
+        assert!(deletion.is_deletion());
+
+        assert!(RefUpdate::parse("").is_none());
+        assert!(RefUpdate::parse("abc def").is_none());
+        assert!(RefUpdate::parse("a b c d").is_none());
+    }
+
+    #[test]
+    fn test_new_branch_is_diffed_against_the_default_branch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git")
+                .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?}");
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        };
+        git(&["init", "-q", "-b", "main"]);
+        std::fs::write(dir.join("app.js"), "console.log(1)\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "initial"]);
+        let main_sha = git(&["rev-parse", "HEAD"]);
+        let repo = GitIntegration::new(dir).unwrap();
+        let creation = |sha: &str| RefUpdate {
+            old_sha: ZERO_SHA.to_string(),
+            new_sha: sha.to_string(),
+            refname: "refs/heads/feature".to_string(),
+        };
+
+        // e.g. `git push origin main:refs/heads/feature` adds no changes
+        let update = creation(&main_sha);
+        assert_eq!(update.diff_base(&repo), Some(main_sha.clone()));
+        let diff = repo.diff_between(update.diff_base(&repo).as_deref(), &main_sha).unwrap();
+        assert!(diff.files.is_empty());
+
+        git(&["checkout", "-qb", "feature"]);
+        std::fs::write(dir.join("new.js"), "eval(input)\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "feature"]);
+        let feature_sha = git(&["rev-parse", "HEAD"]);
+        let update = creation(&feature_sha);
+        let diff = repo.diff_between(update.diff_base(&repo).as_deref(), &feature_sha).unwrap();
+        let paths: Vec<&str> = diff.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["new.js"]);
+    }
+
+    #[test]
+    fn test_hook_template() {
+        let template = hook_template(Severity::Major);
+        assert!(template.starts_with("#!/bin/sh"));
+        assert!(template.contains("exec patingin pre-receive --fail-on major"));
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::PathBuf;
+
+use super::review::{self, ReviewArgs};
+use crate::core::{Language, Severity};
+
+#[derive(Args)]
+pub struct ReportArgs {
+    /// Write a standalone HTML report to FILE
+    #[arg(long, value_name = "FILE")]
+    pub html: PathBuf,
+
+    /// Report on staged changes
+    #[arg(long, conflicts_with_all = ["since", "all"])]
+    pub staged: bool,
+
+    /// Report on changes since a commit, branch or tag
+    #[arg(long, value_name = "REF", conflicts_with = "all")]
+    pub since: Option<String>,
+
+    /// Report on every file in the project instead of a diff
+    #[arg(long)]
+    pub all: bool,
+
+    /// Minimum severity to include
+    #[arg(long, value_name = "LEVEL")]
+    pub severity: Option<Severity>,
+
+    /// Include only files of this language
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<Language>,
+}
+
+impl ReportArgs {
+    /// The review this report runs: `review --format html --output FILE` with the same scope
+    fn review_args(self) -> ReviewArgs {
+        ReviewArgs {
+            staged: self.staged,
+            since: self.since,
+            all: self.all,
+            severity: self.severity,
+            language: self.language,
+            format: Some("html".to_string()),
+            output: Some(self.html),
+            ..Default::default()
+        }
+    }
+}
+
+/// Render the violations of a review into a standalone HTML page
+pub async fn run(args: ReportArgs) -> Result<()> {
+    let path = args.html.clone();
+    review::run(args.review_args()).await?;
+    println!("📄 HTML report written to {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+
+    #[test]
+    fn test_report_is_an_html_review() {
+        let args = ReportArgs {
+            html: PathBuf::from("out.html"),
+            staged: false,
+            since: Some("main".to_string()),
+            all: false,
+            severity: Some(Severity::Major),
+            language: None,
+        };
+
+        let review_args = args.review_args();
+        assert_eq!(review_args.format.as_deref(), Some("html"));
+        assert_eq!(review_args.output, Some(PathBuf::from("out.html")));
+        assert_eq!(review_args.since.as_deref(), Some("main"));
+        assert_eq!(review_args.severity, Some(Severity::Major));
+    }
+}
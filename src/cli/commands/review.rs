@@ -1,12 +1,54 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args;
 use colored::*;
 
-use crate::core::{Language, ProjectDetector, ReviewEngine, Severity};
-use crate::external::fix_engine::{BatchFixRequest, FixEngine};
-use crate::git::{DiffScope, GitDiffParser};
-
-#[derive(Args)]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use crate::config::deprecations::DeprecationPolicy;
+use crate::config::presets::{find_preset, PresetScope, ReviewPreset};
+use crate::config::resolve::{ConfigSource, ResolvedConfig};
+use crate::config::{Config, ReviewDefaults};
+use crate::core::audit::{AuditEntry, AuditLog};
+use crate::core::autofix::{apply_fixes, plan_fixes};
+use crate::core::baseline::{AcknowledgedViolation, Baseline};
+use crate::core::branch_policy::{current_branch, BranchPolicies, BranchPolicy};
+use crate::core::budgets::Budgets;
+use crate::core::coverage::FileSkipReason;
+use crate::core::fix_backup::FixBackup;
+use crate::core::gates::TagGates;
+use crate::core::grouping::GroupBy;
+use crate::core::progress::ProgressTracker;
+use crate::core::result_cache::ResultCache;
+use crate::core::review_engine::{ReviewResult, ReviewSummary, SkipReason};
+use crate::core::{Language, ProjectDetector, ReviewEngine, ReviewViolation, Severity};
+use crate::external::commit_status::{set_commit_status, CommitStatus, StatusProvider};
+use crate::external::fix_engine::{BatchFixRequest, FixEngine, DEFAULT_PARALLELISM};
+use crate::external::fix_sessions::{run_sessions, FixSessions};
+use crate::external::github::{render_summary_comment, CommentAction, CommentMode};
+use crate::external::{
+    BitbucketIntegration, FixProviderConfig, GitHubIntegration, PromptTemplates,
+};
+use crate::git::blame::{parse_age, BlameCache, LineAgeFilter};
+use crate::git::fix_commit::FixSnapshot;
+use crate::git::ownership::CodeOwners;
+use crate::git::readiness::NotReady;
+use crate::git::tree::scan_tree;
+use crate::git::{
+    CommitInfo, DiffScope, FileDiff, GitDiff, GitDiffParser, GitIntegration, ParseWarning, Vcs,
+};
+use crate::report::accessibility::{Accessibility, OutputConfig};
+use crate::report::json::{JsonSummary, JsonViolation};
+use crate::report::markdown::{escape_markdown_cell, render_markdown};
+use crate::report::mentions::MentionsConfig;
+use crate::report::{
+    report, severity_breakdown, FileProgress, OmittedRule, ReportContext, Reporter,
+    ReporterRegistry, SampleSummary, Truncation,
+};
+
+#[derive(Args, Default)]
 pub struct ReviewArgs {
     /// Analyze staged changes (pre-commit check)
     #[arg(long)]
@@ -20,96 +62,1641 @@ pub struct ReviewArgs {
     #[arg(long, value_name = "REF")]
     pub since: Option<String>,
 
+    /// Changes since the merge base of HEAD and this branch, leaving out upstream changes
+    /// made after the branch was created (e.g. `origin/main`)
+    #[arg(
+        long,
+        value_name = "REF",
+        conflicts_with_all = [
+            "staged", "uncommitted", "since", "all", "per_commit", "commit", "range", "file",
+            "stdin", "bitbucket_pr"
+        ]
+    )]
+    pub since_merge_base: Option<String>,
+
+    /// Analyze every file in the project not excluded by .gitignore, instead of a diff
+    #[arg(long)]
+    pub all: bool,
+
+    /// Review each commit in a range separately (e.g. main..feature)
+    #[arg(
+        long,
+        value_name = "BASE..HEAD",
+        conflicts_with_all = ["staged", "uncommitted", "since", "all", "fix_mode"]
+    )]
+    pub per_commit: Option<String>,
+
+    /// Review the changes one commit introduced, like `git show` (merges against their first
+    /// parent)
+    #[arg(
+        long,
+        value_name = "REV",
+        conflicts_with_all = ["staged", "uncommitted", "since", "all", "per_commit", "fix_mode"]
+    )]
+    pub commit: Option<String>,
+
+    /// Review the changes between two commits, ignoring the working tree, e.g. `v1.2..v1.3`
+    /// (`A...B` starts from their merge base)
+    #[arg(
+        long,
+        value_name = "A..B",
+        value_parser = parse_commit_range,
+        conflicts_with_all = ["staged", "uncommitted", "since", "all", "per_commit", "commit", "fix_mode"]
+    )]
+    pub range: Option<CommitRange>,
+
+    /// Review the whole contents of these files instead of a diff, whatever their git state
+    /// (for editor plugins and spot checks, also outside a repository)
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 1..,
+        conflicts_with_all = ["staged", "uncommitted", "since", "all", "per_commit", "commit", "range"]
+    )]
+    pub file: Vec<String>,
+
+    /// Review a unified diff read from standard input instead of running git, e.g.
+    /// `git diff | patingin review --stdin`
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "staged", "uncommitted", "since", "all", "per_commit", "commit", "range", "file",
+            "blame", "ignore_older_than"
+        ]
+    )]
+    pub stdin: bool,
+
+    /// Only review these lines of a single --file, e.g. `120-180` or `42`
+    #[arg(long, value_name = "START-END", requires = "file", value_parser = parse_line_range)]
+    pub lines: Option<RangeInclusive<usize>>,
+
+    /// Fetch missing history (e.g. on shallow CI clones) when a reference cannot be resolved
+    #[arg(long)]
+    pub auto_fetch: bool,
+
+    /// Apply a named preset (pre-commit, pr, nightly, or one defined in patingin.yml)
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+
     /// Show only issues of specified severity and above
     #[arg(long, value_name = "LEVEL")]
     pub severity: Option<Severity>,
 
+    /// Skip findings whose confidence score (0 to 1) is below this, e.g. 0.5 to drop
+    /// matches inside comments and string literals
+    #[arg(long, value_name = "SCORE", value_parser = parse_confidence)]
+    pub min_confidence: Option<f64>,
+
     /// Check only specific language files
     #[arg(long, value_name = "LANG")]
     pub language: Option<Language>,
 
+    /// Skip files matching this glob, e.g. `vendor/**` (repeatable; adds to ignore_paths)
+    #[arg(long, value_name = "GLOB")]
+    pub ignore: Vec<String>,
+
+    /// Review only files matching this glob, e.g. `lib/**` (repeatable; replaces
+    /// include_paths)
+    #[arg(long, value_name = "GLOB")]
+    pub only: Vec<String>,
+
     /// Output results in JSON format
     #[arg(long)]
     pub json: bool,
 
+    /// Stream one JSON object per violation as it is found, followed by a summary record
+    #[arg(long, conflicts_with = "json")]
+    pub ndjson: bool,
+
+    /// Emit results as each file is analyzed, with a `file` progress record per file; needs
+    /// a streaming format such as `--format jsonl`
+    #[arg(long, conflicts_with = "per_commit")]
+    pub stream: bool,
+
+    /// Report at most N findings per rule (e.g. `--sample 3-per-rule`), counting the rest
+    #[arg(long, value_name = "N-per-rule", value_parser = parse_sample_limit)]
+    pub sample: Option<usize>,
+
+    /// Output format: human, json, ndjson (or jsonl), markdown, sarif or a registered custom
+    /// reporter (--json and --ndjson are shorthands)
+    #[arg(long, value_name = "FORMAT", conflicts_with_all = ["json", "ndjson"])]
+    pub format: Option<String>,
+
+    /// Arrange the human report by file (default), by rule or by CODEOWNERS owner; repeats
+    /// of a rule in a file are listed together either way
+    #[arg(long, value_enum, value_name = "BY", default_value_t = GroupBy::File)]
+    pub group_by: GroupBy,
+
+    /// Write the report to FILE instead of stdout, e.g. `--format html --output report.html`
+    #[arg(long, value_name = "FILE", conflicts_with = "per_commit")]
+    pub output: Option<PathBuf>,
+
+    /// Exit with status 1 when violations at or above this severity are found
+    #[arg(long, value_name = "LEVEL")]
+    pub fail_on: Option<Severity>,
+
+    /// Tolerate up to N violations failing --fail-on or a tag gate (every reported
+    /// violation, when neither is set) before exiting with status 1
+    #[arg(long, value_name = "N")]
+    pub max_violations: Option<usize>,
+
+    /// Explain how tag gates from patingin.yml resolve for each reported rule, and list
+    /// diff sections that could not be reviewed
+    #[arg(long)]
+    pub verbose: bool,
+
+    /// Post results to this GitHub pull request (needs GITHUB_TOKEN)
+    #[arg(long, value_name = "NUMBER")]
+    pub github_pr: Option<u64>,
+
+    /// Repository to post to (defaults to GITHUB_REPOSITORY)
+    #[arg(long, value_name = "OWNER/REPO", requires = "github_pr")]
+    pub github_repo: Option<String>,
+
+    /// How to post to GitHub: inline review comments or one sticky summary comment
+    #[arg(long, value_name = "MODE", requires = "github_pr")]
+    pub comment_mode: Option<CommentMode>,
+
+    /// Review this Bitbucket Cloud pull request's diff and comment on its violating lines
+    /// (needs BITBUCKET_TOKEN, or BITBUCKET_USERNAME and BITBUCKET_APP_PASSWORD)
+    #[arg(
+        long,
+        value_name = "ID",
+        conflicts_with_all = [
+            "staged", "uncommitted", "since", "all", "per_commit", "commit", "range", "file",
+            "stdin", "github_pr", "blame", "ignore_older_than"
+        ]
+    )]
+    pub bitbucket_pr: Option<u64>,
+
+    /// Bitbucket repository of the pull request (defaults to the `origin` remote)
+    #[arg(long, value_name = "WORKSPACE/REPO", requires = "bitbucket_pr")]
+    pub bitbucket_repo: Option<String>,
+
+    /// Publish a pass/fail/neutral `patingin` check on the HEAD commit (GitHub check run
+    /// with GITHUB_TOKEN, or GitLab commit status with GITLAB_TOKEN inside GitLab CI)
+    #[arg(long)]
+    pub set_commit_status: bool,
+
+    /// Link the commit status to this URL instead of the current CI job
+    #[arg(long, value_name = "URL", requires = "set_commit_status")]
+    pub status_details_url: Option<String>,
+
+    /// Skip violations on lines whose last non-whitespace change is older than this
+    /// (e.g. 90d, 6m, 1y), so re-indenting old code does not resurface its findings
+    #[arg(long, value_name = "AGE", value_parser = parse_age)]
+    pub ignore_older_than: Option<std::time::Duration>,
+
+    /// Show who last changed each violating line, and in which commit (from git blame)
+    #[arg(long)]
+    pub blame: bool,
+
+    /// Also review lines whose change only re-indents, re-spaces or re-wraps existing code
+    #[arg(long)]
+    pub include_format_only: bool,
+
+    /// List findings that matched a rule but were not reported, with the reason for each
+    #[arg(long)]
+    pub show_skipped: bool,
+
+    /// Do not compare with, or record into, the previous run's state in .patingin/state.json
+    #[arg(long)]
+    pub no_state: bool,
+
+    /// Analyze every file again instead of reusing results cached in .patingin/cache
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Drop violations that are fixed from .patingin/baseline.yml (new ones are not added)
+    #[arg(long, requires = "all")]
+    pub update_baseline: bool,
+
     /// Disable colored output
     #[arg(long)]
     pub no_color: bool,
 
     /// Show fix suggestions (display only)
-    #[arg(long)]
+    #[arg(long, group = "fix_mode")]
     pub suggest: bool,
 
     /// Launch interactive Claude Code session to fix violations
-    #[arg(long)]
+    #[arg(long, group = "fix_mode")]
     pub fix: bool,
 
+    /// With --fix, open a separate Claude Code session for each file, asking before each
+    #[arg(
+        long,
+        requires = "fix",
+        conflicts_with_all = ["no_confirm", "auto_fix", "fix_one_by_one"]
+    )]
+    pub fix_per_file: bool,
+
+    /// With --fix, open a separate Claude Code session for each violation, asking before each
+    #[arg(long, requires = "fix", conflicts_with_all = ["no_confirm", "auto_fix"])]
+    pub fix_one_by_one: bool,
+
     /// Apply Claude Code fixes automatically (DEPRECATED: use --fix)
-    #[arg(long)]
+    #[arg(long, group = "fix_mode")]
     pub auto_fix: bool,
 
-    /// Skip confirmation when applying fixes (use with --auto-fix)
-    #[arg(long)]
+    /// Apply fixes in one batch without asking (with --fix, or --auto-fix)
+    #[arg(long, requires = "fix_mode", conflicts_with_all = ["suggest", "fix_simple"])]
     pub no_confirm: bool,
+
+    /// Generate batch fixes with this provider: `claude` or one configured under
+    /// fix.providers
+    #[arg(
+        long,
+        value_name = "NAME",
+        requires = "fix_mode",
+        conflicts_with_all = ["suggest", "fix_simple"]
+    )]
+    pub fix_provider: Option<String>,
+
+    /// Rewrite violating lines in the working tree with their rules' `auto_fix` templates,
+    /// without an AI provider
+    #[arg(long, group = "fix_mode")]
+    pub fix_simple: bool,
+
+    /// Commit the lines fixes change, with the rule ids fixed in the message, leaving your
+    /// own edits staged or unstaged as they were
+    #[arg(long, requires = "fix_mode", conflicts_with = "suggest")]
+    pub commit_fixes: bool,
+}
+
+/// What `review` does with violations once they are reported. The modes are mutually
+/// exclusive, and every one of them works on the violations left by the severity, language
+/// and age filters: those whose rule is marked fixable, or for `--fix-simple` those whose
+/// rule has a rewrite template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixMode {
+    /// `--suggest`: print each fix suggestion
+    Suggest,
+    /// `--fix`: open interactive Claude Code sessions, one for all violations unless
+    /// `--fix-per-file` or `--fix-one-by-one` split them
+    Interactive { sessions: FixSessions },
+    /// `--fix --no-confirm` or `--auto-fix`: apply fixes in one batch, asking first
+    /// unless `confirm` is off
+    Batch { confirm: bool },
+    /// `--fix-simple`: apply rules' `auto_fix` templates to the working tree
+    Simple,
+}
+
+impl FixMode {
+    fn from_args(args: &ReviewArgs) -> Option<Self> {
+        if args.suggest {
+            Some(FixMode::Suggest)
+        } else if args.fix_simple {
+            Some(FixMode::Simple)
+        } else if args.fix && !args.no_confirm {
+            let sessions = if args.fix_one_by_one {
+                FixSessions::PerViolation
+            } else if args.fix_per_file {
+                FixSessions::PerFile
+            } else {
+                FixSessions::Single
+            };
+            Some(FixMode::Interactive { sessions })
+        } else if args.fix || args.auto_fix {
+            Some(FixMode::Batch { confirm: !args.no_confirm })
+        } else {
+            None
+        }
+    }
+
+    /// Whether the report should mark which violations can be fixed
+    fn shows_auto_fixable(mode: Option<Self>) -> bool {
+        matches!(mode, Some(FixMode::Suggest | FixMode::Batch { .. }))
+    }
+
+    /// Violations this mode acts on, out of those already filtered for the report
+    fn candidates(
+        self,
+        violations: &[crate::core::ReviewViolation],
+    ) -> Vec<crate::core::ReviewViolation> {
+        violations
+            .iter()
+            .filter(|v| match self {
+                FixMode::Simple => v.rule.auto_fix.is_some(),
+                _ => v.auto_fixable,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The flag selecting the mode, for commit messages and backups
+    fn flag(self) -> &'static str {
+        match self {
+            FixMode::Suggest => "--suggest",
+            FixMode::Simple => "--fix-simple",
+            _ => "--fix",
+        }
+    }
+
+    async fn run(
+        self,
+        violations: &[crate::core::ReviewViolation],
+        provider: Option<&str>,
+    ) -> Result<()> {
+        let candidates = self.candidates(violations);
+        match self {
+            FixMode::Suggest => {
+                show_fix_suggestions(&candidates);
+                Ok(())
+            }
+            FixMode::Interactive { sessions } => handle_interactive_fix(&candidates, sessions),
+            FixMode::Batch { confirm } => handle_auto_fix(&candidates, !confirm, provider).await,
+            FixMode::Simple => handle_simple_fix(&candidates, violations.len()),
+        }
+    }
+}
+
+/// `--commit-fixes`: the files a fix mode may change, as they were before it ran, so its
+/// changes can be committed apart from the developer's
+struct FixCommitter {
+    mode: FixMode,
+    candidates: Vec<ReviewViolation>,
+    git: GitIntegration,
+    snapshot: FixSnapshot,
+}
+
+impl FixCommitter {
+    fn start(mode: FixMode, violations: &[ReviewViolation]) -> Result<Self> {
+        let candidates = mode.candidates(violations);
+        let git = GitIntegration::new(".")?;
+        let snapshot = git.snapshot_files(candidates.iter().map(|v| v.file_path.as_str()))?;
+        Ok(Self { mode, candidates, git, snapshot })
+    }
+
+    fn finish(self) -> Result<()> {
+        let root = self.git.workdir().map(Path::to_path_buf).unwrap_or_default();
+        let outcome = self.git.commit_fixes(&self.snapshot, |files| {
+            let fixed: Vec<&ReviewViolation> = self
+                .candidates
+                .iter()
+                .filter(|v| files.contains(&v.file_path) && line_changed(v, &root))
+                .collect();
+            fix_commit_message(self.mode, &fixed, files.len())
+        })?;
+
+        for (path, reason) in &outcome.skipped {
+            eprintln!("⚠️  Left the fixes to {path} uncommitted: {reason}");
+        }
+        match outcome.sha {
+            Some(sha) => println!(
+                "📦 Committed the fixes to {} file(s) as {:.7}; your own changes are untouched",
+                outcome.files.len(),
+                sha
+            ),
+            None if outcome.skipped.is_empty() => println!("💡 No fixes to commit"),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+/// Whether the violation's line no longer reads as it did in the review
+fn line_changed(violation: &ReviewViolation, root: &Path) -> bool {
+    let Ok(content) = std::fs::read_to_string(root.join(&violation.file_path)) else {
+        return true;
+    };
+    let line = violation.line_number.checked_sub(1).and_then(|index| content.lines().nth(index));
+    line.map(|line| line.trim_end_matches('\r')) != Some(violation.content.trim_end_matches('\r'))
+}
+
+/// Subject, the fix mode, a count per rule fixed and a `Patingin-Rules:` trailer listing
+/// the rule ids, which `git log --grep` and `git interpret-trailers` can pick out
+fn fix_commit_message(mode: FixMode, fixed: &[&ReviewViolation], files: usize) -> String {
+    let mut per_rule: BTreeMap<&str, usize> = BTreeMap::new();
+    for violation in fixed {
+        *per_rule.entry(&violation.rule.id).or_default() += 1;
+    }
+    let mut message = format!(
+        "Fix {} patingin violation(s) in {files} file(s)\n\nApplied with `patingin review {}`.\n",
+        fixed.len(),
+        mode.flag()
+    );
+    if !per_rule.is_empty() {
+        message.push_str("\nRules fixed:\n");
+        for (rule, count) in &per_rule {
+            message.push_str(&format!("- {rule}: {count}\n"));
+        }
+        let rules: Vec<&str> = per_rule.keys().copied().collect();
+        message.push_str(&format!("\nPatingin-Rules: {}\n", rules.join(", ")));
+    }
+    message
+}
+
+/// Flags on the command line that may be deprecated
+fn used_flags(args: &ReviewArgs) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+    if args.auto_fix {
+        flags.push("--auto-fix");
+    }
+    flags
 }
 
 pub async fn run(args: ReviewArgs) -> Result<()> {
+    run_with_reporters(args, &ReporterRegistry::new()).await
+}
+
+/// Run a review with `--format` resolved against `reporters`, so binaries embedding patingin
+/// can offer their own output formats next to the built-in ones.
+pub async fn run_with_reporters(mut args: ReviewArgs, reporters: &ReporterRegistry) -> Result<()> {
+    // Precedence: command line, `PATINGIN_*` variables, the preset, the branch's policy,
+    // patingin.yml, the global config.yml, then `patingin.*` git config
+    let resolved = ResolvedConfig::load_current()?;
+    let mut project_config = resolved.config();
+    project_config.apply_path_flags(&args.ignore, &args.only)?;
+    if args.preset.is_none() {
+        args.preset = project_config.review.preset.clone();
+    }
+    // Scopes only apply to diff reviews
+    let diff_review = args.file.is_empty() && args.per_commit.is_none();
+    if let Some(env) = resolved.layer(ConfigSource::Env) {
+        apply_review_defaults(&mut args, &env.config.review, "PATINGIN_SCOPE", diff_review)?;
+    }
+
+    // Presets fill in whatever the command line left unset
+    if let Some(preset_name) = args.preset.clone() {
+        let project_root = ProjectDetector::detect_project(None).ok().map(|info| info.root_path);
+        let preset = find_preset(&preset_name, project_root.as_deref())?;
+        apply_preset(&mut args, &preset)?;
+    }
+    // The branch's gating applies to every kind of review
+    let branch_policy = select_branch_policy()?;
+    if let Some(policy) = &branch_policy {
+        args.fail_on = args.fail_on.or(policy.fail_on);
+        args.max_violations = args.max_violations.or(policy.max_violations);
+    }
+    let file_defaults = resolved.below(ConfigSource::Env).review;
+    apply_review_defaults(&mut args, &file_defaults, "patingin.yml", diff_review)?;
+    // A flag can only turn color off, so the merged value decides between the sources
+    args.no_color |= project_config.review.no_color == Some(true);
+
+    if args.no_color {
+        colored::control::set_override(false);
+    }
+
+    // Deprecated flags warn on stderr, or fail outright under `allow_deprecated: false`
+    let deprecations =
+        DeprecationPolicy::load_for_project()?.check("review", &used_flags(&args))?;
+    for deprecation in &deprecations {
+        eprintln!("⚠️  WARNING: {}", deprecation.message());
+    }
+
+    let output_format = determine_output_format(&args);
+    if !reporters.contains(&output_format) {
+        let available: Vec<String> = reporters
+            .list()
+            .map(|(name, description)| format!("  {name:<10} {description}"))
+            .collect();
+        return Err(anyhow::anyhow!(
+            "Unknown output format '{output_format}'. Available formats:\n{}",
+            available.join("\n")
+        ));
+    }
+
+    if let Some(range) = args.per_commit.clone() {
+        return review_per_commit(
+            &args,
+            &project_config,
+            branch_policy.as_ref(),
+            &range,
+            &output_format,
+        );
+    }
+
+    let out: Box<dyn std::io::Write> = match &args.output {
+        Some(path) => Box::new(std::io::BufWriter::new(
+            std::fs::File::create(path)
+                .with_context(|| format!("Failed to create {}", path.display()))?,
+        )),
+        None => Box::new(std::io::stdout()),
+    };
+    let mut reporter = reporters.create(&output_format, out)?;
+
     // Determine diff scope based on arguments
-    let diff_scope = determine_diff_scope(&args);
+    let history = history_scope(&args)?;
+    let diff_scope = match &history {
+        Some((scope, _)) => scope.clone(),
+        None => determine_diff_scope(&args),
+    };
+    let git_diff = if args.stdin {
+        let mut patch = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut patch)
+            .context("Failed to read a diff from stdin")?;
+        GitDiffParser::parse_patch(&patch)?
+    } else if let Some(pr_id) = args.bitbucket_pr {
+        let repo = BitbucketIntegration::resolve_repository(args.bitbucket_repo.as_deref())?;
+        let patch = BitbucketIntegration::new().pull_request_diff(&repo, pr_id).await?;
+        GitDiffParser::parse_patch(&patch)?
+    } else if !args.file.is_empty() {
+        files_diff(&args.file, args.lines.clone())?
+    } else if args.all {
+        scan_project_tree()?
+    } else {
+        ensure_scope_references(&diff_scope, args.auto_fetch)?;
 
-    // Execute git diff to get changed lines
-    let diff_output = GitDiffParser::execute_git_diff(&diff_scope)?;
+        // Execute git diff to get changed lines
+        let diff_output = GitDiffParser::execute_git_diff(&diff_scope)?;
 
-    // Parse the git diff
-    let git_diff = GitDiffParser::parse(&diff_output)?;
+        // Parse the git diff
+        GitDiffParser::parse(&diff_output)?
+    };
 
-    // Filter files by language if specified
-    let filtered_diff = if let Some(target_language) = &args.language {
+    // Filter files by language if specified, keeping the ones left out for the report
+    let diff_paths: Vec<String> = git_diff.files.iter().map(|file| file.path.clone()).collect();
+    let mut filtered_diff = if let Some(target_language) = &args.language {
         filter_diff_by_language(git_diff, target_language)
     } else {
         git_diff
     };
+    let kept: HashSet<&str> = filtered_diff.files.iter().map(|file| file.path.as_str()).collect();
+    let mut filtered_out: Vec<(String, FileSkipReason)> = diff_paths
+        .into_iter()
+        .filter(|path| !kept.contains(path.as_str()))
+        .map(|path| (path, FileSkipReason::LanguageFilter))
+        .collect();
+    filtered_out.extend(project_config.remove_ignored(&mut filtered_diff));
+    if !args.include_format_only {
+        skip_format_only(&mut filtered_diff);
+    }
+
+    // Review the changes with custom rules if project detected
+    let review_engine = with_result_cache(
+        apply_branch_policy(build_review_engine()?, branch_policy.as_ref()),
+        &args,
+    )?;
+    if args.verbose {
+        report_rule_conflicts(&review_engine);
+    }
+
+    let fix_mode = FixMode::from_args(&args);
+    if args.fix_provider.is_some() && matches!(fix_mode, Some(FixMode::Interactive { .. })) {
+        return Err(anyhow::anyhow!(
+            "--fix-provider applies to batch fixes; add --no-confirm (interactive --fix \
+             sessions run Claude Code)"
+        ));
+    }
+    let output_config = OutputConfig::load_for_project()?;
+    let mut context = ReportContext {
+        scope: match (args.file.as_slice(), history) {
+            _ if args.stdin => "diff from stdin".to_string(),
+            _ if args.bitbucket_pr.is_some() => {
+                format!("Bitbucket pull request #{}", args.bitbucket_pr.unwrap_or_default())
+            }
+            ([path], _) => describe_region(path, args.lines.as_ref()),
+            ([_, _, ..], _) => format!("{} files", args.file.len()),
+            ([], Some((_, description))) => description,
+            ([], None) => describe_scope(&diff_scope),
+        },
+        show_auto_fixable: FixMode::shows_auto_fixable(fix_mode),
+        fix_requested: fix_mode.is_some(),
+        sampling: None,
+        truncation: None,
+        deprecations: deprecations.into_iter().copied().collect(),
+        accessibility: output_config.accessibility,
+        partial_hunks: review_engine.partial_hunks(&filtered_diff),
+        fixed_since_last_run: None,
+        mentions: MentionsConfig::load_for_project()?,
+        parse_warnings: filtered_diff.parse_warnings.clone(),
+        skipped: Vec::new(),
+        files: Vec::new(),
+        acknowledged: Vec::new(),
+        rule_sources: review_engine.rule_sources().clone(),
+        verbose: args.verbose,
+        column_unit: output_config.column_unit,
+        group_by: args.group_by,
+    };
+    report_parse_warnings(&context.parse_warnings, args.verbose);
+
+    if args.stream && !reporter.is_streaming() {
+        return Err(anyhow::anyhow!(
+            "--stream needs a streaming format such as `--format jsonl`, not '{output_format}'"
+        ));
+    }
+    // Streaming reporters get results as they are found and the full report is never built
+    if reporter.is_streaming() {
+        if fix_mode.is_some() {
+            return Err(anyhow::anyhow!(
+                "--suggest, --fix, --fix-simple and --auto-fix need the full report and cannot \
+                 be combined with the streaming '{output_format}' format"
+            ));
+        }
+        if args.show_skipped || args.update_baseline || args.blame {
+            return Err(anyhow::anyhow!(
+                "--show-skipped, --update-baseline and --blame need the full report and cannot \
+                 be combined with the streaming '{output_format}' format"
+            ));
+        }
+        let mut age_filter = age_filter(&args, &diff_scope)?;
+        let mut baseline = Baseline::load(&Baseline::project_path()?)?.matcher();
+        let (summary, gated) = stream_results(
+            reporter.as_mut(),
+            &context,
+            &review_engine,
+            &filtered_diff,
+            &args,
+            |violation| {
+                args.min_confidence.is_some_and(|min| violation.confidence < min)
+                    || baseline.absorbs(violation)
+                    || age_filter.as_mut().is_some_and(|age_filter| {
+                        age_filter.is_older(&violation.file_path, violation.line_number)
+                    })
+            },
+        )?;
+        if args.verbose {
+            explain_gates(review_engine.gates(), &gated, args.fail_on);
+        }
+        let failing = failing_violations(
+            review_engine.gates(),
+            review_engine.budgets(),
+            &gated,
+            args.fail_on,
+            args.max_violations,
+        );
+        record_audit("review", &context.scope, &review_engine, summary, failing)?;
+        enforce_fail_on(&args, &review_engine, &gated);
+        return Ok(());
+    }
+
+    let mut review_result = review_engine.review_git_diff(&filtered_diff)?;
+    context.acknowledged = skip_baselined(&mut review_result, args.update_baseline)?;
+    if let Some(mut age_filter) = age_filter(&args, &diff_scope)? {
+        skip_old_lines(&mut review_result, &mut age_filter);
+    }
+    if let Some(min_confidence) = args.min_confidence {
+        skip_low_confidence(&mut review_result, min_confidence);
+    }
+    if args.blame {
+        attribute_lines(&mut review_result.violations, &diff_scope)?;
+    }
+    assign_owners(&mut review_result.violations)?;
+
+    // Filter violations by severity if specified
+    let filtered_violations = if let Some(min_severity) = args.severity {
+        review_engine
+            .filter_violations_by_severity(&review_result.violations, min_severity)
+            .into_iter()
+            .cloned()
+            .collect()
+    } else {
+        review_result.violations.clone()
+    };
+
+    // Sampling only trims what is reported; gates and fixes still see every violation
+    let sample = args.sample.map(|per_rule| sample_per_rule(&filtered_violations, per_rule));
+    let mut reported_violations =
+        sample.as_ref().map_or(&filtered_violations[..], |s| &s.shown[..]);
+    context.sampling = sample.as_ref().map(|s| s.summary.clone());
+    if let Some(limit) =
+        output_config.max_reported_violations.filter(|limit| reported_violations.len() > *limit)
+    {
+        context.truncation = Some(Truncation { limit, total: reported_violations.len() });
+        reported_violations = &reported_violations[..limit];
+    }
+    if args.show_skipped {
+        context.skipped = review_result.skipped.clone();
+    }
+    context.files =
+        review_engine.file_coverage(&filtered_diff, &filtered_out, &filtered_violations);
+    if !args.no_state {
+        context.fixed_since_last_run = record_progress(&context.scope, &review_result.violations);
+    }
+
+    report(reporter.as_mut(), &context, reported_violations, &review_result.summary)?;
+
+    if let Some(pr_number) = args.github_pr {
+        post_to_github(&args, pr_number, &filtered_violations, &context.mentions).await?;
+    }
+    if let Some(pr_id) = args.bitbucket_pr {
+        let repo = BitbucketIntegration::resolve_repository(args.bitbucket_repo.as_deref())?;
+        let tally = BitbucketIntegration::new()
+            .post_inline_comments(&repo, pr_id, &filtered_violations, &context.mentions)
+            .await?;
+        eprintln!(
+            "💬 Posted {} and updated {} comment(s) on {repo}#{pr_id}, {} already there",
+            tally.posted, tally.updated, tally.unchanged
+        );
+    }
+
+    // Fix modes see every filtered violation, not just the sampled ones
+    if let Some(fix_mode) = fix_mode {
+        let fix_commit = match args.commit_fixes {
+            true => Some(FixCommitter::start(fix_mode, &filtered_violations)?),
+            false => None,
+        };
+        let backup = match fix_mode {
+            FixMode::Suggest => None,
+            _ => Some(FixBackup::take(
+                &project_root(),
+                fix_mode.candidates(&filtered_violations).iter().map(|v| v.file_path.as_str()),
+            )),
+        };
+        fix_mode.run(&filtered_violations, args.fix_provider.as_deref()).await?;
+        if let Some(backup) = backup {
+            let command = format!("patingin review {}", fix_mode.flag());
+            if let Some(saved) = backup.save(&command)? {
+                println!(
+                    "↩️  Backed up {} file(s) before fixing; undo with `patingin fix --rollback`",
+                    saved.files().len()
+                );
+            }
+        }
+        if let Some(fix_commit) = fix_commit {
+            fix_commit.finish()?;
+        }
+    }
+
+    // Gate on severity after all output has been written
+    if args.verbose {
+        explain_gates(review_engine.gates(), &filtered_violations, args.fail_on);
+    }
+    let failing = failing_violations(
+        review_engine.gates(),
+        review_engine.budgets(),
+        &filtered_violations,
+        args.fail_on,
+        args.max_violations,
+    );
+    if args.set_commit_status {
+        let head_sha = GitIntegration::new(".").and_then(|git| git.head_sha())?;
+        publish_commit_status(
+            &filtered_violations,
+            failing,
+            &context.scope,
+            args.github_repo.as_deref(),
+            &head_sha,
+            args.status_details_url.clone(),
+        )
+        .await?;
+    }
+    record_audit(
+        "review",
+        &context.scope,
+        &review_engine,
+        review_engine.create_review_summary(&filtered_violations),
+        failing,
+    )?;
+    enforce_fail_on(&args, &review_engine, &filtered_violations);
+
+    Ok(())
+}
+
+/// Append this run to the project's audit log, when `audit.log` is configured
+pub(crate) fn record_audit(
+    command: &str,
+    scope: &str,
+    review_engine: &ReviewEngine,
+    summary: ReviewSummary,
+    failing: usize,
+) -> Result<()> {
+    let Some(audit_log) = AuditLog::for_project()? else {
+        return Ok(());
+    };
+    audit_log.append(AuditEntry {
+        command: command.to_string(),
+        scope: scope.to_string(),
+        ruleset: review_engine.ruleset_checksum()?,
+        summary,
+        failing,
+    })?;
+    Ok(())
+}
+
+/// Store this run's violations in the project's run state and return how many of the
+/// previous run's have been fixed. State problems only warn: they must not fail a review.
+fn record_progress(scope: &str, violations: &[ReviewViolation]) -> Option<usize> {
+    let recorded = ProgressTracker::for_project(scope)
+        .and_then(|tracker| tracker.map(|mut tracker| tracker.record(violations)).transpose());
+    match recorded {
+        Ok(fixed) => fixed.flatten(),
+        Err(e) => {
+            eprintln!("⚠️  Could not update run state: {e}");
+            None
+        }
+    }
+}
+
+/// Fail early with an actionable message when the scope cannot be diffed (outside a
+/// repository, before the first commit, or when a reference is missing), instead of
+/// surfacing git's stderr. An empty scope is only noted: the review still runs and reports
+/// no violations.
+pub(crate) fn ensure_scope_references(diff_scope: &DiffScope, auto_fetch: bool) -> Result<()> {
+    let Ok(current_dir) = std::env::current_dir() else { return Ok(()) };
+    if Vcs::detect(&current_dir) != Vcs::Git {
+        return Ok(());
+    }
+    let git = GitIntegration::new(&current_dir).map_err(|_| NotReady::NotARepository)?;
+
+    let references: Vec<&str> = match diff_scope {
+        DiffScope::SinceCommit(reference) => vec![reference],
+        DiffScope::Between(from, to) => vec![from, to],
+        DiffScope::Staged | DiffScope::Unstaged | DiffScope::All => Vec::new(),
+    };
+    match git.check_scope(diff_scope) {
+        Ok(()) => Ok(()),
+        Err(not_ready @ NotReady::NothingToDiff(_)) => {
+            eprintln!("💤 {not_ready}");
+            Ok(())
+        }
+        // Fetching may bring the reference in, and the error lists what is available
+        Err(NotReady::MissingReference { .. }) => {
+            for reference in references {
+                git.ensure_reference(reference, auto_fetch)?;
+            }
+            Ok(())
+        }
+        Err(not_ready) => Err(not_ready.into()),
+    }
+}
+
+async fn post_to_github(
+    args: &ReviewArgs,
+    pr_number: u64,
+    violations: &[ReviewViolation],
+    mentions: &MentionsConfig,
+) -> Result<()> {
+    let github = GitHubIntegration::new();
+    let repo = GitHubIntegration::resolve_repository(args.github_repo.as_deref())?;
+    let head_sha = GitIntegration::new(".").and_then(|git| git.head_sha()).ok();
+
+    match args.comment_mode.unwrap_or_default() {
+        CommentMode::Summary => {
+            let body = render_summary_comment(violations, head_sha.as_deref(), mentions);
+            let action = github.upsert_summary_comment(&repo, pr_number, &body).await?;
+            let verb = match action {
+                CommentAction::Created => "Posted",
+                CommentAction::Updated => "Updated",
+            };
+            eprintln!("💬 {verb} summary comment on {repo}#{pr_number}");
+        }
+        CommentMode::Inline => {
+            let head_sha = head_sha
+                .ok_or_else(|| anyhow::anyhow!("Inline comments need the HEAD commit SHA"))?;
+            let posted = github
+                .post_inline_review(&repo, pr_number, &head_sha, violations, mentions)
+                .await?;
+            eprintln!("💬 Posted {posted} inline comment(s) on {repo}#{pr_number}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Set the `patingin` check on `sha` for the provider the CI environment belongs to
+pub(crate) async fn publish_commit_status(
+    violations: &[ReviewViolation],
+    failing: usize,
+    scope: &str,
+    repo: Option<&str>,
+    sha: &str,
+    details_url: Option<String>,
+) -> Result<()> {
+    let provider = StatusProvider::detect();
+    let details_url = details_url.or_else(|| provider.default_details_url());
+    let status = CommitStatus::new(violations, failing, scope, details_url);
+    set_commit_status(provider, repo, sha, &status).await?;
+    eprintln!("🚦 Set {provider} commit status on {}: {}", &sha[..sha.len().min(7)], status.title);
+    Ok(())
+}
+
+/// Show how project rules whose ids were already taken were resolved
+fn report_rule_conflicts(review_engine: &ReviewEngine) {
+    for conflict in review_engine.rule_conflicts() {
+        eprintln!("🔀 Rule conflict: {conflict}");
+    }
+}
+
+/// List diff sections that could not be reviewed with `--verbose`, or count them otherwise
+fn report_parse_warnings(warnings: &[ParseWarning], verbose: bool) {
+    if warnings.is_empty() {
+        return;
+    }
+    if !verbose {
+        eprintln!("⚠️  {} diff section(s) could not be reviewed (see --verbose)", warnings.len());
+        return;
+    }
+    eprintln!("⚠️  {} diff section(s) could not be reviewed:", warnings.len());
+    for warning in warnings {
+        eprintln!("   {warning}");
+    }
+}
+
+/// Formatter commits would otherwise be reviewed as if every re-indented line were new
+pub(crate) fn skip_format_only(git_diff: &mut GitDiff) {
+    let skipped = git_diff.skip_format_only_changes();
+    if skipped > 0 {
+        eprintln!(
+            "🧹 Skipped {skipped} format-only line(s) (use --include-format-only to review them)"
+        );
+    }
+}
+
+/// Blame-based filter for `--ignore-older-than`, when requested
+fn age_filter(args: &ReviewArgs, diff_scope: &DiffScope) -> Result<Option<LineAgeFilter>> {
+    let Some(max_age) = args.ignore_older_than else { return Ok(None) };
+    Ok(Some(LineAgeFilter::new(GitIntegration::new(".")?, diff_scope, max_age)))
+}
+
+/// `--blame`: record who last changed each violating line in the revision the scope ends at
+fn attribute_lines(violations: &mut [ReviewViolation], diff_scope: &DiffScope) -> Result<()> {
+    let mut blame = BlameCache::new(GitIntegration::new(".")?, diff_scope);
+    for violation in violations {
+        violation.blame = blame.line(&violation.file_path, violation.line_number).cloned();
+    }
+    Ok(())
+}
+
+/// CODEOWNERS of the repository being reviewed, if it has one
+fn load_codeowners() -> Result<Option<CodeOwners>> {
+    let git = GitIntegration::new(".").ok();
+    let root = git.as_ref().and_then(|git| git.workdir()).unwrap_or(Path::new("."));
+    CodeOwners::load(root)
+}
+
+/// Record who CODEOWNERS makes responsible for each violation's file
+fn assign_owners(violations: &mut [ReviewViolation]) -> Result<()> {
+    let Some(codeowners) = load_codeowners()? else { return Ok(()) };
+    for violation in violations {
+        violation.owners = codeowners.owners_for(&violation.file_path).to_vec();
+    }
+    Ok(())
+}
+
+fn skip_old_lines(review_result: &mut ReviewResult, age_filter: &mut LineAgeFilter) {
+    let skipped = review_result
+        .skip(SkipReason::OlderThan, |v| age_filter.is_older(&v.file_path, v.line_number));
+    if skipped > 0 {
+        eprintln!("🕰️  Skipped {skipped} violation(s) on lines older than --ignore-older-than");
+    }
+}
+
+fn skip_low_confidence(review_result: &mut ReviewResult, min_confidence: f64) {
+    let skipped = review_result.skip(SkipReason::LowConfidence, |v| v.confidence < min_confidence);
+    if skipped > 0 {
+        eprintln!(
+            "🎯 Skipped {skipped} violation(s) scored below --min-confidence {min_confidence}"
+        );
+    }
+}
+
+/// Skip violations recorded in the project's baseline. With `update_baseline`, entries
+/// this review no longer finds are dropped from the baseline first.
+/// Skip the violations recorded in the baseline and return the accepted risks among them,
+/// which reports list as acknowledged
+pub(crate) fn skip_baselined(
+    review_result: &mut ReviewResult,
+    update_baseline: bool,
+) -> Result<Vec<AcknowledgedViolation>> {
+    let path = Baseline::project_path()?;
+    let mut baseline = Baseline::load(&path)?;
+    if update_baseline {
+        let fixed = baseline.prune(&review_result.violations);
+        baseline.save(&path)?;
+        eprintln!("📌 Dropped {fixed} fixed violation(s) from {}", path.display());
+    }
+    if baseline.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut matcher = baseline.matcher();
+    let mut acknowledged = Vec::new();
+    review_result.skip(SkipReason::AcceptedRisk, |violation| {
+        let risk = matcher.acknowledges(violation);
+        if let Some(risk) = &risk {
+            acknowledged
+                .push(AcknowledgedViolation { violation: violation.clone(), risk: risk.clone() });
+        }
+        risk.is_some()
+    });
+    let skipped = review_result.skip(SkipReason::Baselined, |v| matcher.absorbs(v));
+    if skipped > 0 {
+        eprintln!("📌 Skipped {skipped} baselined violation(s)");
+    }
+    Ok(acknowledged)
+}
+
+/// How many violations fail the run: those failing their tag gate, or `--fail-on` for
+/// rules without gated tags. With `--max-violations` and neither of those, every violation
+/// counts. Violations under a budget only fail beyond its allowance. None fail while they
+/// number no more than `--max-violations`.
+pub(crate) fn failing_violations(
+    gates: &TagGates,
+    budgets: &Budgets,
+    violations: &[ReviewViolation],
+    fail_on: Option<Severity>,
+    max_violations: Option<usize>,
+) -> usize {
+    let unbudgeted: Vec<ReviewViolation> =
+        violations.iter().filter(|v| !budgets.covers(v)).cloned().collect();
+    let failing = if fail_on.is_none() && gates.is_empty() && max_violations.is_some() {
+        unbudgeted.len()
+    } else {
+        gates.failing(&unbudgeted, fail_on)
+    } + budgets.overage(violations);
+    if max_violations.is_some_and(|max| failing <= max) {
+        0
+    } else {
+        failing
+    }
+}
+
+/// Print how much of each budget the violations use, on stderr
+pub(crate) fn report_budgets(budgets: &Budgets, violations: &[ReviewViolation]) {
+    for usage in budgets.usage(violations).iter().filter(|usage| usage.used > 0) {
+        let status = if usage.exceeded() { "❌ exceeded" } else { "✅ within budget" };
+        eprintln!("💰 Budget {usage} violation(s): {status}");
+    }
+}
+
+/// Exit with status 1 when violations fail the run; see `failing_violations`
+fn enforce_fail_on(
+    args: &ReviewArgs,
+    review_engine: &ReviewEngine,
+    violations: &[ReviewViolation],
+) {
+    let (gates, budgets) = (review_engine.gates(), review_engine.budgets());
+    report_budgets(budgets, violations);
+    let failing = failing_violations(gates, budgets, violations, args.fail_on, args.max_violations);
+    if failing == 0 {
+        return;
+    }
+    let budget = match args.max_violations {
+        Some(max) => format!(", more than --max-violations {max}"),
+        None => String::new(),
+    };
+    match args.fail_on {
+        _ if budgets.overage(violations) == failing => {
+            eprintln!("❌ {failing} violation(s) over their budget{budget}")
+        }
+        Some(fail_on) if gates.is_empty() => {
+            eprintln!("❌ {failing} violation(s) at or above {fail_on} severity{budget}")
+        }
+        None if gates.is_empty() => eprintln!("❌ {failing} violation(s){budget}"),
+        _ => eprintln!("❌ {failing} violation(s) failed their gate{budget} (see --verbose)"),
+    }
+    std::process::exit(1);
+}
+
+/// Print the configured gates and how each reported rule resolved against them, at the
+/// rule's most severe violation
+fn explain_gates(gates: &TagGates, violations: &[ReviewViolation], fail_on: Option<Severity>) {
+    if gates.is_empty() {
+        eprintln!("🚦 No tag gates configured");
+    } else {
+        let configured: Vec<String> =
+            gates.tags.iter().map(|(tag, gate)| format!("{tag}: {gate}")).collect();
+        eprintln!("🚦 Tag gates: {}", configured.join(", "));
+    }
+
+    let mut worst: std::collections::BTreeMap<&str, &ReviewViolation> = Default::default();
+    for violation in violations {
+        let entry = worst.entry(violation.rule.id.as_str()).or_insert(violation);
+        if violation.severity < entry.severity {
+            *entry = violation;
+        }
+    }
+    for (rule_id, violation) in worst {
+        let resolution = gates.resolve(&violation.rule, violation.severity, fail_on);
+        eprintln!(
+            "   {rule_id} [{}] at {}: {}",
+            violation.rule.tags.join(", "),
+            violation.severity,
+            resolution.explain(fail_on)
+        );
+    }
+}
+
+/// Violations introduced by a single commit of a `--per-commit` review
+struct CommitReview {
+    commit: CommitInfo,
+    violations: Vec<ReviewViolation>,
+    summary: ReviewSummary,
+}
+
+fn review_per_commit(
+    args: &ReviewArgs,
+    project_config: &Config,
+    branch_policy: Option<&BranchPolicy>,
+    range: &str,
+    output_format: &str,
+) -> Result<()> {
+    if !matches!(output_format, "human" | "json" | "markdown") {
+        return Err(anyhow::anyhow!(
+            "--per-commit supports human, json and markdown output, not '{output_format}'"
+        ));
+    }
+
+    let git = GitIntegration::new(".")?;
+    for reference in range.split("..").filter(|reference| !reference.is_empty()) {
+        git.ensure_reference(reference, args.auto_fetch)?;
+    }
+    let commits = git.commits_in_range(range)?;
+
+    let review_engine =
+        with_result_cache(apply_branch_policy(build_review_engine()?, branch_policy), args)?;
+    if args.verbose {
+        report_rule_conflicts(&review_engine);
+    }
+
+    let mut reviews = Vec::with_capacity(commits.len());
+    for commit in commits {
+        let diff_output = GitDiffParser::execute_git_diff(&commit.diff_scope())?;
+        let mut git_diff = GitDiffParser::parse(&diff_output)?;
+        if let Some(target_language) = &args.language {
+            git_diff = filter_diff_by_language(git_diff, target_language);
+        }
+        project_config.remove_ignored(&mut git_diff);
+        if !args.include_format_only {
+            skip_format_only(&mut git_diff);
+        }
+        report_parse_warnings(&git_diff.parse_warnings, args.verbose);
+
+        let mut review_result = review_engine.review_git_diff(&git_diff)?;
+        skip_baselined(&mut review_result, false)?;
+        if let Some(mut age_filter) = age_filter(args, &commit.diff_scope())? {
+            skip_old_lines(&mut review_result, &mut age_filter);
+        }
+        if let Some(min_confidence) = args.min_confidence {
+            skip_low_confidence(&mut review_result, min_confidence);
+        }
+        if args.blame {
+            attribute_lines(&mut review_result.violations, &commit.diff_scope())?;
+        }
+        assign_owners(&mut review_result.violations)?;
+        let violations: Vec<ReviewViolation> = match args.severity {
+            Some(min_severity) => review_engine
+                .filter_violations_by_severity(&review_result.violations, min_severity)
+                .into_iter()
+                .cloned()
+                .collect(),
+            None => review_result.violations,
+        };
+
+        let mut summary = ReviewSummary::default();
+        violations.iter().for_each(|v| summary.record(v));
+        reviews.push(CommitReview { commit, violations, summary });
+    }
+
+    match output_format {
+        "json" => output_per_commit_json(range, &reviews)?,
+        "markdown" => print!("{}", render_per_commit_markdown(range, &reviews)),
+        _ => output_per_commit_human(
+            range,
+            &reviews,
+            OutputConfig::load_for_project()?.accessibility,
+        ),
+    }
+
+    let all_violations: Vec<ReviewViolation> =
+        reviews.into_iter().flat_map(|review| review.violations).collect();
+    if args.verbose {
+        explain_gates(review_engine.gates(), &all_violations, args.fail_on);
+    }
+    enforce_fail_on(args, &review_engine, &all_violations);
+
+    Ok(())
+}
+
+fn aggregate_summary(reviews: &[CommitReview]) -> ReviewSummary {
+    let mut summary = ReviewSummary::default();
+    reviews.iter().flat_map(|review| &review.violations).for_each(|v| summary.record(v));
+    summary
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonCommitReview {
+    sha: String,
+    summary_line: String,
+    violations: Vec<JsonViolation>,
+    summary: JsonSummary,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonPerCommitOutput {
+    range: String,
+    commits: Vec<JsonCommitReview>,
+    summary: JsonSummary,
+}
+
+fn build_per_commit_json(range: &str, reviews: &[CommitReview]) -> JsonPerCommitOutput {
+    JsonPerCommitOutput {
+        range: range.to_string(),
+        commits: reviews
+            .iter()
+            .map(|review| JsonCommitReview {
+                sha: review.commit.sha.clone(),
+                summary_line: review.commit.summary.clone(),
+                violations: review.violations.iter().map(JsonViolation::from).collect(),
+                summary: JsonSummary::from(&review.summary),
+            })
+            .collect(),
+        summary: JsonSummary::from(&aggregate_summary(reviews)),
+    }
+}
+
+fn output_per_commit_json(range: &str, reviews: &[CommitReview]) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&build_per_commit_json(range, reviews))?);
+    Ok(())
+}
+
+fn render_per_commit_markdown(range: &str, reviews: &[CommitReview]) -> String {
+    let mut report = format!("# 🔍 patingin patch series review: `{range}`\n\n");
+    for review in reviews {
+        report.push_str(&format!(
+            "## {} {}\n\n",
+            review.commit.short_sha(),
+            escape_markdown_cell(&review.commit.summary)
+        ));
+        // Skip the per-scope header; the commit heading already identifies the changes
+        let section =
+            render_markdown(&review.violations, &describe_scope(&review.commit.diff_scope()));
+        report.push_str(section.split_once("\n\n").map_or("", |(_, body)| body));
+        report.push('\n');
+    }
+
+    let total = aggregate_summary(reviews);
+    report.push_str(&format!(
+        "---\n**Total: {} violations across {} commits** ({})\n",
+        total.total_violations,
+        reviews.len(),
+        severity_breakdown(|severity| total.count(severity), true)
+    ));
+    report
+}
+
+fn output_per_commit_human(range: &str, reviews: &[CommitReview], a: Accessibility) {
+    println!("{}Patch Series Review: {} ({} commits)", a.icon("🔍 "), range.bold(), reviews.len());
+    println!();
+
+    for review in reviews {
+        let count = review.violations.len();
+        let status = if count == 0 {
+            a.highlight(&format!("{}clean", a.icon("✅ ")), Color::Green).to_string()
+        } else {
+            a.highlight(&format!("{count} violation(s)"), Color::Yellow).to_string()
+        };
+        println!(
+            "{}{} {} — {}",
+            a.icon("📦 "),
+            a.highlight(review.commit.short_sha(), Color::Cyan),
+            review.commit.summary,
+            status
+        );
+
+        for violation in &review.violations {
+            println!(
+                "   {} {}:{} {} ({})",
+                a.severity_marker(violation.severity),
+                violation.file_path,
+                violation.line_number,
+                violation.rule.name,
+                a.muted(&violation.rule.id)
+            );
+        }
+    }
+
+    let total = aggregate_summary(reviews);
+    println!();
+    println!(
+        "{}Summary: {} violations across {} commits ({})",
+        a.icon("📊 "),
+        total.total_violations,
+        reviews.len(),
+        severity_breakdown(|severity| total.count(severity), false)
+    );
+}
+
+/// Review engine with the detected project's custom rules, formatting overrides and tag gates
+pub(crate) fn build_review_engine() -> Result<ReviewEngine> {
+    let Ok(project_info) = ProjectDetector::detect_project(None) else {
+        return Ok(ReviewEngine::new());
+    };
+
+    ReviewEngine::for_project(&project_info.name, &project_info.root_path)
+}
+
+/// `review_engine` reusing per-file results of earlier runs from `.patingin/cache`, unless
+/// `--no-cache` is given or no project is detected
+fn with_result_cache(review_engine: ReviewEngine, args: &ReviewArgs) -> Result<ReviewEngine> {
+    if args.no_cache {
+        return Ok(review_engine);
+    }
+    Ok(match ResultCache::for_project(&review_engine.cache_key()?)? {
+        Some(cache) => review_engine.with_cache(cache),
+        None => review_engine,
+    })
+}
+
+/// The `branch_policies:` entry for the branch under review, announced on stderr
+pub(crate) fn select_branch_policy() -> Result<Option<BranchPolicy>> {
+    let policies = BranchPolicies::load_for_project()?;
+    if policies.0.is_empty() {
+        return Ok(None);
+    }
+    let Some(branch) = current_branch() else {
+        eprintln!("🌿 Could not tell which branch this is; no branch policy applied");
+        return Ok(None);
+    };
+    match policies.matching(&branch) {
+        Some((glob, policy)) => {
+            eprintln!("🌿 Branch policy `{glob}` applies to {branch}: {}", policy.describe());
+            Ok(Some(policy.clone()))
+        }
+        None => {
+            eprintln!("🌿 No branch policy matches {branch}");
+            Ok(None)
+        }
+    }
+}
+
+/// `review_engine` with the branch policy's tag gates over the project's
+pub(crate) fn apply_branch_policy(
+    review_engine: ReviewEngine,
+    policy: Option<&BranchPolicy>,
+) -> ReviewEngine {
+    match policy {
+        Some(policy) if !policy.gates.is_empty() => {
+            let gates = review_engine.gates().overlay(&policy.gates);
+            review_engine.with_gates(gates)
+        }
+        _ => review_engine,
+    }
+}
+
+/// Fill unset review options from a preset; explicit flags always win.
+fn apply_preset(args: &mut ReviewArgs, preset: &ReviewPreset) -> Result<()> {
+    let scope_given = args.staged
+        || args.uncommitted
+        || args.all
+        || args.since.is_some()
+        || args.since_merge_base.is_some()
+        || args.commit.is_some()
+        || args.range.is_some()
+        || args.stdin
+        || args.bitbucket_pr.is_some();
+
+    if !scope_given {
+        match preset.scope {
+            Some(PresetScope::Staged) => args.staged = true,
+            Some(PresetScope::Uncommitted) => args.uncommitted = true,
+            Some(PresetScope::All) => args.all = true,
+            Some(PresetScope::Head) | None => {}
+            Some(PresetScope::MergeBase) => {
+                let git = GitIntegration::new(".")?;
+                let base_ref = match &preset.base {
+                    Some(base) => base.clone(),
+                    None => git.get_upstream_reference().ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Preset '{}' needs an upstream branch; set `base` in the preset or pass --since",
+                            preset.name
+                        )
+                    })?,
+                };
+                args.since = Some(git.get_merge_base(&base_ref)?);
+            }
+        }
+    }
+
+    if args.severity.is_none() {
+        args.severity = preset.severity;
+    }
+    if args.fail_on.is_none() {
+        args.fail_on = preset.fail_on;
+    }
+    if args.format.is_none() && !args.json && !args.ndjson {
+        args.format = preset.format.clone();
+        // The preset's output file only goes with its own format
+        if args.output.is_none() && args.per_commit.is_none() {
+            args.output = preset.output.clone();
+        }
+    }
+
+    Ok(())
+}
+
+/// Fill options still unset from the `review:` settings of a config source. Its scope, named
+/// `scope_name` in errors, only applies to a `diff_review`.
+fn apply_review_defaults(
+    args: &mut ReviewArgs,
+    defaults: &ReviewDefaults,
+    scope_name: &str,
+    diff_review: bool,
+) -> Result<()> {
+    if diff_review {
+        apply_preset(args, &defaults.scope_preset(scope_name))?;
+    }
+    args.severity = args.severity.or(defaults.severity);
+    args.fail_on = args.fail_on.or(defaults.fail_on);
+    args.max_violations = args.max_violations.or(defaults.max_violations);
+    if args.language.is_none() {
+        args.language = defaults.language.clone();
+    }
+    args.sample = args.sample.or(defaults.sample);
+    if args.format.is_none() && !args.json && !args.ndjson {
+        args.format = defaults.format.clone();
+    }
+    Ok(())
+}
+
+/// `--range`: the commits between `from` and `to`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitRange {
+    pub from: String,
+    pub to: String,
+    /// `A...B`: start from the merge base of the two, like `git diff A...B`
+    pub from_merge_base: bool,
+}
+
+impl std::fmt::Display for CommitRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let dots = if self.from_merge_base { "..." } else { ".." };
+        write!(f, "{}{dots}{}", self.from, self.to)
+    }
+}
+
+pub(crate) fn parse_commit_range(value: &str) -> std::result::Result<CommitRange, String> {
+    let (from, to, from_merge_base) = match value.split_once("...") {
+        Some((from, to)) => (from, to, true),
+        None => {
+            let (from, to) = value.split_once("..").ok_or_else(|| {
+                format!("expected a commit range such as `main..feature`, got `{value}`")
+            })?;
+            (from, to, false)
+        }
+    };
+    if from.is_empty() {
+        return Err(format!("`{value}` has no start commit"));
+    }
+    let to = if to.is_empty() { "HEAD" } else { to };
+    Ok(CommitRange { from: from.to_string(), to: to.to_string(), from_merge_base })
+}
+
+/// The scope of `--commit`, `--range` or `--since-merge-base`, resolved against the
+/// repository, and how to describe it in the report
+fn history_scope(args: &ReviewArgs) -> Result<Option<(DiffScope, String)>> {
+    if let Some(reference) = &args.since_merge_base {
+        let git = GitIntegration::new(".")?;
+        git.ensure_reference(reference, args.auto_fetch)?;
+        let merge_base = git.get_merge_base(reference).with_context(|| {
+            format!(
+                "No merge base between HEAD and {reference}; a shallow clone may need more \
+                 history (git fetch --unshallow)"
+            )
+        })?;
+        let description =
+            format!("changes since the merge base with {reference} ({merge_base:.7})");
+        return Ok(Some((DiffScope::SinceCommit(merge_base), description)));
+    }
+    if let Some(reference) = &args.commit {
+        let git = GitIntegration::new(".")?;
+        git.ensure_reference(reference, args.auto_fetch)?;
+        let commit = git.commit_info(reference)?;
+        let description = format!("commit {} ({})", commit.short_sha(), commit.summary);
+        return Ok(Some((commit.diff_scope(), description)));
+    }
+    let Some(range) = &args.range else { return Ok(None) };
+    let git = GitIntegration::new(".")?;
+    git.ensure_reference(&range.from, args.auto_fetch)?;
+    git.ensure_reference(&range.to, args.auto_fetch)?;
+    let from = match range.from_merge_base {
+        true => git.merge_base_between(&range.from, &range.to)?,
+        false => range.from.clone(),
+    };
+    Ok(Some((DiffScope::Between(from, range.to.clone()), range.to_string())))
+}
+
+fn parse_line_range(value: &str) -> std::result::Result<RangeInclusive<usize>, String> {
+    let invalid = || format!("expected a line range such as `120-180` or `42`, got `{value}`");
+    let (start, end) = value.split_once('-').unwrap_or((value, value));
+    let start: usize = start.trim().parse().map_err(|_| invalid())?;
+    let end: usize = end.trim().parse().map_err(|_| invalid())?;
+    if start == 0 || end < start {
+        return Err(invalid());
+    }
+    Ok(start..=end)
+}
+
+/// A diff treating every line of the given files as added, or only `lines` of a single file
+fn files_diff(paths: &[String], lines: Option<RangeInclusive<usize>>) -> Result<GitDiff> {
+    if lines.is_some() && paths.len() > 1 {
+        return Err(anyhow::anyhow!("--lines applies to a single --file, not {}", paths.len()));
+    }
+    let mut diff = GitDiff { files: Vec::new(), parse_warnings: Vec::new() };
+    for path in paths {
+        diff.files.extend(file_region_diff(path, lines.clone())?.files);
+    }
+    Ok(diff)
+}
+
+/// A diff treating the given lines of a working tree file (all of it by default) as added
+fn file_region_diff(path: &str, lines: Option<RangeInclusive<usize>>) -> Result<GitDiff> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read {path}: {e}"))?;
+    let line_count = content.lines().count();
+    // An empty file has nothing to review unless lines of it were asked for
+    if line_count == 0 && lines.is_none() {
+        return Ok(GitDiff { files: Vec::new(), parse_warnings: Vec::new() });
+    }
+    let lines = lines.unwrap_or(1..=line_count.max(1));
+    if *lines.start() > line_count {
+        return Err(anyhow::anyhow!(
+            "{path} has {line_count} lines, so --lines {}-{} is past its end",
+            lines.start(),
+            lines.end()
+        ));
+    }
+    Ok(GitDiff {
+        files: vec![FileDiff::from_region(path, &content, lines)],
+        parse_warnings: Vec::new(),
+    })
+}
+
+/// Every file of the project (or the current directory outside one) with a known
+/// language, for `--all`
+pub(crate) fn scan_project_tree() -> Result<GitDiff> {
+    let root = match ProjectDetector::detect_project(None) {
+        Ok(project_info) => project_info.root_path,
+        Err(_) => std::env::current_dir()?,
+    };
+    scan_tree(&root, |path| {
+        let extension = std::path::Path::new(path).extension().and_then(|e| e.to_str());
+        extension.and_then(Language::from_extension).is_some()
+    })
+}
+
+fn describe_region(path: &str, lines: Option<&RangeInclusive<usize>>) -> String {
+    match lines {
+        Some(lines) => format!("{path} lines {}-{}", lines.start(), lines.end()),
+        None => path.to_string(),
+    }
+}
+
+fn parse_sample_limit(value: &str) -> std::result::Result<usize, String> {
+    let count = value.strip_suffix("-per-rule").unwrap_or(value);
+    match count.parse::<usize>() {
+        Ok(limit) if limit > 0 => Ok(limit),
+        _ => Err(format!("expected a positive count such as `3` or `3-per-rule`, got `{value}`")),
+    }
+}
+
+fn parse_confidence(value: &str) -> std::result::Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(score) if (0.0..=1.0).contains(&score) => Ok(score),
+        _ => Err(format!("expected a score from 0 to 1 such as `0.5`, got `{value}`")),
+    }
+}
+
+/// Violations trimmed to a fixed number per rule for digestible first runs on legacy code
+struct ViolationSample {
+    shown: Vec<ReviewViolation>,
+    summary: SampleSummary,
+}
+
+fn sample_per_rule(violations: &[ReviewViolation], per_rule: usize) -> ViolationSample {
+    use std::collections::HashMap;
 
-    // Review the changes with custom rules if project detected
-    let review_engine = if let Ok(project_info) = ProjectDetector::detect_project(None) {
-        ReviewEngine::new_with_custom_rules(&project_info.name)
-    } else {
-        ReviewEngine::new()
-    };
-    let review_result = review_engine.review_git_diff(&filtered_diff)?;
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    let mut shown = Vec::new();
+    for violation in violations {
+        let count = seen.entry(&violation.rule.id).or_default();
+        *count += 1;
+        if *count <= per_rule {
+            shown.push(violation.clone());
+        }
+    }
 
-    // Filter violations by severity if specified
-    let filtered_violations = if let Some(min_severity) = args.severity {
-        review_engine
-            .filter_violations_by_severity(&review_result.violations, min_severity)
-            .into_iter()
-            .cloned()
-            .collect()
-    } else {
-        review_result.violations.clone()
-    };
+    let mut omitted: Vec<OmittedRule> = seen
+        .into_iter()
+        .filter(|(_, count)| *count > per_rule)
+        .map(|(rule_id, count)| OmittedRule {
+            rule_id: rule_id.to_string(),
+            severity: violations
+                .iter()
+                .find(|v| v.rule.id == rule_id)
+                .map_or(Severity::Warning, |v| v.severity),
+            count: count - per_rule,
+        })
+        .collect();
+    omitted.sort_by(|a, b| {
+        a.severity.cmp(&b.severity).then(b.count.cmp(&a.count)).then(a.rule_id.cmp(&b.rule_id))
+    });
+
+    let summary = SampleSummary { per_rule, shown: shown.len(), total: violations.len(), omitted };
+    ViolationSample { shown, summary }
+}
 
-    // Output results
+/// Name of the reporter to use; `--json` and `--ndjson` are shorthands for `--format`
+fn determine_output_format(args: &ReviewArgs) -> String {
     if args.json {
-        output_json_results(&review_result, &filtered_violations)?;
+        "json".to_string()
+    } else if args.ndjson {
+        "ndjson".to_string()
     } else {
-        output_human_readable_results(&filtered_violations, &diff_scope, &args)?;
-    }
-
-    // Handle fix requests
-    if args.fix {
-        handle_interactive_fix(&filtered_violations).await?;
-    } else if args.auto_fix {
-        // Show deprecation warning
-        eprintln!("⚠️  WARNING: --auto-fix is deprecated. Use --fix for interactive Claude Code sessions.");
-        eprintln!("   The --auto-fix flag will be removed in a future version.");
-        eprintln!();
-        handle_auto_fix(&filtered_violations, args.no_confirm).await?;
-    } else if args.suggest {
-        show_fix_suggestions(&filtered_violations);
+        args.format.clone().unwrap_or_else(|| "human".to_string())
     }
-
-    Ok(())
 }
 
 fn determine_diff_scope(args: &ReviewArgs) -> DiffScope {
@@ -117,6 +1704,8 @@ fn determine_diff_scope(args: &ReviewArgs) -> DiffScope {
         DiffScope::Staged
     } else if args.uncommitted {
         DiffScope::Unstaged
+    } else if args.all {
+        DiffScope::All
     } else if let Some(ref reference) = args.since {
         DiffScope::SinceCommit(reference.clone())
     } else {
@@ -125,7 +1714,7 @@ fn determine_diff_scope(args: &ReviewArgs) -> DiffScope {
     }
 }
 
-fn filter_diff_by_language(
+pub(crate) fn filter_diff_by_language(
     git_diff: crate::git::GitDiff,
     target_language: &Language,
 ) -> crate::git::GitDiff {
@@ -143,198 +1732,103 @@ fn filter_diff_by_language(
         })
         .collect();
 
-    crate::git::GitDiff { files: filtered_files }
-}
-
-fn output_json_results(
-    review_result: &crate::core::review_engine::ReviewResult,
-    violations: &[crate::core::ReviewViolation],
-) -> Result<()> {
-    use serde::{Deserialize, Serialize};
-    use serde_json;
-
-    #[derive(Serialize, Deserialize)]
-    struct JsonViolation {
-        file_path: String,
-        line_number: usize,
-        rule_id: String,
-        rule_name: String,
-        severity: String,
-        language: String,
-        description: String,
-        fix_suggestion: String,
-        auto_fixable: bool,
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct JsonOutput {
-        violations: Vec<JsonViolation>,
-        summary: JsonSummary,
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct JsonSummary {
-        total_violations: usize,
-        critical_count: usize,
-        major_count: usize,
-        warning_count: usize,
-        files_affected: usize,
-        auto_fixable_count: usize,
-    }
-
-    let json_violations: Vec<JsonViolation> = violations
-        .iter()
-        .map(|v| JsonViolation {
-            file_path: v.file_path.clone(),
-            line_number: v.line_number,
-            rule_id: v.rule.id.clone(),
-            rule_name: v.rule.name.clone(),
-            severity: format!("{:?}", v.severity).to_lowercase(),
-            language: format!("{:?}", v.language).to_lowercase(),
-            description: v.rule.description.clone(),
-            fix_suggestion: v.fix_suggestion.clone(),
-            auto_fixable: v.auto_fixable,
-        })
-        .collect();
-
-    // These are now from review_result.summary, but keeping for validation
-    let _critical_count = violations.iter().filter(|v| v.severity == Severity::Critical).count();
-    let _major_count = violations.iter().filter(|v| v.severity == Severity::Major).count();
-    let _warning_count = violations.iter().filter(|v| v.severity == Severity::Warning).count();
-    let _auto_fixable_count = violations.iter().filter(|v| v.auto_fixable).count();
-
-    let mut _files_affected: Vec<_> = violations.iter().map(|v| &v.file_path).collect();
-    _files_affected.sort();
-    _files_affected.dedup();
-
-    let json_output = JsonOutput {
-        violations: json_violations,
-        summary: JsonSummary {
-            total_violations: review_result.summary.total_violations,
-            critical_count: review_result.summary.critical_count,
-            major_count: review_result.summary.major_count,
-            warning_count: review_result.summary.warning_count,
-            files_affected: review_result.summary.files_affected.len(),
-            auto_fixable_count: review_result.summary.auto_fixable_count,
-        },
-    };
-
-    println!("{}", serde_json::to_string_pretty(&json_output)?);
-    Ok(())
+    crate::git::GitDiff { files: filtered_files, parse_warnings: git_diff.parse_warnings }
 }
 
-fn output_human_readable_results(
-    violations: &[crate::core::ReviewViolation],
-    diff_scope: &DiffScope,
+/// Stream violations to the reporter while the diff is being reviewed. Only the violations
+/// that count toward `--fail-on`, `--max-violations`, tag gates or budgets are kept, so the
+/// run can be gated like a buffered one; `failing_violations` gives the same count for them
+/// as for every violation. With `--stream` the reporter also hears about every analyzed
+/// file, so editors can show progress.
+fn stream_results(
+    reporter: &mut dyn Reporter,
+    context: &ReportContext,
+    review_engine: &ReviewEngine,
+    git_diff: &GitDiff,
     args: &ReviewArgs,
-) -> Result<()> {
-    // Header
-    let scope_description = match diff_scope {
-        DiffScope::Staged => "staged changes",
-        DiffScope::Unstaged => "unstaged changes",
-        DiffScope::SinceCommit(ref reference) => {
-            if reference == "HEAD" {
-                "changes since last commit"
+    mut skip: impl FnMut(&ReviewViolation) -> bool,
+) -> Result<(ReviewSummary, Vec<ReviewViolation>)> {
+    let mut emitted_per_rule: std::collections::HashMap<String, usize> = Default::default();
+    let mut files_reviewed = 0;
+    let codeowners = load_codeowners()?;
+    let (gates, budgets) = (review_engine.gates(), review_engine.budgets());
+    // Without a severity threshold or gates, --max-violations counts every violation
+    let counts_all = args.fail_on.is_none() && gates.is_empty() && args.max_violations.is_some();
+    let mut gated = Vec::new();
+
+    reporter.on_start(context)?;
+    let summary = review_engine.review_git_diff_streaming(git_diff, |file_diff, violations| {
+        let owners = codeowners.as_ref().map_or(&[][..], |c| c.owners_for(&file_diff.path));
+        let mut reported = 0;
+        for violation in violations {
+            if args.severity.is_some_and(|min| !violation.severity.is_at_least(min))
+                || skip(violation)
+            {
+                continue;
+            }
+            if counts_all
+                || budgets.covers(violation)
+                || gates.failing(std::slice::from_ref(violation), args.fail_on) > 0
+            {
+                gated.push(violation.clone());
+            }
+            if let Some(limit) = args.sample {
+                let emitted = emitted_per_rule.entry(violation.rule.id.clone()).or_default();
+                if *emitted >= limit {
+                    continue;
+                }
+                *emitted += 1;
+            }
+            if owners.is_empty() {
+                reporter.on_violation(violation)?;
             } else {
-                reference
+                reporter.on_violation(&ReviewViolation {
+                    owners: owners.to_vec(),
+                    ..violation.clone()
+                })?;
             }
+            reported += 1;
         }
-    };
-
-    println!("🔍 Code Review: {}", scope_description.bold());
-
-    if violations.is_empty() {
-        println!("✅ No anti-pattern violations found!");
-        return Ok(());
-    }
-
-    // Group violations by file
-    let mut violations_by_file: std::collections::HashMap<
-        String,
-        Vec<&crate::core::ReviewViolation>,
-    > = std::collections::HashMap::new();
-    for violation in violations {
-        violations_by_file.entry(violation.file_path.clone()).or_default().push(violation);
-    }
-
-    println!("📊 Found {} violations in {} files\n", violations.len(), violations_by_file.len());
-
-    // Show violations grouped by file
-    for (file_path, file_violations) in violations_by_file {
-        println!("📁 {}", file_path.bold());
-
-        for violation in file_violations {
-            let severity_icon = match violation.severity {
-                Severity::Critical => "🔴 CRITICAL".red(),
-                Severity::Major => "🟡 MAJOR".yellow(),
-                Severity::Warning => "🔵 WARNING".blue(),
-            };
-
-            println!(
-                "  {} {} ({})",
-                severity_icon,
-                violation.rule.name,
-                violation.rule.id.dimmed()
-            );
-
-            // Show line number and content
-            println!(
-                "    Line {}: {}",
-                violation.line_number.to_string().cyan(),
-                violation.content.dimmed()
-            );
-
-            // Show fix suggestion
-            println!("    💡 Fix: {}", violation.fix_suggestion);
-
-            if violation.auto_fixable && (args.suggest || args.auto_fix) {
-                println!("    ✨ Auto-fixable with Claude Code");
-            }
-
-            println!();
+        files_reviewed += 1;
+        if args.stream {
+            reporter.on_file(&FileProgress {
+                path: file_diff.path.clone(),
+                violations: reported,
+                files_reviewed,
+                files_total: git_diff.files.len(),
+            })?;
         }
-    }
-
-    // Summary
-    let critical_count = violations.iter().filter(|v| v.severity == Severity::Critical).count();
-    let major_count = violations.iter().filter(|v| v.severity == Severity::Major).count();
-    let warning_count = violations.iter().filter(|v| v.severity == Severity::Warning).count();
-    let auto_fixable_count = violations.iter().filter(|v| v.auto_fixable).count();
-
-    println!("📊 Summary: {} violations", violations.len());
-    if critical_count > 0 {
-        println!("   🔴 Critical: {critical_count}");
-    }
-    if major_count > 0 {
-        println!("   🟡 Major: {major_count}");
-    }
-    if warning_count > 0 {
-        println!("   🔵 Warning: {warning_count}");
-    }
-
-    if auto_fixable_count > 0 {
-        println!("   ✨ Auto-fixable: {auto_fixable_count}");
+        Ok(())
+    })?;
+    reporter.on_summary(&summary)?;
+    Ok((summary, gated))
+}
 
-        if !args.fix && !args.auto_fix && !args.suggest {
-            println!("\n💡 Use {} to see suggested fixes", "--suggest".cyan());
-            println!("💡 Use {} to launch interactive Claude Code session", "--fix".cyan());
+pub(crate) fn describe_scope(diff_scope: &DiffScope) -> String {
+    match diff_scope {
+        DiffScope::Staged => "staged changes".to_string(),
+        DiffScope::Unstaged => "unstaged changes".to_string(),
+        DiffScope::All => "entire repository".to_string(),
+        DiffScope::Between(from, to) => format!("{from}..{to}"),
+        DiffScope::SinceCommit(ref reference) => {
+            if reference == "HEAD" {
+                "changes since last commit".to_string()
+            } else {
+                reference.clone()
+            }
         }
     }
-
-    Ok(())
 }
 
 fn show_fix_suggestions(violations: &[crate::core::ReviewViolation]) {
-    let auto_fixable: Vec<_> = violations.iter().filter(|v| v.auto_fixable).collect();
-
-    if auto_fixable.is_empty() {
+    if violations.is_empty() {
         println!("💡 No auto-fixable violations found");
         return;
     }
 
     println!("\n🔧 Suggested Fixes:\n");
 
-    for violation in auto_fixable {
+    for violation in violations {
         println!("📁 {}:{}", violation.file_path, violation.line_number);
         println!("   Issue: {}", violation.rule.name);
         println!("   Current: {}", violation.content.red());
@@ -343,26 +1837,63 @@ fn show_fix_suggestions(violations: &[crate::core::ReviewViolation]) {
     }
 }
 
+/// Root of the detected project, which fixes' paths are relative to
+pub(crate) fn project_root() -> PathBuf {
+    ProjectDetector::detect_project(None)
+        .map(|info| info.root_path)
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+/// Apply the rewrite templates of `candidates`' rules, out of `total` reported violations
+fn handle_simple_fix(candidates: &[crate::core::ReviewViolation], total: usize) -> Result<()> {
+    let root = project_root();
+    let fixes = plan_fixes(candidates, &root)?;
+    if fixes.is_empty() {
+        println!("💡 No violations with a rewrite template to apply");
+    } else {
+        apply_fixes(&fixes, &root)?;
+        println!("\n🪄 Applied {} simple fix(es):\n", fixes.len());
+        for fix in &fixes {
+            println!("📁 {}:{} ({})", fix.file_path, fix.line_number, fix.rule_ids.join(", "));
+            println!("   {}", format!("- {}", fix.before.trim()).red());
+            println!("   {}", format!("+ {}", fix.after.trim()).green());
+        }
+    }
+
+    let fixed: usize = fixes.iter().map(|fix| fix.rule_ids.len()).sum();
+    if total > fixed {
+        println!(
+            "\n💡 {} violation(s) have no template or no longer match; use --fix for those",
+            total - fixed
+        );
+    }
+    Ok(())
+}
+
 async fn handle_auto_fix(
     violations: &[crate::core::ReviewViolation],
     no_confirm: bool,
+    provider: Option<&str>,
 ) -> Result<()> {
-    let auto_fixable: Vec<_> = violations.iter().filter(|v| v.auto_fixable).cloned().collect();
-
-    if auto_fixable.is_empty() {
+    if violations.is_empty() {
         println!("💡 No auto-fixable violations found");
         return Ok(());
     }
 
     // Create fix engine and batch request
-    let fix_engine = FixEngine::new();
+    let fix_config = FixProviderConfig::load_for_project()?;
+    let fix_engine = FixEngine::new()
+        .with_provider(fix_config.provider(provider)?)
+        .with_prompts(PromptTemplates::load_for_project()?)
+        .with_verify_command(fix_config.verify_command)
+        .with_parallelism(fix_config.parallelism.unwrap_or(DEFAULT_PARALLELISM));
 
     // Preview what will be fixed
-    fix_engine.preview_batch_fixes(&auto_fixable)?;
+    fix_engine.preview_batch_fixes(violations)?;
 
     // Ask for confirmation unless --no-confirm is used
     if !no_confirm {
-        print!("\n🤖 Apply fixes with Claude Code? [y/N]: ");
+        print!("\n🤖 Apply fixes with {}? [y/N]: ", fix_engine.provider_name());
         use std::io::{self, Write};
         io::stdout().flush().unwrap();
 
@@ -379,7 +1910,7 @@ async fn handle_auto_fix(
 
     // Create batch fix request
     let batch_request = BatchFixRequest {
-        violations: auto_fixable,
+        violations: violations.to_vec(),
         dry_run: false,
         interactive: !no_confirm, // Interactive mode unless --no-confirm is used
         confidence_threshold: 0.7,
@@ -394,7 +1925,10 @@ async fn handle_auto_fix(
     Ok(())
 }
 
-async fn handle_interactive_fix(violations: &[crate::core::ReviewViolation]) -> Result<()> {
+fn handle_interactive_fix(
+    violations: &[crate::core::ReviewViolation],
+    sessions: FixSessions,
+) -> Result<()> {
     if violations.is_empty() {
         println!("✅ No violations found to fix!");
         return Ok(());
@@ -409,6 +1943,30 @@ async fn handle_interactive_fix(violations: &[crate::core::ReviewViolation]) ->
         return Ok(());
     }
 
+    // Determine which command to use
+    let claude_cmd = if which("claude").is_ok() { "claude" } else { "claude-code" };
+    use std::process::Command;
+
+    if sessions != FixSessions::Single {
+        let sessions = sessions.split(violations);
+        println!(
+            "🔍 Found {} violation(s). Fixing them in {} Claude Code session(s)...",
+            violations.len(),
+            sessions.len()
+        );
+        let tally = run_sessions(
+            &sessions,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stdout(),
+            |session| {
+                Ok(Command::new(claude_cmd).arg(create_claude_query(session)?).status()?.success())
+            },
+        )?;
+        println!("\n📊 Fix sessions: {tally}");
+        println!("💡 Run 'patingin review' again to check if violations were fixed.");
+        return Ok(());
+    }
+
     println!(
         "🔍 Found {} violation(s). Launching interactive Claude Code session...",
         violations.len()
@@ -417,11 +1975,7 @@ async fn handle_interactive_fix(violations: &[crate::core::ReviewViolation]) ->
     // Create the comprehensive query for Claude Code
     let query = create_claude_query(violations)?;
 
-    // Determine which command to use
-    let claude_cmd = if which("claude").is_ok() { "claude" } else { "claude-code" };
-
     // Launch Claude Code with the query
-    use std::process::Command;
     let status = Command::new(claude_cmd).arg(&query).status()?;
 
     if status.success() {
@@ -435,110 +1989,18 @@ async fn handle_interactive_fix(violations: &[crate::core::ReviewViolation]) ->
 }
 
 fn create_claude_query(violations: &[crate::core::ReviewViolation]) -> Result<String> {
-    use crate::core::ProjectDetector;
-    use std::collections::HashMap;
-    use std::env;
-
-    // Get project information
-    let project_info = match ProjectDetector::detect_project(None) {
-        Ok(info) => info,
+    let (project_name, languages) = match ProjectDetector::detect_project(None) {
+        Ok(info) => (info.name, info.languages.iter().map(ToString::to_string).collect()),
         Err(_) => {
             // Fallback project info
-            let current_dir = env::current_dir()?;
+            let current_dir = std::env::current_dir()?;
             let project_name =
                 current_dir.file_name().and_then(|n| n.to_str()).unwrap_or("unknown-project");
-
-            crate::core::project_detector::ProjectInfo {
-                name: project_name.to_string(),
-                root_path: current_dir,
-                languages: vec![],
-                package_files: vec![],
-                project_type: crate::core::project_detector::ProjectType::Generic,
-            }
+            (project_name.to_string(), Vec::new())
         }
     };
 
-    // Group violations by file
-    let mut files_with_violations: HashMap<String, Vec<&crate::core::ReviewViolation>> =
-        HashMap::new();
-    for violation in violations {
-        files_with_violations.entry(violation.file_path.clone()).or_default().push(violation);
-    }
-
-    // Format languages
-    let languages: Vec<String> = project_info.languages.iter().map(|l| format!("{l:?}")).collect();
-    let languages_str =
-        if languages.is_empty() { "Unknown".to_string() } else { languages.join(", ") };
-
-    // Build the comprehensive query
-    let mut query = format!(
-        "Fix these code quality violations in my project:\n\n\
-        PROJECT: {} ({})\n\
-        FILES AFFECTED: {} files with {} violations\n\n\
-        VIOLATIONS FOUND:\n\n",
-        project_info.name,
-        languages_str,
-        files_with_violations.len(),
-        violations.len()
-    );
-
-    // Add each violation with context
-    for (file_path, file_violations) in files_with_violations {
-        for violation in file_violations {
-            let severity_icon = match violation.severity {
-                crate::core::Severity::Critical => "🔴",
-                crate::core::Severity::Major => "🟡",
-                crate::core::Severity::Warning => "🔵",
-            };
-
-            let severity_text = match violation.severity {
-                crate::core::Severity::Critical => "CRITICAL",
-                crate::core::Severity::Major => "MAJOR",
-                crate::core::Severity::Warning => "WARNING",
-            };
-
-            query.push_str(&format!(
-                "📁 {}:{}\n\
-                {} {}: {} ({})\n\
-                   Problem: {}\n\
-                   Code:\n",
-                file_path,
-                violation.line_number,
-                severity_icon,
-                severity_text,
-                violation.rule.name,
-                violation.rule.id,
-                violation.rule.description
-            ));
-
-            // Add context lines with line numbers
-            let context_start = violation.line_number.saturating_sub(2);
-
-            // Show context before
-            for (i, line) in violation.context_before.iter().enumerate() {
-                let line_num = context_start + i;
-                query.push_str(&format!("   {line_num} │ {line}\n"));
-            }
-
-            // Show the violation line
-            query.push_str(&format!(
-                "   {} │ {}  ← VIOLATION\n",
-                violation.line_number, violation.content
-            ));
-
-            // Show context after
-            for (i, line) in violation.context_after.iter().enumerate() {
-                let line_num = violation.line_number + 1 + i;
-                query.push_str(&format!("   {line_num} │ {line}\n"));
-            }
-
-            query.push_str(&format!("   Fix: {}\n\n", violation.fix_suggestion));
-        }
-    }
-
-    query.push_str("Please help me fix these issues interactively. Show me the problems and guide me through solutions.");
-
-    Ok(query)
+    PromptTemplates::load_for_project()?.render_session(&project_name, &languages, violations)
 }
 
 #[cfg(test)]
@@ -546,6 +2008,7 @@ mod review_command_tests {
     use super::*;
     use crate::core::{AntiPattern, DetectionMethod, Language, ReviewViolation, Severity};
     use crate::git::DiffScope;
+    use std::collections::BTreeMap;
 
     fn create_test_args() -> ReviewArgs {
         ReviewArgs {
@@ -553,6 +2016,8 @@ mod review_command_tests {
             uncommitted: false,
             since: None,
             severity: None,
+            min_confidence: None,
+            stream: false,
             language: None,
             json: false,
             no_color: false,
@@ -560,6 +2025,7 @@ mod review_command_tests {
             fix: false,
             auto_fix: false,
             no_confirm: false,
+            ..Default::default()
         }
     }
 
@@ -576,6 +2042,10 @@ mod review_command_tests {
             claude_code_fixable: true,
             examples: vec![],
             tags: vec![],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
         };
 
@@ -591,29 +2061,13 @@ mod review_command_tests {
             context_before: vec!["# Previous line".to_string()],
             context_after: vec!["# Next line".to_string()],
             confidence: 0.85,
+            span: None,
+            blame: None,
+            captures: BTreeMap::new(),
+            owners: Vec::new(),
         }
     }
 
-    fn create_test_review_result() -> crate::core::review_engine::ReviewResult {
-        use crate::core::review_engine::{ReviewResult, ReviewSummary};
-        use std::collections::HashMap;
-
-        let violations = vec![create_test_violation()];
-        let mut files_with_violations = HashMap::new();
-        files_with_violations.insert("test.ex".to_string(), violations.clone());
-
-        let summary = ReviewSummary {
-            total_violations: 1,
-            critical_count: 0,
-            major_count: 1,
-            warning_count: 0,
-            files_affected: vec!["test.ex".to_string()],
-            auto_fixable_count: 1,
-        };
-
-        ReviewResult { violations, files_with_violations, summary }
-    }
-
     #[test]
     fn test_determine_diff_scope_default() {
         let args = create_test_args();
@@ -681,45 +2135,261 @@ mod review_command_tests {
     }
 
     #[test]
-    fn test_output_json_results_structure() {
-        let review_result = create_test_review_result();
-        let violations = vec![create_test_violation()];
+    fn test_determine_diff_scope_all() {
+        let mut args = create_test_args();
+        args.all = true;
+        assert_eq!(determine_diff_scope(&args), DiffScope::All);
+    }
 
-        // Capture stdout to test JSON structure
-        let result = output_json_results(&review_result, &violations);
-        assert!(result.is_ok());
+    #[test]
+    fn test_apply_preset_fills_unset_options() {
+        let mut args = create_test_args();
+        let preset = find_preset("pre-commit", None).unwrap();
 
-        // Test that the function runs without panic
-        // In a real test, we'd capture and parse the JSON output
+        apply_preset(&mut args, &preset).unwrap();
+
+        assert!(args.staged);
+        assert_eq!(args.severity, Some(Severity::Major));
+        assert_eq!(args.fail_on, Some(Severity::Critical));
+        assert_eq!(determine_output_format(&args), "human");
     }
 
     #[test]
-    fn test_output_json_results_empty_violations() {
-        let review_result = create_test_review_result();
-        let violations: Vec<ReviewViolation> = vec![];
+    fn test_apply_preset_respects_explicit_flags() {
+        let mut args = create_test_args();
+        args.since = Some("HEAD~2".to_string());
+        args.severity = Some(Severity::Critical);
+        args.json = true;
+        let preset = find_preset("nightly", None).unwrap();
 
-        let result = output_json_results(&review_result, &violations);
-        assert!(result.is_ok());
+        apply_preset(&mut args, &preset).unwrap();
+
+        assert!(!args.all);
+        assert_eq!(determine_diff_scope(&args), DiffScope::SinceCommit("HEAD~2".to_string()));
+        assert_eq!(args.severity, Some(Severity::Critical));
+        assert_eq!(determine_output_format(&args), "json");
+        // The HTML report file does not apply to JSON on stdout
+        assert_eq!(args.output, None);
+
+        let mut args = create_test_args();
+        apply_preset(&mut args, &find_preset("nightly", None).unwrap()).unwrap();
+        assert_eq!(determine_output_format(&args), "html");
+        assert_eq!(args.output, Some(PathBuf::from("patingin-report.html")));
     }
 
     #[test]
-    fn test_output_human_readable_results() {
-        let violations = vec![create_test_violation()];
-        let diff_scope = DiffScope::SinceCommit("HEAD".to_string());
-        let args = create_test_args();
+    fn test_review_defaults_fill_only_unset_options() {
+        let mut args = create_test_args();
+        args.severity = Some(Severity::Critical);
+        args.json = true;
+        let defaults = ReviewDefaults {
+            scope: Some(PresetScope::All),
+            severity: Some(Severity::Warning),
+            fail_on: Some(Severity::Major),
+            max_violations: Some(5),
+            format: Some("markdown".to_string()),
+            ..Default::default()
+        };
 
-        let result = output_human_readable_results(&violations, &diff_scope, &args);
-        assert!(result.is_ok());
+        apply_review_defaults(&mut args, &defaults, "patingin.yml", false).unwrap();
+
+        assert!(!args.all);
+        assert_eq!(args.severity, Some(Severity::Critical));
+        assert_eq!(args.fail_on, Some(Severity::Major));
+        assert_eq!(args.max_violations, Some(5));
+        assert_eq!(determine_output_format(&args), "json");
+
+        apply_review_defaults(&mut args, &defaults, "patingin.yml", true).unwrap();
+        assert!(args.all);
+    }
+
+    fn create_commit_review(sha: &str, violations: Vec<ReviewViolation>) -> CommitReview {
+        let mut summary = ReviewSummary::default();
+        violations.iter().for_each(|v| summary.record(v));
+        CommitReview {
+            commit: CommitInfo {
+                sha: sha.to_string(),
+                summary: format!("Commit {sha}"),
+                author: "Test".to_string(),
+                parent: None,
+            },
+            violations,
+            summary,
+        }
     }
 
     #[test]
-    fn test_output_human_readable_results_empty() {
-        let violations: Vec<ReviewViolation> = vec![];
-        let diff_scope = DiffScope::Staged;
-        let args = create_test_args();
+    fn test_per_commit_json_nests_violations_under_commits() {
+        let reviews = vec![
+            create_commit_review("aaaaaaa111", vec![create_test_violation()]),
+            create_commit_review("bbbbbbb222", vec![]),
+            create_commit_review(
+                "ccccccc333",
+                vec![create_test_violation(), create_test_violation()],
+            ),
+        ];
 
-        let result = output_human_readable_results(&violations, &diff_scope, &args);
-        assert!(result.is_ok());
+        let output = build_per_commit_json("main..feature", &reviews);
+        let value = serde_json::to_value(&output).unwrap();
+
+        assert_eq!(value["range"], "main..feature");
+        assert_eq!(value["commits"].as_array().unwrap().len(), 3);
+        assert_eq!(value["commits"][0]["sha"], "aaaaaaa111");
+        assert_eq!(value["commits"][0]["violations"].as_array().unwrap().len(), 1);
+        assert_eq!(value["commits"][1]["summary"]["total_violations"], 0);
+        assert_eq!(value["commits"][2]["summary"]["total_violations"], 2);
+        assert_eq!(value["summary"]["total_violations"], 3);
+    }
+
+    #[test]
+    fn test_per_commit_markdown_sections() {
+        let reviews = vec![
+            create_commit_review("aaaaaaa111", vec![create_test_violation()]),
+            create_commit_review("bbbbbbb222", vec![]),
+        ];
+
+        let report = render_per_commit_markdown("main..feature", &reviews);
+
+        assert!(report.contains("## aaaaaaa Commit aaaaaaa111"));
+        assert!(report.contains("## bbbbbbb Commit bbbbbbb222"));
+        assert!(report.contains("### `test.ex`"));
+        assert!(report.contains("**Total: 1 violations across 2 commits**"));
+        assert!(!report.contains("patingin review:"));
+    }
+
+    fn create_rule_violation(
+        rule_id: &str,
+        severity: Severity,
+        line_number: usize,
+    ) -> ReviewViolation {
+        let mut violation = create_test_violation();
+        violation.rule.id = rule_id.to_string();
+        violation.severity = severity;
+        violation.line_number = line_number;
+        violation
+    }
+
+    #[test]
+    fn test_parse_sample_limit() {
+        assert_eq!(parse_sample_limit("3"), Ok(3));
+        assert_eq!(parse_sample_limit("5-per-rule"), Ok(5));
+        assert!(parse_sample_limit("0").is_err());
+        assert!(parse_sample_limit("many").is_err());
+    }
+
+    #[test]
+    fn test_parse_confidence() {
+        assert_eq!(parse_confidence("0.5"), Ok(0.5));
+        assert_eq!(parse_confidence("1"), Ok(1.0));
+        assert!(parse_confidence("1.5").is_err());
+        assert!(parse_confidence("high").is_err());
+    }
+
+    #[test]
+    fn test_parse_commit_range() {
+        let range = parse_commit_range("v1.2..v1.3").unwrap();
+        assert_eq!((range.from.as_str(), range.to.as_str()), ("v1.2", "v1.3"));
+        assert!(!range.from_merge_base);
+
+        let range = parse_commit_range("main...").unwrap();
+        assert_eq!((range.from.as_str(), range.to.as_str()), ("main", "HEAD"));
+        assert!(range.from_merge_base);
+        assert_eq!(range.to_string(), "main...HEAD");
+
+        assert!(parse_commit_range("main").is_err());
+        assert!(parse_commit_range("..feature").is_err());
+    }
+
+    #[test]
+    fn test_commit_and_range_override_preset_scope() {
+        let mut args = create_test_args();
+        args.commit = Some("HEAD~1".to_string());
+        apply_preset(&mut args, &find_preset("nightly", None).unwrap()).unwrap();
+        assert!(!args.all);
+
+        let mut args = create_test_args();
+        args.range = Some(parse_commit_range("main..feature").unwrap());
+        apply_preset(&mut args, &find_preset("pre-commit", None).unwrap()).unwrap();
+        assert!(!args.staged);
+    }
+
+    #[test]
+    fn test_parse_line_range() {
+        assert_eq!(parse_line_range("120-180"), Ok(120..=180));
+        assert_eq!(parse_line_range("42"), Ok(42..=42));
+        assert!(parse_line_range("0-3").is_err());
+        assert!(parse_line_range("180-120").is_err());
+        assert!(parse_line_range("start-end").is_err());
+    }
+
+    #[test]
+    fn test_file_region_review_checks_only_the_region() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("user.ex");
+        std::fs::write(
+            &path,
+            "def a(x) do\n  String.to_atom(x)\nend\ndef b(y) do\n  String.to_atom(y)\nend\n",
+        )
+        .unwrap();
+        let path = path.to_str().unwrap();
+
+        let region = file_region_diff(path, Some(4..=6)).unwrap();
+        let result = ReviewEngine::new().review_git_diff(&region).unwrap();
+        let lines: Vec<usize> = result.violations.iter().map(|v| v.line_number).collect();
+        assert_eq!(lines, [5]);
+
+        assert_eq!(file_region_diff(path, None).unwrap().files[0].added_lines.len(), 6);
+        assert!(file_region_diff(path, Some(7..=9)).unwrap_err().to_string().contains("6 lines"));
+        assert_eq!(describe_region("lib/user.ex", Some(&(4..=6))), "lib/user.ex lines 4-6");
+    }
+
+    #[test]
+    fn test_files_diff_covers_every_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = |name: &str| temp_dir.path().join(name).to_str().unwrap().to_string();
+        std::fs::write(path("a.ex"), "def a(x), do: String.to_atom(x)\n").unwrap();
+        std::fs::write(path("b.py"), "import os\nprint(os.name)\n").unwrap();
+        std::fs::write(path("empty.ex"), "").unwrap();
+        let paths = [path("a.ex"), path("b.py"), path("empty.ex")];
+
+        let diff = files_diff(&paths, None).unwrap();
+        let lines: Vec<usize> = diff.files.iter().map(|file| file.added_lines.len()).collect();
+        assert_eq!(lines, [1, 2]);
+
+        let error = files_diff(&paths, Some(1..=1)).unwrap_err().to_string();
+        assert_eq!(error, "--lines applies to a single --file, not 3");
+        assert!(files_diff(&[path("missing.ex")], None).is_err());
+    }
+
+    #[test]
+    fn test_sample_per_rule_caps_and_counts() {
+        let mut violations: Vec<ReviewViolation> = (1..=5)
+            .map(|line| create_rule_violation("noisy_warning", Severity::Warning, line))
+            .collect();
+        violations
+            .extend((1..=3).map(|line| create_rule_violation("atoms", Severity::Critical, line)));
+        violations.push(create_rule_violation("rare", Severity::Major, 1));
+
+        let sample = sample_per_rule(&violations, 2);
+
+        assert_eq!(sample.summary.total, 9);
+        assert_eq!(sample.shown.len(), 5);
+        assert_eq!(sample.summary.shown, 5);
+        assert_eq!(sample.shown.iter().filter(|v| v.rule.id == "noisy_warning").count(), 2);
+        assert_eq!(sample.shown.iter().filter(|v| v.rule.id == "rare").count(), 1);
+        // Omitted rules are ordered most severe first
+        let omitted = |rule_id: &str, severity, count| OmittedRule {
+            rule_id: rule_id.to_string(),
+            severity,
+            count,
+        };
+        assert_eq!(
+            sample.summary.omitted,
+            vec![
+                omitted("atoms", Severity::Critical, 1),
+                omitted("noisy_warning", Severity::Warning, 3)
+            ]
+        );
     }
 
     #[test]
@@ -731,13 +2401,14 @@ mod review_command_tests {
         // In real tests, we'd capture stdout and verify output
     }
 
-    #[test]
-    fn test_show_fix_suggestions_no_auto_fixable() {
+    #[tokio::test]
+    async fn test_show_fix_suggestions_no_auto_fixable() {
         let mut violation = create_test_violation();
         violation.auto_fixable = false;
         let violations = vec![violation];
 
-        show_fix_suggestions(&violations);
+        assert!(FixMode::Suggest.candidates(&violations).is_empty());
+        FixMode::Suggest.run(&violations, None).await.unwrap();
     }
 
     #[test]
@@ -751,7 +2422,7 @@ mod review_command_tests {
         let violations = vec![create_test_violation()];
 
         // Use no_confirm=true to avoid waiting for user input in tests
-        let result = handle_auto_fix(&violations, true).await;
+        let result = handle_auto_fix(&violations, true, None).await;
         assert!(result.is_ok());
     }
 
@@ -761,8 +2432,8 @@ mod review_command_tests {
         violation.auto_fixable = false;
         let violations = vec![violation];
 
-        // Use no_confirm=true to avoid waiting for user input in tests
-        let result = handle_auto_fix(&violations, true).await;
+        // Unconfirmed batches never wait for user input in tests
+        let result = FixMode::Batch { confirm: false }.run(&violations, None).await;
         assert!(result.is_ok());
     }
 
@@ -771,16 +2442,216 @@ mod review_command_tests {
         let violations: Vec<ReviewViolation> = vec![];
 
         // Use no_confirm=true to avoid waiting for user input in tests
-        let result = handle_auto_fix(&violations, true).await;
+        let result = handle_auto_fix(&violations, true, None).await;
         assert!(result.is_ok());
     }
 
+    fn parse_review_args(flags: &[&str]) -> Result<ReviewArgs, clap::Error> {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            review: ReviewArgs,
+        }
+
+        let argv = std::iter::once("review").chain(flags.iter().copied());
+        Cli::try_parse_from(argv).map(|cli| cli.review)
+    }
+
+    #[test]
+    fn test_max_violations_tolerates_failing_violations() {
+        use crate::report::test_support::violation;
+        let gates = TagGates::default();
+        let violations = vec![
+            violation("a.ex", 1, Severity::Critical),
+            violation("a.ex", 2, Severity::Warning),
+            violation("b.ex", 3, Severity::Warning),
+        ];
+        let budgets = Budgets::default();
+        let failing =
+            |fail_on, max| failing_violations(&gates, &budgets, &violations, fail_on, max);
+
+        assert_eq!(failing(None, None), 0);
+        assert_eq!(failing(Some(Severity::Critical), None), 1);
+        assert_eq!(failing(Some(Severity::Critical), Some(1)), 0);
+        assert_eq!(failing(Some(Severity::Warning), Some(2)), 3);
+        // Without --fail-on the budget counts every violation
+        assert_eq!(failing(None, Some(3)), 0);
+        assert_eq!(failing(None, Some(2)), 3);
+    }
+
+    #[test]
+    fn test_budgeted_violations_fail_only_over_budget() {
+        use crate::core::budgets::Budget;
+        use crate::report::test_support::violation;
+        let gates = TagGates::default();
+        let budgets = Budgets(vec![Budget {
+            glob: "legacy/**".to_string(),
+            allowed: [(Severity::Critical, 1)].into_iter().collect(),
+        }]);
+        let mut violations = vec![
+            violation("legacy/a.ex", 1, Severity::Critical),
+            violation("lib/b.ex", 2, Severity::Warning),
+        ];
+        let failing = |violations: &[ReviewViolation]| {
+            failing_violations(&gates, &budgets, violations, Some(Severity::Critical), None)
+        };
+
+        assert_eq!(failing(&violations), 0);
+        violations.push(violation("legacy/c.ex", 3, Severity::Critical));
+        assert_eq!(failing(&violations), 1);
+        violations.push(violation("lib/d.ex", 4, Severity::Critical));
+        assert_eq!(failing(&violations), 2);
+    }
+
+    #[test]
+    fn test_fix_mode_matrix() {
+        let mode = |flags: &[&str]| FixMode::from_args(&parse_review_args(flags).unwrap());
+
+        assert_eq!(mode(&[]), None);
+        assert_eq!(mode(&["--suggest"]), Some(FixMode::Suggest));
+        assert_eq!(mode(&["--fix"]), Some(FixMode::Interactive { sessions: FixSessions::Single }));
+        assert_eq!(
+            mode(&["--fix", "--fix-per-file"]),
+            Some(FixMode::Interactive { sessions: FixSessions::PerFile })
+        );
+        assert_eq!(
+            mode(&["--fix", "--fix-one-by-one"]),
+            Some(FixMode::Interactive { sessions: FixSessions::PerViolation })
+        );
+        assert_eq!(mode(&["--fix", "--no-confirm"]), Some(FixMode::Batch { confirm: false }));
+        assert_eq!(mode(&["--auto-fix"]), Some(FixMode::Batch { confirm: true }));
+        assert_eq!(
+            mode(&["--auto-fix", "--no-confirm", "--severity", "critical"]),
+            Some(FixMode::Batch { confirm: false })
+        );
+        assert_eq!(
+            mode(&["--fix", "--no-confirm", "--fix-provider", "ollama"]),
+            Some(FixMode::Batch { confirm: false })
+        );
+        assert_eq!(mode(&["--fix-simple"]), Some(FixMode::Simple));
+
+        for conflicting in [
+            &["--suggest", "--fix"][..],
+            &["--suggest", "--auto-fix"],
+            &["--fix", "--auto-fix"],
+            &["--suggest", "--no-confirm"],
+            &["--no-confirm"],
+            &["--per-commit", "main..feature", "--suggest"],
+            &["--fix-per-file"],
+            &["--auto-fix", "--fix-one-by-one"],
+            &["--fix", "--no-confirm", "--fix-per-file"],
+            &["--fix", "--fix-per-file", "--fix-one-by-one"],
+            &["--fix-provider", "ollama"],
+            &["--suggest", "--fix-provider", "ollama"],
+            &["--fix-simple", "--fix"],
+            &["--fix-simple", "--no-confirm"],
+            &["--fix-simple", "--fix-provider", "ollama"],
+        ] {
+            assert!(parse_review_args(conflicting).is_err(), "{conflicting:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_stream_flag() {
+        let args = parse_review_args(&["--format", "jsonl", "--stream"]).unwrap();
+        assert!(args.stream);
+        assert_eq!(determine_output_format(&args), "jsonl");
+        assert!(parse_review_args(&["--stream", "--per-commit", "main..feature"]).is_err());
+    }
+
+    #[test]
+    fn test_bitbucket_pr_flags() {
+        let args =
+            parse_review_args(&["--bitbucket-pr", "7", "--bitbucket-repo", "acme/app"]).unwrap();
+        assert_eq!(args.bitbucket_pr, Some(7));
+        assert_eq!(args.bitbucket_repo.as_deref(), Some("acme/app"));
+        // The pull request is the scope
+        assert!(parse_review_args(&["--bitbucket-pr", "7", "--staged"]).is_err());
+        assert!(parse_review_args(&["--bitbucket-pr", "7", "--github-pr", "7"]).is_err());
+        assert!(parse_review_args(&["--bitbucket-repo", "acme/app"]).is_err());
+    }
+
+    #[test]
+    fn test_fix_commit_message_lists_rules() {
+        use crate::report::test_support::violation;
+        let mut other = violation("b.ex", 2, Severity::Warning);
+        other.rule.id = "a_rule".to_string();
+        let first = violation("a.ex", 1, Severity::Major);
+        let second = violation("a.ex", 5, Severity::Major);
+
+        let message = fix_commit_message(FixMode::Simple, &[&first, &other, &second], 2);
+
+        let rule = &first.rule.id;
+        assert_eq!(
+            message,
+            format!(
+                "Fix 3 patingin violation(s) in 2 file(s)\n\nApplied with `patingin review \
+                 --fix-simple`.\n\nRules fixed:\n- a_rule: 1\n- {rule}: 2\n\n\
+                 Patingin-Rules: a_rule, {rule}\n"
+            )
+        );
+        assert!(!fix_commit_message(FixMode::Batch { confirm: false }, &[], 1).contains("Rules"));
+        assert!(parse_review_args(&["--commit-fixes"]).is_err());
+        assert!(parse_review_args(&["--suggest", "--commit-fixes"]).is_err());
+        assert!(parse_review_args(&["--fix-simple", "--commit-fixes"]).unwrap().commit_fixes);
+    }
+
+    #[test]
+    fn test_since_merge_base_is_a_scope() {
+        let mut args = parse_review_args(&["--since-merge-base", "origin/main"]).unwrap();
+        assert_eq!(args.since_merge_base.as_deref(), Some("origin/main"));
+        apply_preset(&mut args, &find_preset("nightly", None).unwrap()).unwrap();
+        assert!(!args.all);
+
+        assert!(parse_review_args(&["--since-merge-base", "main", "--since", "main"]).is_err());
+        assert!(parse_review_args(&["--since-merge-base", "main", "--range", "a..b"]).is_err());
+    }
+
+    #[test]
+    fn test_fix_modes_share_one_candidate_set() {
+        let mut not_fixable = create_test_violation();
+        not_fixable.auto_fixable = false;
+        not_fixable.line_number = 7;
+        let violations = vec![create_test_violation(), not_fixable];
+
+        let candidates = FixMode::Suggest.candidates(&violations);
+        assert_eq!(candidates.len(), 1);
+        assert!(candidates[0].auto_fixable);
+        assert_eq!(
+            FixMode::Interactive { sessions: FixSessions::Single }.candidates(&violations).len(),
+            1
+        );
+
+        // Simple fixes go by the rule's template instead
+        let mut templated = violations[1].clone();
+        templated.rule.auto_fix = Some(crate::core::autofix::AutoFix {
+            search: "IO\\.inspect".to_string(),
+            replace: "Logger.debug".to_string(),
+            unless: None,
+        });
+        let candidates = FixMode::Simple.candidates(&[violations[0].clone(), templated]);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].line_number, 7);
+        assert!(!FixMode::shows_auto_fixable(Some(FixMode::Simple)));
+
+        assert!(FixMode::shows_auto_fixable(Some(FixMode::Suggest)));
+        assert!(FixMode::shows_auto_fixable(Some(FixMode::Batch { confirm: true })));
+        assert!(!FixMode::shows_auto_fixable(Some(FixMode::Interactive {
+            sessions: FixSessions::PerFile
+        })));
+        assert!(!FixMode::shows_auto_fixable(None));
+    }
+
     #[test]
     fn test_filter_diff_by_language() {
         use crate::git::{ChangeType, ChangedLine, FileDiff, GitDiff};
 
         let file_diff = FileDiff {
             path: "test.ex".to_string(),
+            previous_path: None,
+            kind: Default::default(),
             added_lines: vec![ChangedLine {
                 line_number: 1,
                 content: "defmodule Test do".to_string(),
@@ -789,9 +2660,11 @@ mod review_command_tests {
                 context_after: vec![],
             }],
             removed_lines: vec![],
+            format_only_lines: vec![],
+            hunks: vec![],
         };
 
-        let git_diff = GitDiff { files: vec![file_diff] };
+        let git_diff = GitDiff { files: vec![file_diff], parse_warnings: Vec::new() };
 
         let filtered = filter_diff_by_language(git_diff, &Language::Elixir);
         assert_eq!(filtered.files.len(), 1);
@@ -804,6 +2677,8 @@ mod review_command_tests {
 
         let file_diff = FileDiff {
             path: "test.py".to_string(),
+            previous_path: None,
+            kind: Default::default(),
             added_lines: vec![ChangedLine {
                 line_number: 1,
                 content: "def test():".to_string(),
@@ -812,9 +2687,11 @@ mod review_command_tests {
                 context_after: vec![],
             }],
             removed_lines: vec![],
+            format_only_lines: vec![],
+            hunks: vec![],
         };
 
-        let git_diff = GitDiff { files: vec![file_diff] };
+        let git_diff = GitDiff { files: vec![file_diff], parse_warnings: Vec::new() };
 
         let filtered = filter_diff_by_language(git_diff, &Language::Elixir);
         assert_eq!(filtered.files.len(), 0);
@@ -822,7 +2699,7 @@ mod review_command_tests {
 
     #[test]
     fn test_multiple_violations_summary() {
-        let violations = vec![
+        let violations = [
             {
                 let mut v = create_test_violation();
                 v.severity = Severity::Critical;
@@ -0,0 +1,143 @@
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::process::Command;
+
+use crate::core::CustomRule;
+
+/// Comment at the top of the file the rule is edited in
+const HEADER: &str = "\
+# Save and close to update the rule. The pattern must be a valid regex and the
+# severity one of critical, major or warning.
+";
+
+/// Open `path` in `$EDITOR`, or `vi` without one, and wait for it to close
+pub fn open_in_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
+    // Editors are often set with arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let Some(program) = words.next() else {
+        bail!("$EDITOR is empty");
+    };
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{editor}'"))?;
+    if !status.success() {
+        bail!("Editor '{editor}' exited with {status}");
+    }
+    Ok(())
+}
+
+/// Let `edit` change `rule` as YAML in a temporary file. An edit that is not a valid rule
+/// is reported on `out`, and the file is opened again unless the answer on `input` is no.
+/// `None` when the rule is left unchanged or the edit is given up.
+pub fn edit_rule(
+    rule: &CustomRule,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    mut edit: impl FnMut(&Path) -> Result<()>,
+) -> Result<Option<CustomRule>> {
+    let original = serde_yaml::to_string(rule)?;
+    let file = tempfile::Builder::new().prefix("patingin-rule-").suffix(".yml").tempfile()?;
+    std::fs::write(file.path(), format!("{HEADER}{original}"))?;
+
+    loop {
+        edit(file.path())?;
+        let content = std::fs::read_to_string(file.path())?;
+        match parse_rule(&content) {
+            Ok(edited) if serde_yaml::to_string(&edited)? == original => {
+                writeln!(out, "💤 No changes to {}", rule.id)?;
+                return Ok(None);
+            }
+            Ok(edited) => return Ok(Some(edited)),
+            Err(e) => writeln!(out, "❌ {e:#}")?,
+        }
+
+        write!(out, "❓ Edit again? [Y/n]: ")?;
+        out.flush()?;
+        let mut answer = String::new();
+        // End of input is taken as giving up, so a closed stdin never loops
+        if input.read_line(&mut answer)? == 0 || answer.trim().to_lowercase().starts_with('n') {
+            writeln!(out, "🚫 Rule not changed")?;
+            return Ok(None);
+        }
+    }
+}
+
+fn parse_rule(content: &str) -> Result<CustomRule> {
+    let rule: CustomRule = serde_yaml::from_str(content).context("Not a valid rule")?;
+    rule.validate()?;
+    Ok(rule)
+}
+
+#[cfg(test)]
+mod rule_editor_tests {
+    use super::*;
+
+    fn rule() -> CustomRule {
+        CustomRule {
+            id: "no_print".to_string(),
+            description: "No print".to_string(),
+            pattern: r"print\(".to_string(),
+            severity: "warning".to_string(),
+            fix: "Use logging".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
+            enabled: true,
+            file_scope: None,
+            examples: vec![],
+        }
+    }
+
+    /// An editor that replaces `from` with `to` in the file
+    fn replacing(from: &'static str, to: &'static str) -> impl FnMut(&Path) -> Result<()> {
+        move |path| {
+            let content = std::fs::read_to_string(path)?;
+            assert!(content.starts_with(HEADER));
+            std::fs::write(path, content.replace(from, to))?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_edited_rules_are_validated() {
+        let mut out = Vec::new();
+        let mut edits = vec![
+            replacing("severity: warning", "severity: urgent"),
+            replacing("severity: urgent", "severity: major"),
+        ]
+        .into_iter();
+
+        let edited =
+            edit_rule(&rule(), &mut "\n".as_bytes(), &mut out, |path| edits.next().unwrap()(path))
+                .unwrap()
+                .unwrap();
+
+        assert_eq!(edited.severity, "major");
+        assert_eq!(edited.pattern, r"print\(");
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(
+            "❌ Invalid severity 'urgent': expected blocker, critical, major, warning or info"
+        ));
+    }
+
+    #[test]
+    fn test_unchanged_and_abandoned_edits() {
+        let mut out = Vec::new();
+        let unchanged = edit_rule(&rule(), &mut "".as_bytes(), &mut out, |_| Ok(())).unwrap();
+        assert!(unchanged.is_none());
+        assert!(String::from_utf8(out).unwrap().contains("💤 No changes to no_print"));
+
+        let mut out = Vec::new();
+        let broken = replacing(r"print\(", "print(");
+        let abandoned = edit_rule(&rule(), &mut "n\n".as_bytes(), &mut out, broken).unwrap();
+        assert!(abandoned.is_none());
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("❌ Invalid pattern 'print('"));
+        assert!(out.contains("🚫 Rule not changed"));
+    }
+}
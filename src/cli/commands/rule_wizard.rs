@@ -0,0 +1,328 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use regex::Regex;
+use std::io::{BufRead, Write};
+
+use crate::core::{CodeExample, CustomRule, Severity};
+
+/// Fix suggestion of a rule added without one
+pub const DEFAULT_FIX: &str = "Review and fix according to team guidelines";
+
+/// What `rules --add` was given on the command line; the wizard offers these as defaults
+#[derive(Debug, Clone, Default)]
+pub struct RuleDraft {
+    pub description: Option<String>,
+    pub pattern: Option<String>,
+    pub severity: Option<Severity>,
+    pub fix: Option<String>,
+}
+
+/// Rule id derived from its description, e.g. `use_team_logger` for "Use team logger"
+pub fn rule_id(description: &str) -> String {
+    description
+        .to_lowercase()
+        .replace(' ', "_")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_')
+        .collect()
+}
+
+/// Compile a rule pattern the way reviews do, naming the pattern when it is invalid
+pub fn compile_pattern(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).with_context(|| format!("Invalid pattern '{pattern}'"))
+}
+
+/// A project rule with the given parts
+pub fn new_rule(
+    description: String,
+    pattern: String,
+    severity: Severity,
+    fix: String,
+    examples: Vec<CodeExample>,
+) -> CustomRule {
+    CustomRule {
+        id: rule_id(&description),
+        description,
+        pattern,
+        severity: severity.to_string(),
+        fix,
+        ai_hint: None,
+        auto_fix: None,
+        skip_comments: false,
+        skip_strings: false,
+        enabled: true,
+        file_scope: None,
+        examples,
+    }
+}
+
+/// Ask on `input` for each part of a rule the draft leaves open, previewing the pattern on
+/// sample lines before it is accepted. `None` when the rule is not saved or input ends.
+pub fn run_wizard(
+    draft: RuleDraft,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<Option<CustomRule>> {
+    let mut prompt = Prompt { input, out };
+    let rule = ask_rule(&mut prompt, draft)?;
+    if rule.is_none() {
+        writeln!(prompt.out, "🚫 Rule not added")?;
+    }
+    Ok(rule)
+}
+
+fn ask_rule(prompt: &mut Prompt, draft: RuleDraft) -> Result<Option<CustomRule>> {
+    let description = match draft.description {
+        Some(description) => description,
+        None => match prompt.ask_required("Description", None)? {
+            Some(description) => description,
+            None => return Ok(None),
+        },
+    };
+
+    let Some((pattern, regex)) = ask_pattern(prompt, draft.pattern)? else {
+        return Ok(None);
+    };
+
+    let default_severity = draft.severity.unwrap_or(Severity::Warning).to_string();
+    let severity = loop {
+        let Some(answer) = prompt
+            .ask("Severity (blocker, critical, major, warning, info)", Some(&default_severity))?
+        else {
+            return Ok(None);
+        };
+        match Severity::from_str(&answer, true) {
+            Ok(severity) => break severity,
+            Err(_) => {
+                writeln!(prompt.out, "❌ Expected blocker, critical, major, warning or info")?
+            }
+        }
+    };
+
+    let default_fix = draft.fix.unwrap_or_else(|| DEFAULT_FIX.to_string());
+    let Some(fix) = prompt.ask("Fix suggestion", Some(&default_fix))? else {
+        return Ok(None);
+    };
+
+    let Some(examples) = ask_example(prompt, &regex, &description)? else {
+        return Ok(None);
+    };
+
+    let rule = new_rule(description, pattern, severity, fix, examples);
+    writeln!(prompt.out, "\n📋 {} ({})", rule.id, rule.severity)?;
+    writeln!(prompt.out, "   Pattern: {}", rule.pattern)?;
+    writeln!(prompt.out, "   Fix: {}", rule.fix)?;
+    for example in &rule.examples {
+        writeln!(prompt.out, "   Bad:  {}", example.bad)?;
+        writeln!(prompt.out, "   Good: {}", example.good)?;
+    }
+    match prompt.confirm("Save this rule?")? {
+        true => Ok(Some(rule)),
+        false => Ok(None),
+    }
+}
+
+/// Ask for a pattern until one compiles and is kept after trying it on sample lines
+fn ask_pattern(prompt: &mut Prompt, default: Option<String>) -> Result<Option<(String, Regex)>> {
+    let mut default = default;
+    loop {
+        let Some(pattern) = prompt.ask_required("Pattern (regex)", default.as_deref())? else {
+            return Ok(None);
+        };
+        let regex = match compile_pattern(&pattern) {
+            Ok(regex) => regex,
+            Err(e) => {
+                writeln!(prompt.out, "❌ {e:#}")?;
+                continue;
+            }
+        };
+
+        writeln!(prompt.out, "🧪 Try the pattern on sample lines; an empty line finishes")?;
+        loop {
+            write!(prompt.out, "   > ")?;
+            prompt.out.flush()?;
+            let Some(line) = prompt.read_line()? else {
+                return Ok(None);
+            };
+            if line.is_empty() {
+                break;
+            }
+            writeln!(prompt.out, "   {}", preview(&regex, &line))?;
+        }
+
+        if prompt.confirm("Keep this pattern?")? {
+            return Ok(Some((pattern, regex)));
+        }
+        default = Some(pattern);
+    }
+}
+
+/// Ask for one bad/good example pair, warning when the pattern misses the bad code or
+/// flags the good code; no examples when the bad code is left empty
+fn ask_example(
+    prompt: &mut Prompt,
+    regex: &Regex,
+    description: &str,
+) -> Result<Option<Vec<CodeExample>>> {
+    let Some(bad) = prompt.ask("Bad example (empty to skip)", None)? else {
+        return Ok(None);
+    };
+    if bad.is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+    if !regex.is_match(&bad) {
+        writeln!(prompt.out, "⚠️  The pattern does not match the bad example")?;
+    }
+
+    let Some(good) = prompt.ask_required("Good example", None)? else {
+        return Ok(None);
+    };
+    if regex.is_match(&good) {
+        writeln!(prompt.out, "⚠️  The pattern also matches the good example")?;
+    }
+
+    let Some(explanation) = prompt.ask("Why", Some(description))? else {
+        return Ok(None);
+    };
+    Ok(Some(vec![CodeExample { bad, good, explanation }]))
+}
+
+/// How the pattern treats a sample line, e.g. `✅ match at column 5: IO.puts`
+fn preview(regex: &Regex, line: &str) -> String {
+    match regex.find(line) {
+        Some(found) => format!("✅ match at column {}: {}", found.start() + 1, found.as_str()),
+        None => "⚪ no match".to_string(),
+    }
+}
+
+struct Prompt<'a> {
+    input: &'a mut dyn BufRead,
+    out: &'a mut dyn Write,
+}
+
+impl Prompt<'_> {
+    /// One line of input without its line ending; `None` once input ends
+    fn read_line(&mut self) -> Result<Option<String>> {
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+    }
+
+    /// The trimmed answer to `question`, or `default` for an empty one
+    fn ask(&mut self, question: &str, default: Option<&str>) -> Result<Option<String>> {
+        match default {
+            Some(default) => write!(self.out, "❓ {question} [{default}]: ")?,
+            None => write!(self.out, "❓ {question}: ")?,
+        }
+        self.out.flush()?;
+
+        let Some(answer) = self.read_line()? else {
+            return Ok(None);
+        };
+        let answer = answer.trim();
+        Ok(Some(match (answer.is_empty(), default) {
+            (true, Some(default)) => default.to_string(),
+            _ => answer.to_string(),
+        }))
+    }
+
+    /// Like `ask`, asking again until the answer is not empty
+    fn ask_required(&mut self, question: &str, default: Option<&str>) -> Result<Option<String>> {
+        loop {
+            match self.ask(question, default)? {
+                Some(answer) if answer.is_empty() => {
+                    writeln!(self.out, "❌ An answer is required")?;
+                }
+                answer => return Ok(answer),
+            }
+        }
+    }
+
+    /// Yes unless the answer starts with `n`; no once input ends
+    fn confirm(&mut self, question: &str) -> Result<bool> {
+        write!(self.out, "❓ {question} [Y/n]: ")?;
+        self.out.flush()?;
+        Ok(match self.read_line()? {
+            Some(answer) => !answer.trim().to_lowercase().starts_with('n'),
+            None => false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod rule_wizard_tests {
+    use super::*;
+
+    fn run(draft: RuleDraft, script: &str) -> (Option<CustomRule>, String) {
+        let mut input = script.as_bytes();
+        let mut out = Vec::new();
+        let rule = run_wizard(draft, &mut input, &mut out).unwrap();
+        (rule, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_wizard_builds_a_rule() {
+        let draft =
+            RuleDraft { description: Some("Avoid IO.puts".to_string()), ..Default::default() };
+        let script = [
+            "IO.puts(",          // invalid regex, asked again
+            r"IO\.puts",         // pattern
+            "  IO.puts(\"hi\")", // sample lines
+            "Logger.info(\"hi\")",
+            "",
+            "y",                       // keep the pattern
+            "urgent",                  // invalid severity, asked again
+            "major",                   // severity
+            "Use Logger",              // fix
+            "IO.puts(\"debug\")",      // bad example
+            "Logger.debug(\"debug\")", // good example
+            "",                        // why: the description
+            "",                        // save
+        ]
+        .map(|line| format!("{line}\n"))
+        .concat();
+
+        let (rule, out) = run(draft, &script);
+
+        let rule = rule.unwrap();
+        assert_eq!(rule.id, "avoid_ioputs");
+        assert_eq!(rule.pattern, r"IO\.puts");
+        assert_eq!(rule.severity, "major");
+        assert_eq!(rule.fix, "Use Logger");
+        assert_eq!(rule.examples.len(), 1);
+        assert_eq!(rule.examples[0].explanation, "Avoid IO.puts");
+        assert!(out.contains("❌ Invalid pattern 'IO.puts('"));
+        assert!(out.contains("✅ match at column 3: IO.puts"));
+        assert!(out.contains("⚪ no match"));
+        assert!(out.contains("❌ Expected blocker, critical, major, warning or info"));
+        assert!(!out.contains("⚠️"));
+    }
+
+    #[test]
+    fn test_wizard_keeps_command_line_defaults_and_can_be_cancelled() {
+        let draft = RuleDraft {
+            description: Some("No dbg".to_string()),
+            pattern: Some(r"dbg!\(".to_string()),
+            severity: Some(Severity::Critical),
+            fix: Some("Remove it".to_string()),
+        };
+
+        let (rule, out) = run(draft.clone(), &"\n".repeat(7));
+        let rule = rule.unwrap();
+        assert_eq!((rule.pattern.as_str(), rule.severity.as_str()), (r"dbg!\(", "critical"));
+        assert_eq!(rule.fix, "Remove it");
+        assert!(rule.examples.is_empty());
+        assert!(out.contains("❓ Pattern (regex) [dbg!\\(]: "));
+
+        // A rejected pattern is offered again, and input ending stops the wizard
+        let (rule, out) = run(draft.clone(), "\n\nn\n\nfoo\n");
+        assert!(rule.is_none());
+        assert!(out.contains("🚫 Rule not added"));
+
+        let (rule, out) = run(draft, "\n\n\n\n\nprintln!(x)\nx\n\nn\n");
+        assert!(rule.is_none());
+        assert!(out.contains("⚠️  The pattern does not match the bad example"));
+    }
+}
@@ -1,9 +1,30 @@
+use super::review::build_review_engine;
+use super::rule_editor;
+use super::rule_wizard::{self, RuleDraft};
+use crate::config::resolve::ResolvedConfig;
+use crate::config::{find_project_config, Config};
+use crate::core::baseline::{Baseline, BASELINE_FILE};
+use crate::core::custom_rules::REPO_RULES_FILE;
+use crate::core::languages::{declared_languages, LanguageDefinition};
+use crate::core::provenance::RuleSource;
+use crate::core::provenance::RuleSources;
 use crate::core::registry::PatternRegistry;
-use crate::core::{CustomRule, CustomRulesManager, Language, ProjectDetector, Severity};
-use anyhow::Result;
-use clap::Args;
-use std::collections::HashMap;
+use crate::core::rule_conflicts::{ConflictPolicy, RuleConflicts};
+use crate::core::rule_doctor::{
+    find_bad_references, find_id_collisions, find_never_matching, find_team_shadowing,
+    settings_references, suppression_references, RuleProblem, RuleReference,
+};
+use crate::core::rule_packs;
+use crate::core::stale_rules::find_stale_rules;
+use crate::core::suppression::{Suppression, SuppressionScope};
+use crate::core::{AntiPattern, CustomRulesManager, Language, ProjectDetector, Severity};
+use crate::git::GitIntegration;
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::path::{Path, PathBuf};
 
 #[derive(Args)]
 pub struct RulesArgs {
@@ -35,6 +56,26 @@ pub struct RulesArgs {
     #[arg(long)]
     pub sql: bool,
 
+    /// Show only Ruby rules
+    #[arg(long)]
+    pub ruby: bool,
+
+    /// Show only PHP rules
+    #[arg(long)]
+    pub php: bool,
+
+    /// Show only Java rules
+    #[arg(long)]
+    pub java: bool,
+
+    /// Show only Kotlin rules
+    #[arg(long)]
+    pub kotlin: bool,
+
+    /// Show only rules for this language, including languages declared in config
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<Language>,
+
     /// Show only global built-in rules
     #[arg(long)]
     pub global: bool,
@@ -51,6 +92,11 @@ pub struct RulesArgs {
     #[arg(long, value_name = "QUERY")]
     pub search: Option<String>,
 
+    /// Show where each rule comes from: a built-in or installed pack, the user rules file
+    /// or the repository's .patingin/rules.yml
+    #[arg(long)]
+    pub source: bool,
+
     /// Show detailed rule explanation with examples
     #[arg(long, value_name = "RULE_ID")]
     pub detail: Option<String>,
@@ -59,25 +105,151 @@ pub struct RulesArgs {
     #[arg(long)]
     pub add: bool,
 
+    /// Ask for the added rule's pattern, severity, fix and an example, previewing the
+    /// pattern on sample lines (with --add)
+    #[arg(long, requires = "add")]
+    pub interactive: bool,
+
+    /// Regex the added rule matches changed lines with (with --add)
+    #[arg(long, value_name = "REGEX", requires = "add")]
+    pub pattern: Option<String>,
+
+    /// Severity of the added rule (with --add; default: warning)
+    #[arg(long, value_name = "SEVERITY", requires = "add")]
+    pub severity: Option<Severity>,
+
+    /// Fix suggestion of the added rule (with --add)
+    #[arg(long, value_name = "TEXT", requires = "add")]
+    pub fix: Option<String>,
+
     /// Remove specific project rule
     #[arg(long, value_name = "RULE_ID")]
     pub remove: Option<String>,
 
-    /// Edit existing project rule
+    /// Edit a project rule in $EDITOR; saved once its pattern and severity are valid
     #[arg(long, value_name = "RULE_ID")]
     pub edit: Option<String>,
 
+    /// Disable a built-in or project rule for the current project (with --project)
+    #[arg(long, value_name = "RULE_ID", requires = "project")]
+    pub disable: Option<String>,
+
+    /// Re-enable a rule for the current project (with --project)
+    #[arg(long, value_name = "RULE_ID", requires = "project")]
+    pub enable: Option<String>,
+
+    /// Change a rule's severity for the current project, e.g. `leftover_debugger=critical`
+    /// (with --project)
+    #[arg(long, value_name = "RULE_ID=SEVERITY", requires = "project")]
+    pub set_severity: Option<String>,
+
+    /// Drop the current project's overrides of a rule (with --project)
+    #[arg(long, value_name = "RULE_ID", requires = "project")]
+    pub reset: Option<String>,
+
+    /// Check the project's rules for ids already taken by a built-in or earlier project
+    /// rule, and show how the `rule_conflicts:` policy resolves them
+    #[arg(long)]
+    pub lint: bool,
+
     /// Rule description when adding
     #[arg(value_name = "DESCRIPTION")]
     pub description: Option<String>,
+
+    #[command(subcommand)]
+    pub action: Option<RulesAction>,
+}
+
+#[derive(Subcommand)]
+pub enum RulesAction {
+    /// Remove project rules that matched nothing in recent history or target files the
+    /// project no longer has, and the rules of projects whose checkout is gone
+    Prune {
+        /// Only list the stale rules; leave the rules file alone
+        #[arg(long)]
+        dry_run: bool,
+
+        /// How many recent commits to look for matches in
+        #[arg(long, value_name = "COMMITS", default_value_t = 500)]
+        history: usize,
+    },
+    /// Check the rules a review loads for duplicate ids, rules shadowed by another source,
+    /// patterns that can never match, and disabled or unknown rules still named by settings,
+    /// the baseline or suppression comments
+    Doctor,
+    /// Install a rule pack from a URL or a file under ~/.config/patingin/packs/, loaded
+    /// with the built-in rules from then on
+    Import {
+        /// URL or path of a YAML rule pack
+        #[arg(value_name = "URL|PATH")]
+        source: String,
+
+        /// Name to install the pack under (default: its file name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Dump the rules a review of this project enforces, after packs, custom rules and
+    /// overrides are merged, so they can be audited and versioned
+    Export {
+        /// Format to write the rules in
+        #[arg(long, value_enum, default_value_t = ExportFormat::Yaml)]
+        format: ExportFormat,
+
+        /// Only export rules for this language
+        #[arg(long, value_name = "LANG")]
+        language: Option<Language>,
+
+        /// Only export the project's custom rules: the user's and the team's
+        #[arg(long)]
+        project: bool,
+
+        /// Write the rules to this file instead of stdout
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Yaml,
+    Json,
 }
 
 pub async fn run(args: RulesArgs) -> Result<()> {
     use crate::core::registry::PatternRegistry;
 
+    match args.action {
+        Some(RulesAction::Prune { dry_run, history }) => return prune_rules(dry_run, history),
+        Some(RulesAction::Doctor) => return rules_doctor(),
+        Some(RulesAction::Import { source, name }) => return import_pack(&source, name).await,
+        Some(RulesAction::Export { format, language, project, output }) => {
+            return export_rules(format, language.as_ref(), project, output.as_deref())
+        }
+        None => {}
+    }
+
     let mut registry = PatternRegistry::new();
     registry.load_built_in_patterns()?;
 
+    if args.lint {
+        return lint_rules(registry);
+    }
+
+    if let Some(change) = OverrideChange::from_args(&args)? {
+        return handle_override(&registry, change);
+    }
+
+    // Show rules as this project runs them, unless the built-in catalogue was asked for.
+    // Settings may name project rules, which are not loaded here, so `review` reports the
+    // unknown ids instead.
+    if !args.global {
+        if let Ok(project_info) = ProjectDetector::detect_project(None) {
+            let overrides = CustomRulesManager::new().project_overrides(&project_info.name)?;
+            registry.apply_rule_settings(&overrides);
+        }
+        registry.apply_rule_settings(&Config::load_for_project()?.rules);
+    }
+
     // Handle specific rule detail view first
     if let Some(rule_id) = &args.detail {
         return show_rule_detail(&registry, rule_id);
@@ -104,23 +276,24 @@ pub async fn run(args: RulesArgs) -> Result<()> {
         let project_info = ProjectDetector::detect_project(None)?;
         let project_name = project_info.name.clone();
 
-        // For --project flag, only show custom rules
-        let manager = CustomRulesManager::new();
-        let custom_patterns = manager.get_project_rules(&project_name)?;
+        // For --project flag, only show custom rules: the user's and the team's, merged the
+        // way a review merges them
+        let mut custom_registry = PatternRegistry::new();
+        custom_registry.load_custom_rules_with_policy(
+            &project_name,
+            Some(&project_info.root_path),
+            ConflictPolicy::default(),
+        )?;
 
-        if custom_patterns.is_empty() {
+        if custom_registry.all_patterns().is_empty() {
             println!("📋 No custom rules found for project '{project_name}'");
             println!("💡 Add custom rules with: patingin rules --add --project --<language> \"rule description\"");
+            println!("💡 Or share rules with your team in {REPO_RULES_FILE}");
             return Ok(());
         }
 
-        let mut custom_registry = PatternRegistry::new();
-        for pattern in custom_patterns {
-            custom_registry.add_pattern(pattern);
-        }
-
         // Show only custom rules
-        return show_custom_rules(&custom_registry, &project_name, &target_languages);
+        return show_custom_rules(&custom_registry, &project_name, &target_languages, args.source);
     }
 
     // Get rules based on filters
@@ -132,33 +305,287 @@ pub async fn run(args: RulesArgs) -> Result<()> {
     };
 
     // Show organized rule listing
-    show_organized_rules(&all_rules, &target_languages, &args)
+    let sources = args.source.then(|| registry.rule_sources());
+    show_organized_rules(&all_rules, &target_languages, sources, &args)
 }
 
-fn determine_target_languages(args: &RulesArgs) -> Result<Vec<Language>> {
-    let mut languages = Vec::new();
+/// Load the project's rules over the built-ins the way a review does and report id
+/// conflicts; exits with 1 when the `error` policy refuses them
+fn lint_rules(mut registry: PatternRegistry) -> Result<()> {
+    let project_info = ProjectDetector::detect_project(None)?;
+    let policy = match find_project_config(&project_info.root_path) {
+        Some(config_path) => ConflictPolicy::load(&config_path)?,
+        None => ConflictPolicy::default(),
+    };
+
+    println!("🔍 Linting rules for project '{}' (rule_conflicts: {policy})", project_info.name);
+    if let Err(e) = registry.load_custom_rules_with_policy(
+        &project_info.name,
+        Some(&project_info.root_path),
+        policy,
+    ) {
+        let Some(RuleConflicts(conflicts)) = e.downcast_ref::<RuleConflicts>() else {
+            return Err(e);
+        };
+        for conflict in conflicts {
+            println!("❌ {conflict}");
+        }
+        println!(
+            "💡 Rename the rules, or set `rule_conflicts:` in patingin.yml to prefer-custom, \
+             prefer-builtin or rename"
+        );
+        std::process::exit(1);
+    }
+
+    if registry.conflicts().is_empty() {
+        println!("✅ No rule id conflicts");
+        return Ok(());
+    }
+    for conflict in registry.conflicts() {
+        println!("🔀 {conflict}");
+    }
+    println!("⚠️  {} rule id conflict(s) resolved by the policy", registry.conflicts().len());
+    Ok(())
+}
+
+/// Find the current project's dead rules and projects whose checkout is gone, and remove
+/// them from the user rules file unless `dry_run`
+fn prune_rules(dry_run: bool, history: usize) -> Result<()> {
+    let project_info = ProjectDetector::detect_project(None)?;
+    let manager = CustomRulesManager::new();
+    let rules = manager.get_project_rules(&project_info.name)?;
+
+    let git = GitIntegration::new(&project_info.root_path)?;
+    let files = git.tracked_files()?;
+    // A repository without commits has no history to sample
+    let sample = git.added_lines(history).unwrap_or_default();
+    println!(
+        "🧹 Checking {} custom rule(s) of project '{}' against the last {} commit(s)",
+        rules.len(),
+        project_info.name,
+        sample.commits
+    );
+
+    let mut stale = find_stale_rules(&rules, &files, &sample);
+    stale.sort_by_key(|rule| (rule.language.to_string(), rule.rule_id.clone()));
+    let missing = manager.projects_with_missing_paths()?;
+    if stale.is_empty() && missing.is_empty() {
+        println!("✅ No stale rules found");
+        return Ok(());
+    }
+
+    for rule in &stale {
+        println!("🗑️  {} ({}): {}", rule.rule_id, rule.language, rule.reason);
+    }
+    for project in &missing {
+        println!("🗑️  Project '{project}': its checkout no longer exists");
+    }
+
+    if dry_run {
+        println!("💡 Run `patingin rules prune` to remove them");
+        return Ok(());
+    }
 
-    // Check individual language flags
-    if args.elixir {
-        languages.push(Language::Elixir);
+    for rule in &stale {
+        manager.remove_project_rule(&project_info.name, &rule.rule_id)?;
     }
-    if args.javascript {
-        languages.push(Language::JavaScript);
+    for project in &missing {
+        manager.remove_project(project)?;
+    }
+    println!(
+        "✅ Removed {} rule(s) and {} project(s) from {}",
+        stale.len(),
+        missing.len(),
+        manager.config_path().display()
+    );
+    Ok(())
+}
+
+/// Load the project's rules the way a review does, and report every problem `rule_doctor`
+/// finds with them; exits with 1 when there is one
+fn rules_doctor() -> Result<()> {
+    let project_info = ProjectDetector::detect_project(None)?;
+    let root = &project_info.root_path;
+    let policy = match find_project_config(root) {
+        Some(config_path) => ConflictPolicy::load(&config_path)?,
+        None => ConflictPolicy::default(),
+    };
+
+    let mut registry = PatternRegistry::new();
+    registry.load_built_in_patterns()?;
+    let mut conflicts = Vec::new();
+    if let Err(e) = registry.load_custom_rules_with_policy(&project_info.name, Some(root), policy) {
+        let Some(RuleConflicts(refused)) = e.downcast_ref::<RuleConflicts>() else {
+            return Err(e);
+        };
+        conflicts.extend(refused.iter().cloned());
+    }
+    conflicts.extend(registry.conflicts().iter().cloned());
+
+    // Settings apply the way a review applies them: the user's overrides were applied with
+    // the custom rules, then global config and the project's on top
+    let manager = CustomRulesManager::new();
+    let mut references = settings_references(
+        &manager.project_overrides(&project_info.name)?,
+        &manager.config_path().display().to_string(),
+    );
+    for layer in ResolvedConfig::load(root)?.layers().iter().rev() {
+        let Some(path) = &layer.path else { continue };
+        registry.apply_rule_settings(&layer.config.rules);
+        references.extend(settings_references(&layer.config.rules, &path.display().to_string()));
+    }
+    for entry in Baseline::load(&root.join(BASELINE_FILE))?.violations {
+        references.push(RuleReference {
+            rule_id: entry.rule,
+            by: BASELINE_FILE.to_string(),
+            disables: false,
+        });
+    }
+    for path in GitIntegration::new(root)?.tracked_files()? {
+        if let Ok(content) = std::fs::read_to_string(root.join(&path)) {
+            references.extend(suppression_references(&path, &content));
+        }
+    }
+
+    let user_source = RuleSource::Project { project: project_info.name.clone() };
+    let team_source = RuleSource::Repository { path: REPO_RULES_FILE.to_string() };
+    let mut problems = find_id_collisions(registry.shadowed(), &conflicts);
+    problems.extend(find_team_shadowing(
+        &manager.get_project_rules(&project_info.name)?,
+        &CustomRulesManager::get_repo_rules(root)?,
+        &user_source,
+        &team_source,
+    ));
+    problems.extend(find_never_matching(&registry));
+    problems.extend(find_bad_references(&registry, &references));
+
+    println!(
+        "🩺 Checking {} rule(s) of project '{}'",
+        registry.all_patterns().len(),
+        project_info.name
+    );
+    if problems.is_empty() {
+        println!("✅ No rule problems found");
+        return Ok(());
     }
-    if args.typescript {
-        languages.push(Language::TypeScript);
+
+    let mut sections: Vec<(&str, Vec<&RuleProblem>)> = Vec::new();
+    for problem in &problems {
+        let heading = problem.kind.heading();
+        match sections.iter_mut().find(|(existing, _)| *existing == heading) {
+            Some((_, section)) => section.push(problem),
+            None => sections.push((heading, vec![problem])),
+        }
     }
-    if args.python {
-        languages.push(Language::Python);
+    for (heading, section) in sections {
+        println!();
+        println!("{heading}");
+        for problem in section {
+            println!("   {problem}");
+        }
     }
-    if args.rust {
-        languages.push(Language::Rust);
+    println!();
+    println!("⚠️  {} rule problem(s) found", problems.len());
+    std::process::exit(1);
+}
+
+/// A rule as exported, with where it was loaded from
+#[derive(Serialize)]
+struct ExportedRule<'a> {
+    #[serde(flatten)]
+    rule: &'a AntiPattern,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<&'a RuleSource>,
+}
+
+#[derive(Serialize)]
+struct RulesExport<'a> {
+    patingin_version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    rules: Vec<ExportedRule<'a>>,
+}
+
+/// Write the rules a review loads, disabled ones included with `enabled: false`, ordered by
+/// id so exports diff cleanly between versions
+fn export_rules(
+    format: ExportFormat,
+    language: Option<&Language>,
+    project_only: bool,
+    output: Option<&Path>,
+) -> Result<()> {
+    let project = ProjectDetector::detect_project(None).ok().map(|info| info.name);
+    if project_only && project.is_none() {
+        return Err(anyhow::anyhow!("--project needs to be run inside a project"));
     }
-    if args.zig {
-        languages.push(Language::Zig);
+    let review_engine = build_review_engine()?;
+    let rules = exported_rules(review_engine.registry(), language, project_only);
+
+    let count = rules.len();
+    let export = RulesExport { patingin_version: env!("CARGO_PKG_VERSION"), project, rules };
+    let content = match format {
+        ExportFormat::Yaml => serde_yaml::to_string(&export)?,
+        ExportFormat::Json => serde_json::to_string_pretty(&export)? + "\n",
+    };
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("📤 Exported {count} rule(s) to {}", path.display());
+        }
+        None => print!("{content}"),
     }
-    if args.sql {
-        languages.push(Language::Sql);
+    Ok(())
+}
+
+/// The registry's rules for `language`, or only the custom ones with `project_only`
+fn exported_rules<'a>(
+    registry: &'a PatternRegistry,
+    language: Option<&Language>,
+    project_only: bool,
+) -> Vec<ExportedRule<'a>> {
+    let sources = registry.rule_sources();
+    registry
+        .all_patterns()
+        .into_iter()
+        .filter(|rule| language.map_or(true, |language| rule.language == *language))
+        .filter(|rule| {
+            !project_only
+                || matches!(
+                    sources.get(rule),
+                    Some(RuleSource::Project { .. } | RuleSource::Repository { .. })
+                )
+        })
+        .map(|rule| ExportedRule { rule, source: sources.get(rule) })
+        .collect()
+}
+
+async fn import_pack(source: &str, name: Option<String>) -> Result<()> {
+    let name = match name {
+        Some(name) => {
+            rule_packs::validate_pack_name(&name)?;
+            name
+        }
+        None => rule_packs::pack_name(source)?,
+    };
+
+    println!("📥 Importing rule pack '{name}' from {source}");
+    let content = rule_packs::fetch_pack(source).await?;
+    let rule_count = rule_packs::validate_pack(&content)
+        .with_context(|| format!("{source} is not a valid rule pack"))?;
+    let (path, replaced) =
+        rule_packs::install_pack(&rule_packs::packs_dir(), &name, source, &content)?;
+
+    let action = if replaced { "Updated" } else { "Installed" };
+    println!("✅ {action} {rule_count} rule(s) in {}", path.display());
+    println!("💡 Reviews load the pack with the built-in rules; delete the file to remove it");
+    Ok(())
+}
+
+fn determine_target_languages(args: &RulesArgs) -> Result<Vec<Language>> {
+    let mut languages = flagged_languages(args);
+    if let Some(language) = &args.language {
+        languages.push(language.clone());
     }
 
     // If specific languages requested, return them
@@ -168,15 +595,7 @@ fn determine_target_languages(args: &RulesArgs) -> Result<Vec<Language>> {
 
     // If showing global, project, or all-projects, show all languages
     if args.global || args.project || args.all_projects || args.search.is_some() {
-        return Ok(vec![
-            Language::Elixir,
-            Language::JavaScript,
-            Language::TypeScript,
-            Language::Python,
-            Language::Rust,
-            Language::Zig,
-            Language::Sql,
-        ]);
+        return Ok(all_languages());
     }
 
     // Default: detect project languages using ProjectDetector
@@ -185,54 +604,58 @@ fn determine_target_languages(args: &RulesArgs) -> Result<Vec<Language>> {
         Ok(project_info) => {
             if project_info.languages.is_empty() {
                 // No languages detected, show all
-                Ok(vec![
-                    Language::Elixir,
-                    Language::JavaScript,
-                    Language::TypeScript,
-                    Language::Python,
-                    Language::Rust,
-                    Language::Zig,
-                    Language::Sql,
-                ])
+                Ok(all_languages())
             } else {
-                Ok(project_info.languages)
+                Ok(project_info.languages.into_iter().chain([Language::repository()]).collect())
             }
         }
         Err(_) => {
             // Fallback to all languages if detection fails
-            Ok(vec![
-                Language::Elixir,
-                Language::JavaScript,
-                Language::TypeScript,
-                Language::Python,
-                Language::Rust,
-                Language::Zig,
-                Language::Sql,
-            ])
+            Ok(all_languages())
         }
     }
 }
 
+/// Built-in languages followed by those declared in config and the repository rules
+fn all_languages() -> Vec<Language> {
+    Language::BUILT_IN
+        .into_iter()
+        .chain(declared_languages().iter().map(LanguageDefinition::language))
+        .chain([Language::repository()])
+        .collect()
+}
+
 fn show_rule_detail(
     registry: &crate::core::registry::PatternRegistry,
     rule_id: &str,
 ) -> Result<()> {
     use colored::*;
 
-    if let Some(rule) = registry.get_pattern(rule_id) {
+    // A rule declared for several languages is shown once, with each variant's specifics
+    let variants = registry.get_variants(rule_id);
+    let labelled = |label: &str, variant: &crate::core::AntiPattern| match variants.len() {
+        1 => label.to_string(),
+        _ => format!("{label} ({})", variant.language),
+    };
+
+    if let Some(rule) = variants.first() {
         println!("Rule: {}", rule.name.bold());
         println!("ID: {}", rule.id);
-        println!("Language: {}", rule.language);
-        println!(
-            "Severity: {}",
-            match rule.severity {
-                crate::core::Severity::Critical => "CRITICAL".red(),
-                crate::core::Severity::Major => "MAJOR".yellow(),
-                crate::core::Severity::Warning => "WARNING".blue(),
-            }
-        );
+        if variants.len() > 1 {
+            let languages: Vec<String> = variants.iter().map(|v| v.language.to_string()).collect();
+            println!("Languages: {}", languages.join(", "));
+        } else {
+            println!("Language: {}", rule.language);
+        }
+        println!("Severity: {}", severity_name(rule.severity));
         println!("Description: {}", rule.description);
-        println!("Fix: {}", rule.fix_suggestion);
+        if variants.iter().all(|v| v.fix_suggestion == rule.fix_suggestion) {
+            println!("Fix: {}", rule.fix_suggestion);
+        } else {
+            for variant in &variants {
+                println!("{}: {}", labelled("Fix", variant), variant.fix_suggestion);
+            }
+        }
         if let Some(url) = &rule.source_url {
             println!("Source: {url}");
         }
@@ -240,10 +663,29 @@ fn show_rule_detail(
             "Claude Code Fixable: {}",
             if rule.claude_code_fixable { "Yes".green() } else { "No".red() }
         );
+        if let Some(ai_hint) = &rule.ai_hint {
+            println!("AI Hint: {ai_hint}");
+        }
+        if let Some(auto_fix) = &rule.auto_fix {
+            println!("Simple Fix: {} → {}", auto_fix.search, auto_fix.replace);
+        }
+        for variant in &variants {
+            if let Some(comment) =
+                Suppression::comment_for(&variant.language, &rule.id, SuppressionScope::NextLine)
+            {
+                println!("{}: {}", labelled("Suppress", variant), comment.dimmed());
+            }
+        }
 
-        if !rule.examples.is_empty() {
+        let mut examples: Vec<&crate::core::CodeExample> = Vec::new();
+        for example in variants.iter().flat_map(|v| &v.examples) {
+            if !examples.contains(&example) {
+                examples.push(example);
+            }
+        }
+        if !examples.is_empty() {
             println!("\nExamples:");
-            for example in &rule.examples {
+            for example in examples {
                 println!("  Bad:  {}", example.bad.red());
                 println!("  Good: {}", example.good.green());
                 println!("  Why:  {}", example.explanation);
@@ -258,7 +700,7 @@ fn show_rule_detail(
 fn handle_add_rule(args: &RulesArgs) -> Result<()> {
     if !args.project {
         println!("❌ Error: --project flag is required when adding rules");
-        println!("💡 Example: patingin rules add --project --elixir \"avoid IO.puts in production code\"");
+        println!("💡 Example: patingin rules --add --project --elixir \"avoid IO.puts in production code\" --pattern 'IO\\.puts'");
         return Ok(());
     }
 
@@ -270,55 +712,83 @@ fn handle_add_rule(args: &RulesArgs) -> Result<()> {
     let project_name = project_info.name.clone();
     let project_path = project_info.root_path.to_string_lossy().to_string();
 
-    // Get rule description from args
-    let description = match &args.description {
-        Some(desc) => desc.clone(),
-        None => {
-            println!("❌ Error: Rule description is required");
-            println!("💡 Example: patingin rules add --project --elixir \"avoid IO.puts in production code\"");
-            return Ok(());
-        }
-    };
-
-    // Create interactive prompt for additional rule details
     println!("📋 Adding custom rule to project: {project_name}");
-    println!("🏷️  Language: {language:?}");
-    println!("📝 Description: {description}");
-    println!();
+    println!("🏷️  Language: {language}");
 
-    // For now, create a simple regex pattern based on description
-    // In the future, this could be an interactive prompt
-    let rule_id = description
-        .to_lowercase()
-        .replace(" ", "_")
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '_')
-        .collect::<String>();
-
-    let pattern = description.split_whitespace().last().unwrap_or("TODO").to_string();
-
-    let custom_rule = CustomRule {
-        id: rule_id.clone(),
-        description: description.clone(),
-        pattern,
-        severity: "warning".to_string(), // Default to warning
-        fix: "Review and fix according to team guidelines".to_string(),
-        enabled: true,
+    let draft = RuleDraft {
+        description: args.description.clone(),
+        pattern: args.pattern.clone(),
+        severity: args.severity,
+        fix: args.fix.clone(),
+    };
+    let custom_rule = if args.interactive {
+        let stdin = std::io::stdin();
+        match rule_wizard::run_wizard(draft, &mut stdin.lock(), &mut std::io::stdout())? {
+            Some(rule) => rule,
+            None => return Ok(()),
+        }
+    } else {
+        let (Some(description), Some(pattern)) = (draft.description, draft.pattern) else {
+            println!("❌ Error: A rule description and --pattern are required");
+            println!("💡 Example: patingin rules --add --project --elixir \"avoid IO.puts in production code\" --pattern 'IO\\.puts'");
+            println!("💡 Or let patingin ask for them: patingin rules --add --project --elixir --interactive");
+            return Ok(());
+        };
+        rule_wizard::compile_pattern(&pattern)?;
+        println!("📝 Description: {description}");
+        rule_wizard::new_rule(
+            description,
+            pattern,
+            draft.severity.unwrap_or(Severity::Warning),
+            draft.fix.unwrap_or_else(|| rule_wizard::DEFAULT_FIX.to_string()),
+            Vec::new(),
+        )
     };
 
     // Add rule using CustomRulesManager
     let manager = CustomRulesManager::new();
+    let rule_id = custom_rule.id.clone();
+    let pattern_id = format!("custom_{rule_id}");
+    if manager.get_project_rules(&project_name)?.iter().any(|pattern| pattern.id == pattern_id) {
+        println!("❌ Project '{project_name}' already has a rule '{rule_id}'");
+        println!("💡 Remove it first with: patingin rules --remove {rule_id}");
+        return Ok(());
+    }
     manager.add_project_rule(&project_name, &project_path, language, custom_rule)?;
 
     println!("✅ Successfully added custom rule: {rule_id}");
     println!("📁 Saved to: ~/.config/patingin/rules.yml");
-    println!("💡 You can edit the pattern and settings in the config file");
+    println!("💡 See it with: patingin rules --project");
 
     Ok(())
 }
 
 fn get_language_from_args(args: &RulesArgs) -> Result<Language> {
-    match (
+    let flagged = flagged_languages(args);
+    if let Some(language) = &args.language {
+        if !flagged.is_empty() {
+            anyhow::bail!("Use either --language or a language flag, not both");
+        }
+        return Ok(language.clone());
+    }
+
+    match <[Language; 1]>::try_from(flagged) {
+        Ok([language]) => Ok(language),
+        Err(_) => {
+            let flags: Vec<String> =
+                Language::BUILT_IN.iter().map(|language| format!("--{language}")).collect();
+            anyhow::bail!(
+                "Please specify exactly one language flag ({}) or --language <LANG>",
+                flags.join(", ")
+            );
+        }
+    }
+}
+
+/// Languages selected with the per-language flags (`--elixir`, `--rust`, ...), in
+/// `Language::BUILT_IN` order
+fn flagged_languages(args: &RulesArgs) -> Vec<Language> {
+    let flags = [
         args.elixir,
         args.javascript,
         args.typescript,
@@ -326,18 +796,17 @@ fn get_language_from_args(args: &RulesArgs) -> Result<Language> {
         args.rust,
         args.zig,
         args.sql,
-    ) {
-        (true, false, false, false, false, false, false) => Ok(Language::Elixir),
-        (false, true, false, false, false, false, false) => Ok(Language::JavaScript),
-        (false, false, true, false, false, false, false) => Ok(Language::TypeScript),
-        (false, false, false, true, false, false, false) => Ok(Language::Python),
-        (false, false, false, false, true, false, false) => Ok(Language::Rust),
-        (false, false, false, false, false, true, false) => Ok(Language::Zig),
-        (false, false, false, false, false, false, true) => Ok(Language::Sql),
-        _ => {
-            anyhow::bail!("Please specify exactly one language flag (--elixir, --javascript, --typescript, --python, --rust, --zig, --sql)");
-        }
-    }
+        args.ruby,
+        args.php,
+        args.java,
+        args.kotlin,
+    ];
+    Language::BUILT_IN
+        .into_iter()
+        .zip(flags)
+        .filter(|(_, flag)| *flag)
+        .map(|(language, _)| language)
+        .collect()
 }
 
 fn handle_remove_rule(rule_id: &str) -> Result<()> {
@@ -360,9 +829,134 @@ fn handle_remove_rule(rule_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// A per-project override requested on the command line
+enum OverrideChange {
+    Disable(String),
+    Enable(String),
+    Severity(String, Severity),
+    Reset(String),
+}
+
+impl OverrideChange {
+    fn from_args(args: &RulesArgs) -> Result<Option<Self>> {
+        if let Some(id) = &args.disable {
+            return Ok(Some(Self::Disable(id.clone())));
+        }
+        if let Some(id) = &args.enable {
+            return Ok(Some(Self::Enable(id.clone())));
+        }
+        if let Some(setting) = &args.set_severity {
+            let (id, severity) = setting.split_once('=').ok_or_else(|| {
+                anyhow::anyhow!("--set-severity expects RULE_ID=SEVERITY, got '{setting}'")
+            })?;
+            let severity = Severity::from_str(severity, true).map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid severity '{severity}' for {id}: expected blocker, critical, major, \
+                     warning or info"
+                )
+            })?;
+            return Ok(Some(Self::Severity(id.to_string(), severity)));
+        }
+        Ok(args.reset.clone().map(Self::Reset))
+    }
+
+    fn rule_id(&self) -> &str {
+        match self {
+            Self::Disable(id) | Self::Enable(id) | Self::Severity(id, _) | Self::Reset(id) => id,
+        }
+    }
+}
+
+/// Record a severity or enablement override for the current project in
+/// ~/.config/patingin/rules.yml; reviews of the project apply it when loading rules
+fn handle_override(registry: &PatternRegistry, change: OverrideChange) -> Result<()> {
+    let project_info = ProjectDetector::detect_project(None)?;
+    let project_name = project_info.name.clone();
+    let manager = CustomRulesManager::new();
+
+    let id = change.rule_id().to_string();
+    let is_project_rule = || -> Result<bool> {
+        Ok(manager.get_project_rules(&project_name)?.iter().any(|pattern| pattern.id == id))
+    };
+    if !matches!(change, OverrideChange::Reset(_))
+        && registry.get_variants(&id).is_empty()
+        && !is_project_rule()?
+    {
+        println!("❌ No rule with id '{id}'");
+        println!("💡 Use 'patingin rules --search <keyword>' to find rule ids");
+        return Ok(());
+    }
+
+    let mut changed = true;
+    let project_path = project_info.root_path.display().to_string();
+    manager.update_project_overrides(&project_name, &project_path, |overrides| match &change {
+        OverrideChange::Disable(id) => overrides.disable(id),
+        OverrideChange::Enable(id) => overrides.enable(id),
+        OverrideChange::Severity(id, severity) => {
+            overrides.severity.insert(id.clone(), *severity);
+        }
+        OverrideChange::Reset(id) => changed = overrides.reset(id),
+    })?;
+
+    match change {
+        OverrideChange::Disable(_) => println!("🔕 Disabled {id} for project '{project_name}'"),
+        OverrideChange::Enable(_) => println!("🔔 Enabled {id} for project '{project_name}'"),
+        OverrideChange::Severity(_, severity) => {
+            println!("🎚️  {id} is now {severity} for project '{project_name}'")
+        }
+        OverrideChange::Reset(_) if changed => {
+            println!("↩️  Dropped the overrides of {id} for project '{project_name}'")
+        }
+        OverrideChange::Reset(_) => {
+            println!("💤 Project '{project_name}' has no overrides for {id}")
+        }
+    }
+    println!("📁 Updated: ~/.config/patingin/rules.yml");
+    Ok(())
+}
+
+/// Open the project rule in `$EDITOR` and write it back to ~/.config/patingin/rules.yml once
+/// it is a valid rule. Takes the id as in the rules file or as reviews report it (`custom_<id>`).
 fn handle_edit_rule(rule_id: &str) -> Result<()> {
-    println!("Edit rule '{rule_id}' functionality not yet implemented");
-    // TODO: Implement rule editing in ~/.config/patingin/rules.yml
+    let project_info = ProjectDetector::detect_project(None)?;
+    let project_name = project_info.name.clone();
+    let manager = CustomRulesManager::new();
+
+    let found = match manager.get_project_rule(&project_name, rule_id)? {
+        Some(found) => Some(found),
+        None => match rule_id.strip_prefix("custom_") {
+            Some(id) => manager.get_project_rule(&project_name, id)?,
+            None => None,
+        },
+    };
+    let Some((language, rule)) = found else {
+        println!("❌ Rule '{rule_id}' not found in project '{project_name}'");
+        println!("💡 Use 'patingin rules --project' to see available custom rules");
+        return Ok(());
+    };
+
+    println!("✏️  Editing {} ({language}) of project '{project_name}'", rule.id);
+    let stdin = std::io::stdin();
+    let edited = rule_editor::edit_rule(
+        &rule,
+        &mut stdin.lock(),
+        &mut std::io::stdout(),
+        rule_editor::open_in_editor,
+    )?;
+    let Some(edited) = edited else {
+        return Ok(());
+    };
+
+    if edited.id != rule.id && manager.get_project_rule(&project_name, &edited.id)?.is_some() {
+        println!("❌ Project '{project_name}' already has a rule '{}'", edited.id);
+        println!("💡 Keep the id '{}' or pick another one", rule.id);
+        return Ok(());
+    }
+    let edited_id = edited.id.clone();
+    manager.replace_project_rule(&project_name, &rule.id, edited)?;
+
+    println!("✅ Successfully updated custom rule: {edited_id}");
+    println!("📁 Updated: ~/.config/patingin/rules.yml");
     Ok(())
 }
 
@@ -370,6 +964,7 @@ fn show_custom_rules(
     registry: &PatternRegistry,
     project_name: &str,
     target_languages: &[Language],
+    show_source: bool,
 ) -> Result<()> {
     println!("📋 Custom Rules for Project: {project_name}");
     println!();
@@ -382,31 +977,20 @@ fn show_custom_rules(
             continue;
         }
 
-        let (critical_count, major_count, warning_count) = count_patterns_by_severity(&patterns);
         total_rules += patterns.len();
 
         let (emoji, name) = get_language_display_info(language);
         println!("{} {} ({} rules)", emoji, name, patterns.len());
-        if critical_count > 0 {
-            println!("  🔴 Critical: {critical_count}");
-        }
-        if major_count > 0 {
-            println!("  🟡 Major: {major_count}");
-        }
-        if warning_count > 0 {
-            println!("  🔵 Warning: {warning_count}");
-        }
+        print_severity_counts(&patterns);
 
         // Show all rules
         for pattern in patterns.iter() {
-            let severity_icon = match pattern.severity {
-                Severity::Critical => "🔴",
-                Severity::Major => "🟡",
-                Severity::Warning => "🔵",
-            };
+            let severity_icon = pattern.severity.icon();
             let rule_name = pattern.name.clone();
             let rule_id = pattern.id.strip_prefix("custom_").unwrap_or(&pattern.id);
-            println!("    {severity_icon} {rule_name} ({rule_id})");
+            let disabled = if pattern.enabled { "" } else { " [disabled]" };
+            let source = source_column(show_source.then(|| registry.rule_sources()), pattern);
+            println!("    {severity_icon} {rule_name} ({rule_id}){disabled}{source}");
         }
 
         // Show all rules - no truncation
@@ -417,7 +1001,10 @@ fn show_custom_rules(
     println!();
     println!("💡 Use --detail <rule_id> to see detailed info about a specific rule");
     println!("💡 Use 'remove <rule_id>' to remove a custom rule");
-    println!("💡 Edit ~/.config/patingin/rules.yml to modify rule patterns and settings");
+    println!(
+        "💡 Edit ~/.config/patingin/rules.yml, or {REPO_RULES_FILE} for team rules, to modify \
+         rule patterns and settings"
+    );
 
     Ok(())
 }
@@ -425,6 +1012,7 @@ fn show_custom_rules(
 fn show_organized_rules(
     rules: &[&crate::core::AntiPattern],
     target_languages: &[Language],
+    sources: Option<&RuleSources>,
     args: &RulesArgs,
 ) -> Result<()> {
     use colored::*;
@@ -437,13 +1025,8 @@ fn show_organized_rules(
         && !args.project
         && !args.all_projects
         && args.search.is_none()
-        && !args.elixir
-        && !args.javascript
-        && !args.typescript
-        && !args.python
-        && !args.rust
-        && !args.zig
-        && !args.sql
+        && flagged_languages(args).is_empty()
+        && args.language.is_none()
     {
         if let Some(ref info) = project_info {
             println!("📋 Rules for Your Project\n");
@@ -478,33 +1061,21 @@ fn show_organized_rules(
             }
 
             let (emoji, name) = get_language_display_info(language);
-            let critical_count =
-                lang_rules.iter().filter(|p| p.severity == Severity::Critical).count();
-            let major_count = lang_rules.iter().filter(|p| p.severity == Severity::Major).count();
-            let warning_count =
-                lang_rules.iter().filter(|p| p.severity == Severity::Warning).count();
-
             println!("{} {} ({} rules)", emoji, name.bold(), lang_rules.len());
-
-            if critical_count > 0 {
-                println!("  {} Critical: {}", "🔴".red(), critical_count);
-            }
-            if major_count > 0 {
-                println!("  {} Major: {}", "🟡".yellow(), major_count);
-            }
-            if warning_count > 0 {
-                println!("  {} Warning: {}", "🔵".blue(), warning_count);
-            }
+            print_severity_counts(lang_rules);
 
             // Show all rules for this language
             for rule in lang_rules.iter() {
-                let severity_str = match rule.severity {
-                    Severity::Critical => "CRITICAL".red(),
-                    Severity::Major => "MAJOR".yellow(),
-                    Severity::Warning => "WARNING".blue(),
-                };
-
-                println!("    {} {} ({})", severity_str, rule.name, rule.id.dimmed());
+                let severity_str = severity_name(rule.severity);
+
+                let disabled = if rule.enabled { "" } else { " [disabled]" };
+                let source = source_column(sources, rule);
+                println!(
+                    "    {} {} ({}){disabled}{source}",
+                    severity_str,
+                    rule.name,
+                    rule.id.dimmed()
+                );
             }
 
             // Show all rules - no truncation
@@ -530,15 +1101,52 @@ fn show_organized_rules(
     Ok(())
 }
 
-fn count_patterns_by_severity(patterns: &[&crate::core::AntiPattern]) -> (usize, usize, usize) {
-    let critical_count = patterns.iter().filter(|p| p.severity == Severity::Critical).count();
-    let major_count = patterns.iter().filter(|p| p.severity == Severity::Major).count();
-    let warning_count = patterns.iter().filter(|p| p.severity == Severity::Warning).count();
-    (critical_count, major_count, warning_count)
+/// The `--source` column of a rule listing: where `rule` was loaded from, or nothing when
+/// sources are not shown
+fn source_column(sources: Option<&RuleSources>, rule: &crate::core::AntiPattern) -> String {
+    use colored::*;
+
+    let Some(sources) = sources else {
+        return String::new();
+    };
+    let source = sources.get(rule).map_or_else(|| "unknown source".to_string(), |s| s.to_string());
+    format!("  {}", format!("[{source}]").dimmed())
+}
+
+/// How many of `patterns` have each severity, most severe first; absent levels are left out
+fn count_patterns_by_severity(patterns: &[&crate::core::AntiPattern]) -> BTreeMap<Severity, usize> {
+    let mut counts = BTreeMap::new();
+    for pattern in patterns {
+        *counts.entry(pattern.severity).or_default() += 1;
+    }
+    counts
+}
+
+/// One "  🔴 Critical: 3" line per severity present in `patterns`
+fn print_severity_counts(patterns: &[&crate::core::AntiPattern]) {
+    for (severity, count) in count_patterns_by_severity(patterns) {
+        let name = severity.to_string();
+        let title = name[..1].to_uppercase() + &name[1..];
+        println!("  {} {title}: {count}", severity.icon());
+    }
 }
 
-fn get_language_display_info(language: &Language) -> (&'static str, &'static str) {
-    match language {
+/// Upper-case severity name in the severity's color
+fn severity_name(severity: Severity) -> colored::ColoredString {
+    use colored::*;
+
+    let name = severity.to_string().to_uppercase();
+    match severity {
+        Severity::Blocker => name.red().bold(),
+        Severity::Critical => name.red(),
+        Severity::Major => name.yellow(),
+        Severity::Warning => name.blue(),
+        Severity::Info => name.normal(),
+    }
+}
+
+fn get_language_display_info(language: &Language) -> (&'static str, String) {
+    let (emoji, name) = match language {
         Language::Elixir => ("⚗️", "Elixir"),
         Language::JavaScript => ("📜", "JavaScript"),
         Language::TypeScript => ("🔷", "TypeScript"),
@@ -546,7 +1154,13 @@ fn get_language_display_info(language: &Language) -> (&'static str, &'static str
         Language::Rust => ("🦀", "Rust"),
         Language::Zig => ("⚡", "Zig"),
         Language::Sql => ("🗃️", "SQL"),
-    }
+        Language::Ruby => ("💎", "Ruby"),
+        Language::Php => ("🐘", "PHP"),
+        Language::Java => ("☕", "Java"),
+        Language::Kotlin => ("🟣", "Kotlin"),
+        Language::Other(name) => return ("🧩", name.clone()),
+    };
+    (emoji, name.to_string())
 }
 
 #[cfg(test)]
@@ -562,18 +1176,59 @@ mod rules_command_tests {
             rust: false,
             zig: false,
             sql: false,
+            ruby: false,
+            php: false,
+            java: false,
+            kotlin: false,
+            language: None,
             global: false,
             project: false,
             all_projects: false,
             search: None,
+            source: false,
             detail: None,
             add: false,
+            interactive: false,
+            pattern: None,
+            severity: None,
+            fix: None,
             remove: None,
             edit: None,
+            disable: None,
+            enable: None,
+            set_severity: None,
+            reset: None,
+            lint: false,
             description: None,
+            action: None,
         }
     }
 
+    #[test]
+    fn test_override_flags() {
+        let mut args = create_test_args();
+        assert!(OverrideChange::from_args(&args).unwrap().is_none());
+
+        args.set_severity = Some("leftover_debugger=Critical".to_string());
+        assert!(matches!(
+            OverrideChange::from_args(&args).unwrap(),
+            Some(OverrideChange::Severity(id, Severity::Critical)) if id == "leftover_debugger"
+        ));
+
+        args.set_severity = Some("leftover_debugger".to_string());
+        assert!(OverrideChange::from_args(&args).is_err());
+        args.set_severity = Some("leftover_debugger=urgent".to_string());
+        let error = OverrideChange::from_args(&args).err().unwrap();
+        assert!(error.to_string().contains("expected blocker, critical, major, warning or info"));
+
+        args.set_severity = None;
+        args.disable = Some("console_log_production".to_string());
+        assert_eq!(
+            OverrideChange::from_args(&args).unwrap().unwrap().rule_id(),
+            "console_log_production"
+        );
+    }
+
     #[tokio::test]
     async fn test_get_language_from_args_single_language() {
         let mut args = create_test_args();
@@ -593,6 +1248,7 @@ mod rules_command_tests {
         let result = get_language_from_args(&args);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("exactly one language"));
+        assert_eq!(flagged_languages(&args), [Language::Elixir, Language::JavaScript]);
     }
 
     #[tokio::test]
@@ -601,7 +1257,10 @@ mod rules_command_tests {
 
         let result = get_language_from_args(&args);
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("exactly one language"));
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("exactly one language"));
+        assert!(message.contains("--elixir, --javascript,"));
+        assert!(message.contains("--java, --kotlin)"));
     }
 
     #[tokio::test]
@@ -614,6 +1273,10 @@ mod rules_command_tests {
             ("rust", Language::Rust),
             ("zig", Language::Zig),
             ("sql", Language::Sql),
+            ("ruby", Language::Ruby),
+            ("php", Language::Php),
+            ("java", Language::Java),
+            ("kotlin", Language::Kotlin),
         ] {
             let mut args = create_test_args();
             match flag_name {
@@ -624,6 +1287,10 @@ mod rules_command_tests {
                 "rust" => args.rust = true,
                 "zig" => args.zig = true,
                 "sql" => args.sql = true,
+                "ruby" => args.ruby = true,
+                "php" => args.php = true,
+                "java" => args.java = true,
+                "kotlin" => args.kotlin = true,
                 _ => unreachable!(),
             }
 
@@ -637,7 +1304,7 @@ mod rules_command_tests {
     async fn test_count_patterns_by_severity() {
         use crate::core::{AntiPattern, DetectionMethod, Severity};
 
-        let patterns = vec![
+        let patterns = [
             AntiPattern {
                 id: "critical1".to_string(),
                 name: "Critical Pattern".to_string(),
@@ -650,6 +1317,10 @@ mod rules_command_tests {
                 claude_code_fixable: false,
                 examples: vec![],
                 tags: vec![],
+                ai_hint: None,
+                auto_fix: None,
+                skip_comments: false,
+                skip_strings: false,
                 enabled: true,
             },
             AntiPattern {
@@ -664,6 +1335,10 @@ mod rules_command_tests {
                 claude_code_fixable: false,
                 examples: vec![],
                 tags: vec![],
+                ai_hint: None,
+                auto_fix: None,
+                skip_comments: false,
+                skip_strings: false,
                 enabled: true,
             },
             AntiPattern {
@@ -678,16 +1353,37 @@ mod rules_command_tests {
                 claude_code_fixable: false,
                 examples: vec![],
                 tags: vec![],
+                ai_hint: None,
+                auto_fix: None,
+                skip_comments: false,
+                skip_strings: false,
                 enabled: true,
             },
         ];
 
         let pattern_refs: Vec<&AntiPattern> = patterns.iter().collect();
-        let (critical, major, warning) = count_patterns_by_severity(&pattern_refs);
+        let counts = count_patterns_by_severity(&pattern_refs);
 
-        assert_eq!(critical, 1);
-        assert_eq!(major, 1);
-        assert_eq!(warning, 1);
+        assert_eq!(counts[&Severity::Critical], 1);
+        assert_eq!(counts[&Severity::Major], 1);
+        assert_eq!(counts[&Severity::Warning], 1);
+        assert!(!counts.contains_key(&Severity::Blocker) && !counts.contains_key(&Severity::Info));
+    }
+
+    #[test]
+    fn test_source_column() {
+        colored::control::set_override(false);
+        let mut registry = PatternRegistry::new();
+        registry.load_embedded_elixir_rules().unwrap();
+        let rule = registry.get_patterns_for_language(&Language::Elixir)[0].clone();
+
+        assert_eq!(source_column(None, &rule), "");
+        let column = source_column(Some(registry.rule_sources()), &rule);
+        assert!(column.starts_with("  [built-in elixir pack v"), "{column}");
+
+        let mut sources = RuleSources::default();
+        sources.insert(&rule, RuleSource::Repository { path: REPO_RULES_FILE.to_string() });
+        assert_eq!(source_column(Some(&sources), &rule), "  [team rules in .patingin/rules.yml]");
     }
 
     #[tokio::test]
@@ -700,11 +1396,15 @@ mod rules_command_tests {
             (Language::Rust, ("🦀", "Rust")),
             (Language::Zig, ("⚡", "Zig")),
             (Language::Sql, ("🗃️", "SQL")),
+            (Language::Ruby, ("💎", "Ruby")),
+            (Language::Php, ("🐘", "PHP")),
+            (Language::Java, ("☕", "Java")),
+            (Language::Kotlin, ("🟣", "Kotlin")),
         ];
 
         for (language, expected) in test_cases {
-            let result = get_language_display_info(&language);
-            assert_eq!(result, expected);
+            let (emoji, name) = get_language_display_info(&language);
+            assert_eq!((emoji, name.as_str()), expected);
         }
     }
 
@@ -753,4 +1453,27 @@ mod rules_command_tests {
         let result = run(args).await;
         assert!(result.is_ok()); // Should show "no custom rules" message
     }
+
+    #[test]
+    fn test_export_round_trips_as_rules() {
+        let mut registry = PatternRegistry::new();
+        registry.load_built_in_patterns().unwrap();
+
+        let rules = exported_rules(&registry, Some(&Language::Python), false);
+        assert!(!rules.is_empty());
+        assert!(rules.iter().all(|exported| exported.rule.language == Language::Python));
+        assert!(exported_rules(&registry, None, true).is_empty());
+
+        let export = RulesExport { patingin_version: "1.0.0", project: None, rules };
+        let yaml = serde_yaml::to_string(&export).unwrap();
+        assert!(yaml.contains("  source:\n    kind: built_in\n    pack: python\n"), "{yaml}");
+
+        #[derive(serde::Deserialize)]
+        struct Exported {
+            rules: Vec<AntiPattern>,
+        }
+        let parsed: Exported = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.rules.len(), export.rules.len());
+        assert_eq!(parsed.rules[0].id, export.rules[0].rule.id);
+    }
 }
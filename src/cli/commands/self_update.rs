@@ -0,0 +1,121 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use colored::*;
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use crate::core::updates::{is_newer, UpdateCheckState};
+use crate::core::CustomRulesManager;
+use crate::external::releases::{
+    asset_name, parse_checksums, replace_executable, verify_checksum, ReleaseClient,
+    CHECKSUMS_ASSET,
+};
+
+/// The passive check must not hold up the command it runs before
+const NOTICE_TIMEOUT: Duration = Duration::from_secs(2);
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Args)]
+pub struct SelfUpdateArgs {
+    /// Only report whether a newer release is available
+    #[arg(long)]
+    pub check: bool,
+
+    /// Reinstall the latest release even if this binary is up to date
+    #[arg(long, conflicts_with = "check")]
+    pub force: bool,
+}
+
+pub async fn run(args: SelfUpdateArgs) -> Result<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let client = ReleaseClient::new(DOWNLOAD_TIMEOUT)?;
+    let release = client.latest().await?;
+    record_check(release.version());
+
+    let newer = is_newer(release.version(), current);
+    if !newer && !args.force {
+        println!("{} patingin {current} is up to date", "✅".green());
+        return Ok(());
+    }
+    if args.check {
+        println!(
+            "{} patingin {} is available (you have {current}). Run `patingin self-update` to install it.",
+            "⬆️ ".cyan(),
+            release.version().bold()
+        );
+        return Ok(());
+    }
+
+    let target = env!("PATINGIN_TARGET");
+    let binary_name = asset_name(target);
+    let binary = release.asset(&binary_name).ok_or_else(|| {
+        anyhow!(
+            "Release {} has no binary for {target}; install it from source with `cargo install`",
+            release.tag_name
+        )
+    })?;
+    let checksums = release.asset(CHECKSUMS_ASSET).ok_or_else(|| {
+        anyhow!("Release {} has no {CHECKSUMS_ASSET} to verify the download", release.tag_name)
+    })?;
+
+    println!("{} Downloading {}...", "📥".cyan(), binary_name);
+    let bytes = client.download(binary).await?;
+    let checksums = String::from_utf8(client.download(checksums).await?)?;
+    verify_checksum(&binary_name, &bytes, &parse_checksums(&checksums))?;
+    println!("{} Checksum verified", "🔒".green());
+
+    let executable = std::env::current_exe()?;
+    replace_executable(&executable, &bytes)?;
+    println!(
+        "{} Updated {} from {current} to {}",
+        "🎉".green(),
+        executable.display(),
+        release.version().bold()
+    );
+    Ok(())
+}
+
+/// Print a notice on stderr when a newer release is out. The release is looked up at most
+/// once per `updates.interval_hours`, and never in CI, when stderr is not a terminal, when
+/// `PATINGIN_NO_UPDATE_CHECK` is set or when `updates.check` is off. Failures are ignored.
+pub async fn notify_if_outdated() {
+    if std::env::var_os("CI").is_some()
+        || std::env::var_os("PATINGIN_NO_UPDATE_CHECK").is_some()
+        || !std::io::stderr().is_terminal()
+    {
+        return;
+    }
+    let manager = CustomRulesManager::new();
+    let Ok(config) = manager.load_config() else {
+        return;
+    };
+    let path = UpdateCheckState::default_path(manager.config_path());
+    let state = UpdateCheckState::load(&path);
+    if !config.updates.check || !state.is_due(&config.updates, chrono::Utc::now()) {
+        return;
+    }
+
+    let latest = match ReleaseClient::new(NOTICE_TIMEOUT) {
+        Ok(client) => client.latest().await.ok(),
+        Err(_) => None,
+    };
+    // Record the attempt even when it failed, so an offline machine is not slowed down on
+    // every run
+    let latest = latest.map(|release| release.version().to_string());
+    let _ = UpdateCheckState::checked_now(latest.clone()).save(&path);
+
+    let current = env!("CARGO_PKG_VERSION");
+    if let Some(latest) = latest.filter(|latest| is_newer(latest, current)) {
+        eprintln!(
+            "{} patingin {latest} is available (you have {current}); run `patingin self-update`",
+            "⬆️ ".cyan()
+        );
+    }
+}
+
+/// Remember the release `self-update` just looked up, so the passive check waits a full
+/// interval before looking again
+fn record_check(latest: &str) {
+    let path = UpdateCheckState::default_path(CustomRulesManager::new().config_path());
+    let _ = UpdateCheckState::checked_now(Some(latest.to_string())).save(&path);
+}
@@ -0,0 +1,285 @@
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::review::{
+    build_review_engine, filter_diff_by_language, parse_commit_range, scan_project_tree,
+    CommitRange,
+};
+use crate::config::Config;
+use crate::core::result_cache::ResultCache;
+use crate::core::{Language, ReviewViolation, Severity};
+use crate::git::{DiffScope, GitDiffParser, GitIntegration};
+use crate::report::severity_breakdown;
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// Count the violations added between two commits, e.g. `v1.2..v1.3` (`A...B` starts
+    /// from their merge base), instead of every file in the project
+    #[arg(long, value_name = "A..B", value_parser = parse_commit_range)]
+    pub range: Option<CommitRange>,
+
+    /// Check only specific language files
+    #[arg(long, value_name = "LANG")]
+    pub language: Option<Language>,
+
+    /// Count directories by their first N path components, e.g. 1 for `lib/`
+    #[arg(long, value_name = "N")]
+    pub depth: Option<usize>,
+
+    /// Rows shown for each breakdown and for the worst offenders (0 for all)
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    pub top: usize,
+
+    /// Output the statistics in JSON format
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Violations sharing a rule, language, directory or file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Breakdown {
+    pub name: String,
+    pub violations: usize,
+    pub auto_fixable: usize,
+    pub auto_fixable_percent: f64,
+    /// The most severe of the violations
+    pub worst_severity: Severity,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    /// What was counted, e.g. "whole project" or "v1.2..v1.3"
+    pub scope: String,
+    pub files_analyzed: usize,
+    pub violations: usize,
+    pub auto_fixable: usize,
+    pub auto_fixable_percent: f64,
+    pub by_severity: BTreeMap<Severity, usize>,
+    pub by_rule: Vec<Breakdown>,
+    pub by_language: Vec<Breakdown>,
+    pub by_directory: Vec<Breakdown>,
+    /// Files with the most violations
+    pub worst_files: Vec<Breakdown>,
+}
+
+pub async fn run(args: StatsArgs) -> Result<()> {
+    let (mut git_diff, scope) = match &args.range {
+        Some(range) => {
+            let git = GitIntegration::new(".")?;
+            git.ensure_reference(&range.from, false)?;
+            git.ensure_reference(&range.to, false)?;
+            let from = match range.from_merge_base {
+                true => git.merge_base_between(&range.from, &range.to)?,
+                false => range.from.clone(),
+            };
+            let diff_scope = DiffScope::Between(from, range.to.clone());
+            (
+                GitDiffParser::parse(&GitDiffParser::execute_git_diff(&diff_scope)?)?,
+                range.to_string(),
+            )
+        }
+        None => (scan_project_tree()?, "whole project".to_string()),
+    };
+    if let Some(language) = &args.language {
+        git_diff = filter_diff_by_language(git_diff, language);
+    }
+    Config::load_for_project()?.remove_ignored(&mut git_diff);
+
+    // Repository-wide counts are what the result cache speeds up most
+    let mut review_engine = build_review_engine()?;
+    if let Some(cache) = ResultCache::for_project(&review_engine.cache_key()?)? {
+        review_engine = review_engine.with_cache(cache);
+    }
+    let review_result = review_engine.review_git_diff(&git_diff)?;
+
+    let stats =
+        compute_stats(&review_result.violations, scope, git_diff.files.len(), args.depth, args.top);
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        output_table(&stats);
+    }
+    Ok(())
+}
+
+/// The share of `part` in `whole` as a percentage with one decimal
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        return 0.0;
+    }
+    (part as f64 * 1000.0 / whole as f64).round() / 10.0
+}
+
+/// The directory of `file_path` counted under, `.` for the project root
+fn directory(file_path: &str, depth: Option<usize>) -> String {
+    let mut components: Vec<&str> = file_path.split('/').collect();
+    components.pop();
+    if let Some(depth) = depth {
+        components.truncate(depth.max(1));
+    }
+    if components.is_empty() {
+        ".".to_string()
+    } else {
+        components.join("/")
+    }
+}
+
+/// `violations` counted by `key`, most violations first and at most `top` of them
+fn breakdown(
+    violations: &[ReviewViolation],
+    key: impl Fn(&ReviewViolation) -> String,
+    top: usize,
+) -> Vec<Breakdown> {
+    let mut groups: BTreeMap<String, Vec<&ReviewViolation>> = BTreeMap::new();
+    for violation in violations {
+        groups.entry(key(violation)).or_default().push(violation);
+    }
+
+    let mut rows: Vec<Breakdown> = groups
+        .into_iter()
+        .map(|(name, group)| {
+            let auto_fixable = group.iter().filter(|v| v.auto_fixable).count();
+            Breakdown {
+                name,
+                violations: group.len(),
+                auto_fixable,
+                auto_fixable_percent: percent(auto_fixable, group.len()),
+                worst_severity: group.iter().map(|v| v.severity).min().unwrap_or(Severity::Info),
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.violations
+            .cmp(&a.violations)
+            .then(a.worst_severity.cmp(&b.worst_severity))
+            .then(a.name.cmp(&b.name))
+    });
+    if top > 0 {
+        rows.truncate(top);
+    }
+    rows
+}
+
+pub fn compute_stats(
+    violations: &[ReviewViolation],
+    scope: String,
+    files_analyzed: usize,
+    depth: Option<usize>,
+    top: usize,
+) -> Stats {
+    let auto_fixable = violations.iter().filter(|v| v.auto_fixable).count();
+    let mut by_severity: BTreeMap<Severity, usize> =
+        Severity::ALL.into_iter().map(|severity| (severity, 0)).collect();
+    for violation in violations {
+        *by_severity.entry(violation.severity).or_default() += 1;
+    }
+
+    Stats {
+        scope,
+        files_analyzed,
+        violations: violations.len(),
+        auto_fixable,
+        auto_fixable_percent: percent(auto_fixable, violations.len()),
+        by_severity,
+        by_rule: breakdown(violations, |v| v.rule.id.clone(), top),
+        by_language: breakdown(violations, |v| v.language.to_string(), top),
+        by_directory: breakdown(violations, |v| directory(&v.file_path, depth), top),
+        worst_files: breakdown(violations, |v| v.file_path.clone(), top),
+    }
+}
+
+fn output_table(stats: &Stats) {
+    println!("📈 Statistics: {} ({} files analyzed)", stats.scope.bold(), stats.files_analyzed);
+    println!();
+    if stats.violations == 0 {
+        println!("✅ No anti-pattern violations found!");
+        return;
+    }
+
+    println!(
+        "📊 {} violations: {}",
+        stats.violations,
+        severity_breakdown(|severity| stats.by_severity[&severity], true)
+    );
+    println!("✨ Auto-fixable: {} ({:.1}%)", stats.auto_fixable, stats.auto_fixable_percent);
+
+    for (title, rows) in [
+        ("📏 By rule", &stats.by_rule),
+        ("🗣️  By language", &stats.by_language),
+        ("📂 By directory", &stats.by_directory),
+        ("🔥 Worst offenders", &stats.worst_files),
+    ] {
+        println!();
+        println!("{title}");
+        println!("   {:>6}  {:>8}  Name", "Count", "Fixable");
+        for row in rows {
+            println!(
+                "   {:>6}  {:>7.1}%  {} {}",
+                row.violations,
+                row.auto_fixable_percent,
+                row.worst_severity.icon(),
+                row.name
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+    use crate::report::test_support::violation;
+
+    fn found(file_path: &str, rule: &str, severity: Severity, fixable: bool) -> ReviewViolation {
+        let mut violation = violation(file_path, 1, severity);
+        violation.rule.id = rule.to_string();
+        violation.auto_fixable = fixable;
+        violation
+    }
+
+    #[test]
+    fn test_counts_by_rule_language_directory_and_file() {
+        let mut js = found("assets/js/app.js", "double_equals", Severity::Major, true);
+        js.language = Language::JavaScript;
+        let violations = vec![
+            found("lib/app/user.ex", "debug_print", Severity::Warning, true),
+            found("lib/app/user.ex", "debug_print", Severity::Warning, true),
+            found("lib/app/auth.ex", "atoms", Severity::Critical, false),
+            found("mix.exs", "debug_print", Severity::Warning, false),
+            js,
+        ];
+
+        let stats = compute_stats(&violations, "whole project".to_string(), 9, None, 10);
+        assert_eq!(stats.violations, 5);
+        assert_eq!(stats.auto_fixable, 3);
+        assert_eq!(stats.auto_fixable_percent, 60.0);
+        assert_eq!(stats.by_severity[&Severity::Warning], 3);
+        assert_eq!(stats.by_severity[&Severity::Blocker], 0);
+
+        let rule = &stats.by_rule[0];
+        assert_eq!(
+            (rule.name.as_str(), rule.violations, rule.auto_fixable_percent),
+            ("debug_print", 3, 66.7)
+        );
+        let languages: Vec<_> = stats.by_language.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(languages, ["elixir", "javascript"]);
+        let directories: Vec<_> =
+            stats.by_directory.iter().map(|row| (row.name.as_str(), row.violations)).collect();
+        assert_eq!(directories, [("lib/app", 3), ("assets/js", 1), (".", 1)]);
+        assert_eq!(stats.worst_files[0].name, "lib/app/user.ex");
+        // Ties go to the more severe file
+        assert_eq!(stats.worst_files[1].name, "lib/app/auth.ex");
+
+        let shallow = compute_stats(&violations, String::new(), 9, Some(1), 2);
+        let directories: Vec<_> =
+            shallow.by_directory.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(directories, ["lib", "assets"]);
+        assert_eq!(shallow.by_rule.len(), 2);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["by_severity"]["warning"], 3);
+        assert_eq!(json["by_rule"][0]["worst_severity"], "warning");
+    }
+}
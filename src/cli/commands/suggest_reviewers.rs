@@ -0,0 +1,299 @@
+use anyhow::{anyhow, Result};
+use clap::Args;
+use colored::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::external::github::CommentAction;
+use crate::external::GitHubIntegration;
+use crate::git::ownership::{AuthorActivity, CodeOwners};
+use crate::git::{DiffScope, GitDiffParser, GitIntegration};
+
+/// Hidden marker identifying the reviewer suggestion comment, so reruns edit it in place
+const SUGGESTION_COMMENT_MARKER: &str = "<!-- patingin:reviewer-suggestions -->";
+
+#[derive(Args)]
+pub struct SuggestReviewersArgs {
+    /// Use staged changes instead of all changes since the last commit
+    #[arg(long)]
+    pub staged: bool,
+
+    /// Use changes since a specific commit/branch/tag (e.g. origin/main)
+    #[arg(long, value_name = "REF", conflicts_with = "staged")]
+    pub since: Option<String>,
+
+    /// Number of reviewers to suggest
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub limit: usize,
+
+    /// How many recent commits of history to inspect
+    #[arg(long, value_name = "COMMITS", default_value_t = 500)]
+    pub history: usize,
+
+    /// Do not leave out yourself (git config user.email)
+    #[arg(long)]
+    pub include_self: bool,
+
+    /// Output suggestions in JSON format
+    #[arg(long)]
+    pub json: bool,
+
+    /// Post the suggestions as a comment on this GitHub pull request (needs GITHUB_TOKEN)
+    #[arg(long, value_name = "NUMBER")]
+    pub github_pr: Option<u64>,
+
+    /// Repository to post to (defaults to GITHUB_REPOSITORY)
+    #[arg(long, value_name = "OWNER/REPO", requires = "github_pr")]
+    pub github_repo: Option<String>,
+}
+
+/// A person or team worth asking to review the change, with the evidence for it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReviewerSuggestion {
+    pub reviewer: String,
+    pub score: usize,
+    /// Changed files CODEOWNERS assigns to this reviewer
+    pub owned_files: usize,
+    /// Changed files this reviewer committed to recently
+    pub touched_files: usize,
+    pub commits: usize,
+    /// Date (YYYY-MM-DD) of their latest commit to the changed files
+    pub last_commit: Option<String>,
+}
+
+impl ReviewerSuggestion {
+    fn reasons(&self) -> String {
+        let mut reasons = Vec::new();
+        if self.owned_files > 0 {
+            reasons.push(format!("owns {} changed file(s) (CODEOWNERS)", self.owned_files));
+        }
+        if self.commits > 0 {
+            let mut history = format!(
+                "{} commit(s) touching {} changed file(s)",
+                self.commits, self.touched_files
+            );
+            if let Some(last_commit) = &self.last_commit {
+                history.push_str(&format!(", last on {last_commit}"));
+            }
+            reasons.push(history);
+        }
+        reasons.join("; ")
+    }
+}
+
+pub async fn run(args: SuggestReviewersArgs) -> Result<()> {
+    let git = GitIntegration::new(".")?;
+    let root = git.workdir().ok_or_else(|| anyhow!("suggest-reviewers needs a working tree"))?;
+
+    let scope = if args.staged {
+        DiffScope::Staged
+    } else {
+        DiffScope::SinceCommit(args.since.clone().unwrap_or_else(|| "HEAD".to_string()))
+    };
+    let git_diff = GitDiffParser::parse(&GitDiffParser::execute_git_diff(&scope)?)?;
+    let changed_files: Vec<String> = git_diff.files.into_iter().map(|file| file.path).collect();
+
+    if changed_files.is_empty() {
+        println!("✅ No changed files; nothing to route");
+        return Ok(());
+    }
+
+    let codeowners = CodeOwners::load(root)?;
+    let authors = git.recent_authors(&changed_files, args.history)?;
+    let exclude = if args.include_self { None } else { git.user_email() };
+
+    let suggestions = rank_reviewers(
+        &changed_files,
+        codeowners.as_ref(),
+        &authors,
+        exclude.as_deref(),
+        args.limit,
+    );
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&suggestions)?);
+    } else {
+        output_suggestions(&suggestions, changed_files.len());
+    }
+
+    if let Some(pr_number) = args.github_pr {
+        let github = GitHubIntegration::new();
+        let repo = GitHubIntegration::resolve_repository(args.github_repo.as_deref())?;
+        let body = render_suggestion_comment(&suggestions, changed_files.len());
+        let action = github
+            .upsert_marked_comment(&repo, pr_number, SUGGESTION_COMMENT_MARKER, &body)
+            .await?;
+        let verb = match action {
+            CommentAction::Created => "Posted",
+            CommentAction::Updated => "Updated",
+        };
+        eprintln!("💬 {verb} reviewer suggestions on {repo}#{pr_number}");
+    }
+
+    Ok(())
+}
+
+/// Score candidates for the changed files: ownership counts more than history, since
+/// CODEOWNERS is an explicit statement of responsibility.
+pub fn rank_reviewers(
+    changed_files: &[String],
+    codeowners: Option<&CodeOwners>,
+    authors: &[AuthorActivity],
+    exclude_email: Option<&str>,
+    limit: usize,
+) -> Vec<ReviewerSuggestion> {
+    let exclude_email = exclude_email.map(str::to_lowercase);
+    let is_excluded = |candidate: &str| {
+        exclude_email.as_deref().is_some_and(|email| candidate.to_lowercase() == email)
+    };
+
+    let mut candidates: BTreeMap<String, ReviewerSuggestion> = BTreeMap::new();
+    let mut candidate = |key: &str, reviewer: String| {
+        candidates.entry(key.to_lowercase()).or_insert_with(|| ReviewerSuggestion {
+            reviewer,
+            score: 0,
+            owned_files: 0,
+            touched_files: 0,
+            commits: 0,
+            last_commit: None,
+        });
+        key.to_lowercase()
+    };
+
+    let mut keys = Vec::new();
+    if let Some(codeowners) = codeowners {
+        for file in changed_files {
+            for owner in codeowners.owners_for(file) {
+                if !is_excluded(owner) {
+                    keys.push((candidate(owner, owner.clone()), true, None));
+                }
+            }
+        }
+    }
+    for author in authors.iter().filter(|author| !is_excluded(&author.email)) {
+        // An email listed in CODEOWNERS and the same committer are one person
+        let key = candidate(&author.email, format!("{} <{}>", author.name, author.email));
+        keys.push((key, false, Some(author)));
+    }
+
+    for (key, owned, author) in keys {
+        let suggestion = candidates.get_mut(&key).expect("candidate registered above");
+        if owned {
+            suggestion.owned_files += 1;
+        }
+        if let Some(author) = author {
+            suggestion.reviewer = format!("{} <{}>", author.name, author.email);
+            suggestion.touched_files = author.files.len();
+            suggestion.commits = author.commits;
+            suggestion.last_commit = chrono::DateTime::from_timestamp(author.last_commit, 0)
+                .map(|time| time.format("%Y-%m-%d").to_string());
+        }
+    }
+
+    let mut suggestions: Vec<ReviewerSuggestion> = candidates
+        .into_values()
+        .map(|mut suggestion| {
+            suggestion.score =
+                suggestion.owned_files * 3 + suggestion.touched_files * 2 + suggestion.commits;
+            suggestion
+        })
+        .collect();
+    suggestions.sort_by(|a, b| b.score.cmp(&a.score).then(a.reviewer.cmp(&b.reviewer)));
+    suggestions.truncate(limit);
+    suggestions
+}
+
+fn output_suggestions(suggestions: &[ReviewerSuggestion], changed_files: usize) {
+    println!("👥 Suggested reviewers for {changed_files} changed file(s)");
+
+    if suggestions.is_empty() {
+        println!("   No owners or recent authors found for these files");
+        return;
+    }
+
+    for (rank, suggestion) in suggestions.iter().enumerate() {
+        println!(
+            "   {}. {} — {}",
+            rank + 1,
+            suggestion.reviewer.bold(),
+            suggestion.reasons().dimmed()
+        );
+    }
+}
+
+fn render_suggestion_comment(suggestions: &[ReviewerSuggestion], changed_files: usize) -> String {
+    let mut body = format!(
+        "{SUGGESTION_COMMENT_MARKER}\n## 👥 Suggested reviewers\n\nBased on CODEOWNERS and the history of the {changed_files} changed file(s):\n\n"
+    );
+    if suggestions.is_empty() {
+        body.push_str("No owners or recent authors found for these files.\n");
+    }
+    for suggestion in suggestions {
+        body.push_str(&format!("- **{}** — {}\n", suggestion.reviewer, suggestion.reasons()));
+    }
+    body
+}
+
+#[cfg(test)]
+mod suggest_reviewers_tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn author(name: &str, files: &[&str], commits: usize) -> AuthorActivity {
+        AuthorActivity {
+            name: name.to_string(),
+            email: format!("{}@example.com", name.to_lowercase()),
+            commits,
+            files: files.iter().map(|file| file.to_string()).collect::<BTreeSet<_>>(),
+            last_commit: 1_760_000_000,
+        }
+    }
+
+    #[test]
+    fn test_rank_reviewers_combines_ownership_and_history() {
+        let changed = vec!["lib/auth.ex".to_string(), "lib/user.ex".to_string()];
+        let codeowners =
+            CodeOwners::parse("lib/ @backend\nlib/auth.ex @security bob@example.com\n");
+        let authors = vec![
+            author("Alice", &["lib/user.ex", "lib/auth.ex"], 4),
+            author("Bob", &["lib/auth.ex"], 1),
+        ];
+
+        let suggestions = rank_reviewers(&changed, Some(&codeowners), &authors, None, 10);
+        let names: Vec<&str> = suggestions.iter().map(|s| s.reviewer.as_str()).collect();
+
+        assert_eq!(
+            names,
+            ["Alice <alice@example.com>", "Bob <bob@example.com>", "@backend", "@security"]
+        );
+        // Bob is both a code owner (by email) and a recent author
+        assert_eq!(suggestions[1].owned_files, 1);
+        assert_eq!(suggestions[1].commits, 1);
+        assert_eq!(suggestions[1].last_commit.as_deref(), Some("2025-10-09"));
+        assert_eq!(suggestions[3].owned_files, 1);
+    }
+
+    #[test]
+    fn test_rank_reviewers_excludes_self_and_limits() {
+        let changed = vec!["lib/user.ex".to_string()];
+        let authors =
+            vec![author("Alice", &["lib/user.ex"], 9), author("Bob", &["lib/user.ex"], 1)];
+
+        let suggestions = rank_reviewers(&changed, None, &authors, Some("ALICE@example.com"), 1);
+
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].reviewer, "Bob <bob@example.com>");
+        assert_eq!(
+            suggestions[0].reasons(),
+            "1 commit(s) touching 1 changed file(s), last on 2025-10-09"
+        );
+    }
+
+    #[test]
+    fn test_render_suggestion_comment_has_marker() {
+        let body = render_suggestion_comment(&[], 2);
+        assert!(body.starts_with(SUGGESTION_COMMENT_MARKER));
+        assert!(body.contains("history of the 2 changed file(s)"));
+        assert!(body.contains("No owners or recent authors"));
+    }
+}
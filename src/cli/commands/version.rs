@@ -0,0 +1,165 @@
+use anyhow::Result;
+use clap::Args;
+use colored::*;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::config::resolve::global_config_path;
+use crate::config::{find_project_config, PROJECT_CONFIG_FILES};
+use crate::core::audit::hash_bytes;
+use crate::core::registry::BUILT_IN_RULE_PACKS;
+use crate::core::{CustomRulesManager, ProjectDetector};
+
+#[derive(Args)]
+pub struct VersionArgs {
+    /// Include the build commit, target and features, the bundled rule packs and the
+    /// config files in use
+    #[arg(long)]
+    pub verbose: bool,
+
+    /// Output everything `--verbose` shows in JSON format
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// What a bug report or CI log needs to pin down the exact tool state
+#[derive(Debug, Serialize)]
+struct VersionInfo {
+    version: &'static str,
+    /// Commit the binary was built from; absent for builds outside a git checkout
+    git_sha: Option<&'static str>,
+    target: &'static str,
+    profile: &'static str,
+    features: Vec<&'static str>,
+    rule_packs: Vec<RulePackInfo>,
+    config: ConfigPaths,
+}
+
+/// A rule file compiled into the binary. Packs are not versioned separately, so a checksum
+/// of the file identifies its revision.
+#[derive(Debug, Serialize)]
+struct RulePackInfo {
+    name: &'static str,
+    rules: usize,
+    checksum: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ConfigPaths {
+    user_rules: PathBuf,
+    user_rules_exists: bool,
+    global_config: PathBuf,
+    global_config_exists: bool,
+    /// The detected project's config file, if it has one
+    project_config: Option<PathBuf>,
+    project_config_names: Vec<&'static str>,
+}
+
+pub async fn run(args: VersionArgs) -> Result<()> {
+    if !args.verbose && !args.json {
+        println!("{}", version_line());
+        return Ok(());
+    }
+
+    let info = collect()?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
+
+    println!("{}", version_line().bold());
+    let features =
+        if info.features.is_empty() { "none".to_string() } else { info.features.join(", ") };
+    println!("🔧 Build: {}, {} profile, features: {features}", info.target, info.profile);
+
+    println!("📦 Bundled rule packs:");
+    for pack in &info.rule_packs {
+        println!("   {:<12} {:>3} rules  {}", pack.name, pack.rules, pack.checksum.dimmed());
+    }
+
+    println!("📁 Config:");
+    let found = if info.config.user_rules_exists { "" } else { " (not found)" };
+    println!("   User rules:     {}{found}", info.config.user_rules.display());
+    let found = if info.config.global_config_exists { "" } else { " (not found)" };
+    println!("   Global config:  {}{found}", info.config.global_config.display());
+    match &info.config.project_config {
+        Some(path) => println!("   Project config: {}", path.display()),
+        None => println!(
+            "   Project config: none (looked for {})",
+            info.config.project_config_names.join(", ")
+        ),
+    }
+    Ok(())
+}
+
+/// `patingin 0.1.0 (1a2b3c4d5e6f)`, without the commit when it is unknown
+fn version_line() -> String {
+    match git_sha() {
+        Some(sha) => format!("patingin {} ({sha})", env!("CARGO_PKG_VERSION")),
+        None => format!("patingin {}", env!("CARGO_PKG_VERSION")),
+    }
+}
+
+fn git_sha() -> Option<&'static str> {
+    Some(env!("PATINGIN_GIT_SHA")).filter(|sha| !sha.is_empty())
+}
+
+fn collect() -> Result<VersionInfo> {
+    let user_rules = CustomRulesManager::new().config_path().to_path_buf();
+    let global_config = global_config_path();
+    let project_config = ProjectDetector::detect_project(None)
+        .ok()
+        .and_then(|project_info| find_project_config(&project_info.root_path));
+
+    Ok(VersionInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: git_sha(),
+        target: env!("PATINGIN_TARGET"),
+        profile: if cfg!(debug_assertions) { "debug" } else { "release" },
+        features: env!("PATINGIN_FEATURES").split(',').filter(|f| !f.is_empty()).collect(),
+        rule_packs: rule_packs()?,
+        config: ConfigPaths {
+            user_rules_exists: user_rules.exists(),
+            user_rules,
+            global_config_exists: global_config.exists(),
+            global_config,
+            project_config,
+            project_config_names: PROJECT_CONFIG_FILES.to_vec(),
+        },
+    })
+}
+
+fn rule_packs() -> Result<Vec<RulePackInfo>> {
+    BUILT_IN_RULE_PACKS
+        .iter()
+        .map(|(name, yaml)| {
+            let rules: Vec<serde_yaml::Value> = serde_yaml::from_str(yaml)?;
+            Ok(RulePackInfo {
+                name,
+                rules: rules.len(),
+                checksum: hash_bytes(yaml.as_bytes())?[..12].to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod version_tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_packs_are_counted_and_checksummed() {
+        let packs = rule_packs().unwrap();
+        assert_eq!(packs.len(), BUILT_IN_RULE_PACKS.len());
+
+        let elixir = packs.iter().find(|pack| pack.name == "elixir").unwrap();
+        assert!(elixir.rules > 0);
+        assert_eq!(elixir.checksum.len(), 12);
+        assert!(packs.iter().all(|pack| pack.checksum != elixir.checksum || pack.name == "elixir"));
+    }
+
+    #[test]
+    fn test_version_line_names_the_package_version() {
+        assert!(version_line().starts_with(&format!("patingin {}", env!("CARGO_PKG_VERSION"))));
+    }
+}
@@ -1,6 +1,7 @@
 pub mod commands;
 
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(
@@ -11,6 +12,14 @@ use clap::{Parser, Subcommand};
                   Analyze only what changed, show exactly where problems are."
 )]
 pub struct Cli {
+    /// Use this project config file instead of the project's patingin.yml
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Use this user rules file instead of ~/.config/patingin/rules.yml
+    #[arg(long, global = true, value_name = "FILE")]
+    pub user_config: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -21,7 +30,59 @@ pub enum Commands {
     Rules(commands::rules::RulesArgs),
 
     /// Analyze git diff changes for anti-pattern violations  
-    Review(commands::review::ReviewArgs),
+    Review(Box<commands::review::ReviewArgs>),
+
+    /// Install or remove the pre-commit hook that blocks commits with violations
+    Hook(commands::hook::HookArgs),
+
+    /// Server-side push gate for git pre-receive hooks (works in bare repositories)
+    PreReceive(commands::pre_receive::PreReceiveArgs),
+
+    /// Review a pull or merge request in CI since its merge base, or run inside a specific
+    /// provider with its event data, annotations, job summary and outputs
+    Ci(commands::ci::CiArgs),
+
+    /// Show or roll back the files the last `review` fix run changed
+    Fix(commands::fix::FixArgs),
+
+    /// Write a standalone HTML report of a review's violations, with snippets, filters and
+    /// charts
+    Report(commands::report::ReportArgs),
+
+    /// Rank files most in need of refactoring by violation density and churn
+    Hotspots(commands::hotspots::HotspotsArgs),
+
+    /// Report violations introduced and fixed per commit and per author since a reference
+    History(commands::history::HistoryArgs),
+
+    /// Count violations across the project or a commit range by rule, language and
+    /// directory, with the worst offenders and how much is auto-fixable
+    Stats(commands::stats::StatsArgs),
+
+    /// Interactive quiz on fixing anti-patterns, from rule examples or this repository
+    Learn(commands::learn::LearnArgs),
+
+    /// Suggest reviewers for the current changes from CODEOWNERS and recent history
+    SuggestReviewers(commands::suggest_reviewers::SuggestReviewersArgs),
+
+    /// Show or verify the audit log of review runs (enable with `audit.log` in patingin.yml)
+    Audit(commands::audit::AuditArgs),
+
+    /// List deprecated flags with their replacements and removal timeline
+    Deprecations(commands::deprecations::DeprecationsArgs),
+
+    /// Show the resolved configuration and where each setting comes from
+    Config(commands::config::ConfigArgs),
+
+    /// Show the version, or with --verbose the build, bundled rule packs and config paths
+    Version(commands::version::VersionArgs),
+
+    /// Download and install the latest release binary, verified against its checksum
+    SelfUpdate(commands::self_update::SelfUpdateArgs),
+
+    /// Record every current violation in .patingin/baseline.yml, so review only reports
+    /// new ones
+    Baseline,
 
     /// Comprehensive environment and configuration status check
     Setup,
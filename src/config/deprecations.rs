@@ -0,0 +1,132 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::find_project_config;
+use crate::core::ProjectDetector;
+
+/// A flag slated for removal, with its replacement and timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Deprecation {
+    pub command: &'static str,
+    pub flag: &'static str,
+    pub replacement: &'static str,
+    pub deprecated_in: &'static str,
+    pub removal_in: &'static str,
+    pub note: &'static str,
+}
+
+impl Deprecation {
+    /// One-line warning, e.g. "--auto-fix is deprecated and will be removed in 0.2.0; use --fix"
+    pub fn message(&self) -> String {
+        format!(
+            "{} is deprecated and will be removed in {}; use {}",
+            self.flag, self.removal_in, self.replacement
+        )
+    }
+}
+
+/// Every deprecated flag, oldest first
+pub const DEPRECATIONS: &[Deprecation] = &[Deprecation {
+    command: "review",
+    flag: "--auto-fix",
+    replacement: "--fix",
+    deprecated_in: "0.1.0",
+    removal_in: "0.2.0",
+    note: "--fix opens an interactive Claude Code session instead of applying fixes blindly",
+}];
+
+pub fn find_deprecation(command: &str, flag: &str) -> Option<&'static Deprecation> {
+    DEPRECATIONS.iter().find(|d| d.command == command && d.flag == flag)
+}
+
+/// How deprecated flags are treated. With `allow_deprecated: false` in the project config
+/// they are hard errors, so scripts still relying on them fail before removal does it for
+/// them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct DeprecationPolicy {
+    #[serde(default = "default_allow_deprecated")]
+    pub allow_deprecated: bool,
+}
+
+fn default_allow_deprecated() -> bool {
+    true
+}
+
+impl Default for DeprecationPolicy {
+    fn default() -> Self {
+        Self { allow_deprecated: default_allow_deprecated() }
+    }
+}
+
+impl DeprecationPolicy {
+    /// Read the top-level `allow_deprecated` key of a project config file
+    pub fn load(config_path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        serde_yaml::from_str::<Option<Self>>(&content)
+            .map(Option::unwrap_or_default)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))
+    }
+
+    /// The detected project's policy; deprecated flags are allowed without a project config
+    pub fn load_for_project() -> Result<Self> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(Self::default());
+        };
+        match find_project_config(&project_info.root_path) {
+            Some(config_path) => Self::load(&config_path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// The deprecations behind `flags` of `command`, or an error when the policy forbids
+    /// them
+    pub fn check(&self, command: &str, flags: &[&str]) -> Result<Vec<&'static Deprecation>> {
+        let used: Vec<&'static Deprecation> =
+            flags.iter().filter_map(|flag| find_deprecation(command, flag)).collect();
+        if !self.allow_deprecated {
+            if let Some(deprecation) = used.first() {
+                bail!(
+                    "{} (deprecated flags are disabled by `allow_deprecated: false`)",
+                    deprecation.message()
+                );
+            }
+        }
+        Ok(used)
+    }
+}
+
+#[cfg(test)]
+mod deprecations_tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_allows_deprecated_flags_by_default() {
+        let used = DeprecationPolicy::default().check("review", &["--auto-fix", "--fix"]).unwrap();
+
+        assert_eq!(used.len(), 1);
+        assert_eq!(
+            used[0].message(),
+            "--auto-fix is deprecated and will be removed in 0.2.0; use --fix"
+        );
+        assert!(DeprecationPolicy::default().check("ci", &["--auto-fix"]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_kill_switch_makes_deprecated_flags_errors() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(&config_path, "allow_deprecated: false\ngates:\n  style: off\n").unwrap();
+
+        let policy = DeprecationPolicy::load(&config_path).unwrap();
+        let error = policy.check("review", &["--auto-fix"]).unwrap_err().to_string();
+
+        assert!(error.contains("--auto-fix is deprecated"));
+        assert!(error.contains("allow_deprecated: false"));
+        assert!(policy.check("review", &["--fix"]).unwrap().is_empty());
+
+        std::fs::write(&config_path, "").unwrap();
+        assert!(DeprecationPolicy::load(&config_path).unwrap().allow_deprecated);
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::path::Path;
+
+use crate::core::{Language, Severity};
+
+/// Review defaults read from the `patingin.*` section of git config.
+///
+/// Teams already distribute git config through `include.path`, so this lets repository
+/// defaults travel with the repository:
+///
+/// ```text
+/// git config patingin.severity major
+/// git config patingin.failOn critical
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GitConfigDefaults {
+    pub preset: Option<String>,
+    pub severity: Option<Severity>,
+    pub fail_on: Option<Severity>,
+    /// Reporter name, validated when the reporter is created
+    pub format: Option<String>,
+    pub language: Option<Language>,
+    pub sample: Option<usize>,
+    pub no_color: Option<bool>,
+}
+
+impl GitConfigDefaults {
+    /// Read the layered git config (system, global, repository, includes) for `dir`.
+    /// Outside a repository only the global and system files are consulted.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut config = match git2::Repository::discover(dir) {
+            Ok(repo) => repo.config()?,
+            Err(_) => git2::Config::open_default()?,
+        };
+        Self::from_config(&config.snapshot()?)
+    }
+
+    fn from_config(config: &git2::Config) -> Result<Self> {
+        let string = |key: &str| config.get_string(&format!("patingin.{key}")).ok();
+
+        Ok(Self {
+            preset: string("preset"),
+            severity: parse_value("git config patingin.severity", string("severity"))?,
+            fail_on: parse_value("git config patingin.failOn", string("failOn"))?,
+            format: string("format"),
+            language: string("language")
+                .map(|value| {
+                    value.parse::<Language>().map_err(|error| {
+                        anyhow!("Invalid git config patingin.language = '{value}': {error}")
+                    })
+                })
+                .transpose()?,
+            sample: string("sample")
+                .map(|value| {
+                    value.parse::<usize>().map_err(|_| {
+                        anyhow!("Invalid git config patingin.sample = '{value}': expected a number")
+                    })
+                })
+                .transpose()?,
+            no_color: config.get_bool("patingin.noColor").ok(),
+        })
+    }
+}
+
+/// Parse `value` as one of `T`'s names, naming `setting` and the expected names when it is not
+pub(crate) fn parse_value<T: ValueEnum>(setting: &str, value: Option<String>) -> Result<Option<T>> {
+    value
+        .map(|value| {
+            T::from_str(&value, true).map_err(|_| {
+                let expected: Vec<String> = T::value_variants()
+                    .iter()
+                    .filter_map(|variant| variant.to_possible_value())
+                    .map(|possible| possible.get_name().to_string())
+                    .collect();
+                anyhow!("Invalid {setting} = '{value}': expected one of {}", expected.join(", "))
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod git_config_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config_with(entries: &[(&str, &str)]) -> (TempDir, git2::Config) {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = git2::Config::open(&temp_dir.path().join("config")).unwrap();
+        for (key, value) in entries {
+            config.set_str(key, value).unwrap();
+        }
+        (temp_dir, config)
+    }
+
+    #[test]
+    fn test_reads_patingin_section() {
+        let (_dir, config) = config_with(&[
+            ("patingin.severity", "major"),
+            ("patingin.failOn", "critical"),
+            ("patingin.format", "markdown"),
+            ("patingin.preset", "pr"),
+            ("patingin.sample", "5"),
+            ("patingin.noColor", "true"),
+        ]);
+
+        let defaults = GitConfigDefaults::from_config(&config).unwrap();
+
+        assert_eq!(defaults.severity, Some(Severity::Major));
+        assert_eq!(defaults.fail_on, Some(Severity::Critical));
+        assert_eq!(defaults.format.as_deref(), Some("markdown"));
+        assert_eq!(defaults.preset.as_deref(), Some("pr"));
+        assert_eq!(defaults.sample, Some(5));
+        assert_eq!(defaults.no_color, Some(true));
+        assert_eq!(defaults.language, None);
+    }
+
+    #[test]
+    fn test_invalid_value_names_key() {
+        let (_dir, config) = config_with(&[("patingin.failOn", "blocker-ish")]);
+
+        let error = GitConfigDefaults::from_config(&config).unwrap_err().to_string();
+
+        assert!(error.contains("patingin.failOn"));
+        assert!(error.contains("critical, major, warning"));
+    }
+
+    #[test]
+    fn test_repository_config_is_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        repo.config().unwrap().set_str("patingin.severity", "critical").unwrap();
+
+        let defaults = GitConfigDefaults::load(temp_dir.path()).unwrap();
+        assert_eq!(defaults.severity, Some(Severity::Critical));
+    }
+}
@@ -1,43 +1,463 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::core::coverage::FileSkipReason;
+use crate::core::languages::{declare_languages, load_language_definitions};
+use crate::core::{CustomRulesManager, Language, Severity};
+use crate::git::GitDiff;
+use presets::{PresetScope, ReviewPreset};
+use resolve::ResolvedConfig;
+
+pub mod deprecations;
+pub mod git_config;
+pub mod presets;
+pub mod resolve;
+
+/// Project-level config file names, in lookup order
+pub const PROJECT_CONFIG_FILES: [&str; 3] =
+    ["patingin.yml", ".patingin.yml", ".patingin/config.yml"];
+
+/// Config files given on the command line, which replace discovery for the whole run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigOverrides {
+    /// `--config`: used instead of the project's `patingin.yml`
+    pub project: Option<PathBuf>,
+    /// `--user-config`: used instead of `~/.config/patingin/rules.yml`
+    pub user: Option<PathBuf>,
+}
+
+static CONFIG_OVERRIDES: Lazy<RwLock<ConfigOverrides>> = Lazy::new(Default::default);
+
+/// Use the given config files for the rest of the process. The project config must exist;
+/// the user config may not yet, since rule changes create it.
+pub fn override_config_paths(overrides: ConfigOverrides) -> Result<()> {
+    let project = overrides
+        .project
+        .map(|path| {
+            path.canonicalize().with_context(|| format!("Config file {} not found", path.display()))
+        })
+        .transpose()?;
+    let user = match overrides.user {
+        Some(path) => Some(match path.canonicalize() {
+            Ok(path) => path,
+            Err(_) => std::env::current_dir()?.join(path),
+        }),
+        None => None,
+    };
+
+    *CONFIG_OVERRIDES.write().unwrap_or_else(PoisonError::into_inner) =
+        ConfigOverrides { project, user };
+    Ok(())
+}
+
+pub fn config_overrides() -> ConfigOverrides {
+    CONFIG_OVERRIDES.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Locate the project config file under `project_root`, if any. A `--config` file is used
+/// whatever the project.
+pub fn find_project_config(project_root: &Path) -> Option<PathBuf> {
+    if let Some(path) = config_overrides().project {
+        return Some(path);
+    }
+    PROJECT_CONFIG_FILES.iter().map(|name| project_root.join(name)).find(|path| path.exists())
+}
+
+/// Declare the languages listed under `languages:` in the user rules file and then the
+/// project config of the repository containing `dir`, so the project can refine them.
+pub fn declare_configured_languages(dir: &Path) -> Result<()> {
+    declare_languages(&CustomRulesManager::new().load_config()?.languages)?;
+
+    let project_root = git2::Repository::discover(dir)
+        .ok()
+        .and_then(|repo| repo.workdir().map(Path::to_path_buf))
+        .unwrap_or_else(|| dir.to_path_buf());
+    if let Some(config_path) = find_project_config(&project_root) {
+        declare_languages(&load_language_definitions(&config_path)?)?;
+    }
+    Ok(())
+}
+
+/// Project settings read from `patingin.yml`, or from the global `config.yml` for every
+/// project. Other sections of `patingin.yml`, such as `gates:`, `formatting:` or `output:`, are
+/// read by the features they configure, from the project file only.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Config {
-    pub version: String,
-    pub settings: Settings,
+    #[serde(default)]
+    pub rules: RuleSettings,
+    /// Globs of files that are never reviewed, e.g. `deps/**`
+    #[serde(default)]
+    pub ignore_paths: Vec<String>,
+    /// Globs of the only files reviewed, e.g. `lib/**`; every file when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_paths: Vec<String>,
+    #[serde(default)]
+    pub review: ReviewDefaults,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Settings {
-    pub auto_fix: bool,
-    pub severity_threshold: String,
-    pub focus_languages: Vec<String>,
+/// Per-rule settings, keyed by rule id. They apply to every language variant of a rule.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleSettings {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled: Vec<String>,
+    /// Rules that ship disabled but should run in this project; `disabled` wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enabled: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, Severity>,
+    /// Limits of `line_count` and `ratio` rules
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub thresholds: BTreeMap<String, f64>,
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            version: "1.0".to_string(),
-            settings: Settings {
-                auto_fix: false,
-                severity_threshold: "major".to_string(),
-                focus_languages: vec![],
-            },
+impl RuleSettings {
+    pub fn is_empty(&self) -> bool {
+        self.disabled.is_empty()
+            && self.enabled.is_empty()
+            && self.severity.is_empty()
+            && self.thresholds.is_empty()
+    }
+
+    /// Run `id` again: drop it from `disabled`, and list it in `enabled` in case it ships
+    /// disabled
+    pub fn enable(&mut self, id: &str) {
+        self.disabled.retain(|disabled| disabled != id);
+        if !self.enabled.iter().any(|enabled| enabled == id) {
+            self.enabled.push(id.to_string());
+        }
+    }
+
+    pub fn disable(&mut self, id: &str) {
+        self.enabled.retain(|enabled| enabled != id);
+        if !self.disabled.iter().any(|disabled| disabled == id) {
+            self.disabled.push(id.to_string());
+        }
+    }
+
+    /// Drop every setting for `id`; returns whether there was any
+    pub fn reset(&mut self, id: &str) -> bool {
+        let before = self.clone();
+        self.disabled.retain(|disabled| disabled != id);
+        self.enabled.retain(|enabled| enabled != id);
+        self.severity.remove(id);
+        self.thresholds.remove(id);
+        *self != before
+    }
+
+    /// These settings laid over `lower`: a rule enabled or disabled here is no longer
+    /// disabled or enabled there, and severities and thresholds set here replace its own
+    pub fn over(self, lower: RuleSettings) -> RuleSettings {
+        let mut merged = lower;
+        for id in &self.enabled {
+            merged.enable(id);
+        }
+        for id in &self.disabled {
+            merged.disable(id);
+        }
+        merged.severity.extend(self.severity);
+        merged.thresholds.extend(self.thresholds);
+        merged
+    }
+}
+
+/// What `patingin review` reviews, and how it reports and fails, unless flags say otherwise.
+/// Each setting can also come from a `PATINGIN_*` environment variable or git config; see
+/// [`resolve::ResolvedConfig`] for the order they apply in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewDefaults {
+    /// `--preset`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub scope: Option<PresetScope>,
+    /// Reference used by the `merge-base` scope; defaults to the branch upstream
+    #[serde(default)]
+    pub base: Option<String>,
+    /// `--severity`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+    /// `--fail-on`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_on: Option<Severity>,
+    /// `--max-violations`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_violations: Option<usize>,
+    /// `--format`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// `--language`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    /// `--sample`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample: Option<usize>,
+    /// `--no-color`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_color: Option<bool>,
+}
+
+impl ReviewDefaults {
+    /// These settings, with the ones left unset taken from `lower`
+    pub fn or(self, lower: &ReviewDefaults) -> ReviewDefaults {
+        ReviewDefaults {
+            preset: self.preset.or_else(|| lower.preset.clone()),
+            scope: self.scope.or(lower.scope),
+            base: self.base.or_else(|| lower.base.clone()),
+            severity: self.severity.or(lower.severity),
+            fail_on: self.fail_on.or(lower.fail_on),
+            max_violations: self.max_violations.or(lower.max_violations),
+            format: self.format.or_else(|| lower.format.clone()),
+            language: self.language.or_else(|| lower.language.clone()),
+            sample: self.sample.or(lower.sample),
+            no_color: self.no_color.or(lower.no_color),
+        }
+    }
+
+    /// Every setting by its key under `review:`, with its value when set
+    pub fn entries(&self) -> [(&'static str, Option<String>); 10] {
+        fn text(value: &Option<impl ToString>) -> Option<String> {
+            value.as_ref().map(ToString::to_string)
+        }
+        [
+            ("preset", self.preset.clone()),
+            ("scope", self.scope.map(|scope| scope.to_string())),
+            ("base", self.base.clone()),
+            ("severity", text(&self.severity)),
+            ("fail_on", text(&self.fail_on)),
+            ("max_violations", text(&self.max_violations)),
+            ("format", self.format.clone()),
+            ("language", text(&self.language)),
+            ("sample", text(&self.sample)),
+            ("no_color", text(&self.no_color)),
+        ]
+    }
+
+    /// The scope and base as a preset, so they fill unset flags the way presets do
+    pub fn scope_preset(&self, name: &str) -> ReviewPreset {
+        ReviewPreset {
+            name: name.to_string(),
+            description: String::new(),
+            scope: self.scope,
+            base: self.base.clone(),
+            severity: None,
+            fail_on: None,
+            format: None,
+            output: None,
         }
     }
 }
 
 impl Config {
-    #[allow(dead_code)]
-    pub fn load<P: AsRef<Path>>(_path: P) -> Result<Self> {
-        // TODO: Implement config loading
-        Ok(Self::default())
+    /// Read a project config file; an empty file is the defaults
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: Option<Self> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        let config = config.unwrap_or_default();
+
+        validate_globs(&config.ignore_paths, || format!("ignore_paths in {}", path.display()))?;
+        validate_globs(&config.include_paths, || format!("include_paths in {}", path.display()))?;
+        Ok(config)
+    }
+
+    /// The config for the current directory, every source merged; see [`ResolvedConfig`]
+    pub fn load_for_project() -> Result<Self> {
+        Ok(ResolvedConfig::load_current()?.config())
     }
 
-    #[allow(dead_code)]
-    pub fn save<P: AsRef<Path>>(&self, _path: P) -> Result<()> {
-        // TODO: Implement config saving
+    /// This config laid over `lower`: ignored paths add up, include paths and rule and review
+    /// settings set here replace those of `lower`
+    pub fn over(self, lower: Config) -> Config {
+        let mut ignore_paths = lower.ignore_paths;
+        for glob in self.ignore_paths {
+            if !ignore_paths.contains(&glob) {
+                ignore_paths.push(glob);
+            }
+        }
+        Config {
+            rules: self.rules.over(lower.rules),
+            ignore_paths,
+            include_paths: match self.include_paths.is_empty() {
+                true => lower.include_paths,
+                false => self.include_paths,
+            },
+            review: self.review.or(&lower.review),
+        }
+    }
+
+    #[allow(dead_code)] // Used in tests and by library users
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Add `review --ignore` globs to `ignore_paths`, and let `--only` globs replace
+    /// `include_paths`
+    pub fn apply_path_flags(&mut self, ignore: &[String], only: &[String]) -> Result<()> {
+        validate_globs(ignore, || "--ignore".to_string())?;
+        validate_globs(only, || "--only".to_string())?;
+        self.ignore_paths.extend(ignore.iter().cloned());
+        if !only.is_empty() {
+            self.include_paths = only.to_vec();
+        }
         Ok(())
     }
+
+    /// Why `path`, relative to the project root, is not reviewed: it matches `ignore_paths`
+    /// or `include_paths` is set and it matches none of them
+    pub fn path_skip_reason(&self, path: &str) -> Option<FileSkipReason> {
+        if matches_any(&self.ignore_paths, path) {
+            Some(FileSkipReason::IgnoredPath)
+        } else if !self.include_paths.is_empty() && !matches_any(&self.include_paths, path) {
+            Some(FileSkipReason::NotIncluded)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `path`, relative to the project root, is left out of reviews
+    pub fn is_ignored(&self, path: &str) -> bool {
+        self.path_skip_reason(path).is_some()
+    }
+
+    /// Drop the files `ignore_paths` and `include_paths` leave out from `git_diff`, before
+    /// any rule runs, and return their paths with the reason
+    pub fn remove_ignored(&self, git_diff: &mut GitDiff) -> Vec<(String, FileSkipReason)> {
+        let mut ignored = Vec::new();
+        git_diff.files.retain(|file| match self.path_skip_reason(&file.path) {
+            Some(reason) => {
+                ignored.push((file.path.clone(), reason));
+                false
+            }
+            None => true,
+        });
+        git_diff
+            .parse_warnings
+            .retain(|warning| warning.path.as_deref().map_or(true, |path| !self.is_ignored(path)));
+        ignored
+    }
+}
+
+/// Fail on the first glob that does not parse, naming where it came from
+fn validate_globs(globs: &[String], source: impl Fn() -> String) -> Result<()> {
+    for glob in globs {
+        glob::Pattern::new(glob).with_context(|| format!("Invalid {} glob '{glob}'", source()))?;
+    }
+    Ok(())
+}
+
+fn matches_any(globs: &[String], path: &str) -> bool {
+    globs
+        .iter()
+        .filter_map(|glob| glob::Pattern::new(glob).ok())
+        .any(|pattern| pattern.matches(path))
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    use crate::git::FileDiff;
+
+    #[test]
+    fn test_load_project_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(
+            &config_path,
+            "rules:\n  disabled: [comments_overuse]\n  severity:\n    dynamic_atom_creation: major\n  \
+             thresholds:\n    elixir_long_function: 80\nignore_paths:\n  - \"deps/**\"\n\
+             review:\n  scope: staged\ngates:\n  security: critical\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&config_path).unwrap();
+        assert_eq!(config.rules.disabled, vec!["comments_overuse"]);
+        assert_eq!(config.rules.severity["dynamic_atom_creation"], Severity::Major);
+        assert_eq!(config.rules.thresholds["elixir_long_function"], 80.0);
+        assert_eq!(config.review.scope, Some(PresetScope::Staged));
+        assert_eq!(config.review.scope_preset("patingin.yml").scope, Some(PresetScope::Staged));
+
+        std::fs::write(&config_path, "").unwrap();
+        assert_eq!(Config::load(&config_path).unwrap(), Config::default());
+        std::fs::write(&config_path, "ignore_paths: [\"[deps\"]\n").unwrap();
+        assert!(Config::load(&config_path).is_err());
+    }
+
+    #[test]
+    fn test_rule_settings_enable_disable_reset() {
+        let mut settings = RuleSettings::default();
+        settings.disable("leftover_debugger");
+        settings.disable("leftover_debugger");
+        assert_eq!(settings.disabled, vec!["leftover_debugger"]);
+
+        settings.enable("leftover_debugger");
+        assert!(settings.disabled.is_empty());
+        assert_eq!(settings.enabled, vec!["leftover_debugger"]);
+
+        settings.severity.insert("leftover_debugger".to_string(), Severity::Warning);
+        assert!(settings.reset("leftover_debugger"));
+        assert!(settings.is_empty());
+        assert!(!settings.reset("leftover_debugger"));
+    }
+
+    #[test]
+    fn test_remove_ignored_files() {
+        let config = Config { ignore_paths: vec!["deps/**".to_string()], ..Default::default() };
+        let mut git_diff = GitDiff {
+            files: vec![
+                FileDiff::from_region("deps/jason/lib/jason.ex", "x\n", 1..=1),
+                FileDiff::from_region("lib/app.ex", "x\n", 1..=1),
+            ],
+            parse_warnings: Vec::new(),
+        };
+
+        assert_eq!(
+            config.remove_ignored(&mut git_diff),
+            vec![("deps/jason/lib/jason.ex".to_string(), FileSkipReason::IgnoredPath)]
+        );
+        assert_eq!(git_diff.files.len(), 1);
+        assert!(!config.is_ignored("lib/app.ex"));
+    }
+
+    #[test]
+    fn test_path_flags() {
+        let mut config = Config {
+            ignore_paths: vec!["deps/**".to_string()],
+            include_paths: vec!["lib/**".to_string()],
+            ..Default::default()
+        };
+        config
+            .apply_path_flags(
+                &["**/*_generated.ex".to_string()],
+                &["lib/**".to_string(), "test/**".to_string()],
+            )
+            .unwrap();
+
+        assert_eq!(config.path_skip_reason("lib/app.ex"), None);
+        assert_eq!(config.path_skip_reason("test/app_test.exs"), None);
+        assert_eq!(config.path_skip_reason("deps/x/lib/x.ex"), Some(FileSkipReason::IgnoredPath));
+        assert_eq!(
+            config.path_skip_reason("lib/schema_generated.ex"),
+            Some(FileSkipReason::IgnoredPath)
+        );
+        assert_eq!(
+            config.path_skip_reason("priv/repo/seeds.exs"),
+            Some(FileSkipReason::NotIncluded)
+        );
+
+        // Without --only the config's include_paths stay
+        let mut config = Config { include_paths: vec!["lib/**".to_string()], ..Default::default() };
+        config.apply_path_flags(&[], &[]).unwrap();
+        assert!(config.is_ignored("test/app_test.exs"));
+
+        let error = config.apply_path_flags(&["[vendor".to_string()], &[]).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid --ignore glob '[vendor'");
+    }
 }
@@ -0,0 +1,201 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::Severity;
+
+/// Which changes a preset reviews
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PresetScope {
+    /// Staged changes (`git diff --cached`)
+    Staged,
+    /// Unstaged changes (`git diff`)
+    Uncommitted,
+    /// Changes since the last commit (`git diff HEAD`)
+    Head,
+    /// Changes since the merge base with `base` (or the branch upstream)
+    MergeBase,
+    /// Every tracked file in the repository
+    All,
+}
+
+impl std::fmt::Display for PresetScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PresetScope::Staged => write!(f, "staged"),
+            PresetScope::Uncommitted => write!(f, "uncommitted"),
+            PresetScope::Head => write!(f, "head"),
+            PresetScope::MergeBase => write!(f, "merge-base"),
+            PresetScope::All => write!(f, "all"),
+        }
+    }
+}
+
+/// A named bundle of review flags, e.g. `patingin review --preset pre-commit`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewPreset {
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub scope: Option<PresetScope>,
+    /// Reference used by the `merge-base` scope; defaults to the branch upstream
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub severity: Option<Severity>,
+    #[serde(default)]
+    pub fail_on: Option<Severity>,
+    /// Reporter name, as accepted by `--format`
+    #[serde(default)]
+    pub format: Option<String>,
+    /// File the report is written to, when the preset's `format` is the one used
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PresetsSection {
+    #[serde(default)]
+    presets: HashMap<String, ReviewPreset>,
+}
+
+/// Presets shipped with patingin
+pub fn builtin_presets() -> Vec<ReviewPreset> {
+    vec![
+        ReviewPreset {
+            name: "pre-commit".to_string(),
+            description: "Staged changes, critical and major issues, fail on critical".to_string(),
+            scope: Some(PresetScope::Staged),
+            base: None,
+            severity: Some(Severity::Major),
+            fail_on: Some(Severity::Critical),
+            format: None,
+            output: None,
+        },
+        ReviewPreset {
+            name: "pr".to_string(),
+            description: "Branch changes since the merge base with upstream, as markdown"
+                .to_string(),
+            scope: Some(PresetScope::MergeBase),
+            base: None,
+            severity: Some(Severity::Warning),
+            fail_on: None,
+            format: Some("markdown".to_string()),
+            output: None,
+        },
+        ReviewPreset {
+            name: "nightly".to_string(),
+            description: "Whole repository, all severities, HTML report in patingin-report.html"
+                .to_string(),
+            scope: Some(PresetScope::All),
+            base: None,
+            severity: Some(Severity::Info),
+            fail_on: None,
+            format: Some("html".to_string()),
+            output: Some(PathBuf::from("patingin-report.html")),
+        },
+    ]
+}
+
+/// Load presets declared under `presets:` in a project config file
+pub fn load_config_presets(config_path: &Path) -> Result<Vec<ReviewPreset>> {
+    let content = fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    let section: PresetsSection = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse presets in {}", config_path.display()))?;
+
+    let mut presets: Vec<ReviewPreset> = section
+        .presets
+        .into_iter()
+        .map(|(name, mut preset)| {
+            preset.name = name;
+            preset
+        })
+        .collect();
+    presets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(presets)
+}
+
+/// Find a preset by name. Presets from the project config override built-ins of the same name.
+pub fn find_preset(name: &str, project_root: Option<&Path>) -> Result<ReviewPreset> {
+    if let Some(config_path) = project_root.and_then(super::find_project_config) {
+        if let Some(preset) =
+            load_config_presets(&config_path)?.into_iter().find(|preset| preset.name == name)
+        {
+            return Ok(preset);
+        }
+    }
+
+    builtin_presets().into_iter().find(|preset| preset.name == name).ok_or_else(|| {
+        let known: Vec<String> = builtin_presets().into_iter().map(|p| p.name).collect();
+        anyhow::anyhow!("Unknown preset '{name}'. Built-in presets: {}", known.join(", "))
+    })
+}
+
+#[cfg(test)]
+mod presets_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_presets() {
+        let pre_commit = find_preset("pre-commit", None).unwrap();
+        assert_eq!(pre_commit.scope, Some(PresetScope::Staged));
+        assert_eq!(pre_commit.severity, Some(Severity::Major));
+        assert_eq!(pre_commit.fail_on, Some(Severity::Critical));
+
+        let pr = find_preset("pr", None).unwrap();
+        assert_eq!(pr.scope, Some(PresetScope::MergeBase));
+        assert_eq!(pr.format.as_deref(), Some("markdown"));
+
+        let nightly = find_preset("nightly", None).unwrap();
+        assert_eq!(nightly.scope, Some(PresetScope::All));
+        assert_eq!(nightly.format.as_deref(), Some("html"));
+        assert_eq!(nightly.output, Some(PathBuf::from("patingin-report.html")));
+    }
+
+    #[test]
+    fn test_unknown_preset_lists_builtins() {
+        let err = find_preset("weekly", None).unwrap_err().to_string();
+        assert!(err.contains("weekly"));
+        assert!(err.contains("pre-commit"));
+    }
+
+    #[test]
+    fn test_config_presets_extend_and_override_builtins() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("patingin.yml"),
+            r#"
+presets:
+  pre-commit:
+    scope: staged
+    severity: critical
+    fail_on: critical
+  release:
+    description: "Everything since the last tag"
+    scope: merge-base
+    base: v1.0.0
+    format: json
+"#,
+        )
+        .unwrap();
+
+        let overridden = find_preset("pre-commit", Some(temp_dir.path())).unwrap();
+        assert_eq!(overridden.severity, Some(Severity::Critical));
+
+        let release = find_preset("release", Some(temp_dir.path())).unwrap();
+        assert_eq!(release.name, "release");
+        assert_eq!(release.scope, Some(PresetScope::MergeBase));
+        assert_eq!(release.base.as_deref(), Some("v1.0.0"));
+        assert_eq!(release.format.as_deref(), Some("json"));
+
+        // Built-ins not mentioned in the config remain available
+        assert!(find_preset("nightly", Some(temp_dir.path())).is_ok());
+    }
+}
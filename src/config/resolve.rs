@@ -0,0 +1,313 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::git_config::{parse_value, GitConfigDefaults};
+use super::{find_project_config, Config, ReviewDefaults};
+use crate::core::{Language, ProjectDetector};
+
+/// The config file that applies to every project, `~/.config/patingin/config.yml`
+pub fn global_config_path() -> PathBuf {
+    let home_dir = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home_dir).join(".config/patingin/config.yml")
+}
+
+/// Where a setting came from, highest precedence first after the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigSource {
+    /// `PATINGIN_*` environment variables
+    Env,
+    /// The project's `patingin.yml`, or the `--config` file
+    Project,
+    /// `~/.config/patingin/config.yml`
+    Global,
+    /// The `patingin.*` section of git config
+    GitConfig,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Env => write!(f, "env"),
+            ConfigSource::Project => write!(f, "project"),
+            ConfigSource::Global => write!(f, "global"),
+            ConfigSource::GitConfig => write!(f, "git config"),
+        }
+    }
+}
+
+/// One source of settings and, for config files, the file it was read from
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    pub source: ConfigSource,
+    pub path: Option<PathBuf>,
+    pub config: Config,
+}
+
+/// Every source of settings for a run, merged with documented precedence:
+///
+/// 1. command-line flags, applied by each command
+/// 2. `PATINGIN_*` environment variables
+/// 3. the project's `patingin.yml`
+/// 4. the global `~/.config/patingin/config.yml`
+/// 5. the `patingin.*` section of git config
+///
+/// Environment variables and git config only carry `review:` settings. A review `--preset`
+/// sits between the environment and `patingin.yml`. Sections outside `Config`, such as
+/// `gates` or `mentions`, are read from the project file by their own loaders instead.
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    /// Highest precedence first
+    layers: Vec<ConfigLayer>,
+}
+
+impl ResolvedConfig {
+    pub fn new(layers: Vec<ConfigLayer>) -> Self {
+        let mut layers = layers;
+        layers.sort_by_key(|layer| layer.source);
+        Self { layers }
+    }
+
+    /// Every source for the project containing `dir`
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut layers = vec![ConfigLayer {
+            source: ConfigSource::Env,
+            path: None,
+            config: Config {
+                review: env_defaults(|name| std::env::var(name).ok())?,
+                ..Config::default()
+            },
+        }];
+        let project_root = ProjectDetector::detect_project(Some(dir))
+            .map(|info| info.root_path)
+            .unwrap_or_else(|_| dir.to_path_buf());
+        if let Some(path) = find_project_config(&project_root) {
+            layers.push(ConfigLayer {
+                source: ConfigSource::Project,
+                config: Config::load(&path)?,
+                path: Some(path),
+            });
+        }
+        let global_path = global_config_path();
+        if global_path.exists() {
+            layers.push(ConfigLayer {
+                source: ConfigSource::Global,
+                config: Config::load(&global_path)?,
+                path: Some(global_path),
+            });
+        }
+        layers.push(ConfigLayer {
+            source: ConfigSource::GitConfig,
+            path: None,
+            config: Config { review: GitConfigDefaults::load(dir)?.into(), ..Config::default() },
+        });
+        Ok(Self::new(layers))
+    }
+
+    /// Every source for the current directory
+    pub fn load_current() -> Result<Self> {
+        Self::load(&std::env::current_dir()?)
+    }
+
+    pub fn layers(&self) -> &[ConfigLayer] {
+        &self.layers
+    }
+
+    pub fn layer(&self, source: ConfigSource) -> Option<&ConfigLayer> {
+        self.layers.iter().find(|layer| layer.source == source)
+    }
+
+    /// Every layer merged
+    pub fn config(&self) -> Config {
+        merge(&self.layers)
+    }
+
+    /// The layers below `source` merged, for settings something between them overrides
+    pub fn below(&self, source: ConfigSource) -> Config {
+        let lower: Vec<ConfigLayer> =
+            self.layers.iter().filter(|layer| layer.source > source).cloned().collect();
+        merge(&lower)
+    }
+
+    /// Each `review:` setting with its value and the source it came from, unset ones as `None`
+    pub fn review_settings(&self) -> Vec<(&'static str, Option<(String, ConfigSource)>)> {
+        let mut settings: Vec<(&'static str, Option<(String, ConfigSource)>)> =
+            ReviewDefaults::default().entries().iter().map(|(key, _)| (*key, None)).collect();
+        // Lowest first, so each source replaces the ones below it
+        for layer in self.layers.iter().rev() {
+            for ((_, setting), (_, value)) in settings.iter_mut().zip(layer.config.review.entries())
+            {
+                if let Some(value) = value {
+                    *setting = Some((value, layer.source));
+                }
+            }
+        }
+        settings
+    }
+}
+
+fn merge(layers: &[ConfigLayer]) -> Config {
+    layers.iter().rev().fold(Config::default(), |merged, layer| layer.config.clone().over(merged))
+}
+
+/// The `review:` settings given as `PATINGIN_*` variables, read through `var`. Empty
+/// variables count as unset.
+pub fn env_defaults(var: impl Fn(&str) -> Option<String>) -> Result<ReviewDefaults> {
+    let var = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+    let parsed = |name: &str| -> Result<Option<usize>> { parse_env(name, var(name), "a number") };
+
+    Ok(ReviewDefaults {
+        preset: var("PATINGIN_PRESET"),
+        scope: var("PATINGIN_SCOPE")
+            .map(|value| {
+                serde_yaml::from_str(&value).map_err(|_| {
+                    anyhow!(
+                        "Invalid PATINGIN_SCOPE = '{value}': expected one of staged, \
+                         uncommitted, head, merge-base, all"
+                    )
+                })
+            })
+            .transpose()?,
+        base: var("PATINGIN_BASE"),
+        severity: parse_value("PATINGIN_SEVERITY", var("PATINGIN_SEVERITY"))?,
+        fail_on: parse_value("PATINGIN_FAIL_ON", var("PATINGIN_FAIL_ON"))?,
+        max_violations: parsed("PATINGIN_MAX_VIOLATIONS")?,
+        format: var("PATINGIN_FORMAT"),
+        language: var("PATINGIN_LANGUAGE")
+            .map(|value| {
+                Language::from_str(&value)
+                    .map_err(|error| anyhow!("Invalid PATINGIN_LANGUAGE = '{value}': {error}"))
+            })
+            .transpose()?,
+        sample: parsed("PATINGIN_SAMPLE")?,
+        no_color: parse_env("PATINGIN_NO_COLOR", var("PATINGIN_NO_COLOR"), "true or false")?,
+    })
+}
+
+fn parse_env<T: FromStr>(name: &str, value: Option<String>, expected: &str) -> Result<Option<T>> {
+    value
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("Invalid {name} = '{value}': expected {expected}"))
+        })
+        .transpose()
+}
+
+impl From<GitConfigDefaults> for ReviewDefaults {
+    fn from(defaults: GitConfigDefaults) -> Self {
+        ReviewDefaults {
+            preset: defaults.preset,
+            severity: defaults.severity,
+            fail_on: defaults.fail_on,
+            format: defaults.format,
+            language: defaults.language,
+            sample: defaults.sample,
+            no_color: defaults.no_color,
+            ..ReviewDefaults::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod resolve_tests {
+    use super::*;
+    use crate::config::presets::PresetScope;
+    use crate::core::Severity;
+    use std::collections::HashMap;
+
+    fn layer(source: ConfigSource, yaml: &str) -> ConfigLayer {
+        ConfigLayer { source, path: None, config: serde_yaml::from_str(yaml).unwrap() }
+    }
+
+    fn env(vars: &[(&str, &str)]) -> Result<ReviewDefaults> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect();
+        env_defaults(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_higher_sources_win() {
+        let resolved = ResolvedConfig::new(vec![
+            layer(ConfigSource::GitConfig, "review: {severity: warning, format: markdown}"),
+            layer(ConfigSource::Global, "review: {severity: major, fail_on: major, sample: 3}"),
+            layer(ConfigSource::Env, "review: {fail_on: critical}"),
+            layer(ConfigSource::Project, "review: {severity: critical, scope: staged}"),
+        ]);
+
+        let review = resolved.config().review;
+        assert_eq!(review.fail_on, Some(Severity::Critical));
+        assert_eq!(review.severity, Some(Severity::Critical));
+        assert_eq!(review.scope, Some(PresetScope::Staged));
+        assert_eq!(review.sample, Some(3));
+        assert_eq!(review.format.as_deref(), Some("markdown"));
+
+        let below_env = resolved.below(ConfigSource::Env).review;
+        assert_eq!(below_env.fail_on, Some(Severity::Major));
+
+        let settings: HashMap<_, _> = resolved.review_settings().into_iter().collect();
+        assert_eq!(settings["fail_on"], Some(("critical".to_string(), ConfigSource::Env)));
+        assert_eq!(settings["sample"], Some(("3".to_string(), ConfigSource::Global)));
+        assert_eq!(settings["format"], Some(("markdown".to_string(), ConfigSource::GitConfig)));
+        assert_eq!(settings["base"], None);
+    }
+
+    #[test]
+    fn test_project_rules_and_paths_refine_global_ones() {
+        let resolved = ResolvedConfig::new(vec![
+            layer(
+                ConfigSource::Global,
+                "rules: {disabled: [long_line, debug_print], severity: {a: warning}}\n\
+                 ignore_paths: [vendor/**]\ninclude_paths: [src/**]",
+            ),
+            layer(
+                ConfigSource::Project,
+                "rules: {enabled: [long_line], severity: {a: critical}}\n\
+                 ignore_paths: [priv/**, vendor/**]",
+            ),
+        ]);
+
+        let config = resolved.config();
+        assert_eq!(config.rules.disabled, vec!["debug_print"]);
+        assert_eq!(config.rules.enabled, vec!["long_line"]);
+        assert_eq!(config.rules.severity["a"], Severity::Critical);
+        assert_eq!(config.ignore_paths, vec!["vendor/**", "priv/**"]);
+        assert_eq!(config.include_paths, vec!["src/**"]);
+    }
+
+    #[test]
+    fn test_env_variables() {
+        let review = env(&[
+            ("PATINGIN_FAIL_ON", "Major"),
+            ("PATINGIN_SCOPE", "merge-base"),
+            ("PATINGIN_MAX_VIOLATIONS", "10"),
+            ("PATINGIN_LANGUAGE", "python"),
+            ("PATINGIN_NO_COLOR", "true"),
+            ("PATINGIN_FORMAT", ""),
+        ])
+        .unwrap();
+
+        assert_eq!(review.fail_on, Some(Severity::Major));
+        assert_eq!(review.scope, Some(PresetScope::MergeBase));
+        assert_eq!(review.max_violations, Some(10));
+        assert_eq!(review.language, Some(Language::Python));
+        assert_eq!(review.no_color, Some(true));
+        assert_eq!(review.format, None);
+    }
+
+    #[test]
+    fn test_invalid_env_variables_are_named() {
+        let error = env(&[("PATINGIN_SEVERITY", "blocker-ish")]).unwrap_err().to_string();
+        assert!(error.contains("Invalid PATINGIN_SEVERITY = 'blocker-ish'"), "{error}");
+        assert!(error.contains("critical, major, warning"));
+
+        let error = env(&[("PATINGIN_SAMPLE", "many")]).unwrap_err().to_string();
+        assert_eq!(error, "Invalid PATINGIN_SAMPLE = 'many': expected a number");
+
+        let error = env(&[("PATINGIN_SCOPE", "everything")]).unwrap_err().to_string();
+        assert!(error.contains("merge-base"));
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::custom_rules::CustomRulesManager;
+use super::review_engine::{ReviewEngine, ReviewViolation};
+use super::{Language, ProjectDetector};
+use crate::config::{declare_configured_languages, PROJECT_CONFIG_FILES};
+use crate::git::FileDiff;
+
+/// Size and modification time of each watched config file, `None` while it does not exist
+type Fingerprint = Vec<Option<(SystemTime, u64)>>;
+
+/// How often the host could answer from its caches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Analyses answered from the file cache
+    pub hits: usize,
+    /// Analyses that ran the rules
+    pub misses: usize,
+    /// Times a config change rebuilt the rules and dropped the caches
+    pub reloads: usize,
+}
+
+struct CachedAnalysis {
+    content_hash: u64,
+    violations: Vec<ReviewViolation>,
+}
+
+/// Long-lived analysis state for watch, editor and server integrations.
+///
+/// A one-shot `patingin review` loads and compiles every rule, reads the project config
+/// and detects languages from scratch. A host keeps all of that warm between requests and
+/// remembers each file's violations by content, so re-analysing after a save only runs
+/// the rules on files that changed. Before each request it checks the user rules file and
+/// the project config for changes (by size and modification time) and rebuilds everything
+/// when one was edited, created or deleted.
+pub struct AnalysisHost {
+    project_name: String,
+    project_root: PathBuf,
+    watched: Vec<PathBuf>,
+    fingerprint: Fingerprint,
+    engine: ReviewEngine,
+    languages: HashMap<String, Option<Language>>,
+    files: HashMap<String, CachedAnalysis>,
+    stats: CacheStats,
+}
+
+#[allow(dead_code)] // Entry point for integrations embedding the library
+impl AnalysisHost {
+    /// A host for the project at `project_root`, watching its config files and the user
+    /// rules file
+    pub fn new(project_name: &str, project_root: &Path) -> Result<Self> {
+        let mut watched: Vec<PathBuf> =
+            PROJECT_CONFIG_FILES.iter().map(|name| project_root.join(name)).collect();
+        watched.push(CustomRulesManager::new().config_path().to_path_buf());
+        Self::watching(project_name, project_root, watched)
+    }
+
+    /// A host for the project containing the current directory
+    pub fn for_project() -> Result<Self> {
+        let project_info = ProjectDetector::detect_project(None)?;
+        Self::new(&project_info.name, &project_info.root_path)
+    }
+
+    fn watching(project_name: &str, project_root: &Path, watched: Vec<PathBuf>) -> Result<Self> {
+        let fingerprint = fingerprint(&watched);
+        Ok(Self {
+            project_name: project_name.to_string(),
+            project_root: project_root.to_path_buf(),
+            engine: load_engine(project_name, project_root)?,
+            watched,
+            fingerprint,
+            languages: HashMap::new(),
+            files: HashMap::new(),
+            stats: CacheStats::default(),
+        })
+    }
+
+    /// Rebuild the rules and drop every cache if a watched config file changed since the
+    /// last check. Returns whether it did.
+    pub fn refresh(&mut self) -> Result<bool> {
+        let current = fingerprint(&self.watched);
+        if current == self.fingerprint {
+            return Ok(false);
+        }
+        self.engine = load_engine(&self.project_name, &self.project_root)?;
+        self.fingerprint = current;
+        self.languages.clear();
+        self.files.clear();
+        self.stats.reloads += 1;
+        Ok(true)
+    }
+
+    /// Violations in `content`, the current text of `path` (relative to the project root),
+    /// reusing the last result when the text has not changed
+    pub fn analyze(&mut self, path: &str, content: &str) -> Result<&[ReviewViolation]> {
+        self.refresh()?;
+
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        let content_hash = hasher.finish();
+
+        let cached = self.files.get(path).is_some_and(|c| c.content_hash == content_hash);
+        if cached {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+            let file_diff = FileDiff::from_region(path, content, 1..=usize::MAX);
+            let violations = self.engine.review_file_diff(&file_diff)?;
+            self.files.insert(path.to_string(), CachedAnalysis { content_hash, violations });
+        }
+        Ok(&self.files[path].violations)
+    }
+
+    /// Forget `path`, e.g. after it was deleted or renamed
+    pub fn invalidate(&mut self, path: &str) {
+        self.files.remove(path);
+        self.languages.remove(path);
+    }
+
+    /// The language rules treat `path` as, remembered per path
+    pub fn language_of(&mut self, path: &str) -> Option<Language> {
+        let engine = &self.engine;
+        self.languages
+            .entry(path.to_string())
+            .or_insert_with(|| engine.detect_language_from_path(path))
+            .clone()
+    }
+
+    pub fn engine(&self) -> &ReviewEngine {
+        &self.engine
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+fn load_engine(project_name: &str, project_root: &Path) -> Result<ReviewEngine> {
+    declare_configured_languages(project_root)?;
+    ReviewEngine::for_project(project_name, project_root)
+}
+
+fn fingerprint(paths: &[PathBuf]) -> Fingerprint {
+    paths
+        .iter()
+        .map(|path| {
+            let metadata = std::fs::metadata(path).ok()?;
+            Some((metadata.modified().ok()?, metadata.len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod analysis_host_tests {
+    use super::*;
+
+    fn host(root: &Path) -> AnalysisHost {
+        let watched = vec![root.join("patingin.yml"), root.join("rules.yml")];
+        AnalysisHost::watching("analysis-host-test", root, watched).unwrap()
+    }
+
+    #[test]
+    fn test_unchanged_files_are_answered_from_cache() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut host = host(temp_dir.path());
+
+        let first = host.analyze("lib/user.ex", "atom = String.to_atom(input)\n").unwrap().len();
+        let again = host.analyze("lib/user.ex", "atom = String.to_atom(input)\n").unwrap().len();
+        assert_eq!(first, again);
+        assert!(first > 0);
+        assert_eq!(host.stats(), CacheStats { hits: 1, misses: 1, reloads: 0 });
+
+        assert!(host.analyze("lib/user.ex", "atom = :ok\n").unwrap().is_empty());
+        assert_eq!(host.stats().misses, 2);
+
+        host.invalidate("lib/user.ex");
+        host.analyze("lib/user.ex", "atom = :ok\n").unwrap();
+        assert_eq!(host.stats().misses, 3);
+        assert_eq!(host.language_of("lib/user.ex"), Some(Language::Elixir));
+    }
+
+    #[test]
+    fn test_config_change_rebuilds_rules() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut host = host(temp_dir.path());
+        let line = "atom = String.to_atom(input)\n";
+
+        assert!(!host.analyze("lib/user.ex", line).unwrap().is_empty());
+        assert!(!host.refresh().unwrap());
+
+        // Creating the project config turns the security gate off
+        std::fs::write(temp_dir.path().join("patingin.yml"), "gates:\n  security: off\n").unwrap();
+        let violations = host.analyze("lib/user.ex", line).unwrap();
+        assert!(violations.iter().all(|v| !v.rule.tags.contains(&"security".to_string())));
+        assert_eq!(host.stats().reloads, 1);
+        assert_eq!(host.stats().hits, 0);
+
+        // Removing it again is a change too
+        std::fs::remove_file(temp_dir.path().join("patingin.yml")).unwrap();
+        assert!(host.refresh().unwrap());
+        assert!(!host.analyze("lib/user.ex", line).unwrap().is_empty());
+    }
+}
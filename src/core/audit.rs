@@ -0,0 +1,315 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::review_engine::ReviewSummary;
+use crate::config::find_project_config;
+use crate::core::ProjectDetector;
+
+/// `prev` of the first record in a log
+const GENESIS: &str = "0000000000000000000000000000000000000000";
+
+/// The `audit:` section of the project config.
+///
+/// ```yaml
+/// audit:
+///   log: .patingin/audit.jsonl
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AuditConfig {
+    /// Log file, relative to the project root; no log is written when unset
+    pub log: Option<PathBuf>,
+}
+
+impl AuditConfig {
+    /// Read the `audit:` section of a project config file; absent means auditing is off
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct AuditSection {
+            #[serde(default)]
+            audit: AuditConfig,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<AuditSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.map(|section| section.audit).unwrap_or_default())
+    }
+}
+
+/// What a single run decided, before it is chained into the log
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub command: String,
+    pub scope: String,
+    pub ruleset: String,
+    pub summary: ReviewSummary,
+    /// Violations that failed `--fail-on` or their tag gate
+    pub failing: usize,
+}
+
+/// One line of the audit log. `hash` covers every other field, including `prev` (the hash
+/// of the record before it), so editing or deleting a record breaks the chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: String,
+    pub user: String,
+    pub command: String,
+    pub scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+    /// Checksum of the rules that were active, so a run can be tied to an exact rule set
+    pub ruleset: String,
+    pub total_violations: usize,
+    /// Left out when zero, like `info`, so records written before these levels existed
+    /// still hash the same
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub blocker: usize,
+    pub critical: usize,
+    pub major: usize,
+    pub warning: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub info: usize,
+    /// `pass` or `fail`: whether the run exited with a failing status
+    pub decision: String,
+    pub prev: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hash: String,
+}
+
+impl AuditRecord {
+    fn compute_hash(&self) -> Result<String> {
+        let unhashed = AuditRecord { hash: String::new(), ..self.clone() };
+        hash_bytes(&serde_json::to_vec(&unhashed)?)
+    }
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+/// SHA-1 (as a git blob) of `bytes`, hex encoded
+pub fn hash_bytes(bytes: &[u8]) -> Result<String> {
+    Ok(git2::Oid::hash_object(git2::ObjectType::Blob, bytes)?.to_string())
+}
+
+/// Where `patingin audit verify` found the chain broken
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditProblem {
+    /// 1-based line in the log
+    pub line: usize,
+    pub reason: String,
+}
+
+/// An append-only JSONL log of review runs, hash chained for tamper evidence
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The log configured for the detected project, if auditing is enabled
+    pub fn for_project() -> Result<Option<Self>> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(None);
+        };
+        let Some(config_path) = find_project_config(&project_info.root_path) else {
+            return Ok(None);
+        };
+        Ok(AuditConfig::load(&config_path)?
+            .log
+            .map(|log| Self::new(project_info.root_path.join(log))))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Chain `entry` onto the last record and append it
+    pub fn append(&self, entry: AuditEntry) -> Result<AuditRecord> {
+        let prev = match self.read()?.last() {
+            Some(last) => last.hash.clone(),
+            None => GENESIS.to_string(),
+        };
+        let git = std::env::current_dir().ok().and_then(|dir| git2::Repository::discover(dir).ok());
+
+        let mut record = AuditRecord {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            user: current_user(git.as_ref()),
+            command: entry.command,
+            scope: entry.scope,
+            head: git
+                .as_ref()
+                .and_then(|repo| repo.head().ok()?.peel_to_commit().ok())
+                .map(|commit| commit.id().to_string()),
+            ruleset: entry.ruleset,
+            total_violations: entry.summary.total_violations,
+            blocker: entry.summary.blocker_count,
+            critical: entry.summary.critical_count,
+            major: entry.summary.major_count,
+            warning: entry.summary.warning_count,
+            info: entry.summary.info_count,
+            decision: if entry.failing > 0 { "fail" } else { "pass" }.to_string(),
+            prev,
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash()?;
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open audit log {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        Ok(record)
+    }
+
+    /// Every record, oldest first; a missing log is empty
+    pub fn read(&self) -> Result<Vec<AuditRecord>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read audit log {}", self.path.display()))?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    anyhow!("{}:{}: invalid audit record: {e}", self.path.display(), index + 1)
+                })
+            })
+            .collect()
+    }
+
+    /// Check every record's hash and its link to the record before it. Returns the number
+    /// of records checked and any problems found.
+    pub fn verify(&self) -> Result<(usize, Vec<AuditProblem>)> {
+        let mut problems = Vec::new();
+        let mut prev = GENESIS.to_string();
+        let mut checked = 0;
+
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, problems)),
+            Err(e) => return Err(e).context(format!("Failed to read {}", self.path.display())),
+        };
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            checked += 1;
+            let problem = |reason: String| AuditProblem { line: index + 1, reason };
+            let record: AuditRecord = match serde_json::from_str(line) {
+                Ok(record) => record,
+                Err(e) => {
+                    problems.push(problem(format!("not a valid record: {e}")));
+                    continue;
+                }
+            };
+            if record.prev != prev {
+                problems.push(problem("does not follow the record before it".to_string()));
+            }
+            if record.compute_hash()? != record.hash {
+                problems.push(problem("contents do not match its hash".to_string()));
+            }
+            prev = record.hash;
+        }
+        Ok((checked, problems))
+    }
+}
+
+/// `Name <email>` from git config, falling back to the login name
+fn current_user(repo: Option<&git2::Repository>) -> String {
+    let config = match repo {
+        Some(repo) => repo.config().ok(),
+        None => git2::Config::open_default().ok(),
+    };
+    let get = |key: &str| config.as_ref().and_then(|config| config.get_string(key).ok());
+    match (get("user.name"), get("user.email")) {
+        (Some(name), Some(email)) => format!("{name} <{email}>"),
+        (Some(name), None) => name,
+        (None, Some(email)) => email,
+        (None, None) => std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod audit_tests {
+    use super::*;
+
+    fn entry(failing: usize) -> AuditEntry {
+        AuditEntry {
+            command: "review".to_string(),
+            scope: "staged changes".to_string(),
+            ruleset: "abc".to_string(),
+            summary: ReviewSummary { total_violations: 2, critical_count: 1, ..Default::default() },
+            failing,
+        }
+    }
+
+    #[test]
+    fn test_records_are_chained() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let log = AuditLog::new(temp_dir.path().join("audit/log.jsonl"));
+
+        let first = log.append(entry(0)).unwrap();
+        let second = log.append(entry(1)).unwrap();
+
+        assert_eq!(first.prev, GENESIS);
+        assert_eq!(second.prev, first.hash);
+        assert_eq!(second.decision, "fail");
+        assert_eq!(log.read().unwrap(), [first, second]);
+        assert_eq!(log.verify().unwrap(), (2, Vec::new()));
+    }
+
+    #[test]
+    fn test_verify_detects_edits_and_deletions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let log = AuditLog::new(temp_dir.path().join("audit.jsonl"));
+        for failing in [1, 0, 0] {
+            log.append(entry(failing)).unwrap();
+        }
+
+        let content = std::fs::read_to_string(log.path()).unwrap();
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        lines[0] = lines[0].replace("\"decision\":\"fail\"", "\"decision\":\"pass\"");
+        lines.remove(1);
+        std::fs::write(log.path(), lines.join("\n")).unwrap();
+
+        let (checked, problems) = log.verify().unwrap();
+        assert_eq!(checked, 2);
+        assert_eq!(
+            problems[0],
+            AuditProblem { line: 1, reason: "contents do not match its hash".to_string() }
+        );
+        assert_eq!(problems[1].line, 2);
+        assert_eq!(problems[1].reason, "does not follow the record before it");
+    }
+
+    #[test]
+    fn test_audit_is_off_unless_configured() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+
+        std::fs::write(&config_path, "gates:\n  style: off\n").unwrap();
+        assert!(AuditConfig::load(&config_path).unwrap().log.is_none());
+
+        std::fs::write(&config_path, "audit:\n  log: .patingin/audit.jsonl\n").unwrap();
+        assert_eq!(
+            AuditConfig::load(&config_path).unwrap().log,
+            Some(PathBuf::from(".patingin/audit.jsonl"))
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::core::lexer::{Lexer, LineRegions};
+use crate::core::review_engine::ignored_region;
+use crate::core::{AntiPattern, DetectionMethod, ReviewViolation};
+
+/// A rule's deterministic rewrite: matches of `search` on a violating line are replaced
+/// with `replace`, which may refer to capture groups as `$1` or `${name}`. Matches
+/// overlapping a match of `unless` are left alone, for the cases where the rewrite would
+/// change what the code does.
+///
+/// ```yaml
+/// auto_fix:
+///   search: "String\\.to_atom\\("
+///   replace: "String.to_existing_atom("
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoFix {
+    pub search: String,
+    pub replace: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unless: Option<String>,
+}
+
+impl AutoFix {
+    fn regex(&self) -> Result<Regex> {
+        Regex::new(&self.search)
+            .with_context(|| format!("Invalid auto_fix search pattern '{}'", self.search))
+    }
+
+    fn unless_regex(&self) -> Result<Option<Regex>> {
+        self.unless
+            .as_deref()
+            .map(|unless| {
+                Regex::new(unless)
+                    .with_context(|| format!("Invalid auto_fix unless pattern '{unless}'"))
+            })
+            .transpose()
+    }
+
+    /// Check that `search` and `unless` compile
+    pub fn validate(&self) -> Result<()> {
+        self.regex()?;
+        self.unless_regex().map(|_| ())
+    }
+
+    /// `line` with the matches at spans `allowed` accepts rewritten, or `None` when there
+    /// are none
+    pub fn apply(
+        &self,
+        line: &str,
+        allowed: impl Fn(&Range<usize>) -> bool,
+    ) -> Result<Option<String>> {
+        let regex = self.regex()?;
+        let kept: Vec<Range<usize>> = match self.unless_regex()? {
+            Some(unless) => unless.find_iter(line).map(|found| found.range()).collect(),
+            None => Vec::new(),
+        };
+
+        let mut rewritten = String::with_capacity(line.len());
+        let mut end = 0;
+        let mut changed = false;
+        for captures in regex.captures_iter(line) {
+            let span = captures.get(0).map_or(0..0, |found| found.range());
+            if kept.iter().any(|kept| overlap(kept, &span)) || !allowed(&span) {
+                continue;
+            }
+            rewritten.push_str(&line[end..span.start]);
+            captures.expand(&self.replace, &mut rewritten);
+            end = span.end;
+            changed = true;
+        }
+        if !changed {
+            return Ok(None);
+        }
+        rewritten.push_str(&line[end..]);
+        Ok(Some(rewritten))
+    }
+}
+
+fn overlap(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// Whether `rule`'s template may rewrite `span` of `line`: outside the comments and
+/// strings the rule skips, and for regex rules, overlapping a match of the detection
+/// pattern that was not skipped either
+fn rewritable<'a>(
+    rule: &'a AntiPattern,
+    line: &str,
+    regions: &'a LineRegions,
+) -> impl Fn(&Range<usize>) -> bool + 'a {
+    let detected: Option<Vec<Range<usize>>> = match &rule.detection_method {
+        DetectionMethod::Regex { pattern } => Regex::new(pattern).ok().map(|regex| {
+            regex
+                .find_iter(line)
+                .map(|found| found.range())
+                .filter(|span| ignored_region(rule, span, regions).is_none())
+                .collect()
+        }),
+        _ => None,
+    };
+    move |span| {
+        ignored_region(rule, span, regions).is_none()
+            && detected.as_ref().map_or(true, |detected| detected.iter().any(|d| overlap(d, span)))
+    }
+}
+
+/// A file being fixed, lexed up to the line being rewritten
+struct SourceFile {
+    lines: Vec<String>,
+    lexer: Lexer,
+    lexed: usize,
+}
+
+impl SourceFile {
+    /// Regions of `text` standing in for 1-based line `number`. Lines are asked for in
+    /// order, so the file is lexed once.
+    fn regions(&mut self, number: usize, text: &str) -> LineRegions {
+        while self.lexed + 1 < number {
+            self.lexer.line(&self.lines[self.lexed]);
+            self.lexed += 1;
+        }
+        self.lexer.clone().line(text)
+    }
+}
+
+/// One line rewritten by rule templates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimpleFix {
+    pub file_path: String,
+    pub line_number: usize,
+    /// Rules whose templates changed the line, in the order they were applied
+    pub rule_ids: Vec<String>,
+    pub before: String,
+    pub after: String,
+}
+
+/// The rewrites for those of `violations` whose rule has an `auto_fix`, reading the files
+/// under `root`. Templates of several violations on one line apply in turn. Lines that no
+/// longer read as they did in the review, e.g. after a staged-only review or a later edit,
+/// are left alone.
+pub fn plan_fixes(violations: &[ReviewViolation], root: &Path) -> Result<Vec<SimpleFix>> {
+    let mut by_line: BTreeMap<(&str, usize), Vec<&ReviewViolation>> = BTreeMap::new();
+    for violation in violations.iter().filter(|violation| violation.rule.auto_fix.is_some()) {
+        by_line.entry((&violation.file_path, violation.line_number)).or_default().push(violation);
+    }
+
+    let mut files: HashMap<&str, Option<SourceFile>> = HashMap::new();
+    let mut fixes = Vec::new();
+    for ((file_path, line_number), line_violations) in by_line {
+        let file = files.entry(file_path).or_insert_with(|| {
+            let content = fs::read_to_string(root.join(file_path)).ok()?;
+            Some(SourceFile {
+                lines: content.lines().map(str::to_string).collect(),
+                lexer: Lexer::new(&line_violations[0].language),
+                lexed: 0,
+            })
+        });
+        let Some(file) = file else { continue };
+        let Some(before) = line_number.checked_sub(1).and_then(|index| file.lines.get(index))
+        else {
+            continue;
+        };
+        if before.as_str() != line_violations[0].content.trim_end_matches('\r') {
+            continue;
+        }
+        let before = before.clone();
+
+        let mut after = before.clone();
+        let mut rule_ids = Vec::new();
+        for violation in line_violations {
+            let Some(auto_fix) = &violation.rule.auto_fix else { continue };
+            let regions = file.regions(line_number, &after);
+            let allowed = rewritable(&violation.rule, &after, &regions);
+            if let Some(rewritten) = auto_fix.apply(&after, allowed)? {
+                if rewritten != after {
+                    after = rewritten;
+                    rule_ids.push(violation.rule.id.clone());
+                }
+            }
+        }
+        if !rule_ids.is_empty() {
+            fixes.push(SimpleFix {
+                file_path: file_path.to_string(),
+                line_number,
+                rule_ids,
+                before,
+                after,
+            });
+        }
+    }
+    Ok(fixes)
+}
+
+/// Write `fixes` into the files under `root`, keeping each line's ending
+pub fn apply_fixes(fixes: &[SimpleFix], root: &Path) -> Result<()> {
+    let mut by_file: BTreeMap<&str, HashMap<usize, &str>> = BTreeMap::new();
+    for fix in fixes {
+        by_file.entry(&fix.file_path).or_default().insert(fix.line_number, &fix.after);
+    }
+
+    for (file_path, rewrites) in by_file {
+        let path = root.join(file_path);
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut fixed = String::with_capacity(content.len());
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            match rewrites.get(&(index + 1)) {
+                Some(after) => {
+                    let body = line.trim_end_matches(['\n', '\r']);
+                    fixed.push_str(after);
+                    fixed.push_str(&line[body.len()..]);
+                }
+                None => fixed.push_str(line),
+            }
+        }
+        fs::write(&path, fixed).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod autofix_tests {
+    use super::*;
+    use crate::core::{Language, Severity};
+    use crate::report::test_support::violation;
+
+    fn fixable(
+        line_number: usize,
+        content: &str,
+        id: &str,
+        search: &str,
+        replace: &str,
+    ) -> ReviewViolation {
+        let mut violation = violation("lib/a.js", line_number, Severity::Major);
+        violation.content = content.to_string();
+        violation.rule.id = id.to_string();
+        violation.rule.detection_method = DetectionMethod::Regex { pattern: search.to_string() };
+        violation.rule.auto_fix = Some(AutoFix {
+            search: search.to_string(),
+            replace: replace.to_string(),
+            unless: None,
+        });
+        violation.language = Language::JavaScript;
+        violation
+    }
+
+    #[test]
+    fn test_templates_rewrite_matching_lines_in_place() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("lib/a.js");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "if (a == b) {\r\n  x = String.to_atom(y)\r\n}\r\nkeep == me\n").unwrap();
+
+        let equals = r"(^|[^=!<>])(==|!=)($|[^=])";
+        let violations = vec![
+            fixable(1, "if (a == b) {", "double_equals", equals, "${1}${2}=${3}"),
+            fixable(
+                2,
+                "  x = String.to_atom(y)",
+                "atoms",
+                r"String\.to_atom\(",
+                "String.to_existing_atom(",
+            ),
+            fixable(2, "  x = String.to_atom(y)", "never", "nothing", "here"),
+            // Stale: the file no longer has this content on line 4
+            fixable(4, "keep != me", "double_equals", equals, "${1}${2}=${3}"),
+        ];
+
+        let fixes = plan_fixes(&violations, temp_dir.path()).unwrap();
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[0].after, "if (a === b) {");
+        assert_eq!(fixes[1].rule_ids, ["atoms"]);
+
+        apply_fixes(&fixes, temp_dir.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "if (a === b) {\r\n  x = String.to_existing_atom(y)\r\n}\r\nkeep == me\n"
+        );
+    }
+
+    #[test]
+    fn test_rewrites_stay_within_detected_code() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let lines = [
+            r#"if (a == "b == c") { go(); } // d == e"#,
+            "/* a == b",
+            "   c == d */ if (e == f) {}",
+            "if (x == null || y == 1) {}",
+            "if (x == 1 && y == 2) {}",
+        ];
+        fs::create_dir_all(temp_dir.path().join("lib")).unwrap();
+        fs::write(temp_dir.path().join("lib/a.js"), lines.join("\n") + "\n").unwrap();
+
+        let equals = r"(^|[^=!<>])(==|!=)($|[^=])";
+        let strict = |line_number: usize| {
+            let mut violation =
+                fixable(line_number, lines[line_number - 1], "double_equals", equals, "$1$2=$3");
+            violation.rule.skip_comments = true;
+            violation.rule.skip_strings = true;
+            violation.rule.auto_fix.as_mut().unwrap().unless = Some(r"[!=]=\s*null\b".to_string());
+            violation
+        };
+        let mut only_x = fixable(5, lines[4], "x_equals", equals, "$1$2=$3");
+        only_x.rule.detection_method = DetectionMethod::Regex { pattern: r"\bx ==".to_string() };
+
+        let fixes =
+            plan_fixes(&[strict(1), strict(3), strict(4), only_x], temp_dir.path()).unwrap();
+        let after: Vec<&str> = fixes.iter().map(|fix| fix.after.as_str()).collect();
+        assert_eq!(
+            after,
+            [
+                r#"if (a === "b == c") { go(); } // d == e"#,
+                "   c == d */ if (e === f) {}",
+                "if (x == null || y === 1) {}",
+                "if (x === 1 && y == 2) {}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_search_pattern_is_named() {
+        let auto_fix = AutoFix { search: "(".to_string(), replace: String::new(), unless: None };
+        let error = format!("{:#}", auto_fix.validate().unwrap_err());
+        assert!(error.contains("Invalid auto_fix search pattern '('"), "{error}");
+    }
+}
@@ -0,0 +1,292 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::core::{ProjectDetector, ReviewViolation, Severity};
+
+/// Baseline file, relative to the project root
+pub const BASELINE_FILE: &str = ".patingin/baseline.yml";
+
+const HEADER: &str = "\
+# Violations accepted by `patingin baseline`; `patingin review` does not report them.
+# Regenerate with `patingin baseline`, or drop fixed entries with
+# `patingin review --all --update-baseline`.
+";
+
+/// Violations that existed when the baseline was recorded. Entries are matched by
+/// fingerprint, so they survive unrelated edits moving the code around; `rule`, `file` and
+/// `line` only help reading the file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    #[serde(default)]
+    pub violations: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub rule: String,
+    pub file: String,
+    pub line: usize,
+    /// Set when the violation was triaged as an accepted risk; reports then list it as
+    /// acknowledged instead of only counting it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted: Option<AcceptedRisk>,
+}
+
+/// Why and by whom a violation was accepted during triage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AcceptedRisk {
+    pub reason: String,
+    /// `Name <email>` from git config
+    pub author: String,
+    /// `YYYY-MM-DD`
+    pub date: String,
+}
+
+impl AcceptedRisk {
+    /// Accepted today by the git user of the current directory
+    pub fn now(reason: String) -> Self {
+        let config = git2::Repository::discover(".")
+            .and_then(|repo| repo.config())
+            .or_else(|_| git2::Config::open_default());
+        let setting = |key: &str| config.as_ref().ok().and_then(|c| c.get_string(key).ok());
+        let author = match (setting("user.name"), setting("user.email")) {
+            (Some(name), Some(email)) => format!("{name} <{email}>"),
+            (Some(name), None) => name,
+            (None, _) => std::env::var("USER").unwrap_or_else(|_| "unknown".to_string()),
+        };
+        Self { reason, author, date: chrono::Local::now().format("%Y-%m-%d").to_string() }
+    }
+
+    /// Critical and major findings need a reason to be accepted; warnings may go without
+    pub fn requires_reason(severity: Severity) -> bool {
+        severity.is_at_least(Severity::Major)
+    }
+}
+
+/// A violation that was not reported because it is an accepted risk
+#[derive(Debug, Clone)]
+pub struct AcknowledgedViolation {
+    pub violation: ReviewViolation,
+    pub risk: AcceptedRisk,
+}
+
+impl Baseline {
+    pub fn from_violations(violations: &[ReviewViolation]) -> Self {
+        let mut violations: Vec<BaselineEntry> = violations
+            .iter()
+            .map(|violation| BaselineEntry {
+                fingerprint: violation.fingerprint(),
+                rule: violation.rule.id.clone(),
+                file: violation.file_path.clone(),
+                line: violation.line_number,
+                accepted: None,
+            })
+            .collect();
+        violations.sort_by(|a, b| (&a.file, a.line, &a.rule).cmp(&(&b.file, b.line, &b.rule)));
+        Self { violations }
+    }
+
+    /// Where the detected project keeps its baseline, or the current directory's outside one
+    pub fn project_path() -> Result<PathBuf> {
+        let root = match ProjectDetector::detect_project(None) {
+            Ok(project_info) => project_info.root_path,
+            Err(_) => std::env::current_dir()?,
+        };
+        Ok(root.join(BASELINE_FILE))
+    }
+
+    /// Read the baseline at `path`; a missing file is an empty baseline
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let baseline: Option<Self> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(baseline.unwrap_or_default())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = format!("{HEADER}{}", serde_yaml::to_string(self)?);
+        std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Keep the triage decisions of `previous` for the entries that are still present, so
+    /// re-recording the baseline does not forget accepted risks
+    pub fn keep_accepted(&mut self, previous: &Baseline) {
+        let mut accepted: HashMap<&str, Vec<&AcceptedRisk>> = HashMap::new();
+        for entry in &previous.violations {
+            if let Some(risk) = &entry.accepted {
+                accepted.entry(entry.fingerprint.as_str()).or_default().push(risk);
+            }
+        }
+        for entry in &mut self.violations {
+            if let Some(risk) = accepted.get_mut(entry.fingerprint.as_str()).and_then(Vec::pop) {
+                entry.accepted = Some(risk.clone());
+            }
+        }
+    }
+
+    /// Record `violation` as an accepted risk in the baseline at `path`
+    pub fn accept_risk(path: &Path, violation: &ReviewViolation, risk: AcceptedRisk) -> Result<()> {
+        let mut baseline = Self::load(path)?;
+        let mut entry = Self::from_violations(std::slice::from_ref(violation)).violations.remove(0);
+        entry.accepted = Some(risk);
+        baseline.violations.push(entry);
+        baseline.save(path)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Matches violations against this baseline. Each entry absorbs one violation, so a
+    /// second copy of a baselined line is still reported.
+    pub fn matcher(&self) -> BaselineMatcher {
+        let mut remaining: HashMap<String, usize> = HashMap::new();
+        let mut accepted: HashMap<String, Vec<AcceptedRisk>> = HashMap::new();
+        for entry in &self.violations {
+            match &entry.accepted {
+                Some(risk) => {
+                    accepted.entry(entry.fingerprint.clone()).or_default().push(risk.clone())
+                }
+                None => *remaining.entry(entry.fingerprint.clone()).or_default() += 1,
+            }
+        }
+        BaselineMatcher { remaining, accepted }
+    }
+
+    /// Drop the entries `violations` no longer contains, i.e. the fixed ones, and return how
+    /// many were dropped. Nothing is added, so new violations are still reported.
+    pub fn prune(&mut self, violations: &[ReviewViolation]) -> usize {
+        let mut current: HashMap<String, usize> = HashMap::new();
+        for violation in violations {
+            *current.entry(violation.fingerprint()).or_default() += 1;
+        }
+
+        let before = self.violations.len();
+        self.violations.retain(|entry| match current.get_mut(&entry.fingerprint) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        });
+        before - self.violations.len()
+    }
+}
+
+pub struct BaselineMatcher {
+    remaining: HashMap<String, usize>,
+    accepted: HashMap<String, Vec<AcceptedRisk>>,
+}
+
+impl BaselineMatcher {
+    /// Whether `violation` is baselined, using up the entry that matched it
+    pub fn absorbs(&mut self, violation: &ReviewViolation) -> bool {
+        match self.remaining.get_mut(&violation.fingerprint()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => self.acknowledges(violation).is_some(),
+        }
+    }
+
+    /// The accepted risk `violation` matches, using up its entry
+    pub fn acknowledges(&mut self, violation: &ReviewViolation) -> Option<AcceptedRisk> {
+        self.accepted.get_mut(&violation.fingerprint())?.pop()
+    }
+}
+
+#[cfg(test)]
+mod baseline_tests {
+    use super::*;
+    use crate::report::test_support::violation;
+
+    #[test]
+    fn test_baselined_violations_are_absorbed_once() {
+        let old = violation("lib/a.ex", 3, Severity::Critical);
+        let baseline = Baseline::from_violations(std::slice::from_ref(&old));
+
+        let mut moved = old.clone();
+        moved.line_number = 10;
+        let mut copy = old.clone();
+        copy.line_number = 20;
+        let new = violation("lib/b.ex", 1, Severity::Critical);
+
+        let mut matcher = baseline.matcher();
+        assert!(matcher.absorbs(&moved));
+        assert!(!matcher.absorbs(&copy));
+        assert!(!matcher.absorbs(&new));
+    }
+
+    #[test]
+    fn test_prune_drops_fixed_entries_only() {
+        let kept = violation("lib/a.ex", 3, Severity::Critical);
+        let fixed = violation("lib/b.ex", 7, Severity::Major);
+        let mut baseline = Baseline::from_violations(&[kept.clone(), fixed]);
+
+        let new = violation("lib/c.ex", 1, Severity::Warning);
+        assert_eq!(baseline.prune(&[kept.clone(), new]), 1);
+        assert_eq!(baseline, Baseline::from_violations(&[kept]));
+    }
+
+    #[test]
+    fn test_accepted_risks_are_acknowledged_and_kept() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(BASELINE_FILE);
+        let plain = violation("lib/a.ex", 3, Severity::Warning);
+        Baseline::from_violations(std::slice::from_ref(&plain)).save(&path).unwrap();
+
+        let risky = violation("lib/b.ex", 7, Severity::Critical);
+        let risk = AcceptedRisk {
+            reason: "Input is an internal enum".to_string(),
+            author: "Dev <dev@example.com>".to_string(),
+            date: "2026-10-16".to_string(),
+        };
+        Baseline::accept_risk(&path, &risky, risk.clone()).unwrap();
+
+        let baseline = Baseline::load(&path).unwrap();
+        let mut matcher = baseline.matcher();
+        assert_eq!(matcher.acknowledges(&plain), None);
+        assert_eq!(matcher.acknowledges(&risky), Some(risk.clone()));
+        assert!(matcher.absorbs(&plain));
+        assert!(!matcher.absorbs(&risky));
+
+        let mut rerecorded = Baseline::from_violations(&[plain, risky]);
+        rerecorded.keep_accepted(&baseline);
+        assert_eq!(rerecorded.violations.iter().filter_map(|e| e.accepted.clone()).count(), 1);
+        assert!(AcceptedRisk::requires_reason(Severity::Major));
+        assert!(!AcceptedRisk::requires_reason(Severity::Warning));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(BASELINE_FILE);
+        assert!(Baseline::load(&path).unwrap().is_empty());
+
+        let baseline = Baseline::from_violations(&[
+            violation("lib/b.ex", 1, Severity::Major),
+            violation("lib/a.ex", 9, Severity::Critical),
+        ]);
+        baseline.save(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# Violations accepted by `patingin baseline`"));
+        let loaded = Baseline::load(&path).unwrap();
+        assert_eq!(loaded, baseline);
+        assert_eq!(loaded.violations[0].file, "lib/a.ex");
+
+        std::fs::write(&path, "violations: nope\n").unwrap();
+        assert!(Baseline::load(&path).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::config::find_project_config;
+use crate::core::gates::TagGates;
+use crate::core::{ProjectDetector, Severity};
+use crate::git::GitIntegration;
+
+/// Variables CI systems set to the branch being built, which matters when the checkout is
+/// a detached HEAD. A pull request's source branch comes before the ref that was pushed.
+const CI_BRANCH_VARIABLES: &[&str] =
+    &["GITHUB_HEAD_REF", "GITHUB_REF_NAME", "CI_COMMIT_REF_NAME", "BITBUCKET_BRANCH"];
+
+/// Gating for branches whose name matches one of `branches`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct BranchPolicy {
+    /// Globs of branch names, e.g. `release/*`
+    pub branches: Vec<String>,
+    #[serde(default)]
+    pub fail_on: Option<Severity>,
+    #[serde(default)]
+    pub max_violations: Option<usize>,
+    /// Tag gates replacing those `gates:` sets for the same tags
+    #[serde(default)]
+    pub gates: TagGates,
+}
+
+impl BranchPolicy {
+    /// What the policy changes, e.g. "fail on major, at most 0 violation(s), gates style: warning"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(fail_on) = self.fail_on {
+            parts.push(format!("fail on {fail_on}"));
+        }
+        if let Some(max_violations) = self.max_violations {
+            parts.push(format!("at most {max_violations} violation(s)"));
+        }
+        if !self.gates.is_empty() {
+            let gates: Vec<String> =
+                self.gates.tags.iter().map(|(tag, gate)| format!("{tag}: {gate}")).collect();
+            parts.push(format!("gates {}", gates.join(", ")));
+        }
+        if parts.is_empty() {
+            "no changes to the project's gating".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// The `branch_policies:` section of the project config. Policies are checked in order and
+/// the first one with a matching glob applies.
+///
+/// ```yaml
+/// branch_policies:
+///   - branches: ["release/*", "hotfix/*"]
+///     fail_on: major
+///     max_violations: 0
+///     gates:
+///       style: warning
+///   - branches: ["feature/*"]
+///     fail_on: critical
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BranchPolicies(pub Vec<BranchPolicy>);
+
+impl BranchPolicies {
+    /// Read the `branch_policies:` section of a project config file; absent means none
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct BranchPoliciesSection {
+            #[serde(default)]
+            branch_policies: Vec<BranchPolicy>,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<BranchPoliciesSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        let policies = section.map(|section| section.branch_policies).unwrap_or_default();
+
+        for glob in policies.iter().flat_map(|policy| &policy.branches) {
+            glob::Pattern::new(glob).with_context(|| {
+                format!("Invalid branch_policies glob '{glob}' in {}", config_path.display())
+            })?;
+        }
+        Ok(Self(policies))
+    }
+
+    /// The detected project's policies, or none outside a configured project
+    pub fn load_for_project() -> Result<Self> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(Self::default());
+        };
+        match find_project_config(&project_info.root_path) {
+            Some(config_path) => Self::load(&config_path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// The first policy matching `branch`, with the glob that matched
+    pub fn matching(&self, branch: &str) -> Option<(&str, &BranchPolicy)> {
+        self.0.iter().find_map(|policy| {
+            let glob = policy.branches.iter().find(|glob| {
+                glob::Pattern::new(glob).is_ok_and(|pattern| pattern.matches(branch))
+            })?;
+            Some((glob.as_str(), policy))
+        })
+    }
+}
+
+/// The branch being reviewed: the checked-out branch, or on a detached HEAD (as CI checks
+/// out) the branch the CI system names. `None` when neither knows.
+pub fn current_branch() -> Option<String> {
+    let checked_out = GitIntegration::new(".").ok().and_then(|git| git.get_current_branch().ok());
+    checked_out
+        .filter(|branch| branch != "HEAD" && branch != "(no branch)")
+        .or_else(|| ci_branch(|name| std::env::var(name).ok()))
+}
+
+/// The branch named by the first CI variable `lookup` finds set and non-empty
+fn ci_branch(lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    CI_BRANCH_VARIABLES.iter().filter_map(|name| lookup(name)).find(|branch| !branch.is_empty())
+}
+
+#[cfg(test)]
+mod branch_policy_tests {
+    use super::*;
+    use crate::core::gates::Gate;
+
+    #[test]
+    fn test_first_matching_policy_applies() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(
+            &config_path,
+            r#"
+branch_policies:
+  - branches: ["release/*", "main"]
+    fail_on: major
+    max_violations: 0
+    gates:
+      style: warning
+  - branches: ["*"]
+    fail_on: critical
+"#,
+        )
+        .unwrap();
+        let policies = BranchPolicies::load(&config_path).unwrap();
+
+        let (glob, release) = policies.matching("release/2.1").unwrap();
+        assert_eq!(glob, "release/*");
+        assert_eq!(release.fail_on, Some(Severity::Major));
+        assert_eq!(release.gates.tags["style"], Gate::FailAt(Severity::Warning));
+        assert_eq!(
+            release.describe(),
+            "fail on major, at most 0 violation(s), gates style: warning"
+        );
+        assert_eq!(policies.matching("main").unwrap().0, "main");
+
+        let (glob, feature) = policies.matching("feature/login").unwrap();
+        assert_eq!(glob, "*");
+        assert_eq!(feature.describe(), "fail on critical");
+
+        std::fs::write(&config_path, "review:\n  fail_on: major\n").unwrap();
+        assert!(BranchPolicies::load(&config_path).unwrap().matching("main").is_none());
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_globs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(&config_path, "branch_policies:\n  - branches: [\"release/[\"]\n").unwrap();
+
+        let error = format!("{:#}", BranchPolicies::load(&config_path).unwrap_err());
+        assert!(error.contains("Invalid branch_policies glob 'release/['"));
+    }
+
+    #[test]
+    fn test_ci_branch_prefers_the_pull_request_source() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+            }
+        };
+        let pull_request =
+            env(&[("GITHUB_HEAD_REF", "feature/x"), ("GITHUB_REF_NAME", "42/merge")]);
+        assert_eq!(ci_branch(pull_request).as_deref(), Some("feature/x"));
+        // Pushes leave GITHUB_HEAD_REF empty
+        let push = env(&[("GITHUB_HEAD_REF", ""), ("GITHUB_REF_NAME", "release/1.0")]);
+        assert_eq!(ci_branch(push).as_deref(), Some("release/1.0"));
+        assert_eq!(ci_branch(env(&[("CI_COMMIT_REF_NAME", "main")])).as_deref(), Some("main"));
+        assert_eq!(ci_branch(env(&[])), None);
+    }
+}
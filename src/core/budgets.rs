@@ -0,0 +1,183 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::core::{ReviewViolation, Severity};
+
+/// Violations a directory may have per severity before the run fails
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budget {
+    pub glob: String,
+    pub allowed: BTreeMap<Severity, usize>,
+}
+
+/// The `budgets:` section of the project config, mapping path globs to the violations
+/// allowed in them per severity, so legacy code can be cleaned up gradually.
+///
+/// ```yaml
+/// budgets:
+///   "legacy/**": { major: 50, critical: 5 }
+///   "vendor/**": { warning: 100 }
+/// ```
+///
+/// A violation counts against the first budget, in file order, whose glob matches its file
+/// and which lists its severity. Budgeted violations fail the run only by exceeding their
+/// budget; all others follow `--fail-on` and the tag gates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Budgets(pub Vec<Budget>);
+
+/// How much of one severity of a budget a review used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetUsage {
+    pub glob: String,
+    pub severity: Severity,
+    pub used: usize,
+    pub allowed: usize,
+}
+
+impl BudgetUsage {
+    pub fn exceeded(&self) -> bool {
+        self.used > self.allowed
+    }
+
+    /// Violations beyond the allowance
+    pub fn overage(&self) -> usize {
+        self.used.saturating_sub(self.allowed)
+    }
+}
+
+impl std::fmt::Display for BudgetUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}/{} {}", self.glob, self.used, self.allowed, self.severity)
+    }
+}
+
+impl Budgets {
+    /// Read the `budgets:` section of a project config file; absent means no budgets
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct BudgetsSection {
+            #[serde(default)]
+            budgets: serde_yaml::Mapping,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<BudgetsSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        let Some(section) = section else { return Ok(Self::default()) };
+
+        // A mapping rather than a BTreeMap, to keep the order budgets are written in
+        let mut budgets = Vec::new();
+        for (glob, allowed) in section.budgets {
+            let glob = glob.as_str().map(String::from).with_context(|| {
+                format!("budgets keys must be path globs in {}", config_path.display())
+            })?;
+            glob::Pattern::new(&glob).with_context(|| {
+                format!("Invalid budgets glob '{glob}' in {}", config_path.display())
+            })?;
+            let allowed = serde_yaml::from_value(allowed).with_context(|| {
+                format!(
+                    "Invalid budget for '{glob}' in {}; expected counts per severity, e.g. \
+                     {{major: 50}}",
+                    config_path.display()
+                )
+            })?;
+            budgets.push(Budget { glob, allowed });
+        }
+        Ok(Self(budgets))
+    }
+
+    /// Index of the budget `violation` counts against, if any
+    fn budget_of(&self, violation: &ReviewViolation) -> Option<usize> {
+        self.0.iter().position(|budget| {
+            budget.allowed.contains_key(&violation.severity)
+                && glob::Pattern::new(&budget.glob)
+                    .is_ok_and(|pattern| pattern.matches(&violation.file_path))
+        })
+    }
+
+    /// Whether `violation` counts against a budget instead of `--fail-on` and the gates
+    pub fn covers(&self, violation: &ReviewViolation) -> bool {
+        self.budget_of(violation).is_some()
+    }
+
+    /// Usage of every budgeted severity, in the order the budgets are written
+    pub fn usage(&self, violations: &[ReviewViolation]) -> Vec<BudgetUsage> {
+        let mut used: BTreeMap<(usize, Severity), usize> = BTreeMap::new();
+        for violation in violations {
+            if let Some(index) = self.budget_of(violation) {
+                *used.entry((index, violation.severity)).or_default() += 1;
+            }
+        }
+        self.0
+            .iter()
+            .enumerate()
+            .flat_map(|(index, budget)| {
+                let used = &used;
+                budget.allowed.iter().map(move |(severity, allowed)| BudgetUsage {
+                    glob: budget.glob.clone(),
+                    severity: *severity,
+                    used: used.get(&(index, *severity)).copied().unwrap_or(0),
+                    allowed: *allowed,
+                })
+            })
+            .collect()
+    }
+
+    /// Budgeted violations beyond their allowance, which fail the run
+    pub fn overage(&self, violations: &[ReviewViolation]) -> usize {
+        self.usage(violations).iter().map(BudgetUsage::overage).sum()
+    }
+}
+
+#[cfg(test)]
+mod budgets_tests {
+    use super::*;
+    use crate::report::test_support::violation;
+
+    fn load(yaml: &str) -> Result<Budgets> {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(&config_path, yaml).unwrap();
+        Budgets::load(&config_path)
+    }
+
+    #[test]
+    fn test_first_matching_budget_listing_the_severity_applies() {
+        let budgets = load(
+            r#"
+budgets:
+  "legacy/payments/**": { critical: 1 }
+  "legacy/**": { major: 2, critical: 0 }
+"#,
+        )
+        .unwrap();
+        let violations = vec![
+            violation("legacy/payments/charge.ex", 1, Severity::Critical),
+            violation("legacy/payments/charge.ex", 2, Severity::Major),
+            violation("legacy/user.ex", 3, Severity::Major),
+            violation("legacy/user.ex", 4, Severity::Major),
+            violation("lib/user.ex", 5, Severity::Major),
+            violation("legacy/user.ex", 6, Severity::Warning),
+        ];
+
+        let usage: Vec<String> = budgets.usage(&violations).iter().map(|u| u.to_string()).collect();
+        assert_eq!(
+            usage,
+            ["legacy/payments/**: 1/1 critical", "legacy/**: 0/0 critical", "legacy/**: 3/2 major"]
+        );
+        assert_eq!(budgets.overage(&violations), 1);
+        assert!(!budgets.covers(&violations[4]));
+        assert!(!budgets.covers(&violations[5]));
+    }
+
+    #[test]
+    fn test_absent_and_invalid_budgets() {
+        assert!(load("review:\n  fail_on: major\n").unwrap().0.is_empty());
+        assert!(load("").unwrap().0.is_empty());
+        assert!(load("budgets:\n  \"legacy/[\": { major: 1 }\n").is_err());
+        assert!(load("budgets:\n  legacy/**: { huge: 1 }\n").is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use std::path::Path;
+
+use super::languages::CommentSyntax;
+use super::lexer::LineRegions;
+use super::prefilter::required_literals;
+use super::review_engine::ReviewViolation;
+use super::{AntiPattern, DetectionMethod};
+
+/// Rules that measure something (line length, nesting, block length) are not guesses
+const MEASURED: f64 = 0.95;
+/// A pattern without fixed text, such as `\w+\s*\+\s*\w+`, matches plenty of innocent code
+const LEAST_SPECIFIC: f64 = 0.55;
+/// A pattern requiring text such as `String.to_atom` rarely matches anything else
+const MOST_SPECIFIC: f64 = 0.95;
+/// Length of required text from which a pattern counts as fully specific
+const SPECIFIC_LITERAL_LEN: usize = 12;
+/// Ratio rules count occurrences, which says little about any one of them
+const RATIO: f64 = 0.6;
+const OTHER: f64 = 0.5;
+
+/// Code in a comment is not run
+const IN_COMMENT: f64 = 0.4;
+/// Code in a string literal is usually text, not code
+const IN_STRING: f64 = 0.6;
+/// Tests break rules on purpose, e.g. to exercise error paths
+const IN_TEST_FILE: f64 = 0.85;
+
+/// How likely `violation` is a real problem, from 0 to 1: how specific its rule's
+/// detection is, lowered when the match sits in a comment or string literal the rule is
+/// not about, or in a test file. `regions` are the comments and strings of the matched line.
+pub fn score(violation: &ReviewViolation, regions: &LineRegions) -> f64 {
+    let rule = &violation.rule;
+    let mut confidence = method_confidence(rule);
+
+    if let (Some(span), Some(regex)) = (&violation.span, pattern_text(rule)) {
+        let syntax = violation.language.comment_syntax();
+        if regions.in_comment(span) && !mentions_comments(regex, &syntax) {
+            confidence *= IN_COMMENT;
+        } else if regions.in_string(span) && !regex.contains(['"', '\'', '`']) {
+            confidence *= IN_STRING;
+        }
+    }
+    if is_test_file(&violation.file_path) && !rule.tags.iter().any(|tag| tag == "testing") {
+        confidence *= IN_TEST_FILE;
+    }
+    (confidence * 100.0).round() / 100.0
+}
+
+fn method_confidence(rule: &AntiPattern) -> f64 {
+    match &rule.detection_method {
+        DetectionMethod::LineLength { .. }
+        | DetectionMethod::IndentDepth { .. }
+        | DetectionMethod::LineCount { .. } => MEASURED,
+        DetectionMethod::SymlinkReplacement => 1.0,
+        DetectionMethod::Regex { pattern } | DetectionMethod::Multiline { pattern, .. } => {
+            specificity(pattern)
+        }
+        DetectionMethod::Ratio { .. } => RATIO,
+        _ => OTHER,
+    }
+}
+
+/// Confidence from the shortest text every match must contain
+fn specificity(pattern: &str) -> f64 {
+    let literal_len = required_literals(pattern)
+        .and_then(|literals| literals.iter().map(Vec::len).min())
+        .unwrap_or(0)
+        .min(SPECIFIC_LITERAL_LEN);
+    LEAST_SPECIFIC
+        + (MOST_SPECIFIC - LEAST_SPECIFIC) * literal_len as f64 / SPECIFIC_LITERAL_LEN as f64
+}
+
+/// The regex of rules matching text on the line
+fn pattern_text(rule: &AntiPattern) -> Option<&str> {
+    match &rule.detection_method {
+        DetectionMethod::Regex { pattern } | DetectionMethod::Multiline { pattern, .. } => {
+            Some(pattern)
+        }
+        _ => None,
+    }
+}
+
+/// Whether a rule looks for comments itself, e.g. leftover TODOs
+fn mentions_comments(regex: &str, syntax: &CommentSyntax) -> bool {
+    let mentions = |token: &str| regex.contains(token) || regex.contains(&regex::escape(token));
+    syntax.line.as_deref().is_some_and(mentions)
+        || syntax.block.as_ref().is_some_and(|(open, _)| mentions(open))
+}
+
+fn is_test_file(file_path: &str) -> bool {
+    let path = Path::new(file_path);
+    let in_test_dir = path.parent().is_some_and(|dir| {
+        dir.components().any(|component| {
+            matches!(
+                component.as_os_str().to_str(),
+                Some("test" | "tests" | "spec" | "__tests__" | "testing")
+            )
+        })
+    });
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+    in_test_dir
+        || name.starts_with("test_")
+        || [".test.", "_test.", ".spec.", "_spec."].iter().any(|marker| name.contains(marker))
+}
+
+#[cfg(test)]
+mod confidence_tests {
+    use super::*;
+    use crate::core::lexer::Lexer;
+    use crate::core::{Language, Severity};
+    use crate::report::test_support::violation;
+
+    /// `score` with the regions of the matched line on its own
+    fn score_line(violation: &ReviewViolation) -> f64 {
+        score(violation, &Lexer::new(&violation.language).line(&violation.content))
+    }
+
+    fn matched(file: &str, pattern: &str, content: &str) -> ReviewViolation {
+        let mut found = violation(file, 1, Severity::Major);
+        found.rule.detection_method = DetectionMethod::Regex { pattern: pattern.to_string() };
+        found.language = Language::Elixir;
+        found.content = content.to_string();
+        let start = regex::Regex::new(pattern).unwrap().find(content).unwrap();
+        found.span = Some(start.range());
+        found
+    }
+
+    #[test]
+    fn test_specific_patterns_score_higher() {
+        let atoms = matched("lib/a.ex", r"String\.to_atom\(", "String.to_atom(x)");
+        let plus = matched("lib/a.ex", r"\w+\s*\+\s*\w+", "a + b");
+        assert_eq!(score_line(&atoms), 0.95);
+        assert_eq!(score_line(&plus), 0.55);
+
+        let mut long_line = violation("lib/a.ex", 1, Severity::Warning);
+        long_line.rule.detection_method =
+            DetectionMethod::LineLength { max_width: 98, tab_width: 2 };
+        assert_eq!(score_line(&long_line), 0.95);
+    }
+
+    #[test]
+    fn test_comments_strings_and_tests_lower_confidence() {
+        let code = score_line(&matched("lib/a.ex", r"String\.to_atom\(", "String.to_atom(x)"));
+        let commented =
+            score_line(&matched("lib/a.ex", r"String\.to_atom\(", "# avoid String.to_atom(x)"));
+        let quoted =
+            score_line(&matched("lib/a.ex", r"String\.to_atom\(", r#"msg = "String.to_atom(x)""#));
+        let tested =
+            score_line(&matched("test/a_test.exs", r"String\.to_atom\(", "String.to_atom(x)"));
+        assert!(
+            commented < quoted && quoted < tested && tested < code,
+            "{commented} {quoted} {tested} {code}"
+        );
+
+        // A `#` inside a string does not start a comment
+        let interpolated = score_line(&matched(
+            "lib/a.ex",
+            r"String\.to_atom\(",
+            r##""#{a}" <> String.to_atom(x)"##,
+        ));
+        assert_eq!(interpolated, code);
+        // Rules about comments or strings are not marked down for matching them
+        let todo = score_line(&matched("lib/a.ex", r"#\s*TODO", "# TODO: remove"));
+        let sql = score_line(&matched("lib/a.ex", r#""SELECT .*#\{"#, r#"q = "SELECT * #{id}""#));
+        assert_eq!(todo, 0.68);
+        assert_eq!(sql, 0.82);
+    }
+
+    #[test]
+    fn test_test_files() {
+        for path in ["test/user_test.exs", "src/__tests__/a.js", "lib/a.spec.ts", "test_api.py"] {
+            assert!(is_test_file(path), "{path}");
+        }
+        for path in ["lib/testing_helpers.ex", "src/contest.rs", "lib/user.ex"] {
+            assert!(!is_test_file(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_non_ascii_lines() {
+        let found =
+            matched("lib/a.ex", r"String\.to_atom\(", "é = \"ü\\é\" <> String.to_atom(x) # ü");
+        assert_eq!(score_line(&found), 0.95);
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use super::Language;
+
+/// Whether a file from the diff was reviewed, so CI can tell a clean file from one that
+/// was never looked at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCoverage {
+    pub path: String,
+    pub language: Option<Language>,
+    pub status: FileStatus,
+    /// Violations found in the file
+    pub violations: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileStatus {
+    Analyzed,
+    /// Some hunks were too large and only sampled against critical rules
+    PartiallyAnalyzed,
+    Skipped(FileSkipReason),
+}
+
+/// Why a file from the diff was not reviewed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileSkipReason {
+    /// Left out by `--language`
+    LanguageFilter,
+    /// Matches `ignore_paths` in the project config, or `--ignore`
+    IgnoredPath,
+    /// Matches none of `include_paths` in the project config, or of `--only`
+    NotIncluded,
+    /// No enabled rule applies to the file's extension
+    NoRules,
+    /// The change only deletes lines, or only reformats them
+    NoAddedLines,
+    /// The change deletes the file
+    Deleted,
+    /// Binary content, which has no lines to review
+    Binary,
+    /// A symlink that did not replace a file; only its target changed
+    Symlink,
+    /// A Git LFS pointer; the content lives outside the repository
+    LfsPointer,
+    /// The diff section could not be parsed, e.g. a malformed hunk; see `parse_warnings`
+    Unparsed,
+}
+
+impl std::fmt::Display for FileSkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileSkipReason::LanguageFilter => write!(f, "excluded by --language"),
+            FileSkipReason::IgnoredPath => write!(f, "matches ignore_paths or --ignore"),
+            FileSkipReason::NotIncluded => write!(f, "outside include_paths or --only"),
+            FileSkipReason::NoRules => write!(f, "no rules for this file type"),
+            FileSkipReason::NoAddedLines => write!(f, "no added lines"),
+            FileSkipReason::Deleted => write!(f, "deleted"),
+            FileSkipReason::Binary => write!(f, "binary file"),
+            FileSkipReason::Symlink => write!(f, "symlink"),
+            FileSkipReason::LfsPointer => write!(f, "Git LFS pointer"),
+            FileSkipReason::Unparsed => write!(f, "could not be parsed"),
+        }
+    }
+}
@@ -1,37 +1,171 @@
-use super::pattern::{AntiPattern, DetectionMethod, Language, Severity};
-use anyhow::Result;
+use super::autofix::AutoFix;
+use super::languages::LanguageDefinition;
+use super::pattern::{AntiPattern, CodeExample, DetectionMethod, Language, Severity};
+use super::updates::UpdateSettings;
+use crate::config::RuleSettings;
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CustomRulesConfig {
+    /// Languages beyond the built-in ones that project rules may target
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<LanguageDefinition>,
+    /// The "new version available" notice
+    #[serde(default, skip_serializing_if = "UpdateSettings::is_default")]
+    pub updates: UpdateSettings,
+    #[serde(default)]
     pub projects: HashMap<String, ProjectRules>,
 }
 
+/// Team rules file committed to a repository, relative to the project root
+pub const REPO_RULES_FILE: &str = ".patingin/rules.yml";
+
+/// The team rules file: the same rules as a project's entry in the user rules file, shared
+/// through the repository instead of kept per user
+///
+/// ```yaml
+/// rules:
+///   elixir:
+///     - id: no_io_inspect
+///       description: Remove IO.inspect before merging
+///       pattern: "IO\\.inspect"
+///       severity: major
+///       fix: Use Logger.debug
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RepoRules {
+    #[serde(default)]
+    pub rules: HashMap<String, Vec<CustomRule>>, // language -> rules
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProjectRules {
     pub path: String,
     pub git_root: bool,
+    #[serde(default)]
     pub rules: HashMap<String, Vec<CustomRule>>, // language -> rules
+    /// Severity and enablement overrides for built-in and project rules, by rule id
+    #[serde(default, skip_serializing_if = "RuleSettings::is_empty")]
+    pub overrides: RuleSettings,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomRule {
     pub id: String,
     pub description: String,
     pub pattern: String,
     pub severity: String,
     pub fix: String,
+    /// Extra guidance for AI fixes of this rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_hint: Option<String>,
+    /// Rewrite template applied by `review --fix-simple`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_fix: Option<AutoFix>,
+    /// Ignore matches inside comments
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_comments: bool,
+    /// Ignore matches inside string literals
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_strings: bool,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Match `pattern` against the whole file instead of each changed line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_scope: Option<FileScope>,
+    /// Bad and good code shown by `rules --detail`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<CodeExample>,
+}
+
+/// How a whole-file rule is checked; see `DetectionMethod::Multiline`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileScope {
+    /// The rule only fires when this regex matches nowhere near the match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unless: Option<String>,
+    /// Lines around the match that `unless` is searched in; the whole file without it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<usize>,
 }
 
 fn default_enabled() -> bool {
     true
 }
 
+/// The enabled rules of a `language -> rules` map as patterns; languages patingin does not
+/// know are skipped
+fn rules_to_patterns(rules: &HashMap<String, Vec<CustomRule>>) -> Vec<AntiPattern> {
+    let mut patterns = Vec::new();
+    for (language_str, custom_rules) in rules {
+        let Ok(language) = language_str.parse::<Language>() else {
+            continue;
+        };
+
+        for custom_rule in custom_rules.iter().filter(|rule| rule.enabled) {
+            let severity =
+                Severity::from_str(&custom_rule.severity, false).unwrap_or(Severity::Warning);
+
+            patterns.push(AntiPattern {
+                id: format!("custom_{}", custom_rule.id),
+                name: custom_rule.description.clone(),
+                language: language.clone(),
+                severity,
+                description: custom_rule.description.clone(),
+                detection_method: match &custom_rule.file_scope {
+                    Some(scope) => DetectionMethod::Multiline {
+                        pattern: custom_rule.pattern.clone(),
+                        unless: scope.unless.clone(),
+                        window: scope.window,
+                    },
+                    None => DetectionMethod::Regex { pattern: custom_rule.pattern.clone() },
+                },
+                fix_suggestion: custom_rule.fix.clone(),
+                source_url: Some("Custom project rule".to_string()),
+                claude_code_fixable: false,
+                examples: custom_rule.examples.clone(),
+                tags: vec!["custom".to_string()],
+                ai_hint: custom_rule.ai_hint.clone(),
+                auto_fix: custom_rule.auto_fix.clone(),
+                skip_comments: custom_rule.skip_comments,
+                skip_strings: custom_rule.skip_strings,
+                enabled: true,
+            });
+        }
+    }
+    patterns
+}
+
+impl CustomRule {
+    /// Why reviews could not use the rule: an empty id, a pattern that does not compile or
+    /// a severity other than critical, major or warning
+    pub fn validate(&self) -> Result<()> {
+        if self.id.trim().is_empty() {
+            bail!("The rule id is empty");
+        }
+        Regex::new(&self.pattern).with_context(|| format!("Invalid pattern '{}'", self.pattern))?;
+        if let Some(unless) = self.file_scope.as_ref().and_then(|scope| scope.unless.as_ref()) {
+            Regex::new(unless).with_context(|| format!("Invalid unless pattern '{unless}'"))?;
+        }
+        if let Some(auto_fix) = &self.auto_fix {
+            auto_fix.validate()?;
+        }
+        if Severity::from_str(&self.severity, false).is_err() {
+            bail!(
+                "Invalid severity '{}': expected blocker, critical, major, warning or info",
+                self.severity
+            );
+        }
+        Ok(())
+    }
+}
+
 pub struct CustomRulesManager {
     config_path: String,
 }
@@ -43,7 +177,11 @@ impl Default for CustomRulesManager {
 }
 
 impl CustomRulesManager {
+    /// The manager of `~/.config/patingin/rules.yml`, or of the `--user-config` file
     pub fn new() -> Self {
+        if let Some(path) = crate::config::config_overrides().user {
+            return Self { config_path: path.to_string_lossy().into_owned() };
+        }
         let home_dir = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
         let config_path = format!("{home_dir}/.config/patingin/rules.yml");
         Self { config_path }
@@ -54,14 +192,19 @@ impl CustomRulesManager {
         Self { config_path }
     }
 
+    /// The user rules file, whether or not it exists yet
+    pub fn config_path(&self) -> &Path {
+        Path::new(&self.config_path)
+    }
+
     pub fn load_config(&self) -> Result<CustomRulesConfig> {
         if !Path::new(&self.config_path).exists() {
-            return Ok(CustomRulesConfig { projects: HashMap::new() });
+            return Ok(CustomRulesConfig::default());
         }
 
         let content = fs::read_to_string(&self.config_path)?;
-        let config: CustomRulesConfig = serde_yaml::from_str(&content)?;
-        Ok(config)
+        let config: Option<CustomRulesConfig> = serde_yaml::from_str(&content)?;
+        Ok(config.unwrap_or_default())
     }
 
     pub fn save_config(&self, config: &CustomRulesConfig) -> Result<()> {
@@ -89,6 +232,7 @@ impl CustomRulesManager {
                 path: project_path.to_string(),
                 git_root: true,
                 rules: HashMap::new(),
+                overrides: RuleSettings::default(),
             });
 
         let language_key = language.to_string().to_lowercase();
@@ -101,53 +245,116 @@ impl CustomRulesManager {
 
     pub fn get_project_rules(&self, project_name: &str) -> Result<Vec<AntiPattern>> {
         let config = self.load_config()?;
-        let mut patterns = Vec::new();
-
-        if let Some(project_rules) = config.projects.get(project_name) {
-            for (language_str, custom_rules) in &project_rules.rules {
-                let language = match language_str.as_str() {
-                    "elixir" => Language::Elixir,
-                    "javascript" => Language::JavaScript,
-                    "typescript" => Language::TypeScript,
-                    "python" => Language::Python,
-                    "rust" => Language::Rust,
-                    "zig" => Language::Zig,
-                    "sql" => Language::Sql,
-                    _ => continue,
-                };
-
-                for custom_rule in custom_rules {
-                    if custom_rule.enabled {
-                        let severity = match custom_rule.severity.as_str() {
-                            "critical" => Severity::Critical,
-                            "major" => Severity::Major,
-                            "warning" => Severity::Warning,
-                            _ => Severity::Warning,
-                        };
-
-                        let pattern = AntiPattern {
-                            id: format!("custom_{}", custom_rule.id),
-                            name: custom_rule.description.clone(),
-                            language: language.clone(),
-                            severity,
-                            description: custom_rule.description.clone(),
-                            detection_method: DetectionMethod::Regex {
-                                pattern: custom_rule.pattern.clone(),
-                            },
-                            fix_suggestion: custom_rule.fix.clone(),
-                            source_url: Some("Custom project rule".to_string()),
-                            claude_code_fixable: false,
-                            examples: vec![],
-                            tags: vec!["custom".to_string()],
-                            enabled: true,
-                        };
-                        patterns.push(pattern);
-                    }
-                }
-            }
+        Ok(config
+            .projects
+            .get(project_name)
+            .map(|project_rules| rules_to_patterns(&project_rules.rules))
+            .unwrap_or_default())
+    }
+
+    /// The team rules committed to the repository at `project_root`, none without a
+    /// `.patingin/rules.yml`. Unlike the user rules file, every rule must be valid: a typo
+    /// there would silently change the review of everyone on the team.
+    pub fn get_repo_rules(project_root: &Path) -> Result<Vec<AntiPattern>> {
+        let path = project_root.join(REPO_RULES_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let repo_rules: Option<RepoRules> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        let repo_rules = repo_rules.unwrap_or_default();
+        for rule in repo_rules.rules.values().flatten() {
+            rule.validate()
+                .with_context(|| format!("Invalid rule '{}' in {REPO_RULES_FILE}", rule.id))?;
         }
+        Ok(rules_to_patterns(&repo_rules.rules))
+    }
+
+    /// The project's rules from the user rules file and the team rules under `project_root`.
+    /// A team rule replaces the user's rule of the same id and language, the way
+    /// `patingin.yml` wins over the global config, so everyone reviews with the same rule;
+    /// the user's overrides still apply to it.
+    pub fn get_user_and_team_rules(
+        &self,
+        project_name: &str,
+        project_root: Option<&Path>,
+    ) -> Result<(Vec<AntiPattern>, Vec<AntiPattern>)> {
+        let mut user_rules = self.get_project_rules(project_name)?;
+        let team_rules = match project_root {
+            Some(project_root) => Self::get_repo_rules(project_root)?,
+            None => Vec::new(),
+        };
+        user_rules.retain(|rule| {
+            !team_rules.iter().any(|team| team.id == rule.id && team.language == rule.language)
+        });
+        Ok((user_rules, team_rules))
+    }
+
+    /// The project's rule overrides; none for a project without an entry
+    pub fn project_overrides(&self, project_name: &str) -> Result<RuleSettings> {
+        let config = self.load_config()?;
+        Ok(config
+            .projects
+            .get(project_name)
+            .map(|project_rules| project_rules.overrides.clone())
+            .unwrap_or_default())
+    }
+
+    /// Change the project's rule overrides with `update` and save them
+    pub fn update_project_overrides(
+        &self,
+        project_name: &str,
+        project_path: &str,
+        update: impl FnOnce(&mut RuleSettings),
+    ) -> Result<()> {
+        let mut config = self.load_config()?;
+        let project_rules =
+            config.projects.entry(project_name.to_string()).or_insert(ProjectRules {
+                path: project_path.to_string(),
+                git_root: true,
+                rules: HashMap::new(),
+                overrides: RuleSettings::default(),
+            });
+        update(&mut project_rules.overrides);
+        self.save_config(&config)
+    }
 
-        Ok(patterns)
+    /// The project rule with `rule_id` and the language it is listed under
+    pub fn get_project_rule(
+        &self,
+        project_name: &str,
+        rule_id: &str,
+    ) -> Result<Option<(String, CustomRule)>> {
+        let config = self.load_config()?;
+        let Some(project_rules) = config.projects.get(project_name) else {
+            return Ok(None);
+        };
+        Ok(project_rules.rules.iter().find_map(|(language, rules)| {
+            let rule = rules.iter().find(|rule| rule.id == rule_id)?;
+            Some((language.clone(), rule.clone()))
+        }))
+    }
+
+    /// Put `rule` in place of the project rule with `rule_id`; false when there is none
+    pub fn replace_project_rule(
+        &self,
+        project_name: &str,
+        rule_id: &str,
+        rule: CustomRule,
+    ) -> Result<bool> {
+        let mut config = self.load_config()?;
+        let existing = config.projects.get_mut(project_name).and_then(|project_rules| {
+            project_rules.rules.values_mut().flatten().find(|existing| existing.id == rule_id)
+        });
+        let Some(existing) = existing else {
+            return Ok(false);
+        };
+        *existing = rule;
+        self.save_config(&config)?;
+        Ok(true)
     }
 
     pub fn remove_project_rule(&self, project_name: &str, rule_id: &str) -> Result<bool> {
@@ -173,6 +380,29 @@ impl CustomRulesManager {
 
         Ok(found)
     }
+
+    /// Projects whose recorded path no longer exists, e.g. a deleted or moved checkout
+    pub fn projects_with_missing_paths(&self) -> Result<Vec<String>> {
+        let config = self.load_config()?;
+        let mut missing: Vec<String> = config
+            .projects
+            .iter()
+            .filter(|(_, project_rules)| !Path::new(&project_rules.path).exists())
+            .map(|(name, _)| name.clone())
+            .collect();
+        missing.sort();
+        Ok(missing)
+    }
+
+    /// Drop a project's rules and overrides; false when it had no entry
+    pub fn remove_project(&self, project_name: &str) -> Result<bool> {
+        let mut config = self.load_config()?;
+        let found = config.projects.remove(project_name).is_some();
+        if found {
+            self.save_config(&config)?;
+        }
+        Ok(found)
+    }
 }
 
 #[cfg(test)]
@@ -192,6 +422,10 @@ mod custom_rules_tests {
         let (_temp_dir, manager) = setup_test_config();
         let config = manager.load_config().unwrap();
         assert_eq!(config.projects.len(), 0);
+
+        // An empty file, e.g. `--user-config /dev/null` in a hermetic CI job
+        std::fs::write(manager.config_path(), "").unwrap();
+        assert_eq!(manager.load_config().unwrap().projects.len(), 0);
     }
 
     #[test]
@@ -204,7 +438,13 @@ mod custom_rules_tests {
             pattern: r"console\.log\(".to_string(),
             severity: "warning".to_string(),
             fix: "Use proper logging library".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
+            file_scope: None,
+            examples: vec![],
         };
 
         manager
@@ -233,7 +473,13 @@ mod custom_rules_tests {
             pattern: r"console\.log\(".to_string(),
             severity: "warning".to_string(),
             fix: "Use proper logging library".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
+            file_scope: None,
+            examples: vec![],
         };
 
         let elixir_rule = CustomRule {
@@ -242,7 +488,13 @@ mod custom_rules_tests {
             pattern: r"GenServer\.call.*:sync".to_string(),
             severity: "major".to_string(),
             fix: "Use async GenServer.cast".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
+            file_scope: None,
+            examples: vec![],
         };
 
         manager
@@ -277,7 +529,13 @@ mod custom_rules_tests {
             pattern: "test".to_string(),
             severity: "warning".to_string(),
             fix: "Fix test".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
+            file_scope: None,
+            examples: vec![],
         };
 
         manager.add_project_rule("my-app", "/path", Language::JavaScript, custom_rule).unwrap();
@@ -299,6 +557,73 @@ mod custom_rules_tests {
         assert!(!removed);
     }
 
+    #[test]
+    fn test_replace_project_rule() {
+        let (_temp_dir, manager) = setup_test_config();
+        let rule = CustomRule {
+            id: "no_print".to_string(),
+            description: "No print".to_string(),
+            pattern: r"print\(".to_string(),
+            severity: "warning".to_string(),
+            fix: "Use logging".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
+            enabled: true,
+            file_scope: None,
+            examples: vec![],
+        };
+        manager.add_project_rule("my-app", "/path", Language::Python, rule.clone()).unwrap();
+
+        let (language, found) = manager.get_project_rule("my-app", "no_print").unwrap().unwrap();
+        assert_eq!((language.as_str(), found.pattern.as_str()), ("python", r"print\("));
+        assert!(manager.get_project_rule("my-app", "other").unwrap().is_none());
+        assert!(manager.get_project_rule("other-app", "no_print").unwrap().is_none());
+
+        let edited = CustomRule { severity: "major".to_string(), ..rule.clone() };
+        assert!(manager.replace_project_rule("my-app", "no_print", edited).unwrap());
+        assert!(!manager.replace_project_rule("my-app", "other", rule).unwrap());
+        let patterns = manager.get_project_rules("my-app").unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].severity, Severity::Major);
+    }
+
+    #[test]
+    fn test_validate_rule() {
+        let rule = CustomRule {
+            id: "no_print".to_string(),
+            description: "No print".to_string(),
+            pattern: r"print\(".to_string(),
+            severity: "warning".to_string(),
+            fix: "Use logging".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
+            enabled: true,
+            file_scope: None,
+            examples: vec![],
+        };
+        assert!(rule.validate().is_ok());
+
+        let error = |rule: CustomRule| rule.validate().unwrap_err().to_string();
+        assert_eq!(
+            error(CustomRule { id: " ".to_string(), ..rule.clone() }),
+            "The rule id is empty"
+        );
+        assert_eq!(
+            error(CustomRule { pattern: "print(".to_string(), ..rule.clone() }),
+            "Invalid pattern 'print('"
+        );
+        assert_eq!(
+            error(CustomRule { severity: "urgent".to_string(), ..rule.clone() }),
+            "Invalid severity 'urgent': expected blocker, critical, major, warning or info"
+        );
+        let file_scope = Some(FileScope { unless: Some("[".to_string()), window: None });
+        assert_eq!(error(CustomRule { file_scope, ..rule }), "Invalid unless pattern '['");
+    }
+
     #[test]
     fn test_disabled_rules_not_loaded() {
         let (_temp_dir, manager) = setup_test_config();
@@ -309,7 +634,13 @@ mod custom_rules_tests {
             pattern: "disabled".to_string(),
             severity: "warning".to_string(),
             fix: "Should not appear".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: false,
+            file_scope: None,
+            examples: vec![],
         };
 
         manager.add_project_rule("my-app", "/path", Language::JavaScript, disabled_rule).unwrap();
@@ -328,7 +659,13 @@ mod custom_rules_tests {
             pattern: "persist".to_string(),
             severity: "major".to_string(),
             fix: "Should be saved".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
+            file_scope: None,
+            examples: vec![],
         };
 
         manager
@@ -344,4 +681,134 @@ mod custom_rules_tests {
         assert_eq!(patterns[0].language, Language::Python);
         assert_eq!(patterns[0].severity, Severity::Major);
     }
+
+    #[test]
+    fn test_project_overrides_round_trip() {
+        let (_temp_dir, manager) = setup_test_config();
+        assert!(manager.project_overrides("app").unwrap().is_empty());
+
+        manager
+            .update_project_overrides("app", "/code/app", |overrides| {
+                overrides.disable("console_log_production");
+                overrides.severity.insert("leftover_debugger".to_string(), Severity::Critical);
+            })
+            .unwrap();
+
+        let overrides = manager.project_overrides("app").unwrap();
+        assert_eq!(overrides.disabled, vec!["console_log_production"]);
+        assert_eq!(overrides.severity["leftover_debugger"], Severity::Critical);
+        assert!(manager.project_overrides("other").unwrap().is_empty());
+        assert!(manager.get_project_rules("app").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_projects_with_missing_paths() {
+        let (temp_dir, manager) = setup_test_config();
+        let rule = |id: &str| CustomRule {
+            id: id.to_string(),
+            description: id.to_string(),
+            pattern: id.to_string(),
+            severity: "warning".to_string(),
+            fix: String::new(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
+            enabled: true,
+            file_scope: None,
+            examples: vec![],
+        };
+        let live = temp_dir.path().to_string_lossy().to_string();
+        manager.add_project_rule("live", &live, Language::Elixir, rule("a")).unwrap();
+        manager.add_project_rule("gone", "/no/such/checkout", Language::Elixir, rule("b")).unwrap();
+
+        assert_eq!(manager.projects_with_missing_paths().unwrap(), vec!["gone"]);
+        assert!(manager.remove_project("gone").unwrap());
+        assert!(!manager.remove_project("gone").unwrap());
+        assert!(manager.projects_with_missing_paths().unwrap().is_empty());
+        assert_eq!(manager.get_project_rules("live").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_file_scope_rules_match_the_whole_file() {
+        let (_temp_dir, manager) = setup_test_config();
+        let yaml = r#"
+projects:
+  app:
+    path: /code/app
+    git_root: true
+    rules:
+      elixir:
+        - id: moduledoc
+          description: Modules need a @moduledoc
+          pattern: '^defmodule '
+          severity: warning
+          fix: Add a @moduledoc
+          file_scope:
+            unless: '@moduledoc'
+"#;
+        fs::write(&manager.config_path, yaml).unwrap();
+
+        let patterns = manager.get_project_rules("app").unwrap();
+        assert!(matches!(
+            &patterns[0].detection_method,
+            DetectionMethod::Multiline { pattern, unless: Some(unless), window: None }
+                if pattern == "^defmodule " && unless == "@moduledoc"
+        ));
+    }
+
+    #[test]
+    fn test_team_rules_replace_user_rules_with_the_same_id() {
+        let (temp_dir, manager) = setup_test_config();
+        let user = r#"
+projects:
+  app:
+    path: /code/app
+    git_root: true
+    rules:
+      elixir:
+        - { id: no_inspect, description: Mine, pattern: 'IO\.inspect', severity: warning, fix: Remove it }
+        - { id: no_pry, description: No pry, pattern: 'IEx\.pry', severity: major, fix: Remove it }
+"#;
+        fs::write(&manager.config_path, user).unwrap();
+        let root = temp_dir.path().join("app");
+        assert!(manager.get_user_and_team_rules("app", Some(&root)).unwrap().1.is_empty());
+
+        fs::create_dir_all(root.join(".patingin")).unwrap();
+        let team = r#"
+rules:
+  elixir:
+    - { id: no_inspect, description: Team, pattern: 'IO\.inspect', severity: critical, fix: Use Logger }
+  python:
+    - { id: no_print, description: No print, pattern: 'print\(', severity: major, fix: Use logging }
+"#;
+        fs::write(root.join(REPO_RULES_FILE), team).unwrap();
+
+        let (user_rules, team_rules) = manager.get_user_and_team_rules("app", Some(&root)).unwrap();
+        let ids: Vec<_> = user_rules.iter().map(|rule| rule.id.as_str()).collect();
+        assert_eq!(ids, ["custom_no_pry"]);
+        assert_eq!(team_rules.len(), 2);
+        let inspect = team_rules.iter().find(|rule| rule.id == "custom_no_inspect").unwrap();
+        assert_eq!((inspect.name.as_str(), inspect.severity), ("Team", Severity::Critical));
+
+        // Without a project root only the user's rules load
+        let (user_rules, team_rules) = manager.get_user_and_team_rules("app", None).unwrap();
+        assert_eq!((user_rules.len(), team_rules.len()), (2, 0));
+    }
+
+    #[test]
+    fn test_invalid_team_rules_are_named() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join(".patingin")).unwrap();
+        let team = r#"
+rules:
+  elixir:
+    - { id: broken, description: Broken, pattern: '(', severity: major, fix: Fix it }
+"#;
+        fs::write(temp_dir.path().join(REPO_RULES_FILE), team).unwrap();
+
+        let error =
+            format!("{:#}", CustomRulesManager::get_repo_rules(temp_dir.path()).unwrap_err());
+        assert!(error.contains("Invalid rule 'broken' in .patingin/rules.yml"), "{error}");
+    }
 }
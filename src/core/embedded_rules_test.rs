@@ -120,7 +120,7 @@ mod embedded_rules_tests {
         
         // Test lookup performance with compiled patterns
         let start = Instant::now();
-        let _compiled_pattern = registry.get_compiled_pattern("dynamic_atom_creation");
+        let _compiled_pattern = registry.get_compiled_variant("dynamic_atom_creation", &Language::Elixir);
         let duration = start.elapsed();
         
         assert!(duration.as_micros() < 10, "Compiled pattern lookup should be < 10 microseconds");
@@ -0,0 +1,254 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::audit::hash_bytes;
+
+/// Backup of the last fix run, relative to the project root
+pub const BACKUP_DIR: &str = ".patingin/fix-backup";
+
+const MANIFEST: &str = "manifest.json";
+
+/// Contents of the files a fix run may write, read before it writes them.
+///
+/// Once the run is over, `save` keeps the files it changed in `.patingin/fix-backup/`,
+/// replacing the backup of the previous run, so `patingin fix --rollback` can put them back
+/// if the fixes broke the build.
+#[derive(Debug)]
+pub struct FixBackup {
+    root: PathBuf,
+    /// `None` for files that did not exist
+    files: BTreeMap<String, Option<Vec<u8>>>,
+}
+
+/// One file in a saved backup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackedUpFile {
+    /// Path relative to the project root
+    pub path: String,
+    /// Whether the file existed before the fixes; rolling back removes it otherwise
+    pub existed: bool,
+    /// Hash of the file as the fixes left it, `None` if they removed it, to notice later
+    /// edits a rollback would throw away
+    pub fixed_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Manifest {
+    created_at: String,
+    command: String,
+    files: Vec<BackedUpFile>,
+}
+
+/// The backup `save` left on disk
+#[derive(Debug)]
+pub struct SavedFixBackup {
+    dir: PathBuf,
+    root: PathBuf,
+    manifest: Manifest,
+}
+
+impl FixBackup {
+    /// Read `paths`, relative to `root`, before fixes are written to them
+    pub fn take<'a>(root: &Path, paths: impl IntoIterator<Item = &'a str>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| (path.to_string(), fs::read(root.join(path)).ok()))
+            .collect();
+        Self { root: root.to_path_buf(), files }
+    }
+
+    /// Keep the files that changed since `take` as the backup of the run `command`. Returns
+    /// `None`, leaving the previous backup alone, when nothing changed.
+    pub fn save(self, command: &str) -> Result<Option<SavedFixBackup>> {
+        let mut changed = Vec::new();
+        for (path, before) in &self.files {
+            let after = fs::read(self.root.join(path)).ok();
+            if after != *before {
+                changed.push((path, before, after));
+            }
+        }
+        if changed.is_empty() {
+            return Ok(None);
+        }
+
+        // Written next to the old backup and swapped in, so a failed save keeps the old one
+        let dir = self.root.join(BACKUP_DIR);
+        let staging = dir.with_extension("new");
+        let _ = fs::remove_dir_all(&staging);
+        fs::create_dir_all(staging.join("files"))
+            .with_context(|| format!("Failed to create {}", staging.display()))?;
+        fs::write(staging.join(".gitignore"), "*\n")?;
+
+        let mut files = Vec::new();
+        for (index, (path, before, after)) in changed.into_iter().enumerate() {
+            if let Some(before) = before {
+                fs::write(staging.join("files").join(index.to_string()), before)?;
+            }
+            files.push(BackedUpFile {
+                path: path.clone(),
+                existed: before.is_some(),
+                fixed_hash: after.as_deref().map(hash_bytes).transpose()?,
+            });
+        }
+        let manifest = Manifest {
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            command: command.to_string(),
+            files,
+        };
+        fs::write(staging.join(MANIFEST), serde_json::to_string_pretty(&manifest)?)?;
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::rename(&staging, &dir)
+            .with_context(|| format!("Failed to move the fix backup to {}", dir.display()))?;
+        Ok(Some(SavedFixBackup { dir, root: self.root, manifest }))
+    }
+
+    /// Put the files back as `take` read them, without keeping a backup
+    pub fn restore(&self) -> Result<()> {
+        for (path, before) in &self.files {
+            put_back(&self.root.join(path), before.as_deref())?;
+        }
+        Ok(())
+    }
+}
+
+/// Write `content` to `path`, or remove it for files that did not exist
+fn put_back(path: &Path, content: Option<&[u8]>) -> Result<()> {
+    match content {
+        Some(content) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content)
+                .with_context(|| format!("Failed to restore {}", path.display()))
+        }
+        None if path.exists() => {
+            fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))
+        }
+        None => Ok(()),
+    }
+}
+
+impl SavedFixBackup {
+    /// The backup of the last fix run under `root`, if there is one
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let dir = root.join(BACKUP_DIR);
+        let manifest_path = dir.join(MANIFEST);
+        if !manifest_path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let manifest = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+        Ok(Some(Self { dir, root: root.to_path_buf(), manifest }))
+    }
+
+    /// When the fixes were applied, in RFC 3339
+    pub fn created_at(&self) -> &str {
+        &self.manifest.created_at
+    }
+
+    /// The command that applied them
+    pub fn command(&self) -> &str {
+        &self.manifest.command
+    }
+
+    pub fn files(&self) -> &[BackedUpFile] {
+        &self.manifest.files
+    }
+
+    /// Files edited since the fixes were applied, whose edits a rollback would lose
+    pub fn edited_since(&self) -> Vec<&str> {
+        self.manifest
+            .files
+            .iter()
+            .filter(|file| {
+                let current = fs::read(self.root.join(&file.path)).ok();
+                current.as_deref().map(hash_bytes).transpose().ok() != Some(file.fixed_hash.clone())
+            })
+            .map(|file| file.path.as_str())
+            .collect()
+    }
+
+    /// Put every file back as it was before the fixes and remove the backup. Refuses when
+    /// files were edited since, unless `force` is set.
+    pub fn restore(self, force: bool) -> Result<Vec<BackedUpFile>> {
+        let edited = self.edited_since();
+        if !edited.is_empty() && !force {
+            bail!(
+                "{} changed since the fixes were applied; rolling back would lose those \
+                 edits (use --force to roll back anyway)",
+                edited.join(", ")
+            );
+        }
+
+        for (index, file) in self.manifest.files.iter().enumerate() {
+            let content = match file.existed {
+                true => Some(
+                    fs::read(self.dir.join("files").join(index.to_string()))
+                        .with_context(|| format!("The backup of {} is missing", file.path))?,
+                ),
+                false => None,
+            };
+            put_back(&self.root.join(&file.path), content.as_deref())?;
+        }
+        fs::remove_dir_all(&self.dir)
+            .with_context(|| format!("Failed to remove {}", self.dir.display()))?;
+        Ok(self.manifest.files)
+    }
+}
+
+#[cfg(test)]
+mod fix_backup_tests {
+    use super::*;
+
+    #[test]
+    fn test_rollback_restores_changed_files_only() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::write(root.join("lib/a.ex"), "IO.inspect(x)\n").unwrap();
+        fs::write(root.join("lib/b.ex"), "untouched\n").unwrap();
+
+        let backup = FixBackup::take(root, ["lib/a.ex", "lib/b.ex", "lib/new.ex"]);
+        fs::write(root.join("lib/a.ex"), "x\n").unwrap();
+        fs::write(root.join("lib/new.ex"), "created\n").unwrap();
+        let saved = backup.save("patingin review --fix-simple").unwrap().unwrap();
+
+        let saved_paths: Vec<&str> = saved.files().iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(saved_paths, ["lib/a.ex", "lib/new.ex"]);
+        assert!(saved.edited_since().is_empty());
+
+        let loaded = SavedFixBackup::load(root).unwrap().unwrap();
+        assert_eq!(loaded.command(), "patingin review --fix-simple");
+        loaded.restore(false).unwrap();
+        assert_eq!(fs::read_to_string(root.join("lib/a.ex")).unwrap(), "IO.inspect(x)\n");
+        assert_eq!(fs::read_to_string(root.join("lib/b.ex")).unwrap(), "untouched\n");
+        assert!(!root.join("lib/new.ex").exists());
+        assert!(SavedFixBackup::load(root).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rollback_refuses_to_lose_later_edits() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("a.js"), "console.log(1)\n").unwrap();
+
+        let backup = FixBackup::take(root, ["a.js"]);
+        assert!(FixBackup::take(root, ["a.js"]).save("unchanged").unwrap().is_none());
+        fs::write(root.join("a.js"), "\n").unwrap();
+        backup.save("patingin review --fix").unwrap();
+        fs::write(root.join("a.js"), "edited after the fix\n").unwrap();
+
+        let saved = SavedFixBackup::load(root).unwrap().unwrap();
+        assert_eq!(saved.edited_since(), ["a.js"]);
+        assert!(saved.restore(false).unwrap_err().to_string().contains("a.js changed"));
+
+        SavedFixBackup::load(root).unwrap().unwrap().restore(true).unwrap();
+        assert_eq!(fs::read_to_string(root.join("a.js")).unwrap(), "console.log(1)\n");
+    }
+}
@@ -0,0 +1,331 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::core::Language;
+
+/// Width limits used by the `line_length` and `indent_depth` detection methods.
+///
+/// Every field is optional so the same struct can describe a rule's defaults as well as
+/// per-language and per-path overrides layered on top of them.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct FormattingLimits {
+    pub max_line_width: Option<usize>,
+    pub max_indent_depth: Option<usize>,
+    pub tab_width: Option<usize>,
+    pub indent_width: Option<usize>,
+}
+
+impl FormattingLimits {
+    /// Values set in `other` win over values set in `self`
+    fn overlay(&self, other: &FormattingLimits) -> FormattingLimits {
+        FormattingLimits {
+            max_line_width: other.max_line_width.or(self.max_line_width),
+            max_indent_depth: other.max_indent_depth.or(self.max_indent_depth),
+            tab_width: other.tab_width.or(self.tab_width),
+            indent_width: other.indent_width.or(self.indent_width),
+        }
+    }
+}
+
+/// Limits that apply to files matching a glob, e.g. generated code or migrations
+#[derive(Debug, Clone, Deserialize)]
+pub struct PathFormatting {
+    pub glob: String,
+    #[serde(flatten)]
+    pub limits: FormattingLimits,
+}
+
+/// The `formatting:` section of the project config.
+///
+/// ```yaml
+/// formatting:
+///   max_line_width: 100
+///   tab_width: 4
+///   languages:
+///     python: { max_line_width: 88 }
+///   paths:
+///     - glob: "priv/repo/migrations/**"
+///       max_line_width: 160
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FormattingConfig {
+    #[serde(flatten)]
+    pub defaults: FormattingLimits,
+    #[serde(default)]
+    pub languages: BTreeMap<String, FormattingLimits>,
+    #[serde(default)]
+    pub paths: Vec<PathFormatting>,
+}
+
+impl FormattingConfig {
+    /// Read the `formatting:` section of a project config file; absent means no overrides.
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct FormattingSection {
+            #[serde(default)]
+            formatting: FormattingConfig,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: FormattingSection = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.formatting)
+    }
+
+    /// Effective limits for a file: project defaults, then the language, then the last
+    /// matching path entry.
+    pub fn resolve(&self, file_path: &str, language: &Language) -> FormattingLimits {
+        let mut limits = self.defaults.clone();
+        if let Some(language_limits) = self.languages.get(&language.to_string()) {
+            limits = limits.overlay(language_limits);
+        }
+        for path_formatting in &self.paths {
+            let matches = glob::Pattern::new(&path_formatting.glob)
+                .map(|pattern| pattern.matches(file_path))
+                .unwrap_or(false);
+            if matches {
+                limits = limits.overlay(&path_formatting.limits);
+            }
+        }
+        limits
+    }
+}
+
+/// Columns a character occupies in a terminal or editor.
+///
+/// Combining marks and zero-width characters take no space, East Asian wide/fullwidth
+/// characters and emoji take two; everything else takes one.
+pub fn char_width(ch: char) -> usize {
+    let code = ch as u32;
+    match code {
+        0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F
+        | 0xFEFF => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Display width of `text`, expanding tabs to the next multiple of `tab_width`
+pub fn display_width(text: &str, tab_width: usize) -> usize {
+    let tab_width = tab_width.max(1);
+    text.chars().fold(0, |column, ch| {
+        if ch == '\t' {
+            column + tab_width - column % tab_width
+        } else {
+            column + char_width(ch)
+        }
+    })
+}
+
+/// Nesting level of a line: the width of its leading whitespace in units of `indent_width`
+pub fn indent_depth(line: &str, indent_width: usize, tab_width: usize) -> usize {
+    let leading: String = line.chars().take_while(|ch| *ch == ' ' || *ch == '\t').collect();
+    display_width(&leading, tab_width) / indent_width.max(1)
+}
+
+/// Index of the last line of the block opened at `lines[start]`: every following line
+/// indented deeper than it, plus a closing `end`, `}`, `]` or `)` at its own indentation.
+/// A closer that opens again, such as `) -> Result<()> {` ending a wrapped signature, keeps
+/// the block going, as does a lone `{` opening the body on the line after the header (PSR-12
+/// style). Blank lines never end a block.
+pub fn block_end(lines: &[&str], start: usize, tab_width: usize) -> usize {
+    let leading_width = |line: &str| {
+        let leading: String = line.chars().take_while(|ch| *ch == ' ' || *ch == '\t').collect();
+        display_width(&leading, tab_width)
+    };
+    let header_width = leading_width(lines[start]);
+
+    let mut end = start;
+    for (index, line) in lines.iter().enumerate().skip(start + 1) {
+        let code = line.trim();
+        if code.is_empty() {
+            continue;
+        }
+        let width = leading_width(line);
+        if width > header_width {
+            end = index;
+            continue;
+        }
+        let closes = code.starts_with(['}', ']', ')'])
+            || code.split(|ch: char| !ch.is_alphanumeric() && ch != '_').next() == Some("end");
+        if width == header_width && code == "{" && end == start {
+            end = index;
+            continue;
+        }
+        if width < header_width || !closes {
+            break;
+        }
+        end = index;
+        if !code.ends_with(['{', '(', '[']) {
+            break;
+        }
+    }
+    end
+}
+
+/// Deterministic suggestion for where to wrap a line that is wider than `max_width`.
+///
+/// Prefers the last comma, opening bracket or space that still fits, so the same line
+/// always produces the same advice.
+pub fn wrap_suggestion(line: &str, max_width: usize, tab_width: usize) -> String {
+    let width = display_width(line, tab_width);
+    let indent: String = line.chars().take_while(|ch| ch.is_whitespace()).collect();
+
+    let mut column = 0;
+    let mut best_break: Option<(usize, usize)> = None; // (byte offset after break char, column)
+    for (offset, ch) in line.char_indices() {
+        column = if ch == '\t' {
+            column + tab_width.max(1) - column % tab_width.max(1)
+        } else {
+            column + char_width(ch)
+        };
+        if column > max_width {
+            break;
+        }
+        if offset >= indent.len() && matches!(ch, ',' | '(' | '[' | '{' | ' ') {
+            best_break = Some((offset + ch.len_utf8(), column));
+        }
+    }
+
+    match best_break {
+        Some((offset, break_column)) => {
+            let head = line[..offset].trim();
+            let tail = line[offset..].trim();
+            format!(
+                "Line is {width} columns wide (limit {max_width}). Break after column {break_column} and indent the continuation: `{}` ⏎ `{}`",
+                shorten(head, true),
+                shorten(tail, false)
+            )
+        }
+        None => format!(
+            "Line is {width} columns wide (limit {max_width}). Extract part of the expression into a named variable"
+        ),
+    }
+}
+
+/// Keep quoted snippets short: the end of the head and the start of the tail matter most
+fn shorten(text: &str, keep_end: bool) -> String {
+    const MAX_CHARS: usize = 30;
+    let count = text.chars().count();
+    if count <= MAX_CHARS {
+        return text.to_string();
+    }
+    if keep_end {
+        format!("…{}", text.chars().skip(count - MAX_CHARS).collect::<String>())
+    } else {
+        format!("{}…", text.chars().take(MAX_CHARS).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod formatting_tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width_expands_tabs_and_unicode() {
+        assert_eq!(display_width("abc", 4), 3);
+        assert_eq!(display_width("\tx", 4), 5);
+        assert_eq!(display_width("ab\tx", 4), 5);
+        assert_eq!(display_width("\tx", 8), 9);
+        // Wide CJK characters take two columns, combining accents none
+        assert_eq!(display_width("日本語", 4), 6);
+        assert_eq!(display_width("e\u{0301}", 4), 1);
+        // Multi-byte but single-width characters are not counted by byte length
+        assert_eq!(display_width("ñandú", 4), 5);
+    }
+
+    #[test]
+    fn test_indent_depth() {
+        assert_eq!(indent_depth("      deep", 2, 4), 3);
+        assert_eq!(indent_depth("\t\tdeep", 4, 4), 2);
+        assert_eq!(indent_depth("\t  deep", 2, 4), 3);
+        assert_eq!(indent_depth("flat", 2, 4), 0);
+    }
+
+    #[test]
+    fn test_wrap_suggestion_is_deterministic() {
+        let line = "    result = some_function(first_argument, second_argument, third_argument)";
+        let suggestion = wrap_suggestion(line, 60, 4);
+
+        assert_eq!(suggestion, wrap_suggestion(line, 60, 4));
+        assert!(suggestion.starts_with("Line is 75 columns wide (limit 60)"));
+        assert!(suggestion.contains("Break after column 60"));
+        assert!(suggestion.ends_with("second_argument,` ⏎ `third_argument)`"));
+
+        let unbreakable = wrap_suggestion(&"x".repeat(50), 20, 4);
+        assert!(unbreakable.contains("Extract part of the expression"));
+    }
+
+    #[test]
+    fn test_resolve_layers_language_and_path() {
+        let config: FormattingConfig = serde_yaml::from_str(
+            r#"
+max_line_width: 100
+tab_width: 4
+languages:
+  python:
+    max_line_width: 88
+paths:
+  - glob: "migrations/**"
+    max_line_width: 160
+"#,
+        )
+        .unwrap();
+
+        let python = config.resolve("app/models.py", &Language::Python);
+        assert_eq!(python.max_line_width, Some(88));
+        assert_eq!(python.tab_width, Some(4));
+
+        let elixir = config.resolve("lib/user.ex", &Language::Elixir);
+        assert_eq!(elixir.max_line_width, Some(100));
+
+        let migration = config.resolve("migrations/0001_init.py", &Language::Python);
+        assert_eq!(migration.max_line_width, Some(160));
+    }
+
+    #[test]
+    fn test_block_end_follows_indentation() {
+        let elixir = ["  def run(x) do", "    x + 1", "", "  end", "  def other, do: 1"];
+        assert_eq!(block_end(&elixir, 0, 4), 3);
+        assert_eq!(block_end(&elixir, 4, 4), 4);
+
+        let rust = [
+            "fn parse(",
+            "    input: &str,",
+            ") -> Result<()> {",
+            "    Ok(())",
+            "}",
+            "fn next() {}",
+        ];
+        assert_eq!(block_end(&rust, 0, 4), 4);
+
+        let python = ["def run():", "\treturn 1", "", "print(run())"];
+        assert_eq!(block_end(&python, 0, 4), 1);
+
+        let php =
+            ["    public function run(): int", "    {", "        return 1;", "    }", "    {"];
+        assert_eq!(block_end(&php, 0, 4), 3);
+    }
+}
@@ -0,0 +1,280 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::core::{AntiPattern, ReviewViolation, Severity};
+
+/// How a tag is gated during a rollout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Gate {
+    /// Drop the tag's violations entirely
+    Off,
+    /// Show the tag's violations but never fail on them
+    Report,
+    /// Fail on the tag's violations at or above this severity, report the rest
+    FailAt(Severity),
+}
+
+impl std::str::FromStr for Gate {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "off" => Ok(Gate::Off),
+            "report" => Ok(Gate::Report),
+            "fail" => Ok(Gate::FailAt(Severity::Info)),
+            "blocker" => Ok(Gate::FailAt(Severity::Blocker)),
+            "critical" => Ok(Gate::FailAt(Severity::Critical)),
+            "major" => Ok(Gate::FailAt(Severity::Major)),
+            "warning" => Ok(Gate::FailAt(Severity::Warning)),
+            "info" => Ok(Gate::FailAt(Severity::Info)),
+            _ => Err(anyhow!(
+                "Invalid gate '{value}': expected off, report, fail, blocker, critical, major, \
+                 warning or info"
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Gate {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl std::fmt::Display for Gate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gate::Off => write!(f, "off"),
+            Gate::Report => write!(f, "report"),
+            Gate::FailAt(severity) => write!(f, "{severity}"),
+        }
+    }
+}
+
+/// What happens to a violation once gates are applied. Ordered so the strictest action
+/// compares greatest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GateAction {
+    Silence,
+    Report,
+    Fail,
+}
+
+impl std::fmt::Display for GateAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GateAction::Silence => write!(f, "silence"),
+            GateAction::Report => write!(f, "report"),
+            GateAction::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+/// The effective action for a violation and the tag gate that decided it, if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateResolution {
+    pub action: GateAction,
+    pub decided_by: Option<(String, Gate)>,
+}
+
+impl GateResolution {
+    /// One line for verbose output, e.g. "tag `security` gated at critical → fail"
+    pub fn explain(&self, fail_on: Option<Severity>) -> String {
+        match &self.decided_by {
+            Some((tag, Gate::FailAt(severity))) => {
+                format!("tag `{tag}` gated at {severity} → {}", self.action)
+            }
+            Some((tag, gate)) => format!("tag `{tag}` gated {gate} → {}", self.action),
+            None => match fail_on {
+                Some(fail_on) => format!("no gated tag, --fail-on {fail_on} → {}", self.action),
+                None => format!("no gated tag → {}", self.action),
+            },
+        }
+    }
+}
+
+/// The `gates:` section of the project config, mapping rule tags to gates.
+///
+/// ```yaml
+/// gates:
+///   security: critical   # fail on critical security findings, report the rest
+///   style: off           # not enforced yet
+///   performance: report  # visible, never blocking
+/// ```
+///
+/// A rule with several gated tags gets the strictest of their actions, so it is only
+/// silenced when every gated tag is off. Rules without gated tags follow `--fail-on`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct TagGates {
+    #[serde(flatten)]
+    pub tags: BTreeMap<String, Gate>,
+}
+
+impl TagGates {
+    /// Read the `gates:` section of a project config file; absent means no gates.
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct GatesSection {
+            #[serde(default)]
+            gates: TagGates,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: GatesSection = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.gates)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// These gates with the tags `other` gates replacing theirs
+    pub fn overlay(&self, other: &TagGates) -> TagGates {
+        let mut tags = self.tags.clone();
+        tags.extend(other.tags.iter().map(|(tag, gate)| (tag.clone(), *gate)));
+        TagGates { tags }
+    }
+
+    pub fn resolve(
+        &self,
+        rule: &AntiPattern,
+        severity: Severity,
+        fail_on: Option<Severity>,
+    ) -> GateResolution {
+        let mut resolution: Option<GateResolution> = None;
+        for tag in &rule.tags {
+            let Some(gate) = self.tags.get(tag) else { continue };
+            let action = match gate {
+                Gate::Off => GateAction::Silence,
+                Gate::Report => GateAction::Report,
+                Gate::FailAt(threshold) if severity.is_at_least(*threshold) => GateAction::Fail,
+                Gate::FailAt(_) => GateAction::Report,
+            };
+            if resolution.as_ref().map_or(true, |current| action > current.action) {
+                resolution =
+                    Some(GateResolution { action, decided_by: Some((tag.clone(), *gate)) });
+            }
+        }
+
+        resolution.unwrap_or_else(|| GateResolution {
+            action: if fail_on.is_some_and(|fail_on| severity.is_at_least(fail_on)) {
+                GateAction::Fail
+            } else {
+                GateAction::Report
+            },
+            decided_by: None,
+        })
+    }
+
+    /// Whether the rule's findings are dropped whatever their severity
+    pub fn silences(&self, rule: &AntiPattern) -> bool {
+        self.resolve(rule, rule.severity, None).action == GateAction::Silence
+    }
+
+    /// Number of violations whose effective action is to fail the run
+    pub fn failing(&self, violations: &[ReviewViolation], fail_on: Option<Severity>) -> usize {
+        violations
+            .iter()
+            .filter(|v| self.resolve(&v.rule, v.severity, fail_on).action == GateAction::Fail)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod gates_tests {
+    use super::*;
+    use crate::core::{DetectionMethod, Language};
+
+    fn rule(tags: &[&str]) -> AntiPattern {
+        AntiPattern {
+            id: "test_rule".to_string(),
+            name: "Test Rule".to_string(),
+            language: Language::Elixir,
+            severity: Severity::Major,
+            description: String::new(),
+            detection_method: DetectionMethod::Regex { pattern: "x".to_string() },
+            fix_suggestion: String::new(),
+            source_url: None,
+            claude_code_fixable: false,
+            examples: vec![],
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
+            enabled: true,
+        }
+    }
+
+    fn gates(yaml: &str) -> TagGates {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_severity_gate_fails_at_threshold_and_reports_below() {
+        let gates = gates("security: critical\n");
+        let security = rule(&["security"]);
+
+        let critical = gates.resolve(&security, Severity::Critical, None);
+        assert_eq!(critical.action, GateAction::Fail);
+        assert_eq!(critical.explain(None), "tag `security` gated at critical → fail");
+
+        assert_eq!(gates.resolve(&security, Severity::Major, None).action, GateAction::Report);
+    }
+
+    #[test]
+    fn test_ungated_rules_follow_fail_on() {
+        let gates = gates("style: off\n");
+        let other = rule(&["performance"]);
+
+        let resolution = gates.resolve(&other, Severity::Major, Some(Severity::Major));
+        assert_eq!(resolution.action, GateAction::Fail);
+        assert_eq!(
+            resolution.explain(Some(Severity::Major)),
+            "no gated tag, --fail-on major → fail"
+        );
+        assert_eq!(
+            gates.resolve(&other, Severity::Warning, Some(Severity::Major)).action,
+            GateAction::Report
+        );
+    }
+
+    #[test]
+    fn test_strictest_gate_wins_and_report_overrides_fail_on() {
+        let gates = gates("style: off\nsecurity: fail\nperformance: report\n");
+
+        assert!(gates.silences(&rule(&["style"])));
+        assert!(!gates.silences(&rule(&["style", "security"])));
+        assert_eq!(
+            gates.resolve(&rule(&["style", "security"]), Severity::Warning, None).decided_by,
+            Some(("security".to_string(), Gate::FailAt(Severity::Info)))
+        );
+        // Report-only tags are exempt from --fail-on during rollout
+        assert_eq!(
+            gates
+                .resolve(&rule(&["performance"]), Severity::Critical, Some(Severity::Warning))
+                .action,
+            GateAction::Report
+        );
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_gate() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(&config_path, "gates:\n  security: maybe\n").unwrap();
+
+        let error = format!("{:#}", TagGates::load(&config_path).unwrap_err());
+        assert!(error.contains("Invalid gate 'maybe'"));
+
+        std::fs::write(&config_path, "formatting:\n  max_line_width: 100\n").unwrap();
+        assert!(TagGates::load(&config_path).unwrap().is_empty());
+    }
+}
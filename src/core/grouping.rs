@@ -0,0 +1,241 @@
+use clap::ValueEnum;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+
+use crate::core::review_engine::{ReviewViolation, SkipReason, SkippedViolation};
+
+/// How the human report arranges violations (`review --group-by`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    /// One section per file, each rule's findings in it together
+    #[default]
+    File,
+    /// One section per rule, most severe first, listing the files it fires in
+    Rule,
+    /// One section per CODEOWNERS owner, listing the files and rules they are responsible
+    /// for; a finding with several owners is listed under each
+    Owner,
+}
+
+/// Section of `--group-by owner` for findings in files CODEOWNERS does not cover
+pub const UNOWNED: &str = "(no owner)";
+
+/// What makes two findings on the same line of a file the same finding
+#[derive(PartialEq, Eq, Hash)]
+enum DuplicateKey {
+    /// The same rule, e.g. matched twice through rule variants or packs
+    Rule(String),
+    /// Different rules flagging exactly the same code
+    Span(Range<usize>),
+}
+
+fn duplicate_keys(violation: &ReviewViolation) -> Vec<(String, usize, DuplicateKey)> {
+    let mut keys = vec![DuplicateKey::Rule(violation.rule.id.clone())];
+    keys.extend(violation.span.clone().map(DuplicateKey::Span));
+    keys.into_iter().map(|key| (violation.file_path.clone(), violation.line_number, key)).collect()
+}
+
+/// Merge duplicate findings: the same rule on the same line, or different rules matching
+/// the same code on it. The most severe finding of each set is kept, the first on a tie;
+/// the others go to `skipped` so `--show-skipped` can still list them.
+pub fn dedup_violations(
+    violations: Vec<ReviewViolation>,
+    skipped: &mut Vec<SkippedViolation>,
+) -> Vec<ReviewViolation> {
+    let mut kept: Vec<ReviewViolation> = Vec::with_capacity(violations.len());
+    let mut seen: HashMap<(String, usize, DuplicateKey), usize> = HashMap::new();
+
+    for violation in violations {
+        let keys = duplicate_keys(&violation);
+        let Some(index) = keys.iter().find_map(|key| seen.get(key).copied()) else {
+            seen.extend(keys.into_iter().map(|key| (key, kept.len())));
+            kept.push(violation);
+            continue;
+        };
+        for key in keys {
+            seen.entry(key).or_insert(index);
+        }
+        let duplicate = if violation.severity < kept[index].severity {
+            std::mem::replace(&mut kept[index], violation)
+        } else {
+            violation
+        };
+        skipped.push(SkippedViolation { violation: duplicate, reason: SkipReason::Duplicate });
+    }
+    kept
+}
+
+/// One section of a grouped report: a file, a rule or an owner, with its findings split into
+/// groups of one rule in one file, each in line order
+#[derive(Debug)]
+pub struct ViolationSection<'a> {
+    /// The file path, rule id or owner the section is about
+    pub key: &'a str,
+    pub groups: Vec<Vec<&'a ReviewViolation>>,
+}
+
+impl ViolationSection<'_> {
+    pub fn count(&self) -> usize {
+        self.groups.iter().map(Vec::len).sum()
+    }
+
+    /// How many files the section's findings are in
+    pub fn file_count(&self) -> usize {
+        let mut files: Vec<&str> =
+            self.groups.iter().map(|group| group[0].file_path.as_str()).collect();
+        files.dedup();
+        files.len()
+    }
+}
+
+/// The sections a finding goes in
+fn sections(violation: &ReviewViolation, by: GroupBy) -> Vec<&str> {
+    match by {
+        GroupBy::File => vec![&violation.file_path],
+        GroupBy::Rule => vec![&violation.rule.id],
+        GroupBy::Owner if violation.owners.is_empty() => vec![UNOWNED],
+        GroupBy::Owner => violation.owners.iter().map(String::as_str).collect(),
+    }
+}
+
+/// The group of a finding within its section: its rule in its file
+fn group(violation: &ReviewViolation) -> (&str, &str) {
+    (&violation.file_path, &violation.rule.id)
+}
+
+/// `violations` arranged for the report. By file, sections follow the path and groups the
+/// line of each rule's first finding; by rule, sections go from the most severe rule and
+/// groups follow the path; by owner, sections follow the owner, unowned findings last, and
+/// groups the path and line.
+pub fn group_violations(violations: &[ReviewViolation], by: GroupBy) -> Vec<ViolationSection<'_>> {
+    let mut ordered: Vec<&ReviewViolation> = violations.iter().collect();
+    ordered.sort_by(|a, b| (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number)));
+
+    let mut sections: BTreeMap<&str, Vec<Vec<&ReviewViolation>>> = BTreeMap::new();
+    for violation in ordered {
+        for section in self::sections(violation, by) {
+            let groups = sections.entry(section).or_default();
+            match groups.iter_mut().find(|existing| group(existing[0]) == group(violation)) {
+                Some(existing) => existing.push(violation),
+                None => groups.push(vec![violation]),
+            }
+        }
+    }
+
+    let mut sections: Vec<ViolationSection> =
+        sections.into_iter().map(|(key, groups)| ViolationSection { key, groups }).collect();
+    if by == GroupBy::Rule {
+        let severity = |section: &ViolationSection| {
+            section.groups.iter().flatten().map(|violation| violation.severity).min()
+        };
+        sections.sort_by_key(|section| severity(section));
+    }
+    if by == GroupBy::Owner {
+        sections.sort_by_key(|section| section.key == UNOWNED);
+    }
+    sections
+}
+
+#[cfg(test)]
+mod grouping_tests {
+    use super::*;
+    use crate::core::Severity;
+    use crate::report::test_support::violation;
+
+    fn found(file: &str, line: usize, rule: &str, severity: Severity) -> ReviewViolation {
+        let mut violation = violation(file, line, severity);
+        violation.rule.id = rule.to_string();
+        violation
+    }
+
+    #[test]
+    fn test_duplicates_are_merged_keeping_the_most_severe() {
+        let mut custom = found("a.js", 3, "team_no_console", Severity::Critical);
+        custom.span = Some(0..12);
+        let mut builtin = found("a.js", 3, "console_log", Severity::Major);
+        builtin.span = Some(0..12);
+        let mut elsewhere_on_line = found("a.js", 3, "long_line", Severity::Warning);
+        elsewhere_on_line.span = None;
+
+        let mut skipped = Vec::new();
+        let kept = dedup_violations(
+            vec![
+                builtin,
+                found("a.js", 5, "console_log", Severity::Major),
+                custom,
+                found("a.js", 5, "console_log", Severity::Major),
+                elsewhere_on_line,
+                found("b.js", 5, "console_log", Severity::Major),
+            ],
+            &mut skipped,
+        );
+
+        let kept: Vec<_> = kept
+            .iter()
+            .map(|v| (v.file_path.as_str(), v.line_number, v.rule.id.as_str()))
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                ("a.js", 3, "team_no_console"),
+                ("a.js", 5, "console_log"),
+                ("a.js", 3, "long_line"),
+                ("b.js", 5, "console_log"),
+            ]
+        );
+        let merged: Vec<_> =
+            skipped.iter().map(|s| (s.violation.rule.id.as_str(), s.reason)).collect();
+        assert_eq!(
+            merged,
+            [("console_log", SkipReason::Duplicate), ("console_log", SkipReason::Duplicate)]
+        );
+    }
+
+    #[test]
+    fn test_grouping_by_file_and_by_rule() {
+        let violations = vec![
+            found("b.ex", 9, "debug_print", Severity::Warning),
+            found("a.ex", 7, "atoms", Severity::Critical),
+            found("a.ex", 2, "debug_print", Severity::Warning),
+            found("a.ex", 4, "debug_print", Severity::Warning),
+        ];
+
+        let by_file = group_violations(&violations, GroupBy::File);
+        assert_eq!(by_file.len(), 2);
+        assert_eq!(by_file[0].key, "a.ex");
+        assert_eq!(by_file[0].count(), 3);
+        let lines: Vec<Vec<usize>> = by_file[0]
+            .groups
+            .iter()
+            .map(|group| group.iter().map(|v| v.line_number).collect())
+            .collect();
+        assert_eq!(lines, [vec![2, 4], vec![7]]);
+
+        let by_rule = group_violations(&violations, GroupBy::Rule);
+        let keys: Vec<_> = by_rule.iter().map(|section| section.key).collect();
+        assert_eq!(keys, ["atoms", "debug_print"]);
+        let files: Vec<_> =
+            by_rule[1].groups.iter().map(|group| group[0].file_path.as_str()).collect();
+        assert_eq!(files, ["a.ex", "b.ex"]);
+        assert_eq!(by_rule[1].groups[0].len(), 2);
+    }
+
+    #[test]
+    fn test_grouping_by_owner() {
+        let mut shared = found("lib/pay.ex", 3, "atoms", Severity::Critical);
+        shared.owners = vec!["@acme/payments".to_string(), "@jane".to_string()];
+        let mut payments = found("lib/pay.ex", 8, "debug_print", Severity::Warning);
+        payments.owners = vec!["@acme/payments".to_string()];
+        let violations =
+            vec![found("README.md", 1, "long_line", Severity::Warning), payments, shared];
+
+        let by_owner = group_violations(&violations, GroupBy::Owner);
+        let keys: Vec<_> = by_owner.iter().map(|section| (section.key, section.count())).collect();
+        assert_eq!(keys, [("@acme/payments", 2), ("@jane", 1), (UNOWNED, 1)]);
+        // One group per rule in each file, in line order
+        let lines: Vec<usize> =
+            by_owner[0].groups.iter().map(|group| group[0].line_number).collect();
+        assert_eq!(lines, [3, 8]);
+        assert_eq!(by_owner[0].file_count(), 1);
+    }
+}
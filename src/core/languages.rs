@@ -0,0 +1,307 @@
+use anyhow::{bail, Context, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{PoisonError, RwLock};
+
+use super::pattern::Language;
+
+/// Line and block comment tokens of a language
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentSyntax {
+    #[serde(default)]
+    pub line: Option<String>,
+    #[serde(default)]
+    pub block: Option<(String, String)>,
+}
+
+impl CommentSyntax {
+    fn new(line: Option<&str>, block: Option<(&str, &str)>) -> Self {
+        Self {
+            line: line.map(String::from),
+            block: block.map(|(open, close)| (open.to_string(), close.to_string())),
+        }
+    }
+
+    /// `text` as a comment: a line comment when the language has them, otherwise a block
+    /// comment. `None` when the comment syntax is unknown.
+    pub fn comment(&self, text: &str) -> Option<String> {
+        match (&self.line, &self.block) {
+            (Some(line), _) => Some(format!("{line} {text}")),
+            (None, Some((open, close))) => Some(format!("{open} {text} {close}")),
+            (None, None) => None,
+        }
+    }
+
+    /// Text of the first comment on `line`, without its delimiters. Does not know about
+    /// string literals, so callers should look for distinctive markers in the result.
+    pub fn comment_text<'a>(&self, line: &'a str) -> Option<&'a str> {
+        let line_start = self.line.as_ref().and_then(|token| {
+            line.find(token.as_str()).map(|index| (index, &line[index + token.len()..]))
+        });
+        let block_start = self.block.as_ref().and_then(|(open, close)| {
+            line.find(open.as_str()).map(|index| {
+                let rest = &line[index + open.len()..];
+                (index, rest.find(close.as_str()).map_or(rest, |end| &rest[..end]))
+            })
+        });
+
+        let (_, text) = match (line_start, block_start) {
+            (Some(line), Some(block)) => {
+                if block.0 < line.0 {
+                    block
+                } else {
+                    line
+                }
+            }
+            (Some(found), None) | (None, Some(found)) => found,
+            (None, None) => return None,
+        };
+        Some(text.trim())
+    }
+
+    /// How comments are written, for prompts and messages, e.g. "`//` line comments,
+    /// `/* */` block comments"
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(line) = &self.line {
+            parts.push(format!("`{line}` line comments"));
+        }
+        if let Some((open, close)) = &self.block {
+            parts.push(format!("`{open} {close}` block comments"));
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
+
+impl Language {
+    /// Comment tokens of this language; declared languages use their config entry
+    pub fn comment_syntax(&self) -> CommentSyntax {
+        match self {
+            Language::Elixir | Language::Python | Language::Ruby => {
+                CommentSyntax::new(Some("#"), None)
+            }
+            Language::JavaScript
+            | Language::TypeScript
+            | Language::Rust
+            | Language::Php
+            | Language::Java
+            | Language::Kotlin => CommentSyntax::new(Some("//"), Some(("/*", "*/"))),
+            Language::Zig => CommentSyntax::new(Some("//"), None),
+            Language::Sql => CommentSyntax::new(Some("--"), Some(("/*", "*/"))),
+            Language::Other(name) => {
+                language_definition(name).map(|definition| definition.comments).unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// A language patingin does not ship rules for, declared in config so custom rule packs
+/// can target it without waiting for a release.
+///
+/// ```yaml
+/// languages:
+///   - name: lua
+///     extensions: [lua]
+///     comments:
+///       line: "--"
+///       block: ["--[[", "]]"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageDefinition {
+    pub name: String,
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub comments: CommentSyntax,
+}
+
+impl LanguageDefinition {
+    pub fn language(&self) -> Language {
+        Language::Other(self.name.clone())
+    }
+
+    /// Lowercase the name and extensions and drop leading dots, rejecting definitions that
+    /// would shadow a built-in language.
+    fn normalized(&self) -> Result<Self> {
+        let language: Language = self.name.parse()?;
+        let Language::Other(name) = language else {
+            bail!("Language '{}' is built in and cannot be redeclared", self.name);
+        };
+        if self.extensions.is_empty() {
+            bail!("Language '{name}' must declare at least one file extension");
+        }
+
+        let mut extensions = Vec::new();
+        for extension in &self.extensions {
+            let extension = extension.trim().trim_start_matches('.').to_lowercase();
+            if let Some(owner) = Language::from_extension(&extension).filter(|l| l.is_built_in()) {
+                bail!("Language '{name}' cannot claim .{extension}, which belongs to {owner}");
+            }
+            extensions.push(extension);
+        }
+
+        Ok(Self { name, extensions, comments: self.comments.clone() })
+    }
+}
+
+static DECLARED_LANGUAGES: Lazy<RwLock<Vec<LanguageDefinition>>> = Lazy::new(Default::default);
+
+/// Make `definitions` known to language detection and rule matching for the rest of the
+/// process. Declaring a name again replaces the earlier definition.
+pub fn declare_languages(definitions: &[LanguageDefinition]) -> Result<()> {
+    let normalized =
+        definitions.iter().map(LanguageDefinition::normalized).collect::<Result<Vec<_>>>()?;
+
+    let mut declared = DECLARED_LANGUAGES.write().unwrap_or_else(PoisonError::into_inner);
+    for definition in normalized {
+        declared.retain(|existing| existing.name != definition.name);
+        declared.push(definition);
+    }
+    Ok(())
+}
+
+/// Every declared language, in declaration order
+pub fn declared_languages() -> Vec<LanguageDefinition> {
+    DECLARED_LANGUAGES.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+pub fn language_definition(name: &str) -> Option<LanguageDefinition> {
+    DECLARED_LANGUAGES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|definition| definition.name == name)
+        .cloned()
+}
+
+/// The declared language owning a (lowercase, dotless) file extension
+pub fn declared_language_for_extension(extension: &str) -> Option<Language> {
+    DECLARED_LANGUAGES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|definition| definition.extensions.iter().any(|ext| ext == extension))
+        .map(LanguageDefinition::language)
+}
+
+/// Read the `languages:` section of a config file; absent means nothing declared.
+pub fn load_language_definitions(config_path: &Path) -> Result<Vec<LanguageDefinition>> {
+    #[derive(Deserialize)]
+    struct LanguagesSection {
+        #[serde(default)]
+        languages: Vec<LanguageDefinition>,
+    }
+
+    let content = std::fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    let section: LanguagesSection = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+    Ok(section.languages)
+}
+
+#[cfg(test)]
+mod languages_tests {
+    use super::*;
+
+    fn definition(name: &str, extensions: &[&str]) -> LanguageDefinition {
+        LanguageDefinition {
+            name: name.to_string(),
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+            comments: CommentSyntax::default(),
+        }
+    }
+
+    #[test]
+    fn test_declared_language_is_detected_by_extension() {
+        declare_languages(&[definition("Lua", &[".LUA"])]).unwrap();
+
+        assert_eq!(Language::from_extension("lua"), Some(Language::Other("lua".to_string())));
+        assert_eq!(language_definition("lua").unwrap().extensions, ["lua"]);
+        assert_eq!(Language::from_extension("rs"), Some(Language::Rust));
+    }
+
+    #[test]
+    fn test_redeclaring_replaces_definition() {
+        declare_languages(&[definition("dart", &["dart"])]).unwrap();
+        declare_languages(&[definition("dart", &["dart2"])]).unwrap();
+
+        assert_eq!(Language::from_extension("dart"), None);
+        assert_eq!(declared_languages().iter().filter(|d| d.name == "dart").count(), 1);
+    }
+
+    #[test]
+    fn test_built_in_languages_cannot_be_shadowed() {
+        let error = declare_languages(&[definition("rust", &["rs"])]).unwrap_err();
+        assert!(error.to_string().contains("built in"));
+
+        let error = declare_languages(&[definition("elixir2", &["exs"])]).unwrap_err();
+        assert!(error.to_string().contains("belongs to elixir"));
+
+        let error = declare_languages(&[definition("nim", &[])]).unwrap_err();
+        assert!(error.to_string().contains("at least one file extension"));
+    }
+
+    #[test]
+    fn test_load_language_definitions_from_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(
+            &config_path,
+            "languages:\n  - name: lua\n    extensions: [lua]\n    comments:\n      line: \"--\"\n      block: [\"--[[\", \"]]\"]\n",
+        )
+        .unwrap();
+
+        let definitions = load_language_definitions(&config_path).unwrap();
+
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].comments.line.as_deref(), Some("--"));
+        assert_eq!(definitions[0].comments.block, Some(("--[[".to_string(), "]]".to_string())));
+    }
+
+    #[test]
+    fn test_built_in_comment_syntax() {
+        assert_eq!(Language::Python.comment_syntax().comment("note").unwrap(), "# note");
+        assert_eq!(Language::Sql.comment_syntax().comment("note").unwrap(), "-- note");
+        assert_eq!(Language::Rust.comment_syntax().comment("note").unwrap(), "// note");
+        assert_eq!(
+            Language::TypeScript.comment_syntax().describe().unwrap(),
+            "`//` line comments, `/* */` block comments"
+        );
+        assert_eq!(Language::Other("cobol".to_string()).comment_syntax().comment("x"), None);
+    }
+
+    #[test]
+    fn test_block_only_languages_use_block_comments() {
+        declare_languages(&[LanguageDefinition {
+            name: "ocaml".to_string(),
+            extensions: vec!["ml".to_string()],
+            comments: CommentSyntax::new(None, Some(("(*", "*)"))),
+        }])
+        .unwrap();
+
+        let syntax = Language::Other("ocaml".to_string()).comment_syntax();
+        assert_eq!(syntax.comment("note").unwrap(), "(* note *)");
+        assert_eq!(syntax.comment_text("let x = 1 (* note *)"), Some("note"));
+    }
+
+    #[test]
+    fn test_comment_text_finds_first_comment() {
+        let syntax = Language::JavaScript.comment_syntax();
+        assert_eq!(syntax.comment_text("foo(); // trailing"), Some("trailing"));
+        assert_eq!(syntax.comment_text("foo(); /* inline */ bar(); // later"), Some("inline"));
+        assert_eq!(syntax.comment_text("foo();"), None);
+        assert_eq!(Language::Elixir.comment_syntax().comment_text("x = 1 # why"), Some("why"));
+    }
+
+    #[test]
+    fn test_language_names_round_trip() {
+        assert_eq!("TypeScript".parse::<Language>().unwrap(), Language::TypeScript);
+        assert_eq!("Kotlin".parse::<Language>().unwrap(), Language::Kotlin);
+        assert_eq!("lua".parse::<Language>().unwrap(), Language::Other("lua".to_string()));
+        assert!("".parse::<Language>().is_err());
+
+        let yaml = serde_yaml::to_string(&vec![Language::Sql, Language::Other("lua".into())]);
+        let parsed: Vec<Language> = serde_yaml::from_str(&yaml.unwrap()).unwrap();
+        assert_eq!(parsed, [Language::Sql, Language::Other("lua".to_string())]);
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::git::{FileDiff, FileKind};
+
+/// The `large_hunks:` section of the project config. Hunks adding more lines than
+/// `max_added_lines` (lockfiles, generated code) are only partially analyzed: about
+/// `sample_lines` of their lines, evenly spread, are checked against critical rules only,
+/// so one huge hunk cannot dominate review time.
+///
+/// ```yaml
+/// large_hunks:
+///   max_added_lines: 2000
+///   sample_lines: 200
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct LargeHunkPolicy {
+    #[serde(default = "default_max_added_lines")]
+    pub max_added_lines: usize,
+    #[serde(default = "default_sample_lines")]
+    pub sample_lines: usize,
+}
+
+fn default_max_added_lines() -> usize {
+    2000
+}
+
+fn default_sample_lines() -> usize {
+    200
+}
+
+impl Default for LargeHunkPolicy {
+    fn default() -> Self {
+        Self { max_added_lines: default_max_added_lines(), sample_lines: default_sample_lines() }
+    }
+}
+
+/// A hunk that was too large to review in full
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialHunk {
+    pub file_path: String,
+    pub first_line: usize,
+    pub last_line: usize,
+    pub added_lines: usize,
+    /// Lines that were checked, against critical rules only
+    pub checked_lines: usize,
+}
+
+impl LargeHunkPolicy {
+    /// Read the `large_hunks:` section of a project config file; absent keeps the defaults
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct LargeHunksSection {
+            #[serde(default)]
+            large_hunks: LargeHunkPolicy,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<LargeHunksSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.map(|section| section.large_hunks).unwrap_or_default())
+    }
+
+    /// Hunks of `file` that exceed the limit, as ranges into its added lines
+    pub fn large_hunks<'a>(
+        &'a self,
+        file: &'a FileDiff,
+    ) -> impl Iterator<Item = Range<usize>> + 'a {
+        file.hunks.iter().filter(|hunk| hunk.len() > self.max_added_lines).cloned()
+    }
+
+    /// Indexes of the lines of an oversized hunk that are checked, evenly spread over it
+    pub fn sample(&self, hunk: Range<usize>) -> impl Iterator<Item = usize> {
+        let sample_lines = self.sample_lines.max(1);
+        let stride = ((hunk.len() + sample_lines - 1) / sample_lines).max(1);
+        hunk.step_by(stride)
+    }
+
+    /// Every hunk in `files` that will only be partially analyzed
+    pub fn partial_hunks(&self, files: &[FileDiff]) -> Vec<PartialHunk> {
+        files
+            .iter()
+            .filter(|file| file.kind == FileKind::Regular)
+            .flat_map(|file| {
+                self.large_hunks(file).map(move |hunk| PartialHunk {
+                    file_path: file.path.clone(),
+                    first_line: file.added_lines[hunk.start].line_number,
+                    last_line: file.added_lines[hunk.end - 1].line_number,
+                    added_lines: hunk.len(),
+                    checked_lines: self.sample(hunk).count(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod large_hunks_tests {
+    use super::*;
+
+    fn file_with_hunks(hunk_sizes: &[usize]) -> FileDiff {
+        let total: usize = hunk_sizes.iter().sum();
+        let content = "x\n".repeat(total);
+        let mut file = FileDiff::from_region("Cargo.lock", &content, 1..=total);
+        let mut start = 0;
+        file.hunks = hunk_sizes
+            .iter()
+            .map(|size| {
+                start += size;
+                start - size..start
+            })
+            .collect();
+        file
+    }
+
+    #[test]
+    fn test_only_oversized_hunks_are_partial() {
+        let policy = LargeHunkPolicy { max_added_lines: 100, sample_lines: 10 };
+        let file = file_with_hunks(&[50, 1000]);
+
+        let partial = policy.partial_hunks(&[file]);
+
+        assert_eq!(
+            partial,
+            [PartialHunk {
+                file_path: "Cargo.lock".to_string(),
+                first_line: 51,
+                last_line: 1050,
+                added_lines: 1000,
+                checked_lines: 10,
+            }]
+        );
+        assert_eq!(policy.sample(50..1050).take(3).collect::<Vec<_>>(), [50, 150, 250]);
+    }
+
+    #[test]
+    fn test_load_large_hunks_section() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+
+        std::fs::write(&config_path, "large_hunks:\n  max_added_lines: 500\n").unwrap();
+        let policy = LargeHunkPolicy::load(&config_path).unwrap();
+        assert_eq!(policy, LargeHunkPolicy { max_added_lines: 500, sample_lines: 200 });
+
+        std::fs::write(&config_path, "gates:\n  style: off\n").unwrap();
+        assert_eq!(LargeHunkPolicy::load(&config_path).unwrap(), LargeHunkPolicy::default());
+    }
+}
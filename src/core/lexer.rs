@@ -0,0 +1,381 @@
+use std::ops::Range;
+
+use super::languages::CommentSyntax;
+use super::pattern::Language;
+
+/// A kind of string literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Quote {
+    open: &'static str,
+    close: &'static str,
+    /// Continues on the next line when not closed
+    multiline: bool,
+    /// `\` escapes the next character
+    escapes: bool,
+}
+
+impl Quote {
+    const fn new(delimiter: &'static str, multiline: bool) -> Self {
+        Self { open: delimiter, close: delimiter, multiline, escapes: true }
+    }
+
+    const fn raw(delimiter: &'static str, multiline: bool) -> Self {
+        Self { open: delimiter, close: delimiter, multiline, escapes: false }
+    }
+}
+
+/// How a language writes comments and string literals
+#[derive(Debug, Clone)]
+struct Syntax {
+    line_comment: Option<String>,
+    block_comment: Option<(String, String)>,
+    /// Block comments nest, e.g. `/* a /* b */ c */` in Rust and Kotlin
+    nested_blocks: bool,
+    /// Longest opening delimiter first, so `"""` is not read as an empty `""`
+    quotes: &'static [Quote],
+    /// `'x'` and `'\n'` are char literals; any other `'` is a lifetime or label
+    char_literals: bool,
+}
+
+/// String literals of each language, longest opening delimiter first
+const ELIXIR_QUOTES: &[Quote] = &[
+    Quote::new("\"\"\"", true),
+    Quote::new("'''", true),
+    Quote::new("\"", true),
+    Quote::new("'", true),
+];
+const PYTHON_QUOTES: &[Quote] = &[
+    Quote::new("\"\"\"", true),
+    Quote::new("'''", true),
+    Quote::new("\"", false),
+    Quote::new("'", false),
+];
+const RUBY_QUOTES: &[Quote] = &[Quote::new("\"", true), Quote::new("'", true)];
+const JS_QUOTES: &[Quote] =
+    &[Quote::new("`", true), Quote::new("\"", false), Quote::new("'", false)];
+const RUST_QUOTES: &[Quote] = &[Quote::new("\"", true)];
+const ZIG_QUOTES: &[Quote] = &[Quote::new("\"", false)];
+const JAVA_QUOTES: &[Quote] = &[Quote::new("\"\"\"", true), Quote::new("\"", false)];
+const KOTLIN_QUOTES: &[Quote] = &[Quote::raw("\"\"\"", true), Quote::new("\"", false)];
+/// `''` inside a string reads as a closed string followed by another one
+const SQL_QUOTES: &[Quote] = &[Quote::raw("'", true)];
+const OTHER_QUOTES: &[Quote] = &[Quote::new("\"", false), Quote::new("'", false)];
+
+impl Syntax {
+    fn of(language: &Language) -> Self {
+        let (quotes, nested_blocks, char_literals) = match language {
+            Language::Elixir => (ELIXIR_QUOTES, false, false),
+            Language::Python => (PYTHON_QUOTES, false, false),
+            Language::Ruby | Language::Php => (RUBY_QUOTES, false, false),
+            Language::JavaScript | Language::TypeScript => (JS_QUOTES, false, false),
+            Language::Rust => (RUST_QUOTES, true, true),
+            Language::Zig => (ZIG_QUOTES, false, true),
+            Language::Java => (JAVA_QUOTES, false, true),
+            Language::Kotlin => (KOTLIN_QUOTES, true, true),
+            Language::Sql => (SQL_QUOTES, false, false),
+            Language::Other(_) => (OTHER_QUOTES, false, false),
+        };
+
+        // Empty tokens from a language declaration would match everywhere
+        let CommentSyntax { line, block } = language.comment_syntax();
+        Self {
+            line_comment: line.filter(|token| !token.is_empty()),
+            block_comment: block.filter(|(open, close)| !open.is_empty() && !close.is_empty()),
+            nested_blocks,
+            quotes,
+            char_literals,
+        }
+    }
+}
+
+/// What the lexer is inside of at a point in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Code,
+    BlockComment { depth: usize },
+    String(Quote),
+}
+
+/// Byte ranges of a line that are comments, including their delimiters, and the contents
+/// of string literals, without their quotes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineRegions {
+    comments: Vec<Range<usize>>,
+    strings: Vec<Range<usize>>,
+}
+
+impl LineRegions {
+    /// Whether a match at `span` starts inside a comment
+    pub fn in_comment(&self, span: &Range<usize>) -> bool {
+        self.comments.iter().any(|comment| comment.contains(&span.start))
+    }
+
+    /// Whether a match at `span` lies within one string literal; a match including the
+    /// quotes is about the literal, not its text
+    pub fn in_string(&self, span: &Range<usize>) -> bool {
+        self.strings.iter().any(|string| string.start <= span.start && span.end <= string.end)
+    }
+}
+
+/// Splits lines of one file into code, comments and string literals, carrying block
+/// comments and multiline strings over from one line to the next. A lightweight
+/// approximation: sigils, raw strings and heredoc markers read as code.
+#[derive(Debug, Clone)]
+pub struct Lexer {
+    syntax: Syntax,
+    state: State,
+}
+
+impl Lexer {
+    pub fn new(language: &Language) -> Self {
+        Self { syntax: Syntax::of(language), state: State::Code }
+    }
+
+    /// Regions of the next line of the file
+    pub fn line(&mut self, line: &str) -> LineRegions {
+        let syntax = &self.syntax;
+        let bytes = line.as_bytes();
+        let mut regions = LineRegions::default();
+        // Where the comment or string being read started; 0 when it came from a line above
+        let mut start = 0;
+        let mut index = 0;
+
+        while index < bytes.len() {
+            let rest = &bytes[index..];
+            match self.state {
+                State::Code => {
+                    if let Some(token) = &syntax.line_comment {
+                        if rest.starts_with(token.as_bytes()) {
+                            regions.comments.push(index..bytes.len());
+                            return regions;
+                        }
+                    }
+                    if let Some((open, _)) = &syntax.block_comment {
+                        if rest.starts_with(open.as_bytes()) {
+                            self.state = State::BlockComment { depth: 1 };
+                            start = index;
+                            index += open.len();
+                            continue;
+                        }
+                    }
+                    if let Some(quote) =
+                        syntax.quotes.iter().find(|quote| rest.starts_with(quote.open.as_bytes()))
+                    {
+                        self.state = State::String(*quote);
+                        index += quote.open.len();
+                        start = index;
+                        continue;
+                    }
+                    if syntax.char_literals && rest[0] == b'\'' {
+                        if let Some(len) = char_literal_len(&line[index..]) {
+                            regions.strings.push(index + 1..index + len - 1);
+                            index += len;
+                            continue;
+                        }
+                    }
+                    index += 1;
+                }
+                State::BlockComment { depth } => {
+                    let Some((open, close)) = &syntax.block_comment else {
+                        self.state = State::Code;
+                        continue;
+                    };
+                    if rest.starts_with(close.as_bytes()) {
+                        index += close.len();
+                        if depth == 1 {
+                            regions.comments.push(start..index);
+                            self.state = State::Code;
+                        } else {
+                            self.state = State::BlockComment { depth: depth - 1 };
+                        }
+                    } else if syntax.nested_blocks && rest.starts_with(open.as_bytes()) {
+                        index += open.len();
+                        self.state = State::BlockComment { depth: depth + 1 };
+                    } else {
+                        index += 1;
+                    }
+                }
+                State::String(quote) => {
+                    if quote.escapes && rest[0] == b'\\' {
+                        index += 2;
+                    } else if rest.starts_with(quote.close.as_bytes()) {
+                        regions.strings.push(start..index);
+                        index += quote.close.len();
+                        self.state = State::Code;
+                    } else {
+                        index += 1;
+                    }
+                }
+            }
+        }
+
+        match self.state {
+            State::Code => {}
+            State::BlockComment { .. } => regions.comments.push(start..bytes.len()),
+            State::String(quote) => {
+                regions.strings.push(start.min(bytes.len())..bytes.len());
+                // An unterminated single-line string is a typo; do not let it swallow the file
+                if !quote.multiline {
+                    self.state = State::Code;
+                }
+            }
+        }
+        regions
+    }
+}
+
+/// Length of the char literal `rest` starts with, or `None` when its `'` starts a lifetime
+/// or label such as `'a` or `'outer:`
+fn char_literal_len(rest: &str) -> Option<usize> {
+    let mut chars = rest.get(1..)?.char_indices();
+    let (_, first) = chars.next()?;
+    if first == '\\' {
+        // `'\n'`, `'\''`, `'\u{1F600}'`
+        let end = rest.get(3..)?.find('\'').filter(|end| *end <= 8)?;
+        return Some(end + 4);
+    }
+    let (at, next) = chars.next()?;
+    (next == '\'').then_some(at + 2)
+}
+
+/// Regions of every line of a file, lexed from its first line
+#[derive(Debug, Clone, Default)]
+pub struct FileRegions(Vec<LineRegions>);
+
+impl FileRegions {
+    pub fn new(source: &str, language: &Language) -> Self {
+        let mut lexer = Lexer::new(language);
+        Self(source.lines().map(|line| lexer.line(line.trim_end_matches('\r'))).collect())
+    }
+
+    /// Regions of 1-based line `number`
+    pub fn line(&self, number: usize) -> Option<&LineRegions> {
+        self.0.get(number.checked_sub(1)?)
+    }
+}
+
+/// Regions of `line` when only the lines just above it are known, as in a diff without
+/// the file. A line starting with `*` in a language with block comments is taken to be
+/// inside a doc comment whose opening is out of view.
+pub fn regions_after(context_before: &[String], line: &str, language: &Language) -> LineRegions {
+    let mut lexer = Lexer::new(language);
+    for above in context_before {
+        lexer.line(above);
+    }
+    let continues_doc_comment = lexer.state == State::Code
+        && lexer.syntax.block_comment.is_some()
+        && line.trim_start().starts_with('*')
+        && !line.trim_start().starts_with("*/");
+    if continues_doc_comment {
+        let start = line.len() - line.trim_start().len();
+        let mut regions = LineRegions::default();
+        regions.comments.push(start..line.len());
+        return regions;
+    }
+    lexer.line(line)
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use super::*;
+
+    fn lex(language: Language, source: &str) -> Vec<LineRegions> {
+        let mut lexer = Lexer::new(&language);
+        source.lines().map(|line| lexer.line(line)).collect()
+    }
+
+    /// Text of the comments and strings of each line
+    fn texts(line: &str, regions: &LineRegions) -> (Vec<String>, Vec<String>) {
+        let text = |ranges: &[Range<usize>]| {
+            ranges.iter().map(|range| line[range.clone()].to_string()).collect()
+        };
+        (text(&regions.comments), text(&regions.strings))
+    }
+
+    #[test]
+    fn test_line_comments_and_strings() {
+        let line = r##"x = "a # b" <> to_string(y) # why "not""##;
+        let regions = &lex(Language::Elixir, line)[0];
+        assert_eq!(
+            texts(line, regions),
+            (vec![r#"# why "not""#.to_string()], vec!["a # b".to_string()])
+        );
+
+        let line = r#"let s = "say \"hi\" // no"; // yes"#;
+        let regions = &lex(Language::Rust, line)[0];
+        assert_eq!(
+            texts(line, regions),
+            (vec!["// yes".to_string()], vec![r#"say \"hi\" // no"#.to_string()])
+        );
+    }
+
+    #[test]
+    fn test_block_comments_span_lines_and_nest() {
+        let source = "a(); /* one\n /* two */ still\n */ b(\"c\");";
+        let lines: Vec<&str> = source.lines().collect();
+        let regions = lex(Language::Rust, source);
+        assert_eq!(texts(lines[0], &regions[0]).0, ["/* one"]);
+        assert_eq!(texts(lines[1], &regions[1]).0, [" /* two */ still"]);
+        assert_eq!(texts(lines[2], &regions[2]), (vec![" */".to_string()], vec!["c".to_string()]));
+
+        // JavaScript block comments do not nest
+        let regions = lex(Language::JavaScript, source);
+        assert_eq!(texts(lines[1], &regions[1]).0, [" /* two */"]);
+        assert!(regions[2].comments.is_empty());
+    }
+
+    #[test]
+    fn test_multiline_strings() {
+        let source = "doc = \"\"\"\nString.to_atom(x) # not a comment\n\"\"\"\nString.to_atom(y)";
+        let regions = lex(Language::Elixir, source);
+        assert!(regions[1].in_string(&(0..17)));
+        assert!(!regions[1].in_comment(&(18..19)));
+        assert!(!regions[3].in_string(&(0..17)));
+
+        // A single-line string left open ends with its line
+        let regions = lex(Language::Python, "x = 'oops\ny = eval(z)");
+        assert!(!regions[1].in_string(&(4..8)));
+    }
+
+    #[test]
+    fn test_char_literals_and_lifetimes() {
+        let line =
+            r#"fn f<'a>(c: char) -> &'a str { if c == '"' || c == '\'' { "q" } else { "" } }"#;
+        let regions = &lex(Language::Rust, line)[0];
+        assert_eq!(texts(line, regions).1, ["\"", "\\'", "q", ""]);
+    }
+
+    #[test]
+    fn test_spans() {
+        let line = r#"log("eval(x)") // eval(y)"#;
+        let regions = &lex(Language::JavaScript, line)[0];
+        assert!(regions.in_string(&(5..12)));
+        // A match including the quotes is not inside the string
+        assert!(!regions.in_string(&(4..13)));
+        assert!(regions.in_comment(&(18..25)));
+        assert!(!regions.in_comment(&(0..3)));
+    }
+
+    #[test]
+    fn test_regions_after_context() {
+        let context = vec!["/**".to_string(), " * Example:".to_string()];
+        assert!(regions_after(&context, "eval(x)", &Language::JavaScript).in_comment(&(0..4)));
+        // The opening of a doc comment above the context
+        assert!(regions_after(&[], " * eval(x)", &Language::JavaScript).in_comment(&(3..7)));
+        assert!(!regions_after(&[], "eval(x)", &Language::JavaScript).in_comment(&(0..4)));
+
+        let file = FileRegions::new("\"\"\"\nx\n\"\"\"\ny", &Language::Python);
+        assert!(file.line(2).unwrap().in_string(&(0..1)));
+        assert!(!file.line(4).unwrap().in_string(&(0..1)));
+        assert!(file.line(0).is_none());
+    }
+
+    #[test]
+    fn test_non_ascii_lines() {
+        let line = "é = \"ü\\é\" <> x # ü";
+        let regions = &lex(Language::Elixir, line)[0];
+        assert_eq!(texts(line, regions), (vec!["# ü".to_string()], vec!["ü\\é".to_string()]));
+        let line = "let c = 'é'; // ü";
+        assert_eq!(texts(line, &lex(Language::Rust, line)[0]).1, ["é"]);
+    }
+}
@@ -0,0 +1,169 @@
+use regex::{RegexSet, RegexSetBuilder, SetMatches};
+use std::collections::HashMap;
+
+use super::prefilter::{LineCandidates, LiteralPrefilter};
+use super::registry::PatternRegistry;
+use super::{AntiPattern, DetectionMethod};
+
+/// The regex line rules of one language compiled into one `RegexSet`, so a single pass
+/// over a line tells which of them match it
+#[derive(Debug)]
+pub struct LanguageRuleSet {
+    set: RegexSet,
+    /// Position in `set` of each rule id
+    indexes: HashMap<String, usize>,
+}
+
+impl LanguageRuleSet {
+    /// The set of the regex line rules among `patterns`, all of one language. Rules whose
+    /// regex does not compile are left out, and `None` when no rule is left.
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a AntiPattern>) -> Option<Self> {
+        let mut regexes = Vec::new();
+        let mut indexes = HashMap::new();
+        for pattern in patterns {
+            let DetectionMethod::Regex { pattern: regex } = &pattern.detection_method else {
+                continue;
+            };
+            if regex_syntax::Parser::new().parse(regex).is_err()
+                || indexes.contains_key(&pattern.id)
+            {
+                continue;
+            }
+            indexes.insert(pattern.id.clone(), regexes.len());
+            regexes.push(regex.as_str());
+        }
+        if regexes.is_empty() {
+            return None;
+        }
+        // Hundreds of rules outgrow the default lazy DFA cache, and a set that keeps clearing
+        // it is slower than running the rules one by one. Past the size limit they do that.
+        let set = RegexSetBuilder::new(regexes).dfa_size_limit(8 << 20).build().ok()?;
+        Some(Self { set, indexes })
+    }
+
+    /// Position of rule `id` in the set, `None` for rules not in it
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.indexes.get(id).copied()
+    }
+
+    /// Which rules of the set match `line`, by position
+    pub fn matches(&self, line: &str) -> SetMatches {
+        self.set.matches(line)
+    }
+}
+
+/// Picks, for each changed line of a file, the rules worth a full match: the literal
+/// prefilter rules out those whose required text is absent, then one `RegexSet` pass per
+/// language keeps the regex rules that match. Rules in neither are always picked.
+pub struct LineMatcher<'a> {
+    prefilter: &'a LiteralPrefilter,
+    filter_indexes: Vec<Option<usize>>,
+    sets: Vec<&'a LanguageRuleSet>,
+    /// For each rule, its set in `sets` and its position in it
+    set_indexes: Vec<Option<(usize, usize)>>,
+    candidates: LineCandidates,
+    set_matches: Vec<Option<SetMatches>>,
+    picked: Vec<usize>,
+}
+
+impl<'a> LineMatcher<'a> {
+    pub fn new(registry: &'a PatternRegistry, patterns: &[&AntiPattern]) -> Self {
+        let prefilter = registry.prefilter();
+        let mut sets: Vec<&LanguageRuleSet> = Vec::new();
+        let set_indexes = patterns
+            .iter()
+            .map(|pattern| {
+                let set = registry.rule_set(&pattern.language)?;
+                let index = set.index_of(&pattern.id)?;
+                let slot = match sets.iter().position(|known| std::ptr::eq(*known, set)) {
+                    Some(slot) => slot,
+                    None => {
+                        sets.push(set);
+                        sets.len() - 1
+                    }
+                };
+                Some((slot, index))
+            })
+            .collect();
+
+        Self {
+            prefilter,
+            filter_indexes: patterns.iter().map(|pattern| prefilter.index_of(pattern)).collect(),
+            set_matches: vec![None; sets.len()],
+            sets,
+            set_indexes,
+            candidates: LineCandidates::default(),
+            picked: Vec::new(),
+        }
+    }
+
+    /// Positions in the matcher's rules of those `line` may match
+    pub fn pick(&mut self, line: &str) -> &[usize] {
+        self.prefilter.scan(line, &mut self.candidates);
+        self.set_matches.iter_mut().for_each(|matches| *matches = None);
+        self.picked.clear();
+
+        for (position, filter_index) in self.filter_indexes.iter().enumerate() {
+            if !self.candidates.may_match(*filter_index) {
+                continue;
+            }
+            // A set is only run once one of its rules passes the prefilter
+            if let Some((slot, index)) = self.set_indexes[position] {
+                let set = self.sets[slot];
+                if !self.set_matches[slot].get_or_insert_with(|| set.matches(line)).matched(index) {
+                    continue;
+                }
+            }
+            self.picked.push(position);
+        }
+        &self.picked
+    }
+}
+
+#[cfg(test)]
+mod line_matcher_tests {
+    use super::*;
+    use crate::core::Language;
+
+    #[test]
+    fn test_picks_the_rules_a_line_matches() {
+        let mut registry = PatternRegistry::new();
+        registry.load_all_embedded_rules().unwrap();
+        let set = registry.rule_set(&Language::Elixir).unwrap();
+        assert!(set.index_of("dynamic_atom_creation").is_some());
+        // Ratio and whole-file rules are matched their own way
+        assert!(set.index_of("comments_overuse").is_none());
+        assert!(set.index_of("complex_else_in_with").is_none());
+
+        let patterns = registry.get_patterns_for_file("lib/app.ex");
+        let mut matcher = LineMatcher::new(&registry, &patterns);
+        let picked: Vec<&str> = matcher
+            .pick("  atom = String.to_atom(name)")
+            .iter()
+            .map(|&position| patterns[position].id.as_str())
+            .collect();
+        assert!(picked.contains(&"dynamic_atom_creation"), "{picked:?}");
+        assert!(!picked.contains(&"sql_injection_ecto"), "{picked:?}");
+
+        let picked: Vec<&str> = matcher
+            .pick("  IO.inspect(user)")
+            .iter()
+            .map(|&position| patterns[position].id.as_str())
+            .collect();
+        assert!(!picked.contains(&"dynamic_atom_creation"), "{picked:?}");
+    }
+
+    #[test]
+    fn test_rules_with_invalid_regex_stay_out_of_the_set() {
+        let mut registry = PatternRegistry::new();
+        registry.load_all_embedded_rules().unwrap();
+        let mut broken = registry.get_pattern("dynamic_atom_creation").unwrap().clone();
+        broken.id = "broken".to_string();
+        broken.detection_method = DetectionMethod::Regex { pattern: "(".to_string() };
+        registry.add_pattern(broken);
+
+        let set = registry.rule_set(&Language::Elixir).unwrap();
+        assert!(set.index_of("broken").is_none());
+        assert!(set.index_of("dynamic_atom_creation").is_some());
+    }
+}
@@ -1,8 +1,35 @@
+pub mod analysis_host;
+pub mod audit;
+pub mod autofix;
+pub mod baseline;
+pub mod branch_policy;
+pub mod budgets;
+pub mod confidence;
+pub mod coverage;
 pub mod custom_rules;
+pub mod fix_backup;
+pub mod formatting;
+pub mod gates;
+pub mod grouping;
+pub mod languages;
+pub mod large_hunks;
+pub mod lexer;
+pub mod line_matcher;
 pub mod pattern;
+pub mod prefilter;
+pub mod progress;
 pub mod project_detector;
+pub mod provenance;
 pub mod registry;
+pub mod result_cache;
 pub mod review_engine;
+pub mod rule_conflicts;
+pub mod rule_doctor;
+pub mod rule_packs;
+pub mod stale_rules;
+pub mod suppression;
+pub mod symlinks;
+pub mod updates;
 
 pub use custom_rules::{CustomRule, CustomRulesManager};
 pub use pattern::{AntiPattern, CodeExample, DetectionMethod, Language, Severity};
@@ -1,8 +1,14 @@
+use anyhow::bail;
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, ValueEnum)]
-#[serde(rename_all = "lowercase")]
+use super::autofix::AutoFix;
+use super::languages;
+
+/// A language rules can target. `Other` names a language declared in config (see
+/// [`languages::LanguageDefinition`]) so rule packs can cover languages patingin does not ship.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
 pub enum Language {
     Elixir,
     JavaScript,
@@ -11,6 +17,56 @@ pub enum Language {
     Rust,
     Zig,
     Sql,
+    Ruby,
+    Php,
+    Java,
+    Kotlin,
+    Other(String),
+}
+
+impl Language {
+    /// Languages patingin ships rules for
+    pub const BUILT_IN: [Language; 11] = [
+        Language::Elixir,
+        Language::JavaScript,
+        Language::TypeScript,
+        Language::Python,
+        Language::Rust,
+        Language::Zig,
+        Language::Sql,
+        Language::Ruby,
+        Language::Php,
+        Language::Java,
+        Language::Kotlin,
+    ];
+
+    /// Pseudo-language of rules about repository changes (symlinks) rather than code
+    pub fn repository() -> Language {
+        Language::Other("git".to_string())
+    }
+
+    pub fn is_built_in(&self) -> bool {
+        !matches!(self, Language::Other(_))
+    }
+
+    /// The language owning a file extension: built-in languages first, then declared ones
+    pub fn from_extension(extension: &str) -> Option<Language> {
+        let extension = extension.to_lowercase();
+        match extension.as_str() {
+            "ex" | "exs" => Some(Language::Elixir),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Language::JavaScript),
+            "ts" | "tsx" => Some(Language::TypeScript),
+            "py" | "pyw" | "pyi" => Some(Language::Python),
+            "rs" => Some(Language::Rust),
+            "zig" => Some(Language::Zig),
+            "sql" | "psql" | "mysql" => Some(Language::Sql),
+            "rb" | "erb" => Some(Language::Ruby),
+            "php" => Some(Language::Php),
+            "java" => Some(Language::Java),
+            "kt" | "kts" => Some(Language::Kotlin),
+            _ => languages::declared_language_for_extension(&extension),
+        }
+    }
 }
 
 impl std::fmt::Display for Language {
@@ -23,24 +79,89 @@ impl std::fmt::Display for Language {
             Language::Rust => write!(f, "rust"),
             Language::Zig => write!(f, "zig"),
             Language::Sql => write!(f, "sql"),
+            Language::Ruby => write!(f, "ruby"),
+            Language::Php => write!(f, "php"),
+            Language::Java => write!(f, "java"),
+            Language::Kotlin => write!(f, "kotlin"),
+            Language::Other(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Case-insensitive; any name that is not built in becomes `Language::Other`
+impl std::str::FromStr for Language {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> anyhow::Result<Self> {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            bail!("Invalid language name '{name}'");
         }
+
+        Ok(Language::BUILT_IN
+            .into_iter()
+            .find(|language| language.to_string() == name)
+            .unwrap_or(Language::Other(name)))
+    }
+}
+
+impl TryFrom<String> for Language {
+    type Error = anyhow::Error;
+
+    fn try_from(name: String) -> anyhow::Result<Self> {
+        name.parse()
+    }
+}
+
+impl From<Language> for String {
+    fn from(language: Language) -> Self {
+        language.to_string()
     }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
+    /// Must never ship, e.g. leaked secrets
+    Blocker,
     Critical,
     Major,
     Warning,
+    /// Worth knowing about, never worth failing a build over on its own
+    Info,
+}
+
+impl Severity {
+    /// Whether this severity meets `threshold`. Variants are ordered most severe first,
+    /// so `Critical.is_at_least(Major)` is true and `Warning.is_at_least(Major)` is false.
+    pub fn is_at_least(self, threshold: Severity) -> bool {
+        self <= threshold
+    }
+
+    /// Every level, most severe first
+    pub const ALL: [Severity; 5] =
+        [Severity::Blocker, Severity::Critical, Severity::Major, Severity::Warning, Severity::Info];
+
+    /// Emoji marking this level in human-readable output
+    pub fn icon(self) -> &'static str {
+        match self {
+            Severity::Blocker => "⛔",
+            Severity::Critical => "🔴",
+            Severity::Major => "🟡",
+            Severity::Warning => "🔵",
+            Severity::Info => "⚪",
+        }
+    }
 }
 
 impl std::fmt::Display for Severity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Severity::Blocker => write!(f, "blocker"),
             Severity::Critical => write!(f, "critical"),
             Severity::Major => write!(f, "major"),
             Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
         }
     }
 }
@@ -48,14 +169,51 @@ impl std::fmt::Display for Severity {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum DetectionMethod {
-    Regex { pattern: String },
-    Ast { pattern: String },
-    LineCount { threshold: usize, pattern: String },
-    Ratio { threshold: f64, pattern: String },
-    Custom { pattern: String },
+    Regex {
+        pattern: String,
+    },
+    Ast {
+        pattern: String,
+    },
+    LineCount {
+        threshold: usize,
+        pattern: String,
+    },
+    Ratio {
+        threshold: f64,
+        pattern: String,
+    },
+    Custom {
+        pattern: String,
+    },
+    /// Lines wider than `max_width` display columns (tabs and wide characters accounted for)
+    LineLength {
+        max_width: usize,
+        tab_width: usize,
+    },
+    /// Lines nested deeper than `max_depth` levels of `indent_width` columns
+    IndentDepth {
+        max_depth: usize,
+        indent_width: usize,
+        tab_width: usize,
+    },
+    /// A regex over the whole file instead of one line, so a match may span lines; `^` and
+    /// `$` match at line boundaries. A match is reported at its first line when an added
+    /// line falls inside it. With `unless`, a match only counts when `unless` matches nowhere
+    /// within `window` lines of it, or nowhere in the file without a window, and then when an
+    /// added line falls in that range.
+    Multiline {
+        pattern: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unless: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        window: Option<usize>,
+    },
+    /// A regular file turned into a symbolic link; checked per changed path, not per line
+    SymlinkReplacement,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CodeExample {
     pub bad: String,
     pub good: String,
@@ -75,6 +233,18 @@ pub struct AntiPattern {
     pub claude_code_fixable: bool,
     pub examples: Vec<CodeExample>,
     pub tags: Vec<String>,
+    /// Extra guidance for AI fixes, beyond the fix suggestion and examples
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_hint: Option<String>,
+    /// Rewrite template that `review --fix-simple` applies without an AI provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_fix: Option<AutoFix>,
+    /// Ignore matches inside comments, e.g. commented-out code
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_comments: bool,
+    /// Ignore matches inside string literals, e.g. log messages naming the call
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_strings: bool,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
 }
@@ -85,7 +255,7 @@ fn default_enabled() -> bool {
 
 impl AntiPattern {
     pub fn matches_file_extension(&self, extension: &str) -> bool {
-        match self.language {
+        match &self.language {
             Language::Elixir => matches!(extension, "ex" | "exs"),
             Language::JavaScript => matches!(extension, "js" | "jsx" | "mjs"),
             Language::TypeScript => matches!(extension, "ts" | "tsx"),
@@ -93,6 +263,12 @@ impl AntiPattern {
             Language::Rust => matches!(extension, "rs"),
             Language::Zig => matches!(extension, "zig"),
             Language::Sql => matches!(extension, "sql"),
+            Language::Ruby => matches!(extension, "rb" | "erb"),
+            Language::Php => matches!(extension, "php"),
+            Language::Java => matches!(extension, "java"),
+            Language::Kotlin => matches!(extension, "kt" | "kts"),
+            Language::Other(name) => languages::language_definition(name)
+                .is_some_and(|definition| definition.extensions.iter().any(|ext| ext == extension)),
         }
     }
 }
@@ -0,0 +1,179 @@
+use aho_corasick::AhoCorasick;
+use regex_syntax::hir::literal::{ExtractKind, Extractor};
+use std::collections::HashMap;
+
+use super::{AntiPattern, DetectionMethod, Language};
+
+/// Literals one of which every match of `pattern` contains, or `None` when the pattern has
+/// no such set, e.g. one starting and ending in `\w+`. Matches start with one of the prefix
+/// literals and end with one of the suffix literals; whichever set has the longer shortest
+/// literal is kept, as it rules out more lines.
+pub fn required_literals(pattern: &str) -> Option<Vec<Vec<u8>>> {
+    let hir = regex_syntax::Parser::new().parse(pattern).ok()?;
+    let extract = |kind: ExtractKind| -> Option<Vec<Vec<u8>>> {
+        let seq = Extractor::new().kind(kind).extract(&hir);
+        let literals = seq.literals()?;
+        // An empty literal is in every line, and an empty set is a pattern matching nothing
+        if literals.is_empty() || literals.iter().any(|literal| literal.as_bytes().is_empty()) {
+            return None;
+        }
+        Some(literals.iter().map(|literal| literal.as_bytes().to_vec()).collect())
+    };
+    let shortest = |literals: &Vec<Vec<u8>>| literals.iter().map(Vec::len).min().unwrap_or(0);
+
+    match (extract(ExtractKind::Prefix), extract(ExtractKind::Suffix)) {
+        (Some(prefixes), Some(suffixes)) if shortest(&suffixes) > shortest(&prefixes) => {
+            Some(suffixes)
+        }
+        (Some(prefixes), _) => Some(prefixes),
+        (None, suffixes) => suffixes,
+    }
+}
+
+/// A multi-literal search deciding which line rules a changed line can possibly match, so
+/// only those run their regex. Rules without required literals are never filtered out.
+#[derive(Debug, Default)]
+pub struct LiteralPrefilter {
+    matcher: Option<AhoCorasick>,
+    /// For each literal of `matcher`, the filtered rules requiring it
+    rules_by_literal: Vec<Vec<usize>>,
+    /// Index of each filtered rule variant
+    filtered: HashMap<(String, Language), usize>,
+}
+
+/// Which filtered rules a line contains a required literal of, from `LiteralPrefilter::scan`
+#[derive(Debug, Default)]
+pub struct LineCandidates(Vec<bool>);
+
+impl LiteralPrefilter {
+    /// The prefilter for the regex line rules among `patterns`
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a AntiPattern>) -> Self {
+        let mut literal_ids: HashMap<Vec<u8>, usize> = HashMap::new();
+        let mut rules_by_literal: Vec<Vec<usize>> = Vec::new();
+        let mut filtered = HashMap::new();
+
+        for pattern in patterns {
+            let DetectionMethod::Regex { pattern: regex } = &pattern.detection_method else {
+                continue;
+            };
+            let Some(literals) = required_literals(regex) else { continue };
+            let rule = filtered.len();
+            filtered.insert((pattern.id.clone(), pattern.language.clone()), rule);
+            for literal in literals {
+                let next = literal_ids.len();
+                let id = *literal_ids.entry(literal).or_insert(next);
+                if id == rules_by_literal.len() {
+                    rules_by_literal.push(Vec::new());
+                }
+                rules_by_literal[id].push(rule);
+            }
+        }
+
+        let mut literals: Vec<(Vec<u8>, usize)> = literal_ids.into_iter().collect();
+        literals.sort_by_key(|(_, id)| *id);
+        let matcher = match literals.is_empty() {
+            true => None,
+            false => AhoCorasick::new(literals.iter().map(|(literal, _)| literal)).ok(),
+        };
+        // Without a matcher nothing can be ruled out
+        if matcher.is_none() {
+            filtered.clear();
+        }
+        Self { matcher, rules_by_literal, filtered }
+    }
+
+    /// The index `pattern` is filtered under; `None` for rules that always run
+    pub fn index_of(&self, pattern: &AntiPattern) -> Option<usize> {
+        self.filtered.get(&(pattern.id.clone(), pattern.language.clone())).copied()
+    }
+
+    /// Find the filtered rules `line` has a required literal of, reusing `candidates`
+    pub fn scan(&self, line: &str, candidates: &mut LineCandidates) {
+        candidates.0.clear();
+        candidates.0.resize(self.filtered.len(), false);
+        let Some(matcher) = &self.matcher else { return };
+        for found in matcher.find_overlapping_iter(line) {
+            for &rule in &self.rules_by_literal[found.pattern().as_usize()] {
+                candidates.0[rule] = true;
+            }
+        }
+    }
+}
+
+impl LineCandidates {
+    /// Whether the rule at `index` (from `LiteralPrefilter::index_of`) may match the line;
+    /// always for rules that are not filtered
+    pub fn may_match(&self, index: Option<usize>) -> bool {
+        index.map_or(true, |index| self.0[index])
+    }
+}
+
+#[cfg(test)]
+mod prefilter_tests {
+    use super::*;
+    use crate::core::registry::PatternRegistry;
+    use regex::Regex;
+
+    fn literals(pattern: &str) -> Option<Vec<String>> {
+        let literals = required_literals(pattern)?;
+        Some(literals.into_iter().map(|literal| String::from_utf8(literal).unwrap()).collect())
+    }
+
+    #[test]
+    fn test_required_literals() {
+        assert_eq!(literals(r"String\.to_atom\s*\("), Some(vec!["String.to_atom".to_string()]));
+        assert_eq!(
+            literals(r"(IO|Logger)\.inspect"),
+            Some(vec!["IO.inspect".into(), "Logger.inspect".into()])
+        );
+        // No prefix, but every match ends with the literal
+        assert_eq!(literals(r"\w+\s*==\s*nil"), Some(vec!["nil".to_string()]));
+        assert_eq!(literals(r"\w+\s*\+\s*\w+"), None);
+        assert_eq!(literals(r"^"), None);
+        assert_eq!(literals("("), None);
+        let case_insensitive = literals(r"(?i)todo").unwrap();
+        assert!(case_insensitive.contains(&"ToDo".to_string()), "{case_insensitive:?}");
+    }
+
+    #[test]
+    fn test_prefilter_never_drops_a_matching_line() {
+        let mut registry = PatternRegistry::new();
+        registry.load_all_embedded_rules().unwrap();
+        let patterns = registry.all_patterns();
+        let prefilter = LiteralPrefilter::new(patterns.iter().copied());
+        assert!(!prefilter.filtered.is_empty());
+
+        let lines: Vec<&str> = patterns
+            .iter()
+            .flat_map(|pattern| &pattern.examples)
+            .flat_map(|example| example.bad.lines().chain(example.good.lines()))
+            .collect();
+        let line_rules: Vec<(&AntiPattern, Regex)> = patterns
+            .iter()
+            .filter_map(|pattern| match &pattern.detection_method {
+                DetectionMethod::Regex { pattern: regex } => {
+                    Some((*pattern, Regex::new(regex).ok()?))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut candidates = LineCandidates::default();
+        for line in lines {
+            prefilter.scan(line, &mut candidates);
+            for (pattern, regex) in &line_rules {
+                if regex.is_match(line) {
+                    assert!(
+                        candidates.may_match(prefilter.index_of(pattern)),
+                        "{} was filtered out of {line:?}",
+                        pattern.id
+                    );
+                }
+            }
+        }
+
+        prefilter.scan("let x = 1;", &mut candidates);
+        let atoms = registry.get_pattern("dynamic_atom_creation").unwrap();
+        assert!(!candidates.may_match(prefilter.index_of(atoms)));
+    }
+}
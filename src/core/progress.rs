@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use crate::core::{ProjectDetector, ReviewViolation};
+use crate::git::GitIntegration;
+
+/// Run state file, relative to the project root
+pub const STATE_FILE: &str = ".patingin/state.json";
+
+/// What `.patingin/state.json` holds: fingerprints of the violations the last run found,
+/// by branch and then by reviewed scope, so "staged changes" and "changes since main" on
+/// the same branch are tracked apart.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct RunState {
+    #[serde(default)]
+    branches: BTreeMap<String, BTreeMap<String, BTreeSet<String>>>,
+}
+
+/// Tracks violations across runs, to report how many disappeared since the previous run of
+/// the same scope on the same branch
+pub struct ProgressTracker {
+    path: PathBuf,
+    branch: String,
+    scope: String,
+    state: RunState,
+}
+
+impl ProgressTracker {
+    /// Load the state kept at `path`. A missing or unreadable file starts fresh: the state
+    /// only feeds a progress note, so it must never fail a review.
+    pub fn open(path: impl Into<PathBuf>, branch: &str, scope: &str) -> Self {
+        let path = path.into();
+        let state = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, branch: branch.to_string(), scope: scope.to_string(), state }
+    }
+
+    /// The tracker for the detected project and its current branch
+    pub fn for_project(scope: &str) -> Result<Option<Self>> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(None);
+        };
+        let root = &project_info.root_path;
+        let branch = match GitIntegration::new(root) {
+            Ok(git) => git.get_current_branch()?,
+            Err(_) => "(no branch)".to_string(),
+        };
+        Ok(Some(Self::open(root.join(STATE_FILE), &branch, scope)))
+    }
+
+    /// Store this run's violations and return how many of the previous run's are gone, or
+    /// `None` when this scope has not been reviewed on this branch before
+    pub fn record(&mut self, violations: &[ReviewViolation]) -> Result<Option<usize>> {
+        let current: BTreeSet<String> = violations.iter().map(|v| v.fingerprint()).collect();
+        let scopes = self.state.branches.entry(self.branch.clone()).or_default();
+        let fixed = scopes.get(&self.scope).map(|previous| previous.difference(&current).count());
+        scopes.insert(self.scope.clone(), current);
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.state)?)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        Ok(fixed)
+    }
+}
+
+#[cfg(test)]
+mod progress_tests {
+    use super::*;
+    use crate::core::Severity;
+    use crate::report::test_support::violation;
+
+    #[test]
+    fn test_fixed_violations_are_counted_per_branch_and_scope() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(STATE_FILE);
+        let mut moved = violation("lib/b.ex", 9, Severity::Warning);
+        moved.content = "b".to_string();
+        let before = vec![violation("lib/a.ex", 1, Severity::Critical), moved.clone()];
+
+        let mut tracker = ProgressTracker::open(&path, "feature", "staged changes");
+        assert_eq!(tracker.record(&before).unwrap(), None);
+
+        // A finding that only moved lines is not fixed
+        moved.line_number = 12;
+        let mut tracker = ProgressTracker::open(&path, "feature", "staged changes");
+        assert_eq!(tracker.record(&[moved]).unwrap(), Some(1));
+
+        let mut tracker = ProgressTracker::open(&path, "feature", "changes since main");
+        assert_eq!(tracker.record(&[]).unwrap(), None);
+        let mut tracker = ProgressTracker::open(&path, "main", "staged changes");
+        assert_eq!(tracker.record(&[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_unreadable_state_starts_fresh() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("state.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let mut tracker = ProgressTracker::open(&path, "main", "staged changes");
+        assert_eq!(tracker.record(&[]).unwrap(), None);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("\"main\""));
+    }
+}
@@ -23,6 +23,10 @@ pub enum ProjectType {
     Python,
     Rust,
     Zig,
+    Ruby,
+    Php,
+    Java,
+    Kotlin,
     Generic,
 }
 
@@ -79,6 +83,11 @@ impl ProjectDetector {
             "requirements.txt", // Python
             "Cargo.toml",       // Rust
             "build.zig",        // Zig
+            "Gemfile",          // Ruby
+            "composer.json",    // PHP
+            "pom.xml",          // Java
+            "build.gradle",     // Java
+            "build.gradle.kts", // Kotlin
         ];
 
         let mut current = start_path.to_path_buf();
@@ -204,6 +213,11 @@ impl ProjectDetector {
             ("requirements.txt", Language::Python, ProjectType::Python),
             ("Cargo.toml", Language::Rust, ProjectType::Rust),
             ("build.zig", Language::Zig, ProjectType::Zig),
+            ("Gemfile", Language::Ruby, ProjectType::Ruby),
+            ("composer.json", Language::Php, ProjectType::Php),
+            ("pom.xml", Language::Java, ProjectType::Java),
+            ("build.gradle", Language::Java, ProjectType::Java),
+            ("build.gradle.kts", Language::Kotlin, ProjectType::Kotlin),
         ];
 
         for (file_name, language, proj_type) in package_checks {
@@ -238,29 +252,17 @@ impl ProjectDetector {
     fn detect_languages_from_files(project_root: &Path) -> Result<Vec<Language>> {
         let mut languages = Vec::new();
 
-        let extension_map = vec![
-            (vec!["ex", "exs"], Language::Elixir),
-            (vec!["js", "jsx", "mjs", "cjs"], Language::JavaScript),
-            (vec!["ts", "tsx"], Language::TypeScript),
-            (vec!["py", "pyw", "pyi"], Language::Python),
-            (vec!["rs"], Language::Rust),
-            (vec!["zig"], Language::Zig),
-            (vec!["sql", "psql", "mysql"], Language::Sql),
-        ];
-
         // Walk through directory and collect extensions
         if let Ok(entries) = fs::read_dir(project_root) {
             for entry in entries.flatten() {
                 if let Ok(metadata) = entry.metadata() {
                     if metadata.is_file() {
                         if let Some(extension) = entry.path().extension() {
-                            if let Some(ext_str) = extension.to_str() {
-                                for (extensions, language) in &extension_map {
-                                    if extensions.contains(&ext_str.to_lowercase().as_str())
-                                        && !languages.contains(language)
-                                    {
-                                        languages.push(language.clone());
-                                    }
+                            if let Some(language) =
+                                extension.to_str().and_then(Language::from_extension)
+                            {
+                                if !languages.contains(&language) {
+                                    languages.push(language);
                                 }
                             }
                         }
@@ -277,12 +279,7 @@ impl ProjectDetector {
         let lang_list = if project_info.languages.is_empty() {
             "unknown".to_string()
         } else {
-            project_info
-                .languages
-                .iter()
-                .map(|l| format!("{l:?}").to_lowercase())
-                .collect::<Vec<_>>()
-                .join(", ")
+            project_info.languages.iter().map(|l| l.to_string()).collect::<Vec<_>>().join(", ")
         };
 
         format!(
@@ -382,6 +379,63 @@ mod project_detector_tests {
         assert!(project_info.package_files.contains(&"Cargo.toml".to_string()));
     }
 
+    #[test]
+    fn test_detect_ruby_project() {
+        let temp_dir = TempDir::new().expect("Should create temp dir");
+        let project_root = temp_dir.path();
+
+        fs::write(project_root.join("Gemfile"), "source \"https://rubygems.org\"\ngem \"rails\"")
+            .expect("Should write Gemfile");
+
+        let project_info =
+            ProjectDetector::analyze_project(project_root).expect("Should detect Ruby project");
+
+        assert!(project_info.languages.contains(&Language::Ruby));
+        assert!(matches!(project_info.project_type, ProjectType::Ruby));
+        assert!(project_info.package_files.contains(&"Gemfile".to_string()));
+    }
+
+    #[test]
+    fn test_detect_php_project() {
+        let temp_dir = TempDir::new().expect("Should create temp dir");
+        let project_root = temp_dir.path();
+
+        fs::write(project_root.join("composer.json"), r#"{"name": "acme/shop"}"#)
+            .expect("Should write composer.json");
+
+        let project_info =
+            ProjectDetector::analyze_project(project_root).expect("Should detect PHP project");
+
+        assert!(project_info.languages.contains(&Language::Php));
+        assert!(matches!(project_info.project_type, ProjectType::Php));
+        assert!(project_info.package_files.contains(&"composer.json".to_string()));
+    }
+
+    #[test]
+    fn test_detect_jvm_projects() {
+        for (build_file, language) in [
+            ("pom.xml", Language::Java),
+            ("build.gradle", Language::Java),
+            ("build.gradle.kts", Language::Kotlin),
+        ] {
+            let temp_dir = TempDir::new().expect("Should create temp dir");
+            fs::write(temp_dir.path().join(build_file), "").expect("Should write build file");
+
+            let project_info = ProjectDetector::analyze_project(temp_dir.path())
+                .expect("Should detect JVM project");
+
+            assert_eq!(project_info.package_files, [build_file]);
+            assert!(
+                matches!(
+                    (&language, &project_info.project_type),
+                    (Language::Java, ProjectType::Java) | (Language::Kotlin, ProjectType::Kotlin)
+                ),
+                "{build_file}"
+            );
+            assert_eq!(project_info.languages, [language]);
+        }
+    }
+
     #[test]
     fn test_git_root_detection() {
         let temp_dir = TempDir::new().expect("Should create temp dir");
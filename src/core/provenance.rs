@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{AntiPattern, Language};
+
+/// Where a rule was loaded from, so a finding can be taken to the right place: upstream
+/// docs for a built-in rule, the team for a project rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleSource {
+    /// A rule pack compiled into the binary. Packs ship with patingin, so they carry its
+    /// version.
+    BuiltIn { pack: String, version: String },
+    /// A rule pack installed with `patingin rules import`
+    Installed { pack: String },
+    /// A custom rule of the project, from the user rules file
+    Project { project: String },
+    /// A team rule committed to the repository, at `path` relative to its root
+    Repository { path: String },
+}
+
+impl RuleSource {
+    pub fn built_in(pack: &str) -> Self {
+        RuleSource::BuiltIn {
+            pack: pack.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    pub fn installed(pack: &str) -> Self {
+        RuleSource::Installed { pack: pack.to_string() }
+    }
+}
+
+impl std::fmt::Display for RuleSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleSource::BuiltIn { pack, version } => write!(f, "built-in {pack} pack v{version}"),
+            RuleSource::Installed { pack } => write!(f, "installed {pack} pack"),
+            RuleSource::Project { project } => write!(f, "custom rules of project {project}"),
+            RuleSource::Repository { path } => write!(f, "team rules in {path}"),
+        }
+    }
+}
+
+/// The source of every language variant of the loaded rules
+#[derive(Debug, Clone, Default)]
+pub struct RuleSources(HashMap<(String, Language), RuleSource>);
+
+impl RuleSources {
+    pub fn insert(&mut self, rule: &AntiPattern, source: RuleSource) {
+        self.0.insert((rule.id.clone(), rule.language.clone()), source);
+    }
+
+    /// Forget the source of a variant that is being replaced
+    pub fn remove(&mut self, rule: &AntiPattern) {
+        self.0.remove(&(rule.id.clone(), rule.language.clone()));
+    }
+
+    /// The source of the variant `rule` is; `None` for rules added without one
+    pub fn get(&self, rule: &AntiPattern) -> Option<&RuleSource> {
+        self.0.get(&(rule.id.clone(), rule.language.clone()))
+    }
+}
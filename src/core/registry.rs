@@ -1,15 +1,96 @@
-use super::custom_rules::CustomRulesManager;
-use super::pattern::{AntiPattern, Language, Severity};
+use super::autofix::AutoFix;
+use super::custom_rules::{CustomRulesManager, REPO_RULES_FILE};
+use super::languages::declare_languages;
+use super::line_matcher::LanguageRuleSet;
+use super::pattern::{AntiPattern, DetectionMethod, Language, Severity};
+use super::prefilter::LiteralPrefilter;
+use super::provenance::{RuleSource, RuleSources};
+use super::rule_conflicts::{
+    ConflictPolicy, Resolution, RuleConflict, RuleConflicts, RuleOrigin, ShadowedRule,
+};
+use super::rule_packs::installed_packs;
+use crate::config::RuleSettings;
 use anyhow::Result;
-use once_cell::sync::Lazy;
-use regex::Regex;
+use clap::ValueEnum;
+use once_cell::sync::{Lazy, OnceCell};
+use regex::{Regex, RegexBuilder};
 use std::collections::HashMap;
 use std::path::Path;
 
+const ELIXIR_RULES: &str = include_str!("../rules/builtin/elixir.yml");
+const JAVASCRIPT_RULES: &str = include_str!("../rules/builtin/javascript.yml");
+const TYPESCRIPT_RULES: &str = include_str!("../rules/builtin/typescript.yml");
+const PYTHON_RULES: &str = include_str!("../rules/builtin/python.yml");
+const RUST_RULES: &str = include_str!("../rules/builtin/rust.yml");
+const ZIG_RULES: &str = include_str!("../rules/builtin/zig.yml");
+const SQL_RULES: &str = include_str!("../rules/builtin/sql.yml");
+const RUBY_RULES: &str = include_str!("../rules/builtin/ruby.yml");
+const PHP_RULES: &str = include_str!("../rules/builtin/php.yml");
+const JAVA_RULES: &str = include_str!("../rules/builtin/java.yml");
+const KOTLIN_RULES: &str = include_str!("../rules/builtin/kotlin.yml");
+const FORMATTING_RULES: &str = include_str!("../rules/builtin/formatting.yml");
+const REPOSITORY_RULES: &str = include_str!("../rules/builtin/repository.yml");
+const SHARED_RULES: &str = include_str!("../rules/builtin/shared.yml");
+
+/// Rule files compiled into the binary, by pack name, in load order
+pub const BUILT_IN_RULE_PACKS: [(&str, &str); 14] = [
+    ("elixir", ELIXIR_RULES),
+    ("javascript", JAVASCRIPT_RULES),
+    ("typescript", TYPESCRIPT_RULES),
+    ("python", PYTHON_RULES),
+    ("rust", RUST_RULES),
+    ("zig", ZIG_RULES),
+    ("sql", SQL_RULES),
+    ("ruby", RUBY_RULES),
+    ("php", PHP_RULES),
+    ("java", JAVA_RULES),
+    ("kotlin", KOTLIN_RULES),
+    ("formatting", FORMATTING_RULES),
+    ("repository", REPOSITORY_RULES),
+    ("shared", SHARED_RULES),
+];
+
+/// Rules keyed by id and language. A rule declared for several languages keeps one id
+/// (for reports, suppressions and `rules --detail`) and is stored once per language
+/// variant, each with its own pattern.
 pub struct PatternRegistry {
     patterns: HashMap<String, AntiPattern>,
+    variants: HashMap<String, Vec<String>>,
     by_language: HashMap<Language, Vec<String>>,
-    pub compiled_patterns: HashMap<String, Regex>,
+    /// Compiled regexes by rule id, one per language variant
+    pub compiled_patterns: HashMap<String, Vec<(Language, Regex)>>,
+    /// Project rules that collided with a loaded rule, and how they were resolved
+    conflicts: Vec<RuleConflict>,
+    /// Rules replaced by a later rule with the same id and language
+    shadowed: Vec<ShadowedRule>,
+    sources: RuleSources,
+    /// Required literals of the line rules, built on first use after the rules change
+    prefilter: OnceCell<LiteralPrefilter>,
+    /// The regex line rules of each language as one `RegexSet`, built like `prefilter`
+    rule_sets: OnceCell<HashMap<Language, LanguageRuleSet>>,
+}
+
+/// The rules read from a rule pack file
+#[derive(Debug, Default)]
+pub struct ParsedRulePack {
+    /// One entry per language variant
+    pub rules: Vec<AntiPattern>,
+    /// Entries left out, each as "<rule id>: <reason>"
+    pub skipped: Vec<String>,
+}
+
+/// One `rules:` setting applied to a rule variant
+#[derive(Clone, Copy)]
+enum RuleChange {
+    Enable,
+    Disable,
+    Severity(Severity),
+    Threshold(f64),
+}
+
+/// Registry key of one language variant of a rule
+fn variant_key(id: &str, language: &Language) -> String {
+    format!("{id}@{language}")
 }
 
 impl Default for PatternRegistry {
@@ -22,50 +103,83 @@ impl PatternRegistry {
     pub fn new() -> Self {
         Self {
             patterns: HashMap::new(),
+            variants: HashMap::new(),
             by_language: HashMap::new(),
             compiled_patterns: HashMap::new(),
+            conflicts: Vec::new(),
+            shadowed: Vec::new(),
+            sources: RuleSources::default(),
+            prefilter: OnceCell::new(),
+            rule_sets: OnceCell::new(),
         }
     }
 
+    /// Load the built-in rules and the installed rule packs, and compile them
     pub fn load_built_in_patterns(&mut self) -> Result<()> {
         self.load_all_embedded_rules()?;
+        self.load_installed_packs()?;
         self.compile_all_patterns()?;
         Ok(())
     }
 
     pub fn load_embedded_elixir_rules(&mut self) -> Result<()> {
-        const ELIXIR_RULES: &str = include_str!("../rules/builtin/elixir.yml");
-        self.load_rules_from_yaml(ELIXIR_RULES, Language::Elixir)
+        self.load_rules_from_yaml(ELIXIR_RULES, "elixir")
     }
 
     pub fn load_embedded_javascript_rules(&mut self) -> Result<()> {
-        const JAVASCRIPT_RULES: &str = include_str!("../rules/builtin/javascript.yml");
-        self.load_rules_from_yaml(JAVASCRIPT_RULES, Language::JavaScript)
+        self.load_rules_from_yaml(JAVASCRIPT_RULES, "javascript")
     }
 
     pub fn load_embedded_typescript_rules(&mut self) -> Result<()> {
-        const TYPESCRIPT_RULES: &str = include_str!("../rules/builtin/typescript.yml");
-        self.load_rules_from_yaml(TYPESCRIPT_RULES, Language::TypeScript)
+        self.load_rules_from_yaml(TYPESCRIPT_RULES, "typescript")
     }
 
     pub fn load_embedded_python_rules(&mut self) -> Result<()> {
-        const PYTHON_RULES: &str = include_str!("../rules/builtin/python.yml");
-        self.load_rules_from_yaml(PYTHON_RULES, Language::Python)
+        self.load_rules_from_yaml(PYTHON_RULES, "python")
     }
 
     pub fn load_embedded_rust_rules(&mut self) -> Result<()> {
-        const RUST_RULES: &str = include_str!("../rules/builtin/rust.yml");
-        self.load_rules_from_yaml(RUST_RULES, Language::Rust)
+        self.load_rules_from_yaml(RUST_RULES, "rust")
     }
 
     pub fn load_embedded_zig_rules(&mut self) -> Result<()> {
-        const ZIG_RULES: &str = include_str!("../rules/builtin/zig.yml");
-        self.load_rules_from_yaml(ZIG_RULES, Language::Zig)
+        self.load_rules_from_yaml(ZIG_RULES, "zig")
     }
 
     pub fn load_embedded_sql_rules(&mut self) -> Result<()> {
-        const SQL_RULES: &str = include_str!("../rules/builtin/sql.yml");
-        self.load_rules_from_yaml(SQL_RULES, Language::Sql)
+        self.load_rules_from_yaml(SQL_RULES, "sql")
+    }
+
+    pub fn load_embedded_ruby_rules(&mut self) -> Result<()> {
+        self.load_rules_from_yaml(RUBY_RULES, "ruby")
+    }
+
+    pub fn load_embedded_php_rules(&mut self) -> Result<()> {
+        self.load_rules_from_yaml(PHP_RULES, "php")
+    }
+
+    pub fn load_embedded_java_rules(&mut self) -> Result<()> {
+        self.load_rules_from_yaml(JAVA_RULES, "java")
+    }
+
+    pub fn load_embedded_kotlin_rules(&mut self) -> Result<()> {
+        self.load_rules_from_yaml(KOTLIN_RULES, "kotlin")
+    }
+
+    /// Long-line and deep-indentation rules for every language, kept apart from the
+    /// anti-pattern catalogues because they are style checks
+    pub fn load_embedded_formatting_rules(&mut self) -> Result<()> {
+        self.load_rules_from_yaml(FORMATTING_RULES, "formatting")
+    }
+
+    /// Language-independent checks on symlinks and other repository-level changes
+    pub fn load_embedded_repository_rules(&mut self) -> Result<()> {
+        self.load_rules_from_yaml(REPOSITORY_RULES, "repository")
+    }
+
+    /// Rules declared once with a pattern variant per language
+    pub fn load_embedded_shared_rules(&mut self) -> Result<()> {
+        self.load_rules_from_yaml(SHARED_RULES, "shared")
     }
 
     pub fn load_all_embedded_rules(&mut self) -> Result<()> {
@@ -76,75 +190,351 @@ impl PatternRegistry {
         self.load_embedded_rust_rules()?;
         self.load_embedded_zig_rules()?;
         self.load_embedded_sql_rules()?;
+        self.load_embedded_ruby_rules()?;
+        self.load_embedded_php_rules()?;
+        self.load_embedded_java_rules()?;
+        self.load_embedded_kotlin_rules()?;
+        self.load_embedded_formatting_rules()?;
+        self.load_embedded_repository_rules()?;
+        self.load_embedded_shared_rules()?;
         Ok(())
     }
 
+    #[allow(dead_code)] // Used in tests and by library consumers
     pub fn load_custom_rules(&mut self, project_name: &str) -> Result<()> {
+        self.load_custom_rules_with_policy(project_name, None, ConflictPolicy::default())
+    }
+
+    /// Load a project's rules and apply its overrides, resolving id collisions with
+    /// `policy`. Under `ConflictPolicy::Error` a collision fails with `RuleConflicts` and no
+    /// rule is loaded.
+    ///
+    /// With a `project_root`, the team rules of its `.patingin/rules.yml` are loaded too;
+    /// see `CustomRulesManager::get_user_and_team_rules` for which of two rules wins.
+    pub fn load_custom_rules_with_policy(
+        &mut self,
+        project_name: &str,
+        project_root: Option<&Path>,
+        policy: ConflictPolicy,
+    ) -> Result<()> {
         let custom_rules_manager = CustomRulesManager::new();
-        let custom_patterns = custom_rules_manager.get_project_rules(project_name)?;
+        declare_languages(&custom_rules_manager.load_config()?.languages)?;
+        let (custom_patterns, repo_patterns) =
+            custom_rules_manager.get_user_and_team_rules(project_name, project_root)?;
+
+        let source = RuleSource::Project { project: project_name.to_string() };
+        self.add_custom_patterns(custom_patterns, &source, policy)?;
+        let source = RuleSource::Repository { path: REPO_RULES_FILE.to_string() };
+        self.add_custom_patterns(repo_patterns, &source, policy)?;
+
+        // Overrides are validated when `patingin rules` records them, so ids of rules that
+        // were removed since are ignored
+        self.apply_rule_settings(&custom_rules_manager.project_overrides(project_name)?);
+        Ok(())
+    }
 
-        for pattern in custom_patterns {
-            self.add_pattern(pattern);
+    /// Register project rules loaded from `source`. A rule whose id and language are taken,
+    /// by a built-in rule or an earlier project rule, is resolved with `policy` and recorded
+    /// in `conflicts`.
+    pub fn add_custom_patterns(
+        &mut self,
+        patterns: Vec<AntiPattern>,
+        source: &RuleSource,
+        policy: ConflictPolicy,
+    ) -> Result<()> {
+        let mut conflicts = Vec::new();
+        let mut accepted: Vec<AntiPattern> = Vec::new();
+        let accepted_variant = |accepted: &[AntiPattern], id: &str, language: &Language| {
+            accepted.iter().rposition(|p| p.id == id && p.language == *language)
+        };
+
+        for mut pattern in patterns {
+            let earlier = accepted_variant(&accepted, &pattern.id, &pattern.language);
+            let (existing, existing_severity) = match earlier {
+                Some(index) => (RuleOrigin::Project, accepted[index].severity),
+                None => match self.patterns.get(&variant_key(&pattern.id, &pattern.language)) {
+                    Some(built_in) => (RuleOrigin::BuiltIn, built_in.severity),
+                    None => {
+                        accepted.push(pattern);
+                        continue;
+                    }
+                },
+            };
+
+            let resolution = match policy {
+                ConflictPolicy::Error => Resolution::Refused,
+                ConflictPolicy::PreferCustom => Resolution::KeptCustom,
+                ConflictPolicy::PreferBuiltin => Resolution::KeptExisting,
+                ConflictPolicy::Rename => {
+                    let taken = |id: &str| {
+                        self.patterns.contains_key(&variant_key(id, &pattern.language))
+                            || accepted_variant(&accepted, id, &pattern.language).is_some()
+                    };
+                    let new_id = (2..)
+                        .map(|suffix| format!("{}_{suffix}", pattern.id))
+                        .find(|id| !taken(id))
+                        .expect("some suffix is free");
+                    Resolution::Renamed(new_id)
+                }
+            };
+            conflicts.push(RuleConflict {
+                id: pattern.id.clone(),
+                language: pattern.language.clone(),
+                existing,
+                existing_severity,
+                custom_severity: pattern.severity,
+                resolution: resolution.clone(),
+            });
+
+            match resolution {
+                Resolution::Refused | Resolution::KeptExisting => {}
+                Resolution::KeptCustom => {
+                    // A replaced project rule is dropped; `add_pattern` replaces built-ins
+                    if let Some(index) = earlier {
+                        accepted.remove(index);
+                    }
+                    accepted.push(pattern);
+                }
+                Resolution::Renamed(new_id) => {
+                    pattern.id = new_id;
+                    accepted.push(pattern);
+                }
+            }
         }
 
+        if policy == ConflictPolicy::Error && !conflicts.is_empty() {
+            return Err(RuleConflicts(conflicts).into());
+        }
+        for pattern in accepted {
+            self.register(pattern, Some(source.clone()));
+        }
+        self.conflicts.extend(conflicts);
         Ok(())
     }
 
+    /// Apply a project's `rules:` settings to every language variant of the rules they
+    /// name. Returns a warning for each setting that names no rule, or a threshold for a
+    /// rule without one, so typos do not go unnoticed.
+    pub fn apply_rule_settings(&mut self, settings: &RuleSettings) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mut variants_of = |id: &str, setting: &str| -> Vec<String> {
+            let keys = self.variants.get(id).cloned().unwrap_or_default();
+            if keys.is_empty() {
+                warnings.push(format!("rules.{setting}: no rule with id '{id}'"));
+            }
+            keys
+        };
+
+        let mut changes: Vec<(String, RuleChange)> = Vec::new();
+        for id in &settings.enabled {
+            changes.extend(variants_of(id, "enabled").into_iter().map(|k| (k, RuleChange::Enable)));
+        }
+        for id in &settings.disabled {
+            changes
+                .extend(variants_of(id, "disabled").into_iter().map(|k| (k, RuleChange::Disable)));
+        }
+        for (id, severity) in &settings.severity {
+            let keys = variants_of(id, "severity");
+            changes.extend(keys.into_iter().map(|k| (k, RuleChange::Severity(*severity))));
+        }
+        for (id, threshold) in &settings.thresholds {
+            let keys = variants_of(id, "thresholds");
+            changes.extend(keys.into_iter().map(|k| (k, RuleChange::Threshold(*threshold))));
+        }
+
+        for (key, change) in changes {
+            let Some(pattern) = self.patterns.get_mut(&key) else {
+                continue;
+            };
+            match (change, &mut pattern.detection_method) {
+                (RuleChange::Enable, _) => pattern.enabled = true,
+                (RuleChange::Disable, _) => pattern.enabled = false,
+                (RuleChange::Severity(severity), _) => pattern.severity = severity,
+                (RuleChange::Threshold(value), DetectionMethod::LineCount { threshold, .. }) => {
+                    *threshold = value as usize;
+                }
+                (RuleChange::Threshold(value), DetectionMethod::Ratio { threshold, .. }) => {
+                    *threshold = value;
+                }
+                (RuleChange::Threshold(_), _) => {
+                    let warning = format!("rules.thresholds: '{}' has no threshold", pattern.id);
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+            }
+        }
+        warnings
+    }
+
+    pub fn rule_sources(&self) -> &RuleSources {
+        &self.sources
+    }
+
+    /// Rules replaced by a later one with the same id and language, in the order it happened
+    pub fn shadowed(&self) -> &[ShadowedRule] {
+        &self.shadowed
+    }
+
+    /// Project rules that collided with a loaded rule, and how each was resolved
+    pub fn conflicts(&self) -> &[RuleConflict] {
+        &self.conflicts
+    }
+
     pub fn compile_all_patterns(&mut self) -> Result<()> {
         use crate::core::DetectionMethod;
 
+        self.compiled_patterns.clear();
+        self.prefilter.take();
+        self.rule_sets.take();
         for pattern in self.patterns.values() {
-            if let DetectionMethod::Regex { pattern: regex_pattern } = &pattern.detection_method {
-                match Regex::new(regex_pattern) {
-                    Ok(compiled) => {
-                        self.compiled_patterns.insert(pattern.id.clone(), compiled);
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "Warning: Failed to compile regex for pattern {}: {e}",
-                            pattern.id
-                        );
-                    }
+            // Block rules match the line that opens a block, so they are compiled the same way
+            let compiled = match &pattern.detection_method {
+                DetectionMethod::Regex { pattern: regex_pattern }
+                | DetectionMethod::LineCount { pattern: regex_pattern, .. } => {
+                    Regex::new(regex_pattern)
+                }
+                // Whole-file patterns anchor `^` and `$` at lines, as line rules do
+                DetectionMethod::Multiline { pattern: regex_pattern, .. } => {
+                    RegexBuilder::new(regex_pattern).multi_line(true).build()
+                }
+                _ => continue,
+            };
+            match compiled {
+                Ok(compiled) => {
+                    self.compiled_patterns
+                        .entry(pattern.id.clone())
+                        .or_default()
+                        .push((pattern.language.clone(), compiled));
+                }
+                Err(e) => {
+                    eprintln!("Warning: Failed to compile regex for pattern {}: {e}", pattern.id);
                 }
             }
         }
         Ok(())
     }
 
-    pub fn get_compiled_pattern(&self, id: &str) -> Option<&Regex> {
-        self.compiled_patterns.get(id)
+    /// The literal prefilter over every registered line rule, so a line is only matched
+    /// against the rules whose required literals it contains
+    pub fn prefilter(&self) -> &LiteralPrefilter {
+        self.prefilter.get_or_init(|| LiteralPrefilter::new(self.patterns.values()))
     }
 
-    fn load_rules_from_yaml(
-        &mut self,
-        yaml_content: &str,
-        _expected_language: Language,
-    ) -> Result<()> {
+    /// The regex line rules of `language` as one `RegexSet`, for telling in one pass which
+    /// of them match a line
+    pub fn rule_set(&self, language: &Language) -> Option<&LanguageRuleSet> {
+        let sets = self.rule_sets.get_or_init(|| {
+            let mut by_language: HashMap<&Language, Vec<&AntiPattern>> = HashMap::new();
+            for pattern in self.patterns.values() {
+                by_language.entry(&pattern.language).or_default().push(pattern);
+            }
+            by_language
+                .into_iter()
+                .filter_map(|(language, patterns)| {
+                    Some((language.clone(), LanguageRuleSet::new(patterns)?))
+                })
+                .collect()
+        });
+        sets.get(language)
+    }
+
+    /// Compiled regex of one language variant of a rule
+    pub fn get_compiled_variant(&self, id: &str, language: &Language) -> Option<&Regex> {
+        self.compiled_patterns
+            .get(id)?
+            .iter()
+            .find(|(variant_language, _)| variant_language == language)
+            .map(|(_, regex)| regex)
+    }
+
+    /// Register the rules of the built-in pack `pack`
+    fn load_rules_from_yaml(&mut self, yaml_content: &str, pack: &str) -> Result<()> {
+        self.load_rule_pack(yaml_content, RuleSource::built_in(pack))
+    }
+
+    /// Register the rules of a rule pack file, attributed to `source`. Entries this version
+    /// does not understand are skipped, so packs written for newer versions still load.
+    pub fn load_rule_pack(&mut self, yaml_content: &str, source: RuleSource) -> Result<()> {
+        for rule in Self::parse_rule_pack(yaml_content)?.rules {
+            self.register(rule, Some(source.clone()));
+        }
+        Ok(())
+    }
+
+    /// Register the packs installed with `rules import`, after the built-in ones so that a
+    /// pack rule with a built-in id replaces it. A pack that fails to load is skipped with
+    /// a warning rather than failing every review.
+    pub fn load_installed_packs(&mut self) -> Result<()> {
+        for pack in installed_packs()? {
+            let loaded = std::fs::read_to_string(&pack.path).map_err(anyhow::Error::from).and_then(
+                |content| self.load_rule_pack(&content, RuleSource::installed(&pack.name)),
+            );
+            if let Err(e) = loaded {
+                eprintln!("Warning: Skipping rule pack {}: {e}", pack.path.display());
+            }
+        }
+        Ok(())
+    }
+
+    /// Rules of a rule pack file, in the schema of the built-in packs, with the entries
+    /// that were skipped and why
+    pub fn parse_rule_pack(yaml_content: &str) -> Result<ParsedRulePack> {
         #[derive(serde::Deserialize)]
         struct YamlRule {
             id: String,
             name: String,
-            language: String,
+            #[serde(default)]
+            language: Option<String>,
             severity: String,
             description: String,
-            detection_method: YamlDetectionMethod,
+            #[serde(default)]
+            detection_method: Option<YamlDetectionMethod>,
             fix_suggestion: String,
             source_url: Option<String>,
             claude_code_fixable: bool,
+            #[serde(default)]
             examples: Vec<YamlExample>,
             tags: Vec<String>,
+            #[serde(default)]
+            ai_hint: Option<String>,
+            #[serde(default)]
+            auto_fix: Option<AutoFix>,
+            #[serde(default)]
+            skip_comments: bool,
+            #[serde(default)]
+            skip_strings: bool,
             enabled: bool,
+            /// Per-language patterns of one logical rule, used instead of `language` and
+            /// `detection_method`
+            #[serde(default)]
+            variants: Vec<YamlVariant>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct YamlVariant {
+            language: String,
+            detection_method: YamlDetectionMethod,
+            #[serde(default)]
+            fix_suggestion: Option<String>,
+            #[serde(default)]
+            examples: Option<Vec<YamlExample>>,
         }
 
         #[derive(serde::Deserialize)]
         struct YamlDetectionMethod {
             #[serde(rename = "type")]
             method_type: String,
+            #[serde(default)]
             pattern: String,
             threshold: Option<f64>,
+            tab_width: Option<usize>,
+            indent_width: Option<usize>,
+            unless: Option<String>,
+            window: Option<usize>,
         }
 
-        #[derive(serde::Deserialize)]
+        #[derive(Clone, serde::Deserialize)]
         struct YamlExample {
             bad: String,
             good: String,
@@ -152,67 +542,125 @@ impl PatternRegistry {
         }
 
         let yaml_rules: Vec<YamlRule> = serde_yaml::from_str(yaml_content)?;
+        let mut parsed = ParsedRulePack::default();
 
         for yaml_rule in yaml_rules {
             use crate::core::{CodeExample, DetectionMethod};
 
-            let language = match yaml_rule.language.as_str() {
-                "elixir" => Language::Elixir,
-                "javascript" => Language::JavaScript,
-                "typescript" => Language::TypeScript,
-                "python" => Language::Python,
-                "rust" => Language::Rust,
-                "zig" => Language::Zig,
-                "sql" => Language::Sql,
-                _ => continue, // Skip unknown languages
+            let Ok(severity) = Severity::from_str(&yaml_rule.severity, false) else {
+                parsed
+                    .skipped
+                    .push(format!("{}: unknown severity '{}'", yaml_rule.id, yaml_rule.severity));
+                continue;
             };
 
-            let severity = match yaml_rule.severity.as_str() {
-                "critical" => Severity::Critical,
-                "major" => Severity::Major,
-                "warning" => Severity::Warning,
-                _ => continue, // Skip unknown severities
-            };
-
-            let detection_method = match yaml_rule.detection_method.method_type.as_str() {
-                "regex" => DetectionMethod::Regex { pattern: yaml_rule.detection_method.pattern },
-                "ratio" => DetectionMethod::Ratio {
-                    pattern: yaml_rule.detection_method.pattern,
-                    threshold: yaml_rule.detection_method.threshold.unwrap_or(0.3),
-                },
-                "line_count" => DetectionMethod::LineCount {
-                    threshold: yaml_rule.detection_method.threshold.unwrap_or(10.0) as usize,
-                    pattern: yaml_rule.detection_method.pattern,
-                },
-                "custom" => DetectionMethod::Custom { pattern: yaml_rule.detection_method.pattern },
-                _ => continue, // Skip unknown detection methods
-            };
-
-            let examples = yaml_rule
-                .examples
-                .into_iter()
-                .map(|ex| CodeExample { bad: ex.bad, good: ex.good, explanation: ex.explanation })
-                .collect();
+            if let Some(Err(error)) = yaml_rule.auto_fix.as_ref().map(AutoFix::validate) {
+                parsed.skipped.push(format!("{}: {error:#}", yaml_rule.id));
+                continue;
+            }
 
-            let pattern = AntiPattern {
-                id: yaml_rule.id,
-                name: yaml_rule.name,
-                language,
-                severity,
-                description: yaml_rule.description,
-                detection_method,
-                fix_suggestion: yaml_rule.fix_suggestion,
-                source_url: yaml_rule.source_url,
-                claude_code_fixable: yaml_rule.claude_code_fixable,
-                examples,
-                tags: yaml_rule.tags,
-                enabled: yaml_rule.enabled,
+            // A rule without variants is its own single variant
+            let variants = if yaml_rule.variants.is_empty() {
+                let (Some(language), Some(detection_method)) =
+                    (yaml_rule.language, yaml_rule.detection_method)
+                else {
+                    parsed.skipped.push(format!(
+                        "{}: needs `language` and `detection_method`, or `variants`",
+                        yaml_rule.id
+                    ));
+                    continue;
+                };
+                vec![YamlVariant {
+                    language,
+                    detection_method,
+                    fix_suggestion: None,
+                    examples: None,
+                }]
+            } else {
+                yaml_rule.variants
             };
 
-            self.add_pattern(pattern);
+            for variant in variants {
+                let Ok(language) = variant.language.parse::<Language>() else {
+                    parsed
+                        .skipped
+                        .push(format!("{}: unknown language '{}'", yaml_rule.id, variant.language));
+                    continue;
+                };
+
+                let method = variant.detection_method;
+                let detection_method = match method.method_type.as_str() {
+                    "regex" => DetectionMethod::Regex { pattern: method.pattern },
+                    "ratio" => DetectionMethod::Ratio {
+                        pattern: method.pattern,
+                        threshold: method.threshold.unwrap_or(0.3),
+                    },
+                    "line_count" => DetectionMethod::LineCount {
+                        threshold: method.threshold.unwrap_or(10.0) as usize,
+                        pattern: method.pattern,
+                    },
+                    "custom" => DetectionMethod::Custom { pattern: method.pattern },
+                    "line_length" => DetectionMethod::LineLength {
+                        max_width: method.threshold.unwrap_or(100.0) as usize,
+                        tab_width: method.tab_width.unwrap_or(4),
+                    },
+                    "indent_depth" => DetectionMethod::IndentDepth {
+                        max_depth: method.threshold.unwrap_or(5.0) as usize,
+                        indent_width: method.indent_width.unwrap_or(4),
+                        tab_width: method.tab_width.unwrap_or(4),
+                    },
+                    "multiline" => DetectionMethod::Multiline {
+                        pattern: method.pattern,
+                        unless: method.unless,
+                        window: method.window,
+                    },
+                    "symlink_replacement" => DetectionMethod::SymlinkReplacement,
+                    other => {
+                        parsed.skipped.push(format!(
+                            "{} ({language}): unknown detection method '{other}'",
+                            yaml_rule.id
+                        ));
+                        continue;
+                    }
+                };
+
+                let examples = variant
+                    .examples
+                    .unwrap_or_else(|| yaml_rule.examples.clone())
+                    .into_iter()
+                    .map(|ex| CodeExample {
+                        bad: ex.bad,
+                        good: ex.good,
+                        explanation: ex.explanation,
+                    })
+                    .collect();
+
+                let pattern = AntiPattern {
+                    id: yaml_rule.id.clone(),
+                    name: yaml_rule.name.clone(),
+                    language,
+                    severity,
+                    description: yaml_rule.description.clone(),
+                    detection_method,
+                    fix_suggestion: variant
+                        .fix_suggestion
+                        .unwrap_or_else(|| yaml_rule.fix_suggestion.clone()),
+                    source_url: yaml_rule.source_url.clone(),
+                    claude_code_fixable: yaml_rule.claude_code_fixable,
+                    examples,
+                    tags: yaml_rule.tags.clone(),
+                    ai_hint: yaml_rule.ai_hint.clone(),
+                    auto_fix: yaml_rule.auto_fix.clone(),
+                    skip_comments: yaml_rule.skip_comments,
+                    skip_strings: yaml_rule.skip_strings,
+                    enabled: yaml_rule.enabled,
+                };
+
+                parsed.rules.push(pattern);
+            }
         }
 
-        Ok(())
+        Ok(parsed)
     }
 
     #[allow(dead_code)]
@@ -221,22 +669,63 @@ impl PatternRegistry {
         Ok(())
     }
 
+    /// Register a rule, replacing any earlier rule with the same id and language. The
+    /// same id for another language adds a variant of that rule.
     pub fn add_pattern(&mut self, pattern: AntiPattern) {
-        let id = pattern.id.clone();
+        self.register(pattern, None);
+    }
+
+    /// `add_pattern`, recording where the rule came from and which rule it replaced
+    fn register(&mut self, pattern: AntiPattern, source: Option<RuleSource>) {
+        let shadowed = self.sources.get(&pattern).cloned();
+        match &source {
+            Some(source) => self.sources.insert(&pattern, source.clone()),
+            None => self.sources.remove(&pattern),
+        }
+        let key = variant_key(&pattern.id, &pattern.language);
         let language = pattern.language.clone();
+        let id = pattern.id.clone();
 
-        self.patterns.insert(id.clone(), pattern);
-        self.by_language.entry(language).or_default().push(id);
+        if let Some(compiled) = self.compiled_patterns.get_mut(&id) {
+            compiled.retain(|(variant_language, _)| *variant_language != language);
+        }
+        self.prefilter.take();
+        self.rule_sets.take();
+        if self.patterns.insert(key.clone(), pattern).is_none() {
+            self.variants.entry(id).or_default().push(key.clone());
+            self.by_language.entry(language).or_default().push(key);
+        } else {
+            self.shadowed.push(ShadowedRule { id, language, shadowed, by: source });
+        }
     }
 
+    /// A rule by id; for multi-language rules, the first variant registered
+    #[allow(dead_code)] // Used in tests and by library consumers
     pub fn get_pattern(&self, id: &str) -> Option<&AntiPattern> {
-        self.patterns.get(id)
+        self.get_variants(id).into_iter().next()
+    }
+
+    /// Every language variant of a rule, in the order they were registered
+    pub fn get_variants(&self, id: &str) -> Vec<&AntiPattern> {
+        self.variants
+            .get(id)
+            .map(|keys| keys.iter().filter_map(|key| self.patterns.get(key)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Every registered rule variant, ordered by id and then language
+    pub fn all_patterns(&self) -> Vec<&AntiPattern> {
+        let mut patterns: Vec<&AntiPattern> = self.patterns.values().collect();
+        patterns.sort_by(|a, b| {
+            a.id.cmp(&b.id).then_with(|| a.language.to_string().cmp(&b.language.to_string()))
+        });
+        patterns
     }
 
     pub fn get_patterns_for_language(&self, language: &Language) -> Vec<&AntiPattern> {
         self.by_language
             .get(language)
-            .map(|ids| ids.iter().filter_map(|id| self.patterns.get(id)).collect())
+            .map(|keys| keys.iter().filter_map(|key| self.patterns.get(key)).collect())
             .unwrap_or_default()
     }
 
@@ -286,6 +775,10 @@ impl PatternRegistry {
                 }
             ],
             tags: vec!["security".to_string(), "memory".to_string()],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
         };
         self.add_pattern(pattern);
@@ -316,6 +809,10 @@ impl PatternRegistry {
                     .to_string(),
             }],
             tags: vec!["maintainability".to_string()],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
         };
         self.add_pattern(pattern);
@@ -402,6 +899,10 @@ mod tests {
             claude_code_fixable: true,
             examples: vec![],
             tags: vec!["memory".to_string()],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
         };
 
@@ -417,6 +918,10 @@ mod tests {
             claude_code_fixable: true,
             examples: vec![],
             tags: vec!["security".to_string()],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
         };
 
@@ -455,7 +960,13 @@ mod tests {
             pattern: r"console\.log\(".to_string(),
             severity: "warning".to_string(),
             fix: "Use proper logging library".to_string(),
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
+            file_scope: None,
+            examples: vec![],
         };
 
         custom_rules_manager
@@ -537,6 +1048,242 @@ mod tests {
         assert_eq!(registry.patterns.len(), 1000);
     }
 
+    #[test]
+    fn test_language_variants_share_one_id() {
+        let mut registry = PatternRegistry::new();
+        registry.load_embedded_shared_rules().unwrap();
+        registry.compile_all_patterns().unwrap();
+
+        let variants = registry.get_variants("leftover_debugger");
+        let languages: Vec<String> = variants.iter().map(|v| v.language.to_string()).collect();
+        assert_eq!(languages, ["elixir", "javascript", "typescript", "python", "rust", "ruby"]);
+        assert!(variants.iter().all(|v| v.name == "Leftover Debugger Breakpoint"));
+
+        // Variants inherit shared fields unless they override them
+        assert_eq!(variants[3].fix_suggestion, "Remove the breakpoint before committing");
+        assert!(variants[4].fix_suggestion.starts_with("Remove dbg!"));
+        assert!(variants[2].examples.is_empty());
+
+        let python = registry.get_patterns_for_file("app/views.py");
+        assert_eq!(python.len(), 1);
+        assert_eq!(python[0].id, "leftover_debugger");
+        let regex = registry.get_compiled_variant("leftover_debugger", &Language::Python).unwrap();
+        assert!(regex.is_match("    breakpoint()"));
+        assert!(!regex.is_match("dbg!(x)"));
+
+        // Re-adding a variant replaces it rather than adding a duplicate
+        let mut rust = variants[4].clone();
+        rust.severity = Severity::Critical;
+        registry.add_pattern(rust);
+        assert_eq!(registry.get_variants("leftover_debugger").len(), 6);
+        assert_eq!(registry.get_patterns_for_language(&Language::Rust).len(), 1);
+        assert_eq!(
+            registry.get_patterns_for_language(&Language::Rust)[0].severity,
+            Severity::Critical
+        );
+    }
+
+    #[test]
+    fn test_apply_rule_settings() {
+        let mut registry = PatternRegistry::new();
+        registry.load_built_in_patterns().unwrap();
+        let settings = RuleSettings {
+            disabled: vec!["dynamic_atom_creation".to_string(), "no_such_rule".to_string()],
+            enabled: vec!["dynamic_atom_creation".to_string()],
+            severity: [("leftover_debugger".to_string(), Severity::Critical)].into(),
+            thresholds: [
+                ("elixir_long_function".to_string(), 80.0),
+                ("leftover_debugger".to_string(), 3.0),
+            ]
+            .into(),
+        };
+
+        let warnings = registry.apply_rule_settings(&settings);
+        assert_eq!(
+            warnings,
+            vec![
+                "rules.disabled: no rule with id 'no_such_rule'".to_string(),
+                "rules.thresholds: 'leftover_debugger' has no threshold".to_string(),
+            ]
+        );
+        assert!(!registry.get_pattern("dynamic_atom_creation").unwrap().enabled);
+        assert!(registry
+            .get_variants("leftover_debugger")
+            .iter()
+            .all(|variant| variant.severity == Severity::Critical));
+        assert!(matches!(
+            registry.get_pattern("elixir_long_function").unwrap().detection_method,
+            DetectionMethod::LineCount { threshold: 80, .. }
+        ));
+    }
+
+    fn registry_with_conflicting_rules(policy: ConflictPolicy) -> (PatternRegistry, Result<()>) {
+        let mut registry = PatternRegistry::new();
+        registry.add_pattern(create_test_pattern("no_io", Language::Elixir, Severity::Major));
+        let result = registry.add_custom_patterns(
+            vec![
+                create_test_pattern("no_io", Language::Elixir, Severity::Critical),
+                create_test_pattern("no_io", Language::Python, Severity::Warning),
+                create_test_pattern("no_io", Language::Python, Severity::Critical),
+            ],
+            &RuleSource::Project { project: "app".to_string() },
+            policy,
+        );
+        (registry, result)
+    }
+
+    #[test]
+    fn test_custom_rule_conflicts_prefer_custom() {
+        let (registry, result) = registry_with_conflicting_rules(ConflictPolicy::PreferCustom);
+        result.unwrap();
+        let elixir = registry.get_patterns_for_language(&Language::Elixir);
+        assert_eq!(elixir.len(), 1);
+        assert_eq!(elixir[0].severity, Severity::Critical);
+        let python = registry.get_patterns_for_language(&Language::Python);
+        assert_eq!(python.len(), 1);
+        assert_eq!(python[0].severity, Severity::Critical);
+
+        let conflicts = registry.conflicts();
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].existing, RuleOrigin::BuiltIn);
+        assert_eq!(conflicts[0].existing_severity, Severity::Major);
+        assert_eq!(conflicts[1].existing, RuleOrigin::Project);
+        assert!(conflicts.iter().all(|c| c.resolution == Resolution::KeptCustom));
+    }
+
+    #[test]
+    fn test_custom_rule_conflicts_prefer_builtin() {
+        let (registry, result) = registry_with_conflicting_rules(ConflictPolicy::PreferBuiltin);
+        result.unwrap();
+        assert_eq!(
+            registry.get_patterns_for_language(&Language::Elixir)[0].severity,
+            Severity::Major
+        );
+        let python = registry.get_patterns_for_language(&Language::Python);
+        assert_eq!(python.len(), 1);
+        assert_eq!(python[0].severity, Severity::Warning);
+        assert!(registry.conflicts().iter().all(|c| c.resolution == Resolution::KeptExisting));
+    }
+
+    #[test]
+    fn test_custom_rule_conflicts_rename() {
+        let (registry, result) = registry_with_conflicting_rules(ConflictPolicy::Rename);
+        result.unwrap();
+        let elixir = registry.get_patterns_for_language(&Language::Elixir);
+        assert_eq!(elixir.len(), 2);
+        let renamed = elixir.iter().find(|p| p.id == "no_io_2").unwrap();
+        assert_eq!(renamed.severity, Severity::Critical);
+        assert_eq!(registry.get_patterns_for_language(&Language::Python).len(), 2);
+        assert_eq!(
+            registry.conflicts().iter().map(|c| c.resolution.clone()).collect::<Vec<_>>(),
+            vec![Resolution::Renamed("no_io_2".into()), Resolution::Renamed("no_io_2".into())]
+        );
+        assert!(registry.conflicts()[0].to_string().contains("was loaded as no_io_2"));
+    }
+
+    #[test]
+    fn test_built_in_auto_fix_templates() {
+        let mut registry = PatternRegistry::new();
+        registry.load_built_in_patterns().unwrap();
+        let fix = |id: &str, line: &str| {
+            let rule = registry.get_pattern(id).unwrap();
+            rule.auto_fix.as_ref().unwrap().apply(line, |_| true).unwrap()
+        };
+
+        assert_eq!(
+            fix("double_equals", "if (a == b || c != d)").unwrap(),
+            "if (a === b || c !== d)"
+        );
+        assert_eq!(fix("double_equals", "if (a === b || c !== d)"), None);
+        // `== null` also matches undefined, so rewriting it would change behaviour
+        assert_eq!(fix("double_equals", "if (x == null || y != null)"), None);
+        assert_eq!(fix("double_equals", "x != null && y == 1").unwrap(), "x != null && y === 1");
+        assert_eq!(
+            fix("dynamic_atom_creation", "String.to_atom (name)").unwrap(),
+            "String.to_existing_atom (name)"
+        );
+    }
+
+    #[test]
+    fn test_rule_packs_opt_out_of_comments_and_strings() {
+        let parsed = PatternRegistry::parse_rule_pack(
+            r#"
+- id: "debug_print"
+  name: "Debug print"
+  language: "python"
+  severity: "warning"
+  description: "Leftover debug output"
+  detection_method:
+    type: "regex"
+    pattern: "print\\("
+  fix_suggestion: "Remove it"
+  source_url: null
+  claude_code_fixable: false
+  tags: []
+  skip_comments: true
+  enabled: true
+"#,
+        )
+        .unwrap();
+        assert!(parsed.rules[0].skip_comments);
+        assert!(!parsed.rules[0].skip_strings);
+
+        let mut registry = PatternRegistry::new();
+        registry.load_built_in_patterns().unwrap();
+        let rule = registry.get_pattern("double_equals").unwrap();
+        assert!(rule.skip_comments && rule.skip_strings);
+    }
+
+    #[test]
+    fn test_built_in_rules_pass_the_doctor() {
+        use crate::core::rule_doctor::{find_id_collisions, find_never_matching};
+
+        let mut registry = PatternRegistry::new();
+        registry.load_all_embedded_rules().unwrap();
+        assert_eq!(find_id_collisions(registry.shadowed(), registry.conflicts()), []);
+        assert_eq!(find_never_matching(&registry), []);
+    }
+
+    #[test]
+    fn test_rule_sources() {
+        let mut registry = PatternRegistry::new();
+        registry.load_built_in_patterns().unwrap();
+        let atom = registry.get_pattern("dynamic_atom_creation").unwrap();
+        assert_eq!(registry.rule_sources().get(atom), Some(&RuleSource::built_in("elixir")));
+        assert_eq!(
+            RuleSource::built_in("elixir").to_string(),
+            format!("built-in elixir pack v{}", env!("CARGO_PKG_VERSION"))
+        );
+
+        // A project rule replacing a built-in variant takes over its source
+        let (registry, result) = registry_with_conflicting_rules(ConflictPolicy::PreferCustom);
+        result.unwrap();
+        let elixir = registry.get_patterns_for_language(&Language::Elixir)[0];
+        let source = registry.rule_sources().get(elixir).unwrap();
+        assert_eq!(source.to_string(), "custom rules of project app");
+
+        let (registry, _) = registry_with_conflicting_rules(ConflictPolicy::PreferBuiltin);
+        let elixir = registry.get_patterns_for_language(&Language::Elixir)[0];
+        assert_eq!(registry.rule_sources().get(elixir), None);
+    }
+
+    #[test]
+    fn test_custom_rule_conflicts_error_loads_nothing() {
+        let (registry, result) = registry_with_conflicting_rules(ConflictPolicy::Error);
+        let error = result.unwrap_err();
+        let conflicts = error.downcast_ref::<RuleConflicts>().unwrap();
+        assert_eq!(conflicts.0.len(), 2);
+        assert!(conflicts.0.iter().all(|c| c.resolution == Resolution::Refused));
+        assert!(error.to_string().contains("rule_conflicts:"));
+
+        assert!(registry.get_patterns_for_language(&Language::Python).is_empty());
+        assert_eq!(
+            registry.get_patterns_for_language(&Language::Elixir)[0].severity,
+            Severity::Major
+        );
+        assert!(registry.conflicts().is_empty());
+    }
+
     // Helper function to create test patterns
     fn create_test_pattern(id: &str, language: Language, severity: Severity) -> AntiPattern {
         AntiPattern {
@@ -555,6 +1302,10 @@ mod tests {
                 explanation: "Why the good example is better".to_string(),
             }],
             tags: vec!["test".to_string()],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
         }
     }
@@ -599,6 +1350,117 @@ mod tests {
             assert!(js_patterns.len() >= 8, "Should have at least 8 JavaScript patterns");
         }
 
+        /// Every regex rule flags the bad code of its examples and not the good code
+        fn assert_examples_match(registry: &PatternRegistry, language: &Language) {
+            for pattern in registry.get_patterns_for_language(language) {
+                if !matches!(pattern.detection_method, DetectionMethod::Regex { .. }) {
+                    continue;
+                }
+                let regex = registry.get_compiled_variant(&pattern.id, language).unwrap();
+                for example in &pattern.examples {
+                    assert!(regex.is_match(&example.bad), "{} misses {}", pattern.id, example.bad);
+                    assert!(
+                        !regex.is_match(&example.good),
+                        "{} flags {}",
+                        pattern.id,
+                        example.good
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_embedded_ruby_rules_load() {
+            let mut registry = PatternRegistry::new();
+            registry.load_embedded_ruby_rules().expect("Should load embedded Ruby rules");
+            registry.compile_all_patterns().expect("Ruby patterns should compile");
+
+            let ruby_patterns = registry.get_patterns_for_file("app/models/user.rb");
+            assert!(ruby_patterns.len() >= 6, "Should have at least 6 Ruby patterns");
+            assert_eq!(
+                registry.get_patterns_for_file("app/views/users/show.html.erb").len(),
+                ruby_patterns.len()
+            );
+            assert_examples_match(&registry, &Language::Ruby);
+
+            let matches = |id: &str, line: &str| {
+                registry.get_compiled_variant(id, &Language::Ruby).unwrap().is_match(line)
+            };
+            assert!(matches("rescue_exception", "  rescue ::Exception"));
+            assert!(!matches("rescue_exception", "  rescue ExceptionNotifier::Error"));
+            assert!(matches("string_eval", "instance_eval \"@#{name}\""));
+            assert!(!matches("string_eval", "record.instance_eval { @name }"));
+            assert!(!matches("string_eval", "evaluate(x)"));
+            assert!(matches("unscoped_update_all", "Admin::User.all.delete_all"));
+            assert!(!matches("unscoped_update_all", "user.posts.delete_all"));
+            assert!(matches("send_with_params", "params[:type].constantize.new"));
+        }
+
+        #[test]
+        fn test_embedded_php_rules_load() {
+            let mut registry = PatternRegistry::new();
+            registry.load_embedded_php_rules().expect("Should load embedded PHP rules");
+            registry.compile_all_patterns().expect("PHP patterns should compile");
+
+            assert!(registry.get_patterns_for_file("src/index.php").len() >= 6);
+            assert_examples_match(&registry, &Language::Php);
+
+            let matches = |id: &str, line: &str| {
+                registry.get_compiled_variant(id, &Language::Php).unwrap().is_match(line)
+            };
+            assert!(!matches("php_eval", "$parser->eval($input);"));
+            assert!(matches(
+                "mysqli_query_injection",
+                "$db->query('DELETE FROM t WHERE id = ' . $id);"
+            ));
+            assert!(!matches("mysqli_query_injection", "$db->query('SELECT 1');"));
+            assert!(matches("var_dump_left_in_code", "    dd($request->all());"));
+            assert!(!matches("var_dump_left_in_code", "$this->add($item);"));
+            assert!(matches("unescaped_request_output", "<?= $_GET['q'] ?>"));
+            assert!(!matches("error_suppression_operator", " * @Route(\"/users\")"));
+        }
+
+        #[test]
+        fn test_embedded_jvm_rules_load() {
+            let mut registry = PatternRegistry::new();
+            registry.load_embedded_java_rules().expect("Should load embedded Java rules");
+            registry.load_embedded_kotlin_rules().expect("Should load embedded Kotlin rules");
+            registry.load_embedded_shared_rules().expect("Should load shared rules");
+            registry.load_embedded_formatting_rules().expect("Should load formatting rules");
+            registry.compile_all_patterns().expect("JVM patterns should compile");
+
+            assert_examples_match(&registry, &Language::Java);
+            assert_examples_match(&registry, &Language::Kotlin);
+            let languages: Vec<String> = registry
+                .get_variants("catch_throwable")
+                .iter()
+                .map(|variant| variant.language.to_string())
+                .collect();
+            assert_eq!(languages, ["java", "kotlin"]);
+
+            let java = |id: &str, line: &str| {
+                registry.get_compiled_variant(id, &Language::Java).unwrap().is_match(line)
+            };
+            assert!(java("system_out_println", "System.err.printf(\"%d%n\", count);"));
+            assert!(!java("string_reference_equality", "if (\"admin\".equals(role)) {"));
+            assert!(java("catch_throwable", "} catch (final Throwable t) {"));
+            assert!(java(
+                "java_long_function",
+                "    public static <T> List<T> map(List<T> items) {"
+            ));
+            assert!(java("java_long_function", "    private Order(String id) {"));
+            assert!(!java("java_long_function", "    private final Foo foo = new Foo("));
+
+            let kotlin = |id: &str, line: &str| {
+                registry.get_compiled_variant(id, &Language::Kotlin).unwrap().is_match(line)
+            };
+            assert!(kotlin("not_null_assertion", "val id = items[0]!!.id"));
+            assert!(kotlin("not_null_assertion", "load()!!"));
+            assert!(!kotlin("not_null_assertion", "if (!!enabled) run()"));
+            assert!(!kotlin("kotlin_println", "logger.println(\"x\")"));
+            assert!(kotlin("empty_catch_block", "try { close() } catch (e: IOException) { }"));
+        }
+
         #[test]
         fn test_load_all_embedded_rules() {
             let mut registry = PatternRegistry::new();
@@ -647,11 +1509,12 @@ mod tests {
             assert!(result.is_ok(), "All regex patterns should compile successfully");
 
             // Test that compiled patterns are accessible
-            assert!(registry.compiled_patterns.len() > 0, "Should have compiled patterns");
+            assert!(!registry.compiled_patterns.is_empty(), "Should have compiled patterns");
 
             // Test lookup performance with compiled patterns
             let start = Instant::now();
-            let _compiled_pattern = registry.get_compiled_pattern("dynamic_atom_creation");
+            let _compiled_pattern =
+                registry.get_compiled_variant("dynamic_atom_creation", &Language::Elixir);
             let duration = start.elapsed();
 
             assert!(
@@ -73,6 +73,7 @@ mod tests {
             claude_code_fixable: true,
             examples: vec![],
             tags: vec!["memory".to_string()],
+            ai_hint: None,
             enabled: true,
         };
         
@@ -88,6 +89,7 @@ mod tests {
             claude_code_fixable: true,
             examples: vec![],
             tags: vec!["security".to_string()],
+            ai_hint: None,
             enabled: true,
         };
         
@@ -267,6 +269,7 @@ mod tests {
                 }
             ],
             tags: vec!["test".to_string()],
+            ai_hint: None,
             enabled: true,
         }
     }
@@ -0,0 +1,136 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::audit::hash_bytes;
+use crate::core::review_engine::{ReviewViolation, SkippedViolation};
+use crate::core::ProjectDetector;
+
+/// Result cache directory, relative to the project root
+pub const CACHE_DIR: &str = ".patingin/cache";
+
+/// Entries kept for one rule set before the cache starts over, so edits made while the rules
+/// stay the same cannot grow it without bound
+const MAX_ENTRIES: usize = 10_000;
+
+/// What one cache entry holds: the review of one file
+#[derive(Serialize, Deserialize)]
+struct CachedReview {
+    violations: Vec<ReviewViolation>,
+    skipped: Vec<SkippedViolation>,
+}
+
+/// Per-file review results kept on disk, so reviewing an unchanged tree again is near-instant.
+///
+/// Entries live in a directory named after the engine key, a hash of the patingin version,
+/// every rule and the project's review settings: editing a custom rule or `patingin.yml`
+/// starts an empty cache and removes the old one. Within it, each file's review is keyed by
+/// its diff and its current content. A cache that cannot be read or written only costs
+/// speed, never a review.
+#[derive(Debug)]
+pub struct ResultCache {
+    dir: PathBuf,
+}
+
+impl ResultCache {
+    /// The cache under `cache_root` for rules and settings hashing to `engine_key`. Caches
+    /// for other keys are removed, since nothing can hit them again.
+    pub fn open(cache_root: &Path, engine_key: &str) -> Result<Self> {
+        let dir = cache_root.join(engine_key);
+        let entries = fs::read_dir(&dir).map(|entries| entries.count()).unwrap_or(0);
+        if !dir.is_dir() || entries > MAX_ENTRIES {
+            if let Ok(stale) = fs::read_dir(cache_root) {
+                for entry in stale.flatten().filter(|entry| entry.path().is_dir()) {
+                    let _ = fs::remove_dir_all(entry.path());
+                }
+            }
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            // Keep cached results out of commits even when the rest of .patingin/ is tracked
+            fs::write(cache_root.join(".gitignore"), "*\n")?;
+        }
+        Ok(Self { dir })
+    }
+
+    /// The cache of the detected project, or `None` outside a project
+    pub fn for_project(engine_key: &str) -> Result<Option<Self>> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(None);
+        };
+        Self::open(&project_info.root_path.join(CACHE_DIR), engine_key).map(Some)
+    }
+
+    /// Key for a file's review, from everything the review reads: its diff (including the
+    /// detected language) and its current content, `None` when it is not on disk
+    pub fn file_key(diff: &str, source: Option<&[u8]>) -> Result<String> {
+        let mut bytes = diff.as_bytes().to_vec();
+        if let Some(source) = source {
+            bytes.push(0);
+            bytes.extend_from_slice(source);
+        }
+        hash_bytes(&bytes)
+    }
+
+    /// The cached review for `key`, if there is a readable one
+    pub fn get(&self, key: &str) -> Option<(Vec<ReviewViolation>, Vec<SkippedViolation>)> {
+        let content = fs::read(self.entry(key)).ok()?;
+        let cached: CachedReview = serde_json::from_slice(&content).ok()?;
+        Some((cached.violations, cached.skipped))
+    }
+
+    /// Store the review for `key`. The entry is written under a temporary name first, so a
+    /// concurrent run never reads half of it.
+    pub fn put(
+        &self,
+        key: &str,
+        violations: &[ReviewViolation],
+        skipped: &[SkippedViolation],
+    ) -> Result<()> {
+        let cached = CachedReview { violations: violations.to_vec(), skipped: skipped.to_vec() };
+        let path = self.entry(key);
+        let temp = path.with_extension(format!("tmp{}", std::process::id()));
+        fs::write(&temp, serde_json::to_vec(&cached)?)?;
+        fs::rename(&temp, &path).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    fn entry(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+#[cfg(test)]
+mod result_cache_tests {
+    use super::*;
+    use crate::core::review_engine::SkipReason;
+    use crate::core::Severity;
+    use crate::report::test_support::violation;
+
+    #[test]
+    fn test_entries_round_trip_and_other_rule_sets_are_dropped() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join(CACHE_DIR);
+        let found = violation("lib/a.ex", 3, Severity::Major);
+        let skipped =
+            SkippedViolation { violation: found.clone(), reason: SkipReason::InlineSuppression };
+
+        let cache = ResultCache::open(&root, "rules-v1").unwrap();
+        let key = ResultCache::file_key("lib/a.ex +3", Some(b"IO.inspect(x)")).unwrap();
+        assert!(cache.get(&key).is_none());
+        cache.put(&key, std::slice::from_ref(&found), &[skipped]).unwrap();
+
+        let (violations, skipped) = cache.get(&key).unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].fingerprint(), found.fingerprint());
+        assert_eq!(skipped[0].reason, SkipReason::InlineSuppression);
+        assert_ne!(key, ResultCache::file_key("lib/a.ex +3", Some(b"IO.puts(x)")).unwrap());
+        assert_eq!(fs::read_to_string(root.join(".gitignore")).unwrap(), "*\n");
+
+        // Reopening with the same rules keeps the entry; other rules start over
+        assert!(ResultCache::open(&root, "rules-v1").unwrap().get(&key).is_some());
+        let cache = ResultCache::open(&root, "rules-v2").unwrap();
+        assert!(cache.get(&key).is_none());
+        assert!(!root.join("rules-v1").exists());
+    }
+}
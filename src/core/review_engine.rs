@@ -1,13 +1,33 @@
 use anyhow::Result;
-use regex::Regex;
-use std::collections::HashMap;
-use std::path::Path;
-
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Range, RangeInclusive};
+use std::path::{Path, PathBuf};
+
+use crate::config::resolve::ResolvedConfig;
+use crate::core::audit;
+use crate::core::budgets::Budgets;
+use crate::core::confidence;
+use crate::core::coverage::{FileCoverage, FileSkipReason, FileStatus};
+use crate::core::formatting::{self, FormattingConfig};
+use crate::core::gates::TagGates;
+use crate::core::grouping::dedup_violations;
+use crate::core::large_hunks::{LargeHunkPolicy, PartialHunk};
+use crate::core::lexer::{self, FileRegions, LineRegions};
+use crate::core::line_matcher::LineMatcher;
+use crate::core::provenance::RuleSources;
 use crate::core::registry::PatternRegistry;
+use crate::core::result_cache::ResultCache;
+use crate::core::rule_conflicts::{ConflictPolicy, RuleConflict, RuleConflicts};
+use crate::core::suppression;
+use crate::core::symlinks::SymlinkPolicy;
 use crate::core::{AntiPattern, DetectionMethod, Language, Severity};
-use crate::git::{ChangedLine, GitDiff};
+use crate::git::blame::LineBlame;
+use crate::git::{ChangeType, ChangedLine, FileDiff, FileKind, GitDiff};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewViolation {
     pub rule: AntiPattern,
     pub file_path: String,
@@ -21,8 +41,87 @@ pub struct ReviewViolation {
     pub context_before: Vec<String>,
     #[allow(dead_code)] // Used in tests and context display
     pub context_after: Vec<String>,
-    #[allow(dead_code)] // Used in AI integration and tests
+    /// How likely the finding is a real problem, from 0 to 1; see `confidence::score`
     pub confidence: f64,
+    /// Byte range of `content` the rule matched; `None` for rules about the whole line.
+    /// Reports convert it to columns with `report::positions`.
+    pub span: Option<Range<usize>>,
+    /// Who last changed the line, filled in by `review --blame`; `None` otherwise and for
+    /// lines not committed yet
+    pub blame: Option<LineBlame>,
+    /// What the rule's capture groups matched, by group number and by name for named
+    /// groups, e.g. `{"1": "params[\"kind\"]"}`. Groups that did not take part are left out.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub captures: BTreeMap<String, String>,
+    /// Teams or people CODEOWNERS assigns the file to, in the order listed there; empty when
+    /// the repository has no CODEOWNERS file or no entry covers the file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+}
+
+impl ReviewViolation {
+    /// Stable identifier for this finding that survives unrelated edits shifting line numbers:
+    /// FNV-1a over the rule id, file path and trimmed line content, as 16 hex digits.
+    pub fn fingerprint(&self) -> String {
+        const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        let mut hash = FNV_OFFSET;
+        for part in [self.rule.id.as_str(), self.file_path.as_str(), self.content.trim()] {
+            for byte in part.bytes().chain(std::iter::once(0)) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+        format!("{hash:016x}")
+    }
+}
+
+/// Why a finding that matched a rule was not reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// A `patingin-ignore` comment on the line or the line above
+    InlineSuppression,
+    /// The line is older than `--ignore-older-than`
+    OlderThan,
+    /// A symlink replacing a file inside `symlinks.allowed_dirs`
+    AllowedSymlink,
+    /// Recorded in `.patingin/baseline.yml`
+    Baselined,
+    /// Triaged as an accepted risk in `.patingin/baseline.yml`
+    AcceptedRisk,
+    /// The same finding as another one reported on the line
+    Duplicate,
+    /// Scored below `--min-confidence`
+    LowConfidence,
+    /// Inside a comment, for a rule with `skip_comments`
+    InComment,
+    /// Inside a string literal, for a rule with `skip_strings`
+    InString,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::InlineSuppression => write!(f, "inline suppression"),
+            SkipReason::OlderThan => write!(f, "older than --ignore-older-than"),
+            SkipReason::AllowedSymlink => write!(f, "allowed symlink"),
+            SkipReason::Baselined => write!(f, "in the baseline"),
+            SkipReason::AcceptedRisk => write!(f, "accepted risk"),
+            SkipReason::Duplicate => write!(f, "duplicate"),
+            SkipReason::LowConfidence => write!(f, "below --min-confidence"),
+            SkipReason::InComment => write!(f, "in a comment"),
+            SkipReason::InString => write!(f, "in a string literal"),
+        }
+    }
+}
+
+/// A finding that was not reported, kept so `--show-skipped` can explain it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedViolation {
+    pub violation: ReviewViolation,
+    pub reason: SkipReason,
 }
 
 #[derive(Debug)]
@@ -31,20 +130,100 @@ pub struct ReviewResult {
     #[allow(dead_code)] // Used in tests and JSON output
     pub files_with_violations: HashMap<String, Vec<ReviewViolation>>,
     pub summary: ReviewSummary,
+    pub skipped: Vec<SkippedViolation>,
 }
 
-#[derive(Debug)]
+impl ReviewResult {
+    /// Move the violations matching `skip` to the skipped ones for `reason`, recounting the
+    /// summary. Returns how many were skipped.
+    pub fn skip(
+        &mut self,
+        reason: SkipReason,
+        mut skip: impl FnMut(&ReviewViolation) -> bool,
+    ) -> usize {
+        let before = self.skipped.len();
+        let (skipped, kept) = std::mem::take(&mut self.violations)
+            .into_iter()
+            .partition::<Vec<_>, _>(|violation| skip(violation));
+        self.violations = kept;
+        self.skipped
+            .extend(skipped.into_iter().map(|violation| SkippedViolation { violation, reason }));
+
+        self.files_with_violations.clear();
+        self.summary = ReviewSummary::default();
+        for violation in &self.violations {
+            self.summary.record(violation);
+            self.files_with_violations
+                .entry(violation.file_path.clone())
+                .or_default()
+                .push(violation.clone());
+        }
+        self.skipped.iter().for_each(|skipped| self.summary.record_skip(skipped.reason));
+        self.skipped.len() - before
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ReviewSummary {
     pub total_violations: usize,
+    pub blocker_count: usize,
     pub critical_count: usize,
     pub major_count: usize,
     pub warning_count: usize,
+    pub info_count: usize,
     pub files_affected: Vec<String>,
     pub auto_fixable_count: usize,
+    /// Findings that matched a rule but were not reported, by reason
+    pub skipped: BTreeMap<SkipReason, usize>,
+}
+
+impl ReviewSummary {
+    /// Fold a single violation into the running totals.
+    pub fn record(&mut self, violation: &ReviewViolation) {
+        self.total_violations += 1;
+        match violation.severity {
+            Severity::Blocker => self.blocker_count += 1,
+            Severity::Critical => self.critical_count += 1,
+            Severity::Major => self.major_count += 1,
+            Severity::Warning => self.warning_count += 1,
+            Severity::Info => self.info_count += 1,
+        }
+        if violation.auto_fixable {
+            self.auto_fixable_count += 1;
+        }
+        if !self.files_affected.contains(&violation.file_path) {
+            self.files_affected.push(violation.file_path.clone());
+        }
+    }
+
+    /// Reported violations of `severity`
+    pub fn count(&self, severity: Severity) -> usize {
+        match severity {
+            Severity::Blocker => self.blocker_count,
+            Severity::Critical => self.critical_count,
+            Severity::Major => self.major_count,
+            Severity::Warning => self.warning_count,
+            Severity::Info => self.info_count,
+        }
+    }
+
+    /// Count a finding that was not reported
+    pub fn record_skip(&mut self, reason: SkipReason) {
+        *self.skipped.entry(reason).or_default() += 1;
+    }
 }
 
 pub struct ReviewEngine {
     registry: PatternRegistry,
+    formatting: FormattingConfig,
+    gates: TagGates,
+    budgets: Budgets,
+    symlinks: SymlinkPolicy,
+    large_hunks: LargeHunkPolicy,
+    /// Where diff paths are read from when a rule needs the whole file
+    source_root: PathBuf,
+    /// Per-file results of earlier runs with the same rules and settings
+    cache: Option<ResultCache>,
 }
 
 impl Default for ReviewEngine {
@@ -58,30 +237,267 @@ impl ReviewEngine {
         let mut registry = PatternRegistry::new();
         registry.load_built_in_patterns().expect("Failed to load built-in patterns");
 
-        Self { registry }
+        Self {
+            registry,
+            formatting: FormattingConfig::default(),
+            gates: TagGates::default(),
+            budgets: Budgets::default(),
+            symlinks: SymlinkPolicy::default(),
+            large_hunks: LargeHunkPolicy::default(),
+            source_root: PathBuf::from("."),
+            cache: None,
+        }
     }
 
+    #[allow(dead_code)] // Used in tests and by library users
     pub fn new_with_custom_rules(project_name: &str) -> Self {
+        Self::with_custom_rules(project_name, None, ConflictPolicy::default())
+            .expect("Failed to load built-in patterns")
+    }
+
+    /// Built-in rules plus the project's custom rules and the team rules under
+    /// `project_root`, with rule id collisions resolved by `policy`. Custom rules that cannot
+    /// be read only warn, but collisions under `ConflictPolicy::Error` fail.
+    fn with_custom_rules(
+        project_name: &str,
+        project_root: Option<&Path>,
+        policy: ConflictPolicy,
+    ) -> Result<Self> {
         let mut registry = PatternRegistry::new();
-        registry.load_built_in_patterns().expect("Failed to load built-in patterns");
+        registry.load_built_in_patterns()?;
 
         // Load custom rules for the project
-        if let Err(e) = registry.load_custom_rules(project_name) {
+        if let Err(e) = registry.load_custom_rules_with_policy(project_name, project_root, policy) {
+            if e.is::<RuleConflicts>() {
+                return Err(e);
+            }
             eprintln!("Warning: Failed to load custom rules for {project_name}: {e}");
         }
 
-        Self { registry }
+        Ok(Self {
+            registry,
+            formatting: FormattingConfig::default(),
+            gates: TagGates::default(),
+            budgets: Budgets::default(),
+            symlinks: SymlinkPolicy::default(),
+            large_hunks: LargeHunkPolicy::default(),
+            source_root: PathBuf::from("."),
+            cache: None,
+        })
+    }
+
+    /// The engine for a project: built-in rules, the project's custom and team rules and the
+    /// formatting limits, tag gates and symlink policy of its config file
+    pub fn for_project(project_name: &str, project_root: &Path) -> Result<Self> {
+        let config_path = crate::config::find_project_config(project_root);
+        let policy = config_path.as_deref().map(ConflictPolicy::load).transpose()?;
+        let policy = policy.unwrap_or_default();
+        let mut review_engine = Self::with_custom_rules(project_name, Some(project_root), policy)?
+            .with_source_root(project_root);
+        // Global rule settings first, so the project's override them
+        for layer in ResolvedConfig::load(project_root)?.layers().iter().rev() {
+            let Some(path) = &layer.path else { continue };
+            for warning in review_engine.registry.apply_rule_settings(&layer.config.rules) {
+                eprintln!("⚠️  {}: {warning}", path.display());
+            }
+        }
+        if let Some(config_path) = config_path {
+            review_engine = review_engine
+                .with_formatting(FormattingConfig::load(&config_path)?)
+                .with_gates(TagGates::load(&config_path)?)
+                .with_budgets(Budgets::load(&config_path)?)
+                .with_symlinks(SymlinkPolicy::load(&config_path)?)
+                .with_large_hunks(LargeHunkPolicy::load(&config_path)?);
+        }
+        Ok(review_engine)
+    }
+
+    /// Apply project-level line width and indentation overrides
+    pub fn with_formatting(mut self, formatting: FormattingConfig) -> Self {
+        self.formatting = formatting;
+        self
+    }
+
+    /// Apply project-level tag gates; rules whose gated tags are all off are not checked
+    pub fn with_gates(mut self, gates: TagGates) -> Self {
+        self.gates = gates;
+        self
+    }
+
+    pub fn gates(&self) -> &TagGates {
+        &self.gates
+    }
+
+    /// Apply project-level violation budgets per directory
+    pub fn with_budgets(mut self, budgets: Budgets) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    pub fn budgets(&self) -> &Budgets {
+        &self.budgets
+    }
+
+    /// The rules this review checks, with overrides applied
+    pub fn registry(&self) -> &PatternRegistry {
+        &self.registry
+    }
+
+    /// Where each loaded rule came from
+    pub fn rule_sources(&self) -> &RuleSources {
+        self.registry.rule_sources()
+    }
+
+    /// Custom rules that collided with a loaded rule id, and how each was resolved
+    pub fn rule_conflicts(&self) -> &[RuleConflict] {
+        self.registry.conflicts()
+    }
+
+    /// Directories where replacing a file with a symlink is expected
+    pub fn with_symlinks(mut self, symlinks: SymlinkPolicy) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// Limit above which a hunk is sampled against critical rules only
+    pub fn with_large_hunks(mut self, large_hunks: LargeHunkPolicy) -> Self {
+        self.large_hunks = large_hunks;
+        self
+    }
+
+    /// Directory diff paths are relative to, for rules that measure whole blocks of a file
+    pub fn with_source_root(mut self, source_root: impl Into<PathBuf>) -> Self {
+        self.source_root = source_root.into();
+        self
+    }
+
+    /// Reuse and store per-file results in `cache`, which must have been opened with this
+    /// engine's `cache_key`
+    pub fn with_cache(mut self, cache: ResultCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Hash of everything besides the file itself that decides a file's review: the patingin
+    /// version, every rule and the project's formatting, gate, symlink and large hunk settings
+    pub fn cache_key(&self) -> Result<String> {
+        let settings = format!(
+            "{:?}{:?}{:?}{:?}",
+            self.formatting, self.gates, self.symlinks, self.large_hunks
+        );
+        let key = (env!("CARGO_PKG_VERSION"), self.registry.all_patterns(), settings);
+        audit::hash_bytes(&serde_json::to_vec(&key)?)
+    }
+
+    /// Hunks of `git_diff` this engine will only partially analyze, for the report
+    pub fn partial_hunks(&self, git_diff: &GitDiff) -> Vec<PartialHunk> {
+        self.large_hunks.partial_hunks(&git_diff.files)
+    }
+
+    /// Every file of `git_diff`, whether it was analyzed and how many of `violations` it
+    /// has, sorted by path. `filtered_out` are files removed from the diff before the review,
+    /// e.g. by `--language`; files that only appear in parse warnings are listed as unparsed.
+    pub fn file_coverage(
+        &self,
+        git_diff: &GitDiff,
+        filtered_out: &[(String, FileSkipReason)],
+        violations: &[ReviewViolation],
+    ) -> Vec<FileCoverage> {
+        let mut per_file: HashMap<&str, usize> = HashMap::new();
+        for violation in violations {
+            *per_file.entry(violation.file_path.as_str()).or_default() += 1;
+        }
+
+        let status = |file_diff: &FileDiff| match file_diff.kind {
+            FileKind::Symlink { replaced_file: false } => {
+                FileStatus::Skipped(FileSkipReason::Symlink)
+            }
+            FileKind::LfsPointer => FileStatus::Skipped(FileSkipReason::LfsPointer),
+            FileKind::Binary => FileStatus::Skipped(FileSkipReason::Binary),
+            FileKind::Deleted => FileStatus::Skipped(FileSkipReason::Deleted),
+            _ if file_diff.added_lines.is_empty() => {
+                FileStatus::Skipped(FileSkipReason::NoAddedLines)
+            }
+            FileKind::Symlink { replaced_file: true } => FileStatus::Analyzed,
+            FileKind::Regular
+                if self.registry.get_patterns_for_file(&file_diff.path).is_empty() =>
+            {
+                FileStatus::Skipped(FileSkipReason::NoRules)
+            }
+            FileKind::Regular if self.large_hunks.large_hunks(file_diff).next().is_some() => {
+                FileStatus::PartiallyAnalyzed
+            }
+            FileKind::Regular => FileStatus::Analyzed,
+        };
+        // A file replaced by a symlink is deleted and re-created under the same path; the
+        // deletion is listed only when nothing else is
+        let (deleted, present): (Vec<&FileDiff>, Vec<&FileDiff>) =
+            git_diff.files.iter().partition(|file_diff| file_diff.kind == FileKind::Deleted);
+        let reviewed = present
+            .into_iter()
+            .chain(deleted)
+            .map(|file_diff| (file_diff.path.clone(), status(file_diff)));
+        let filtered =
+            filtered_out.iter().map(|(path, reason)| (path.clone(), FileStatus::Skipped(*reason)));
+        let unparsed = git_diff
+            .parse_warnings
+            .iter()
+            .filter_map(|warning| warning.path.clone())
+            .map(|path| (path, FileStatus::Skipped(FileSkipReason::Unparsed)));
+
+        let mut coverage: Vec<FileCoverage> = Vec::new();
+        for (path, status) in reviewed.chain(filtered).chain(unparsed) {
+            if coverage.iter().any(|file| file.path == path) {
+                continue;
+            }
+            coverage.push(FileCoverage {
+                language: self.detect_language_from_path(&path),
+                violations: per_file.get(path.as_str()).copied().unwrap_or(0),
+                path,
+                status,
+            });
+        }
+        coverage.sort_by(|a, b| a.path.cmp(&b.path));
+        coverage
+    }
+
+    /// Checksum of every rule this engine checks, after tag gates silence theirs, for the
+    /// audit log
+    pub fn ruleset_checksum(&self) -> Result<String> {
+        let mut patterns = self.registry.all_patterns();
+        patterns.retain(|pattern| !self.gates.silences(pattern));
+        audit::hash_bytes(&serde_json::to_vec(&patterns)?)
     }
 
+    #[allow(dead_code)] // Used in tests and by library users
     pub fn review_changed_lines(
         &self,
         file_path: &str,
         changed_lines: &[ChangedLine],
+    ) -> Result<Vec<ReviewViolation>> {
+        self.review_lines(file_path, changed_lines.iter(), None, None, &mut Vec::new())
+    }
+
+    /// Check `changed_lines` against the rules for `file_path`, or only those at or above
+    /// `min_severity`. Suppressed findings go to `skipped`. With the file's diff and source,
+    /// comments and strings are found by lexing the whole file; without, from the lines
+    /// above each changed line.
+    fn review_lines<'a>(
+        &self,
+        file_path: &str,
+        changed_lines: impl Iterator<Item = &'a ChangedLine>,
+        min_severity: Option<Severity>,
+        source: Option<(&FileDiff, &OnceCell<Option<String>>)>,
+        skipped: &mut Vec<SkippedViolation>,
     ) -> Result<Vec<ReviewViolation>> {
         let mut violations = Vec::new();
 
         // Get patterns for this specific file (more efficient than language detection)
-        let patterns = self.registry.get_patterns_for_file(file_path);
+        let mut patterns = self.registry.get_patterns_for_file(file_path);
+        patterns.retain(|pattern| {
+            !self.gates.silences(pattern)
+                && min_severity.map_or(true, |min| pattern.severity.is_at_least(min))
+        });
 
         if patterns.is_empty() {
             return Ok(violations); // Skip if no patterns match this file type
@@ -89,30 +505,451 @@ impl ReviewEngine {
 
         // Still detect language for violation metadata
         let language = self.detect_language_from_path(file_path).unwrap_or(Language::JavaScript);
+        let comment_syntax = language.comment_syntax();
+
+        // Only the rules a line's literals and the language's `RegexSet` pick run in full
+        let mut matcher = LineMatcher::new(&self.registry, &patterns);
+        // Lexed only once a line matches something
+        let file_regions = OnceCell::new();
+        let regions_of = |changed_line: &ChangedLine| {
+            let file = file_regions.get_or_init(|| {
+                let (file_diff, source) = source?;
+                Some(FileRegions::new(self.source(source, file_diff)?, &language))
+            });
+            file.as_ref()
+                .and_then(|file| file.line(changed_line.line_number))
+                .cloned()
+                .unwrap_or_else(|| {
+                    lexer::regions_after(
+                        &changed_line.context_before,
+                        &changed_line.content,
+                        &language,
+                    )
+                })
+        };
 
         // Check each changed line against patterns
         for changed_line in changed_lines {
-            for pattern in &patterns {
-                if let Some(violation) = self.check_line_against_pattern(
+            let line_regions = OnceCell::new();
+            let regions = || line_regions.get_or_init(|| regions_of(changed_line));
+            for &position in matcher.pick(&changed_line.content) {
+                let pattern = patterns[position];
+                if let Some(mut violation) = self.check_line_against_pattern(
                     file_path,
                     changed_line,
                     pattern,
                     language.clone(),
+                    &|span| ignored_region(pattern, span, regions()).is_some(),
                 )? {
+                    let regions = regions();
+                    violation.confidence = confidence::score(&violation, regions);
+                    let ignored = violation
+                        .span
+                        .as_ref()
+                        .and_then(|span| ignored_region(pattern, span, regions));
+                    if let Some(reason) = ignored {
+                        skipped.push(SkippedViolation { violation, reason });
+                    } else if suppression::is_suppressed(changed_line, &pattern.id, &comment_syntax)
+                    {
+                        skipped.push(SkippedViolation {
+                            violation,
+                            reason: SkipReason::InlineSuppression,
+                        });
+                    } else {
+                        violations.push(violation);
+                    }
+                }
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Review a regular file's added lines; oversized hunks only get a sample of their
+    /// lines checked against critical rules
+    fn review_regular_file(
+        &self,
+        file_diff: &FileDiff,
+        skipped: &mut Vec<SkippedViolation>,
+    ) -> Result<Vec<ReviewViolation>> {
+        let large: Vec<_> = self.large_hunks.large_hunks(file_diff).collect();
+        let in_large_hunk = |index: &usize| large.iter().any(|hunk| hunk.contains(index));
+        let regular = (0..file_diff.added_lines.len()).filter(|index| !in_large_hunk(index));
+        // All rules share one read of the file, made only when one of them needs it
+        let source = OnceCell::new();
+        let mut violations = self.review_lines(
+            &file_diff.path,
+            regular.map(|index| &file_diff.added_lines[index]),
+            None,
+            Some((file_diff, &source)),
+            skipped,
+        )?;
+        for hunk in &large {
+            violations.extend(self.review_lines(
+                &file_diff.path,
+                self.large_hunks.sample(hunk.clone()).map(|index| &file_diff.added_lines[index]),
+                Some(Severity::Critical),
+                Some((file_diff, &source)),
+                skipped,
+            )?);
+        }
+        violations.extend(self.review_block_lengths(file_diff, &source, skipped)?);
+        violations.extend(self.review_multiline(file_diff, &source, skipped)?);
+        violations.sort_by_key(|violation| violation.line_number);
+        Ok(violations)
+    }
+
+    /// The whole file of `file_diff`, read from the source root on first use. `None` when it
+    /// cannot be read or no longer matches the diff, so whole-file rules do not measure a
+    /// file the diff does not describe.
+    fn source<'a>(
+        &self,
+        source: &'a OnceCell<Option<String>>,
+        file_diff: &FileDiff,
+    ) -> Option<&'a str> {
+        let content = source.get_or_init(|| {
+            let content = std::fs::read_to_string(self.source_root.join(&file_diff.path)).ok()?;
+            let lines: Vec<&str> =
+                content.lines().map(|line| line.trim_end_matches('\r')).collect();
+            let matches_diff = file_diff.added_lines.iter().all(|added| {
+                let line = added.line_number.checked_sub(1).and_then(|index| lines.get(index));
+                line.is_some_and(|line| *line == added.content.trim_end_matches('\r'))
+            });
+            matches_diff.then_some(content)
+        });
+        content.as_deref()
+    }
+
+    /// Check `line_count` rules: a block opened by a line matching the rule's pattern that
+    /// spans more than its threshold is reported at its first line, when any of its lines
+    /// was added.
+    fn review_block_lengths(
+        &self,
+        file_diff: &FileDiff,
+        source: &OnceCell<Option<String>>,
+        skipped: &mut Vec<SkippedViolation>,
+    ) -> Result<Vec<ReviewViolation>> {
+        let mut patterns = self.registry.get_patterns_for_file(&file_diff.path);
+        patterns.retain(|pattern| {
+            matches!(pattern.detection_method, DetectionMethod::LineCount { .. })
+                && !self.gates.silences(pattern)
+        });
+        if patterns.is_empty() || file_diff.added_lines.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let Some(content) = self.source(source, file_diff) else {
+            return Ok(Vec::new());
+        };
+        let lines: Vec<&str> = content.lines().map(|line| line.trim_end_matches('\r')).collect();
+
+        let language =
+            self.detect_language_from_path(&file_diff.path).unwrap_or(Language::JavaScript);
+        let comment_syntax = language.comment_syntax();
+        let tab_width = self.formatting.resolve(&file_diff.path, &language).tab_width.unwrap_or(4);
+
+        let mut violations = Vec::new();
+        for pattern in patterns {
+            let DetectionMethod::LineCount { threshold, .. } = pattern.detection_method else {
+                continue;
+            };
+            let Some(regex) = self.registry.get_compiled_variant(&pattern.id, &pattern.language)
+            else {
+                continue;
+            };
+
+            for (start, header) in lines.iter().enumerate() {
+                if !regex.is_match(header) {
+                    continue;
+                }
+                let end = formatting::block_end(&lines, start, tab_width);
+                let length = end - start + 1;
+                let block = start + 1..=end + 1;
+                if length <= threshold
+                    || !file_diff.added_lines.iter().any(|added| block.contains(&added.line_number))
+                {
+                    continue;
+                }
+
+                let header_line = ChangedLine {
+                    line_number: start + 1,
+                    content: header.to_string(),
+                    change_type: ChangeType::Added,
+                    context_before: start
+                        .checked_sub(1)
+                        .map(|above| lines[above].to_string())
+                        .into_iter()
+                        .collect(),
+                    context_after: Vec::new(),
+                };
+                let mut violation = ReviewViolation {
+                    rule: pattern.clone(),
+                    file_path: file_diff.path.clone(),
+                    line_number: header_line.line_number,
+                    content: header_line.content.clone(),
+                    severity: pattern.severity,
+                    language: language.clone(),
+                    fix_suggestion: format!(
+                        "Spans {length} lines (limit {threshold}). {}",
+                        pattern.fix_suggestion
+                    ),
+                    auto_fixable: pattern.claude_code_fixable,
+                    context_before: header_line.context_before.clone(),
+                    context_after: Vec::new(),
+                    confidence: 0.0,
+                    span: None,
+                    blame: None,
+                    captures: BTreeMap::new(),
+                    owners: Vec::new(),
+                };
+                violation.confidence = confidence::score(&violation, &LineRegions::default());
+                if suppression::is_suppressed(&header_line, &pattern.id, &comment_syntax) {
+                    skipped.push(SkippedViolation {
+                        violation,
+                        reason: SkipReason::InlineSuppression,
+                    });
+                } else {
+                    violations.push(violation);
+                }
+            }
+        }
+        Ok(violations)
+    }
+
+    /// Check `multiline` rules against the whole file; see `DetectionMethod::Multiline`
+    fn review_multiline(
+        &self,
+        file_diff: &FileDiff,
+        source: &OnceCell<Option<String>>,
+        skipped: &mut Vec<SkippedViolation>,
+    ) -> Result<Vec<ReviewViolation>> {
+        let mut patterns = self.registry.get_patterns_for_file(&file_diff.path);
+        patterns.retain(|pattern| {
+            matches!(pattern.detection_method, DetectionMethod::Multiline { .. })
+                && !self.gates.silences(pattern)
+        });
+        if patterns.is_empty() || file_diff.added_lines.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let Some(content) = self.source(source, file_diff) else {
+            return Ok(Vec::new());
+        };
+        let lines: Vec<&str> = content.lines().map(|line| line.trim_end_matches('\r')).collect();
+        let line_starts: Vec<usize> =
+            std::iter::once(0).chain(content.match_indices('\n').map(|(at, _)| at + 1)).collect();
+        // 1-based number of the line holding byte `offset`
+        let line_of =
+            |offset: usize| line_starts.partition_point(|start| *start <= offset).min(lines.len());
+        let has_added = |range: &RangeInclusive<usize>| {
+            file_diff.added_lines.iter().any(|added| range.contains(&added.line_number))
+        };
+
+        let language =
+            self.detect_language_from_path(&file_diff.path).unwrap_or(Language::JavaScript);
+        let comment_syntax = language.comment_syntax();
+        let file_regions = OnceCell::new();
+
+        let mut violations = Vec::new();
+        for pattern in patterns {
+            let DetectionMethod::Multiline { unless, window, .. } = &pattern.detection_method
+            else {
+                continue;
+            };
+            let Some(regex) = self.registry.get_compiled_variant(&pattern.id, &pattern.language)
+            else {
+                continue;
+            };
+            let unless = match unless {
+                Some(unless) => match RegexBuilder::new(unless).multi_line(true).build() {
+                    Ok(unless) => Some(unless),
+                    Err(_) => continue,
+                },
+                None => None,
+            };
+
+            for captured in regex.captures_iter(content) {
+                let Some(found) = captured.get(0) else { continue };
+                let first = line_of(found.start());
+                let last = line_of(found.end().saturating_sub(1).max(found.start()));
+                let range = match window {
+                    Some(window) => first.saturating_sub(*window).max(1)..=last + window,
+                    None if unless.is_some() => 1..=lines.len(),
+                    None => first..=last,
+                };
+                if !has_added(&range) {
+                    continue;
+                }
+                if let Some(unless) = &unless {
+                    let start = line_starts[range.start() - 1];
+                    let end = line_starts.get(*range.end()).copied().unwrap_or(content.len());
+                    if unless.is_match(&content[start..end]) {
+                        continue;
+                    }
+                }
+
+                let header = lines.get(first - 1).copied().unwrap_or_default();
+                let header_start = line_starts[first - 1];
+                let header_line = ChangedLine {
+                    line_number: first,
+                    content: header.to_string(),
+                    change_type: ChangeType::Added,
+                    context_before: first
+                        .checked_sub(2)
+                        .map(|above| lines[above].to_string())
+                        .into_iter()
+                        .collect(),
+                    context_after: lines[first..last].iter().map(|line| line.to_string()).collect(),
+                };
+                let mut violation = ReviewViolation {
+                    rule: pattern.clone(),
+                    file_path: file_diff.path.clone(),
+                    line_number: first,
+                    content: header_line.content.clone(),
+                    severity: pattern.severity,
+                    language: language.clone(),
+                    fix_suggestion: interpolate_captures(&pattern.fix_suggestion, regex, &captured),
+                    auto_fixable: pattern.claude_code_fixable,
+                    context_before: header_line.context_before.clone(),
+                    context_after: header_line.context_after.clone(),
+                    confidence: 0.0,
+                    span: Some(
+                        found.start() - header_start
+                            ..found.end().min(header_start + header.len()) - header_start,
+                    ),
+                    blame: None,
+                    captures: capture_map(regex, &captured),
+                    owners: Vec::new(),
+                };
+                let regions = file_regions
+                    .get_or_init(|| FileRegions::new(content, &language))
+                    .line(first)
+                    .cloned()
+                    .unwrap_or_default();
+                violation.confidence = confidence::score(&violation, &regions);
+                let ignored = violation
+                    .span
+                    .as_ref()
+                    .and_then(|span| ignored_region(pattern, span, &regions));
+                if let Some(reason) = ignored {
+                    skipped.push(SkippedViolation { violation, reason });
+                } else if suppression::is_suppressed(&header_line, &pattern.id, &comment_syntax) {
+                    skipped.push(SkippedViolation {
+                        violation,
+                        reason: SkipReason::InlineSuppression,
+                    });
+                } else {
                     violations.push(violation);
                 }
             }
         }
+        Ok(violations)
+    }
 
+    /// Review one changed path. Content rules only see regular files: symlink targets and
+    /// LFS pointers are not code, so symlinks only get the repository-level checks.
+    pub fn review_file_diff(&self, file_diff: &FileDiff) -> Result<Vec<ReviewViolation>> {
+        self.review_file(file_diff, &mut Vec::new())
+    }
+
+    /// `review_file_diff`, collecting the findings that are not reported in `skipped`. With
+    /// a cache, a file reviewed before with the same diff and content is not analyzed again.
+    fn review_file(
+        &self,
+        file_diff: &FileDiff,
+        skipped: &mut Vec<SkippedViolation>,
+    ) -> Result<Vec<ReviewViolation>> {
+        let Some(cache) = &self.cache else {
+            return self.analyze_file(file_diff, skipped);
+        };
+        let language = self.detect_language_from_path(&file_diff.path);
+        let source = std::fs::read(self.source_root.join(&file_diff.path)).ok();
+        let key = ResultCache::file_key(&format!("{language:?}{file_diff:?}"), source.as_deref())?;
+        if let Some((violations, cached_skipped)) = cache.get(&key) {
+            skipped.extend(cached_skipped);
+            return Ok(violations);
+        }
+
+        let mut file_skipped = Vec::new();
+        let violations = self.analyze_file(file_diff, &mut file_skipped)?;
+        // A failed write only means the next run analyzes this file again
+        let _ = cache.put(&key, &violations, &file_skipped);
+        skipped.extend(file_skipped);
         Ok(violations)
     }
 
+    /// Every finding of `file_diff`, with duplicates merged
+    fn analyze_file(
+        &self,
+        file_diff: &FileDiff,
+        skipped: &mut Vec<SkippedViolation>,
+    ) -> Result<Vec<ReviewViolation>> {
+        let violations = self.find_violations(file_diff, skipped)?;
+        Ok(dedup_violations(violations, skipped))
+    }
+
+    fn find_violations(
+        &self,
+        file_diff: &FileDiff,
+        skipped: &mut Vec<SkippedViolation>,
+    ) -> Result<Vec<ReviewViolation>> {
+        match file_diff.kind {
+            FileKind::Regular => self.review_regular_file(file_diff, skipped),
+            FileKind::LfsPointer | FileKind::Binary | FileKind::Deleted => Ok(Vec::new()),
+            FileKind::Symlink { replaced_file } => {
+                if !replaced_file {
+                    return Ok(Vec::new());
+                }
+                let target = file_diff.added_lines.first();
+                let violations: Vec<ReviewViolation> = self
+                    .registry
+                    .all_patterns()
+                    .into_iter()
+                    .filter(|pattern| {
+                        pattern.enabled
+                            && matches!(
+                                pattern.detection_method,
+                                DetectionMethod::SymlinkReplacement
+                            )
+                            && !self.gates.silences(pattern)
+                    })
+                    .map(|pattern| ReviewViolation {
+                        rule: pattern.clone(),
+                        file_path: file_diff.path.clone(),
+                        line_number: target.map_or(1, |line| line.line_number),
+                        content: target.map_or_else(String::new, |line| line.content.clone()),
+                        severity: pattern.severity,
+                        language: pattern.language.clone(),
+                        fix_suggestion: pattern.fix_suggestion.clone(),
+                        auto_fixable: pattern.claude_code_fixable,
+                        context_before: Vec::new(),
+                        context_after: Vec::new(),
+                        confidence: 1.0,
+                        span: None,
+                        blame: None,
+                        captures: BTreeMap::new(),
+                        owners: Vec::new(),
+                    })
+                    .collect();
+                if !self.symlinks.allows(&file_diff.path) {
+                    return Ok(violations);
+                }
+                skipped.extend(violations.into_iter().map(|violation| SkippedViolation {
+                    violation,
+                    reason: SkipReason::AllowedSymlink,
+                }));
+                Ok(Vec::new())
+            }
+        }
+    }
+
     pub fn review_git_diff(&self, git_diff: &GitDiff) -> Result<ReviewResult> {
         let mut all_violations = Vec::new();
         let mut files_with_violations = HashMap::new();
+        let mut skipped = Vec::new();
 
         for file_diff in &git_diff.files {
-            let violations = self.review_changed_lines(&file_diff.path, &file_diff.added_lines)?;
+            let violations = self.review_file(file_diff, &mut skipped)?;
 
             if !violations.is_empty() {
                 files_with_violations.insert(file_diff.path.clone(), violations.clone());
@@ -120,9 +957,34 @@ impl ReviewEngine {
             }
         }
 
-        let summary = self.create_review_summary(&all_violations);
+        let mut summary = self.create_review_summary(&all_violations);
+        skipped.iter().for_each(|skipped| summary.record_skip(skipped.reason));
 
-        Ok(ReviewResult { violations: all_violations, files_with_violations, summary })
+        Ok(ReviewResult { violations: all_violations, files_with_violations, summary, skipped })
+    }
+
+    /// Review a diff file by file, handing each file and its violations to `on_file` as
+    /// soon as it has been analyzed instead of collecting everything into a `ReviewResult`.
+    pub fn review_git_diff_streaming<F>(
+        &self,
+        git_diff: &GitDiff,
+        mut on_file: F,
+    ) -> Result<ReviewSummary>
+    where
+        F: FnMut(&FileDiff, &[ReviewViolation]) -> Result<()>,
+    {
+        let mut summary = ReviewSummary::default();
+
+        for file_diff in &git_diff.files {
+            let mut skipped = Vec::new();
+            let violations = self.review_file(file_diff, &mut skipped)?;
+            skipped.iter().for_each(|skipped| summary.record_skip(skipped.reason));
+
+            violations.iter().for_each(|violation| summary.record(violation));
+            on_file(file_diff, &violations)?;
+        }
+
+        Ok(summary)
     }
 
     pub fn filter_violations_by_severity<'a>(
@@ -130,45 +992,21 @@ impl ReviewEngine {
         violations: &'a [ReviewViolation],
         min_severity: Severity,
     ) -> Vec<&'a ReviewViolation> {
-        violations.iter().filter(|v| v.severity >= min_severity).collect()
+        violations.iter().filter(|v| v.severity.is_at_least(min_severity)).collect()
     }
 
     pub fn create_review_summary(&self, violations: &[ReviewViolation]) -> ReviewSummary {
-        let total_violations = violations.len();
-        let critical_count = violations.iter().filter(|v| v.severity == Severity::Critical).count();
-        let major_count = violations.iter().filter(|v| v.severity == Severity::Major).count();
-        let warning_count = violations.iter().filter(|v| v.severity == Severity::Warning).count();
-        let auto_fixable_count = violations.iter().filter(|v| v.auto_fixable).count();
-
-        let mut files_affected: Vec<String> =
-            violations.iter().map(|v| v.file_path.clone()).collect();
-        files_affected.sort();
-        files_affected.dedup();
-
-        ReviewSummary {
-            total_violations,
-            critical_count,
-            major_count,
-            warning_count,
-            files_affected,
-            auto_fixable_count,
-        }
+        let mut summary = ReviewSummary::default();
+        violations.iter().for_each(|violation| summary.record(violation));
+        summary.files_affected.sort();
+        summary
     }
 
     pub fn detect_language_from_path(&self, file_path: &str) -> Option<Language> {
         let path = Path::new(file_path);
         let extension = path.extension()?.to_str()?;
 
-        match extension.to_lowercase().as_str() {
-            "ex" | "exs" => Some(Language::Elixir),
-            "js" | "jsx" | "mjs" | "cjs" => Some(Language::JavaScript),
-            "ts" | "tsx" => Some(Language::TypeScript),
-            "py" | "pyw" | "pyi" => Some(Language::Python),
-            "rs" => Some(Language::Rust),
-            "zig" => Some(Language::Zig),
-            "sql" | "psql" | "mysql" => Some(Language::Sql),
-            _ => None,
-        }
+        Language::from_extension(extension)
     }
 
     fn check_line_against_pattern(
@@ -177,23 +1015,72 @@ impl ReviewEngine {
         changed_line: &ChangedLine,
         pattern: &AntiPattern,
         language: Language,
+        ignores: &dyn Fn(&Range<usize>) -> bool,
     ) -> Result<Option<ReviewViolation>> {
         // Skip disabled patterns
         if !pattern.enabled {
             return Ok(None);
         }
 
+        let mut fix_suggestion = pattern.fix_suggestion.clone();
+        let mut span = None;
+        let mut captures = BTreeMap::new();
+
         let matched = match &pattern.detection_method {
             DetectionMethod::Regex { pattern: regex_pattern } => {
-                // Use pre-compiled regex if available
-                if let Some(compiled_regex) = self.registry.get_compiled_pattern(&pattern.id) {
-                    compiled_regex.is_match(&changed_line.content)
-                } else {
-                    // Fallback to creating regex on the fly
-                    match Regex::new(regex_pattern) {
-                        Ok(regex) => regex.is_match(&changed_line.content),
-                        Err(_) => false, // Skip patterns with invalid regex
+                // Use pre-compiled regex if available, falling back to creating it on the fly
+                // (patterns with an invalid regex are skipped)
+                let fallback;
+                let regex = match self.registry.get_compiled_variant(&pattern.id, &pattern.language)
+                {
+                    Some(compiled_regex) => Some(compiled_regex),
+                    None => {
+                        fallback = Regex::new(regex_pattern).ok();
+                        fallback.as_ref()
+                    }
+                };
+                // The first match the rule does not ignore, or else the first match
+                let skip_ignored = pattern.skip_comments || pattern.skip_strings;
+                match regex {
+                    // Only pay for capture groups when the rule has some or refers to them
+                    Some(regex)
+                        if regex.captures_len() > 1 || pattern.fix_suggestion.contains('$') =>
+                    {
+                        let mut all = regex.captures_iter(&changed_line.content);
+                        let first = all.next();
+                        let kept = |found: &regex::Captures| {
+                            found.get(0).map_or(true, |found| !ignores(&found.range()))
+                        };
+                        let found = match first {
+                            Some(first) if skip_ignored && !kept(&first) => {
+                                all.find(kept).or(Some(first))
+                            }
+                            first => first,
+                        };
+                        match found {
+                            Some(found) => {
+                                fix_suggestion =
+                                    interpolate_captures(&pattern.fix_suggestion, regex, &found);
+                                captures = capture_map(regex, &found);
+                                span = found.get(0).map(|found| found.range());
+                                true
+                            }
+                            None => false,
+                        }
+                    }
+                    Some(regex) => {
+                        let mut all = regex.find_iter(&changed_line.content);
+                        let first = all.next().map(|found| found.range());
+                        span = match first {
+                            Some(first) if skip_ignored && ignores(&first) => all
+                                .map(|found| found.range())
+                                .find(|span| !ignores(span))
+                                .or(Some(first)),
+                            first => first,
+                        };
+                        span.is_some()
                     }
+                    None => false,
                 }
             }
             DetectionMethod::Ratio { pattern: regex_pattern, threshold } => {
@@ -212,11 +1099,36 @@ impl ReviewEngine {
                     Err(_) => false,
                 }
             }
-            DetectionMethod::LineCount { threshold: _, pattern: _ } => {
-                // Line count detection would need more context (entire function/file)
-                // For now, skip this detection method for single lines
-                false
+            DetectionMethod::LineLength { max_width, tab_width } => {
+                let limits = self.formatting.resolve(file_path, &language);
+                let max_width = limits.max_line_width.unwrap_or(*max_width);
+                let tab_width = limits.tab_width.unwrap_or(*tab_width);
+                let too_long =
+                    formatting::display_width(&changed_line.content, tab_width) > max_width;
+                if too_long {
+                    fix_suggestion =
+                        formatting::wrap_suggestion(&changed_line.content, max_width, tab_width);
+                }
+                too_long
+            }
+            DetectionMethod::IndentDepth { max_depth, indent_width, tab_width } => {
+                let limits = self.formatting.resolve(file_path, &language);
+                let max_depth = limits.max_indent_depth.unwrap_or(*max_depth);
+                let indent_width = limits.indent_width.unwrap_or(*indent_width);
+                let tab_width = limits.tab_width.unwrap_or(*tab_width);
+                let depth =
+                    formatting::indent_depth(&changed_line.content, indent_width, tab_width);
+                let too_deep = depth > max_depth && !changed_line.content.trim().is_empty();
+                if too_deep {
+                    fix_suggestion = format!(
+                        "Nested {depth} levels deep (limit {max_depth}). {}",
+                        pattern.fix_suggestion
+                    );
+                }
+                too_deep
             }
+            // Measured per block by `review_block_lengths`, which needs the whole file
+            DetectionMethod::LineCount { .. } => false,
             _ => false, // Other detection methods not implemented yet
         };
 
@@ -228,11 +1140,15 @@ impl ReviewEngine {
                 content: changed_line.content.clone(),
                 severity: pattern.severity,
                 language,
-                fix_suggestion: pattern.fix_suggestion.clone(),
+                fix_suggestion,
                 auto_fixable: pattern.claude_code_fixable,
                 context_before: changed_line.context_before.clone(),
                 context_after: changed_line.context_after.clone(),
-                confidence: 0.85, // Default confidence score
+                confidence: 0.0,
+                span,
+                blame: None,
+                captures,
+                owners: Vec::new(),
             };
 
             Ok(Some(violation))
@@ -242,6 +1158,79 @@ impl ReviewEngine {
     }
 }
 
+/// Why `rule` ignores a match at `span`: it starts inside a comment or lies inside a string
+/// literal, and the rule opted out of those with `skip_comments` or `skip_strings`
+pub(crate) fn ignored_region(
+    rule: &AntiPattern,
+    span: &Range<usize>,
+    regions: &LineRegions,
+) -> Option<SkipReason> {
+    if rule.skip_comments && regions.in_comment(span) {
+        Some(SkipReason::InComment)
+    } else if rule.skip_strings && regions.in_string(span) {
+        Some(SkipReason::InString)
+    } else {
+        None
+    }
+}
+
+/// Fill `$1`, `${1}` or `${name}` in a fix suggestion with what the rule's regex captured,
+/// so the suggestion names the offending code. `$$` is a literal `$`; a group that did not
+/// participate in the match is empty, and a reference to a group the regex does not have is
+/// kept as written.
+fn interpolate_captures(template: &str, regex: &Regex, captures: &regex::Captures) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(dollar) = rest.find('$') {
+        result.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+
+        let (reference, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (Some(&braced[..end]), end + 2),
+                None => (None, 0),
+            }
+        } else if let Some(escaped) = after.strip_prefix('$') {
+            result.push('$');
+            rest = escaped;
+            continue;
+        } else {
+            let digits = after.bytes().take_while(u8::is_ascii_digit).count();
+            ((digits > 0).then(|| &after[..digits]), digits)
+        };
+
+        let group = reference.and_then(|reference| match reference.parse::<usize>() {
+            Ok(index) => (index < captures.len()).then(|| captures.get(index)),
+            Err(_) => regex
+                .capture_names()
+                .any(|name| name == Some(reference))
+                .then(|| captures.name(reference)),
+        });
+        match group {
+            Some(group) => result.push_str(group.map_or("", |group| group.as_str())),
+            None => result.push_str(&rest[dollar..dollar + 1 + consumed]),
+        }
+        rest = &after[consumed..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// What each participating group of `captures` matched, keyed by number and, for named
+/// groups, also by name
+fn capture_map(regex: &Regex, captures: &regex::Captures) -> BTreeMap<String, String> {
+    let mut map = BTreeMap::new();
+    for (index, name) in regex.capture_names().enumerate().skip(1) {
+        let Some(group) = captures.get(index) else { continue };
+        map.insert(index.to_string(), group.as_str().to_string());
+        if let Some(name) = name {
+            map.insert(name.to_string(), group.as_str().to_string());
+        }
+    }
+    map
+}
+
 #[cfg(test)]
 mod review_engine_tests {
     use super::*;
@@ -275,7 +1264,7 @@ mod review_engine_tests {
             .expect("Should review changed lines");
 
         // Should detect the dynamic atom creation anti-pattern
-        assert!(violations.len() > 0, "Should detect violations");
+        assert!(!violations.is_empty(), "Should detect violations");
 
         let atom_violation = violations
             .iter()
@@ -285,6 +1274,35 @@ mod review_engine_tests {
         assert_eq!(atom_violation.line_number, 42);
         assert_eq!(atom_violation.severity, Severity::Critical);
         assert!(atom_violation.fix_suggestion.contains("String.to_existing_atom"));
+        let span = atom_violation.span.clone().expect("Regex rules record what they matched");
+        assert!(atom_violation.content[span].starts_with("String.to_atom"));
+    }
+
+    #[test]
+    fn test_suppression_comments_silence_violations() {
+        let engine = ReviewEngine::new();
+        let changed_line = |line_number: usize, content: &str, line_above: &str| ChangedLine {
+            line_number,
+            content: content.to_string(),
+            change_type: ChangeType::Added,
+            context_before: vec![line_above.to_string()],
+            context_after: vec![],
+        };
+        let changed_lines = vec![
+            changed_line(10, "a = String.to_atom(a) # patingin-ignore: dynamic_atom_creation", ""),
+            changed_line(12, "b = String.to_atom(b)", "# patingin-ignore-next-line"),
+            changed_line(14, "c = String.to_atom(c) // patingin-ignore", ""),
+        ];
+
+        let violations = engine.review_changed_lines("lib/user.ex", &changed_lines).unwrap();
+        let atom_lines: Vec<usize> = violations
+            .iter()
+            .filter(|v| v.rule.id == "dynamic_atom_creation")
+            .map(|v| v.line_number)
+            .collect();
+
+        // `//` is not an Elixir comment, so line 14 is still reported
+        assert_eq!(atom_lines, vec![14]);
     }
 
     #[test]
@@ -397,6 +1415,12 @@ index 1234567..abcdefg 100644
             ("src/main.rs", Language::Rust),
             ("lib/math.zig", Language::Zig),
             ("migrations/001_users.sql", Language::Sql),
+            ("app/models/user.rb", Language::Ruby),
+            ("app/views/users/show.html.erb", Language::Ruby),
+            ("src/Controller/UserController.php", Language::Php),
+            ("src/main/java/App.java", Language::Java),
+            ("src/main/kotlin/App.kt", Language::Kotlin),
+            ("build.gradle.kts", Language::Kotlin),
         ];
 
         for (file_path, expected_lang) in test_files {
@@ -433,6 +1457,10 @@ index 1234567..abcdefg 100644
                 claude_code_fixable: true,
                 examples: vec![],
                 tags: vec![],
+                ai_hint: None,
+                auto_fix: None,
+                skip_comments: false,
+                skip_strings: false,
                 enabled: true,
             },
             file_path: "test.ex".to_string(),
@@ -445,6 +1473,10 @@ index 1234567..abcdefg 100644
             context_before: vec![],
             context_after: vec![],
             confidence: 0.9,
+            span: None,
+            blame: None,
+            captures: BTreeMap::new(),
+            owners: Vec::new(),
         }];
 
         let summary = engine.create_review_summary(&violations);
@@ -454,4 +1486,622 @@ index 1234567..abcdefg 100644
         assert_eq!(summary.auto_fixable_count, 1);
         assert_eq!(summary.files_affected, vec!["test.ex"]);
     }
+
+    fn added_line(line_number: usize, content: &str) -> ChangedLine {
+        ChangedLine {
+            line_number,
+            content: content.to_string(),
+            change_type: ChangeType::Added,
+            context_before: vec![],
+            context_after: vec![],
+        }
+    }
+
+    #[test]
+    fn test_long_line_uses_display_width() {
+        let engine = ReviewEngine::new();
+        // 60 wide characters take 120 columns...
+        let wide = format!("x = \"{}\"", "日".repeat(60));
+        // ...while 80 two-byte characters take 80 columns despite being 160 bytes
+        let accented = format!("x = \"{}\"", "é".repeat(80));
+
+        let violations = engine
+            .review_changed_lines("app/text.py", &[added_line(1, &wide), added_line(2, &accented)])
+            .unwrap();
+        let long_lines: Vec<_> =
+            violations.iter().filter(|v| v.rule.id == "python_long_line").collect();
+
+        assert_eq!(long_lines.len(), 1);
+        assert_eq!(long_lines[0].line_number, 1);
+        assert!(long_lines[0].fix_suggestion.starts_with("Line is 126 columns wide (limit 88)"));
+    }
+
+    #[test]
+    fn test_formatting_overrides_apply_per_path() {
+        let formatting: FormattingConfig = serde_yaml::from_str(
+            "max_indent_depth: 2\npaths:\n  - glob: \"legacy/**\"\n    max_indent_depth: 10\n",
+        )
+        .unwrap();
+        let engine = ReviewEngine::new().with_formatting(formatting);
+        let nested = [added_line(7, "\t\t\treturn value")];
+
+        let strict = engine.review_changed_lines("src/lib.rs", &nested).unwrap();
+        let deep = strict.iter().find(|v| v.rule.id == "rust_deep_indentation").unwrap();
+        assert!(deep.fix_suggestion.starts_with("Nested 3 levels deep (limit 2)"));
+
+        let relaxed = engine.review_changed_lines("legacy/old.rs", &nested).unwrap();
+        assert!(relaxed.iter().all(|v| v.rule.id != "rust_deep_indentation"));
+    }
+
+    #[test]
+    fn test_tag_gates_silence_rules_only_when_every_gated_tag_is_off() {
+        let line = [added_line(1, "atom = String.to_atom(input)")];
+        let reviewed = |gates: &str| {
+            let engine = ReviewEngine::new().with_gates(serde_yaml::from_str(gates).unwrap());
+            engine.review_changed_lines("lib/user.ex", &line).unwrap()
+        };
+
+        // dynamic_atom_creation is tagged security and memory
+        let silenced = reviewed("security: off\nmemory: off\n");
+        assert!(silenced.iter().all(|v| v.rule.id != "dynamic_atom_creation"));
+
+        let reported = reviewed("security: off\nmemory: report\n");
+        assert!(reported.iter().any(|v| v.rule.id == "dynamic_atom_creation"));
+    }
+
+    #[test]
+    fn test_violation_fingerprint_ignores_line_number() {
+        let engine = ReviewEngine::new();
+        let line = |line_number: usize, content: &str| ChangedLine {
+            line_number,
+            content: content.to_string(),
+            change_type: ChangeType::Added,
+            context_before: vec![],
+            context_after: vec![],
+        };
+
+        let first = engine
+            .review_changed_lines("lib/user.ex", &[line(3, "atom = String.to_atom(input)")])
+            .unwrap();
+        let moved = engine
+            .review_changed_lines("lib/user.ex", &[line(40, "  atom = String.to_atom(input)")])
+            .unwrap();
+        let other_file = engine
+            .review_changed_lines("lib/auth.ex", &[line(3, "atom = String.to_atom(input)")])
+            .unwrap();
+
+        let fingerprint = first[0].fingerprint();
+        assert_eq!(fingerprint.len(), 16);
+        assert_eq!(fingerprint, moved[0].fingerprint());
+        assert_ne!(fingerprint, other_file[0].fingerprint());
+    }
+
+    #[test]
+    fn test_filter_violations_by_severity_keeps_more_severe() {
+        let engine = ReviewEngine::new();
+        let changed_lines = vec![
+            ChangedLine {
+                line_number: 1,
+                content: "atom = String.to_atom(input)".to_string(),
+                change_type: ChangeType::Added,
+                context_before: vec![],
+                context_after: vec![],
+            },
+            ChangedLine {
+                line_number: 2,
+                content: "def run(a, b, c, d, e, f) do".to_string(),
+                change_type: ChangeType::Added,
+                context_before: vec![],
+                context_after: vec![],
+            },
+        ];
+        let violations = engine.review_changed_lines("lib/user.ex", &changed_lines).unwrap();
+
+        let critical_only = engine.filter_violations_by_severity(&violations, Severity::Critical);
+        assert!(!critical_only.is_empty());
+        assert!(critical_only.iter().all(|v| v.severity == Severity::Critical));
+
+        let major_and_up = engine.filter_violations_by_severity(&violations, Severity::Major);
+        assert!(major_and_up.iter().any(|v| v.severity == Severity::Major));
+        assert!(major_and_up.iter().all(|v| v.severity != Severity::Warning));
+    }
+
+    #[test]
+    fn test_review_git_diff_streaming_matches_batch() {
+        let diff_output = r#"diff --git a/lib/user.ex b/lib/user.ex
+index 1234567..abcdefg 100644
+--- a/lib/user.ex
++++ b/lib/user.ex
+@@ -10,7 +10,8 @@ defmodule User do
+   def create_user(name) do
++    atom = String.to_atom(name)
++    other = String.to_atom(dynamic_name)
+   end
+diff --git a/src/app.js b/src/app.js
+index 1234567..abcdefg 100644
+--- a/src/app.js
++++ b/src/app.js
+@@ -1,3 +1,4 @@
++console.log('debug');
+ export default app;"#;
+
+        let git_diff = GitDiffParser::parse(diff_output).expect("Should parse diff");
+        let engine = ReviewEngine::new();
+
+        let batch = engine.review_git_diff(&git_diff).expect("Should review diff");
+
+        let mut streamed = Vec::new();
+        let mut files = Vec::new();
+        let summary = engine
+            .review_git_diff_streaming(&git_diff, |file_diff, violations| {
+                files.push(file_diff.path.clone());
+                streamed.extend(violations.iter().map(|v| (v.file_path.clone(), v.line_number)));
+                Ok(())
+            })
+            .expect("Should stream review");
+
+        assert_eq!(files.len(), git_diff.files.len());
+        assert_eq!(streamed.len(), batch.violations.len());
+        assert_eq!(summary.total_violations, batch.summary.total_violations);
+        assert_eq!(summary.critical_count, batch.summary.critical_count);
+        assert_eq!(summary.files_affected.len(), batch.summary.files_affected.len());
+    }
+
+    #[test]
+    fn test_symlinks_and_lfs_pointers_skip_content_rules() {
+        let diff_output = r#"diff --git a/lib/user.ex b/lib/user.ex
+old mode 100644
+new mode 120000
+index 1234567..abcdefg
+--- a/lib/user.ex
++++ b/lib/user.ex
+@@ -1 +1 @@
+-defmodule User do
++String.to_atom(x)
+diff --git a/lib/data.ex b/lib/data.ex
+index 1234567..abcdefg 100644
+--- a/lib/data.ex
++++ b/lib/data.ex
+@@ -1,3 +1,3 @@
+ version https://git-lfs.github.com/spec/v1
+-oid sha256:aaaa
++oid sha256:String.to_atom(x)
+ size 10"#;
+        let git_diff = GitDiffParser::parse(diff_output).expect("Should parse diff");
+
+        let result = ReviewEngine::new().review_git_diff(&git_diff).unwrap();
+        let rules: Vec<(&str, &str)> =
+            result.violations.iter().map(|v| (v.file_path.as_str(), v.rule.id.as_str())).collect();
+        assert_eq!(rules, [("lib/user.ex", "symlink_replaces_file")]);
+
+        let allowed = ReviewEngine::new()
+            .with_symlinks(SymlinkPolicy { allowed_dirs: vec!["lib".to_string()] })
+            .review_git_diff(&git_diff)
+            .unwrap();
+        assert!(allowed.violations.is_empty());
+        assert_eq!(allowed.skipped[0].reason, SkipReason::AllowedSymlink);
+        assert_eq!(allowed.summary.skipped[&SkipReason::AllowedSymlink], 1);
+    }
+
+    #[test]
+    fn test_summary_counts_every_severity() {
+        let engine = ReviewEngine::new();
+        let violations: Vec<ReviewViolation> = Severity::ALL
+            .into_iter()
+            .map(|severity| crate::report::test_support::violation("a.ex", 1, severity))
+            .collect();
+
+        let summary = engine.create_review_summary(&violations);
+        assert!(Severity::ALL.into_iter().all(|severity| summary.count(severity) == 1));
+        assert!(Severity::Blocker.is_at_least(Severity::Critical));
+        assert!(!Severity::Info.is_at_least(Severity::Warning));
+        let kept: Vec<Severity> = engine
+            .filter_violations_by_severity(&violations, Severity::Major)
+            .iter()
+            .map(|violation| violation.severity)
+            .collect();
+        assert_eq!(kept, [Severity::Blocker, Severity::Critical, Severity::Major]);
+    }
+
+    #[test]
+    fn test_cached_results_are_reused_until_the_rules_change() {
+        let diff_output = "diff --git a/lib/user.ex b/lib/user.ex
+index 1234567..abcdefg 100644
+--- a/lib/user.ex
++++ b/lib/user.ex
+@@ -1,0 +1,2 @@
++a = String.to_atom(a) # patingin-ignore
++b = String.to_atom(b)";
+        let git_diff = GitDiffParser::parse(diff_output).expect("Should parse diff");
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let engine = ReviewEngine::new().with_source_root(temp_dir.path());
+        let key = engine.cache_key().unwrap();
+        let cached_engine = || {
+            let cache = ResultCache::open(temp_dir.path(), &key).unwrap();
+            ReviewEngine::new().with_source_root(temp_dir.path()).with_cache(cache)
+        };
+
+        let uncached = engine.review_git_diff(&git_diff).unwrap();
+        let first = cached_engine().review_git_diff(&git_diff).unwrap();
+        assert_eq!(first.violations.len(), uncached.violations.len());
+        assert_eq!(first.skipped.len(), 1);
+
+        // A second run reads the stored entry instead of analyzing the file
+        let entries: Vec<PathBuf> = std::fs::read_dir(temp_dir.path().join(&key))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(entries.len(), 1);
+        std::fs::write(&entries[0], r#"{"violations": [], "skipped": []}"#).unwrap();
+        let second = cached_engine().review_git_diff(&git_diff).unwrap();
+        assert!(second.violations.is_empty() && second.skipped.is_empty());
+
+        let mut changed = ReviewEngine::new();
+        changed.registry.apply_rule_settings(&crate::config::RuleSettings {
+            disabled: vec!["dynamic_atom_creation".to_string()],
+            ..Default::default()
+        });
+        assert_ne!(changed.cache_key().unwrap(), key);
+    }
+
+    #[test]
+    fn test_skipped_findings_are_counted_by_reason() {
+        let diff_output = "diff --git a/lib/user.ex b/lib/user.ex
+index 1234567..abcdefg 100644
+--- a/lib/user.ex
++++ b/lib/user.ex
+@@ -1,0 +1,3 @@
++a = String.to_atom(a) # patingin-ignore
++b = String.to_atom(b)
++c = String.to_atom(c)";
+        let git_diff = GitDiffParser::parse(diff_output).expect("Should parse diff");
+        let mut result = ReviewEngine::new().review_git_diff(&git_diff).unwrap();
+        let atom_lines = |violations: &[ReviewViolation]| -> Vec<usize> {
+            violations
+                .iter()
+                .filter(|v| v.rule.id == "dynamic_atom_creation")
+                .map(|v| v.line_number)
+                .collect()
+        };
+        assert_eq!(atom_lines(&result.violations), [2, 3]);
+        assert_eq!(result.skipped[0].violation.line_number, 1);
+        assert_eq!(result.skipped[0].reason, SkipReason::InlineSuppression);
+
+        let total = result.summary.total_violations;
+        assert_eq!(result.skip(SkipReason::OlderThan, |v| v.line_number == 3), 1);
+        assert_eq!(atom_lines(&result.violations), [2]);
+        assert_eq!(result.summary.total_violations, total - 1);
+        assert_eq!(
+            result.summary.skipped.iter().collect::<Vec<_>>(),
+            [(&SkipReason::InlineSuppression, &1), (&SkipReason::OlderThan, &1)]
+        );
+    }
+
+    #[test]
+    fn test_large_hunks_are_sampled_against_critical_rules() {
+        let added: String = (0..10).map(|_| "+    atom = String.to_atom(name) # set\n").collect();
+        let diff_output = format!(
+            "diff --git a/lib/gen.ex b/lib/gen.ex\n\
+             index 1234567..abcdefg 100644\n\
+             --- a/lib/gen.ex\n\
+             +++ b/lib/gen.ex\n\
+             @@ -0,0 +1,10 @@\n{added}"
+        );
+        let git_diff = GitDiffParser::parse(&diff_output).expect("Should parse diff");
+
+        let full = ReviewEngine::new().review_git_diff(&git_diff).unwrap();
+        assert!(full.violations.len() >= 10);
+
+        let engine = ReviewEngine::new()
+            .with_large_hunks(LargeHunkPolicy { max_added_lines: 4, sample_lines: 5 });
+        let sampled = engine.review_git_diff(&git_diff).unwrap();
+        let lines: Vec<usize> = sampled.violations.iter().map(|v| v.line_number).collect();
+
+        assert_eq!(lines, [1, 3, 5, 7, 9]);
+        assert!(sampled.violations.iter().all(|v| v.severity == Severity::Critical));
+        assert_eq!(
+            engine.partial_hunks(&git_diff),
+            [PartialHunk {
+                file_path: "lib/gen.ex".to_string(),
+                first_line: 1,
+                last_line: 10,
+                added_lines: 10,
+                checked_lines: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_rules_can_skip_matches_in_comments_and_strings() {
+        let diff_output = "diff --git a/src/app.js b/src/app.js
+index 1234567..abcdefg 100644
+--- a/src/app.js
++++ b/src/app.js
+@@ -1,0 +1,7 @@
++// console.log(user)
++console.log(user) // debugging
++/*
++  console.log(session)
++*/
++show(\"a == b\"); if (a == b) {}
++show(\"a == b\");";
+        let git_diff = GitDiffParser::parse(diff_output).expect("Should parse diff");
+        let result = ReviewEngine::new().review_git_diff(&git_diff).unwrap();
+        let found: Vec<_> =
+            result.violations.iter().map(|v| (v.rule.id.as_str(), v.line_number)).collect();
+        // A rule reports its first match outside the regions it skips
+        assert_eq!(found, [("console_log_production", 2), ("double_equals", 6)]);
+        let skipped: Vec<_> =
+            result.skipped.iter().map(|s| (s.violation.line_number, s.reason)).collect();
+        assert_eq!(
+            skipped,
+            [(1, SkipReason::InComment), (4, SkipReason::InComment), (7, SkipReason::InString)]
+        );
+
+        // Lexing the whole file finds the comment an added line sits in
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        let content = "/*\n * Usage:\n *\n *\n *\n *\n   console.log(user)\n */\n";
+        std::fs::write(temp_dir.path().join("src/app.js"), content).unwrap();
+        let diff_output = "diff --git a/src/app.js b/src/app.js
+index 1234567..abcdefg 100644
+--- a/src/app.js
++++ b/src/app.js
+@@ -6,0 +7,1 @@
++   console.log(user)";
+        let git_diff = GitDiffParser::parse(diff_output).expect("Should parse diff");
+        let from_diff = ReviewEngine::new().review_git_diff(&git_diff).unwrap();
+        assert_eq!(from_diff.violations.len(), 1);
+        let from_file = ReviewEngine::new()
+            .with_source_root(temp_dir.path())
+            .review_git_diff(&git_diff)
+            .unwrap();
+        assert!(from_file.violations.is_empty());
+        assert_eq!(from_file.skipped[0].reason, SkipReason::InComment);
+    }
+
+    #[test]
+    fn test_long_functions_are_measured_from_the_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let body: String = (0..70).map(|i| format!("    value_{i} = {i}\n")).collect();
+        let content =
+            format!("defmodule Gen do\n  def short, do: :ok\n\n  def long do\n{body}  end\nend\n");
+        std::fs::create_dir_all(temp_dir.path().join("lib")).unwrap();
+        std::fs::write(temp_dir.path().join("lib/gen.ex"), &content).unwrap();
+
+        let diff_for = |line: usize, added: &str| {
+            format!(
+                "diff --git a/lib/gen.ex b/lib/gen.ex\n\
+                 index 1234567..abcdefg 100644\n\
+                 --- a/lib/gen.ex\n\
+                 +++ b/lib/gen.ex\n\
+                 @@ -{line},0 +{line},1 @@\n+{added}\n"
+            )
+        };
+        let engine = ReviewEngine::new().with_source_root(temp_dir.path());
+        let long_functions = |diff: &str| -> Vec<(usize, String)> {
+            let git_diff = GitDiffParser::parse(diff).expect("Should parse diff");
+            let result = engine.review_git_diff(&git_diff).unwrap();
+            result
+                .violations
+                .iter()
+                .filter(|v| v.rule.id == "elixir_long_function")
+                .map(|v| (v.line_number, v.fix_suggestion.clone()))
+                .collect()
+        };
+
+        // A change inside the body reports the function at its definition
+        let inside = long_functions(&diff_for(40, "    value_35 = 35"));
+        assert_eq!(inside.len(), 1);
+        assert_eq!(inside[0].0, 4);
+        assert!(inside[0].1.starts_with("Spans 72 lines (limit 60)."));
+
+        // Changes outside it, or a diff the file no longer matches, report nothing
+        assert!(long_functions(&diff_for(2, "  def short, do: :ok")).is_empty());
+        assert!(long_functions(&diff_for(40, "    changed = true")).is_empty());
+
+        std::fs::write(
+            temp_dir.path().join("lib/gen.ex"),
+            content.replace(
+                "  def long do",
+                "  # patingin-ignore-next-line: elixir_long_function\n  def long do",
+            ),
+        )
+        .unwrap();
+        assert!(long_functions(&diff_for(41, "    value_35 = 35")).is_empty());
+    }
+
+    #[test]
+    fn test_multiline_rules_match_the_whole_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let content = "defmodule Gen do\n  def fetch(url) do\n    HTTPoison.get(url)\n  end\nend\n";
+        std::fs::create_dir_all(temp_dir.path().join("lib")).unwrap();
+        std::fs::write(temp_dir.path().join("lib/gen.ex"), content).unwrap();
+
+        let mut engine = ReviewEngine::new().with_source_root(temp_dir.path());
+        let rule = |id: &str, pattern: &str, unless: Option<&str>, window: Option<usize>| {
+            let mut rule = engine.registry.get_pattern("dynamic_atom_creation").unwrap().clone();
+            rule.id = id.to_string();
+            rule.detection_method = DetectionMethod::Multiline {
+                pattern: pattern.to_string(),
+                unless: unless.map(str::to_string),
+                window,
+            };
+            rule
+        };
+        let rules = [
+            rule("missing_moduledoc", r"^defmodule .*$", Some("@moduledoc"), None),
+            rule("bare_get", r"def \w+\(.*\) do\n\s*HTTPoison\.get", None, None),
+            rule("unhandled_get", r"HTTPoison\.get", Some(r"rescue|\{:error"), Some(1)),
+        ];
+        for rule in rules {
+            engine.registry.add_pattern(rule);
+        }
+        engine.registry.compile_all_patterns().unwrap();
+
+        let found = |line: usize, added: &str| -> Vec<(String, usize)> {
+            let diff = format!(
+                "diff --git a/lib/gen.ex b/lib/gen.ex\n\
+                 index 1234567..abcdefg 100644\n\
+                 --- a/lib/gen.ex\n\
+                 +++ b/lib/gen.ex\n\
+                 @@ -{line},0 +{line},1 @@\n+{added}\n"
+            );
+            let git_diff = GitDiffParser::parse(&diff).expect("Should parse diff");
+            let result = engine.review_git_diff(&git_diff).unwrap();
+            result.violations.iter().map(|v| (v.rule.id.clone(), v.line_number)).collect()
+        };
+
+        // A match spanning lines is reported at its first line
+        let matches = found(3, "    HTTPoison.get(url)");
+        assert_eq!(
+            matches,
+            vec![
+                ("missing_moduledoc".to_string(), 1),
+                ("bare_get".to_string(), 2),
+                ("unhandled_get".to_string(), 3),
+            ]
+        );
+
+        // An added line outside the match or its window only triggers whole-file rules
+        assert_eq!(found(5, "end"), vec![("missing_moduledoc".to_string(), 1)]);
+
+        // `unless` within the window clears the match
+        let handled = content.replace("  end\nend", "  rescue\n    e -> {:error, e}\n  end\nend");
+        std::fs::write(
+            temp_dir.path().join("lib/gen.ex"),
+            format!("  @moduledoc false\n{handled}"),
+        )
+        .unwrap();
+        assert_eq!(found(4, "    HTTPoison.get(url)"), vec![("bare_get".to_string(), 3)]);
+    }
+
+    #[test]
+    fn test_file_coverage_lists_every_file() {
+        let engine = ReviewEngine::new();
+        let mut trimmed = FileDiff::from_region("lib/old.ex", "gone\n", 1..=1);
+        trimmed.added_lines.clear();
+        let mut deleted = FileDiff::from_region("lib/gone.ex", "String.to_atom(x)\n", 1..=1);
+        deleted.kind = FileKind::Deleted;
+        let mut binary = FileDiff::from_region("assets/icon.png", "\u{89}PNG\n", 1..=1);
+        binary.kind = FileKind::Binary;
+        let git_diff = GitDiff {
+            files: vec![
+                FileDiff::from_region("lib/user.ex", "String.to_atom(input)\n", 1..=1),
+                FileDiff::from_region("lib/clean.ex", "x = 1\n", 1..=1),
+                trimmed,
+                deleted,
+                binary,
+            ],
+            parse_warnings: vec![crate::git::ParseWarning {
+                path: Some("assets/logo.png".to_string()),
+                message: "binary file; not reviewed".to_string(),
+            }],
+        };
+        let violations = engine.review_git_diff(&git_diff).unwrap().violations;
+        let coverage = engine.file_coverage(
+            &git_diff,
+            &[("app.js".to_string(), FileSkipReason::LanguageFilter)],
+            &violations,
+        );
+
+        let listed: Vec<(&str, &FileStatus, usize)> = coverage
+            .iter()
+            .map(|file| (file.path.as_str(), &file.status, file.violations))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("app.js", &FileStatus::Skipped(FileSkipReason::LanguageFilter), 0),
+                ("assets/icon.png", &FileStatus::Skipped(FileSkipReason::Binary), 0),
+                ("assets/logo.png", &FileStatus::Skipped(FileSkipReason::Unparsed), 0),
+                ("lib/clean.ex", &FileStatus::Analyzed, 0),
+                ("lib/gone.ex", &FileStatus::Skipped(FileSkipReason::Deleted), 0),
+                ("lib/old.ex", &FileStatus::Skipped(FileSkipReason::NoAddedLines), 0),
+                ("lib/user.ex", &FileStatus::Analyzed, violations.len()),
+            ]
+        );
+        assert!(!violations.is_empty());
+        assert_eq!(coverage[0].language, Some(Language::JavaScript));
+    }
+
+    #[test]
+    fn test_interpolate_captures() {
+        let regex = Regex::new(r"String\.to_atom\((?P<arg>\w+)\)(\.x)?").unwrap();
+        let captures = regex.captures("String.to_atom(user_input)").unwrap();
+        let fill = |template| interpolate_captures(template, &regex, &captures);
+
+        assert_eq!(
+            fill("Replace `String.to_atom($1)` with `String.to_existing_atom(${arg})`"),
+            "Replace `String.to_atom(user_input)` with `String.to_existing_atom(user_input)`"
+        );
+        assert_eq!(fill("costs $$5, [$2]"), "costs $5, []");
+        assert_eq!(fill("$7 ${missing} ${unclosed $ end$"), "$7 ${missing} ${unclosed $ end$");
+    }
+
+    #[test]
+    fn test_fix_suggestion_names_offending_code() {
+        let engine = ReviewEngine::new();
+        let pattern = AntiPattern {
+            id: "to_atom_capture".to_string(),
+            name: "Dynamic Atom".to_string(),
+            language: Language::Elixir,
+            severity: Severity::Critical,
+            description: "Test".to_string(),
+            detection_method: DetectionMethod::Regex {
+                pattern: r"String\.to_atom\((\w+)\)".to_string(),
+            },
+            fix_suggestion: "Replace `String.to_atom($1)` with `String.to_existing_atom($1)`"
+                .to_string(),
+            source_url: None,
+            claude_code_fixable: true,
+            examples: vec![],
+            tags: vec![],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
+            enabled: true,
+        };
+
+        let violation = engine
+            .check_line_against_pattern(
+                "lib/user.ex",
+                &added_line(3, "atom = String.to_atom(params)"),
+                &pattern,
+                Language::Elixir,
+                &|_| false,
+            )
+            .unwrap()
+            .expect("Should match");
+
+        assert_eq!(
+            violation.fix_suggestion,
+            "Replace `String.to_atom(params)` with `String.to_existing_atom(params)`"
+        );
+        assert_eq!(violation.rule.fix_suggestion, pattern.fix_suggestion);
+        assert_eq!(violation.captures, BTreeMap::from([("1".to_string(), "params".to_string())]));
+
+        // Captures are kept when the suggestion does not use them; unused groups are left out
+        let unreferenced = AntiPattern {
+            detection_method: DetectionMethod::Regex {
+                pattern: r"String\.to_atom\((?P<arg>\w+)\)(\.x)?".to_string(),
+            },
+            fix_suggestion: "Use String.to_existing_atom/1".to_string(),
+            ..pattern
+        };
+        let violation = engine
+            .check_line_against_pattern(
+                "lib/user.ex",
+                &added_line(3, "atom = String.to_atom(params)"),
+                &unreferenced,
+                Language::Elixir,
+                &|_| false,
+            )
+            .unwrap()
+            .expect("Should match");
+        let captured: Vec<_> =
+            violation.captures.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(captured, [("1", "params"), ("arg", "params")]);
+    }
 }
@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+use super::provenance::RuleSource;
+use super::{Language, Severity};
+
+/// What to do when a project rule has the same id and language as a rule already loaded,
+/// set with `rule_conflicts:` in the project config
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictPolicy {
+    /// Refuse to review until the ids are made unique
+    Error,
+    /// The project rule replaces the earlier one
+    #[default]
+    PreferCustom,
+    /// The earlier rule stays and the project rule is dropped
+    PreferBuiltin,
+    /// Both are kept; the project rule gets the next free `<id>_2`, `<id>_3`, ...
+    Rename,
+}
+
+impl ConflictPolicy {
+    /// Read `rule_conflicts:` from a project config file; absent means `prefer-custom`
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct RuleConflictsSection {
+            #[serde(default)]
+            rule_conflicts: ConflictPolicy,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<RuleConflictsSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.map(|section| section.rule_conflicts).unwrap_or_default())
+    }
+}
+
+impl std::fmt::Display for ConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictPolicy::Error => write!(f, "error"),
+            ConflictPolicy::PreferCustom => write!(f, "prefer-custom"),
+            ConflictPolicy::PreferBuiltin => write!(f, "prefer-builtin"),
+            ConflictPolicy::Rename => write!(f, "rename"),
+        }
+    }
+}
+
+/// Where the rule a project rule collided with came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleOrigin {
+    BuiltIn,
+    /// An earlier project rule with the same id
+    Project,
+}
+
+impl std::fmt::Display for RuleOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleOrigin::BuiltIn => write!(f, "built-in rule"),
+            RuleOrigin::Project => write!(f, "earlier project rule"),
+        }
+    }
+}
+
+/// How a collision was resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// Nothing was loaded; the policy is `error`
+    Refused,
+    KeptCustom,
+    KeptExisting,
+    Renamed(String),
+}
+
+/// A project rule whose id and language were already taken, and what the policy did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleConflict {
+    pub id: String,
+    pub language: Language,
+    pub existing: RuleOrigin,
+    pub existing_severity: Severity,
+    pub custom_severity: Severity,
+    pub resolution: Resolution,
+}
+
+impl std::fmt::Display for RuleConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (id, language, existing) = (&self.id, &self.language, self.existing);
+        let (existing_severity, custom_severity) = (self.existing_severity, self.custom_severity);
+        match &self.resolution {
+            Resolution::Refused => write!(f, "{id} ({language}) is already a {existing}"),
+            Resolution::KeptCustom => write!(
+                f,
+                "{id} ({language}): the project rule ({custom_severity}) replaced the \
+                 {existing} ({existing_severity})"
+            ),
+            Resolution::KeptExisting => write!(
+                f,
+                "{id} ({language}): the {existing} ({existing_severity}) was kept and the \
+                 project rule ({custom_severity}) dropped"
+            ),
+            Resolution::Renamed(new_id) => write!(
+                f,
+                "{id} ({language}): the project rule ({custom_severity}) was loaded as \
+                 {new_id}, next to the {existing} ({existing_severity})"
+            ),
+        }
+    }
+}
+
+/// A loaded rule that a later rule with the same id and language replaced, e.g. a built-in
+/// rule redefined by an installed pack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShadowedRule {
+    pub id: String,
+    pub language: Language,
+    /// Where the replaced rule came from; `None` for rules added without a source
+    pub shadowed: Option<RuleSource>,
+    /// Where the rule replacing it came from
+    pub by: Option<RuleSource>,
+}
+
+/// Project rules that collided under the `error` policy
+#[derive(Debug)]
+pub struct RuleConflicts(pub Vec<RuleConflict>);
+
+impl std::fmt::Display for RuleConflicts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} project rule id conflict(s):", self.0.len())?;
+        for conflict in &self.0 {
+            write!(f, "\n  • {conflict}")?;
+        }
+        write!(
+            f,
+            "\nRename the rules, or set `rule_conflicts:` in patingin.yml to prefer-custom, \
+             prefer-builtin or rename"
+        )
+    }
+}
+
+impl std::error::Error for RuleConflicts {}
+
+#[cfg(test)]
+mod rule_conflicts_tests {
+    use super::*;
+
+    #[test]
+    fn test_load_policy() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+
+        std::fs::write(&config_path, "rule_conflicts: prefer-builtin\n").unwrap();
+        assert_eq!(ConflictPolicy::load(&config_path).unwrap(), ConflictPolicy::PreferBuiltin);
+
+        std::fs::write(&config_path, "gates:\n  security: critical\n").unwrap();
+        assert_eq!(ConflictPolicy::load(&config_path).unwrap(), ConflictPolicy::PreferCustom);
+
+        std::fs::write(&config_path, "rule_conflicts: overwrite\n").unwrap();
+        assert!(ConflictPolicy::load(&config_path).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+use regex::{Regex, RegexBuilder};
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::provenance::RuleSource;
+use super::registry::PatternRegistry;
+use super::rule_conflicts::{Resolution, RuleConflict, RuleOrigin, ShadowedRule};
+use super::suppression::Suppression;
+use super::{AntiPattern, DetectionMethod, Language};
+use crate::config::RuleSettings;
+
+/// Something wrong with the loaded rules, found by `patingin rules doctor`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleProblem {
+    pub rule_id: String,
+    /// The language variant the problem is about; `None` for problems with every variant
+    pub language: Option<Language>,
+    pub kind: ProblemKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProblemKind {
+    /// Defined more than once by one source; only one definition is used
+    DuplicateId { source: String },
+    /// Replaced by the rule with the same id and language from another source
+    Shadowed { shadowed: String, by: String },
+    /// A pattern that does not compile, or matches none of the rule's own bad examples
+    NeverMatches { reason: String },
+    /// Disabled, yet a setting, baseline entry or suppression comment still names it
+    DisabledButReferenced { by: String },
+    /// A setting, baseline entry or suppression comment naming no loaded rule
+    UnknownRule { by: String },
+}
+
+impl ProblemKind {
+    /// Heading of the doctor report section listing problems of this kind
+    pub fn heading(&self) -> &'static str {
+        match self {
+            ProblemKind::DuplicateId { .. } => "🔁 Duplicate ids",
+            ProblemKind::Shadowed { .. } => "🌗 Shadowed rules",
+            ProblemKind::NeverMatches { .. } => "🚫 Never-matching patterns",
+            ProblemKind::DisabledButReferenced { .. } => "💤 Disabled but referenced",
+            ProblemKind::UnknownRule { .. } => "❓ Unknown rules referenced",
+        }
+    }
+}
+
+impl std::fmt::Display for ProblemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProblemKind::DuplicateId { source } => {
+                write!(f, "defined more than once in {source}; only one definition is used")
+            }
+            ProblemKind::Shadowed { shadowed, by } => {
+                write!(f, "the rule from {shadowed} is replaced by the one from {by}")
+            }
+            ProblemKind::NeverMatches { reason } => write!(f, "{reason}"),
+            ProblemKind::DisabledButReferenced { by } => write!(f, "disabled, but named by {by}"),
+            ProblemKind::UnknownRule { by } => write!(f, "no such rule, but named by {by}"),
+        }
+    }
+}
+
+impl std::fmt::Display for RuleProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.language {
+            Some(language) => write!(f, "{} ({language}): {}", self.rule_id, self.kind),
+            None => write!(f, "{}: {}", self.rule_id, self.kind),
+        }
+    }
+}
+
+/// A place outside the rules that names a rule by id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleReference {
+    pub rule_id: String,
+    /// Where, e.g. "severity in patingin.yml"
+    pub by: String,
+    /// Whether the reference disables the rule, so it is only a problem when the id is
+    /// unknown
+    pub disables: bool,
+}
+
+fn describe(source: Option<&RuleSource>) -> String {
+    source.map_or_else(|| "a rule added without a source".to_string(), ToString::to_string)
+}
+
+/// Rule ids taken twice: the replacements the registry recorded while loading, and the
+/// collisions of project rules that `rule_conflicts:` resolved without replacing anything
+pub fn find_id_collisions(
+    shadowed_rules: &[ShadowedRule],
+    conflicts: &[RuleConflict],
+) -> Vec<RuleProblem> {
+    let mut problems = Vec::new();
+    for shadowed in shadowed_rules {
+        let kind = if shadowed.shadowed == shadowed.by {
+            ProblemKind::DuplicateId { source: describe(shadowed.by.as_ref()) }
+        } else {
+            ProblemKind::Shadowed {
+                shadowed: describe(shadowed.shadowed.as_ref()),
+                by: describe(shadowed.by.as_ref()),
+            }
+        };
+        problems.push(RuleProblem {
+            rule_id: shadowed.id.clone(),
+            language: Some(shadowed.language.clone()),
+            kind,
+        });
+    }
+
+    for conflict in conflicts {
+        let kind = match (conflict.existing, &conflict.resolution) {
+            (RuleOrigin::Project, _) => {
+                ProblemKind::DuplicateId { source: "the project rules".to_string() }
+            }
+            // A replaced rule was recorded above, and a renamed one replaces nothing
+            (RuleOrigin::BuiltIn, Resolution::KeptCustom | Resolution::Renamed(_)) => continue,
+            (RuleOrigin::BuiltIn, Resolution::KeptExisting) => ProblemKind::Shadowed {
+                shadowed: "the project rules".to_string(),
+                by: "the rule loaded before it (rule_conflicts: prefer-builtin)".to_string(),
+            },
+            (RuleOrigin::BuiltIn, Resolution::Refused) => ProblemKind::Shadowed {
+                shadowed: "the project rules".to_string(),
+                by: "the rule loaded before it (rule_conflicts: error)".to_string(),
+            },
+        };
+        problems.push(RuleProblem {
+            rule_id: conflict.id.clone(),
+            language: Some(conflict.language.clone()),
+            kind,
+        });
+    }
+    problems
+}
+
+/// The user's project rules that team rules of the same id and language replace
+pub fn find_team_shadowing(
+    user_rules: &[AntiPattern],
+    team_rules: &[AntiPattern],
+    user_source: &RuleSource,
+    team_source: &RuleSource,
+) -> Vec<RuleProblem> {
+    user_rules
+        .iter()
+        .filter(|rule| {
+            team_rules.iter().any(|team| team.id == rule.id && team.language == rule.language)
+        })
+        .map(|rule| RuleProblem {
+            rule_id: rule.id.clone(),
+            language: Some(rule.language.clone()),
+            kind: ProblemKind::Shadowed {
+                shadowed: user_source.to_string(),
+                by: team_source.to_string(),
+            },
+        })
+        .collect()
+}
+
+/// Why `rule` can never report anything: a pattern that does not compile, or one matching
+/// none of the rule's bad examples. Rules without examples are only checked to compile.
+fn never_matches(rule: &AntiPattern) -> Option<String> {
+    let (pattern, unless, whole_file) = match &rule.detection_method {
+        DetectionMethod::Regex { pattern } | DetectionMethod::LineCount { pattern, .. } => {
+            (pattern, None, false)
+        }
+        DetectionMethod::Multiline { pattern, unless, .. } => (pattern, unless.as_ref(), true),
+        _ => return None,
+    };
+    let Ok(regex) = RegexBuilder::new(pattern).multi_line(whole_file).build() else {
+        return Some(format!("pattern '{pattern}' does not compile"));
+    };
+    if let Some(unless) = unless.filter(|unless| Regex::new(unless).is_err()) {
+        return Some(format!("unless pattern '{unless}' does not compile"));
+    }
+    // A block rule matches the line opening a block, which examples rarely show alone
+    if matches!(rule.detection_method, DetectionMethod::LineCount { .. }) {
+        return None;
+    }
+
+    let bad: Vec<&str> = rule
+        .examples
+        .iter()
+        .map(|example| example.bad.as_str())
+        .filter(|bad| !bad.trim().is_empty())
+        .collect();
+    let matches = |bad: &str| match whole_file {
+        true => regex.is_match(bad),
+        false => bad.lines().any(|line| regex.is_match(line)),
+    };
+    if bad.is_empty() || bad.iter().any(|bad| matches(bad)) {
+        return None;
+    }
+    Some(format!("pattern '{pattern}' matches none of its {} bad example(s)", bad.len()))
+}
+
+/// Loaded rules whose pattern can never report anything
+pub fn find_never_matching(registry: &PatternRegistry) -> Vec<RuleProblem> {
+    registry
+        .all_patterns()
+        .into_iter()
+        .filter_map(|rule| {
+            Some(RuleProblem {
+                rule_id: rule.id.clone(),
+                language: Some(rule.language.clone()),
+                kind: ProblemKind::NeverMatches { reason: never_matches(rule)? },
+            })
+        })
+        .collect()
+}
+
+/// The rule ids `settings` names, found at `location`
+pub fn settings_references(settings: &RuleSettings, location: &str) -> Vec<RuleReference> {
+    let reference = |id: &String, setting: &str, disables: bool| RuleReference {
+        rule_id: id.clone(),
+        by: format!("{setting} in {location}"),
+        disables,
+    };
+    let mut references: Vec<RuleReference> =
+        settings.disabled.iter().map(|id| reference(id, "disabled", true)).collect();
+    references.extend(settings.enabled.iter().map(|id| reference(id, "enabled", false)));
+    references.extend(settings.severity.keys().map(|id| reference(id, "severity", false)));
+    references.extend(settings.thresholds.keys().map(|id| reference(id, "thresholds", false)));
+    references
+}
+
+/// The rule ids named by suppression comments in `content`, the file at `path`. Comments
+/// without ids silence every rule and name none.
+pub fn suppression_references(path: &str, content: &str) -> Vec<RuleReference> {
+    let Some(language) = Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(Language::from_extension)
+    else {
+        return Vec::new();
+    };
+    let syntax = language.comment_syntax();
+
+    let mut references = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let Some(suppression) = Suppression::parse(line, &syntax) else { continue };
+        references.extend(suppression.rule_ids.into_iter().map(|rule_id| RuleReference {
+            rule_id,
+            by: format!("a suppression comment at {path}:{}", index + 1),
+            disables: false,
+        }));
+    }
+    references
+}
+
+/// References to rules that are not loaded, and to rules whose every variant is disabled
+pub fn find_bad_references(
+    registry: &PatternRegistry,
+    references: &[RuleReference],
+) -> Vec<RuleProblem> {
+    // One problem per rule and place, however often the place names it
+    let mut seen: HashSet<(&str, &str)> = HashSet::new();
+    let mut problems = Vec::new();
+    for reference in references {
+        if !seen.insert((&reference.rule_id, &reference.by)) {
+            continue;
+        }
+        let variants = registry.get_variants(&reference.rule_id);
+        let kind = if variants.is_empty() {
+            ProblemKind::UnknownRule { by: reference.by.clone() }
+        } else if !reference.disables && variants.iter().all(|variant| !variant.enabled) {
+            ProblemKind::DisabledButReferenced { by: reference.by.clone() }
+        } else {
+            continue;
+        };
+        problems.push(RuleProblem { rule_id: reference.rule_id.clone(), language: None, kind });
+    }
+    problems
+}
+
+#[cfg(test)]
+mod rule_doctor_tests {
+    use super::*;
+    use crate::core::rule_conflicts::ConflictPolicy;
+    use crate::core::{CodeExample, Severity};
+
+    fn rule(id: &str, pattern: &str, bad: &str) -> AntiPattern {
+        AntiPattern {
+            id: id.to_string(),
+            name: id.to_string(),
+            language: Language::Elixir,
+            severity: Severity::Major,
+            description: String::new(),
+            detection_method: DetectionMethod::Regex { pattern: pattern.to_string() },
+            fix_suggestion: String::new(),
+            source_url: None,
+            claude_code_fixable: false,
+            examples: vec![CodeExample {
+                bad: bad.to_string(),
+                good: String::new(),
+                explanation: String::new(),
+            }],
+            tags: vec![],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_duplicates_and_shadowing_are_told_apart() {
+        let mut registry = PatternRegistry::new();
+        let rule_yaml = r#"
+- id: no_io
+  name: No IO
+  language: elixir
+  severity: major
+  description: Use Logger
+  detection_method: { type: regex, pattern: 'IO\.' }
+  fix_suggestion: Use Logger
+  claude_code_fixable: false
+  tags: []
+  enabled: true
+"#;
+        // The same id twice in one pack, then again in an installed pack
+        registry.load_rule_pack(&rule_yaml.repeat(2), RuleSource::built_in("elixir")).unwrap();
+        registry.load_rule_pack(rule_yaml, RuleSource::installed("team")).unwrap();
+        let project = RuleSource::Project { project: "app".to_string() };
+        registry
+            .add_custom_patterns(
+                vec![rule("no_io", "IO", "IO"), rule("mine", "a", "a"), rule("mine", "b", "b")],
+                &project,
+                ConflictPolicy::PreferBuiltin,
+            )
+            .unwrap();
+
+        let problems: Vec<String> = find_id_collisions(registry.shadowed(), registry.conflicts())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(
+            problems,
+            [
+                format!(
+                    "no_io (elixir): defined more than once in built-in elixir pack v{version}; \
+                     only one definition is used"
+                ),
+                format!(
+                    "no_io (elixir): the rule from built-in elixir pack v{version} is replaced by \
+                     the one from installed team pack"
+                ),
+                "no_io (elixir): the rule from the project rules is replaced by the one from the \
+                 rule loaded before it (rule_conflicts: prefer-builtin)"
+                    .to_string(),
+                "mine (elixir): defined more than once in the project rules; only one definition \
+                 is used"
+                    .to_string(),
+            ]
+        );
+
+        let team = RuleSource::Repository { path: ".patingin/rules.yml".to_string() };
+        let shadowed =
+            find_team_shadowing(&[rule("a", "a", "a")], &[rule("a", "b", "b")], &project, &team);
+        assert_eq!(
+            shadowed[0].to_string(),
+            "a (elixir): the rule from custom rules of project app is replaced by the one from \
+             team rules in .patingin/rules.yml"
+        );
+    }
+
+    #[test]
+    fn test_never_matching_patterns() {
+        let mut registry = PatternRegistry::new();
+        registry.add_pattern(rule("fine", r"IO\.inspect", "x |> IO.inspect()\ny"));
+        registry.add_pattern(rule("broken", "(", "("));
+        registry.add_pattern(rule("misses", r"IO\.puts\n", "IO.puts(x)"));
+        let mut no_examples = rule("no_examples", "anything", "");
+        no_examples.examples.clear();
+        registry.add_pattern(no_examples);
+
+        let problems: Vec<String> =
+            find_never_matching(&registry).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            problems,
+            [
+                "broken (elixir): pattern '(' does not compile",
+                r"misses (elixir): pattern 'IO\.puts\n' matches none of its 1 bad example(s)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_references_to_disabled_and_unknown_rules() {
+        let mut registry = PatternRegistry::new();
+        let mut off = rule("off", "a", "a");
+        off.enabled = false;
+        registry.add_pattern(off);
+        registry.add_pattern(rule("on", "a", "a"));
+
+        let settings = RuleSettings {
+            disabled: vec!["off".to_string(), "typo".to_string()],
+            severity: [("off".to_string(), Severity::Critical)].into(),
+            ..Default::default()
+        };
+        let mut references = settings_references(&settings, "patingin.yml");
+        references.extend(suppression_references(
+            "lib/a.ex",
+            "x # patingin-ignore: on, off\n# patingin-ignore-next-line: off\ny # patingin-ignore\n",
+        ));
+        references.extend(suppression_references("notes.txt", "# patingin-ignore: off"));
+
+        let problems: Vec<String> =
+            find_bad_references(&registry, &references).iter().map(ToString::to_string).collect();
+        assert_eq!(
+            problems,
+            [
+                "typo: no such rule, but named by disabled in patingin.yml",
+                "off: disabled, but named by severity in patingin.yml",
+                "off: disabled, but named by a suppression comment at lib/a.ex:1",
+                "off: disabled, but named by a suppression comment at lib/a.ex:2",
+            ]
+        );
+    }
+}
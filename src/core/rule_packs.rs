@@ -0,0 +1,232 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use super::registry::PatternRegistry;
+use super::DetectionMethod;
+
+/// A rule pack installed with `patingin rules import`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledPack {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Where imported rule packs live: `~/.config/patingin/packs/`
+pub fn packs_dir() -> PathBuf {
+    let home_dir = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home_dir).join(".config").join("patingin").join("packs")
+}
+
+/// The installed packs, ordered by name
+pub fn installed_packs() -> Result<Vec<InstalledPack>> {
+    installed_packs_in(&packs_dir())
+}
+
+fn installed_packs_in(dir: &Path) -> Result<Vec<InstalledPack>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut packs = Vec::new();
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        let is_yaml = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extension == "yml" || extension == "yaml");
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if is_yaml && path.is_file() {
+            packs.push(InstalledPack { name: name.to_string(), path: path.clone() });
+        }
+    }
+    packs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packs)
+}
+
+/// Whether `source` is a URL rather than a local path
+pub fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+/// The content of the pack at `source`, a URL or a local path
+pub async fn fetch_pack(source: &str) -> Result<String> {
+    if !is_url(source) {
+        return std::fs::read_to_string(source).with_context(|| format!("Failed to read {source}"));
+    }
+    let response = reqwest::get(source)
+        .await
+        .with_context(|| format!("Failed to download {source}"))?
+        .error_for_status()
+        .with_context(|| format!("Failed to download {source}"))?;
+    response.text().await.with_context(|| format!("Failed to download {source}"))
+}
+
+/// The name a pack is installed under unless one is given: its file name without the
+/// extension, e.g. `phoenix` for `https://example.com/packs/phoenix.yml?ref=main`
+pub fn pack_name(source: &str) -> Result<String> {
+    let path = source.split(['?', '#']).next().unwrap_or(source);
+    // A URL's host is no file name
+    let path = match path.split_once("://") {
+        Some((_, location)) => location.split_once('/').map_or("", |(_, path)| path),
+        None => path,
+    };
+    let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let name = file_name
+        .strip_suffix(".yml")
+        .or_else(|| file_name.strip_suffix(".yaml"))
+        .unwrap_or(file_name);
+    validate_pack_name(name)
+        .with_context(|| format!("Cannot name a pack after {source}; pass --name"))?;
+    Ok(name.to_string())
+}
+
+/// Pack names become file names, so they are kept to letters, digits, `-`, `_` and `.`
+pub fn validate_pack_name(name: &str) -> Result<()> {
+    let valid_chars =
+        name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if name.is_empty() || name.starts_with('.') || !valid_chars {
+        bail!("Invalid pack name '{name}': use letters, digits, '-', '_' and '.'");
+    }
+    Ok(())
+}
+
+/// Check that `content` is a rule pack this version loads in full, in the schema of the
+/// built-in packs: at least one rule, no entry skipped and every pattern compiling.
+/// Returns the number of rule variants.
+pub fn validate_pack(content: &str) -> Result<usize> {
+    let parsed = PatternRegistry::parse_rule_pack(content)
+        .context("Not a rule pack: expected a list of rules like the built-in packs")?;
+    if !parsed.skipped.is_empty() {
+        bail!("Unsupported rules in pack:\n  {}", parsed.skipped.join("\n  "));
+    }
+    if parsed.rules.is_empty() {
+        bail!("The pack declares no rules");
+    }
+
+    for rule in &parsed.rules {
+        let patterns = match &rule.detection_method {
+            DetectionMethod::Regex { pattern }
+            | DetectionMethod::LineCount { pattern, .. }
+            | DetectionMethod::Ratio { pattern, .. } => vec![pattern],
+            DetectionMethod::Multiline { pattern, unless, .. } => {
+                std::iter::once(pattern).chain(unless).collect()
+            }
+            _ => vec![],
+        };
+        for pattern in patterns {
+            Regex::new(pattern).with_context(|| {
+                format!("Rule {} ({}) has an invalid pattern", rule.id, rule.language)
+            })?;
+        }
+    }
+    Ok(parsed.rules.len())
+}
+
+/// Write `content` into `dir` as pack `name`, noting `source` in a header comment. Returns
+/// the file written and whether it replaced an installed pack of that name.
+pub fn install_pack(
+    dir: &Path,
+    name: &str,
+    source: &str,
+    content: &str,
+) -> Result<(PathBuf, bool)> {
+    validate_pack_name(name)?;
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let path = dir.join(format!("{name}.yml"));
+    let replaced = path.exists();
+    std::fs::write(&path, format!("# Imported from {source}\n{content}"))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok((path, replaced))
+}
+
+#[cfg(test)]
+mod rule_packs_tests {
+    use super::*;
+
+    const PACK: &str = r#"
+- id: no_io_puts
+  name: "No IO.puts"
+  language: elixir
+  severity: warning
+  description: "IO.puts left in library code"
+  detection_method:
+    type: regex
+    pattern: "IO\\.puts"
+  fix_suggestion: "Use Logger"
+  source_url: null
+  claude_code_fixable: true
+  tags: [debugging]
+  enabled: true
+"#;
+
+    #[test]
+    fn test_validate_pack() {
+        assert_eq!(validate_pack(PACK).unwrap(), 1);
+
+        let error = |content: &str| format!("{:#}", validate_pack(content).unwrap_err());
+        assert!(error("rules: nope").contains("Not a rule pack"));
+        assert!(error("[]").contains("declares no rules"));
+        assert!(error(&PACK.replace("severity: warning", "severity: high"))
+            .contains("no_io_puts: unknown severity 'high'"));
+        assert!(error(&PACK.replace("IO\\\\.puts", "IO.puts(")).contains("invalid pattern"));
+    }
+
+    #[test]
+    fn test_packs_may_use_blocker_and_info() {
+        for severity in ["blocker", "info"] {
+            let pack = PACK.replace("severity: warning", &format!("severity: {severity}"));
+            assert_eq!(validate_pack(&pack).unwrap(), 1, "{severity}");
+        }
+    }
+
+    #[test]
+    fn test_pack_auto_fix_templates_are_checked() {
+        let with_fix = |search: &str| {
+            PACK.replace(
+                "  enabled: true",
+                &format!("  auto_fix:\n    search: \"{search}\"\n    replace: Logger.info\n  enabled: true"),
+            )
+        };
+        assert_eq!(validate_pack(&with_fix("IO\\\\.puts")).unwrap(), 1);
+        let error = format!("{:#}", validate_pack(&with_fix("IO.puts(")).unwrap_err());
+        assert!(error.contains("no_io_puts: Invalid auto_fix search pattern"), "{error}");
+    }
+
+    #[test]
+    fn test_pack_names() {
+        assert_eq!(pack_name("https://example.com/packs/phoenix.yml?ref=main").unwrap(), "phoenix");
+        assert_eq!(pack_name("./team-rules.yaml").unwrap(), "team-rules");
+        assert!(pack_name("https://example.com/").is_err());
+        assert!(validate_pack_name("../evil").is_err());
+    }
+
+    #[test]
+    fn test_installed_packs_are_loaded_in_name_order() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (path, replaced) = install_pack(temp_dir.path(), "team", "./team.yml", PACK).unwrap();
+        assert!(!replaced);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("# Imported from ./team.yml\n"));
+        assert!(install_pack(temp_dir.path(), "team", "./team.yml", PACK).unwrap().1);
+        install_pack(temp_dir.path(), "extra", "./extra.yml", PACK).unwrap();
+        std::fs::write(temp_dir.path().join("notes.txt"), "not a pack").unwrap();
+
+        let packs = installed_packs_in(temp_dir.path()).unwrap();
+        let names: Vec<_> = packs.iter().map(|pack| pack.name.as_str()).collect();
+        assert_eq!(names, ["extra", "team"]);
+
+        let mut registry = PatternRegistry::new();
+        let content = std::fs::read_to_string(&packs[1].path).unwrap();
+        registry
+            .load_rule_pack(&content, crate::core::provenance::RuleSource::installed("team"))
+            .unwrap();
+        let rule = registry.get_pattern("no_io_puts").unwrap();
+        assert_eq!(registry.rule_sources().get(rule).unwrap().to_string(), "installed team pack");
+    }
+}
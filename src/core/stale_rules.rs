@@ -0,0 +1,150 @@
+use regex::Regex;
+use std::path::Path;
+
+use super::{AntiPattern, DetectionMethod, Language};
+use crate::git::history::AddedLines;
+
+/// A project rule that no longer earns the time every review spends on it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleRule {
+    /// Id in the user rules file, without the `custom_` prefix reviews report
+    pub rule_id: String,
+    pub language: Language,
+    pub reason: StaleReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaleReason {
+    /// The project has no files the rule applies to any more
+    NoFiles,
+    /// No line added by the sampled commits matches the rule
+    NoRecentMatches { commits: usize },
+}
+
+impl std::fmt::Display for StaleReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaleReason::NoFiles => write!(f, "no files in the project it applies to"),
+            StaleReason::NoRecentMatches { commits } => {
+                write!(f, "matched nothing added in the last {commits} commit(s)")
+            }
+        }
+    }
+}
+
+/// The project `rules` that look dead: those whose language has no file among `files`, and
+/// those matching none of the lines `history` added. An empty history flags nothing by
+/// matches, as a fresh repository has not had the chance to trip any rule.
+pub fn find_stale_rules(
+    rules: &[AntiPattern],
+    files: &[String],
+    history: &AddedLines,
+) -> Vec<StaleRule> {
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let applies = |path: &str| {
+                Path::new(path)
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| rule.matches_file_extension(extension))
+            };
+
+            let reason = if !files.iter().any(|path| applies(path)) {
+                StaleReason::NoFiles
+            } else if history.commits > 0 && !matches_history(rule, history, applies) {
+                StaleReason::NoRecentMatches { commits: history.commits }
+            } else {
+                return None;
+            };
+
+            Some(StaleRule {
+                rule_id: rule.id.strip_prefix("custom_").unwrap_or(&rule.id).to_string(),
+                language: rule.language.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Whether any line `history` added to a file the rule applies to matches it. A pattern that
+/// does not compile counts as matching: `review` reports it, and pruning is not the fix.
+fn matches_history(
+    rule: &AntiPattern,
+    history: &AddedLines,
+    applies: impl Fn(&str) -> bool,
+) -> bool {
+    let DetectionMethod::Regex { pattern } = &rule.detection_method else {
+        return true;
+    };
+    let Ok(regex) = Regex::new(pattern) else {
+        return true;
+    };
+    history.lines.iter().any(|(path, line)| applies(path) && regex.is_match(line))
+}
+
+#[cfg(test)]
+mod stale_rules_tests {
+    use super::*;
+    use crate::core::Severity;
+
+    fn rule(id: &str, language: Language, pattern: &str) -> AntiPattern {
+        AntiPattern {
+            id: format!("custom_{id}"),
+            name: id.to_string(),
+            language,
+            severity: Severity::Warning,
+            description: id.to_string(),
+            detection_method: DetectionMethod::Regex { pattern: pattern.to_string() },
+            fix_suggestion: String::new(),
+            source_url: None,
+            claude_code_fixable: false,
+            examples: vec![],
+            tags: vec!["custom".to_string()],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_flags_rules_without_files_or_recent_matches() {
+        let rules = vec![
+            rule("no_inspect", Language::Elixir, r"IO\.inspect"),
+            rule("no_sleep", Language::Elixir, r"Process\.sleep"),
+            rule("no_print", Language::Python, r"print\("),
+            rule("broken", Language::Elixir, r"("),
+        ];
+        let files = vec!["lib/app.ex".to_string(), "README.md".to_string()];
+        let history = AddedLines {
+            commits: 30,
+            lines: vec![
+                ("lib/app.ex".to_string(), "IO.inspect(user)".to_string()),
+                ("notes.md".to_string(), "Process.sleep(100)".to_string()),
+            ],
+        };
+
+        let stale = find_stale_rules(&rules, &files, &history);
+        assert_eq!(
+            stale,
+            vec![
+                StaleRule {
+                    rule_id: "no_sleep".to_string(),
+                    language: Language::Elixir,
+                    reason: StaleReason::NoRecentMatches { commits: 30 },
+                },
+                StaleRule {
+                    rule_id: "no_print".to_string(),
+                    language: Language::Python,
+                    reason: StaleReason::NoFiles,
+                },
+            ]
+        );
+
+        let no_history = find_stale_rules(&rules, &files, &AddedLines::default());
+        assert_eq!(no_history.len(), 1);
+        assert_eq!(no_history[0].reason, StaleReason::NoFiles);
+    }
+}
@@ -0,0 +1,151 @@
+use super::languages::CommentSyntax;
+use super::Language;
+use crate::git::ChangedLine;
+
+/// Marker recognized inside comments to silence findings
+pub const SUPPRESSION_MARKER: &str = "patingin-ignore";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuppressionScope {
+    /// `patingin-ignore` at the end of the offending line
+    Line,
+    /// `patingin-ignore-next-line` on the line above
+    NextLine,
+}
+
+/// An inline suppression comment, e.g. `# patingin-ignore: dynamic_atom_creation`.
+/// Without rule ids it silences every rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppression {
+    pub scope: SuppressionScope,
+    pub rule_ids: Vec<String>,
+}
+
+impl Suppression {
+    /// Find a suppression in the comment of `line`, written in the language's own syntax
+    pub fn parse(line: &str, syntax: &CommentSyntax) -> Option<Self> {
+        let comment = syntax.comment_text(line)?;
+        let rest = &comment[comment.find(SUPPRESSION_MARKER)? + SUPPRESSION_MARKER.len()..];
+
+        let (scope, rest) = match rest.strip_prefix("-next-line") {
+            Some(rest) => (SuppressionScope::NextLine, rest),
+            None => (SuppressionScope::Line, rest),
+        };
+        let rule_ids = match rest.trim_start().strip_prefix(':') {
+            Some(ids) => ids
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(String::from)
+                .collect(),
+            // Anything else after the marker, e.g. `patingin-ignored`, is not a suppression
+            None if rest.trim().is_empty() => Vec::new(),
+            None => return None,
+        };
+
+        Some(Self { scope, rule_ids })
+    }
+
+    pub fn covers(&self, rule_id: &str) -> bool {
+        self.rule_ids.is_empty() || self.rule_ids.iter().any(|id| id == rule_id)
+    }
+
+    /// The comment that suppresses `rule_id` in `language`; `None` when the language's
+    /// comment syntax is unknown.
+    pub fn comment_for(
+        language: &Language,
+        rule_id: &str,
+        scope: SuppressionScope,
+    ) -> Option<String> {
+        let marker = match scope {
+            SuppressionScope::Line => SUPPRESSION_MARKER.to_string(),
+            SuppressionScope::NextLine => format!("{SUPPRESSION_MARKER}-next-line"),
+        };
+        language.comment_syntax().comment(&format!("{marker}: {rule_id}"))
+    }
+}
+
+/// Whether `rule_id` is silenced on `changed_line`, by a comment on the line itself or a
+/// next-line comment directly above it
+pub fn is_suppressed(changed_line: &ChangedLine, rule_id: &str, syntax: &CommentSyntax) -> bool {
+    let same_line = Suppression::parse(&changed_line.content, syntax)
+        .is_some_and(|s| s.scope == SuppressionScope::Line && s.covers(rule_id));
+    let line_above = changed_line
+        .context_before
+        .last()
+        .and_then(|line| Suppression::parse(line, syntax))
+        .is_some_and(|s| s.scope == SuppressionScope::NextLine && s.covers(rule_id));
+    same_line || line_above
+}
+
+#[cfg(test)]
+mod suppression_tests {
+    use super::*;
+    use crate::git::ChangeType;
+
+    fn changed_line(content: &str, line_above: &str) -> ChangedLine {
+        ChangedLine {
+            line_number: 2,
+            content: content.to_string(),
+            change_type: ChangeType::Added,
+            context_before: vec![line_above.to_string()],
+            context_after: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_uses_language_comment_syntax() {
+        let sql = Language::Sql.comment_syntax();
+        let suppression =
+            Suppression::parse("SELECT * FROM users -- patingin-ignore: select_star, x", &sql)
+                .unwrap();
+        assert_eq!(suppression.scope, SuppressionScope::Line);
+        assert_eq!(suppression.rule_ids, ["select_star", "x"]);
+
+        // A `#` comment means nothing in SQL
+        assert_eq!(Suppression::parse("SELECT 1 # patingin-ignore", &sql), None);
+        assert_eq!(
+            Suppression::parse("x = 1 # patingin-ignored", &Language::Python.comment_syntax()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_line_and_block_comments() {
+        let js = Language::JavaScript.comment_syntax();
+        let suppression = Suppression::parse("/* patingin-ignore-next-line */", &js).unwrap();
+        assert_eq!(suppression.scope, SuppressionScope::NextLine);
+        assert!(suppression.covers("any_rule"));
+    }
+
+    #[test]
+    fn test_is_suppressed_checks_line_and_line_above() {
+        let elixir = Language::Elixir.comment_syntax();
+
+        let inline = changed_line("String.to_atom(x) # patingin-ignore: dynamic_atom_creation", "");
+        assert!(is_suppressed(&inline, "dynamic_atom_creation", &elixir));
+        assert!(!is_suppressed(&inline, "other_rule", &elixir));
+
+        let above = changed_line("String.to_atom(x)", "  # patingin-ignore-next-line");
+        assert!(is_suppressed(&above, "dynamic_atom_creation", &elixir));
+
+        let wrong_scope = changed_line("String.to_atom(x)", "  # patingin-ignore");
+        assert!(!is_suppressed(&wrong_scope, "dynamic_atom_creation", &elixir));
+    }
+
+    #[test]
+    fn test_comment_for_round_trips() {
+        for language in Language::BUILT_IN {
+            let comment =
+                Suppression::comment_for(&language, "rule_a", SuppressionScope::NextLine).unwrap();
+            let parsed = Suppression::parse(&comment, &language.comment_syntax()).unwrap();
+            assert_eq!(parsed.scope, SuppressionScope::NextLine);
+            assert_eq!(parsed.rule_ids, ["rule_a"]);
+        }
+        assert_eq!(
+            Suppression::comment_for(&Language::Sql, "select_star", SuppressionScope::Line)
+                .unwrap(),
+            "-- patingin-ignore: select_star"
+        );
+    }
+}
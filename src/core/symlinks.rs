@@ -0,0 +1,61 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// The `symlinks:` section of the project config: where replacing a file with a symlink is
+/// expected, so `symlink_replaces_file` stays quiet there.
+///
+/// ```yaml
+/// symlinks:
+///   allowed_dirs: ["config/shared", "docs"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct SymlinkPolicy {
+    #[serde(default)]
+    pub allowed_dirs: Vec<String>,
+}
+
+impl SymlinkPolicy {
+    /// Read the `symlinks:` section of a project config file; absent allows no directory
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct SymlinksSection {
+            #[serde(default)]
+            symlinks: SymlinkPolicy,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<SymlinksSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.map(|section| section.symlinks).unwrap_or_default())
+    }
+
+    /// Whether `file_path` lies inside one of the allowed directories
+    pub fn allows(&self, file_path: &str) -> bool {
+        self.allowed_dirs
+            .iter()
+            .any(|dir| Path::new(file_path).starts_with(dir.trim_end_matches('/')))
+    }
+}
+
+#[cfg(test)]
+mod symlinks_tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_dirs_match_whole_components() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(&config_path, "symlinks:\n  allowed_dirs: [\"config/shared/\", docs]\n")
+            .unwrap();
+
+        let policy = SymlinkPolicy::load(&config_path).unwrap();
+
+        assert!(policy.allows("config/shared/app.ex"));
+        assert!(policy.allows("docs/latest"));
+        assert!(!policy.allows("docs-old/latest"));
+        assert!(!policy.allows("lib/app.ex"));
+        assert!(!SymlinkPolicy::default().allows("docs/latest"));
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The `updates:` section of the user rules file, `~/.config/patingin/rules.yml`.
+///
+/// ```yaml
+/// updates:
+///   check: false        # no "new version available" notice
+///   interval_hours: 24
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateSettings {
+    #[serde(default = "default_check")]
+    pub check: bool,
+    /// Least time between two checks for a new release
+    #[serde(default = "default_interval_hours")]
+    pub interval_hours: u64,
+}
+
+fn default_check() -> bool {
+    true
+}
+
+fn default_interval_hours() -> u64 {
+    24
+}
+
+impl Default for UpdateSettings {
+    fn default() -> Self {
+        Self { check: default_check(), interval_hours: default_interval_hours() }
+    }
+}
+
+impl UpdateSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// When the latest release was last looked up and what it was, so the passive check hits the
+/// network at most once per interval
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateCheckState {
+    /// RFC 3339 time of the last lookup
+    pub checked_at: Option<String>,
+    /// Version of the latest release at that time
+    pub latest: Option<String>,
+}
+
+impl UpdateCheckState {
+    /// `update-check.yml` next to the user rules file
+    pub fn default_path(rules_path: &Path) -> PathBuf {
+        rules_path.with_file_name("update-check.yml")
+    }
+
+    /// Read the state at `path`; a missing or unreadable file means no check happened yet
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Whether the last check is older than `interval_hours`
+    pub fn is_due(&self, settings: &UpdateSettings, now: DateTime<Utc>) -> bool {
+        let checked_at = self
+            .checked_at
+            .as_deref()
+            .and_then(|checked_at| DateTime::parse_from_rfc3339(checked_at).ok());
+        checked_at.map_or(true, |checked_at| {
+            let hours = now.signed_duration_since(checked_at).num_hours();
+            u64::try_from(hours).is_ok_and(|hours| hours >= settings.interval_hours)
+        })
+    }
+
+    /// A lookup made just now that found `latest`
+    pub fn checked_now(latest: Option<String>) -> Self {
+        Self { checked_at: Some(Utc::now().to_rfc3339()), latest }
+    }
+}
+
+/// Whether `candidate` is a later release than `current`. Versions compare by their
+/// numeric `major.minor.patch` parts; a leading `v` and any `-pre`/`+build` suffix are
+/// ignored, as are versions that do not parse.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    let core = version.split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+#[cfg(test)]
+mod updates_tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_is_newer_compares_numeric_parts() {
+        assert!(is_newer("0.2.0", "0.1.0"));
+        assert!(is_newer("v0.10.0", "0.9.3"));
+        assert!(is_newer("1.0.0", "0.99.99"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("v0.1.0-rc.1", "0.1.0"));
+        assert!(!is_newer("0.0.9", "0.1.0"));
+        assert!(!is_newer("nightly", "0.1.0"));
+    }
+
+    #[test]
+    fn test_check_is_rate_limited() {
+        let settings = UpdateSettings::default();
+        let now = Utc::now();
+        assert!(UpdateCheckState::default().is_due(&settings, now));
+
+        let at = |hours_ago| UpdateCheckState {
+            checked_at: Some((now - Duration::hours(hours_ago)).to_rfc3339()),
+            latest: None,
+        };
+        let recent = at(2);
+        assert!(!recent.is_due(&settings, now));
+        assert!(recent.is_due(&UpdateSettings { interval_hours: 1, ..settings.clone() }, now));
+
+        assert!(at(25).is_due(&settings, now));
+        let garbled = UpdateCheckState { checked_at: Some("yesterday".into()), latest: None };
+        assert!(garbled.is_due(&settings, now));
+    }
+
+    #[test]
+    fn test_state_round_trips() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = UpdateCheckState::default_path(&temp_dir.path().join("rules.yml"));
+        assert_eq!(UpdateCheckState::load(&path), UpdateCheckState::default());
+
+        let state = UpdateCheckState::checked_now(Some("0.2.0".to_string()));
+        state.save(&path).unwrap();
+        assert_eq!(UpdateCheckState::load(&path), state);
+    }
+}
@@ -0,0 +1,345 @@
+use anyhow::{anyhow, Context, Result};
+
+use super::github::inline_comment_body;
+use crate::core::ReviewViolation;
+use crate::git::{RemoteProvider, RemoteRepository};
+use crate::report::mentions::MentionsConfig;
+
+const DEFAULT_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+const PAGE_SIZE: usize = 100;
+
+/// Hidden marker identifying the summary comment patingin owns, so reruns edit it instead of
+/// adding more. Bitbucket shows HTML comments as text, so this is an empty markdown link
+/// definition, which renders as nothing.
+pub const SUMMARY_COMMENT_MARKER: &str = "[//]: # (patingin:summary-comment)";
+
+/// What a run did to the pull request's comments, inline ones and the summary alike
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CommentTally {
+    pub posted: usize,
+    pub updated: usize,
+    /// Already on the pull request as they would be posted
+    pub unchanged: usize,
+}
+
+/// A comment already on the pull request
+#[derive(Debug, Clone, PartialEq)]
+struct PostedComment {
+    id: u64,
+    raw: String,
+    /// File and new-file line of an inline comment
+    inline: Option<(String, u64)>,
+}
+
+/// What to do with a comment patingin is about to post
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommentPlan {
+    Post,
+    Update(u64),
+    Keep,
+}
+
+/// How requests are signed: a repository/workspace access token, or a username with an app
+/// password
+enum BitbucketAuth {
+    Token(String),
+    AppPassword { username: String, password: String },
+}
+
+pub struct BitbucketIntegration {
+    auth: Option<BitbucketAuth>,
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl Default for BitbucketIntegration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BitbucketIntegration {
+    /// Authenticates with `BITBUCKET_TOKEN`, or `BITBUCKET_USERNAME` and
+    /// `BITBUCKET_APP_PASSWORD`; `BITBUCKET_API_URL` points at another API endpoint
+    pub fn new() -> Self {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let auth = match (env("BITBUCKET_TOKEN"), env("BITBUCKET_USERNAME")) {
+            (Some(token), _) => Some(BitbucketAuth::Token(token)),
+            (None, Some(username)) => env("BITBUCKET_APP_PASSWORD")
+                .map(|password| BitbucketAuth::AppPassword { username, password }),
+            (None, None) => None,
+        };
+        let api_url = std::env::var("BITBUCKET_API_URL")
+            .unwrap_or_else(|_| DEFAULT_API_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        Self { auth, api_url, client: reqwest::Client::new() }
+    }
+
+    /// `workspace/repo` of the repository: the argument, `BITBUCKET_REPO_FULL_NAME` inside
+    /// Bitbucket Pipelines, or the remote when it points at bitbucket.org. An argument that
+    /// is not `workspace/repo` is an error rather than a reason to fall back.
+    pub fn resolve_repository(repo: Option<&str>) -> Result<String> {
+        if let Some(repo) = repo {
+            return match repo.split_once('/') {
+                Some((workspace, name))
+                    if !workspace.is_empty() && !name.is_empty() && !name.contains('/') =>
+                {
+                    Ok(repo.to_string())
+                }
+                _ => Err(anyhow!(
+                    "Invalid --bitbucket-repo '{repo}': expected WORKSPACE/REPO, e.g. acme/app"
+                )),
+            };
+        }
+        std::env::var("BITBUCKET_REPO_FULL_NAME")
+            .ok()
+            .filter(|repo| repo.contains('/'))
+            .or_else(|| RemoteRepository::detect_slug(RemoteProvider::Bitbucket))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Bitbucket repository unknown; pass --bitbucket-repo WORKSPACE/REPO or add \
+                     a bitbucket.org `origin` remote"
+                )
+            })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> Result<reqwest::RequestBuilder> {
+        self.request_url(method, &format!("{}{}", self.api_url, path))
+    }
+
+    /// Like `request`, for the full URLs the API hands out, such as the next page
+    fn request_url(&self, method: reqwest::Method, url: &str) -> Result<reqwest::RequestBuilder> {
+        let request = self
+            .client
+            .request(method, url)
+            .header("User-Agent", concat!("patingin/", env!("CARGO_PKG_VERSION")));
+        match &self.auth {
+            Some(BitbucketAuth::Token(token)) => Ok(request.bearer_auth(token)),
+            Some(BitbucketAuth::AppPassword { username, password }) => {
+                Ok(request.basic_auth(username, Some(password)))
+            }
+            None => Err(anyhow!(
+                "BITBUCKET_TOKEN (or BITBUCKET_USERNAME and BITBUCKET_APP_PASSWORD) is not set; \
+                 it is required to use Bitbucket pull requests"
+            )),
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<String> {
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(anyhow!("Bitbucket API request failed ({status}): {body}"));
+        }
+        Ok(body)
+    }
+
+    /// The unified diff of pull request `pr_id`, from its merge base to its source branch
+    pub async fn pull_request_diff(&self, repo: &str, pr_id: u64) -> Result<String> {
+        let path = format!("/repositories/{repo}/pullrequests/{pr_id}/diff");
+        self.send(self.request(reqwest::Method::GET, &path)?)
+            .await
+            .with_context(|| format!("Failed to fetch the diff of {repo}#{pr_id}"))
+    }
+
+    /// Every comment on pull request `pr_id` that is not deleted
+    async fn list_comments(&self, repo: &str, pr_id: u64) -> Result<Vec<PostedComment>> {
+        let mut url = format!(
+            "{}/repositories/{repo}/pullrequests/{pr_id}/comments?pagelen={PAGE_SIZE}",
+            self.api_url
+        );
+        let mut comments = Vec::new();
+        loop {
+            let body = self.send(self.request_url(reqwest::Method::GET, &url)?).await?;
+            let page: serde_json::Value = serde_json::from_str(&body)
+                .with_context(|| format!("Failed to parse the comments of {repo}#{pr_id}"))?;
+            comments.extend(posted_comments(&page));
+            match page["next"].as_str() {
+                Some(next) => url = next.to_string(),
+                None => return Ok(comments),
+            }
+        }
+    }
+
+    /// Comment on each violating line of pull request `pr_id`, then post one comment with
+    /// the count and any mentions. Bitbucket has no batched reviews, so each comment is its
+    /// own request. Comments from earlier runs are recognized by their hidden marker: those
+    /// still accurate are left alone and the others edited, so reruns add no duplicates.
+    pub async fn post_inline_comments(
+        &self,
+        repo: &str,
+        pr_id: u64,
+        violations: &[ReviewViolation],
+        mentions: &MentionsConfig,
+    ) -> Result<CommentTally> {
+        let mut tally = CommentTally::default();
+        if violations.is_empty() {
+            return Ok(tally);
+        }
+
+        let existing = self.list_comments(repo, pr_id).await?;
+        let mut comments: Vec<(CommentPlan, serde_json::Value)> = violations
+            .iter()
+            .map(|violation| {
+                let payload = inline_comment_payload(violation);
+                let inline = Some((violation.file_path.as_str(), violation.line_number));
+                let raw = payload["content"]["raw"].as_str().unwrap_or_default();
+                (plan_comment(&existing, &violation_marker(violation), inline, raw), payload)
+            })
+            .collect();
+
+        let mut body = format!(
+            "{SUMMARY_COMMENT_MARKER}\n\n🔍 patingin found {} anti-pattern violation(s)",
+            violations.len()
+        );
+        if let Some(mentions) = mentions.render(violations) {
+            body.push_str(&format!("\n\n{mentions}"));
+        }
+        comments.push((
+            plan_comment(&existing, SUMMARY_COMMENT_MARKER, None, &body),
+            serde_json::json!({ "content": { "raw": body } }),
+        ));
+
+        let path = format!("/repositories/{repo}/pullrequests/{pr_id}/comments");
+        for (plan, payload) in comments {
+            match plan {
+                CommentPlan::Post => {
+                    self.send(self.request(reqwest::Method::POST, &path)?.json(&payload)).await?;
+                    tally.posted += 1;
+                }
+                CommentPlan::Update(comment_id) => {
+                    // Only the text changes; the anchor of an inline comment stays put
+                    let payload = serde_json::json!({ "content": payload["content"] });
+                    let comment_path = format!("{path}/{comment_id}");
+                    self.send(self.request(reqwest::Method::PUT, &comment_path)?.json(&payload))
+                        .await?;
+                    tally.updated += 1;
+                }
+                CommentPlan::Keep => tally.unchanged += 1,
+            }
+        }
+
+        Ok(tally)
+    }
+}
+
+/// The comments of one page of the comments API, leaving out deleted ones
+fn posted_comments(page: &serde_json::Value) -> Vec<PostedComment> {
+    page["values"]
+        .as_array()
+        .map_or(&[][..], Vec::as_slice)
+        .iter()
+        .filter(|comment| !comment["deleted"].as_bool().unwrap_or(false))
+        .filter_map(|comment| {
+            let inline = &comment["inline"];
+            Some(PostedComment {
+                id: comment["id"].as_u64()?,
+                raw: comment["content"]["raw"].as_str().unwrap_or_default().to_string(),
+                inline: inline["path"]
+                    .as_str()
+                    .zip(inline["to"].as_u64())
+                    .map(|(path, line)| (path.to_string(), line)),
+            })
+        })
+        .collect()
+}
+
+/// Keep a comment already posted with the same marker and anchor and the same text, edit
+/// it when the text changed, or post a new one
+fn plan_comment(
+    existing: &[PostedComment],
+    marker: &str,
+    inline: Option<(&str, usize)>,
+    raw: &str,
+) -> CommentPlan {
+    let inline = inline.map(|(path, line)| (path.to_string(), line as u64));
+    match existing.iter().find(|comment| comment.inline == inline && comment.raw.contains(marker)) {
+        Some(comment) if comment.raw == raw => CommentPlan::Keep,
+        Some(comment) => CommentPlan::Update(comment.id),
+        None => CommentPlan::Post,
+    }
+}
+
+/// Hidden marker of the inline comment for a violation, naming its rule; the comment's
+/// anchor gives the file and line
+fn violation_marker(violation: &ReviewViolation) -> String {
+    format!("[//]: # (patingin:{})", violation.rule.id)
+}
+
+/// A pull request comment anchored to the violating line of the new file
+fn inline_comment_payload(violation: &ReviewViolation) -> serde_json::Value {
+    let body = format!("{}\n\n{}", violation_marker(violation), inline_comment_body(violation));
+    serde_json::json!({
+        "content": { "raw": body },
+        "inline": { "path": violation.file_path, "to": violation.line_number },
+    })
+}
+
+#[cfg(test)]
+mod bitbucket_tests {
+    use super::*;
+    use crate::core::Severity;
+    use crate::report::test_support::violation;
+
+    #[test]
+    fn test_explicit_repository_wins_over_the_remote() {
+        assert_eq!(
+            BitbucketIntegration::resolve_repository(Some("team/other")).unwrap(),
+            "team/other"
+        );
+    }
+
+    #[test]
+    fn test_explicit_repository_must_name_a_workspace() {
+        for repo in ["myrepo", "team/", "/app", "a/b/c"] {
+            let error = BitbucketIntegration::resolve_repository(Some(repo)).unwrap_err();
+            assert!(error.to_string().contains(&format!("Invalid --bitbucket-repo '{repo}'")));
+        }
+    }
+
+    #[test]
+    fn test_inline_comment_payload() {
+        let payload = inline_comment_payload(&violation("lib/user.ex", 42, Severity::Critical));
+        assert_eq!(payload["inline"]["path"], "lib/user.ex");
+        assert_eq!(payload["inline"]["to"], 42);
+        let raw = payload["content"]["raw"].as_str().unwrap();
+        assert!(raw.starts_with("[//]: # (patingin:test_rule)\n\n🔴 **"), "{raw}");
+    }
+
+    #[test]
+    fn test_reruns_keep_or_edit_their_own_comments() {
+        let violation = violation("lib/user.ex", 42, Severity::Critical);
+        let payload = inline_comment_payload(&violation);
+        let raw = payload["content"]["raw"].as_str().unwrap();
+        let marker = violation_marker(&violation);
+        let page = serde_json::json!({
+            "values": [
+                { "id": 1, "content": { "raw": raw }, "inline": { "path": "lib/user.ex", "to": 42 } },
+                { "id": 2, "content": { "raw": "a person's note" } },
+                { "id": 3, "content": { "raw": format!("{SUMMARY_COMMENT_MARKER}\n\nold") } },
+                { "id": 4, "deleted": true, "content": { "raw": SUMMARY_COMMENT_MARKER } },
+            ],
+        });
+        let existing = posted_comments(&page);
+        assert_eq!(existing.len(), 3);
+
+        let at_42 = Some(("lib/user.ex", 42));
+        assert_eq!(plan_comment(&existing, &marker, at_42, raw), CommentPlan::Keep);
+        assert_eq!(plan_comment(&existing, &marker, at_42, "edited"), CommentPlan::Update(1));
+        assert_eq!(
+            plan_comment(&existing, &marker, Some(("lib/user.ex", 43)), raw),
+            CommentPlan::Post
+        );
+        assert_eq!(
+            plan_comment(&existing, "[//]: # (patingin:other_rule)", at_42, raw),
+            CommentPlan::Post
+        );
+        assert_eq!(
+            plan_comment(&existing, SUMMARY_COMMENT_MARKER, None, "new"),
+            CommentPlan::Update(3)
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::{anyhow, Result};
+
+use super::gitlab::GitLabIntegration;
+use super::GitHubIntegration;
+use crate::core::ReviewViolation;
+use crate::git::{RemoteProvider, RemoteRepository};
+use crate::report::markdown::render_markdown;
+use crate::report::severity_breakdown;
+
+/// Name of the status/check, which is what branch protection rules require
+pub const STATUS_CONTEXT: &str = "patingin";
+
+/// Longest check-run summary GitHub accepts
+const MAX_SUMMARY_BYTES: usize = 65_535;
+
+/// Result of a run as a required check: failing violations fail it, violations that only
+/// report leave it neutral
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusOutcome {
+    Pass,
+    Fail,
+    Neutral,
+}
+
+impl StatusOutcome {
+    pub fn decide(violations: usize, failing: usize) -> Self {
+        if failing > 0 {
+            StatusOutcome::Fail
+        } else if violations > 0 {
+            StatusOutcome::Neutral
+        } else {
+            StatusOutcome::Pass
+        }
+    }
+}
+
+/// What is published for the head commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitStatus {
+    pub outcome: StatusOutcome,
+    /// One line, e.g. "3 violations (1 critical, 2 major, 0 warning); 1 failed the gate"
+    pub title: String,
+    /// Markdown report, shown by providers that have room for it
+    pub summary: String,
+    pub details_url: Option<String>,
+}
+
+impl CommitStatus {
+    pub fn new(
+        violations: &[ReviewViolation],
+        failing: usize,
+        scope: &str,
+        details_url: Option<String>,
+    ) -> Self {
+        let count = |severity| violations.iter().filter(|v| v.severity == severity).count();
+        let mut title = if violations.is_empty() {
+            "No anti-pattern violations".to_string()
+        } else {
+            format!("{} violations ({})", violations.len(), severity_breakdown(count, false))
+        };
+        if failing > 0 {
+            title.push_str(&format!("; {failing} failed the gate"));
+        }
+
+        let mut summary = render_markdown(violations, scope);
+        if summary.len() > MAX_SUMMARY_BYTES {
+            let mut end = MAX_SUMMARY_BYTES - 100;
+            while !summary.is_char_boundary(end) {
+                end -= 1;
+            }
+            summary.truncate(end);
+            summary.push_str("\n\n… truncated; see the full report in the job log\n");
+        }
+
+        Self {
+            outcome: StatusOutcome::decide(violations.len(), failing),
+            title,
+            summary,
+            details_url,
+        }
+    }
+}
+
+/// Where the status is published, detected from the CI environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusProvider {
+    GitHub,
+    GitLab,
+}
+
+impl StatusProvider {
+    /// The CI system running us, or else the host of the repository's remote
+    pub fn detect() -> Self {
+        if std::env::var_os("GITLAB_CI").is_some() || std::env::var_os("CI_API_V4_URL").is_some() {
+            StatusProvider::GitLab
+        } else if std::env::var_os("GITHUB_ACTIONS").is_some() {
+            StatusProvider::GitHub
+        } else {
+            Self::for_remote(RemoteRepository::detect().map(|remote| remote.provider))
+        }
+    }
+
+    /// GitLab for a gitlab.com remote; GitHub otherwise, as before remotes were detected
+    fn for_remote(provider: Option<RemoteProvider>) -> Self {
+        match provider {
+            Some(RemoteProvider::GitLab) => StatusProvider::GitLab,
+            _ => StatusProvider::GitHub,
+        }
+    }
+
+    /// Link to the running job, so the status leads back to the full log
+    pub fn default_details_url(self) -> Option<String> {
+        let var = |name| std::env::var(name).ok().filter(|value: &String| !value.is_empty());
+        match self {
+            StatusProvider::GitHub => {
+                let server = var("GITHUB_SERVER_URL")?;
+                let repo = var("GITHUB_REPOSITORY")?;
+                let run_id = var("GITHUB_RUN_ID")?;
+                Some(format!("{server}/{repo}/actions/runs/{run_id}"))
+            }
+            StatusProvider::GitLab => var("CI_JOB_URL"),
+        }
+    }
+}
+
+impl std::fmt::Display for StatusProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatusProvider::GitHub => write!(f, "GitHub"),
+            StatusProvider::GitLab => write!(f, "GitLab"),
+        }
+    }
+}
+
+/// Publish `status` for `sha` on the detected provider
+pub async fn set_commit_status(
+    provider: StatusProvider,
+    repo: Option<&str>,
+    sha: &str,
+    status: &CommitStatus,
+) -> Result<()> {
+    match provider {
+        StatusProvider::GitHub => {
+            let repo = GitHubIntegration::resolve_repository(repo)?;
+            GitHubIntegration::new().create_check_run(&repo, sha, status).await
+        }
+        StatusProvider::GitLab => {
+            let gitlab = GitLabIntegration::new();
+            let project = repo
+                .map(String::from)
+                .or_else(|| std::env::var("CI_PROJECT_ID").ok())
+                .or_else(|| RemoteRepository::detect_slug(RemoteProvider::GitLab))
+                .ok_or_else(|| anyhow!("GitLab project unknown; set CI_PROJECT_ID"))?;
+            gitlab.set_commit_status(&project, sha, status).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod commit_status_tests {
+    use super::*;
+    use crate::core::Severity;
+    use crate::report::test_support::violation;
+
+    #[test]
+    fn test_outcome_follows_gates() {
+        assert_eq!(StatusOutcome::decide(0, 0), StatusOutcome::Pass);
+        assert_eq!(StatusOutcome::decide(3, 0), StatusOutcome::Neutral);
+        assert_eq!(StatusOutcome::decide(3, 1), StatusOutcome::Fail);
+    }
+
+    #[test]
+    fn test_provider_follows_the_remote_outside_ci() {
+        assert_eq!(
+            StatusProvider::for_remote(Some(RemoteProvider::GitLab)),
+            StatusProvider::GitLab
+        );
+        for provider in [Some(RemoteProvider::GitHub), Some(RemoteProvider::SelfHosted), None] {
+            assert_eq!(StatusProvider::for_remote(provider), StatusProvider::GitHub);
+        }
+    }
+
+    #[test]
+    fn test_status_title_and_summary() {
+        let violations = vec![
+            violation("lib/a.ex", 1, Severity::Critical),
+            violation("lib/b.ex", 2, Severity::Warning),
+        ];
+
+        let status = CommitStatus::new(&violations, 1, "main..feature", None);
+
+        assert_eq!(status.outcome, StatusOutcome::Fail);
+        assert_eq!(
+            status.title,
+            "2 violations (1 critical, 0 major, 1 warning); 1 failed the gate"
+        );
+        assert!(status.summary.contains("main..feature"));
+        assert_eq!(CommitStatus::new(&[], 0, "x", None).title, "No anti-pattern violations");
+    }
+}
@@ -1,10 +1,29 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
 use std::collections::HashMap;
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
-use super::{ClaudeCodeIntegration, FixRequest, FixResult};
-use crate::core::ReviewViolation;
+use super::{
+    apply_fixes_to_file, validate_fix, ClaudeCodeIntegration, FixConflict, FixProvider, FixRequest,
+    FixResult, LineFix, PromptTemplates,
+};
+use crate::core::baseline::{AcceptedRisk, Baseline};
+use crate::core::fix_backup::FixBackup;
+use crate::core::{ProjectDetector, ReviewViolation};
+
+/// Fixes generated at once unless `fix.parallelism` says otherwise
+pub const DEFAULT_PARALLELISM: usize = 4;
+
+/// Calls per fix before a provider error counts as a failed fix
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Wait before retrying a provider error, doubled on each further retry
+const RETRY_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Clone)]
 pub struct BatchFixRequest {
@@ -22,6 +41,8 @@ pub struct BatchFixResult {
     pub skipped_violations: usize,
     pub files_modified: Vec<String>,
     pub fix_details: Vec<FixDetail>,
+    /// Fixes not applied because their line changed after the review
+    pub conflicts: Vec<FixConflict>,
 }
 
 #[derive(Debug, Clone)]
@@ -34,7 +55,32 @@ pub struct FixDetail {
 }
 
 pub struct FixEngine {
-    claude_integration: ClaudeCodeIntegration,
+    provider: Arc<dyn FixProvider>,
+    prompts: PromptTemplates,
+    verify_command: Option<String>,
+    parallelism: usize,
+}
+
+/// Ask `provider` for one fix, retrying provider errors such as rate limits with a growing
+/// delay. A provider that keeps failing yields a failed fix rather than an error, so one
+/// bad reply does not stop the batch.
+async fn generate_fix(provider: &dyn FixProvider, prompt: &str) -> FixResult {
+    let mut attempt = 1;
+    loop {
+        match provider.complete(prompt).await {
+            Ok(response) => return FixResult::from_response(&response, provider.name()),
+            Err(e) if attempt >= MAX_ATTEMPTS => {
+                return FixResult::failed(format!(
+                    "{} execution failed after {attempt} attempts: {e}",
+                    provider.name()
+                ))
+            }
+            Err(_) => {
+                tokio::time::sleep(RETRY_DELAY * 2u32.pow(attempt - 1)).await;
+                attempt += 1;
+            }
+        }
+    }
 }
 
 impl Default for FixEngine {
@@ -45,12 +91,47 @@ impl Default for FixEngine {
 
 impl FixEngine {
     pub fn new() -> Self {
-        Self { claude_integration: ClaudeCodeIntegration::detect() }
+        Self {
+            provider: Arc::new(ClaudeCodeIntegration::detect()),
+            prompts: PromptTemplates::default(),
+            verify_command: None,
+            parallelism: DEFAULT_PARALLELISM,
+        }
+    }
+
+    /// Generate fixes with `provider` instead of Claude Code
+    pub fn with_provider(mut self, provider: Box<dyn FixProvider>) -> Self {
+        self.provider = Arc::from(provider);
+        self
+    }
+
+    /// Ask the provider for at most `parallelism` fixes at a time
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Render fix prompts from project templates instead of the built-in one
+    pub fn with_prompts(mut self, prompts: PromptTemplates) -> Self {
+        self.prompts = prompts;
+        self
+    }
+
+    /// Run `command` through the shell once fixes are written, rolling them back and
+    /// marking them failed when it exits unsuccessfully
+    pub fn with_verify_command(mut self, command: Option<String>) -> Self {
+        self.verify_command = command;
+        self
+    }
+
+    /// Name of the backend generating fixes, for prompts and progress output
+    pub fn provider_name(&self) -> &str {
+        self.provider.name()
     }
 
     pub async fn process_batch_fixes(&self, request: &BatchFixRequest) -> Result<BatchFixResult> {
-        if !self.claude_integration.available {
-            println!("{} Claude Code CLI not available", "⚠️".yellow());
+        if let Err(e) = self.provider.check() {
+            println!("{} {}: {e}", "⚠️".yellow(), self.provider.name());
             return Ok(BatchFixResult {
                 total_violations: request.violations.len(),
                 fixed_violations: 0,
@@ -58,16 +139,29 @@ impl FixEngine {
                 skipped_violations: request.violations.len(),
                 files_modified: vec![],
                 fix_details: vec![],
+                conflicts: vec![],
             });
         }
 
-        println!("🤖 Processing {} violations with Claude Code...", request.violations.len());
+        println!(
+            "🤖 Processing {} violations with {} ({} at a time)...",
+            request.violations.len(),
+            self.provider.name(),
+            self.parallelism
+        );
+
+        let prompts = request
+            .violations
+            .iter()
+            .map(|violation| self.prompts.render_fix(&self.create_fix_request(violation)?))
+            .collect::<Result<Vec<_>>>()?;
+        let fix_results = self.generate_fixes(prompts).await?;
 
         let mut fix_details = Vec::new();
-        let mut files_to_modify: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+        let mut files_to_modify: HashMap<String, Vec<LineFix>> = HashMap::new();
 
-        // Process each violation
-        for (i, violation) in request.violations.iter().enumerate() {
+        // Review and queue each fix, in the order of the violations
+        for ((i, violation), fix_result) in request.violations.iter().enumerate().zip(fix_results) {
             print!(
                 "  [{}/{}] Fixing {} in {}:{}... ",
                 i + 1,
@@ -78,15 +172,12 @@ impl FixEngine {
             );
             io::stdout().flush().unwrap();
 
-            let fix_request = self.create_fix_request(violation)?;
-            let fix_result = self.claude_integration.generate_fix(&fix_request)?;
-
             let mut applied = false;
 
             if fix_result.success && fix_result.confidence >= request.confidence_threshold {
                 if let Some(ref fixed_code) = fix_result.fixed_code {
                     // Validate the fix
-                    if self.claude_integration.validate_fix(
+                    if validate_fix(
                         &violation.content,
                         fixed_code,
                         &format!("{:?}", violation.language).to_lowercase(),
@@ -100,10 +191,13 @@ impl FixEngine {
 
                         if applied && !request.dry_run {
                             // Queue the fix for batch application
-                            files_to_modify
-                                .entry(violation.file_path.clone())
-                                .or_default()
-                                .push((violation.line_number, fixed_code.clone()));
+                            files_to_modify.entry(violation.file_path.clone()).or_default().push(
+                                LineFix {
+                                    line_number: violation.line_number,
+                                    original: violation.content.clone(),
+                                    replacement: fixed_code.clone(),
+                                },
+                            );
                         }
 
                         println!("{}", if applied { "✅ Fixed" } else { "⏭️ Skipped" }.green());
@@ -129,12 +223,42 @@ impl FixEngine {
 
         // Apply all fixes to files (if not dry run)
         let mut files_modified = Vec::new();
+        let mut conflicts = Vec::new();
+        let backup = FixBackup::take(Path::new("."), files_to_modify.keys().map(String::as_str));
         if !request.dry_run {
             for (file_path, fixes) in files_to_modify {
-                if let Err(e) = self.claude_integration.apply_fixes_to_file(&file_path, &fixes) {
-                    eprintln!("❌ Failed to apply fixes to {file_path}: {e}");
-                } else {
-                    files_modified.push(file_path);
+                match apply_fixes_to_file(&file_path, &fixes) {
+                    Ok(file_conflicts) => {
+                        if file_conflicts.len() < fixes.len() {
+                            files_modified.push(file_path);
+                        }
+                        conflicts.extend(file_conflicts);
+                    }
+                    Err(e) => eprintln!("❌ Failed to apply fixes to {file_path}: {e}"),
+                }
+            }
+        }
+        // Fixes after the first for a line are the ones that conflict with it
+        for conflict in &conflicts {
+            if let Some(detail) = fix_details.iter_mut().rev().find(|d| {
+                d.applied
+                    && d.file_path == conflict.file_path
+                    && d.line_number == conflict.line_number
+            }) {
+                detail.applied = false;
+            }
+        }
+
+        if !files_modified.is_empty() {
+            if let Err(reason) = self.verify().await? {
+                backup.restore()?;
+                files_modified.clear();
+                let rolled_back = fix_details.iter().filter(|d| d.applied).count();
+                println!("{} {reason}; rolled back {rolled_back} fix(es)", "❌".red());
+                for detail in fix_details.iter_mut().filter(|d| d.applied) {
+                    detail.applied = false;
+                    detail.fix_result.success = false;
+                    detail.fix_result.error_message = Some(reason.clone());
                 }
             }
         }
@@ -142,7 +266,8 @@ impl FixEngine {
         // Calculate results
         let fixed_violations = fix_details.iter().filter(|d| d.applied).count();
         let failed_violations = fix_details.iter().filter(|d| !d.fix_result.success).count();
-        let skipped_violations = fix_details.len() - fixed_violations - failed_violations;
+        let skipped_violations =
+            fix_details.len() - fixed_violations - failed_violations - conflicts.len();
 
         Ok(BatchFixResult {
             total_violations: request.violations.len(),
@@ -151,9 +276,64 @@ impl FixEngine {
             skipped_violations,
             files_modified,
             fix_details,
+            conflicts,
+        })
+    }
+
+    /// Run the verify command, if any, from the project root. `Ok(Err(reason))` when it
+    /// ran and failed.
+    async fn verify(&self) -> Result<std::result::Result<(), String>> {
+        let Some(command) = &self.verify_command else {
+            return Ok(Ok(()));
+        };
+        let dir = ProjectDetector::detect_project(None)
+            .map(|info| info.root_path)
+            .unwrap_or_else(|_| PathBuf::from("."));
+        println!("🔎 Verifying the fixes with `{command}`...");
+
+        let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+        let status = tokio::process::Command::new(shell)
+            .args([flag, command])
+            .current_dir(&dir)
+            .status()
+            .await
+            .with_context(|| format!("Failed to run the verify command `{command}`"))?;
+        Ok(match status.success() {
+            true => Ok(()),
+            false => Err(format!("Verify command `{command}` failed ({status})")),
         })
     }
 
+    /// Ask the provider for a fix per prompt, `parallelism` at a time, returning the
+    /// results in the order of the prompts
+    async fn generate_fixes(&self, prompts: Vec<String>) -> Result<Vec<FixResult>> {
+        let total = prompts.len();
+        let semaphore = Arc::new(Semaphore::new(self.parallelism));
+        let mut tasks = JoinSet::new();
+        for (index, prompt) in prompts.into_iter().enumerate() {
+            let provider = Arc::clone(&self.provider);
+            let semaphore = Arc::clone(&semaphore);
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                (index, generate_fix(provider.as_ref(), &prompt).await)
+            });
+        }
+
+        let mut results: Vec<Option<FixResult>> = (0..total).map(|_| None).collect();
+        let mut done = 0;
+        while let Some(joined) = tasks.join_next().await {
+            let (index, result) = joined.context("A fix generation task failed")?;
+            results[index] = Some(result);
+            done += 1;
+            print!("\r  ⏳ Generated {done}/{total} fix(es)");
+            io::stdout().flush()?;
+        }
+        if total > 0 {
+            println!();
+        }
+        Ok(results.into_iter().flatten().collect())
+    }
+
     fn create_fix_request(&self, violation: &ReviewViolation) -> Result<FixRequest> {
         Ok(FixRequest {
             file_path: violation.file_path.clone(),
@@ -161,7 +341,14 @@ impl FixEngine {
             original_code: violation.content.clone(),
             violation_description: violation.rule.description.clone(),
             fix_suggestion: violation.fix_suggestion.clone(),
-            language: format!("{:?}", violation.language).to_lowercase(),
+            language: violation.language.to_string(),
+            rule_id: violation.rule.id.clone(),
+            rule_name: violation.rule.name.clone(),
+            severity: violation.severity.to_string(),
+            comment_syntax: violation.language.comment_syntax().describe(),
+            examples: violation.rule.examples.clone(),
+            ai_hint: violation.rule.ai_hint.clone(),
+            captures: violation.captures.clone(),
         })
     }
 
@@ -181,7 +368,7 @@ impl FixEngine {
         println!("\n{}", "After:".green());
         println!("  {}", fixed_code.green());
 
-        print!("\n{} Apply this fix? [y/N/a/q]: ", "❓".cyan());
+        print!("\n{} Apply this fix? [y/N/a/r/q] (r = accept risk): ", "❓".cyan());
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
@@ -193,6 +380,10 @@ impl FixEngine {
                 // TODO: Implement "apply all" functionality
                 Ok(true)
             }
+            "r" | "risk" => {
+                self.accept_risk(violation)?;
+                Ok(false)
+            }
             "q" | "quit" => {
                 println!("Aborting fix process...");
                 std::process::exit(0);
@@ -201,6 +392,33 @@ impl FixEngine {
         }
     }
 
+    /// Records the violation in the project baseline as an accepted risk, so later
+    /// reviews list it as acknowledged instead of reporting it again
+    fn accept_risk(&self, violation: &ReviewViolation) -> Result<()> {
+        let required = AcceptedRisk::requires_reason(violation.severity);
+        let reason = loop {
+            print!("{} Reason{}: ", "📝".cyan(), if required { "" } else { " (optional)" });
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let reason = input.trim().to_string();
+            if !reason.is_empty() || !required {
+                break reason;
+            }
+            println!(
+                "{} A reason is required for {} violations",
+                "⚠️".yellow(),
+                violation.severity
+            );
+        };
+
+        let path = Baseline::project_path()?;
+        Baseline::accept_risk(&path, violation, AcceptedRisk::now(reason))?;
+        println!("{} Accepted as risk in {}", "🛡️".green(), path.display());
+        Ok(())
+    }
+
     pub fn generate_fix_summary(&self, result: &BatchFixResult) {
         println!("\n{}", "🎯 Batch Fix Summary".bold().cyan());
         println!("══════════════════════════════════════");
@@ -209,6 +427,13 @@ impl FixEngine {
         println!("{} Fixed: {}", "✅".green(), result.fixed_violations.to_string().green());
         println!("{} Failed: {}", "❌".red(), result.failed_violations.to_string().red());
         println!("{} Skipped: {}", "⏭️".yellow(), result.skipped_violations.to_string().yellow());
+        if !result.conflicts.is_empty() {
+            println!(
+                "{} Conflicts: {}",
+                "⚠️".yellow(),
+                result.conflicts.len().to_string().yellow()
+            );
+        }
 
         if !result.files_modified.is_empty() {
             println!("\n{} Files modified:", "📝".cyan());
@@ -217,9 +442,24 @@ impl FixEngine {
             }
         }
 
+        if !result.conflicts.is_empty() {
+            println!("\n{} Not applied, the file changed after the review:", "⚠️".yellow());
+            for conflict in &result.conflicts {
+                println!("  • {}: {}", conflict.file_path.cyan(), conflict.reason);
+            }
+        }
+
         // Show detailed results for failed or skipped fixes
-        let problematic_fixes: Vec<_> =
-            result.fix_details.iter().filter(|d| !d.applied || !d.fix_result.success).collect();
+        let is_conflict = |detail: &FixDetail| {
+            result.conflicts.iter().any(|conflict| {
+                conflict.file_path == detail.file_path && conflict.line_number == detail.line_number
+            })
+        };
+        let problematic_fixes: Vec<_> = result
+            .fix_details
+            .iter()
+            .filter(|d| (!d.applied || !d.fix_result.success) && !is_conflict(d))
+            .collect();
 
         if !problematic_fixes.is_empty() {
             println!("\n{} Detailed Results:", "📊".cyan());
@@ -258,7 +498,10 @@ impl FixEngine {
                 );
             }
         }
-        if result.failed_violations > 0 || result.skipped_violations > 0 {
+        if result.failed_violations > 0
+            || result.skipped_violations > 0
+            || !result.conflicts.is_empty()
+        {
             println!("  • Review failed/skipped violations manually");
             println!(
                 "  • Use {} for detailed guidance",
@@ -288,15 +531,14 @@ impl FixEngine {
             violations_by_file.len()
         );
 
+        let confidence_indicator = if self.provider.check().is_ok() {
+            "🤖 High confidence".to_string()
+        } else {
+            format!("❓ {} not available", self.provider.name())
+        };
         for (file_path, file_violations) in violations_by_file {
             println!("\n📁 {}", file_path.bold());
             for violation in file_violations {
-                let confidence_indicator = if self.claude_integration.available {
-                    "🤖 High confidence"
-                } else {
-                    "❓ Claude Code not available"
-                };
-
                 println!(
                     "  {}:{} - {} ({})",
                     violation.line_number.to_string().cyan(),
@@ -328,6 +570,7 @@ impl FixEngine {
 mod fix_engine_tests {
     use super::*;
     use crate::core::{AntiPattern, DetectionMethod, Language, Severity};
+    use std::collections::BTreeMap;
 
     fn create_test_violation() -> ReviewViolation {
         let rule = AntiPattern {
@@ -342,6 +585,10 @@ mod fix_engine_tests {
             claude_code_fixable: true,
             examples: vec![],
             tags: vec![],
+            ai_hint: None,
+            auto_fix: None,
+            skip_comments: false,
+            skip_strings: false,
             enabled: true,
         };
 
@@ -357,6 +604,10 @@ mod fix_engine_tests {
             context_before: vec!["def process_input(input) do".to_string()],
             context_after: vec!["end".to_string()],
             confidence: 0.9,
+            span: None,
+            blame: None,
+            captures: BTreeMap::new(),
+            owners: Vec::new(),
         }
     }
 
@@ -364,7 +615,7 @@ mod fix_engine_tests {
     fn test_fix_engine_creation() {
         let engine = FixEngine::new();
         // Should create without errors
-        assert!(engine.claude_integration.available || !engine.claude_integration.available);
+        assert_eq!(engine.provider_name(), "Claude Code");
     }
 
     #[test]
@@ -398,6 +649,164 @@ mod fix_engine_tests {
         assert_eq!(batch_request.confidence_threshold, 0.7);
     }
 
+    /// Replies with a fixed line, as a model would
+    struct StubProvider;
+
+    #[async_trait::async_trait]
+    impl FixProvider for StubProvider {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn check(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn complete(&self, prompt: &str) -> Result<String> {
+            assert!(prompt.contains("String.to_atom(user_input)"));
+            Ok("```elixir\nString.to_existing_atom(user_input)\n```".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_fixes_use_the_provider() {
+        let engine = FixEngine::new().with_provider(Box::new(StubProvider));
+        let request = BatchFixRequest {
+            violations: vec![create_test_violation()],
+            dry_run: true,
+            interactive: false,
+            confidence_threshold: 0.7,
+        };
+
+        let result = engine.process_batch_fixes(&request).await.unwrap();
+        assert_eq!(result.fixed_violations, 1);
+        assert_eq!(
+            result.fix_details[0].fix_result.fixed_code.as_deref(),
+            Some("String.to_existing_atom(user_input)")
+        );
+        assert!(result.files_modified.is_empty());
+    }
+
+    /// Fails the first call for each prompt, as a rate-limited backend would, and records
+    /// how many calls overlap
+    #[derive(Default)]
+    struct RateLimitedProvider {
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+        seen: std::sync::Mutex<std::collections::HashSet<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl FixProvider for RateLimitedProvider {
+        fn name(&self) -> &str {
+            "limited"
+        }
+
+        fn check(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn complete(&self, prompt: &str) -> Result<String> {
+            use std::sync::atomic::Ordering;
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if self.seen.lock().unwrap().insert(prompt.to_string()) {
+                anyhow::bail!("429 Too Many Requests");
+            }
+            Ok("```elixir\nString.to_existing_atom(user_input)\n```".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_fixes_run_concurrently_and_retry_provider_errors() {
+        let provider = Arc::new(RateLimitedProvider::default());
+        let engine = FixEngine {
+            provider: provider.clone(),
+            prompts: PromptTemplates::default(),
+            verify_command: None,
+            parallelism: 3,
+        };
+        let violations: Vec<ReviewViolation> = (1..=6)
+            .map(|line_number| ReviewViolation { line_number, ..create_test_violation() })
+            .collect();
+        let request = BatchFixRequest {
+            violations,
+            dry_run: true,
+            interactive: false,
+            confidence_threshold: 0.7,
+        };
+
+        let result = engine.process_batch_fixes(&request).await.unwrap();
+
+        assert_eq!((result.fixed_violations, result.failed_violations), (6, 0));
+        let lines: Vec<usize> = result.fix_details.iter().map(|d| d.line_number).collect();
+        assert_eq!(lines, [1, 2, 3, 4, 5, 6]);
+        let max_in_flight = provider.max_in_flight.load(std::sync::atomic::Ordering::SeqCst);
+        assert!((2..=3).contains(&max_in_flight), "{max_in_flight} calls at once");
+    }
+
+    #[tokio::test]
+    async fn test_batch_fixes_report_lines_changed_since_the_review() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("user.ex");
+        std::fs::write(&path, "def f(input) do\n  String.to_atom(other)\nend\n").unwrap();
+        let mut violation = create_test_violation();
+        violation.file_path = path.to_str().unwrap().to_string();
+        violation.line_number = 2;
+
+        let engine = FixEngine::new().with_provider(Box::new(StubProvider));
+        let request = BatchFixRequest {
+            violations: vec![violation],
+            dry_run: false,
+            interactive: false,
+            confidence_threshold: 0.7,
+        };
+        let result = engine.process_batch_fixes(&request).await.unwrap();
+
+        assert_eq!((result.fixed_violations, result.skipped_violations), (0, 0));
+        assert_eq!(result.conflicts.len(), 1);
+        assert!(result.files_modified.is_empty());
+        assert!(std::fs::read_to_string(&path).unwrap().contains("String.to_atom(other)"));
+    }
+
+    #[tokio::test]
+    async fn test_fixes_failing_verification_are_rolled_back() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("user.ex");
+        let original = "def f(user_input) do\n  String.to_atom(user_input)\nend\n";
+        let mut violation = create_test_violation();
+        violation.file_path = path.to_str().unwrap().to_string();
+        violation.line_number = 2;
+        violation.content = "  String.to_atom(user_input)".to_string();
+        let request = BatchFixRequest {
+            violations: vec![violation],
+            dry_run: false,
+            interactive: false,
+            confidence_threshold: 0.7,
+        };
+
+        std::fs::write(&path, original).unwrap();
+        let engine = FixEngine::new()
+            .with_provider(Box::new(StubProvider))
+            .with_verify_command(Some("exit 3".to_string()));
+        let result = engine.process_batch_fixes(&request).await.unwrap();
+        assert_eq!((result.fixed_violations, result.failed_violations), (0, 1));
+        assert!(result.files_modified.is_empty());
+        let error = result.fix_details[0].fix_result.error_message.as_deref().unwrap();
+        assert!(error.contains("`exit 3` failed"), "{error}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+
+        let engine = FixEngine::new()
+            .with_provider(Box::new(StubProvider))
+            .with_verify_command(Some("exit 0".to_string()));
+        let result = engine.process_batch_fixes(&request).await.unwrap();
+        assert_eq!((result.fixed_violations, result.failed_violations), (1, 0));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("to_existing_atom"));
+    }
+
     #[test]
     fn test_preview_batch_fixes() {
         let engine = FixEngine::new();
@@ -418,6 +827,7 @@ mod fix_engine_tests {
             failed_violations: 0,
             skipped_violations: 0,
             files_modified: vec!["test.ex".to_string()],
+            conflicts: vec![],
             fix_details: vec![FixDetail {
                 violation: violation.clone(),
                 fix_result: FixResult {
@@ -0,0 +1,363 @@
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use which::which;
+
+use super::ClaudeCodeIntegration;
+use crate::config::find_project_config;
+use crate::core::ProjectDetector;
+
+/// The provider used when neither `--fix-provider` nor `fix.provider` names one
+pub const DEFAULT_PROVIDER: &str = "claude";
+
+/// Placeholder in a command provider's arguments replaced by the prompt
+const PROMPT_PLACEHOLDER: &str = "{prompt}";
+
+/// A backend that generates fixes for batch fix runs. Providers only carry the prompt to
+/// a model and its reply back; rendering prompts and reading fixes out of the reply is
+/// shared by all of them.
+#[async_trait]
+pub trait FixProvider: Send + Sync {
+    /// Name shown in progress output, e.g. `Claude Code`
+    fn name(&self) -> &str;
+
+    /// Why the provider cannot be used right now, e.g. its CLI is not installed
+    fn check(&self) -> Result<()>;
+
+    /// The model's reply to `prompt`
+    async fn complete(&self, prompt: &str) -> Result<String>;
+}
+
+/// Any CLI that reads a prompt and prints the reply, such as `ollama run codellama` or
+/// an OpenAI CLI. The prompt replaces `{prompt}` in the arguments, or is written to
+/// stdin when no argument has the placeholder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandProvider {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+#[async_trait]
+impl FixProvider for CommandProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&self) -> Result<()> {
+        which(&self.command).with_context(|| format!("{} not found on PATH", self.command))?;
+        Ok(())
+    }
+
+    async fn complete(&self, prompt: &str) -> Result<String> {
+        let takes_argument = self.args.iter().any(|arg| arg.contains(PROMPT_PLACEHOLDER));
+        let args = self.args.iter().map(|arg| arg.replace(PROMPT_PLACEHOLDER, prompt));
+
+        let mut child = tokio::process::Command::new(&self.command)
+            .args(args)
+            .stdin(if takes_argument { Stdio::null() } else { Stdio::piped() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run {}", self.command))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(prompt.as_bytes()).await?;
+        }
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow!("{} failed: {}", self.command, error.trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// An HTTP endpoint taking `{"prompt", "model", "stream": false}` as a JSON POST, which
+/// is Ollama's generate API. The reply is read from `response_field` of a JSON response,
+/// or is the whole body when the response is not JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpProvider {
+    pub name: String,
+    pub url: String,
+    pub model: Option<String>,
+    /// Environment variable holding a token sent as `Authorization: Bearer`
+    pub api_key_env: Option<String>,
+    pub response_field: String,
+}
+
+impl HttpProvider {
+    fn api_key(&self) -> Result<Option<String>> {
+        match &self.api_key_env {
+            Some(variable) => {
+                std::env::var(variable).map(Some).with_context(|| format!("{variable} is not set"))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// The reply in a response body
+    fn reply(&self, body: &str) -> Result<String> {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+            return Ok(body.to_string());
+        };
+        match json.get(&self.response_field).and_then(|reply| reply.as_str()) {
+            Some(reply) => Ok(reply.to_string()),
+            None => bail!("{} replied without a '{}' string field", self.url, self.response_field),
+        }
+    }
+}
+
+#[async_trait]
+impl FixProvider for HttpProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&self) -> Result<()> {
+        self.api_key().map(|_| ())
+    }
+
+    async fn complete(&self, prompt: &str) -> Result<String> {
+        let mut body = serde_json::json!({ "prompt": prompt, "stream": false });
+        if let Some(model) = &self.model {
+            body["model"] = model.clone().into();
+        }
+
+        let mut request = reqwest::Client::new().post(&self.url).json(&body);
+        if let Some(api_key) = self.api_key()? {
+            request = request.bearer_auth(api_key);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", self.url))?
+            .error_for_status()
+            .with_context(|| format!("{} rejected the request", self.url))?;
+        self.reply(&response.text().await?)
+    }
+}
+
+/// One entry of `fix.providers`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum ProviderConfig {
+    /// The Claude Code CLI, as the built-in `claude` provider
+    Claude,
+    Command {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// A local Ollama server
+    Ollama {
+        model: String,
+        #[serde(default = "default_ollama_url")]
+        url: String,
+    },
+    Http {
+        url: String,
+        #[serde(default)]
+        model: Option<String>,
+        #[serde(default)]
+        api_key_env: Option<String>,
+        #[serde(default = "default_response_field")]
+        response_field: String,
+    },
+}
+
+fn default_ollama_url() -> String {
+    "http://localhost:11434/api/generate".to_string()
+}
+
+fn default_response_field() -> String {
+    "response".to_string()
+}
+
+impl ProviderConfig {
+    fn build(&self, name: &str) -> Box<dyn FixProvider> {
+        match self.clone() {
+            ProviderConfig::Claude => Box::new(ClaudeCodeIntegration::detect()),
+            ProviderConfig::Command { command, args } => {
+                Box::new(CommandProvider { name: name.to_string(), command, args })
+            }
+            ProviderConfig::Ollama { model, url } => Box::new(HttpProvider {
+                name: format!("Ollama ({model})"),
+                url,
+                model: Some(model),
+                api_key_env: None,
+                response_field: default_response_field(),
+            }),
+            ProviderConfig::Http { url, model, api_key_env, response_field } => {
+                Box::new(HttpProvider {
+                    name: name.to_string(),
+                    url,
+                    model,
+                    api_key_env,
+                    response_field,
+                })
+            }
+        }
+    }
+}
+
+/// The `fix:` section of the project config: the backends batch fixes can use and which
+/// one is the default, and the command checking the fixes once they are written. `claude`
+/// is always available without configuring it.
+///
+/// ```yaml
+/// fix:
+///   verify_command: mix compile --warnings-as-errors && mix test --stale
+///   parallelism: 8
+///   provider: ollama
+///   providers:
+///     ollama:
+///       type: ollama
+///       model: codellama
+///     openai:
+///       type: command
+///       command: openai
+///       args: [api, chat.completions.create, -m, gpt-4o-mini, -g, user, "{prompt}"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct FixProviderConfig {
+    /// Provider used when `--fix-provider` is not given
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub providers: BTreeMap<String, ProviderConfig>,
+    /// Shell command run from the project root after batch fixes are written; when it
+    /// fails, the fixes are rolled back
+    #[serde(default)]
+    pub verify_command: Option<String>,
+    /// Fixes a batch asks the provider for at once; defaults to 4. Lower it for backends
+    /// with tight rate limits.
+    #[serde(default)]
+    pub parallelism: Option<usize>,
+}
+
+impl FixProviderConfig {
+    /// Read the `fix:` section of a project config file; absent means only `claude`
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct FixSection {
+            #[serde(default)]
+            fix: FixProviderConfig,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<FixSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.map(|section| section.fix).unwrap_or_default())
+    }
+
+    /// The detected project's providers, or only `claude` outside a configured project
+    pub fn load_for_project() -> Result<Self> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(Self::default());
+        };
+        match find_project_config(&project_info.root_path) {
+            Some(config_path) => Self::load(&config_path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// The provider called `name`, or without a name the configured default
+    pub fn provider(&self, name: Option<&str>) -> Result<Box<dyn FixProvider>> {
+        let name = name.or(self.provider.as_deref()).unwrap_or(DEFAULT_PROVIDER);
+        match self.providers.get(name) {
+            Some(config) => Ok(config.build(name)),
+            None if name == DEFAULT_PROVIDER => Ok(ProviderConfig::Claude.build(name)),
+            None => {
+                let mut known: Vec<&str> = self.providers.keys().map(String::as_str).collect();
+                if !known.contains(&DEFAULT_PROVIDER) {
+                    known.insert(0, DEFAULT_PROVIDER);
+                }
+                bail!(
+                    "Unknown fix provider '{name}' (known: {}); configure it under fix.providers",
+                    known.join(", ")
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod fix_provider_tests {
+    use super::*;
+
+    #[test]
+    fn test_providers_resolve_by_name() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+        std::fs::write(
+            &config_path,
+            r#"
+fix:
+  provider: local
+  providers:
+    local:
+      type: ollama
+      model: codellama
+    team:
+      type: http
+      url: https://fixes.example.com/generate
+      response_field: fix
+"#,
+        )
+        .unwrap();
+        let config = FixProviderConfig::load(&config_path).unwrap();
+
+        assert_eq!(config.provider(None).unwrap().name(), "Ollama (codellama)");
+        assert_eq!(config.provider(Some("team")).unwrap().name(), "team");
+        assert_eq!(config.provider(Some("claude")).unwrap().name(), "Claude Code");
+        let error = config.provider(Some("gpt")).err().unwrap().to_string();
+        assert!(error.contains("Unknown fix provider 'gpt' (known: claude, local, team)"));
+
+        std::fs::write(&config_path, "review:\n  fail_on: major\n").unwrap();
+        let config = FixProviderConfig::load(&config_path).unwrap();
+        assert_eq!(config.provider(None).unwrap().name(), "Claude Code");
+
+        std::fs::write(&config_path, "fix:\n  providers:\n    x:\n      type: openai\n").unwrap();
+        assert!(FixProviderConfig::load(&config_path).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_command_provider_passes_the_prompt() {
+        let provider = |args: &[&str]| CommandProvider {
+            name: "echo".to_string(),
+            command: if args.is_empty() { "cat" } else { "echo" }.to_string(),
+            args: args.iter().map(ToString::to_string).collect(),
+        };
+
+        let on_stdin = provider(&[]);
+        assert!(on_stdin.check().is_ok());
+        assert_eq!(on_stdin.complete("fix me").await.unwrap(), "fix me");
+        let as_argument = provider(&["-n", "prompt: {prompt}"]);
+        assert_eq!(as_argument.complete("fix me").await.unwrap(), "prompt: fix me");
+
+        let missing = CommandProvider { command: "no-such-fixer".to_string(), ..provider(&[]) };
+        assert!(missing.check().unwrap_err().to_string().contains("no-such-fixer not found"));
+    }
+
+    #[test]
+    fn test_http_replies_are_read_from_the_response_field() {
+        let provider = HttpProvider {
+            name: "team".to_string(),
+            url: "https://fixes.example.com/generate".to_string(),
+            model: None,
+            api_key_env: Some("PATINGIN_TEST_UNSET_TOKEN".to_string()),
+            response_field: "response".to_string(),
+        };
+
+        assert_eq!(provider.reply(r#"{"response": "ok", "done": true}"#).unwrap(), "ok");
+        assert_eq!(provider.reply("plain text").unwrap(), "plain text");
+        assert!(provider.reply(r#"{"fix": "ok"}"#).is_err());
+        assert!(provider.check().unwrap_err().to_string().contains("is not set"));
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+
+use crate::core::ReviewViolation;
+
+/// How `review --fix` splits the violations between Claude Code sessions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FixSessions {
+    /// One session for every violation
+    #[default]
+    Single,
+    /// `--fix-per-file`: one session per file
+    PerFile,
+    /// `--fix-one-by-one`: one session per violation
+    PerViolation,
+}
+
+impl FixSessions {
+    /// The violations of each session, in file order
+    pub fn split(self, violations: &[ReviewViolation]) -> Vec<Vec<ReviewViolation>> {
+        match self {
+            FixSessions::Single if violations.is_empty() => Vec::new(),
+            FixSessions::Single => vec![violations.to_vec()],
+            FixSessions::PerFile => {
+                let mut by_file: BTreeMap<&str, Vec<ReviewViolation>> = BTreeMap::new();
+                for violation in violations {
+                    by_file.entry(&violation.file_path).or_default().push(violation.clone());
+                }
+                by_file.into_values().collect()
+            }
+            FixSessions::PerViolation => {
+                let mut sorted = violations.to_vec();
+                sorted.sort_by(|a, b| {
+                    (&a.file_path, a.line_number).cmp(&(&b.file_path, b.line_number))
+                });
+                sorted.into_iter().map(|violation| vec![violation]).collect()
+            }
+        }
+    }
+}
+
+/// What happened to the sessions of a `--fix` run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionTally {
+    pub completed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    /// Sessions never reached because the run was aborted
+    pub not_started: usize,
+}
+
+impl std::fmt::Display for SessionTally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} completed, {} failed, {} skipped",
+            self.completed, self.failed, self.skipped
+        )?;
+        if self.not_started > 0 {
+            write!(f, ", {} not started", self.not_started)?;
+        }
+        Ok(())
+    }
+}
+
+/// Short description of a session, e.g. `lib/a.ex (3 violation(s))` or
+/// `lib/a.ex:12 dynamic_atom_creation`
+pub fn describe_session(violations: &[ReviewViolation]) -> String {
+    match violations {
+        [violation] => {
+            format!("{}:{} {}", violation.file_path, violation.line_number, violation.rule.id)
+        }
+        [first, ..] => format!("{} ({} violation(s))", first.file_path, violations.len()),
+        [] => String::new(),
+    }
+}
+
+/// Run `sessions` one after another, asking on `input` before each whether to start it,
+/// skip it or quit. `launch` runs one session and says whether it completed; a launch error
+/// counts as a failed session and the run goes on.
+pub fn run_sessions(
+    sessions: &[Vec<ReviewViolation>],
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    mut launch: impl FnMut(&[ReviewViolation]) -> Result<bool>,
+) -> Result<SessionTally> {
+    let mut tally = SessionTally::default();
+    for (index, session) in sessions.iter().enumerate() {
+        writeln!(
+            out,
+            "\n🧩 Session {}/{}: {}",
+            index + 1,
+            sessions.len(),
+            describe_session(session)
+        )?;
+        write!(out, "❓ Start this session? [Y/s/q] (s = skip, q = quit): ")?;
+        out.flush()?;
+
+        // End of input is taken as quitting, so a closed stdin never starts sessions
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            answer = "q".to_string();
+        }
+        match answer.trim().to_lowercase().as_str() {
+            "s" | "skip" => {
+                tally.skipped += 1;
+                continue;
+            }
+            "q" | "quit" => {
+                tally.not_started = sessions.len() - index;
+                writeln!(out, "Aborting fix sessions...")?;
+                break;
+            }
+            _ => {}
+        }
+
+        match launch(session) {
+            Ok(true) => tally.completed += 1,
+            Ok(false) => {
+                tally.failed += 1;
+                writeln!(out, "❌ Session failed or was cancelled")?;
+            }
+            Err(e) => {
+                tally.failed += 1;
+                writeln!(out, "❌ Session could not be started: {e}")?;
+            }
+        }
+    }
+    Ok(tally)
+}
+
+#[cfg(test)]
+mod fix_sessions_tests {
+    use super::*;
+    use crate::core::Severity;
+    use crate::report::test_support::violation;
+
+    fn violations() -> Vec<ReviewViolation> {
+        vec![
+            violation("lib/b.ex", 7, Severity::Major),
+            violation("lib/a.ex", 9, Severity::Major),
+            violation("lib/b.ex", 2, Severity::Warning),
+        ]
+    }
+
+    fn locations(sessions: &[Vec<ReviewViolation>]) -> Vec<Vec<String>> {
+        sessions
+            .iter()
+            .map(|session| {
+                session.iter().map(|v| format!("{}:{}", v.file_path, v.line_number)).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_sessions() {
+        let violations = violations();
+        assert_eq!(FixSessions::Single.split(&violations).len(), 1);
+        assert!(FixSessions::Single.split(&[]).is_empty());
+        assert_eq!(
+            locations(&FixSessions::PerFile.split(&violations)),
+            [vec!["lib/a.ex:9"], vec!["lib/b.ex:7", "lib/b.ex:2"]]
+        );
+        assert_eq!(
+            locations(&FixSessions::PerViolation.split(&violations)),
+            [vec!["lib/a.ex:9"], vec!["lib/b.ex:2"], vec!["lib/b.ex:7"]]
+        );
+    }
+
+    #[test]
+    fn test_sessions_can_be_skipped_and_aborted() {
+        let sessions = FixSessions::PerViolation.split(&violations());
+        let mut launched = Vec::new();
+        let mut input: &[u8] = b"\ns\nq\n";
+        let mut out = Vec::new();
+
+        let tally = run_sessions(&sessions, &mut input, &mut out, |session| {
+            launched.push(describe_session(session));
+            Ok(false)
+        })
+        .unwrap();
+
+        assert_eq!(launched, ["lib/a.ex:9 test_rule"]);
+        assert_eq!(tally, SessionTally { completed: 0, failed: 1, skipped: 1, not_started: 1 });
+        assert_eq!(tally.to_string(), "0 completed, 1 failed, 1 skipped, 1 not started");
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("🧩 Session 2/3: lib/b.ex:2 test_rule"));
+    }
+
+    #[test]
+    fn test_launch_errors_do_not_stop_the_run() {
+        let sessions = FixSessions::PerFile.split(&violations());
+        let mut input: &[u8] = b"y\n\n";
+        let mut calls = 0;
+
+        let tally = run_sessions(&sessions, &mut input, &mut Vec::new(), |_| {
+            calls += 1;
+            if calls == 1 {
+                anyhow::bail!("claude exited")
+            }
+            Ok(true)
+        })
+        .unwrap();
+
+        assert_eq!(tally, SessionTally { completed: 1, failed: 1, ..Default::default() });
+    }
+}
@@ -0,0 +1,434 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::commit_status::{CommitStatus, StatusOutcome, STATUS_CONTEXT};
+use crate::core::ReviewViolation;
+use crate::git::{RemoteProvider, RemoteRepository};
+use crate::report::html::escape_html;
+use crate::report::markdown::escape_markdown_cell;
+use crate::report::mentions::MentionsConfig;
+use crate::report::severity_breakdown;
+
+/// Hidden marker identifying the comment patingin owns, so reruns edit it instead of adding more
+pub const SUMMARY_COMMENT_MARKER: &str = "<!-- patingin:summary-comment -->";
+
+const DEFAULT_API_URL: &str = "https://api.github.com";
+const PAGE_SIZE: usize = 100;
+
+/// How review results are posted to a pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum CommentMode {
+    /// One review comment per violation, attached to the offending line
+    #[default]
+    Inline,
+    /// A single summary comment that is updated in place on every run
+    Summary,
+}
+
+/// What happened to the sticky summary comment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentAction {
+    Created,
+    Updated,
+}
+
+pub struct GitHubIntegration {
+    token: Option<String>,
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl Default for GitHubIntegration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitHubIntegration {
+    pub fn new() -> Self {
+        let token = std::env::var("GITHUB_TOKEN").ok();
+        let api_url = std::env::var("GITHUB_API_URL")
+            .unwrap_or_else(|_| DEFAULT_API_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        Self { token, api_url, client: reqwest::Client::new() }
+    }
+
+    #[allow(dead_code)]
+    pub fn is_authenticated(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// `owner/name` of the repository, from the argument, `GITHUB_REPOSITORY` or a github.com
+    /// remote
+    pub fn resolve_repository(repo: Option<&str>) -> Result<String> {
+        repo.map(String::from)
+            .or_else(|| std::env::var("GITHUB_REPOSITORY").ok())
+            .filter(|repo| repo.contains('/'))
+            .or_else(|| RemoteRepository::detect_slug(RemoteProvider::GitHub))
+            .ok_or_else(|| anyhow!("GitHub repository unknown; pass --github-repo OWNER/REPO"))
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> Result<reqwest::RequestBuilder> {
+        let token = self
+            .token
+            .as_deref()
+            .ok_or_else(|| anyhow!("GITHUB_TOKEN is not set; it is required to post to GitHub"))?;
+
+        Ok(self
+            .client
+            .request(method, format!("{}{}", self.api_url, path))
+            .bearer_auth(token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", concat!("patingin/", env!("CARGO_PKG_VERSION"))))
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<serde_json::Value> {
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(anyhow!("GitHub API request failed ({status}): {body}"));
+        }
+        Ok(serde_json::from_str(&body).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Create the summary comment on the first run and edit it in place afterwards.
+    pub async fn upsert_summary_comment(
+        &self,
+        repo: &str,
+        pr_number: u64,
+        body: &str,
+    ) -> Result<CommentAction> {
+        self.upsert_marked_comment(repo, pr_number, SUMMARY_COMMENT_MARKER, body).await
+    }
+
+    /// Edit the pull request comment whose body contains `marker`, or create it.
+    /// `body` must contain the marker so the next run finds it again.
+    pub async fn upsert_marked_comment(
+        &self,
+        repo: &str,
+        pr_number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<CommentAction> {
+        let mut page = 1;
+        let existing = loop {
+            let path = format!(
+                "/repos/{repo}/issues/{pr_number}/comments?per_page={PAGE_SIZE}&page={page}"
+            );
+            let comments = self.send(self.request(reqwest::Method::GET, &path)?).await?;
+            let comments = comments.as_array().cloned().unwrap_or_default();
+
+            if let Some(id) = find_marked_comment(&comments, marker) {
+                break Some(id);
+            }
+            if comments.len() < PAGE_SIZE {
+                break None;
+            }
+            page += 1;
+        };
+
+        let payload = serde_json::json!({ "body": body });
+        match existing {
+            Some(comment_id) => {
+                let path = format!("/repos/{repo}/issues/comments/{comment_id}");
+                self.send(self.request(reqwest::Method::PATCH, &path)?.json(&payload)).await?;
+                Ok(CommentAction::Updated)
+            }
+            None => {
+                let path = format!("/repos/{repo}/issues/{pr_number}/comments");
+                self.send(self.request(reqwest::Method::POST, &path)?.json(&payload)).await?;
+                Ok(CommentAction::Created)
+            }
+        }
+    }
+
+    /// Post one pull request review with a comment on each violating line.
+    pub async fn post_inline_review(
+        &self,
+        repo: &str,
+        pr_number: u64,
+        commit_sha: &str,
+        violations: &[ReviewViolation],
+        mentions: &MentionsConfig,
+    ) -> Result<usize> {
+        #[derive(Serialize)]
+        struct InlineComment {
+            path: String,
+            line: usize,
+            side: &'static str,
+            body: String,
+        }
+
+        if violations.is_empty() {
+            return Ok(0);
+        }
+
+        let comments: Vec<InlineComment> = violations
+            .iter()
+            .map(|violation| InlineComment {
+                path: violation.file_path.clone(),
+                line: violation.line_number,
+                side: "RIGHT",
+                body: inline_comment_body(violation),
+            })
+            .collect();
+        let count = comments.len();
+        let mut body = format!("🔍 patingin found {count} anti-pattern violation(s)");
+        if let Some(mentions) = mentions.render(violations) {
+            body.push_str(&format!("\n\n{mentions}"));
+        }
+
+        let payload = serde_json::json!({
+            "commit_id": commit_sha,
+            "event": "COMMENT",
+            "body": body,
+            "comments": comments,
+        });
+        let path = format!("/repos/{repo}/pulls/{pr_number}/reviews");
+        self.send(self.request(reqwest::Method::POST, &path)?.json(&payload)).await?;
+
+        Ok(count)
+    }
+
+    /// Publish a completed `patingin` check run on `sha`, so the repository can require it
+    pub async fn create_check_run(
+        &self,
+        repo: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> Result<()> {
+        let path = format!("/repos/{repo}/check-runs");
+        let payload = check_run_payload(sha, status);
+        self.send(self.request(reqwest::Method::POST, &path)?.json(&payload)).await?;
+        Ok(())
+    }
+}
+
+fn check_run_payload(sha: &str, status: &CommitStatus) -> serde_json::Value {
+    let conclusion = match status.outcome {
+        StatusOutcome::Pass => "success",
+        StatusOutcome::Fail => "failure",
+        StatusOutcome::Neutral => "neutral",
+    };
+    let mut payload = serde_json::json!({
+        "name": STATUS_CONTEXT,
+        "head_sha": sha,
+        "status": "completed",
+        "conclusion": conclusion,
+        "output": { "title": status.title, "summary": status.summary },
+    });
+    if let Some(url) = &status.details_url {
+        payload["details_url"] = serde_json::Value::String(url.clone());
+    }
+    payload
+}
+
+/// Id of the comment carrying `marker`, if any
+fn find_marked_comment(comments: &[serde_json::Value], marker: &str) -> Option<u64> {
+    comments
+        .iter()
+        .find(|comment| comment["body"].as_str().is_some_and(|body| body.contains(marker)))
+        .and_then(|comment| comment["id"].as_u64())
+}
+
+pub(super) fn inline_comment_body(violation: &ReviewViolation) -> String {
+    format!(
+        "{} **{}** (`{}`, {})\n\n💡 {}",
+        violation.severity.icon(),
+        violation.rule.name,
+        violation.rule.id,
+        violation.severity,
+        violation.fix_suggestion
+    )
+}
+
+/// Body of the sticky summary comment: totals plus one collapsible section per file.
+/// Each row carries the violation fingerprint so findings can be tracked across pushes.
+pub fn render_summary_comment(
+    violations: &[ReviewViolation],
+    commit_sha: Option<&str>,
+    mentions: &MentionsConfig,
+) -> String {
+    let mut body = format!("{SUMMARY_COMMENT_MARKER}\n## 🔍 patingin review\n\n");
+
+    if violations.is_empty() {
+        body.push_str("✅ No anti-pattern violations found.\n");
+    } else {
+        let mut by_file: BTreeMap<&str, Vec<&ReviewViolation>> = BTreeMap::new();
+        for violation in violations {
+            by_file.entry(&violation.file_path).or_default().push(violation);
+        }
+
+        let count = |severity| violations.iter().filter(|v| v.severity == severity).count();
+        body.push_str(&format!(
+            "**{} violations** in {} files ({})\n",
+            violations.len(),
+            by_file.len(),
+            severity_breakdown(count, true)
+        ));
+
+        for (file_path, file_violations) in by_file {
+            // The summary line is HTML, where a backtick could still start markdown code
+            body.push_str(&format!(
+                "\n<details>\n<summary><code>{}</code> — {} violation(s)</summary>\n\n",
+                escape_html(file_path).replace('`', "&#96;"),
+                file_violations.len()
+            ));
+            body.push_str(
+                "| Line | Severity | Rule | Fix | Fingerprint |\n|---:|---|---|---|---|\n",
+            );
+            for violation in file_violations {
+                body.push_str(&format!(
+                    "| {} | {} {} | {} (`{}`) | {} | `{}` |\n",
+                    violation.line_number,
+                    violation.severity.icon(),
+                    violation.severity,
+                    escape_markdown_cell(&violation.rule.name),
+                    violation.rule.id,
+                    escape_markdown_cell(&violation.fix_suggestion),
+                    violation.fingerprint()
+                ));
+            }
+            body.push_str("\n</details>\n");
+        }
+    }
+
+    if let Some(mentions) = mentions.render(violations) {
+        body.push_str(&format!("\n{mentions}\n"));
+    }
+
+    let updated_for = commit_sha.map(|sha| format!(" for `{}`", &sha[..sha.len().min(7)]));
+    body.push_str(&format!(
+        "\n<sub>Updated by patingin{}. This comment is edited in place on every run.</sub>\n",
+        updated_for.unwrap_or_default()
+    ));
+    body
+}
+
+#[cfg(test)]
+mod github_tests {
+    use super::*;
+    use crate::core::{AntiPattern, DetectionMethod, Language, Severity};
+
+    fn create_violation(
+        file_path: &str,
+        line_number: usize,
+        severity: Severity,
+    ) -> ReviewViolation {
+        ReviewViolation {
+            rule: AntiPattern {
+                id: "dynamic_atom_creation".to_string(),
+                name: "Dynamic Atom Creation".to_string(),
+                language: Language::Elixir,
+                severity,
+                description: "Atoms are never garbage collected".to_string(),
+                detection_method: DetectionMethod::Regex { pattern: "to_atom".to_string() },
+                fix_suggestion: "Use String.to_existing_atom/1".to_string(),
+                source_url: None,
+                claude_code_fixable: true,
+                examples: vec![],
+                tags: vec![],
+                ai_hint: None,
+                auto_fix: None,
+                skip_comments: false,
+                skip_strings: false,
+                enabled: true,
+            },
+            file_path: file_path.to_string(),
+            line_number,
+            content: "String.to_atom(input)".to_string(),
+            severity,
+            language: Language::Elixir,
+            fix_suggestion: "Use String.to_existing_atom/1 | a whitelist".to_string(),
+            auto_fixable: true,
+            context_before: vec![],
+            context_after: vec![],
+            confidence: 0.85,
+            span: None,
+            blame: None,
+            captures: BTreeMap::new(),
+            owners: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_summary_comment_groups_files() {
+        let violations = vec![
+            create_violation("lib/user.ex", 10, Severity::Critical),
+            create_violation("lib/auth.ex", 4, Severity::Critical),
+            create_violation("lib/user.ex", 20, Severity::Major),
+        ];
+
+        let mentions: MentionsConfig =
+            serde_yaml::from_str("severities:\n  critical: ['@acme/leads']\n").unwrap();
+        let body = render_summary_comment(&violations, Some("0123456789abcdef"), &mentions);
+
+        assert!(body.starts_with(SUMMARY_COMMENT_MARKER));
+        assert!(body.contains("**3 violations** in 2 files (🔴 2 critical, 🟡 1 major"));
+        assert!(body.contains("<summary><code>lib/user.ex</code> — 2 violation(s)</summary>"));
+        assert!(body.find("lib/auth.ex").unwrap() < body.find("lib/user.ex").unwrap());
+        assert!(body.contains(&format!("`{}`", violations[0].fingerprint())));
+        assert!(body.contains("to_existing_atom/1 \\| a whitelist"));
+        assert!(body.contains("for `0123456`"));
+        assert!(body.contains("\n👥 cc @acme/leads (critical)\n"));
+    }
+
+    #[test]
+    fn test_render_summary_comment_escapes_file_paths() {
+        let violations = vec![create_violation("lib/<a> & `b`.ex", 1, Severity::Major)];
+        let body = render_summary_comment(&violations, None, &MentionsConfig::default());
+        assert!(body.contains(
+            "<summary><code>lib/&lt;a&gt; &amp; &#96;b&#96;.ex</code> — 1 violation(s)</summary>"
+        ));
+    }
+
+    #[test]
+    fn test_render_summary_comment_clears_when_clean() {
+        let body = render_summary_comment(&[], None, &MentionsConfig::default());
+        assert!(body.contains(SUMMARY_COMMENT_MARKER));
+        assert!(body.contains("No anti-pattern violations found"));
+        assert!(!body.contains("<details>"));
+    }
+
+    #[test]
+    fn test_find_summary_comment() {
+        let comments = serde_json::json!([
+            { "id": 1, "body": "LGTM" },
+            { "id": 2, "body": format!("{SUMMARY_COMMENT_MARKER}\n## 🔍 patingin review") },
+            { "id": 3, "body": null }
+        ]);
+
+        let comments = comments.as_array().unwrap();
+        assert_eq!(find_marked_comment(comments, SUMMARY_COMMENT_MARKER), Some(2));
+        assert_eq!(find_marked_comment(&comments[..1], SUMMARY_COMMENT_MARKER), None);
+    }
+
+    #[test]
+    fn test_inline_comment_body() {
+        let body = inline_comment_body(&create_violation("lib/user.ex", 1, Severity::Critical));
+        assert!(
+            body.starts_with("🔴 **Dynamic Atom Creation** (`dynamic_atom_creation`, critical)")
+        );
+        assert!(body.contains("💡 Use String.to_existing_atom/1"));
+    }
+
+    #[test]
+    fn test_check_run_payload() {
+        let violations = vec![create_violation("lib/user.ex", 1, Severity::Warning)];
+        let status =
+            CommitStatus::new(&violations, 0, "main..feature", Some("https://ci/1".into()));
+
+        let payload = check_run_payload("abc123", &status);
+
+        assert_eq!(payload["name"], "patingin");
+        assert_eq!(payload["head_sha"], "abc123");
+        assert_eq!(payload["conclusion"], "neutral");
+        assert_eq!(payload["details_url"], "https://ci/1");
+        assert_eq!(payload["output"]["title"], "1 violations (0 critical, 0 major, 1 warning)");
+    }
+}
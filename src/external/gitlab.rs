@@ -0,0 +1,102 @@
+use anyhow::{anyhow, Result};
+
+use super::commit_status::{CommitStatus, StatusOutcome, STATUS_CONTEXT};
+
+const DEFAULT_API_URL: &str = "https://gitlab.com/api/v4";
+
+/// Longest commit status description GitLab stores
+const MAX_DESCRIPTION_CHARS: usize = 255;
+
+pub struct GitLabIntegration {
+    token: Option<String>,
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl Default for GitLabIntegration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitLabIntegration {
+    /// Authenticates with `GITLAB_TOKEN`; the API URL comes from `CI_API_V4_URL` inside
+    /// GitLab CI
+    pub fn new() -> Self {
+        let token = std::env::var("GITLAB_TOKEN").ok();
+        let api_url = std::env::var("CI_API_V4_URL")
+            .unwrap_or_else(|_| DEFAULT_API_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        Self { token, api_url, client: reqwest::Client::new() }
+    }
+
+    /// Set the `patingin` commit status of `sha`. GitLab has no neutral state, so a run
+    /// that only reports violations is a success whose description lists them.
+    pub async fn set_commit_status(
+        &self,
+        project: &str,
+        sha: &str,
+        status: &CommitStatus,
+    ) -> Result<()> {
+        let token = self.token.as_deref().ok_or_else(|| {
+            anyhow!("GITLAB_TOKEN is not set; it is required to set commit statuses")
+        })?;
+
+        let url =
+            format!("{}/projects/{}/statuses/{sha}", self.api_url, project.replace('/', "%2F"));
+        let response = self
+            .client
+            .post(url)
+            .header("PRIVATE-TOKEN", token)
+            .header("User-Agent", concat!("patingin/", env!("CARGO_PKG_VERSION")))
+            .json(&status_payload(status))
+            .send()
+            .await?;
+        let code = response.status();
+        if !code.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("GitLab API request failed ({code}): {body}"));
+        }
+        Ok(())
+    }
+}
+
+fn status_payload(status: &CommitStatus) -> serde_json::Value {
+    let state = match status.outcome {
+        StatusOutcome::Fail => "failed",
+        StatusOutcome::Pass | StatusOutcome::Neutral => "success",
+    };
+    let description: String = status.title.chars().take(MAX_DESCRIPTION_CHARS).collect();
+    let mut payload = serde_json::json!({
+        "state": state,
+        "name": STATUS_CONTEXT,
+        "description": description,
+    });
+    if let Some(url) = &status.details_url {
+        payload["target_url"] = serde_json::Value::String(url.clone());
+    }
+    payload
+}
+
+#[cfg(test)]
+mod gitlab_tests {
+    use super::*;
+
+    #[test]
+    fn test_neutral_runs_succeed_with_their_counts() {
+        let status = CommitStatus {
+            outcome: StatusOutcome::Neutral,
+            title: "2 violations (0 critical, 0 major, 2 warning)".to_string(),
+            summary: String::new(),
+            details_url: Some("https://gitlab.example.com/jobs/1".to_string()),
+        };
+
+        let payload = status_payload(&status);
+
+        assert_eq!(payload["state"], "success");
+        assert_eq!(payload["name"], "patingin");
+        assert_eq!(payload["description"], "2 violations (0 critical, 0 major, 2 warning)");
+        assert_eq!(payload["target_url"], "https://gitlab.example.com/jobs/1");
+    }
+}
@@ -1,10 +1,27 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
 use std::fs;
 use std::process::Command;
 use tempfile::NamedTempFile;
 use which::which;
 
+use crate::core::CodeExample;
+
+pub mod bitbucket;
+pub mod commit_status;
 pub mod fix_engine;
+pub mod fix_provider;
+pub mod fix_sessions;
+pub mod github;
+pub mod gitlab;
+pub mod prompts;
+pub mod releases;
+
+pub use bitbucket::BitbucketIntegration;
+pub use fix_provider::{FixProvider, FixProviderConfig};
+pub use github::GitHubIntegration;
+pub use prompts::PromptTemplates;
 
 pub struct ClaudeCodeIntegration {
     pub available: bool,
@@ -20,6 +37,16 @@ pub struct FixRequest {
     pub violation_description: String,
     pub fix_suggestion: String,
     pub language: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub severity: String,
+    /// How comments are written in the language, so added comments use the right tokens
+    pub comment_syntax: Option<String>,
+    /// The rule's bad/good pairs, given to the model as few-shot examples
+    pub examples: Vec<CodeExample>,
+    pub ai_hint: Option<String>,
+    /// What the rule's capture groups matched, e.g. the offending identifier
+    pub captures: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +57,91 @@ pub struct FixResult {
     pub confidence: f64,
 }
 
+impl FixResult {
+    pub fn failed(error_message: String) -> Self {
+        Self {
+            success: false,
+            fixed_code: None,
+            error_message: Some(error_message),
+            confidence: 0.0,
+        }
+    }
+
+    /// The fix in a provider's reply, which may wrap the code in a markdown block
+    pub fn from_response(response: &str, provider: &str) -> Self {
+        let cleaned_response = response.trim();
+
+        if cleaned_response.is_empty() {
+            return Self::failed(format!("Empty response from {provider}"));
+        }
+
+        // Extract code from markdown blocks if present
+        let fixed_code = if cleaned_response.contains("```") {
+            extract_code_from_markdown(cleaned_response)
+        } else {
+            cleaned_response.to_string()
+        };
+
+        // Calculate confidence based on response quality
+        let confidence = calculate_confidence(&fixed_code);
+
+        Self { success: true, fixed_code: Some(fixed_code), error_message: None, confidence }
+    }
+}
+
+fn extract_code_from_markdown(response: &str) -> String {
+    // Find code blocks in markdown
+    let lines: Vec<&str> = response.lines().collect();
+    let mut in_code_block = false;
+    let mut code_lines = Vec::new();
+
+    for line in lines {
+        if line.starts_with("```") {
+            if in_code_block {
+                break; // End of code block
+            } else {
+                in_code_block = true; // Start of code block
+            }
+        } else if in_code_block {
+            code_lines.push(line);
+        }
+    }
+
+    if code_lines.is_empty() {
+        // Fallback: return the whole response if no code blocks found
+        response.to_string()
+    } else {
+        code_lines.join("\n")
+    }
+}
+
+fn calculate_confidence(fixed_code: &str) -> f64 {
+    // Simple heuristics for confidence calculation
+    let mut confidence: f64 = 0.7; // Base confidence
+
+    // Increase confidence if code looks structured
+    if fixed_code.contains("def ")
+        || fixed_code.contains("function ")
+        || fixed_code.contains("defmodule ")
+    {
+        confidence += 0.1;
+    }
+
+    // Increase confidence if code has proper syntax elements
+    if fixed_code.contains("(") && fixed_code.contains(")") {
+        confidence += 0.1;
+    }
+
+    // Decrease confidence if response looks like an explanation
+    if fixed_code.to_lowercase().contains("here's")
+        || fixed_code.to_lowercase().contains("this code")
+    {
+        confidence -= 0.3;
+    }
+
+    confidence.clamp(0.0, 1.0)
+}
+
 impl ClaudeCodeIntegration {
     pub fn detect() -> Self {
         let (available, command, version) = if which("claude-code").is_ok() {
@@ -55,65 +167,16 @@ impl ClaudeCodeIntegration {
         })
     }
 
-    pub fn generate_fix(&self, request: &FixRequest) -> Result<FixResult> {
-        if !self.available {
-            return Ok(FixResult {
-                success: false,
-                fixed_code: None,
-                error_message: Some("Claude Code CLI not available".to_string()),
-                confidence: 0.0,
-            });
-        }
-
-        // Create a focused prompt for Claude Code
-        let prompt = self.create_fix_prompt(request);
-
-        // Execute Claude Code with the prompt
-        match self.execute_claude_code(&prompt) {
-            Ok(response) => self.parse_claude_response(&response, request),
-            Err(e) => Ok(FixResult {
-                success: false,
-                fixed_code: None,
-                error_message: Some(format!("Claude Code execution failed: {e}")),
-                confidence: 0.0,
-            }),
-        }
-    }
-
-    fn create_fix_prompt(&self, request: &FixRequest) -> String {
-        format!(
-            r#"Fix this {language} code violation:
-
-File: {file_path}
-Line: {line_number}
-
-Issue: {violation_description}
-Suggestion: {fix_suggestion}
-
-Original code:
-```{language}
-{original_code}
-```
-
-Please provide ONLY the fixed code without explanations. Return the corrected line(s) that should replace the original code."#,
-            language = request.language,
-            file_path = request.file_path,
-            line_number = request.line_number,
-            violation_description = request.violation_description,
-            fix_suggestion = request.fix_suggestion,
-            original_code = request.original_code
-        )
-    }
-
-    fn execute_claude_code(&self, prompt: &str) -> Result<String> {
+    async fn execute_claude_code(&self, prompt: &str) -> Result<String> {
         // Create a temporary file for the prompt
         let temp_file = NamedTempFile::new()?;
         fs::write(temp_file.path(), prompt)?;
 
-        // Execute Claude Code with the prompt file
-        let output = Command::new(&self.command)
+        // Execute Claude Code with the prompt file, without blocking other fixes in flight
+        let output = tokio::process::Command::new(&self.command)
             .args(["--file", temp_file.path().to_str().unwrap()])
-            .output()?;
+            .output()
+            .await?;
 
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -122,216 +185,254 @@ Please provide ONLY the fixed code without explanations. Return the corrected li
 
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
+}
 
-    fn parse_claude_response(&self, response: &str, _request: &FixRequest) -> Result<FixResult> {
-        // Parse the Claude Code response
-        let cleaned_response = response.trim();
+#[async_trait]
+impl FixProvider for ClaudeCodeIntegration {
+    fn name(&self) -> &str {
+        "Claude Code"
+    }
 
-        if cleaned_response.is_empty() {
-            return Ok(FixResult {
-                success: false,
-                fixed_code: None,
-                error_message: Some("Empty response from Claude Code".to_string()),
-                confidence: 0.0,
-            });
+    fn check(&self) -> Result<()> {
+        if !self.available {
+            bail!("Claude Code CLI not available");
         }
+        Ok(())
+    }
 
-        // Extract code from markdown blocks if present
-        let fixed_code = if cleaned_response.contains("```") {
-            self.extract_code_from_markdown(cleaned_response)
-        } else {
-            cleaned_response.to_string()
-        };
+    async fn complete(&self, prompt: &str) -> Result<String> {
+        self.execute_claude_code(prompt).await
+    }
+}
 
-        // Calculate confidence based on response quality
-        let confidence = self.calculate_confidence(&fixed_code);
+/// A fix for one line, applied only while the line still reads `original`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineFix {
+    pub line_number: usize,
+    pub original: String,
+    pub replacement: String,
+}
 
-        Ok(FixResult {
-            success: true,
-            fixed_code: Some(fixed_code),
-            error_message: None,
-            confidence,
-        })
-    }
+/// A fix left unapplied because the file no longer has the reviewed line
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixConflict {
+    pub file_path: String,
+    pub line_number: usize,
+    pub reason: String,
+}
+
+/// Apply `fixes` to the file on disk, which may have been edited since the review (or
+/// differ from the staged content that was reviewed). Each fix goes to the line that still
+/// reads its original content: the reviewed line when it is unchanged, or else the one
+/// line with that content. Fixes whose line was changed, removed or is ambiguous are
+/// returned as conflicts, and those lines are left as they are.
+pub fn apply_fixes_to_file(file_path: &str, fixes: &[LineFix]) -> Result<Vec<FixConflict>> {
+    let original_content = fs::read_to_string(file_path)?;
+    let mut lines: Vec<String> = original_content.lines().map(|s| s.to_string()).collect();
+    let matches = |line: &str, original: &str| line.trim_end() == original.trim_end();
+
+    let mut conflicts = Vec::new();
+    let mut targets: Vec<(usize, &str)> = Vec::new();
+    for fix in fixes {
+        let conflict = |reason: String| FixConflict {
+            file_path: file_path.to_string(),
+            line_number: fix.line_number,
+            reason,
+        };
 
-    fn extract_code_from_markdown(&self, response: &str) -> String {
-        // Find code blocks in markdown
-        let lines: Vec<&str> = response.lines().collect();
-        let mut in_code_block = false;
-        let mut code_lines = Vec::new();
-
-        for line in lines {
-            if line.starts_with("```") {
-                if in_code_block {
-                    break; // End of code block
-                } else {
-                    in_code_block = true; // Start of code block
+        let reviewed_line = fix
+            .line_number
+            .checked_sub(1)
+            .filter(|&index| lines.get(index).is_some_and(|line| matches(line, &fix.original)));
+        let target = match reviewed_line {
+            Some(index) => index,
+            None => {
+                let moved: Vec<usize> = (0..lines.len())
+                    .filter(|&index| matches(&lines[index], &fix.original))
+                    .collect();
+                match moved[..] {
+                    [index] => index,
+                    [] => {
+                        conflicts.push(conflict(format!(
+                            "line {} changed since the review",
+                            fix.line_number
+                        )));
+                        continue;
+                    }
+                    _ => {
+                        conflicts.push(conflict(format!(
+                            "line {} changed since the review and its content now appears {} \
+                             times",
+                            fix.line_number,
+                            moved.len()
+                        )));
+                        continue;
+                    }
                 }
-            } else if in_code_block {
-                code_lines.push(line);
             }
-        }
+        };
 
-        if code_lines.is_empty() {
-            // Fallback: return the whole response if no code blocks found
-            response.to_string()
-        } else {
-            code_lines.join("\n")
+        if targets.iter().any(|(index, _)| *index == target) {
+            conflicts.push(conflict(format!("line {} already has a fix", target + 1)));
+            continue;
         }
+        targets.push((target, &fix.replacement));
     }
 
-    fn calculate_confidence(&self, fixed_code: &str) -> f64 {
-        // Simple heuristics for confidence calculation
-        let mut confidence: f64 = 0.7; // Base confidence
-
-        // Increase confidence if code looks structured
-        if fixed_code.contains("def ")
-            || fixed_code.contains("function ")
-            || fixed_code.contains("defmodule ")
-        {
-            confidence += 0.1;
-        }
-
-        // Increase confidence if code has proper syntax elements
-        if fixed_code.contains("(") && fixed_code.contains(")") {
-            confidence += 0.1;
-        }
-
-        // Decrease confidence if response looks like an explanation
-        if fixed_code.to_lowercase().contains("here's")
-            || fixed_code.to_lowercase().contains("this code")
-        {
-            confidence -= 0.3;
-        }
-
-        confidence.clamp(0.0, 1.0)
+    if targets.is_empty() {
+        return Ok(conflicts);
+    }
+    for (index, replacement) in targets {
+        lines[index] = replacement.to_string();
     }
 
-    pub fn apply_fixes_to_file(&self, file_path: &str, fixes: &[(usize, String)]) -> Result<()> {
-        // Read the original file
-        let original_content = fs::read_to_string(file_path)?;
-        let mut lines: Vec<String> = original_content.lines().map(|s| s.to_string()).collect();
-
-        // Apply fixes in reverse order (highest line number first) to maintain line numbers
-        let mut sorted_fixes = fixes.to_vec();
-        sorted_fixes.sort_by(|a, b| b.0.cmp(&a.0));
+    // Keep the file's line endings and final newline
+    let line_ending = if original_content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut modified_content = lines.join(line_ending);
+    if original_content.ends_with('\n') {
+        modified_content.push_str(line_ending);
+    }
+    fs::write(file_path, modified_content)?;
 
-        for (line_number, fixed_line) in sorted_fixes {
-            if line_number > 0 && line_number <= lines.len() {
-                lines[line_number - 1] = fixed_line;
-            }
-        }
+    Ok(conflicts)
+}
 
-        // Write the modified content back to the file
-        let modified_content = lines.join("\n");
-        fs::write(file_path, modified_content)?;
+pub fn validate_fix(original: &str, fixed: &str, language: &str) -> Result<bool> {
+    // Basic validation to ensure the fix is reasonable
 
-        Ok(())
+    // Check if the fix is not empty
+    if fixed.trim().is_empty() {
+        return Ok(false);
     }
 
-    pub fn validate_fix(&self, original: &str, fixed: &str, language: &str) -> Result<bool> {
-        // Basic validation to ensure the fix is reasonable
-
-        // Check if the fix is not empty
-        if fixed.trim().is_empty() {
-            return Ok(false);
-        }
-
-        // Check if the fix is not identical to original
-        if original.trim() == fixed.trim() {
-            return Ok(false);
-        }
+    // Check if the fix is not identical to original
+    if original.trim() == fixed.trim() {
+        return Ok(false);
+    }
 
-        // Language-specific basic syntax validation
-        match language.to_lowercase().as_str() {
-            "elixir" => self.validate_elixir_syntax(fixed),
-            "javascript" | "typescript" => self.validate_javascript_syntax(fixed),
-            "python" => self.validate_python_syntax(fixed),
-            "rust" => self.validate_rust_syntax(fixed),
-            _ => Ok(true), // Default to valid for unknown languages
-        }
+    // Language-specific basic syntax validation
+    match language.to_lowercase().as_str() {
+        "elixir" => validate_elixir_syntax(fixed),
+        "javascript" | "typescript" => validate_javascript_syntax(fixed),
+        "python" => validate_python_syntax(fixed),
+        "rust" => validate_rust_syntax(fixed),
+        _ => Ok(true), // Default to valid for unknown languages
     }
+}
 
-    fn validate_elixir_syntax(&self, code: &str) -> Result<bool> {
-        // Basic Elixir syntax checks
-        let balanced_parens = self.check_balanced_brackets(code, '(', ')');
-        let balanced_braces = self.check_balanced_brackets(code, '{', '}');
-        let balanced_brackets = self.check_balanced_brackets(code, '[', ']');
+fn validate_elixir_syntax(code: &str) -> Result<bool> {
+    // Basic Elixir syntax checks
+    let balanced_parens = check_balanced_brackets(code, '(', ')');
+    let balanced_braces = check_balanced_brackets(code, '{', '}');
+    let balanced_brackets = check_balanced_brackets(code, '[', ']');
 
-        Ok(balanced_parens && balanced_braces && balanced_brackets)
-    }
+    Ok(balanced_parens && balanced_braces && balanced_brackets)
+}
 
-    fn validate_javascript_syntax(&self, code: &str) -> Result<bool> {
-        // Basic JavaScript syntax checks
-        let balanced_parens = self.check_balanced_brackets(code, '(', ')');
-        let balanced_braces = self.check_balanced_brackets(code, '{', '}');
-        let balanced_brackets = self.check_balanced_brackets(code, '[', ']');
+fn validate_javascript_syntax(code: &str) -> Result<bool> {
+    // Basic JavaScript syntax checks
+    let balanced_parens = check_balanced_brackets(code, '(', ')');
+    let balanced_braces = check_balanced_brackets(code, '{', '}');
+    let balanced_brackets = check_balanced_brackets(code, '[', ']');
 
-        Ok(balanced_parens && balanced_braces && balanced_brackets)
-    }
+    Ok(balanced_parens && balanced_braces && balanced_brackets)
+}
 
-    fn validate_python_syntax(&self, code: &str) -> Result<bool> {
-        // Basic Python syntax checks
-        let balanced_parens = self.check_balanced_brackets(code, '(', ')');
-        let balanced_brackets = self.check_balanced_brackets(code, '[', ']');
-
-        // Check for basic Python indentation (simplified)
-        let lines: Vec<&str> = code.lines().collect();
-        for line in lines {
-            if !line.trim().is_empty() && !line.starts_with(' ') && !line.starts_with('\t') {
-                // Allow non-indented lines (top-level statements)
-                continue;
-            }
+fn validate_python_syntax(code: &str) -> Result<bool> {
+    // Basic Python syntax checks
+    let balanced_parens = check_balanced_brackets(code, '(', ')');
+    let balanced_brackets = check_balanced_brackets(code, '[', ']');
+
+    // Check for basic Python indentation (simplified)
+    let lines: Vec<&str> = code.lines().collect();
+    for line in lines {
+        if !line.trim().is_empty() && !line.starts_with(' ') && !line.starts_with('\t') {
+            // Allow non-indented lines (top-level statements)
+            continue;
         }
-
-        Ok(balanced_parens && balanced_brackets)
     }
 
-    fn validate_rust_syntax(&self, code: &str) -> Result<bool> {
-        // Basic Rust syntax checks
-        let balanced_parens = self.check_balanced_brackets(code, '(', ')');
-        let balanced_braces = self.check_balanced_brackets(code, '{', '}');
-        let balanced_brackets = self.check_balanced_brackets(code, '[', ']');
+    Ok(balanced_parens && balanced_brackets)
+}
 
-        Ok(balanced_parens && balanced_braces && balanced_brackets)
-    }
+fn validate_rust_syntax(code: &str) -> Result<bool> {
+    // Basic Rust syntax checks
+    let balanced_parens = check_balanced_brackets(code, '(', ')');
+    let balanced_braces = check_balanced_brackets(code, '{', '}');
+    let balanced_brackets = check_balanced_brackets(code, '[', ']');
 
-    fn check_balanced_brackets(&self, code: &str, open: char, close: char) -> bool {
-        let mut count = 0;
-        for ch in code.chars() {
-            if ch == open {
-                count += 1;
-            } else if ch == close {
-                count -= 1;
-                if count < 0 {
-                    return false;
-                }
+    Ok(balanced_parens && balanced_braces && balanced_brackets)
+}
+
+fn check_balanced_brackets(code: &str, open: char, close: char) -> bool {
+    let mut count = 0;
+    for ch in code.chars() {
+        if ch == open {
+            count += 1;
+        } else if ch == close {
+            count -= 1;
+            if count < 0 {
+                return false;
             }
         }
-        count == 0
     }
+    count == 0
 }
 
-#[allow(dead_code)]
-pub struct GitHubIntegration {
-    token: Option<String>,
-}
+#[cfg(test)]
+mod fix_application_tests {
+    use super::*;
 
-impl Default for GitHubIntegration {
-    fn default() -> Self {
-        Self::new()
+    fn fix(line_number: usize, original: &str, replacement: &str) -> LineFix {
+        LineFix {
+            line_number,
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+        }
     }
-}
 
-impl GitHubIntegration {
-    #[allow(dead_code)]
-    pub fn new() -> Self {
-        let token = std::env::var("GITHUB_TOKEN").ok();
-        Self { token }
+    #[test]
+    fn test_fixes_follow_moved_lines_and_skip_changed_ones() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("a.ex");
+        // Edited after the review: a line inserted at the top, line 3 rewritten
+        std::fs::write(&path, "# new\natom(a)\natom(b)\nrewritten\nend\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let conflicts = apply_fixes_to_file(
+            path,
+            &[
+                fix(1, "atom(a)", "safe(a)"),
+                fix(2, "atom(b)", "safe(b)"),
+                fix(3, "atom(c)", "safe(c)"),
+                fix(3, "atom(b)", "other(b)"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "# new\nsafe(a)\nsafe(b)\nrewritten\nend\n"
+        );
+        let reasons: Vec<&str> = conflicts.iter().map(|c| c.reason.as_str()).collect();
+        assert_eq!(reasons, ["line 3 changed since the review", "line 3 already has a fix"]);
     }
 
-    #[allow(dead_code)]
-    pub fn is_authenticated(&self) -> bool {
-        self.token.is_some()
+    #[test]
+    fn test_ambiguous_lines_are_left_alone() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("a.ex");
+        std::fs::write(&path, "x\r\natom(a)\r\natom(a)\r\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let conflicts = apply_fixes_to_file(path, &[fix(1, "atom(a)", "safe(a)")]).unwrap();
+        assert_eq!(
+            conflicts[0].reason,
+            "line 1 changed since the review and its content now appears 2 times"
+        );
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "x\r\natom(a)\r\natom(a)\r\n");
+
+        apply_fixes_to_file(path, &[fix(2, "atom(a)", "safe(a)")]).unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "x\r\nsafe(a)\r\natom(a)\r\n");
     }
 }
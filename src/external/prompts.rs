@@ -0,0 +1,486 @@
+use anyhow::{Context, Result};
+use minijinja::{context, Environment, UndefinedBehavior, Value};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::FixRequest;
+use crate::config::find_project_config;
+use crate::core::{ProjectDetector, ReviewViolation, Severity};
+
+/// Prompt for fixing a single violation in a batch fix run
+pub const DEFAULT_FIX_TEMPLATE: &str = r#"Fix this {{ language }} code violation:
+
+File: {{ file_path }}
+Line: {{ line_number }}
+
+Issue: {{ violation_description }}
+Suggestion: {{ fix_suggestion }}
+{% if captures %}
+Matched: {% for name, value in captures | items %}{{ name }} = `{{ value }}`{{ ", " if not loop.last }}{% endfor %}
+
+{% endif %}
+
+Original code:
+```{{ language }}
+{{ original_code }}
+```
+
+{% if ai_hint %}
+Guidance: {{ ai_hint }}
+
+{% endif %}
+{% for example in examples %}
+{% if loop.first %}
+Examples of this fix:
+
+{% endif %}
+Before:
+```{{ language }}
+{{ example.bad }}
+```
+After:
+```{{ language }}
+{{ example.good }}
+```
+{% if example.explanation %}
+Why: {{ example.explanation }}
+{% endif %}
+
+{% endfor %}
+{% if comment_syntax %}
+If you add a comment, {{ language }} uses {{ comment_syntax }}.
+
+{% endif %}
+{% if instructions %}
+{{ instructions }}
+
+{% endif %}
+Please provide ONLY the fixed code without explanations. Return the corrected line(s) that should replace the original code."#;
+
+/// Opening query of the interactive session (`review --fix`)
+pub const DEFAULT_SESSION_TEMPLATE: &str = r#"Fix these code quality violations in my project:
+
+PROJECT: {{ project.name }} ({{ project.languages | join(", ") if project.languages else "Unknown" }})
+FILES AFFECTED: {{ files_affected }} files with {{ violations | length }} violations
+
+VIOLATIONS FOUND:
+
+{% for violation in violations %}
+📁 {{ violation.file_path }}:{{ violation.line_number }}
+{{ violation.severity_icon }} {{ violation.severity | upper }}: {{ violation.rule_name }} ({{ violation.rule_id }})
+   Problem: {{ violation.description }}
+   Code:
+{% for line in violation.context_before %}
+   {{ line.number }} │ {{ line.text }}
+{% endfor %}
+   {{ violation.line_number }} │ {{ violation.content }}  ← VIOLATION
+{% for line in violation.context_after %}
+   {{ line.number }} │ {{ line.text }}
+{% endfor %}
+   Fix: {{ violation.fix_suggestion }}
+{% if violation.captures %}
+   Matched: {% for name, value in violation.captures | items %}{{ name }} = `{{ value }}`{{ ", " if not loop.last }}{% endfor %}
+
+{% endif %}
+{% if violation.ai_hint %}
+   Hint: {{ violation.ai_hint }}
+{% endif %}
+
+{% endfor %}
+{% for example in examples %}
+{% if loop.first %}
+EXAMPLES OF EACH FIX:
+
+{% endif %}
+{{ example.rule_name }} ({{ example.rule_id }})
+   Before: {{ example.bad }}
+   After: {{ example.good }}
+
+{% endfor %}
+{% if instructions %}
+{{ instructions }}
+
+{% endif %}
+Please help me fix these issues interactively. Show me the problems and guide me through solutions."#;
+
+/// The `prompts:` section of the project config. Templates use minijinja (Jinja2) syntax;
+/// unset templates fall back to the built-in ones.
+///
+/// ```yaml
+/// prompts:
+///   instructions: Follow our style guide at https://example.com/style
+///   fix: |
+///     Fix {{ rule_name }} in {{ file_path }}:{{ line_number }}.
+///     {{ original_code }}
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PromptTemplates {
+    /// Template for fixing one violation
+    #[serde(default)]
+    pub fix: Option<String>,
+    /// Template for the interactive session covering every violation
+    #[serde(default)]
+    pub session: Option<String>,
+    /// Extra instructions, exposed to templates as `instructions` and included by the
+    /// built-in templates
+    #[serde(default)]
+    pub instructions: Option<String>,
+    /// Bad/good example pairs per rule given to the model as few-shot context; each adds
+    /// tokens to every prompt, so 0 turns them off
+    #[serde(default = "default_few_shot_examples")]
+    pub few_shot_examples: usize,
+}
+
+fn default_few_shot_examples() -> usize {
+    2
+}
+
+impl Default for PromptTemplates {
+    fn default() -> Self {
+        Self {
+            fix: None,
+            session: None,
+            instructions: None,
+            few_shot_examples: default_few_shot_examples(),
+        }
+    }
+}
+
+/// A violation as templates see it
+#[derive(Debug, Serialize)]
+struct PromptViolation<'a> {
+    file_path: &'a str,
+    line_number: usize,
+    severity: Severity,
+    severity_icon: &'static str,
+    rule_id: &'a str,
+    rule_name: &'a str,
+    description: &'a str,
+    content: &'a str,
+    context_before: Vec<PromptLine<'a>>,
+    context_after: Vec<PromptLine<'a>>,
+    fix_suggestion: &'a str,
+    ai_hint: Option<&'a str>,
+    captures: &'a BTreeMap<String, String>,
+}
+
+/// A rule's example, for the session's few-shot section
+#[derive(Debug, Serialize)]
+struct PromptExample<'a> {
+    rule_id: &'a str,
+    rule_name: &'a str,
+    bad: &'a str,
+    good: &'a str,
+    explanation: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct PromptLine<'a> {
+    number: usize,
+    text: &'a str,
+}
+
+impl<'a> From<&'a ReviewViolation> for PromptViolation<'a> {
+    fn from(violation: &'a ReviewViolation) -> Self {
+        let first_before = violation.line_number.saturating_sub(violation.context_before.len());
+        let numbered = |first: usize, lines: &'a [String]| {
+            lines
+                .iter()
+                .enumerate()
+                .map(|(index, text)| PromptLine { number: first + index, text })
+                .collect()
+        };
+
+        Self {
+            file_path: &violation.file_path,
+            line_number: violation.line_number,
+            severity: violation.severity,
+            severity_icon: violation.severity.icon(),
+            rule_id: &violation.rule.id,
+            rule_name: &violation.rule.name,
+            description: &violation.rule.description,
+            content: &violation.content,
+            context_before: numbered(first_before, &violation.context_before),
+            context_after: numbered(violation.line_number + 1, &violation.context_after),
+            fix_suggestion: &violation.fix_suggestion,
+            ai_hint: violation.rule.ai_hint.as_deref(),
+            captures: &violation.captures,
+        }
+    }
+}
+
+impl PromptTemplates {
+    /// Read the `prompts:` section of a project config file, checking the templates compile
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct PromptsSection {
+            #[serde(default)]
+            prompts: PromptTemplates,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: PromptsSection = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+        let templates = section.prompts;
+        let mut env = environment();
+        for (name, source) in [("fix", &templates.fix), ("session", &templates.session)] {
+            if let Some(source) = source {
+                env.add_template(name, source)
+                    .with_context(|| format!("Invalid prompts.{name} template"))?;
+            }
+        }
+        Ok(templates)
+    }
+
+    /// Templates from the detected project's config, or the built-in ones
+    pub fn load_for_project() -> Result<Self> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(Self::default());
+        };
+        match find_project_config(&project_info.root_path) {
+            Some(config_path) => Self::load(&config_path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn render_fix(&self, request: &FixRequest) -> Result<String> {
+        render(
+            "fix",
+            self.fix.as_deref().unwrap_or(DEFAULT_FIX_TEMPLATE),
+            context! {
+                language => request.language,
+                file_path => request.file_path,
+                line_number => request.line_number,
+                rule_id => request.rule_id,
+                rule_name => request.rule_name,
+                severity => request.severity,
+                violation_description => request.violation_description,
+                fix_suggestion => request.fix_suggestion,
+                original_code => request.original_code,
+                comment_syntax => request.comment_syntax,
+                ai_hint => request.ai_hint,
+                captures => request.captures,
+                examples => request.examples.iter().take(self.few_shot_examples).collect::<Vec<_>>(),
+                instructions => self.instructions,
+            },
+        )
+    }
+
+    /// Violations are listed file by file, followed by examples of each rule involved
+    pub fn render_session(
+        &self,
+        project_name: &str,
+        languages: &[String],
+        violations: &[ReviewViolation],
+    ) -> Result<String> {
+        let mut seen_rules = std::collections::HashSet::new();
+        let examples: Vec<PromptExample> = violations
+            .iter()
+            .filter(|violation| seen_rules.insert(&violation.rule.id))
+            .flat_map(|violation| {
+                let rule = &violation.rule;
+                rule.examples.iter().take(self.few_shot_examples).map(|example| PromptExample {
+                    rule_id: &rule.id,
+                    rule_name: &rule.name,
+                    bad: &example.bad,
+                    good: &example.good,
+                    explanation: &example.explanation,
+                })
+            })
+            .collect();
+
+        let mut by_file: BTreeMap<&str, Vec<PromptViolation>> = BTreeMap::new();
+        for violation in violations {
+            by_file.entry(&violation.file_path).or_default().push(violation.into());
+        }
+        let files_affected = by_file.len();
+        let violations: Vec<PromptViolation> = by_file.into_values().flatten().collect();
+
+        render(
+            "session",
+            self.session.as_deref().unwrap_or(DEFAULT_SESSION_TEMPLATE),
+            context! {
+                project => context! { name => project_name, languages => languages },
+                files_affected => files_affected,
+                violations => violations,
+                examples => examples,
+                instructions => self.instructions,
+            },
+        )
+    }
+}
+
+/// Block tags take no room in the output, and misspelled variables are errors rather than
+/// silently empty
+fn environment<'source>() -> Environment<'source> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env
+}
+
+fn render(name: &str, source: &str, context: Value) -> Result<String> {
+    let mut env = environment();
+    env.add_template(name, source).with_context(|| format!("Invalid prompts.{name} template"))?;
+    env.get_template(name)?
+        .render(context)
+        .with_context(|| format!("Failed to render prompts.{name} template"))
+}
+
+#[cfg(test)]
+mod prompts_tests {
+    use super::*;
+    use crate::core::CodeExample;
+    use crate::report::test_support::violation;
+
+    fn fix_request() -> FixRequest {
+        FixRequest {
+            file_path: "lib/user.ex".to_string(),
+            line_number: 42,
+            original_code: "String.to_atom(input)".to_string(),
+            violation_description: "Dynamic atoms are never garbage collected".to_string(),
+            fix_suggestion: "Use String.to_existing_atom/1".to_string(),
+            language: "elixir".to_string(),
+            comment_syntax: Some("`#` line comments".to_string()),
+            rule_id: "dynamic_atom_creation".to_string(),
+            rule_name: "Dynamic Atom Creation".to_string(),
+            severity: "critical".to_string(),
+            examples: vec![],
+            ai_hint: None,
+            captures: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_default_fix_prompt() {
+        let prompt = PromptTemplates::default().render_fix(&fix_request()).unwrap();
+
+        assert!(
+            prompt.starts_with("Fix this elixir code violation:\n\nFile: lib/user.ex\nLine: 42\n")
+        );
+        assert!(prompt.contains("```elixir\nString.to_atom(input)\n```\n\n"));
+        assert!(prompt.contains("If you add a comment, elixir uses `#` line comments.\n\nPlease"));
+        assert!(!prompt.contains("Matched:"));
+    }
+
+    #[test]
+    fn test_prompts_name_what_the_rule_captured() {
+        let captures: BTreeMap<String, String> =
+            [("1", "input"), ("arg", "input")].map(|(k, v)| (k.to_string(), v.to_string())).into();
+        let request = FixRequest { captures: captures.clone(), ..fix_request() };
+        let prompt = PromptTemplates::default().render_fix(&request).unwrap();
+        assert!(prompt.contains(
+            "Suggestion: Use String.to_existing_atom/1\nMatched: 1 = `input`, arg = `input`\n\nOriginal code:"
+        ));
+
+        let mut found = violation("lib/a.ex", 3, Severity::Critical);
+        found.captures = captures;
+        let prompt = PromptTemplates::default().render_session("shop", &[], &[found]).unwrap();
+        assert!(prompt.contains("\n   Matched: 1 = `input`, arg = `input`\n\n"));
+    }
+
+    #[test]
+    fn test_fix_prompt_includes_hint_and_limited_examples() {
+        let example = |bad: &str| CodeExample {
+            bad: bad.to_string(),
+            good: "String.to_existing_atom(input)".to_string(),
+            explanation: "Only existing atoms".to_string(),
+        };
+        let request = FixRequest {
+            examples: vec![example("String.to_atom(a)"), example("String.to_atom(b)")],
+            ai_hint: Some("Prefer explicit mappings for small, known sets".to_string()),
+            ..fix_request()
+        };
+        let templates = PromptTemplates { few_shot_examples: 1, ..Default::default() };
+
+        let prompt = templates.render_fix(&request).unwrap();
+
+        assert!(prompt.contains(
+            "```\n\nGuidance: Prefer explicit mappings for small, known sets\n\nExamples of this fix:\n\nBefore:\n```elixir\nString.to_atom(a)\n```\nAfter:\n```elixir\nString.to_existing_atom(input)\n```\nWhy: Only existing atoms\n\nIf you add a comment"
+        ));
+        assert!(!prompt.contains("String.to_atom(b)"));
+
+        let off = PromptTemplates { few_shot_examples: 0, ..Default::default() };
+        assert!(!off.render_fix(&request).unwrap().contains("Examples of this fix"));
+    }
+
+    #[test]
+    fn test_custom_fix_template_and_instructions() {
+        let templates = PromptTemplates {
+            fix: Some("[{{ severity }}] {{ rule_id }} at {{ file_path }}:{{ line_number }}\n{{ instructions }}".to_string()),
+            instructions: Some("Follow https://example.com/style".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            templates.render_fix(&fix_request()).unwrap(),
+            "[critical] dynamic_atom_creation at lib/user.ex:42\nFollow https://example.com/style"
+        );
+
+        let with_defaults = PromptTemplates { fix: None, ..templates };
+        let prompt = with_defaults.render_fix(&fix_request()).unwrap();
+        assert!(prompt.contains("Follow https://example.com/style\n\nPlease provide ONLY"));
+    }
+
+    #[test]
+    fn test_default_session_prompt_groups_by_file() {
+        let mut first = violation("lib/b.ex", 10, Severity::Critical);
+        first.context_before = vec!["def run do".to_string()];
+        first.context_after = vec!["end".to_string()];
+        let violations = vec![first, violation("lib/a.ex", 3, Severity::Warning)];
+
+        let prompt = PromptTemplates::default()
+            .render_session("shop", &["elixir".to_string()], &violations)
+            .unwrap();
+
+        assert!(prompt.starts_with("Fix these code quality violations in my project:\n\nPROJECT: shop (elixir)\nFILES AFFECTED: 2 files with 2 violations\n"));
+        assert!(prompt.find("📁 lib/a.ex:3").unwrap() < prompt.find("📁 lib/b.ex:10").unwrap());
+        assert!(prompt.contains("🔴 CRITICAL: "));
+        assert!(prompt.contains("   9 │ def run do\n   10 │ "));
+        assert!(prompt.contains("  ← VIOLATION\n   11 │ end\n"));
+        assert!(PromptTemplates::default()
+            .render_session("shop", &[], &[])
+            .unwrap()
+            .contains("(Unknown)"));
+    }
+
+    #[test]
+    fn test_session_prompt_lists_hints_and_examples_once_per_rule() {
+        let mut first = violation("lib/a.ex", 1, Severity::Major);
+        first.rule.ai_hint = Some("Keep the public API unchanged".to_string());
+        first.rule.examples = vec![CodeExample {
+            bad: "bad()".to_string(),
+            good: "good()".to_string(),
+            explanation: String::new(),
+        }];
+        let second = ReviewViolation { line_number: 2, ..first.clone() };
+
+        let prompt =
+            PromptTemplates::default().render_session("shop", &[], &[first, second]).unwrap();
+
+        assert_eq!(prompt.matches("   Hint: Keep the public API unchanged\n").count(), 2);
+        assert!(prompt.contains(
+            "EXAMPLES OF EACH FIX:\n\nTest Rule (test_rule)\n   Before: bad()\n   After: good()\n\nPlease help"
+        ));
+        assert_eq!(prompt.matches("Before: bad()").count(), 1);
+    }
+
+    #[test]
+    fn test_load_reports_invalid_and_misspelled_templates() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+
+        std::fs::write(&config_path, "prompts:\n  fix: \"{% if %}\"\n").unwrap();
+        let error = format!("{:#}", PromptTemplates::load(&config_path).unwrap_err());
+        assert!(error.contains("Invalid prompts.fix template"));
+
+        std::fs::write(&config_path, "prompts:\n  fix: \"{{ file_pth }}\"\n").unwrap();
+        let templates = PromptTemplates::load(&config_path).unwrap();
+        let error = format!("{:#}", templates.render_fix(&fix_request()).unwrap_err());
+        assert!(error.contains("Failed to render prompts.fix template"));
+    }
+}
@@ -0,0 +1,199 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+const DEFAULT_RELEASES_URL: &str = "https://api.github.com/repos/jeryldev/patingin/releases";
+
+/// Release asset listing the SHA-256 of every binary, in `sha256sum` format
+pub const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// A published release, as the GitHub releases API describes it
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// The tag without its leading `v`
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    pub fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Name of the release binary built for `target`, e.g. `patingin-x86_64-unknown-linux-gnu`
+pub fn asset_name(target: &str) -> String {
+    let extension = if target.contains("windows") { ".exe" } else { "" };
+    format!("patingin-{target}{extension}")
+}
+
+/// Reads the releases of patingin, from GitHub or the mirror in `PATINGIN_RELEASES_URL`
+pub struct ReleaseClient {
+    releases_url: String,
+    client: reqwest::Client,
+}
+
+impl ReleaseClient {
+    /// A client whose requests give up after `timeout`
+    pub fn new(timeout: Duration) -> Result<Self> {
+        let releases_url = std::env::var("PATINGIN_RELEASES_URL")
+            .unwrap_or_else(|_| DEFAULT_RELEASES_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent(concat!("patingin/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        Ok(Self { releases_url, client })
+    }
+
+    pub async fn latest(&self) -> Result<Release> {
+        let url = format!("{}/latest", self.releases_url);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to look up the latest release at {url}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("Release lookup failed ({status}): {url}"));
+        }
+        response.json().await.context("Unexpected release metadata")
+    }
+
+    pub async fn download(&self, asset: &ReleaseAsset) -> Result<Vec<u8>> {
+        let response = self.client.get(&asset.browser_download_url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("Downloading {} failed ({status})", asset.name));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+/// The checksums of a `SHA256SUMS` file by file name. Lines are `<hex>  <name>`, where a
+/// `*` before the name marks binary mode.
+pub fn parse_checksums(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (checksum, name) = line.trim().split_once(char::is_whitespace)?;
+            let name = name.trim_start().trim_start_matches('*');
+            Some((name.to_string(), checksum.to_lowercase()))
+        })
+        .collect()
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Fail unless `bytes` hash to the checksum `checksums` lists for `name`
+pub fn verify_checksum(
+    name: &str,
+    bytes: &[u8],
+    checksums: &HashMap<String, String>,
+) -> Result<()> {
+    let expected = checksums
+        .get(name)
+        .ok_or_else(|| anyhow!("{CHECKSUMS_ASSET} of the release has no entry for {name}"))?;
+    let actual = sha256_hex(bytes);
+    if actual != *expected {
+        return Err(anyhow!(
+            "Checksum mismatch for {name}: expected {expected}, downloaded file has {actual}"
+        ));
+    }
+    Ok(())
+}
+
+/// Replace the executable at `path` with `bytes`. The new binary is written next to it and
+/// renamed over it, so an interrupted update leaves the old one in place. Windows cannot
+/// overwrite a running executable, so there the old one is moved aside first.
+pub fn replace_executable(path: &Path, bytes: &[u8]) -> Result<()> {
+    let staged = path.with_extension("new");
+    std::fs::write(&staged, bytes)
+        .with_context(|| format!("Failed to write {}", staged.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    if cfg!(windows) {
+        let old = path.with_extension("old");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(path, &old)
+            .with_context(|| format!("Failed to move {} aside", path.display()))?;
+    }
+    std::fs::rename(&staged, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+#[cfg(test)]
+mod releases_tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_names_follow_the_target() {
+        assert_eq!(asset_name("x86_64-unknown-linux-gnu"), "patingin-x86_64-unknown-linux-gnu");
+        assert_eq!(asset_name("x86_64-pc-windows-msvc"), "patingin-x86_64-pc-windows-msvc.exe");
+    }
+
+    #[test]
+    fn test_checksums_are_verified() {
+        let binary = b"patingin binary";
+        let sums = format!(
+            "{}  patingin-aarch64-apple-darwin\n{} *patingin-x86_64-unknown-linux-gnu\n",
+            "0".repeat(64),
+            sha256_hex(binary).to_uppercase()
+        );
+        let checksums = parse_checksums(&sums);
+        assert_eq!(checksums.len(), 2);
+
+        assert!(verify_checksum("patingin-x86_64-unknown-linux-gnu", binary, &checksums).is_ok());
+        let mismatch =
+            verify_checksum("patingin-aarch64-apple-darwin", binary, &checksums).unwrap_err();
+        assert!(mismatch.to_string().contains("Checksum mismatch"));
+        let missing = verify_checksum("patingin-riscv", binary, &checksums).unwrap_err();
+        assert!(missing.to_string().contains("no entry for patingin-riscv"));
+    }
+
+    #[test]
+    fn test_release_version_and_assets() {
+        let release: Release = serde_json::from_str(
+            r#"{"tag_name": "v0.2.0", "assets": [
+                {"name": "SHA256SUMS", "browser_download_url": "https://example.com/SHA256SUMS"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(release.version(), "0.2.0");
+        assert!(release.asset(CHECKSUMS_ASSET).is_some());
+        assert!(release.asset("patingin-x86_64-unknown-linux-gnu").is_none());
+    }
+
+    #[test]
+    fn test_replace_executable() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("patingin");
+        std::fs::write(&path, b"old").unwrap();
+
+        replace_executable(&path, b"new").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        assert!(!path.with_extension("new").exists());
+    }
+}
@@ -0,0 +1,214 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::{DiffScope, GitIntegration};
+
+impl GitIntegration {
+    /// Who last changed each line of `path`, keyed by line number. Blame ignores
+    /// whitespace, so a re-indented line keeps its last real change. Blames the working
+    /// tree file unless a revision is given; lines not committed yet are left out.
+    pub fn line_blames(
+        &self,
+        path: &str,
+        revision: Option<&str>,
+    ) -> Result<HashMap<usize, LineBlame>> {
+        let workdir =
+            self.repo.workdir().ok_or_else(|| anyhow!("Blame needs a working tree checkout"))?;
+
+        let mut command = Command::new("git");
+        command.current_dir(workdir).args(["blame", "-w", "--line-porcelain"]);
+        if let Some(revision) = revision {
+            command.arg(revision);
+        }
+        let output = command.arg("--").arg(path).output()?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git blame failed for {path}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(parse_line_porcelain(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// The commit that last changed a line
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineBlame {
+    pub commit: String,
+    pub author: String,
+    pub email: String,
+    /// Author time, unix seconds
+    pub time: i64,
+}
+
+impl LineBlame {
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(7)]
+    }
+}
+
+/// Read `git blame --line-porcelain` output: a `<sha> <orig> <final>` header, key/value
+/// lines, then the line content prefixed with a tab. Uncommitted lines (all-zero sha) are
+/// skipped.
+fn parse_line_porcelain(output: &str) -> HashMap<usize, LineBlame> {
+    let mut blames = HashMap::new();
+    let mut header: Option<(String, usize)> = None;
+    let mut blame = LineBlame::default();
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            if let Some((commit, number)) = header.take() {
+                if commit.bytes().any(|byte| byte != b'0') {
+                    blames.insert(number, LineBlame { commit, ..blame.clone() });
+                }
+            }
+        } else if header.is_none() {
+            let mut fields = line.split(' ');
+            let commit = fields.next().unwrap_or_default().to_string();
+            header = fields.nth(1).and_then(|number| number.parse().ok()).map(|n| (commit, n));
+            blame = LineBlame::default();
+        } else if let Some(author) = line.strip_prefix("author ") {
+            blame.author = author.to_string();
+        } else if let Some(email) = line.strip_prefix("author-mail ") {
+            blame.email = email.trim_start_matches('<').trim_end_matches('>').to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            blame.time = time.parse().unwrap_or_default();
+        }
+    }
+
+    blames
+}
+
+/// Blames of the revision a review scope ends at (the `to` commit of a range, otherwise the
+/// working tree), each file blamed once on first use. Files that cannot be blamed (new,
+/// untracked) have no blames.
+pub struct BlameCache {
+    git: GitIntegration,
+    revision: Option<String>,
+    blamed: HashMap<String, HashMap<usize, LineBlame>>,
+}
+
+impl BlameCache {
+    pub fn new(git: GitIntegration, scope: &DiffScope) -> Self {
+        let revision = match scope {
+            DiffScope::Between(_, to) => Some(to.clone()),
+            _ => None,
+        };
+        Self { git, revision, blamed: HashMap::new() }
+    }
+
+    pub fn line(&mut self, file_path: &str, line_number: usize) -> Option<&LineBlame> {
+        let blames = self.blamed.entry(file_path.to_string()).or_insert_with(|| {
+            self.git.line_blames(file_path, self.revision.as_deref()).unwrap_or_default()
+        });
+        blames.get(&line_number)
+    }
+}
+
+/// Drops violations on lines whose last non-whitespace change is older than a cutoff, so a
+/// mass re-indent does not resurface findings in code nobody really touched. Lines without
+/// blame (uncommitted, new or untracked files) are kept.
+pub struct LineAgeFilter {
+    blame: BlameCache,
+    cutoff: i64,
+}
+
+impl LineAgeFilter {
+    pub fn new(git: GitIntegration, scope: &DiffScope, max_age: Duration) -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+        Self { blame: BlameCache::new(git, scope), cutoff: now - max_age.as_secs() as i64 }
+    }
+
+    pub fn is_older(&mut self, file_path: &str, line_number: usize) -> bool {
+        self.blame.line(file_path, line_number).is_some_and(|blame| blame.time < self.cutoff)
+    }
+}
+
+/// Parse an age like `90d`, `6w`, `3m` or `1y` (months are 30 days, years 365)
+pub fn parse_age(value: &str) -> std::result::Result<Duration, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("expected an age like 90d, 6w, 3m or 1y, got '{value}'"))?;
+    let days = match unit {
+        "d" => 1,
+        "w" => 7,
+        "m" => 30,
+        "y" => 365,
+        _ => return Err(format!("unknown age unit in '{value}': use d, w, m or y")),
+    };
+    Ok(Duration::from_secs(amount * days * 24 * 60 * 60))
+}
+
+#[cfg(test)]
+mod blame_tests {
+    use super::*;
+    use git2::{Repository, Signature, Time};
+    use std::path::Path;
+
+    fn commit_at(repo: &Repository, path: &str, content: &str, seconds: i64) {
+        std::fs::write(repo.workdir().unwrap().join(path), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::new("Test", "test@example.com", &Time::new(seconds, 0)).unwrap();
+        let parents: Vec<git2::Commit> =
+            repo.head().ok().and_then(|h| h.peel_to_commit().ok()).into_iter().collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, "change", &tree, &parent_refs).unwrap();
+    }
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("90d"), Ok(Duration::from_secs(90 * 86_400)));
+        assert_eq!(parse_age("1y"), Ok(Duration::from_secs(365 * 86_400)));
+        assert!(parse_age("1 year").is_err());
+        assert!(parse_age("y").is_err());
+    }
+
+    #[test]
+    fn test_parse_line_porcelain() {
+        let output = "abc123 1 1 2\nauthor Test\nauthor-mail <test@example.com>\n\
+                      author-time 1000\nsummary x\n\tfirst\n\
+                      abc123 2 2\nauthor Test\nauthor-time 1000\n\tsecond\n\
+                      def456 1 3 1\nauthor-time 2000\n\tthird\n\
+                      0000000000000000000000000000000000000000 4 4 1\n\
+                      author Not Committed Yet\nauthor-time 3000\n\tfourth\n";
+
+        let blames = parse_line_porcelain(output);
+
+        let times: HashMap<usize, i64> =
+            blames.iter().map(|(line, blame)| (*line, blame.time)).collect();
+        assert_eq!(times, HashMap::from([(1, 1000), (2, 1000), (3, 2000)]));
+        assert_eq!(blames[&1].commit, "abc123");
+        assert_eq!(blames[&1].author, "Test");
+        assert_eq!(blames[&1].email, "test@example.com");
+        assert_eq!(blames[&3].commit, "def456");
+    }
+
+    #[test]
+    fn test_reindented_lines_keep_their_original_age() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        // 2001, long before any sensible cutoff
+        commit_at(&repo, "old.ex", "def run do\nString.to_atom(x)\nend\n", 1_000_000_000);
+        std::fs::write(
+            temp_dir.path().join("old.ex"),
+            "def run do\n    String.to_atom(x)\n    String.to_atom(y)\nend\n",
+        )
+        .unwrap();
+
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        let mut filter = LineAgeFilter::new(git, &DiffScope::Unstaged, parse_age("1y").unwrap());
+
+        assert!(filter.is_older("old.ex", 2));
+        assert!(!filter.is_older("old.ex", 3));
+        assert!(!filter.is_older("missing.ex", 1));
+    }
+}
@@ -0,0 +1,214 @@
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+
+use super::GitIntegration;
+
+/// Working tree contents of files before a fix runs, to tell its edits apart from the
+/// developer's own
+#[derive(Debug, Clone, Default)]
+pub struct FixSnapshot {
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+/// What `commit_fixes` committed
+#[derive(Debug, Clone, Default)]
+pub struct FixCommit {
+    /// The new commit; `None` when no fix could be committed
+    pub sha: Option<String>,
+    /// Files whose fixes were committed
+    pub files: Vec<String>,
+    /// Files whose fixes were left in the working tree, with why
+    pub skipped: Vec<(String, String)>,
+}
+
+impl GitIntegration {
+    /// Record the current contents of `paths`, relative to the repository root. Files that
+    /// cannot be read are left out, so fixes to them are not committed.
+    pub fn snapshot_files<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a str>,
+    ) -> Result<FixSnapshot> {
+        let workdir = self.fix_workdir()?;
+        let files = paths
+            .into_iter()
+            .filter_map(|path| Some((path.to_string(), std::fs::read(workdir.join(path)).ok()?)))
+            .collect();
+        Ok(FixSnapshot { files })
+    }
+
+    /// Commit what changed in the snapshotted files since the snapshot, and nothing else:
+    /// the changes are merged into the files as of HEAD for the commit and as staged for the
+    /// index, so the developer's own edits, staged or not, stay out of it. Files where the
+    /// two overlap, or that HEAD does not have yet, are skipped.
+    pub fn commit_fixes(
+        &self,
+        snapshot: &FixSnapshot,
+        message: impl FnOnce(&[String]) -> String,
+    ) -> Result<FixCommit> {
+        let workdir = self.fix_workdir()?;
+        let head = self.repo.head()?.peel_to_commit()?;
+        let head_tree = head.tree()?;
+        let mut index = self.repo.index()?;
+        let mut commit_index = git2::Index::new()?;
+        commit_index.read_tree(&head_tree)?;
+
+        let mut outcome = FixCommit::default();
+        let mut staged = Vec::new();
+        for (path, before) in &snapshot.files {
+            let after = match std::fs::read(workdir.join(path)) {
+                Ok(after) if after == *before => continue,
+                Ok(after) => after,
+                Err(_) => {
+                    outcome.skipped.push((path.clone(), "the file is gone".to_string()));
+                    continue;
+                }
+            };
+            let (Ok(head_entry), Some(mut commit_entry), Some(mut staged_entry)) = (
+                head_tree.get_path(Path::new(path)),
+                index.get_path(Path::new(path), 0),
+                index.get_path(Path::new(path), 0),
+            ) else {
+                outcome.skipped.push((path.clone(), "the file is not committed yet".to_string()));
+                continue;
+            };
+
+            let committed = self.repo.find_blob(head_entry.id())?.content().to_vec();
+            let Some(committed) = merge_file(&committed, before, &after)? else {
+                let reason = "the fix touches lines that are not committed; commit them first";
+                outcome.skipped.push((path.clone(), reason.to_string()));
+                continue;
+            };
+            let indexed = self.repo.find_blob(staged_entry.id)?.content().to_vec();
+            let Some(indexed) = merge_file(&indexed, before, &after)? else {
+                outcome
+                    .skipped
+                    .push((path.clone(), "the fix touches lines with staged changes".to_string()));
+                continue;
+            };
+
+            commit_entry.id = self.repo.blob(&committed)?;
+            commit_entry.mode = head_entry.filemode() as u32;
+            commit_index.add(&commit_entry)?;
+            staged_entry.id = self.repo.blob(&indexed)?;
+            staged.push(staged_entry);
+            outcome.files.push(path.clone());
+        }
+        if outcome.files.is_empty() {
+            return Ok(outcome);
+        }
+
+        let tree = self.repo.find_tree(commit_index.write_tree_to(&self.repo)?)?;
+        let signature = self.repo.signature()?;
+        let message = message(&outcome.files);
+        let sha =
+            self.repo.commit(Some("HEAD"), &signature, &signature, &message, &tree, &[&head])?;
+        // Stage the fixes too, or the index would show them reverted against the new HEAD
+        for entry in &staged {
+            index.add(entry)?;
+        }
+        index.write()?;
+        outcome.sha = Some(sha.to_string());
+        Ok(outcome)
+    }
+
+    fn fix_workdir(&self) -> Result<&Path> {
+        self.workdir().ok_or_else(|| anyhow!("Committing fixes needs a working tree checkout"))
+    }
+}
+
+/// Three-way merge of the change from `base` to `theirs` into `ours` with `git merge-file`;
+/// `None` when the two conflict
+fn merge_file(ours: &[u8], base: &[u8], theirs: &[u8]) -> Result<Option<Vec<u8>>> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let mut paths = Vec::new();
+    for (name, content) in [("ours", ours), ("base", base), ("theirs", theirs)] {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, content)?;
+        paths.push(path);
+    }
+
+    let output = Command::new("git")
+        .args(["merge-file", "-p", "--quiet"])
+        .args(&paths)
+        .output()
+        .context("Failed to run git merge-file")?;
+    // The exit status is the number of conflicts, or negative on errors
+    match output.status.code() {
+        Some(0) => Ok(Some(output.stdout)),
+        Some(1..=127) => Ok(None),
+        _ => Err(anyhow!(
+            "git merge-file failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod fix_commit_tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    #[test]
+    fn test_only_fixed_lines_are_committed() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q"]);
+        git(dir, &["config", "user.name", "Test"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        let lines: Vec<String> = (1..=12).map(|n| format!("line {n}")).collect();
+        std::fs::write(dir.join("app.js"), lines.join("\n") + "\n").unwrap();
+        std::fs::write(dir.join("new.js"), "console.log(1)\n").unwrap();
+        git(dir, &["add", "app.js"]);
+        git(dir, &["commit", "-qm", "initial"]);
+
+        // The developer's own edits: one staged, one not
+        let mut edited = lines.clone();
+        edited[0] = "staged edit".to_string();
+        std::fs::write(dir.join("app.js"), edited.join("\n") + "\n").unwrap();
+        git(dir, &["add", "app.js"]);
+        edited[11] = "unstaged edit".to_string();
+        std::fs::write(dir.join("app.js"), edited.join("\n") + "\n").unwrap();
+
+        let repo = GitIntegration::new(dir).unwrap();
+        let snapshot = repo.snapshot_files(["app.js", "new.js", "missing.js"]).unwrap();
+        edited[5] = "fixed line".to_string();
+        std::fs::write(dir.join("app.js"), edited.join("\n") + "\n").unwrap();
+        std::fs::write(dir.join("new.js"), "\n").unwrap();
+
+        let outcome =
+            repo.commit_fixes(&snapshot, |files| format!("Fix {}", files.join(", "))).unwrap();
+
+        assert!(outcome.sha.is_some());
+        assert_eq!(outcome.files, ["app.js"]);
+        assert_eq!(
+            outcome.skipped,
+            [("new.js".to_string(), "the file is not committed yet".to_string())]
+        );
+        assert_eq!(git(dir, &["log", "-1", "--format=%s"]), "Fix app.js\n");
+        let committed = git(dir, &["show", "HEAD:app.js"]);
+        assert!(committed.contains("line 1\n") && committed.contains("fixed line\n"));
+        assert!(committed.contains("line 12\n"));
+        // Still staged, still unstaged, fix in neither diff
+        assert_eq!(git(dir, &["diff", "--cached", "--name-only"]), "app.js\n");
+        assert!(git(dir, &["diff", "--cached"]).contains("+staged edit"));
+        let unstaged = git(dir, &["diff"]);
+        assert!(unstaged.contains("+unstaged edit") && !unstaged.contains("fixed line"));
+    }
+}
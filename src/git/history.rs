@@ -0,0 +1,182 @@
+use anyhow::Result;
+use std::collections::HashMap;
+
+use super::{CommitInfo, GitIntegration};
+
+impl GitIntegration {
+    /// Number of commits touching each file among the last `max_commits` non-merge commits on
+    /// HEAD. Renamed files are counted under their new path.
+    pub fn file_churn(&self, max_commits: usize) -> Result<HashMap<String, usize>> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        let mut churn: HashMap<String, usize> = HashMap::new();
+        for oid in revwalk.take(max_commits) {
+            let commit = self.repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+
+            let parent_tree = commit.parent(0).ok().map(|parent| parent.tree()).transpose()?;
+            let diff =
+                self.repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+            for delta in diff.deltas() {
+                if let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) {
+                    *churn.entry(path.to_string_lossy().to_string()).or_default() += 1;
+                }
+            }
+        }
+
+        Ok(churn)
+    }
+
+    /// The commits of `range` oldest first, as `commits_in_range` lists them, without merges:
+    /// a merge's diff against its first parent repeats the commits it merged
+    pub fn non_merge_commits(&self, range: &str) -> Result<Vec<CommitInfo>> {
+        let mut commits = self.commits_in_range(range)?;
+        let mut merges = Vec::new();
+        for commit in &commits {
+            if self.repo.find_commit(git2::Oid::from_str(&commit.sha)?)?.parent_count() > 1 {
+                merges.push(commit.sha.clone());
+            }
+        }
+        commits.retain(|commit| !merges.contains(&commit.sha));
+        Ok(commits)
+    }
+
+    /// Lines added by the last `max_commits` non-merge commits on HEAD, each with the path of
+    /// its file, as a sample of the code the project is still writing
+    pub fn added_lines(&self, max_commits: usize) -> Result<AddedLines> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        let mut sample = AddedLines::default();
+        for oid in revwalk.take(max_commits) {
+            let commit = self.repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            sample.commits += 1;
+
+            let parent_tree = commit.parent(0).ok().map(|parent| parent.tree()).transpose()?;
+            let diff =
+                self.repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+            diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+                if line.origin() == '+' {
+                    if let Some(path) = delta.new_file().path() {
+                        let content = String::from_utf8_lossy(line.content());
+                        sample.lines.push((
+                            path.to_string_lossy().to_string(),
+                            content.trim_end_matches(['\n', '\r']).to_string(),
+                        ));
+                    }
+                }
+                true
+            })?;
+        }
+
+        Ok(sample)
+    }
+}
+
+/// Lines added across a stretch of history
+#[derive(Debug, Default)]
+pub struct AddedLines {
+    /// Non-merge commits read
+    pub commits: usize,
+    /// `(path, line)` of every added line
+    pub lines: Vec<(String, String)>,
+}
+
+#[cfg(test)]
+mod history_tests {
+    use super::*;
+    use git2::Repository;
+    use std::path::Path;
+
+    fn commit(repo: &Repository, files: &[(&str, &str)]) {
+        let workdir = repo.workdir().unwrap();
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            std::fs::write(workdir.join(path), content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parents: Vec<git2::Commit> =
+            repo.head().ok().and_then(|h| h.peel_to_commit().ok()).into_iter().collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, "change", &tree, &parent_refs).unwrap();
+    }
+
+    #[test]
+    fn test_file_churn_counts_commits_per_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        commit(&repo, &[("a.ex", "1"), ("b.ex", "1")]);
+        commit(&repo, &[("a.ex", "2")]);
+        commit(&repo, &[("a.ex", "3"), ("b.ex", "2")]);
+
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        let churn = git.file_churn(100).unwrap();
+        assert_eq!(churn["a.ex"], 3);
+        assert_eq!(churn["b.ex"], 2);
+
+        let recent = git.file_churn(1).unwrap();
+        assert_eq!(recent["a.ex"], 1);
+        assert_eq!(recent["b.ex"], 1);
+    }
+
+    #[test]
+    fn test_non_merge_commits_skip_merges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        commit(&repo, &[("a.ex", "1")]);
+        let root = repo.head().unwrap().peel_to_commit().unwrap();
+        commit(&repo, &[("a.ex", "2")]);
+        let main = repo.head().unwrap().peel_to_commit().unwrap();
+
+        // A side commit off the root, merged back into main
+        let signature = git2::Signature::now("Side", "side@example.com").unwrap();
+        let side_tree = repo.find_tree(root.tree_id()).unwrap();
+        let side = repo.commit(None, &signature, &signature, "side", &side_tree, &[&root]).unwrap();
+        let side = repo.find_commit(side).unwrap();
+        let tree = main.tree().unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "merge", &tree, &[&main, &side]).unwrap();
+
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        let range = root.id().to_string();
+        assert_eq!(git.commits_in_range(&range).unwrap().len(), 3);
+        let commits = git.non_merge_commits(&range).unwrap();
+        let summaries: Vec<(&str, &str)> =
+            commits.iter().map(|c| (c.summary.as_str(), c.author.as_str())).collect();
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries.contains(&("change", "Test")));
+        assert!(summaries.contains(&("side", "Side")));
+    }
+
+    #[test]
+    fn test_added_lines_samples_recent_commits() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        commit(&repo, &[("a.ex", "old\n")]);
+        commit(&repo, &[("a.ex", "old\nnew\n"), ("b.py", "x = 1\n")]);
+
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        let recent = git.added_lines(1).unwrap();
+        assert_eq!(recent.commits, 1);
+        assert_eq!(
+            recent.lines,
+            vec![
+                ("a.ex".to_string(), "new".to_string()),
+                ("b.py".to_string(), "x = 1".to_string())
+            ]
+        );
+
+        let all = git.added_lines(100).unwrap();
+        assert_eq!(all.commits, 2);
+        assert_eq!(all.lines.len(), 3);
+    }
+}
@@ -1,8 +1,21 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use git2::Repository;
-use std::path::Path;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+pub mod blame;
+pub mod fix_commit;
+pub mod history;
+pub mod ownership;
+pub mod readiness;
+pub mod remote;
+pub mod tree;
+pub mod vcs;
+
+pub use remote::{RemoteProvider, RemoteRepository};
+pub use vcs::Vcs;
+
 pub struct GitIntegration {
     repo: Repository,
 }
@@ -13,12 +26,284 @@ impl GitIntegration {
         Ok(Self { repo })
     }
 
+    /// Open the repository git is running us in (honours `GIT_DIR`), as server-side hooks do.
+    pub fn from_env() -> Result<Self> {
+        let repo = Repository::open_from_env()?;
+        Ok(Self { repo })
+    }
+
+    /// Diff two commits straight from the object database, so it works in bare repositories
+    /// where there is no working tree to read. `from = None` diffs against the empty tree.
+    pub fn diff_between(&self, from: Option<&str>, to: &str) -> Result<GitDiff> {
+        let new_tree = self.repo.revparse_single(to)?.peel_to_tree()?;
+        let old_tree = match from {
+            Some(reference) => Some(self.repo.revparse_single(reference)?.peel_to_tree()?),
+            None => None,
+        };
+
+        let diff = self.repo.diff_tree_to_tree(old_tree.as_ref(), Some(&new_tree), None)?;
+
+        let mut patch = String::new();
+        diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+            let content = String::from_utf8_lossy(line.content());
+            match line.origin() {
+                origin @ ('+' | '-' | ' ') => {
+                    patch.push(origin);
+                    patch.push_str(&content);
+                }
+                _ => patch.push_str(&content),
+            }
+            true
+        })?;
+
+        GitDiffParser::parse(&patch)
+    }
+
+    /// Best base for a newly pushed branch: its merge base with the default branch, which is
+    /// the tip itself when the branch starts at a commit already on it. `None` only when
+    /// there is no default branch sharing history with it.
+    pub fn default_branch_merge_base(&self, sha: &str) -> Option<String> {
+        let target = self.repo.revparse_single(sha).ok()?.peel_to_commit().ok()?.id();
+        ["refs/heads/main", "refs/heads/master"].iter().find_map(|branch| {
+            let base = self.repo.refname_to_id(branch).ok()?;
+            Some(self.repo.merge_base(base, target).ok()?.to_string())
+        })
+    }
+
     #[allow(dead_code)]
     pub fn get_changed_files(&self) -> Result<Vec<String>> {
         // TODO: Implement getting changed files
         Ok(vec![])
     }
 
+    /// Resolve the upstream of the current branch (e.g. `origin/main`), falling back to the
+    /// usual default branch names when no upstream is configured.
+    pub fn get_upstream_reference(&self) -> Option<String> {
+        if let Ok(head) = self.repo.head() {
+            if let Some(branch_name) = head.shorthand() {
+                if let Ok(branch) = self.repo.find_branch(branch_name, git2::BranchType::Local) {
+                    if let Ok(upstream) = branch.upstream() {
+                        if let Ok(Some(name)) = upstream.name() {
+                            return Some(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        ["origin/HEAD", "origin/main", "origin/master", "main", "master"]
+            .iter()
+            .find(|candidate| self.repo.revparse_single(candidate).is_ok())
+            .map(|candidate| candidate.to_string())
+    }
+
+    /// Compute the merge base between HEAD and `reference`, returning the commit SHA
+    pub fn get_merge_base(&self, reference: &str) -> Result<String> {
+        let head = self.repo.head()?.peel_to_commit()?.id();
+        let other = self.repo.revparse_single(reference)?.peel_to_commit()?.id();
+        let base = self.repo.merge_base(head, other)?;
+        Ok(base.to_string())
+    }
+
+    /// Merge base of two commits, e.g. a pull request's base and head
+    pub fn merge_base_between(&self, first: &str, second: &str) -> Result<String> {
+        let first = self.repo.revparse_single(first)?.peel_to_commit()?.id();
+        let second = self.repo.revparse_single(second)?.peel_to_commit()?.id();
+        Ok(self.repo.merge_base(first, second)?.to_string())
+    }
+
+    /// Whether this is a shallow clone (e.g. `actions/checkout` with the default `fetch-depth: 1`)
+    pub fn is_shallow(&self) -> bool {
+        self.repo.is_shallow()
+    }
+
+    pub fn resolves(&self, reference: &str) -> bool {
+        self.repo.revparse_single(reference).is_ok()
+    }
+
+    /// Paths of the files in the index, relative to the repository root
+    pub fn tracked_files(&self) -> Result<Vec<String>> {
+        let index = self.repo.index()?;
+        Ok(index.iter().map(|entry| String::from_utf8_lossy(&entry.path).into_owned()).collect())
+    }
+
+    /// Local branches, remote-tracking branches and tags that can be used as `--since` targets
+    pub fn available_references(&self) -> Vec<String> {
+        let mut references: Vec<String> = self
+            .repo
+            .references()
+            .map(|refs| {
+                refs.flatten()
+                    .filter(|r| r.is_branch() || r.is_remote() || r.is_tag())
+                    .filter_map(|r| r.shorthand().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        references.sort();
+        references
+    }
+
+    /// Make sure `reference` can be diffed against, optionally fetching more history first.
+    ///
+    /// Missing references are common on shallow CI clones, where git's own error
+    /// ("bad revision", "unknown revision") gives no hint about the cause.
+    pub fn ensure_reference(&self, reference: &str, auto_fetch: bool) -> Result<()> {
+        if self.resolves(reference) {
+            return Ok(());
+        }
+
+        if auto_fetch {
+            self.fetch_more_history()?;
+            if self.resolves(reference) {
+                return Ok(());
+            }
+        }
+
+        Err(self.missing_reference_error(reference, auto_fetch))
+    }
+
+    /// Unshallow (or plain fetch) from the default remote using the git CLI, which honours the
+    /// user's credentials and transport configuration.
+    fn fetch_more_history(&self) -> Result<()> {
+        self.fetch(&[])
+    }
+
+    /// Fetch `branch` from `origin` into `origin/<branch>`, unshallowing the clone so merge
+    /// bases can be found: CI checkouts are often shallow and of a single branch.
+    pub fn fetch_branch(&self, branch: &str) -> Result<()> {
+        self.fetch(&[
+            "origin".to_string(),
+            format!("+refs/heads/{branch}:refs/remotes/origin/{branch}"),
+        ])
+    }
+
+    fn fetch(&self, args: &[String]) -> Result<()> {
+        let mut command = Command::new("git");
+        command.arg("fetch");
+        if self.is_shallow() {
+            command.arg("--unshallow");
+        }
+        command.args(args);
+        if let Some(workdir) = self.repo.workdir() {
+            command.current_dir(workdir);
+        }
+
+        let output = command.output()?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "git fetch failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+
+    fn missing_reference_error(&self, reference: &str, fetched: bool) -> anyhow::Error {
+        let mut message = readiness::NotReady::MissingReference {
+            reference: reference.to_string(),
+            hint: self.fetch_hint(reference),
+        }
+        .to_string();
+
+        if self.is_shallow() {
+            message.push_str(
+                "\nThis is a shallow clone, so older commits and other branches were not fetched.",
+            );
+        }
+
+        let available = self.available_references();
+        if available.is_empty() {
+            message.push_str("\nNo branches or tags are available locally.");
+        } else {
+            const SHOWN: usize = 10;
+            message.push_str(&format!(
+                "\nAvailable references: {}",
+                available.iter().take(SHOWN).cloned().collect::<Vec<_>>().join(", ")
+            ));
+            if available.len() > SHOWN {
+                message.push_str(&format!(" (and {} more)", available.len() - SHOWN));
+            }
+        }
+
+        message.push_str("\nTo fix this:");
+        if !fetched {
+            message.push_str("\n  • rerun with --auto-fetch to fetch the missing history");
+        }
+        message.push_str(
+            "\n  • or run `git fetch --unshallow` (GitHub Actions: set `fetch-depth: 0` on actions/checkout)\n  • or review a scope that needs no history: --staged, --uncommitted",
+        );
+
+        anyhow::anyhow!(message)
+    }
+
+    /// List the commits in `base..head` oldest first, as `git rev-list --reverse` would.
+    /// A bare reference is treated as `<reference>..HEAD`.
+    pub fn commits_in_range(&self, range: &str) -> Result<Vec<CommitInfo>> {
+        let range = if range.contains("..") { range.to_string() } else { format!("{range}..HEAD") };
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_range(&range)?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            commits.push(CommitInfo::from(&self.repo.find_commit(oid?)?));
+        }
+
+        Ok(commits)
+    }
+
+    /// The commit `reference` points at, e.g. a SHA, `HEAD~2` or a tag
+    pub fn commit_info(&self, reference: &str) -> Result<CommitInfo> {
+        let commit = self
+            .repo
+            .revparse_single(reference)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("'{reference}' is not a commit"))?;
+        Ok(CommitInfo::from(&commit))
+    }
+
+    pub fn head_sha(&self) -> Result<String> {
+        Ok(self.repo.head()?.peel_to_commit()?.id().to_string())
+    }
+
+    /// URL of the `origin` remote, or of the first remote when there is no `origin`
+    pub fn get_remote_url(&self) -> Option<String> {
+        let remote = match self.repo.find_remote("origin") {
+            Ok(remote) => remote,
+            Err(_) => {
+                let names = self.repo.remotes().ok()?;
+                self.repo.find_remote(names.get(0)?).ok()?
+            }
+        };
+        remote.url().map(String::from)
+    }
+
+    /// Host and repository the remote points at
+    pub fn remote_repository(&self) -> Option<RemoteRepository> {
+        RemoteRepository::parse(&self.get_remote_url()?)
+    }
+
+    /// Where git looks for hooks: `core.hooksPath` (relative to the working tree) when set,
+    /// otherwise `hooks` in the common git directory, shared by all worktrees
+    pub fn hooks_dir(&self) -> PathBuf {
+        let configured = self.repo.config().ok().and_then(|c| c.get_path("core.hooksPath").ok());
+        match configured {
+            Some(path) if path.is_relative() => {
+                self.repo.workdir().unwrap_or_else(|| self.repo.path()).join(path)
+            }
+            Some(path) => path,
+            None => {
+                // Linked worktrees name the shared git directory in their `commondir` file
+                let git_dir = self.repo.path();
+                let common_dir = std::fs::read_to_string(git_dir.join("commondir"))
+                    .map(|dir| git_dir.join(dir.trim()))
+                    .unwrap_or_else(|_| git_dir.to_path_buf());
+                common_dir.join("hooks")
+            }
+        }
+    }
+
     pub fn get_current_branch(&self) -> Result<String> {
         match self.repo.head() {
             Ok(head) => {
@@ -33,6 +318,40 @@ impl GitIntegration {
     }
 }
 
+/// A single commit selected for review
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitInfo {
+    pub sha: String,
+    pub summary: String,
+    /// Author name, empty when not valid UTF-8
+    pub author: String,
+    /// First parent; `None` for root commits
+    pub parent: Option<String>,
+}
+
+impl From<&git2::Commit<'_>> for CommitInfo {
+    fn from(commit: &git2::Commit<'_>) -> Self {
+        CommitInfo {
+            sha: commit.id().to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            author: commit.author().name().unwrap_or_default().to_string(),
+            parent: commit.parent_id(0).ok().map(|id| id.to_string()),
+        }
+    }
+}
+
+impl CommitInfo {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(7)]
+    }
+
+    /// Scope covering exactly the changes introduced by this commit
+    pub fn diff_scope(&self) -> DiffScope {
+        let from = self.parent.clone().unwrap_or_else(|| EMPTY_TREE_SHA.to_string());
+        DiffScope::Between(from, self.sha.clone())
+    }
+}
+
 // Git diff parsing structures and functionality
 #[derive(Debug, Clone, PartialEq)]
 pub enum DiffScope {
@@ -42,8 +361,15 @@ pub enum DiffScope {
     Staged,
     /// git diff <commit/branch/tag> (changes since specific reference)
     SinceCommit(String),
+    /// git diff <empty tree> (every tracked line in the working tree)
+    All,
+    /// git diff <from> <to> (changes between two commits, ignoring the working tree)
+    Between(String, String),
 }
 
+/// Hash of git's empty tree; diffing against it reports every file as newly added
+pub const EMPTY_TREE_SHA: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
 #[derive(Debug, Clone)]
 pub struct ChangedLine {
     pub line_number: usize,
@@ -62,16 +388,325 @@ pub enum ChangeType {
     Modified,
 }
 
+/// What a changed path holds. Only regular files have content worth checking against
+/// rules; the "lines" of the others are link targets and pointer metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileKind {
+    #[default]
+    Regular,
+    /// Mode 120000: the single added line is the link target. `replaced_file` is set when
+    /// the path was a regular file before this change.
+    Symlink { replaced_file: bool },
+    /// A Git LFS pointer standing in for content stored outside the repository
+    LfsPointer,
+    /// Binary content, which git shows no lines of
+    Binary,
+    /// The change deletes the file, so nothing in it is left to review
+    Deleted,
+}
+
+/// First line of every Git LFS pointer file
+const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/";
+
+/// Git file mode of symbolic links
+const SYMLINK_MODE: &str = "120000";
+
 #[derive(Debug, Clone)]
 pub struct FileDiff {
+    /// Path after the change; renamed and copied files are reported under their new path
     pub path: String,
+    /// Path before a rename or copy
+    pub previous_path: Option<String>,
+    pub kind: FileKind,
     pub added_lines: Vec<ChangedLine>,
     pub removed_lines: Vec<ChangedLine>,
+    /// Added lines whose change block only re-indented, re-spaced or re-wrapped the
+    /// removed lines
+    pub format_only_lines: Vec<usize>,
+    /// Added lines of each `@@` hunk, as ranges of indexes into `added_lines`
+    pub hunks: Vec<std::ops::Range<usize>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct GitDiff {
     pub files: Vec<FileDiff>,
+    /// Sections of the diff that could not be reviewed, and why
+    pub parse_warnings: Vec<ParseWarning>,
+}
+
+/// A diff section the parser skipped, so users can see why a file was not analyzed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// The file the section belongs to, when its header could be read
+    pub path: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{path}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl GitDiff {
+    /// Drop added lines that only reformat existing code, so formatter commits are not
+    /// reviewed as new code. Returns how many lines were dropped.
+    pub fn skip_format_only_changes(&mut self) -> usize {
+        let mut skipped = 0;
+        for file in &mut self.files {
+            let before = file.added_lines.len();
+            let format_only = &file.format_only_lines;
+
+            // Hunks index into the added lines, so shift them past the dropped ones
+            let mut kept_before = Vec::with_capacity(before + 1);
+            let mut kept = 0;
+            for line in &file.added_lines {
+                kept_before.push(kept);
+                kept += usize::from(!format_only.contains(&line.line_number));
+            }
+            kept_before.push(kept);
+            for hunk in &mut file.hunks {
+                *hunk = kept_before[hunk.start]..kept_before[hunk.end];
+            }
+
+            file.added_lines.retain(|line| !format_only.contains(&line.line_number));
+            skipped += before - file.added_lines.len();
+        }
+        skipped
+    }
+}
+
+/// Context lines kept on each side of a line, as in `git diff`'s default hunks
+const SNIPPET_CONTEXT_LINES: usize = 3;
+
+impl FileDiff {
+    /// Treat lines `lines` (1-based, inclusive) of `content` as added, for reviewing file
+    /// contents that do not come from a diff. Context comes from the surrounding file, so
+    /// suppression comments just above the region still apply. The range is clamped to the
+    /// end of the file.
+    pub fn from_region(path: &str, content: &str, lines: RangeInclusive<usize>) -> Self {
+        let all_lines: Vec<&str> = content.lines().collect();
+        let first = (*lines.start()).max(1);
+        let last = (*lines.end()).min(all_lines.len());
+
+        let added_lines = (first..=last)
+            .map(|line_number| {
+                let index = line_number - 1;
+                let before = index.saturating_sub(SNIPPET_CONTEXT_LINES);
+                let after = (line_number + SNIPPET_CONTEXT_LINES).min(all_lines.len());
+                ChangedLine {
+                    line_number,
+                    content: all_lines[index].to_string(),
+                    change_type: ChangeType::Added,
+                    context_before: all_lines[before..index]
+                        .iter()
+                        .map(|l| l.to_string())
+                        .collect(),
+                    context_after: all_lines[line_number..after]
+                        .iter()
+                        .map(|l| l.to_string())
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        FileDiff {
+            path: path.to_string(),
+            previous_path: None,
+            kind: FileKind::Regular,
+            hunks: std::iter::once(0..added_lines.len()).collect(),
+            added_lines,
+            removed_lines: Vec::new(),
+            format_only_lines: Vec::new(),
+        }
+    }
+}
+
+/// A run of removed then added lines between context lines. It is format-only when both
+/// sides match once all whitespace is ignored.
+#[derive(Default)]
+struct ChangeBlock {
+    removed: String,
+    added: String,
+    added_lines: Vec<usize>,
+}
+
+impl ChangeBlock {
+    fn remove(&mut self, content: &str) {
+        self.removed.extend(content.chars().filter(|c| !c.is_whitespace()));
+    }
+
+    fn add(&mut self, line_number: usize, content: &str) {
+        self.added.extend(content.chars().filter(|c| !c.is_whitespace()));
+        self.added_lines.push(line_number);
+    }
+
+    /// End the block, returning its added line numbers if it was format-only
+    fn finish(&mut self) -> Vec<usize> {
+        let block = std::mem::take(self);
+        if !block.removed.is_empty() && block.removed == block.added {
+            block.added_lines
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+fn is_regular_mode(mode: &str) -> bool {
+    matches!(mode.trim(), "100644" | "100755")
+}
+
+/// An LFS pointer is recognised by its first line, whether added or unchanged context
+fn mark_lfs_pointer(file: &mut FileDiff, line_number: usize, content: &str) {
+    if line_number == 1
+        && file.kind == FileKind::Regular
+        && content.starts_with(LFS_POINTER_VERSION)
+    {
+        file.kind = FileKind::LfsPointer;
+    }
+}
+
+/// Extended header lines git writes between `diff --git` and the first hunk
+const EXTENDED_HEADERS: &[&str] = &[
+    "index ",
+    "--- ",
+    "+++ ",
+    "similarity index ",
+    "dissimilarity index ",
+    "rename from ",
+    "rename to ",
+    "copy from ",
+    "copy to ",
+];
+
+/// Longest excerpt of an offending line quoted in a parse warning
+const EXCERPT_CHARS: usize = 60;
+
+fn excerpt(line: &str) -> String {
+    match line.char_indices().nth(EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// The path of a file in a plain unified diff, from its `--- old` and `+++ new` lines. The
+/// `a/` and `b/` prefixes are dropped when both sides have them, and deleted files are named
+/// by their old path.
+fn unified_header_path(old_line: &str, new_line: &str) -> String {
+    // Timestamps follow a tab, as in `diff -u` output
+    let side = |line: &str| line[4..].split('\t').next().unwrap_or_default().trim().to_string();
+    let (old, new) = (side(old_line), side(new_line));
+    let prefixed = |path: &str, prefix: &str| path == "/dev/null" || path.starts_with(prefix);
+    let strip = prefixed(&old, "a/") && prefixed(&new, "b/");
+    let (path, prefix) = match new.as_str() {
+        "/dev/null" => (old, "a/"),
+        _ => (new, "b/"),
+    };
+    match strip {
+        true => path.strip_prefix(prefix).map(str::to_string).unwrap_or(path),
+        false => path,
+    }
+}
+
+/// What the extended header of the current file said, to explain files without content
+#[derive(Default)]
+struct FileHeader {
+    mode_changed: bool,
+    renamed: bool,
+    deleted: bool,
+    binary: bool,
+    /// Inside a hunk whose header could not be read
+    skipping_hunk: bool,
+    /// An unrecognised line was already reported for this file
+    warned_unrecognised: bool,
+}
+
+impl FileHeader {
+    fn read_extended_line(
+        &mut self,
+        line: &str,
+        file: &mut FileDiff,
+        warnings: &mut Vec<ParseWarning>,
+    ) {
+        if self.binary {
+            // Literal data of a `GIT binary patch`
+        } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+            self.binary = true;
+        } else if let Some(from) =
+            line.strip_prefix("rename from ").or_else(|| line.strip_prefix("copy from "))
+        {
+            self.renamed = true;
+            file.previous_path = Some(from.to_string());
+        } else if let Some(to) =
+            line.strip_prefix("rename to ").or_else(|| line.strip_prefix("copy to "))
+        {
+            file.path = to.to_string();
+        } else if EXTENDED_HEADERS.iter().any(|prefix| line.starts_with(prefix))
+            || line == "---"
+            || line == "+++"
+        {
+            // Known headers carry nothing to review
+        } else if !self.warned_unrecognised {
+            self.warned_unrecognised = true;
+            warnings.push(ParseWarning {
+                path: Some(file.path.clone()),
+                message: format!("unrecognised line `{}` was skipped", excerpt(line)),
+            });
+        }
+    }
+
+    /// Keep `file`, noting why it has nothing to review when it has no hunks
+    fn finish(
+        &self,
+        mut file: FileDiff,
+        files: &mut Vec<FileDiff>,
+        warnings: &mut Vec<ParseWarning>,
+    ) {
+        if self.deleted {
+            file.kind = FileKind::Deleted;
+        } else if self.binary {
+            file.kind = FileKind::Binary;
+        }
+        if file.hunks.is_empty() && !self.deleted {
+            let reason = if self.binary {
+                Some("binary file; not reviewed")
+            } else if self.mode_changed {
+                Some("only the file mode changed; no content to review")
+            } else if self.renamed {
+                Some("renamed or copied without content changes; not reviewed")
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                warnings.push(ParseWarning {
+                    path: Some(file.path.clone()),
+                    message: reason.to_string(),
+                });
+            }
+        }
+        files.push(file);
+    }
+}
+
+/// Append `line` to a context window, keeping only the last `SNIPPET_CONTEXT_LINES`
+fn push_context_line(window: &mut Vec<String>, line: String) {
+    window.push(line);
+    if window.len() > SNIPPET_CONTEXT_LINES {
+        window.remove(0);
+    }
+}
+
+/// Give the next new-file line of a hunk to the added lines before it that still lack
+/// after-context. Context never reaches past the hunk, as git shows nothing beyond it.
+fn extend_context_after(file: &mut FileDiff, awaiting: &mut Vec<usize>, line: &str) {
+    awaiting.retain(|&index| {
+        let context_after = &mut file.added_lines[index].context_after;
+        context_after.push(line.to_string());
+        context_after.len() < SNIPPET_CONTEXT_LINES
+    });
 }
 
 pub struct GitDiffParser;
@@ -79,44 +714,140 @@ pub struct GitDiffParser;
 impl GitDiffParser {
     pub fn parse(diff_output: &str) -> Result<GitDiff> {
         let mut files = Vec::new();
+        let mut parse_warnings = Vec::new();
         let mut current_file: Option<FileDiff> = None;
+        let mut header = FileHeader::default();
         let mut current_line_number = 0;
+        // Unchanged lines before a removed line, and new-file lines before an added one
         let mut context_lines: Vec<String> = Vec::new();
+        let mut new_file_lines: Vec<String> = Vec::new();
+        // Added lines of the hunk still short of after-context, as indexes into added_lines
+        let mut awaiting_after: Vec<usize> = Vec::new();
+        let mut block = ChangeBlock::default();
+        // Git splits a file-to-symlink type change into a deletion and a creation
+        let mut deleted_regular_files: Vec<String> = Vec::new();
+        let mut was_regular_file = false;
+        // Set after a file header that could not be read, until the next one
+        let mut skipping_file = false;
+        let mut warned_preamble = false;
 
         for line in diff_output.lines() {
+            let is_added = line.starts_with('+') && !line.starts_with("+++");
+            let is_removed = line.starts_with('-') && !line.starts_with("---");
+            // A block ends at anything but more added lines, or removals before any addition
+            if !(is_added || (is_removed && block.added_lines.is_empty())) {
+                let format_only = block.finish();
+                if let Some(ref mut file) = current_file {
+                    file.format_only_lines.extend(format_only);
+                }
+            }
+
             if line.starts_with("diff --git") {
                 // Save previous file if exists
                 if let Some(file) = current_file.take() {
-                    files.push(file);
+                    header.finish(file, &mut files, &mut parse_warnings);
                 }
+                header = FileHeader::default();
 
                 // Extract file path from "diff --git a/path b/path"
+                was_regular_file = false;
+                skipping_file = false;
+                awaiting_after.clear();
                 if let Some(path) = Self::extract_file_path(line) {
-                    current_file =
-                        Some(FileDiff { path, added_lines: Vec::new(), removed_lines: Vec::new() });
+                    current_file = Some(FileDiff {
+                        path,
+                        previous_path: None,
+                        kind: FileKind::Regular,
+                        added_lines: Vec::new(),
+                        removed_lines: Vec::new(),
+                        format_only_lines: Vec::new(),
+                        hunks: Vec::new(),
+                    });
+                } else {
+                    skipping_file = true;
+                    parse_warnings.push(ParseWarning {
+                        path: None,
+                        message: format!(
+                            "unrecognised file header `{}` (diff.noprefix or \
+                             diff.mnemonicPrefix set?); its changes were skipped",
+                            excerpt(line)
+                        ),
+                    });
+                }
+            } else if skipping_file || (header.skipping_hunk && !line.starts_with("@@")) {
+                continue;
+            } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
+                header.deleted = true;
+                if let Some(ref file) = current_file {
+                    if is_regular_mode(mode) {
+                        deleted_regular_files.push(file.path.clone());
+                    }
+                }
+            } else if let Some(mode) = line.strip_prefix("old mode ") {
+                header.mode_changed = true;
+                was_regular_file = is_regular_mode(mode);
+            } else if let Some(mode) =
+                line.strip_prefix("new file mode ").or_else(|| line.strip_prefix("new mode "))
+            {
+                if let Some(ref mut file) = current_file {
+                    if mode.trim() == SYMLINK_MODE {
+                        let replaced_file =
+                            was_regular_file || deleted_regular_files.contains(&file.path);
+                        file.kind = FileKind::Symlink { replaced_file };
+                    }
                 }
             } else if line.starts_with("@@") {
                 // Parse hunk header to get line numbers
-                current_line_number = Self::parse_hunk_header(line).unwrap_or(0);
                 context_lines.clear();
-            } else if line.starts_with('+') && !line.starts_with("+++") {
+                new_file_lines.clear();
+                awaiting_after.clear();
+                match Self::parse_hunk_header(line) {
+                    Some(line_number) => {
+                        current_line_number = line_number;
+                        header.skipping_hunk = false;
+                        if let Some(ref mut file) = current_file {
+                            let start = file.added_lines.len();
+                            file.hunks.push(start..start);
+                        }
+                    }
+                    None => {
+                        header.skipping_hunk = true;
+                        parse_warnings.push(ParseWarning {
+                            path: current_file.as_ref().map(|file| file.path.clone()),
+                            message: format!(
+                                "malformed hunk header `{}`; the hunk was skipped",
+                                excerpt(line)
+                            ),
+                        });
+                    }
+                }
+            } else if is_added {
                 // Added line
                 if let Some(ref mut file) = current_file {
                     let content = line[1..].to_string(); // Remove '+' prefix
+                    mark_lfs_pointer(file, current_line_number, &content);
+                    block.add(current_line_number, &content);
+                    extend_context_after(file, &mut awaiting_after, &content);
                     let changed_line = ChangedLine {
                         line_number: current_line_number,
-                        content,
+                        content: content.clone(),
                         change_type: ChangeType::Added,
-                        context_before: context_lines.clone(),
-                        context_after: Vec::new(), // Will be filled later if needed
+                        context_before: new_file_lines.clone(),
+                        context_after: Vec::new(), // Filled in as the following lines are read
                     };
+                    awaiting_after.push(file.added_lines.len());
                     file.added_lines.push(changed_line);
+                    push_context_line(&mut new_file_lines, content);
+                    if let Some(hunk) = file.hunks.last_mut() {
+                        hunk.end = file.added_lines.len();
+                    }
                 }
                 current_line_number += 1;
-            } else if line.starts_with('-') && !line.starts_with("---") {
+            } else if is_removed {
                 // Removed line
                 if let Some(ref mut file) = current_file {
                     let content = line[1..].to_string(); // Remove '-' prefix
+                    block.remove(&content);
                     let changed_line = ChangedLine {
                         line_number: current_line_number,
                         content,
@@ -129,27 +860,78 @@ impl GitDiffParser {
                 // Don't increment line number for removed lines
             } else if let Some(stripped) = line.strip_prefix(' ') {
                 // Context line
-                context_lines.push(stripped.to_string());
-                // Keep only last 3 context lines
-                if context_lines.len() > 3 {
-                    context_lines.remove(0);
+                if let Some(ref mut file) = current_file {
+                    mark_lfs_pointer(file, current_line_number, stripped);
+                    extend_context_after(file, &mut awaiting_after, stripped);
                 }
+                push_context_line(&mut context_lines, stripped.to_string());
+                push_context_line(&mut new_file_lines, stripped.to_string());
                 current_line_number += 1;
-            } else if !line.starts_with("index")
-                && !line.starts_with("---")
-                && !line.starts_with("+++")
-            {
-                // Other lines (binary files, etc.)
+            } else if line.is_empty() || line.starts_with('\\') {
+                // Blank separators and "\ No newline at end of file"
                 continue;
+            } else if let Some(file) = &mut current_file {
+                header.read_extended_line(line, file, &mut parse_warnings);
+            } else if !warned_preamble {
+                warned_preamble = true;
+                parse_warnings.push(ParseWarning {
+                    path: None,
+                    message: format!(
+                        "content before the first `diff --git` header was ignored, starting \
+                         at `{}`",
+                        excerpt(line)
+                    ),
+                });
             }
         }
 
         // Add the last file
-        if let Some(file) = current_file {
-            files.push(file);
+        if let Some(mut file) = current_file {
+            file.format_only_lines.extend(block.finish());
+            header.finish(file, &mut files, &mut parse_warnings);
         }
 
-        Ok(GitDiff { files })
+        Ok(GitDiff { files, parse_warnings })
+    }
+
+    /// Parse a patch from outside patingin: `git diff`, `git show` or `git format-patch`
+    /// output, or a plain unified diff (`diff -u`) without `diff --git` headers. Text before
+    /// the first file, such as a commit message or mail headers, and a trailing mail
+    /// signature are skipped.
+    pub fn parse_patch(input: &str) -> Result<GitDiff> {
+        let lines: Vec<&str> = input.lines().collect();
+        let is_unified_header = |index: usize| {
+            lines[index].starts_with("--- ")
+                && lines.get(index + 1).is_some_and(|next| next.starts_with("+++ "))
+        };
+        let Some(start) = (0..lines.len())
+            .find(|&index| lines[index].starts_with("diff --git") || is_unified_header(index))
+        else {
+            return Self::parse("");
+        };
+        // `git format-patch` ends with "-- " and the git version
+        let end = lines
+            .iter()
+            .rposition(|line| *line == "-- ")
+            .filter(|&index| {
+                index > start
+                    && lines[index + 1..].iter().all(|line| {
+                        !line.starts_with(['+', '-', ' ', '@']) && !line.starts_with("diff --git")
+                    })
+            })
+            .unwrap_or(lines.len());
+
+        let has_git_headers = lines[start..end].iter().any(|line| line.starts_with("diff --git"));
+        let mut patch = String::with_capacity(input.len());
+        for index in start..end {
+            if !has_git_headers && is_unified_header(index) {
+                let path = unified_header_path(lines[index], lines[index + 1]);
+                patch.push_str(&format!("diff --git a/{path} b/{path}\n"));
+            }
+            patch.push_str(lines[index]);
+            patch.push('\n');
+        }
+        Self::parse(&patch)
     }
 
     #[allow(dead_code)]
@@ -158,6 +940,8 @@ impl GitDiffParser {
             DiffScope::Unstaged => "git diff".to_string(),
             DiffScope::Staged => "git diff --cached".to_string(),
             DiffScope::SinceCommit(reference) => format!("git diff {reference}"),
+            DiffScope::All => format!("git diff {EMPTY_TREE_SHA}"),
+            DiffScope::Between(from, to) => format!("git diff {from} {to}"),
         }
     }
 
@@ -169,24 +953,28 @@ impl GitDiffParser {
         scope: &DiffScope,
         working_dir: Option<&Path>,
     ) -> Result<String> {
-        let command_parts: Vec<&str> = match scope {
-            DiffScope::Unstaged => vec!["git", "diff"],
-            DiffScope::Staged => vec!["git", "diff", "--cached"],
-            DiffScope::SinceCommit(reference) => vec!["git", "diff", reference],
+        let detect_from = match working_dir {
+            Some(dir) => dir.to_path_buf(),
+            None => std::env::current_dir()?,
         };
+        let vcs = Vcs::detect(&detect_from);
+        let command_parts = vcs.diff_command(scope)?;
 
-        let mut command = Command::new(command_parts[0]);
+        let mut command = Command::new(&command_parts[0]);
         command.args(&command_parts[1..]);
 
         if let Some(dir) = working_dir {
             command.current_dir(dir);
         }
 
-        let output = command.output()?;
+        let output = command
+            .output()
+            .map_err(|e| anyhow::anyhow!("Failed to run `{}`: {}", command_parts.join(" "), e))?;
 
         if !output.status.success() {
             return Err(anyhow::anyhow!(
-                "Git diff command failed: {}",
+                "{} diff command failed: {}",
+                vcs.name(),
                 String::from_utf8_lossy(&output.stderr)
             ));
         }
@@ -226,6 +1014,114 @@ impl GitDiffParser {
 mod git_diff_tests {
     use super::*;
 
+    #[test]
+    fn test_detects_lfs_pointers_and_symlinks() {
+        let diff_output = "diff --git a/assets/logo.psd b/assets/logo.psd
+index 1111111..2222222 100644
+--- a/assets/logo.psd
++++ b/assets/logo.psd
+@@ -1,3 +1,3 @@
+ version https://git-lfs.github.com/spec/v1
+-oid sha256:aaaa
+-size 100
++oid sha256:bbbb
++size 200
+diff --git a/config/app.ex b/config/app.ex
+deleted file mode 100644
+index 3333333..0000000
+--- a/config/app.ex
++++ /dev/null
+@@ -1 +0,0 @@
+-config :app, key: 1
+diff --git a/config/app.ex b/config/app.ex
+new file mode 120000
+index 0000000..4444444
+--- /dev/null
++++ b/config/app.ex
+@@ -0,0 +1 @@
++../shared/app.ex
+\\ No newline at end of file
+diff --git a/docs/latest b/docs/latest
+new file mode 120000
+index 0000000..5555555
+--- /dev/null
++++ b/docs/latest
+@@ -0,0 +1 @@
++v2
+diff --git a/lib/a.ex b/lib/a.ex
+old mode 100644
+new mode 120000
+";
+
+        let parsed = GitDiffParser::parse(diff_output).unwrap();
+
+        let kinds: Vec<(&str, FileKind)> =
+            parsed.files.iter().map(|file| (file.path.as_str(), file.kind)).collect();
+        assert_eq!(
+            kinds,
+            [
+                ("assets/logo.psd", FileKind::LfsPointer),
+                ("config/app.ex", FileKind::Deleted),
+                ("config/app.ex", FileKind::Symlink { replaced_file: true }),
+                ("docs/latest", FileKind::Symlink { replaced_file: false }),
+                ("lib/a.ex", FileKind::Symlink { replaced_file: true }),
+            ]
+        );
+        assert_eq!(parsed.files[2].added_lines[0].content, "../shared/app.ex");
+    }
+
+    #[test]
+    fn test_file_region_keeps_surrounding_context() {
+        let content = "one\ntwo\nthree\nfour\nfive\nsix\n";
+
+        let region = FileDiff::from_region("lib/a.ex", content, 4..=5);
+
+        let numbers: Vec<usize> = region.added_lines.iter().map(|l| l.line_number).collect();
+        assert_eq!(numbers, [4, 5]);
+        assert_eq!(region.added_lines[0].content, "four");
+        assert_eq!(region.added_lines[0].context_before, ["one", "two", "three"]);
+        assert_eq!(region.added_lines[1].context_after, ["six"]);
+
+        assert_eq!(FileDiff::from_region("lib/a.ex", content, 5..=99).added_lines.len(), 2);
+        assert!(FileDiff::from_region("lib/a.ex", content, 9..=12).added_lines.is_empty());
+    }
+
+    #[test]
+    fn test_added_lines_get_context_from_the_new_file() {
+        let diff_output = "diff --git a/lib/b.ex b/lib/b.ex
+index 1111111..2222222 100644
+--- a/lib/b.ex
++++ b/lib/b.ex
+@@ -1,5 +1,7 @@
+ defmodule B do
+   def a, do: 1
+-  def old, do: 0
++  # patingin-ignore-next-line
++  def c(x), do: String.to_atom(x)
+   def b, do: 2
+   def e, do: 3
+   def f, do: 4
+@@ -20,2 +22,3 @@
+   def g, do: 5
++  def h, do: 6
+";
+
+        let parsed = GitDiffParser::parse(diff_output).unwrap();
+        let added = &parsed.files[0].added_lines;
+
+        assert_eq!(added[1].line_number, 4);
+        assert_eq!(
+            added[1].context_before,
+            ["defmodule B do", "  def a, do: 1", "  # patingin-ignore-next-line"]
+        );
+        assert_eq!(added[1].context_after, ["  def b, do: 2", "  def e, do: 3", "  def f, do: 4"]);
+        assert_eq!(added[0].context_after[0], "  def c(x), do: String.to_atom(x)");
+        // Context stops at the end of the hunk
+        assert_eq!(added[2].context_before, ["  def g, do: 5"]);
+        assert!(added[2].context_after.is_empty());
+        assert_eq!(parsed.files[0].removed_lines[0].context_before.len(), 2);
+    }
+
     #[test]
     fn test_parse_git_diff_basic() {
         let diff_output = r#"diff --git a/lib/user.ex b/lib/user.ex
@@ -248,8 +1144,8 @@ index 1234567..abcdefg 100644
 
         let file_diff = &parsed.files[0];
         assert_eq!(file_diff.path, "lib/user.ex");
-        assert!(file_diff.added_lines.len() > 0);
-        assert!(file_diff.removed_lines.len() > 0);
+        assert!(!file_diff.added_lines.is_empty());
+        assert!(!file_diff.removed_lines.is_empty());
 
         // Should capture the added line with the fix
         let added_lines: Vec<_> = file_diff.added_lines.iter().map(|line| &line.content).collect();
@@ -326,6 +1222,9 @@ index 9876543..fedcba9 100644
         let since_branch_cmd =
             GitDiffParser::build_git_command(&DiffScope::SinceCommit("origin/main".to_string()));
         assert_eq!(since_branch_cmd, "git diff origin/main");
+
+        let all_cmd = GitDiffParser::build_git_command(&DiffScope::All);
+        assert_eq!(all_cmd, format!("git diff {EMPTY_TREE_SHA}"));
     }
 
     #[test]
@@ -341,4 +1240,336 @@ index 9876543..fedcba9 100644
         assert_eq!(parsed.files[0].added_lines.len(), 0);
         assert_eq!(parsed.files[0].removed_lines.len(), 0);
     }
+
+    #[test]
+    fn test_format_only_blocks_are_detected() {
+        let diff = "diff --git a/lib/user.ex b/lib/user.ex\n--- a/lib/user.ex\n+++ b/lib/user.ex\n@@ -1,6 +1,7 @@\n def run(input) do\n-String.to_atom(input)\n+  String.to_atom(input)\n end\n-call(a, b)\n+call(\n+  a,\n+  b\n+)\n def other do\n-old()\n+String.to_atom(other)\n";
+
+        let mut parsed = GitDiffParser::parse(diff).unwrap();
+
+        // Re-indented and re-wrapped blocks are format-only; the real edit is not
+        assert_eq!(parsed.files[0].format_only_lines, [2, 4, 5, 6, 7]);
+        assert_eq!(parsed.skip_format_only_changes(), 5);
+        let remaining: Vec<usize> =
+            parsed.files[0].added_lines.iter().map(|line| line.line_number).collect();
+        assert_eq!(remaining, [9]);
+        assert_eq!(parsed.files[0].hunks, [std::ops::Range { start: 0, end: 1 }]);
+    }
+
+    #[test]
+    fn test_renamed_deleted_and_binary_files() {
+        let diff_output = "diff --git a/lib/old_name.ex b/lib/new_name.ex
+similarity index 90%
+rename from lib/old_name.ex
+rename to lib/new_name.ex
+index 1111111..2222222 100644
+--- a/lib/old_name.ex
++++ b/lib/new_name.ex
+@@ -1,2 +1,2 @@
+ defmodule A do
+-  def f(x), do: x
++  def f(x), do: String.to_atom(x)
+diff --git a/lib/gone.ex b/lib/gone.ex
+deleted file mode 100644
+index 3333333..0000000
+--- a/lib/gone.ex
++++ /dev/null
+@@ -1 +0,0 @@
+-String.to_atom(x)
+diff --git a/assets/logo.png b/assets/logo.png
+new file mode 100644
+index 0000000..4444444
+Binary files /dev/null and b/assets/logo.png differ
+";
+
+        let parsed = GitDiffParser::parse(diff_output).unwrap();
+
+        let files: Vec<(&str, Option<&str>, FileKind)> = parsed
+            .files
+            .iter()
+            .map(|file| (file.path.as_str(), file.previous_path.as_deref(), file.kind))
+            .collect();
+        assert_eq!(
+            files,
+            [
+                ("lib/new_name.ex", Some("lib/old_name.ex"), FileKind::Regular),
+                ("lib/gone.ex", None, FileKind::Deleted),
+                ("assets/logo.png", None, FileKind::Binary),
+            ]
+        );
+        assert_eq!(parsed.files[0].added_lines[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_unreviewable_sections_become_parse_warnings() {
+        let diff_output = "warning: LF will be replaced by CRLF
+diff --git a/bin/run b/bin/run
+old mode 100644
+new mode 100755
+diff --git a/assets/logo.png b/assets/logo.png
+index 1111111..2222222 100644
+Binary files a/assets/logo.png and b/assets/logo.png differ
+diff --git c/lib/a.ex w/lib/a.ex
+--- c/lib/a.ex
++++ w/lib/a.ex
+@@ -1 +1 @@
++String.to_atom(x)
+diff --git a/lib/b.ex b/lib/b.ex
+index 1111111..2222222 100644
+--- a/lib/b.ex
++++ b/lib/b.ex
+@@ -1 +1 @@
+-old
++new
+@@ garbled @@
++String.to_atom(x)
+diff --git a/lib/c.ex b/lib/c.ex
+similarity index 100%
+rename from lib/old.ex
+rename to lib/c.ex
+";
+        let parsed = GitDiffParser::parse(diff_output).unwrap();
+        let messages: Vec<String> = parsed.parse_warnings.iter().map(|w| w.to_string()).collect();
+
+        assert_eq!(
+            messages,
+            [
+                "content before the first `diff --git` header was ignored, starting at \
+                 `warning: LF will be replaced by CRLF`",
+                "bin/run: only the file mode changed; no content to review",
+                "assets/logo.png: binary file; not reviewed",
+                "unrecognised file header `diff --git c/lib/a.ex w/lib/a.ex` (diff.noprefix or \
+                 diff.mnemonicPrefix set?); its changes were skipped",
+                "lib/b.ex: malformed hunk header `@@ garbled @@`; the hunk was skipped",
+                "lib/c.ex: renamed or copied without content changes; not reviewed",
+            ]
+        );
+        let b = parsed.files.iter().find(|file| file.path == "lib/b.ex").unwrap();
+        assert_eq!(b.added_lines.len(), 1);
+        assert_eq!(b.added_lines[0].content, "new");
+        assert!(parsed.files.iter().all(|file| file.path != "lib/a.ex"));
+    }
+
+    #[test]
+    fn test_parse_patch_from_format_patch() {
+        let patch = "From 3f2a91c Mon Sep 17 00:00:00 2001
+From: Ana <ana@example.com>
+Subject: [PATCH] Add f
+
+---
+ lib/a.ex | 1 +
+ 1 file changed, 1 insertion(+)
+
+diff --git a/lib/a.ex b/lib/a.ex
+--- a/lib/a.ex
++++ b/lib/a.ex
+@@ -1,2 +1,3 @@
+ defmodule A do
++  def f(x), do: String.to_atom(x)
+ end
+-- 
+2.43.0
+
+";
+        let parsed = GitDiffParser::parse_patch(patch).unwrap();
+
+        assert_eq!(parsed.files.len(), 1);
+        assert_eq!(parsed.files[0].added_lines[0].line_number, 2);
+        assert!(parsed.parse_warnings.is_empty(), "{:?}", parsed.parse_warnings);
+    }
+
+    #[test]
+    fn test_parse_patch_from_plain_unified_diff() {
+        let patch = "--- lib/a.ex.orig\t2026-01-01 10:00:00
++++ lib/a.ex\t2026-01-02 10:00:00
+@@ -1 +1,2 @@
+ defmodule A do
++  x = 1
+--- a/old.ex
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+--- /dev/null
++++ b/lib/new.ex
+@@ -0,0 +1 @@
++new
+";
+        let parsed = GitDiffParser::parse_patch(patch).unwrap();
+
+        let paths: Vec<&str> = parsed.files.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, ["lib/a.ex", "old.ex", "lib/new.ex"]);
+        assert_eq!(parsed.files[0].added_lines[0].content, "  x = 1");
+        assert_eq!(parsed.files[2].added_lines[0].line_number, 1);
+        assert!(GitDiffParser::parse_patch("no diff here\n").unwrap().files.is_empty());
+    }
+
+    #[test]
+    fn test_regular_diffs_have_no_parse_warnings() {
+        let diff_output = "diff --git a/lib/a.ex b/lib/a.ex
+new file mode 100644
+index 0000000..1111111
+--- /dev/null
++++ b/lib/a.ex
+@@ -0,0 +1,2 @@
++defmodule A do
++end
+\\ No newline at end of file
+diff --git a/lib/empty.ex b/lib/empty.ex
+new file mode 100644
+index 0000000..e69de29
+";
+        assert!(GitDiffParser::parse(diff_output).unwrap().parse_warnings.is_empty());
+    }
+
+    #[test]
+    fn test_hunks_index_added_lines() {
+        let diff = "diff --git a/lib/a.ex b/lib/a.ex\n--- a/lib/a.ex\n+++ b/lib/a.ex\n@@ -1,2 +1,3 @@\n one\n+two\n+three\n@@ -40,2 +41,2 @@\n-old\n+new\n forty\n@@ -60 +61,0 @@\n-gone\n";
+
+        let parsed = GitDiffParser::parse(diff).unwrap();
+
+        assert_eq!(parsed.files[0].hunks, [0..2, 2..3, 3..3]);
+        assert_eq!(parsed.files[0].added_lines[2].line_number, 41);
+        assert_eq!(
+            FileDiff::from_region("a.ex", "a\nb\nc\n", 2..=3).hunks,
+            [std::ops::Range { start: 0, end: 2 }]
+        );
+    }
+
+    fn commit_file(repo: &Repository, path: &str, content: &str, message: &str) -> git2::Oid {
+        let workdir = repo.workdir().unwrap();
+        let file_path = workdir.join(path);
+        std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        std::fs::write(file_path, content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parents: Vec<git2::Commit> =
+            repo.head().ok().and_then(|h| h.peel_to_commit().ok()).into_iter().collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parent_refs).unwrap()
+    }
+
+    #[test]
+    fn test_commits_in_range_oldest_first() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let root = commit_file(&repo, "a.ex", "a\n", "root");
+        let first = commit_file(&repo, "b.ex", "b\n", "first");
+        let second = commit_file(&repo, "c.ex", "c\n", "second");
+
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        let commits = git.commits_in_range(&format!("{root}..{second}")).unwrap();
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].sha, first.to_string());
+        assert_eq!(commits[0].summary, "first");
+        assert_eq!(commits[0].parent, Some(root.to_string()));
+        assert_eq!(commits[1].sha, second.to_string());
+
+        // A bare base reference means "up to HEAD"
+        assert_eq!(git.commits_in_range(&root.to_string()).unwrap(), commits);
+        assert!(git.commits_in_range(&second.to_string()).unwrap().is_empty());
+
+        assert_eq!(git.commit_info("HEAD~1").unwrap(), commits[0]);
+        assert_eq!(git.commit_info(&root.to_string()).unwrap().parent, None);
+        assert!(git.commit_info("no-such-ref").is_err());
+    }
+
+    #[test]
+    fn test_commit_info_diff_scope() {
+        let commit = CommitInfo {
+            sha: "abcdef1234567890".to_string(),
+            summary: "Add feature".to_string(),
+            author: "Test".to_string(),
+            parent: None,
+        };
+        assert_eq!(commit.short_sha(), "abcdef1");
+        assert_eq!(
+            commit.diff_scope(),
+            DiffScope::Between(EMPTY_TREE_SHA.to_string(), "abcdef1234567890".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ensure_reference_reports_available_refs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let root = commit_file(&repo, "a.ex", "a\n", "root");
+        repo.tag_lightweight("v1.0.0", &repo.find_object(root, None).unwrap(), false).unwrap();
+
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        assert!(!git.is_shallow());
+        assert!(git.ensure_reference("HEAD", false).is_ok());
+        assert!(git.ensure_reference("v1.0.0", false).is_ok());
+
+        let error = git.ensure_reference("origin/main", false).unwrap_err().to_string();
+        assert!(error.starts_with("origin/main not found; check the spelling"));
+        assert!(error.contains("v1.0.0"));
+        assert!(error.contains("--auto-fetch"));
+        assert!(error.contains("--staged"));
+    }
+
+    #[test]
+    fn test_hooks_dir_follows_core_hooks_path() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        assert!(git.hooks_dir().ends_with(".git/hooks"));
+
+        repo.config().unwrap().set_str("core.hooksPath", ".githooks").unwrap();
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        assert_eq!(git.hooks_dir(), repo.workdir().unwrap().join(".githooks"));
+    }
+
+    #[test]
+    fn test_remote_url_prefers_origin() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        assert_eq!(git.get_remote_url(), None);
+
+        repo.remote("upstream", "git@gitlab.com:acme/platform/app.git").unwrap();
+        assert_eq!(git.remote_repository().unwrap().provider, RemoteProvider::GitLab);
+
+        repo.remote("origin", "https://github.com/jane/app.git").unwrap();
+        let remote = git.remote_repository().unwrap();
+        assert_eq!((remote.provider, remote.slug.as_str()), (RemoteProvider::GitHub, "jane/app"));
+    }
+
+    #[test]
+    fn test_diff_between_reads_object_database() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let root = commit_file(&repo, "lib/user.ex", "defmodule User do\nend\n", "root");
+        let next = commit_file(
+            &repo,
+            "lib/user.ex",
+            "defmodule User do\n  def to_atom(x), do: String.to_atom(x)\nend\n",
+            "add atom",
+        );
+
+        // A bare clone has no working tree; everything must come from the object database
+        let bare_dir = tempfile::TempDir::new().unwrap();
+        Repository::init_bare(bare_dir.path()).unwrap();
+        let bare = Repository::open_bare(bare_dir.path()).unwrap();
+        bare.remote_anonymous(temp_dir.path().to_str().unwrap())
+            .unwrap()
+            .fetch(&["+refs/heads/*:refs/heads/*"], None, None)
+            .unwrap();
+
+        assert!(bare.is_bare());
+        let git = GitIntegration::new(bare_dir.path()).unwrap();
+
+        let diff = git.diff_between(Some(&root.to_string()), &next.to_string()).unwrap();
+        assert_eq!(diff.files.len(), 1);
+        assert_eq!(diff.files[0].path, "lib/user.ex");
+        assert_eq!(diff.files[0].added_lines.len(), 1);
+        assert_eq!(diff.files[0].added_lines[0].line_number, 2);
+        assert!(diff.files[0].added_lines[0].content.contains("String.to_atom"));
+
+        let initial = git.diff_between(None, &root.to_string()).unwrap();
+        assert_eq!(initial.files[0].added_lines.len(), 2);
+    }
 }
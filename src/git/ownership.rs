@@ -0,0 +1,257 @@
+use anyhow::Result;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use super::GitIntegration;
+
+/// Where GitHub, GitLab and Bitbucket look for a CODEOWNERS file, in priority order
+pub const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// One `pattern @owner ...` line of a CODEOWNERS file
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnershipRule {
+    pub pattern: String,
+    pub owners: Vec<String>,
+}
+
+/// Parsed CODEOWNERS file. As on GitHub, the last matching rule wins.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodeOwners {
+    pub rules: Vec<OwnershipRule>,
+}
+
+impl CodeOwners {
+    /// Load the first CODEOWNERS file found under `root`, if any
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        for location in CODEOWNERS_LOCATIONS {
+            let path = root.join(location);
+            if path.is_file() {
+                return Ok(Some(Self::parse(&std::fs::read_to_string(path)?)));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?.to_string();
+                let owners: Vec<String> = parts.map(String::from).collect();
+                Some(OwnershipRule { pattern, owners })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Owners of `path` (relative to the repository root); empty when unowned
+    pub fn owners_for(&self, path: &str) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| pattern_matches(&rule.pattern, path))
+            .map_or(&[], |rule| &rule.owners[..])
+    }
+}
+
+/// gitignore-style matching used by CODEOWNERS: patterns containing a slash are anchored to
+/// the root, others match at any depth, and a pattern matching a directory owns everything
+/// below it.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let anchored = pattern.trim_end_matches('/').contains('/');
+    let mut glob = pattern.trim_start_matches('/').to_string();
+    if glob.ends_with('/') {
+        glob.push_str("**");
+    }
+    if !anchored {
+        glob = format!("**/{glob}");
+    }
+
+    let Ok(glob) = glob::Pattern::new(&glob) else {
+        return false;
+    };
+    let options = glob::MatchOptions { require_literal_separator: true, ..Default::default() };
+
+    // Match the file itself or any directory containing it
+    let mut candidate = path;
+    loop {
+        if glob.matches_with(candidate, options) {
+            return true;
+        }
+        match candidate.rfind('/') {
+            Some(index) => candidate = &candidate[..index],
+            None => return false,
+        }
+    }
+}
+
+/// Someone who recently committed to a set of files
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorActivity {
+    pub name: String,
+    pub email: String,
+    pub commits: usize,
+    /// Which of the requested files they touched
+    pub files: BTreeSet<String>,
+    /// Unix timestamp of their most recent commit to those files
+    pub last_commit: i64,
+}
+
+impl GitIntegration {
+    /// Root of the working tree; `None` for bare repositories
+    pub fn workdir(&self) -> Option<&Path> {
+        self.repo.workdir()
+    }
+
+    /// `user.email` from git config, used to keep people from being suggested for their own work
+    pub fn user_email(&self) -> Option<String> {
+        self.repo.config().ok()?.get_string("user.email").ok()
+    }
+
+    /// Authors of the last `max_commits` non-merge commits on HEAD that touched any of
+    /// `paths`, most active first.
+    pub fn recent_authors(
+        &self,
+        paths: &[String],
+        max_commits: usize,
+    ) -> Result<Vec<AuthorActivity>> {
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        let mut by_email: HashMap<String, AuthorActivity> = HashMap::new();
+        for oid in revwalk.take(max_commits) {
+            let commit = self.repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+
+            let mut options = git2::DiffOptions::new();
+            options.disable_pathspec_match(true);
+            for path in paths {
+                options.pathspec(path);
+            }
+            let parent_tree = commit.parent(0).ok().map(|parent| parent.tree()).transpose()?;
+            let diff = self.repo.diff_tree_to_tree(
+                parent_tree.as_ref(),
+                Some(&commit.tree()?),
+                Some(&mut options),
+            )?;
+
+            let touched: Vec<String> = diff
+                .deltas()
+                .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+                .map(|path| path.to_string_lossy().to_string())
+                .collect();
+            if touched.is_empty() {
+                continue;
+            }
+
+            let author = commit.author();
+            let email = author.email().unwrap_or_default().to_lowercase();
+            let activity = by_email.entry(email.clone()).or_insert_with(|| AuthorActivity {
+                name: author.name().unwrap_or(&email).to_string(),
+                email,
+                commits: 0,
+                files: BTreeSet::new(),
+                last_commit: 0,
+            });
+            activity.commits += 1;
+            activity.files.extend(touched);
+            activity.last_commit = activity.last_commit.max(commit.time().seconds());
+        }
+
+        let mut authors: Vec<AuthorActivity> = by_email.into_values().collect();
+        authors.sort_by(|a, b| {
+            b.files
+                .len()
+                .cmp(&a.files.len())
+                .then(b.commits.cmp(&a.commits))
+                .then(b.last_commit.cmp(&a.last_commit))
+        });
+        Ok(authors)
+    }
+}
+
+#[cfg(test)]
+mod ownership_tests {
+    use super::*;
+    use git2::Repository;
+
+    #[test]
+    fn test_codeowners_last_match_wins() {
+        let owners = CodeOwners::parse(
+            r#"
+# Default owners
+*           @org/everyone
+*.rs        @rustaceans   # inline comment
+/docs/      @docs-team
+lib/auth/   @security alice@example.com
+"#,
+        );
+
+        assert_eq!(owners.rules.len(), 4);
+        assert_eq!(owners.owners_for("README.md"), ["@org/everyone"]);
+        assert_eq!(owners.owners_for("src/deep/main.rs"), ["@rustaceans"]);
+        assert_eq!(owners.owners_for("docs/guide/intro.md"), ["@docs-team"]);
+        assert_eq!(owners.owners_for("lib/auth/token.ex"), ["@security", "alice@example.com"]);
+        // Anchored patterns do not match deeper copies of the directory
+        assert_eq!(owners.owners_for("vendor/docs/readme.md"), ["@org/everyone"]);
+    }
+
+    #[test]
+    fn test_unanchored_directory_matches_any_depth() {
+        let owners = CodeOwners::parse("migrations @dba\n");
+        assert_eq!(owners.owners_for("apps/api/migrations/001.sql"), ["@dba"]);
+        assert!(owners.owners_for("apps/api/models.py").is_empty());
+    }
+
+    fn commit_as(repo: &Repository, author: &str, path: &str, content: &str) {
+        let workdir = repo.workdir().unwrap();
+        std::fs::create_dir_all(workdir.join(path).parent().unwrap()).unwrap();
+        std::fs::write(workdir.join(path), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature =
+            git2::Signature::now(author, &format!("{}@example.com", author.to_lowercase()))
+                .unwrap();
+        let parents: Vec<git2::Commit> =
+            repo.head().ok().and_then(|h| h.peel_to_commit().ok()).into_iter().collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, "change", &tree, &parent_refs).unwrap();
+    }
+
+    #[test]
+    fn test_recent_authors_ranks_by_files_touched() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        commit_as(&repo, "Alice", "lib/user.ex", "v1\n");
+        commit_as(&repo, "Alice", "lib/auth.ex", "v1\n");
+        commit_as(&repo, "Bob", "lib/user.ex", "v2\n");
+        commit_as(&repo, "Bob", "lib/user.ex", "v3\n");
+        commit_as(&repo, "Carol", "README.md", "hello\n");
+
+        let git = GitIntegration::new(temp_dir.path()).unwrap();
+        let paths = vec!["lib/user.ex".to_string(), "lib/auth.ex".to_string()];
+        let authors = git.recent_authors(&paths, 100).unwrap();
+
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[0].email, "alice@example.com");
+        assert_eq!(authors[0].files.len(), 2);
+        assert_eq!(authors[1].name, "Bob");
+        assert_eq!(authors[1].commits, 2);
+
+        // The history window limits how far back we look
+        let recent = git.recent_authors(&paths, 3).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].name, "Bob");
+    }
+}
@@ -0,0 +1,208 @@
+use std::path::Path;
+
+use super::{DiffScope, GitIntegration};
+
+/// Why a diff scope cannot be reviewed, with the message to show instead of git's stderr
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotReady {
+    NotARepository,
+    /// HEAD is unborn, so a scope relative to it has nothing to diff against
+    NoCommits,
+    MissingReference {
+        reference: String,
+        /// What to run to get it, e.g. "run `git fetch origin`"
+        hint: String,
+    },
+    /// The scope resolves, but has no changes to review
+    NothingToDiff(DiffScope),
+}
+
+impl std::fmt::Display for NotReady {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotReady::NotARepository => write!(
+                f,
+                "Not inside a git repository; run patingin from your project checkout, or \
+                 review files directly with --all or --file"
+            ),
+            NotReady::NoCommits => write!(
+                f,
+                "This repository has no commits yet, so there is no HEAD to diff against; \
+                 review staged changes with --staged, or make a first commit"
+            ),
+            NotReady::MissingReference { reference, hint } => {
+                write!(f, "{reference} not found; {hint}")
+            }
+            NotReady::NothingToDiff(scope) => match scope {
+                DiffScope::Staged => write!(
+                    f,
+                    "No staged changes to review; stage them with `git add`, or review \
+                     unstaged changes with --uncommitted"
+                ),
+                DiffScope::Unstaged => write!(
+                    f,
+                    "No unstaged changes to review; staged changes are reviewed with --staged"
+                ),
+                DiffScope::SinceCommit(reference) => {
+                    write!(f, "No changes since {reference} to review")
+                }
+                DiffScope::Between(from, to) => {
+                    write!(f, "No changes between {from} and {to} to review")
+                }
+                DiffScope::All => write!(f, "No tracked files to review"),
+            },
+        }
+    }
+}
+
+impl std::error::Error for NotReady {}
+
+/// Check that `scope` can be reviewed in the git repository containing `dir`, without
+/// running a diff. Hook scripts can call this to explain a skipped review.
+#[allow(dead_code)] // Used in tests and by library users
+pub fn check_scope(dir: &Path, scope: &DiffScope) -> Result<(), NotReady> {
+    GitIntegration::new(dir).map_err(|_| NotReady::NotARepository)?.check_scope(scope)
+}
+
+impl GitIntegration {
+    /// Check, in order, that HEAD exists when the scope is relative to it, that every
+    /// reference the scope names resolves, and that there is anything to diff. Emptiness is
+    /// only reported when it can be determined, e.g. not for working tree scopes in a bare
+    /// repository.
+    pub fn check_scope(&self, scope: &DiffScope) -> Result<(), NotReady> {
+        let references: Vec<&str> = match scope {
+            DiffScope::SinceCommit(reference) => vec![reference],
+            DiffScope::Between(from, to) => vec![from, to],
+            DiffScope::Staged | DiffScope::Unstaged | DiffScope::All => Vec::new(),
+        };
+
+        let unborn = self.repo.head().is_err();
+        if unborn && references.iter().any(|reference| reference.starts_with("HEAD")) {
+            return Err(NotReady::NoCommits);
+        }
+        for reference in &references {
+            if !self.resolves(reference) {
+                return Err(NotReady::MissingReference {
+                    reference: reference.to_string(),
+                    hint: self.fetch_hint(reference),
+                });
+            }
+        }
+
+        if self.changed_paths(scope) == Some(0) {
+            return Err(NotReady::NothingToDiff(scope.clone()));
+        }
+        Ok(())
+    }
+
+    /// How to get a reference that does not resolve: fetch its remote when it names one
+    pub(super) fn fetch_hint(&self, reference: &str) -> String {
+        let remotes = self.repo.remotes().ok();
+        let remote = remotes.iter().flat_map(|remotes| remotes.iter().flatten()).find(|remote| {
+            reference.strip_prefix(remote).is_some_and(|rest| rest.starts_with('/'))
+        });
+
+        match remote {
+            Some(remote) => format!("run `git fetch {remote}`"),
+            None if reference.len() >= 7 && reference.chars().all(|c| c.is_ascii_hexdigit()) => {
+                "the commit may not have been fetched; run `git fetch`".to_string()
+            }
+            None => "check the spelling, or run `git fetch` if it only exists upstream".to_string(),
+        }
+    }
+
+    /// Number of files `scope` changes, or `None` when git cannot tell without the diff
+    fn changed_paths(&self, scope: &DiffScope) -> Option<usize> {
+        let tree = |reference: &str| self.repo.revparse_single(reference).ok()?.peel_to_tree().ok();
+        let diff = match scope {
+            DiffScope::Staged => {
+                let head = self.repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+                self.repo.diff_tree_to_index(head.as_ref(), None, None)
+            }
+            DiffScope::Unstaged => self.repo.diff_index_to_workdir(None, None),
+            DiffScope::SinceCommit(reference) => {
+                self.repo.diff_tree_to_workdir_with_index(Some(&tree(reference)?), None)
+            }
+            DiffScope::Between(from, to) => {
+                self.repo.diff_tree_to_tree(Some(&tree(from)?), Some(&tree(to)?), None)
+            }
+            DiffScope::All => return self.repo.index().ok().map(|index| index.len()),
+        };
+        diff.ok().map(|diff| diff.deltas().len())
+    }
+}
+
+#[cfg(test)]
+mod readiness_tests {
+    use super::*;
+    use git2::{Repository, Signature};
+
+    fn commit_all(repo: &Repository) {
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"], git2::IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, "change", &tree, &parents).unwrap();
+    }
+
+    #[test]
+    fn test_scopes_relative_to_head_need_a_commit() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join("a.ex"), "a\n").unwrap();
+
+        let since_head = DiffScope::SinceCommit("HEAD".to_string());
+        assert_eq!(check_scope(temp_dir.path(), &since_head), Err(NotReady::NoCommits));
+        assert_eq!(
+            check_scope(temp_dir.path(), &DiffScope::Staged),
+            Err(NotReady::NothingToDiff(DiffScope::Staged))
+        );
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.ex")).unwrap();
+        index.write().unwrap();
+        assert_eq!(check_scope(temp_dir.path(), &DiffScope::Staged), Ok(()));
+    }
+
+    #[test]
+    fn test_missing_references_name_the_remote_to_fetch() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join("a.ex"), "a\n").unwrap();
+        commit_all(&repo);
+        repo.remote("origin", "https://example.com/acme/app.git").unwrap();
+
+        let error = check_scope(temp_dir.path(), &DiffScope::SinceCommit("origin/main".into()))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "origin/main not found; run `git fetch origin`");
+
+        let error =
+            check_scope(temp_dir.path(), &DiffScope::SinceCommit("0123abcd".into())).unwrap_err();
+        assert!(error.to_string().contains("may not have been fetched"));
+    }
+
+    #[test]
+    fn test_empty_scopes_are_reported() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join("a.ex"), "a\n").unwrap();
+        commit_all(&repo);
+
+        let since_head = DiffScope::SinceCommit("HEAD".to_string());
+        assert_eq!(
+            check_scope(temp_dir.path(), &since_head),
+            Err(NotReady::NothingToDiff(since_head.clone()))
+        );
+        assert_eq!(check_scope(temp_dir.path(), &DiffScope::All), Ok(()));
+
+        std::fs::write(temp_dir.path().join("a.ex"), "b\n").unwrap();
+        assert_eq!(check_scope(temp_dir.path(), &since_head), Ok(()));
+        assert_eq!(check_scope(temp_dir.path(), &DiffScope::Unstaged), Ok(()));
+
+        let outside = tempfile::TempDir::new().unwrap();
+        assert_eq!(check_scope(outside.path(), &DiffScope::Staged), Err(NotReady::NotARepository));
+    }
+}
@@ -0,0 +1,95 @@
+use anyhow::Result;
+use std::path::Path;
+
+use super::{FileDiff, GitDiff, ParseWarning};
+
+/// Version control directories, never part of the project's own files
+const VCS_DIRS: &[&str] = &[".git", ".jj", ".hg"];
+
+/// Every file under `root` not excluded by `.gitignore`, `.ignore` or git's exclude files,
+/// as a diff adding all of its lines, so existing debt can be measured instead of only
+/// changes. Only files `include` accepts (by `/`-separated path relative to `root`) are
+/// read. Works outside version control too; ignore files are honoured either way.
+pub fn scan_tree(root: &Path, include: impl Fn(&str) -> bool) -> Result<GitDiff> {
+    let mut files = Vec::new();
+    let mut parse_warnings = Vec::new();
+
+    let walker = ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            !entry.file_name().to_str().is_some_and(|name| VCS_DIRS.contains(&name))
+        })
+        .build();
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                parse_warnings.push(ParseWarning { path: None, message: e.to_string() });
+                continue;
+            }
+        };
+        // Symlinks are not followed, so a linked file is only reviewed at its real path
+        if !entry.file_type().is_some_and(|file_type| file_type.is_file()) {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        let path: Vec<String> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let path = path.join("/");
+        if !include(&path) {
+            continue;
+        }
+
+        let warning = |message: String| ParseWarning { path: Some(path.clone()), message };
+        match std::fs::read(entry.path()).map(String::from_utf8) {
+            Ok(Ok(content)) => files.push(FileDiff::from_region(&path, &content, 1..=usize::MAX)),
+            Ok(Err(_)) => parse_warnings.push(warning("not valid UTF-8; not reviewed".into())),
+            Err(e) => parse_warnings.push(warning(format!("could not be read: {e}"))),
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(GitDiff { files, parse_warnings })
+}
+
+#[cfg(test)]
+mod tree_tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_tree_respects_ignore_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let write = |path: &str, content: &[u8]| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(".gitignore", b"deps/\n*.log\n");
+        write("lib/user.ex", b"defmodule User do\n  String.to_atom(x)\nend\n");
+        write("lib/.formatter.exs", b"[]\n");
+        write("deps/jason/lib/jason.ex", b"String.to_atom(x)\n");
+        write("debug.log", b"String.to_atom(x)\n");
+        write("lib/notes.txt", b"not code\n");
+        write("lib/latin1.ex", b"caf\xe9\n");
+
+        let diff = scan_tree(root, |path| path.ends_with(".ex") || path.ends_with(".exs")).unwrap();
+        let paths: Vec<&str> = diff.files.iter().map(|file| file.path.as_str()).collect();
+
+        assert_eq!(paths, ["lib/.formatter.exs", "lib/user.ex"]);
+        assert_eq!(diff.files[1].added_lines.len(), 3);
+        assert_eq!(diff.files[1].added_lines[1].line_number, 2);
+        assert_eq!(
+            diff.parse_warnings,
+            [ParseWarning {
+                path: Some("lib/latin1.ex".to_string()),
+                message: "not valid UTF-8; not reviewed".to_string(),
+            }]
+        );
+    }
+}
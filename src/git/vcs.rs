@@ -0,0 +1,141 @@
+use anyhow::Result;
+use std::path::Path;
+
+use super::{DiffScope, EMPTY_TREE_SHA};
+
+/// Version control backend used to produce unified diffs.
+///
+/// Every backend is asked for git-style output (`--git`), so the resulting text can be fed
+/// straight into `GitDiffParser::parse`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vcs {
+    Git,
+    Jujutsu,
+    Mercurial,
+}
+
+impl Vcs {
+    /// Detect the backend by walking up from `dir` until a repository marker is found.
+    ///
+    /// A `.jj` directory wins over `.git` in the same directory so colocated jj repositories
+    /// use jj's working-copy model. Falls back to git when nothing is found.
+    pub fn detect(dir: &Path) -> Vcs {
+        for ancestor in dir.ancestors() {
+            if ancestor.join(".jj").is_dir() {
+                return Vcs::Jujutsu;
+            }
+            if ancestor.join(".hg").is_dir() {
+                return Vcs::Mercurial;
+            }
+            if ancestor.join(".git").exists() {
+                return Vcs::Git;
+            }
+        }
+        Vcs::Git
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Vcs::Git => "git",
+            Vcs::Jujutsu => "jj",
+            Vcs::Mercurial => "hg",
+        }
+    }
+
+    /// Build the diff command (program followed by arguments) for a scope.
+    pub fn diff_command(&self, scope: &DiffScope) -> Result<Vec<String>> {
+        let parts: Vec<&str> = match (self, scope) {
+            (Vcs::Git, DiffScope::Unstaged) => vec!["git", "diff"],
+            (Vcs::Git, DiffScope::Staged) => vec!["git", "diff", "--cached"],
+            (Vcs::Git, DiffScope::SinceCommit(reference)) => vec!["git", "diff", reference],
+            (Vcs::Git, DiffScope::All) => vec!["git", "diff", EMPTY_TREE_SHA],
+            (Vcs::Git, DiffScope::Between(from, to)) => vec!["git", "diff", from, to],
+
+            // jj snapshots the working copy into `@`, so uncommitted work is `@` vs its parent
+            (Vcs::Jujutsu, DiffScope::Unstaged) => vec!["jj", "diff", "--git"],
+            (Vcs::Jujutsu, DiffScope::SinceCommit(reference)) if reference == "HEAD" => {
+                vec!["jj", "diff", "--git"]
+            }
+            (Vcs::Jujutsu, DiffScope::SinceCommit(reference)) => {
+                vec!["jj", "diff", "--git", "--from", reference]
+            }
+            (Vcs::Jujutsu, DiffScope::All) => vec!["jj", "diff", "--git", "--from", "root()"],
+            (Vcs::Jujutsu, DiffScope::Between(from, to)) => {
+                vec!["jj", "diff", "--git", "--from", from, "--to", to]
+            }
+
+            (Vcs::Mercurial, DiffScope::Unstaged) => vec!["hg", "diff", "--git"],
+            (Vcs::Mercurial, DiffScope::SinceCommit(reference)) if reference == "HEAD" => {
+                vec!["hg", "diff", "--git"]
+            }
+            (Vcs::Mercurial, DiffScope::SinceCommit(reference)) => {
+                vec!["hg", "diff", "--git", "-r", reference]
+            }
+            (Vcs::Mercurial, DiffScope::All) => vec!["hg", "diff", "--git", "-r", "null"],
+            (Vcs::Mercurial, DiffScope::Between(from, to)) => {
+                vec!["hg", "diff", "--git", "-r", from, "-r", to]
+            }
+
+            (Vcs::Jujutsu | Vcs::Mercurial, DiffScope::Staged) => {
+                return Err(anyhow::anyhow!(
+                    "{} has no staging area; use --uncommitted or --since instead",
+                    self.name()
+                ));
+            }
+        };
+
+        Ok(parts.into_iter().map(String::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod vcs_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_prefers_jj_in_colocated_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        std::fs::create_dir(temp_dir.path().join(".jj")).unwrap();
+        let nested = temp_dir.path().join("lib/deep");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(Vcs::detect(&nested), Vcs::Jujutsu);
+    }
+
+    #[test]
+    fn test_detect_mercurial_and_git() {
+        let hg_dir = TempDir::new().unwrap();
+        std::fs::create_dir(hg_dir.path().join(".hg")).unwrap();
+        assert_eq!(Vcs::detect(hg_dir.path()), Vcs::Mercurial);
+
+        let git_dir = TempDir::new().unwrap();
+        std::fs::create_dir(git_dir.path().join(".git")).unwrap();
+        assert_eq!(Vcs::detect(git_dir.path()), Vcs::Git);
+    }
+
+    #[test]
+    fn test_diff_commands_per_backend() {
+        let head = DiffScope::SinceCommit("HEAD".to_string());
+        let main = DiffScope::SinceCommit("main".to_string());
+
+        assert_eq!(Vcs::Jujutsu.diff_command(&head).unwrap(), ["jj", "diff", "--git"]);
+        assert_eq!(
+            Vcs::Jujutsu.diff_command(&main).unwrap(),
+            ["jj", "diff", "--git", "--from", "main"]
+        );
+        assert_eq!(
+            Vcs::Mercurial.diff_command(&main).unwrap(),
+            ["hg", "diff", "--git", "-r", "main"]
+        );
+        assert_eq!(
+            Vcs::Mercurial.diff_command(&DiffScope::All).unwrap(),
+            ["hg", "diff", "--git", "-r", "null"]
+        );
+        assert_eq!(Vcs::Git.diff_command(&DiffScope::Staged).unwrap(), ["git", "diff", "--cached"]);
+
+        assert!(Vcs::Jujutsu.diff_command(&DiffScope::Staged).is_err());
+        assert!(Vcs::Mercurial.diff_command(&DiffScope::Staged).is_err());
+    }
+}
@@ -1,7 +1,9 @@
 pub mod cli;
+pub mod config;
 pub mod core;
 pub mod external;
 pub mod git;
+pub mod report;
 
 pub use core::*;
 pub use external::*;
@@ -3,6 +3,7 @@ mod config;
 mod core;
 mod external;
 mod git;
+mod report;
 
 use anyhow::Result;
 use clap::Parser;
@@ -20,6 +21,20 @@ async fn main() -> Result<()> {
     // Parse CLI arguments
     let cli = Cli::parse();
 
+    config::override_config_paths(config::ConfigOverrides {
+        project: cli.config.clone(),
+        user: cli.user_config.clone(),
+    })?;
+
+    // Languages declared in config must be known before files are matched to rules
+    if let Err(e) = config::declare_configured_languages(std::path::Path::new(".")) {
+        eprintln!("⚠️  Ignoring declared languages: {e:#}");
+    }
+
+    if !matches!(cli.command, Commands::SelfUpdate(_)) {
+        cli::commands::self_update::notify_if_outdated().await;
+    }
+
     // Execute command
     match cli.command {
         Commands::Rules(args) => {
@@ -28,7 +43,71 @@ async fn main() -> Result<()> {
         }
         Commands::Review(args) => {
             info!("Running review command");
-            cli::commands::review::run(args).await?
+            cli::commands::review::run(*args).await?
+        }
+        Commands::Hook(args) => {
+            info!("Running hook command");
+            cli::commands::hook::run(args).await?
+        }
+        Commands::PreReceive(args) => {
+            info!("Running pre-receive command");
+            cli::commands::pre_receive::run(args).await?
+        }
+        Commands::Ci(args) => {
+            info!("Running ci command");
+            cli::commands::ci::run(args).await?
+        }
+        Commands::Fix(args) => {
+            info!("Running fix command");
+            cli::commands::fix::run(args).await?
+        }
+        Commands::Report(args) => {
+            info!("Running report command");
+            cli::commands::report::run(args).await?
+        }
+        Commands::Hotspots(args) => {
+            info!("Running hotspots command");
+            cli::commands::hotspots::run(args).await?
+        }
+        Commands::History(args) => {
+            info!("Running history command");
+            cli::commands::history::run(args).await?
+        }
+        Commands::Stats(args) => {
+            info!("Running stats command");
+            cli::commands::stats::run(args).await?
+        }
+        Commands::Learn(args) => {
+            info!("Running learn command");
+            cli::commands::learn::run(args).await?
+        }
+        Commands::SuggestReviewers(args) => {
+            info!("Running suggest-reviewers command");
+            cli::commands::suggest_reviewers::run(args).await?
+        }
+        Commands::Audit(args) => {
+            info!("Running audit command");
+            cli::commands::audit::run(args).await?
+        }
+        Commands::Deprecations(args) => {
+            info!("Running deprecations command");
+            cli::commands::deprecations::run(args).await?
+        }
+        Commands::Config(args) => {
+            info!("Running config command");
+            cli::commands::config::run(args).await?
+        }
+        Commands::Version(args) => {
+            info!("Running version command");
+            cli::commands::version::run(args).await?
+        }
+        Commands::SelfUpdate(args) => {
+            info!("Running self-update command");
+            cli::commands::self_update::run(args).await?
+        }
+        Commands::Baseline => {
+            info!("Running baseline command");
+            cli::commands::baseline::run().await?
         }
         Commands::Setup => {
             info!("Running setup command");
@@ -0,0 +1,178 @@
+use anyhow::{Context, Result};
+use colored::*;
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::config::find_project_config;
+use crate::core::{ProjectDetector, Severity};
+
+use super::positions::ColumnUnit;
+
+/// How human-readable reports convey severity.
+///
+/// The standard style relies on emoji and color. `high-contrast` spells severities out as
+/// ordered `[S1 CRITICAL]` prefixes and keeps only bold text, so nothing depends on telling
+/// hues apart. `screen-reader` drops emoji and styling entirely, since both are read aloud
+/// as noise or not at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Accessibility {
+    #[default]
+    Standard,
+    HighContrast,
+    ScreenReader,
+}
+
+impl Accessibility {
+    /// Position of a severity in reading order, most severe first. Blocker ranks 0 so the
+    /// three original levels keep their numbers.
+    fn rank(severity: Severity) -> usize {
+        match severity {
+            Severity::Blocker => 0,
+            Severity::Critical => 1,
+            Severity::Major => 2,
+            Severity::Warning => 3,
+            Severity::Info => 4,
+        }
+    }
+
+    /// A decorative emoji (with its trailing space), or nothing outside the standard style
+    pub fn icon(self, icon: &'static str) -> &'static str {
+        match self {
+            Accessibility::Standard => icon,
+            _ => "",
+        }
+    }
+
+    /// Label opening each violation, e.g. "🔴 CRITICAL" or "[S1 CRITICAL]"
+    pub fn severity_label(self, severity: Severity) -> ColoredString {
+        let name = severity.to_string().to_uppercase();
+        let rank = Self::rank(severity);
+        match (self, severity) {
+            (Accessibility::Standard, Severity::Blocker) => format!("⛔ {name}").red().bold(),
+            (Accessibility::Standard, Severity::Critical) => format!("🔴 {name}").red(),
+            (Accessibility::Standard, Severity::Major) => format!("🟡 {name}").yellow(),
+            (Accessibility::Standard, Severity::Warning) => format!("🔵 {name}").blue(),
+            (Accessibility::Standard, Severity::Info) => format!("⚪ {name}").white(),
+            (Accessibility::HighContrast, _) => format!("[S{rank} {name}]").bold(),
+            (Accessibility::ScreenReader, _) => format!("Severity {rank} {severity}:").normal(),
+        }
+    }
+
+    /// Severity marker in summaries and sampling notes, e.g. "🔴" or "[S1 CRITICAL]"
+    pub fn severity_marker(self, severity: Severity) -> String {
+        match self {
+            Accessibility::Standard => severity.icon().to_string(),
+            _ => self.severity_label(severity).to_string(),
+        }
+    }
+
+    /// Secondary text; dimming is low contrast, so only the standard style uses it
+    pub fn muted(self, text: &str) -> ColoredString {
+        match self {
+            Accessibility::Standard => text.dimmed(),
+            _ => text.normal(),
+        }
+    }
+
+    /// Text highlighted with `color`, or plain bold when color must not carry meaning
+    pub fn highlight(self, text: &str, color: Color) -> ColoredString {
+        match self {
+            Accessibility::Standard => text.color(color),
+            Accessibility::HighContrast => text.bold(),
+            Accessibility::ScreenReader => text.normal(),
+        }
+    }
+}
+
+/// The `output:` section of the project config.
+///
+/// ```yaml
+/// output:
+///   accessibility: high-contrast   # or screen-reader
+///   max_reported_violations: 500
+///   column_unit: utf-8             # or utf-16 (default), code-points
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OutputConfig {
+    #[serde(default)]
+    pub accessibility: Accessibility,
+    /// Violations listed in detail before the report is cut short; unlimited when unset
+    #[serde(default)]
+    pub max_reported_violations: Option<usize>,
+    /// What the columns of JSON and SARIF output count
+    #[serde(default)]
+    pub column_unit: ColumnUnit,
+}
+
+impl OutputConfig {
+    /// Read the `output:` section of a project config file; absent means the standard style
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct OutputSection {
+            #[serde(default)]
+            output: OutputConfig,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<OutputSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.map(|section| section.output).unwrap_or_default())
+    }
+
+    /// The detected project's settings, or the defaults outside a configured project
+    pub fn load_for_project() -> Result<Self> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(Self::default());
+        };
+        match find_project_config(&project_info.root_path) {
+            Some(config_path) => Self::load(&config_path),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod accessibility_tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_labels_are_ordered_text() {
+        colored::control::set_override(false);
+
+        assert_eq!(
+            Accessibility::HighContrast.severity_label(Severity::Critical).to_string(),
+            "[S1 CRITICAL]"
+        );
+        assert_eq!(
+            Accessibility::ScreenReader.severity_label(Severity::Warning).to_string(),
+            "Severity 3 warning:"
+        );
+        assert_eq!(Accessibility::Standard.severity_marker(Severity::Major), "🟡");
+        assert_eq!(Accessibility::ScreenReader.icon("📁 "), "");
+    }
+
+    #[test]
+    fn test_load_output_section() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+
+        std::fs::write(&config_path, "output:\n  accessibility: screen-reader\n").unwrap();
+        assert_eq!(
+            OutputConfig::load(&config_path).unwrap().accessibility,
+            Accessibility::ScreenReader
+        );
+
+        std::fs::write(&config_path, "gates:\n  style: off\n").unwrap();
+        let defaults = OutputConfig::load(&config_path).unwrap();
+        assert_eq!(defaults.accessibility, Accessibility::Standard);
+        assert_eq!(defaults.max_reported_violations, None);
+
+        std::fs::write(&config_path, "output:\n  max_reported_violations: 50\n").unwrap();
+        assert_eq!(OutputConfig::load(&config_path).unwrap().max_reported_violations, Some(50));
+
+        std::fs::write(&config_path, "output:\n  accessibility: loud\n").unwrap();
+        assert!(OutputConfig::load(&config_path).is_err());
+    }
+}
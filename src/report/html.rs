@@ -0,0 +1,631 @@
+use anyhow::Result;
+use minijinja::{context, Environment, UndefinedBehavior};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::ops::Range;
+
+use super::{summary_severities, ReportContext, Reporter};
+use crate::core::review_engine::ReviewSummary;
+use crate::core::{Language, ReviewViolation, Severity};
+
+/// Bars shown in the rule and file charts
+const CHART_BARS: usize = 10;
+
+/// The page: values are HTML-escaped as they are written, except highlighted code
+const TEMPLATE: &str = include_str!("templates/report.html");
+
+/// A standalone HTML page with summary charts, severity filters and highlighted snippets,
+/// e.g. to attach to a CI run as an artifact
+pub struct HtmlReporter {
+    out: Box<dyn Write>,
+    context: ReportContext,
+    violations: Vec<ReviewViolation>,
+}
+
+impl HtmlReporter {
+    pub fn new(out: Box<dyn Write>) -> Self {
+        Self { out, context: ReportContext::default(), violations: Vec::new() }
+    }
+}
+
+impl Reporter for HtmlReporter {
+    fn on_start(&mut self, context: &ReportContext) -> Result<()> {
+        self.context = context.clone();
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &ReviewViolation) -> Result<()> {
+        self.violations.push(violation.clone());
+        Ok(())
+    }
+
+    fn on_summary(&mut self, _summary: &ReviewSummary) -> Result<()> {
+        write!(self.out, "{}", render_html(&self.violations, &self.context)?)?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[derive(Serialize)]
+struct Chart {
+    caption: &'static str,
+    bars: Vec<Bar>,
+}
+
+/// A horizontal bar, `width` percent wide
+#[derive(Serialize)]
+struct Bar {
+    label: String,
+    count: usize,
+    class: &'static str,
+    width: String,
+}
+
+#[derive(Serialize)]
+struct Filter {
+    severity: Severity,
+    class: &'static str,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct FileSection {
+    path: String,
+    violations: Vec<ViolationCard>,
+}
+
+#[derive(Serialize)]
+struct ViolationCard {
+    severity: Severity,
+    class: &'static str,
+    line: usize,
+    rule_name: String,
+    rule_id: String,
+    fix_suggestion: String,
+    source_url: Option<String>,
+    lines: Vec<SnippetLine>,
+}
+
+/// A line of a snippet, already highlighted as HTML
+#[derive(Serialize)]
+struct SnippetLine {
+    number: usize,
+    html: String,
+    hit: bool,
+}
+
+/// Render violations as an HTML page headed by the reviewed scope
+pub fn render_html(violations: &[ReviewViolation], context: &ReportContext) -> Result<String> {
+    let mut violations_by_file: BTreeMap<&str, Vec<&ReviewViolation>> = BTreeMap::new();
+    for violation in violations {
+        violations_by_file.entry(&violation.file_path).or_default().push(violation);
+    }
+    let count = |severity: Severity| violations.iter().filter(|v| v.severity == severity).count();
+    let severities = summary_severities(count);
+
+    let by_severity: Vec<(String, usize, &str)> = severities
+        .iter()
+        .map(|severity| (severity.to_string(), count(*severity), severity_class(*severity)))
+        .collect();
+    let mut by_rule: BTreeMap<&str, (usize, Severity)> = BTreeMap::new();
+    for violation in violations {
+        let entry = by_rule.entry(&violation.rule.id).or_insert((0, violation.severity));
+        entry.0 += 1;
+        // Severities order most severe first
+        entry.1 = entry.1.min(violation.severity);
+    }
+    let mut by_rule: Vec<(String, usize, &str)> = by_rule
+        .into_iter()
+        .map(|(rule, (count, severity))| (rule.to_string(), count, severity_class(severity)))
+        .collect();
+    by_rule.sort_by_key(|(_, count, _)| std::cmp::Reverse(*count));
+    let mut by_file: Vec<(String, usize, &str)> = violations_by_file
+        .iter()
+        .map(|(file, file_violations)| {
+            let worst = file_violations.iter().map(|v| v.severity).min();
+            (file.to_string(), file_violations.len(), worst.map_or("", severity_class))
+        })
+        .collect();
+    by_file.sort_by_key(|(_, count, _)| std::cmp::Reverse(*count));
+
+    let charts = [
+        chart("By severity", &by_severity),
+        chart("Top rules", &by_rule[..by_rule.len().min(CHART_BARS)]),
+        chart("Top files", &by_file[..by_file.len().min(CHART_BARS)]),
+    ];
+    let filters: Vec<Filter> = severities
+        .into_iter()
+        .map(|severity| Filter {
+            severity,
+            class: severity_class(severity),
+            count: count(severity),
+        })
+        .collect();
+    let files: Vec<FileSection> = violations_by_file
+        .into_iter()
+        .map(|(path, file_violations)| FileSection {
+            path: path.to_string(),
+            violations: file_violations.into_iter().map(violation_card).collect(),
+        })
+        .collect();
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    // Every value goes through `escape_html` unless the template marks it safe
+    env.set_formatter(|out, _state, value| {
+        if value.is_safe() {
+            write!(out, "{value}")?;
+        } else {
+            write!(out, "{}", escape_html(&value.to_string()))?;
+        }
+        Ok(())
+    });
+    env.add_template("report.html", TEMPLATE)?;
+    let html = env.get_template("report.html")?.render(context! {
+        title => format!("patingin review: {}", context.scope),
+        total => violations.len(),
+        truncation => context.truncation.map(|t| context! { limit => t.limit, total => t.total }),
+        sampling => context.sampling.as_ref().map(|sample| context! {
+            per_rule => sample.per_rule,
+            shown => sample.shown,
+            total => sample.total,
+        }),
+        charts,
+        filters,
+        files,
+    })?;
+    Ok(html)
+}
+
+fn severity_class(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Blocker => "blocker",
+        Severity::Critical => "critical",
+        Severity::Major => "major",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+/// Horizontal bars sized against the largest count
+fn chart(caption: &'static str, bars: &[(String, usize, &'static str)]) -> Chart {
+    let max = bars.iter().map(|(_, count, _)| *count).max().unwrap_or(0).max(1);
+    let bars = bars
+        .iter()
+        .map(|(label, count, class)| Bar {
+            label: label.clone(),
+            count: *count,
+            class,
+            width: format!("{:.1}", *count as f64 * 60.0 / max as f64),
+        })
+        .collect();
+    Chart { caption, bars }
+}
+
+fn violation_card(violation: &ReviewViolation) -> ViolationCard {
+    // Context lines are the neighbours of the changed line in the new file
+    let first_line = violation.line_number.saturating_sub(violation.context_before.len());
+    let context_line = |number: usize, line: &String| SnippetLine {
+        number,
+        html: highlight(line, &violation.language, None),
+        hit: false,
+    };
+    let mut lines: Vec<SnippetLine> = violation
+        .context_before
+        .iter()
+        .enumerate()
+        .map(|(offset, line)| context_line(first_line + offset, line))
+        .collect();
+    lines.push(SnippetLine {
+        number: violation.line_number,
+        html: highlight(&violation.content, &violation.language, violation.span.clone()),
+        hit: true,
+    });
+    lines.extend(
+        violation
+            .context_after
+            .iter()
+            .enumerate()
+            .map(|(offset, line)| context_line(violation.line_number + 1 + offset, line)),
+    );
+
+    ViolationCard {
+        severity: violation.severity,
+        class: severity_class(violation.severity),
+        line: violation.line_number,
+        rule_name: violation.rule.name.clone(),
+        rule_id: violation.rule.id.clone(),
+        fix_suggestion: violation.fix_suggestion.clone(),
+        source_url: violation.rule.source_url.clone().filter(|url| url.starts_with("http")),
+        lines,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Plain,
+    Keyword,
+    Str,
+    Number,
+    Comment,
+}
+
+impl Token {
+    fn class(self) -> Option<&'static str> {
+        match self {
+            Token::Plain => None,
+            Token::Keyword => Some("k"),
+            Token::Str => Some("s"),
+            Token::Number => Some("n"),
+            Token::Comment => Some("c"),
+        }
+    }
+}
+
+fn keywords(language: &Language) -> &'static [&'static str] {
+    match language {
+        Language::Elixir => &[
+            "def",
+            "defp",
+            "defmodule",
+            "defmacro",
+            "defstruct",
+            "do",
+            "end",
+            "fn",
+            "case",
+            "cond",
+            "with",
+            "if",
+            "else",
+            "unless",
+            "when",
+            "import",
+            "alias",
+            "require",
+            "use",
+            "true",
+            "false",
+            "nil",
+            "raise",
+        ],
+        Language::JavaScript | Language::TypeScript => &[
+            "function",
+            "const",
+            "let",
+            "var",
+            "return",
+            "if",
+            "else",
+            "for",
+            "while",
+            "class",
+            "new",
+            "import",
+            "export",
+            "from",
+            "async",
+            "await",
+            "try",
+            "catch",
+            "throw",
+            "true",
+            "false",
+            "null",
+            "undefined",
+            "interface",
+            "type",
+        ],
+        Language::Python => &[
+            "def", "class", "return", "if", "elif", "else", "for", "while", "import", "from", "as",
+            "with", "try", "except", "raise", "lambda", "True", "False", "None", "async", "await",
+            "pass",
+        ],
+        Language::Rust => &[
+            "fn", "let", "mut", "pub", "struct", "enum", "impl", "trait", "use", "mod", "match",
+            "if", "else", "for", "while", "loop", "return", "unsafe", "async", "await", "true",
+            "false", "Self", "self",
+        ],
+        Language::Zig => &[
+            "fn",
+            "const",
+            "var",
+            "pub",
+            "return",
+            "if",
+            "else",
+            "for",
+            "while",
+            "try",
+            "catch",
+            "defer",
+            "errdefer",
+            "struct",
+            "enum",
+            "union",
+            "comptime",
+            "true",
+            "false",
+            "null",
+            "undefined",
+        ],
+        Language::Sql => &[
+            "SELECT", "FROM", "WHERE", "INSERT", "INTO", "UPDATE", "DELETE", "SET", "VALUES",
+            "JOIN", "LEFT", "INNER", "ON", "AND", "OR", "NOT", "NULL", "ORDER", "GROUP", "BY",
+            "CREATE", "TABLE", "DROP", "ALTER", "select", "from", "where", "insert", "into",
+            "update", "delete", "set", "values", "join", "on", "and", "or", "not", "null",
+        ],
+        Language::Ruby => &[
+            "def", "class", "module", "do", "end", "if", "elsif", "else", "unless", "case", "when",
+            "while", "until", "begin", "rescue", "ensure", "raise", "return", "yield", "self",
+            "nil", "true", "false", "and", "or", "not",
+        ],
+        Language::Php => &[
+            "function",
+            "class",
+            "interface",
+            "trait",
+            "namespace",
+            "use",
+            "public",
+            "protected",
+            "private",
+            "static",
+            "return",
+            "if",
+            "else",
+            "elseif",
+            "foreach",
+            "for",
+            "while",
+            "try",
+            "catch",
+            "throw",
+            "new",
+            "echo",
+            "array",
+            "null",
+            "true",
+            "false",
+        ],
+        Language::Java => &[
+            "class",
+            "interface",
+            "enum",
+            "record",
+            "extends",
+            "implements",
+            "package",
+            "import",
+            "public",
+            "protected",
+            "private",
+            "static",
+            "final",
+            "abstract",
+            "void",
+            "return",
+            "if",
+            "else",
+            "for",
+            "while",
+            "switch",
+            "case",
+            "try",
+            "catch",
+            "finally",
+            "throw",
+            "throws",
+            "new",
+            "this",
+            "null",
+            "true",
+            "false",
+            "var",
+        ],
+        Language::Kotlin => &[
+            "fun",
+            "val",
+            "var",
+            "class",
+            "object",
+            "interface",
+            "data",
+            "sealed",
+            "package",
+            "import",
+            "private",
+            "internal",
+            "override",
+            "suspend",
+            "return",
+            "if",
+            "else",
+            "when",
+            "for",
+            "while",
+            "try",
+            "catch",
+            "finally",
+            "throw",
+            "is",
+            "as",
+            "in",
+            "this",
+            "null",
+            "true",
+            "false",
+        ],
+        Language::Other(_) => &[],
+    }
+}
+
+/// The token each byte of `line` belongs to. A lexer for one line, so strings and block
+/// comments that span lines are only recognized where they start.
+fn classify(line: &str, language: &Language) -> Vec<Token> {
+    let syntax = language.comment_syntax();
+    let keywords = keywords(language);
+    let mut tokens = vec![Token::Plain; line.len()];
+    let is_word = |ch: char| ch.is_alphanumeric() || ch == '_';
+
+    let mut index = 0;
+    while index < line.len() {
+        let rest = &line[index..];
+        let end = if syntax.line.as_deref().is_some_and(|open| rest.starts_with(open)) {
+            tokens[index..].fill(Token::Comment);
+            break;
+        } else if let Some((open, close)) =
+            syntax.block.as_ref().filter(|(open, _)| rest.starts_with(open.as_str()))
+        {
+            let length = rest[open.len()..]
+                .find(close.as_str())
+                .map_or(rest.len(), |end| open.len() + end + close.len());
+            tokens[index..index + length].fill(Token::Comment);
+            index + length
+        } else if let Some(length) = string_length(rest, language) {
+            tokens[index..index + length].fill(Token::Str);
+            index + length
+        } else if rest.starts_with(|ch: char| ch.is_ascii_digit()) {
+            let length = rest.find(|ch: char| !(is_word(ch) || ch == '.')).unwrap_or(rest.len());
+            tokens[index..index + length].fill(Token::Number);
+            index + length
+        } else if rest.starts_with(is_word) {
+            let length = rest.find(|ch: char| !is_word(ch)).unwrap_or(rest.len());
+            if keywords.contains(&&rest[..length]) {
+                tokens[index..index + length].fill(Token::Keyword);
+            }
+            index + length
+        } else {
+            index + rest.chars().next().map_or(1, char::len_utf8)
+        };
+        index = end;
+    }
+    tokens
+}
+
+/// Length of the string literal `rest` starts with; an unterminated double-quoted string
+/// runs to the end of the line
+fn string_length(rest: &str, language: &Language) -> Option<usize> {
+    let quote = rest.chars().next().filter(|ch| matches!(ch, '"' | '\'' | '`'))?;
+    // Rust and Zig quote single characters, so a lifetime like `'a` is not a string
+    let one_character = quote == '\''
+        && matches!(language, Language::Rust | Language::Zig)
+        && !rest[1..].starts_with('\\');
+    let mut escaped = false;
+    for (position, (offset, ch)) in rest.char_indices().enumerate().skip(1) {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if ch == quote => return Some(offset + ch.len_utf8()),
+            _ if one_character && position > 1 => return None,
+            _ => {}
+        }
+    }
+    (quote != '\'').then_some(rest.len())
+}
+
+/// `line` as HTML with its tokens colored and `mark`, a byte range of it, highlighted
+fn highlight(line: &str, language: &Language, mark: Option<Range<usize>>) -> String {
+    let tokens = classify(line, language);
+    let mut html = String::new();
+    let mut open: Option<(Token, bool)> = None;
+    let close = |html: &mut String, (token, marked): (Token, bool)| {
+        if token.class().is_some() {
+            html.push_str("</span>");
+        }
+        if marked {
+            html.push_str("</mark>");
+        }
+    };
+
+    for (index, ch) in line.char_indices() {
+        let state = (tokens[index], mark.as_ref().is_some_and(|mark| mark.contains(&index)));
+        if open != Some(state) {
+            if let Some(previous) = open {
+                close(&mut html, previous);
+            }
+            if state.1 {
+                html.push_str("<mark>");
+            }
+            if let Some(class) = state.0.class() {
+                html.push_str(&format!("<span class=\"{class}\">"));
+            }
+            open = Some(state);
+        }
+        match ch {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            _ => html.push(ch),
+        }
+    }
+    if let Some(previous) = open {
+        close(&mut html, previous);
+    }
+    html
+}
+
+#[cfg(test)]
+mod html_tests {
+    use super::super::test_support::{render, violation};
+    use super::*;
+
+    #[test]
+    fn test_highlight_tokens_and_match() {
+        let line = r#"  IO.puts("<b>") # done"#;
+        assert_eq!(
+            highlight(line, &Language::Elixir, Some(2..9)),
+            r#"  <mark>IO.puts</mark>(<span class="s">"&lt;b&gt;"</span>) <span class="c"># done</span>"#
+        );
+        assert_eq!(
+            highlight("fn f<'a>(c: char) -> bool { c == 'x' }", &Language::Rust, None),
+            "<span class=\"k\">fn</span> f&lt;'a&gt;(c: char) -&gt; bool { c == \
+             <span class=\"s\">'x'</span> }"
+        );
+        assert_eq!(
+            highlight("SELECT 1 -- it's", &Language::Sql, None),
+            "<span class=\"k\">SELECT</span> <span class=\"n\">1</span> \
+             <span class=\"c\">-- it's</span>"
+        );
+        assert_eq!(highlight("", &Language::Python, None), "");
+    }
+
+    #[test]
+    fn test_html_report() {
+        let mut critical = violation("lib/<a>.ex", 12, Severity::Critical);
+        critical.context_before = vec!["def run do".to_string()];
+        critical.context_after = vec!["end".to_string()];
+        critical.span = Some(0..4);
+        critical.fix_suggestion = "Avoid <script> & \"quotes\"".to_string();
+        let context = ReportContext { scope: "staged changes".to_string(), ..Default::default() };
+
+        let html =
+            render("html", &context, &[critical, violation("lib/b.ex", 3, Severity::Warning)]);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>patingin review: staged changes</title>"));
+        assert!(html.contains("<strong>2 violations</strong> in 2 files"));
+        assert!(html.contains("<h2>lib/&lt;a&gt;.ex</h2>"));
+        assert!(!html.contains("<a>"));
+        assert!(html.contains("<p>💡 Avoid &lt;script&gt; &amp; &quot;quotes&quot;</p>"));
+        assert!(html.contains("<input type=\"checkbox\" value=\"critical\" checked> critical (1)"));
+        assert!(html.contains("<span class=\"line\"><span class=\"ln\">11</span>"));
+        assert!(html.contains(
+            "<span class=\"line hit\"><span class=\"ln\">12</span><mark>test</mark>_content()"
+        ));
+        assert!(html.contains("<span class=\"ln\">13</span><span class=\"k\">end</span>"));
+        assert!(html.contains("href=\"https://example.com/test_rule\""));
+        assert!(html.trim_end().ends_with("</html>"));
+
+        let empty = render("html", &context, &[]);
+        assert!(empty.contains("No anti-pattern violations found"));
+    }
+}
@@ -0,0 +1,663 @@
+use anyhow::Result;
+use colored::*;
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use super::accessibility::Accessibility;
+use super::{ReportContext, Reporter, SampleSummary, Truncation};
+use crate::core::baseline::AcknowledgedViolation;
+use crate::core::grouping::{group_violations, GroupBy};
+use crate::core::large_hunks::PartialHunk;
+use crate::core::review_engine::{ReviewSummary, SkippedViolation};
+use crate::core::{ReviewViolation, Severity};
+
+/// Colored terminal output grouped by file, the default `--format`
+pub struct HumanReporter {
+    out: Box<dyn Write>,
+    context: ReportContext,
+    violations: Vec<ReviewViolation>,
+}
+
+impl HumanReporter {
+    pub fn new(out: Box<dyn Write>) -> Self {
+        Self { out, context: ReportContext::default(), violations: Vec::new() }
+    }
+
+    fn write_violations(&mut self) -> Result<()> {
+        let out = &mut self.out;
+        let violations = &self.violations;
+        let a = self.context.accessibility;
+
+        let files: BTreeSet<&str> = violations.iter().map(|v| v.file_path.as_str()).collect();
+        writeln!(
+            out,
+            "{}Found {} violations in {} files\n",
+            a.icon("📊 "),
+            violations.len(),
+            files.len()
+        )?;
+
+        for section in group_violations(violations, self.context.group_by) {
+            match self.context.group_by {
+                GroupBy::File => {
+                    let owners = &section.groups[0][0].owners;
+                    let owners = if owners.is_empty() {
+                        String::new()
+                    } else {
+                        format!("  {}{}", a.icon("👥 "), a.muted(&owners.join(", ")))
+                    };
+                    writeln!(out, "{}{}{owners}", a.icon("📁 "), section.key.bold())?;
+                    for group in &section.groups {
+                        let count = (group.len() > 1).then_some(group.len());
+                        write_rule_heading(out, group[0], count, "  ", a)?;
+                        write_findings(out, group, "    ", a)?;
+                        write_rule_notes(out, group[0], &self.context, "    ")?;
+                        writeln!(out)?;
+                    }
+                }
+                GroupBy::Rule => {
+                    let first = section.groups[0][0];
+                    write_rule_heading(out, first, None, "", a)?;
+                    writeln!(
+                        out,
+                        "  {}",
+                        a.muted(&format!(
+                            "{} violation(s) in {} file(s)",
+                            section.count(),
+                            section.groups.len()
+                        ))
+                    )?;
+                    write_rule_notes(out, first, &self.context, "  ")?;
+                    for group in &section.groups {
+                        writeln!(out, "  {}{}", a.icon("📁 "), group[0].file_path.bold())?;
+                        write_findings(out, group, "    ", a)?;
+                    }
+                    writeln!(out)?;
+                }
+                GroupBy::Owner => {
+                    writeln!(out, "{}{}", a.icon("👥 "), section.key.bold())?;
+                    writeln!(
+                        out,
+                        "  {}",
+                        a.muted(&format!(
+                            "{} violation(s) in {} file(s)",
+                            section.count(),
+                            section.file_count()
+                        ))
+                    )?;
+                    let mut current_file = None;
+                    for group in &section.groups {
+                        let file = group[0].file_path.as_str();
+                        if current_file != Some(file) {
+                            writeln!(out, "  {}{}", a.icon("📁 "), file.bold())?;
+                            current_file = Some(file);
+                        }
+                        let count = (group.len() > 1).then_some(group.len());
+                        write_rule_heading(out, group[0], count, "    ", a)?;
+                        write_findings(out, group, "      ", a)?;
+                        write_rule_notes(out, group[0], &self.context, "      ")?;
+                    }
+                    writeln!(out)?;
+                }
+            }
+        }
+
+        let count = |severity| violations.iter().filter(|v| v.severity == severity).count();
+        let auto_fixable_count = violations.iter().filter(|v| v.auto_fixable).count();
+
+        writeln!(out, "{}Summary: {} violations", a.icon("📊 "), violations.len())?;
+        for severity in Severity::ALL {
+            let name = severity.to_string();
+            let label = name[..1].to_uppercase() + &name[1..];
+            if count(severity) > 0 {
+                match a {
+                    Accessibility::Standard => writeln!(
+                        out,
+                        "   {} {label}: {}",
+                        a.severity_marker(severity),
+                        count(severity)
+                    )?,
+                    _ => writeln!(out, "   {} {}", a.severity_marker(severity), count(severity))?,
+                }
+            }
+        }
+
+        if auto_fixable_count > 0 {
+            writeln!(out, "   {}Auto-fixable: {auto_fixable_count}", a.icon("✨ "))?;
+
+            if !self.context.fix_requested {
+                writeln!(
+                    out,
+                    "\n{}Use {} to see suggested fixes",
+                    a.icon("💡 "),
+                    a.highlight("--suggest", Color::Cyan)
+                )?;
+                writeln!(
+                    out,
+                    "{}Use {} to launch interactive Claude Code session",
+                    a.icon("💡 "),
+                    a.highlight("--fix", Color::Cyan)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A rule's severity, name and id, with how many times it fired when findings are grouped
+fn write_rule_heading(
+    out: &mut dyn Write,
+    violation: &ReviewViolation,
+    count: Option<usize>,
+    indent: &str,
+    a: Accessibility,
+) -> Result<()> {
+    let count = count.map(|count| format!(" ×{count}")).unwrap_or_default();
+    writeln!(
+        out,
+        "{indent}{} {} ({}){count}",
+        a.severity_label(violation.severity),
+        violation.rule.name,
+        a.muted(&violation.rule.id)
+    )?;
+    Ok(())
+}
+
+/// The lines of one rule's findings in one file. A fix shared by every finding is given
+/// once after them, other fixes under their line.
+fn write_findings(
+    out: &mut dyn Write,
+    group: &[&ReviewViolation],
+    indent: &str,
+    a: Accessibility,
+) -> Result<()> {
+    let shared_fix = group.len() > 1
+        && group.iter().all(|violation| violation.fix_suggestion == group[0].fix_suggestion);
+    for violation in group {
+        writeln!(
+            out,
+            "{indent}Line {}: {}",
+            a.highlight(&violation.line_number.to_string(), Color::Cyan),
+            a.muted(&violation.content)
+        )?;
+        if !shared_fix {
+            writeln!(out, "{indent}{}Fix: {}", a.icon("💡 "), violation.fix_suggestion)?;
+        }
+        if let Some(blame) = &violation.blame {
+            writeln!(
+                out,
+                "{indent}{}{} <{}> in {} on {}",
+                a.icon("👤 "),
+                blame.author,
+                blame.email,
+                a.muted(blame.short_commit()),
+                blame_date(blame.time)
+            )?;
+        }
+    }
+    if shared_fix {
+        writeln!(out, "{indent}{}Fix: {}", a.icon("💡 "), group[0].fix_suggestion)?;
+    }
+    Ok(())
+}
+
+/// Where a rule came from (`--verbose`) and whether it can be fixed automatically
+fn write_rule_notes(
+    out: &mut dyn Write,
+    violation: &ReviewViolation,
+    context: &ReportContext,
+    indent: &str,
+) -> Result<()> {
+    let a = context.accessibility;
+    if let Some(source) = context.rule_sources.get(&violation.rule).filter(|_| context.verbose) {
+        writeln!(out, "{indent}{}Rule from {source}", a.icon("📦 "))?;
+    }
+    if violation.auto_fixable && context.show_auto_fixable {
+        writeln!(out, "{indent}{}Auto-fixable with Claude Code", a.icon("✨ "))?;
+    }
+    Ok(())
+}
+
+fn write_sample_note(out: &mut dyn Write, sample: &SampleSummary, a: Accessibility) -> Result<()> {
+    if sample.omitted.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        out,
+        "\n{}Sampled {} per rule: {} of {} violations shown, {} more not shown",
+        a.icon("🔎 "),
+        sample.per_rule,
+        sample.shown,
+        sample.total,
+        sample.total - sample.shown
+    )?;
+    for omitted in &sample.omitted {
+        writeln!(
+            out,
+            "   {} {}: {} more",
+            a.severity_marker(omitted.severity),
+            a.muted(&omitted.rule_id),
+            omitted.count
+        )?;
+    }
+    Ok(())
+}
+
+fn write_truncation_note(
+    out: &mut dyn Write,
+    truncation: Truncation,
+    a: Accessibility,
+) -> Result<()> {
+    writeln!(
+        out,
+        "\n{}Showing the first {} of {} violations; {} more not shown \
+         (output.max_reported_violations)",
+        a.icon("✂️  "),
+        truncation.limit,
+        truncation.total,
+        truncation.total - truncation.limit
+    )?;
+    Ok(())
+}
+
+fn write_partial_hunks_note(
+    out: &mut dyn Write,
+    partial_hunks: &[PartialHunk],
+    a: Accessibility,
+) -> Result<()> {
+    if partial_hunks.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "\n{}Partially analyzed (large hunks, critical rules only):", a.icon("⏩ "))?;
+    for hunk in partial_hunks {
+        writeln!(
+            out,
+            "   {}:{}-{}: {} of {} added lines checked",
+            hunk.file_path, hunk.first_line, hunk.last_line, hunk.checked_lines, hunk.added_lines
+        )?;
+    }
+    Ok(())
+}
+
+/// Count skipped findings by reason, listing each one when `--show-skipped` collected them
+fn write_skipped_note(
+    out: &mut dyn Write,
+    summary: &ReviewSummary,
+    skipped: &[SkippedViolation],
+    a: Accessibility,
+) -> Result<()> {
+    if summary.skipped.is_empty() {
+        return Ok(());
+    }
+    let counts: Vec<String> =
+        summary.skipped.iter().map(|(reason, count)| format!("{count} {reason}")).collect();
+    writeln!(
+        out,
+        "\n{}Skipped {} finding(s): {}",
+        a.icon("🙈 "),
+        summary.skipped.values().sum::<usize>(),
+        counts.join(", ")
+    )?;
+    if skipped.is_empty() {
+        writeln!(out, "   {}", a.muted("List them with --show-skipped"))?;
+    }
+    for skipped in skipped {
+        writeln!(
+            out,
+            "   {}:{} {} ({})",
+            skipped.violation.file_path,
+            skipped.violation.line_number,
+            a.muted(&skipped.violation.rule.id),
+            skipped.reason
+        )?;
+    }
+    Ok(())
+}
+
+/// Author date of a blamed line, e.g. `2025-03-14`
+fn blame_date(time: i64) -> String {
+    chrono::DateTime::from_timestamp(time, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// List the accepted risks, so triaged findings stay visible instead of vanishing
+fn write_acknowledged_note(
+    out: &mut dyn Write,
+    acknowledged: &[AcknowledgedViolation],
+    a: Accessibility,
+) -> Result<()> {
+    if acknowledged.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "\n{}Acknowledged as accepted risk ({}):", a.icon("🛡️  "), acknowledged.len())?;
+    for item in acknowledged {
+        writeln!(
+            out,
+            "   {}:{} {} ({}): {} {}",
+            item.violation.file_path,
+            item.violation.line_number,
+            a.muted(&item.violation.rule.id),
+            item.violation.severity,
+            item.risk.reason,
+            a.muted(&format!("by {} on {}", item.risk.author, item.risk.date))
+        )?;
+    }
+    Ok(())
+}
+
+impl Reporter for HumanReporter {
+    fn on_start(&mut self, context: &ReportContext) -> Result<()> {
+        self.context = context.clone();
+        writeln!(
+            self.out,
+            "{}Code Review: {}",
+            context.accessibility.icon("🔍 "),
+            context.scope.bold()
+        )?;
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &ReviewViolation) -> Result<()> {
+        self.violations.push(violation.clone());
+        Ok(())
+    }
+
+    fn on_summary(&mut self, summary: &ReviewSummary) -> Result<()> {
+        if self.violations.is_empty() {
+            let icon = self.context.accessibility.icon("✅ ");
+            writeln!(self.out, "{icon}No anti-pattern violations found!")?;
+        } else {
+            self.write_violations()?;
+        }
+
+        if let Some(sample) = &self.context.sampling {
+            write_sample_note(&mut self.out, sample, self.context.accessibility)?;
+        }
+        if let Some(truncation) = self.context.truncation {
+            write_truncation_note(&mut self.out, truncation, self.context.accessibility)?;
+        }
+        write_partial_hunks_note(
+            &mut self.out,
+            &self.context.partial_hunks,
+            self.context.accessibility,
+        )?;
+        write_skipped_note(
+            &mut self.out,
+            summary,
+            &self.context.skipped,
+            self.context.accessibility,
+        )?;
+        write_acknowledged_note(
+            &mut self.out,
+            &self.context.acknowledged,
+            self.context.accessibility,
+        )?;
+        if let Some(fixed) = self.context.fixed_since_last_run.filter(|fixed| *fixed > 0) {
+            let a = self.context.accessibility;
+            writeln!(
+                self.out,
+                "\n{}Fixed since last run: {}",
+                a.icon("🎉 "),
+                a.highlight(&fixed.to_string(), Color::Green)
+            )?;
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod human_tests {
+    use super::super::test_support::{render, violation, SharedBuffer};
+    use super::super::{report, OmittedRule};
+    use super::*;
+    use crate::core::baseline::AcceptedRisk;
+    use crate::core::provenance::RuleSource;
+    use crate::core::review_engine::SkipReason;
+    use crate::git::blame::LineBlame;
+
+    fn context() -> ReportContext {
+        ReportContext { scope: "staged changes".to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn test_groups_by_file_with_summary() {
+        colored::control::set_override(false);
+        let violations = vec![
+            violation("lib/user.ex", 10, Severity::Critical),
+            violation("lib/auth.ex", 4, Severity::Major),
+        ];
+
+        let output = render("human", &context(), &violations);
+
+        assert!(output.starts_with("🔍 Code Review: staged changes"));
+        assert!(output.contains("📊 Found 2 violations in 2 files"));
+        assert!(output.find("📁 lib/auth.ex").unwrap() < output.find("📁 lib/user.ex").unwrap());
+        assert!(output.contains("   🔴 Critical: 1"));
+        assert!(output.contains("💡 Use --suggest to see suggested fixes"));
+    }
+
+    #[test]
+    fn test_repeats_of_a_rule_are_grouped() {
+        colored::control::set_override(false);
+        let mut violations = vec![
+            violation("lib/user.ex", 10, Severity::Critical),
+            violation("lib/user.ex", 4, Severity::Critical),
+            violation("lib/auth.ex", 2, Severity::Critical),
+        ];
+        violations[2].fix_suggestion = "Use b".to_string();
+
+        let output = render("human", &context(), &violations);
+        assert!(output.contains(
+            "📁 lib/user.ex\n  🔴 CRITICAL Test Rule (test_rule) ×2\n    Line 4: test_content()\n    \
+             Line 10: test_content()\n    💡 Fix: Use a | b\n"
+        ));
+        assert!(output.contains("📊 Summary: 3 violations"));
+
+        let by_rule = ReportContext { group_by: GroupBy::Rule, ..context() };
+        let output = render("human", &by_rule, &violations);
+        assert!(output.contains(
+            "🔴 CRITICAL Test Rule (test_rule)\n  3 violation(s) in 2 file(s)\n  📁 lib/auth.ex\n    \
+             Line 2: test_content()\n    💡 Fix: Use b\n  📁 lib/user.ex\n"
+        ), "{output}");
+    }
+
+    #[test]
+    fn test_owners_are_shown_and_grouped() {
+        colored::control::set_override(false);
+        let mut violations = vec![
+            violation("lib/pay.ex", 3, Severity::Critical),
+            violation("README.md", 1, Severity::Warning),
+        ];
+        violations[0].owners = vec!["@acme/payments".to_string()];
+
+        let output = render("human", &context(), &violations);
+        assert!(
+            output.contains(
+                "📁 lib/pay.ex  👥 @acme/payments
+"
+            ),
+            "{output}"
+        );
+
+        let by_owner = ReportContext { group_by: GroupBy::Owner, ..context() };
+        let output = render("human", &by_owner, &violations);
+        assert!(output.contains(
+            "👥 @acme/payments\n  1 violation(s) in 1 file(s)\n  📁 lib/pay.ex\n    🔴 CRITICAL \
+             Test Rule (test_rule)\n      Line 3: test_content()\n"
+        ), "{output}");
+        assert!(output.find("👥 @acme/payments").unwrap() < output.find("👥 (no owner)").unwrap());
+    }
+
+    #[test]
+    fn test_empty_and_sampled_output() {
+        colored::control::set_override(false);
+        let output = render("human", &context(), &[]);
+        assert!(output.contains("✅ No anti-pattern violations found!"));
+
+        let sampled = ReportContext {
+            fix_requested: true,
+            sampling: Some(SampleSummary {
+                per_rule: 1,
+                shown: 1,
+                total: 3,
+                omitted: vec![OmittedRule {
+                    rule_id: "test_rule".to_string(),
+                    severity: Severity::Major,
+                    count: 2,
+                }],
+            }),
+            ..context()
+        };
+        let output = render("human", &sampled, &[violation("a.ex", 1, Severity::Major)]);
+        assert!(output.contains("🔎 Sampled 1 per rule: 1 of 3 violations shown, 2 more not shown"));
+        assert!(output.contains("🟡 test_rule: 2 more"));
+        assert!(!output.contains("--suggest"));
+
+        let partial = ReportContext {
+            partial_hunks: vec![PartialHunk {
+                file_path: "priv/static/app.js".to_string(),
+                first_line: 1,
+                last_line: 5000,
+                added_lines: 5000,
+                checked_lines: 200,
+            }],
+            ..context()
+        };
+        let output = render("human", &partial, &[]);
+        assert!(output.contains("⏩ Partially analyzed (large hunks, critical rules only):"));
+        assert!(output.contains("priv/static/app.js:1-5000: 200 of 5000 added lines checked"));
+        assert!(!output.contains("Showing the first"));
+
+        let truncated =
+            ReportContext { truncation: Some(Truncation { limit: 1, total: 40 }), ..context() };
+        let output = render("human", &truncated, &[violation("a.ex", 1, Severity::Major)]);
+        assert!(output.contains(
+            "✂️  Showing the first 1 of 40 violations; 39 more not shown \
+             (output.max_reported_violations)"
+        ));
+    }
+
+    #[test]
+    fn test_fixed_since_last_run() {
+        colored::control::set_override(false);
+        let progress = ReportContext { fixed_since_last_run: Some(3), ..context() };
+        let output = render("human", &progress, &[violation("a.ex", 1, Severity::Major)]);
+        assert!(output.ends_with("\n🎉 Fixed since last run: 3\n"));
+
+        let unchanged = ReportContext { fixed_since_last_run: Some(0), ..context() };
+        assert!(!render("human", &unchanged, &[]).contains("Fixed since last run"));
+    }
+
+    #[test]
+    fn test_skipped_findings_are_counted_and_listed() {
+        colored::control::set_override(false);
+        let mut summary = ReviewSummary::default();
+        summary.record_skip(SkipReason::InlineSuppression);
+        summary.record_skip(SkipReason::InlineSuppression);
+        summary.record_skip(SkipReason::OlderThan);
+        let render_with = |context: &ReportContext| {
+            let buffer = SharedBuffer::default();
+            let mut reporter = HumanReporter::new(Box::new(buffer.clone()));
+            report(&mut reporter, context, &[], &summary).unwrap();
+            buffer.contents()
+        };
+
+        let output = render_with(&context());
+        assert!(output.contains(
+            "🙈 Skipped 3 finding(s): 2 inline suppression, 1 older than --ignore-older-than"
+        ));
+        assert!(output.contains("List them with --show-skipped"));
+
+        let listed = ReportContext {
+            skipped: vec![SkippedViolation {
+                violation: violation("lib/a.ex", 7, Severity::Major),
+                reason: SkipReason::OlderThan,
+            }],
+            ..context()
+        };
+        let output = render_with(&listed);
+        assert!(output.contains("   lib/a.ex:7 test_rule (older than --ignore-older-than)"));
+        assert!(!output.contains("--show-skipped"));
+        assert!(!render("human", &context(), &[]).contains("Skipped"));
+    }
+
+    #[test]
+    fn test_blamed_violations_name_their_author() {
+        colored::control::set_override(false);
+        let mut violations = vec![violation("lib/a.ex", 3, Severity::Major)];
+        assert!(!render("human", &context(), &violations).contains("👤"));
+
+        violations[0].blame = Some(LineBlame {
+            commit: "24ef6ea1c0ffee".to_string(),
+            author: "Ana".to_string(),
+            email: "ana@example.com".to_string(),
+            time: 1_700_000_000,
+        });
+        let output = render("human", &context(), &violations);
+        assert!(output.contains("    👤 Ana <ana@example.com> in 24ef6ea on 2023-11-14\n"));
+    }
+
+    #[test]
+    fn test_verbose_output_names_the_rule_source() {
+        colored::control::set_override(false);
+        let violations = vec![violation("lib/a.ex", 3, Severity::Major)];
+        let mut context = context();
+        context
+            .rule_sources
+            .insert(&violations[0].rule, RuleSource::Project { project: "app".to_string() });
+        assert!(!render("human", &context, &violations).contains("Rule from"));
+
+        let verbose = ReportContext { verbose: true, ..context };
+        let output = render("human", &verbose, &violations);
+        assert!(output.contains("    📦 Rule from custom rules of project app\n"));
+    }
+
+    #[test]
+    fn test_accepted_risks_are_listed_as_acknowledged() {
+        colored::control::set_override(false);
+        let acknowledged = ReportContext {
+            acknowledged: vec![AcknowledgedViolation {
+                violation: violation("lib/a.ex", 3, Severity::Major),
+                risk: AcceptedRisk {
+                    reason: "Internal tool only".to_string(),
+                    author: "Jane <jane@example.com>".to_string(),
+                    date: "2024-05-01".to_string(),
+                },
+            }],
+            ..context()
+        };
+
+        let output = render("human", &acknowledged, &[]);
+        assert!(output.contains("🛡️  Acknowledged as accepted risk (1):"));
+        assert!(output.contains(
+            "   lib/a.ex:3 test_rule (major): Internal tool only by Jane <jane@example.com> on 2024-05-01"
+        ));
+        assert!(!render("human", &context(), &[]).contains("Acknowledged"));
+    }
+
+    #[test]
+    fn test_accessible_output_has_text_labels() {
+        colored::control::set_override(false);
+        let violations = vec![
+            violation("lib/user.ex", 10, Severity::Critical),
+            violation("lib/auth.ex", 4, Severity::Warning),
+        ];
+
+        let high_contrast =
+            ReportContext { accessibility: Accessibility::HighContrast, ..context() };
+        let output = render("human", &high_contrast, &violations);
+        assert!(output.starts_with("Code Review: staged changes"));
+        assert!(output.contains("  [S1 CRITICAL] Test Rule (test_rule)"));
+        assert!(output.contains("   [S3 WARNING] 1"));
+
+        let screen_reader =
+            ReportContext { accessibility: Accessibility::ScreenReader, ..context() };
+        let output = render("human", &screen_reader, &violations);
+        assert!(output.contains("  Severity 1 critical: Test Rule (test_rule)"));
+        assert!(output.contains("lib/auth.ex\n"));
+        assert!(!output.chars().any(|c| c as u32 >= 0x2000));
+    }
+}
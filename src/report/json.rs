@@ -0,0 +1,777 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use super::positions::{violation_columns, ColumnUnit};
+use super::{FileProgress, ReportContext, Reporter, SampleSummary};
+use crate::config::deprecations::Deprecation;
+use crate::core::baseline::AcknowledgedViolation;
+use crate::core::coverage::{FileCoverage, FileSkipReason, FileStatus};
+use crate::core::large_hunks::PartialHunk;
+use crate::core::provenance::{RuleSource, RuleSources};
+use crate::core::review_engine::{ReviewSummary, SkipReason, SkippedViolation};
+use crate::core::ReviewViolation;
+use crate::git::blame::LineBlame;
+use crate::git::ParseWarning;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonViolation {
+    pub file_path: String,
+    pub line_number: usize,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub severity: String,
+    pub language: String,
+    pub description: String,
+    pub fix_suggestion: String,
+    pub auto_fixable: bool,
+    /// How likely the finding is a real problem, from 0 to 1
+    #[serde(default)]
+    pub confidence: f64,
+    /// Where the rule came from, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_source: Option<RuleSource>,
+    /// 1-based column where the matched text starts, counted in `output.column_unit`;
+    /// absent for rules about the whole line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// 1-based column just past the matched text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+    /// Who last changed the line, with `--blame`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame: Option<LineBlame>,
+    /// What the rule's capture groups matched, by group number and name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub captures: BTreeMap<String, String>,
+    /// Owners of the file from CODEOWNERS, when the repository has one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+}
+
+impl From<&ReviewViolation> for JsonViolation {
+    fn from(v: &ReviewViolation) -> Self {
+        Self {
+            file_path: v.file_path.clone(),
+            line_number: v.line_number,
+            rule_id: v.rule.id.clone(),
+            rule_name: v.rule.name.clone(),
+            severity: format!("{:?}", v.severity).to_lowercase(),
+            language: format!("{:?}", v.language).to_lowercase(),
+            description: v.rule.description.clone(),
+            fix_suggestion: v.fix_suggestion.clone(),
+            auto_fixable: v.auto_fixable,
+            confidence: v.confidence,
+            rule_source: None,
+            column: None,
+            end_column: None,
+            blame: v.blame.clone(),
+            captures: v.captures.clone(),
+            owners: v.owners.clone(),
+        }
+    }
+}
+
+impl JsonViolation {
+    /// The violation with the source its rule has in `sources` and its columns in `unit`
+    fn for_report(violation: &ReviewViolation, sources: &RuleSources, unit: ColumnUnit) -> Self {
+        let columns = violation_columns(violation, unit);
+        Self {
+            rule_source: sources.get(&violation.rule).cloned(),
+            column: columns.as_ref().map(|columns| columns.start),
+            end_column: columns.map(|columns| columns.end),
+            ..Self::from(violation)
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonSummary {
+    pub total_violations: usize,
+    #[serde(default)]
+    pub blocker_count: usize,
+    pub critical_count: usize,
+    pub major_count: usize,
+    pub warning_count: usize,
+    #[serde(default)]
+    pub info_count: usize,
+    pub files_affected: usize,
+    pub auto_fixable_count: usize,
+    /// Findings that matched a rule but were not reported, by reason
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub skipped: BTreeMap<SkipReason, usize>,
+}
+
+impl From<&ReviewSummary> for JsonSummary {
+    fn from(summary: &ReviewSummary) -> Self {
+        Self {
+            total_violations: summary.total_violations,
+            blocker_count: summary.blocker_count,
+            critical_count: summary.critical_count,
+            major_count: summary.major_count,
+            warning_count: summary.warning_count,
+            info_count: summary.info_count,
+            files_affected: summary.files_affected.len(),
+            auto_fixable_count: summary.auto_fixable_count,
+            skipped: summary.skipped.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonSampling {
+    pub per_rule: usize,
+    pub shown: usize,
+    pub total_violations: usize,
+    /// Findings not listed in `violations`, keyed by rule id
+    pub omitted: BTreeMap<String, usize>,
+}
+
+impl From<&SampleSummary> for JsonSampling {
+    fn from(sample: &SampleSummary) -> Self {
+        Self {
+            per_rule: sample.per_rule,
+            shown: sample.shown,
+            total_violations: sample.total,
+            omitted: sample
+                .omitted
+                .iter()
+                .map(|omitted| (omitted.rule_id.clone(), omitted.count))
+                .collect(),
+        }
+    }
+}
+
+/// A deprecated flag the run used, so scripts can be migrated before it is removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonDeprecation {
+    pub flag: String,
+    pub replacement: String,
+    pub removal_in: String,
+    pub message: String,
+}
+
+impl From<&Deprecation> for JsonDeprecation {
+    fn from(deprecation: &Deprecation) -> Self {
+        Self {
+            flag: deprecation.flag.to_string(),
+            replacement: deprecation.replacement.to_string(),
+            removal_in: deprecation.removal_in.to_string(),
+            message: deprecation.message(),
+        }
+    }
+}
+
+/// An oversized hunk that was only sampled against critical rules
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonPartialHunk {
+    pub file_path: String,
+    pub first_line: usize,
+    pub last_line: usize,
+    pub added_lines: usize,
+    pub checked_lines: usize,
+}
+
+impl From<&PartialHunk> for JsonPartialHunk {
+    fn from(hunk: &PartialHunk) -> Self {
+        Self {
+            file_path: hunk.file_path.clone(),
+            first_line: hunk.first_line,
+            last_line: hunk.last_line,
+            added_lines: hunk.added_lines,
+            checked_lines: hunk.checked_lines,
+        }
+    }
+}
+
+/// A diff section that was skipped, so a file missing from the results can be explained
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonParseWarning {
+    pub path: Option<String>,
+    pub message: String,
+}
+
+impl From<&ParseWarning> for JsonParseWarning {
+    fn from(warning: &ParseWarning) -> Self {
+        Self { path: warning.path.clone(), message: warning.message.clone() }
+    }
+}
+
+/// A violation triaged as an accepted risk, reported as acknowledged
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonAcknowledged {
+    #[serde(flatten)]
+    pub violation: JsonViolation,
+    pub reason: String,
+    pub accepted_by: String,
+    pub accepted_on: String,
+}
+
+impl From<&AcknowledgedViolation> for JsonAcknowledged {
+    fn from(item: &AcknowledgedViolation) -> Self {
+        Self {
+            violation: JsonViolation::from(&item.violation),
+            reason: item.risk.reason.clone(),
+            accepted_by: item.risk.author.clone(),
+            accepted_on: item.risk.date.clone(),
+        }
+    }
+}
+
+/// A file of the diff, listed whether or not it has violations, for coverage reporting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonFile {
+    pub path: String,
+    pub language: Option<String>,
+    /// `analyzed`, `partially_analyzed` or `skipped`
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<FileSkipReason>,
+    pub violations: usize,
+}
+
+impl From<&FileCoverage> for JsonFile {
+    fn from(file: &FileCoverage) -> Self {
+        let (status, reason) = match file.status {
+            FileStatus::Analyzed => ("analyzed", None),
+            FileStatus::PartiallyAnalyzed => ("partially_analyzed", None),
+            FileStatus::Skipped(reason) => ("skipped", Some(reason)),
+        };
+        Self {
+            path: file.path.clone(),
+            language: file.language.as_ref().map(|l| format!("{l:?}").to_lowercase()),
+            status: status.to_string(),
+            reason,
+            violations: file.violations,
+        }
+    }
+}
+
+/// A finding that was not reported and why, listed with `--show-skipped`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonSkippedViolation {
+    #[serde(flatten)]
+    pub violation: JsonViolation,
+    pub reason: SkipReason,
+}
+
+impl From<&SkippedViolation> for JsonSkippedViolation {
+    fn from(skipped: &SkippedViolation) -> Self {
+        Self { violation: JsonViolation::from(&skipped.violation), reason: skipped.reason }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonOutput {
+    violations: Vec<JsonViolation>,
+    summary: JsonSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sampling: Option<JsonSampling>,
+    /// Whether `output.max_reported_violations` left violations out; `summary` counts them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    deprecations: Vec<JsonDeprecation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    partially_analyzed: Vec<JsonPartialHunk>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fixed_since_last_run: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    parse_warnings: Vec<JsonParseWarning>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    skipped: Vec<JsonSkippedViolation>,
+    #[serde(default)]
+    files: Vec<JsonFile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    acknowledged: Vec<JsonAcknowledged>,
+    /// Reported violations per CODEOWNERS owner; a violation counts for each of its owners
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    by_owner: BTreeMap<String, usize>,
+}
+
+/// A single pretty-printed JSON document, written once the summary is known
+pub struct JsonReporter {
+    out: Box<dyn Write>,
+    sampling: Option<JsonSampling>,
+    truncated: bool,
+    deprecations: Vec<JsonDeprecation>,
+    partially_analyzed: Vec<JsonPartialHunk>,
+    fixed_since_last_run: Option<usize>,
+    parse_warnings: Vec<JsonParseWarning>,
+    skipped: Vec<JsonSkippedViolation>,
+    files: Vec<JsonFile>,
+    acknowledged: Vec<JsonAcknowledged>,
+    rule_sources: RuleSources,
+    column_unit: ColumnUnit,
+    violations: Vec<JsonViolation>,
+}
+
+impl JsonReporter {
+    pub fn new(out: Box<dyn Write>) -> Self {
+        Self {
+            out,
+            sampling: None,
+            truncated: false,
+            deprecations: Vec::new(),
+            partially_analyzed: Vec::new(),
+            fixed_since_last_run: None,
+            parse_warnings: Vec::new(),
+            skipped: Vec::new(),
+            files: Vec::new(),
+            acknowledged: Vec::new(),
+            rule_sources: RuleSources::default(),
+            column_unit: ColumnUnit::default(),
+            violations: Vec::new(),
+        }
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn on_start(&mut self, context: &ReportContext) -> Result<()> {
+        self.sampling = context.sampling.as_ref().map(JsonSampling::from);
+        self.truncated = context.truncation.is_some();
+        self.deprecations = context.deprecations.iter().map(JsonDeprecation::from).collect();
+        self.partially_analyzed = context.partial_hunks.iter().map(JsonPartialHunk::from).collect();
+        self.fixed_since_last_run = context.fixed_since_last_run;
+        self.parse_warnings = context.parse_warnings.iter().map(JsonParseWarning::from).collect();
+        self.skipped = context.skipped.iter().map(JsonSkippedViolation::from).collect();
+        self.files = context.files.iter().map(JsonFile::from).collect();
+        self.acknowledged = context.acknowledged.iter().map(JsonAcknowledged::from).collect();
+        self.rule_sources = context.rule_sources.clone();
+        self.column_unit = context.column_unit;
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &ReviewViolation) -> Result<()> {
+        self.violations.push(JsonViolation::for_report(
+            violation,
+            &self.rule_sources,
+            self.column_unit,
+        ));
+        Ok(())
+    }
+
+    fn on_summary(&mut self, summary: &ReviewSummary) -> Result<()> {
+        let mut by_owner: BTreeMap<String, usize> = BTreeMap::new();
+        for owner in self.violations.iter().flat_map(|violation| &violation.owners) {
+            *by_owner.entry(owner.clone()).or_default() += 1;
+        }
+        let json_output = JsonOutput {
+            violations: std::mem::take(&mut self.violations),
+            summary: JsonSummary::from(summary),
+            sampling: self.sampling.take(),
+            truncated: std::mem::take(&mut self.truncated),
+            deprecations: std::mem::take(&mut self.deprecations),
+            partially_analyzed: std::mem::take(&mut self.partially_analyzed),
+            fixed_since_last_run: self.fixed_since_last_run.take(),
+            parse_warnings: std::mem::take(&mut self.parse_warnings),
+            skipped: std::mem::take(&mut self.skipped),
+            files: std::mem::take(&mut self.files),
+            acknowledged: std::mem::take(&mut self.acknowledged),
+            by_owner,
+        };
+        writeln!(self.out, "{}", serde_json::to_string_pretty(&json_output)?)?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// One line of `--ndjson` output
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum NdjsonRecord {
+    Deprecation(JsonDeprecation),
+    PartiallyAnalyzed(JsonPartialHunk),
+    ParseWarning(JsonParseWarning),
+    Violation(Box<JsonViolation>),
+    File(JsonFileProgress),
+    Summary(JsonSummary),
+}
+
+/// `file` record of `--stream`: one more file of the diff has been analyzed
+#[derive(Serialize, Deserialize)]
+struct JsonFileProgress {
+    path: String,
+    violations: usize,
+    files_reviewed: usize,
+    files_total: usize,
+}
+
+impl From<&FileProgress> for JsonFileProgress {
+    fn from(progress: &FileProgress) -> Self {
+        Self {
+            path: progress.path.clone(),
+            violations: progress.violations,
+            files_reviewed: progress.files_reviewed,
+            files_total: progress.files_total,
+        }
+    }
+}
+
+/// Newline-delimited JSON, one record per violation followed by a summary record.
+/// Each line is flushed immediately so pipelines can process results incrementally.
+pub struct NdjsonReporter {
+    out: Box<dyn Write>,
+    rule_sources: RuleSources,
+    column_unit: ColumnUnit,
+}
+
+impl NdjsonReporter {
+    pub fn new(out: Box<dyn Write>) -> Self {
+        Self { out, rule_sources: RuleSources::default(), column_unit: ColumnUnit::default() }
+    }
+
+    fn write_record(&mut self, record: &NdjsonRecord) -> Result<()> {
+        serde_json::to_writer(&mut self.out, record)?;
+        writeln!(self.out)?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+impl Reporter for NdjsonReporter {
+    fn on_start(&mut self, context: &ReportContext) -> Result<()> {
+        self.rule_sources = context.rule_sources.clone();
+        self.column_unit = context.column_unit;
+        for deprecation in &context.deprecations {
+            self.write_record(&NdjsonRecord::Deprecation(JsonDeprecation::from(deprecation)))?;
+        }
+        for hunk in &context.partial_hunks {
+            self.write_record(&NdjsonRecord::PartiallyAnalyzed(JsonPartialHunk::from(hunk)))?;
+        }
+        for warning in &context.parse_warnings {
+            self.write_record(&NdjsonRecord::ParseWarning(JsonParseWarning::from(warning)))?;
+        }
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &ReviewViolation) -> Result<()> {
+        let violation = JsonViolation::for_report(violation, &self.rule_sources, self.column_unit);
+        self.write_record(&NdjsonRecord::Violation(Box::new(violation)))
+    }
+
+    fn on_file(&mut self, progress: &FileProgress) -> Result<()> {
+        self.write_record(&NdjsonRecord::File(JsonFileProgress::from(progress)))
+    }
+
+    fn on_summary(&mut self, summary: &ReviewSummary) -> Result<()> {
+        self.write_record(&NdjsonRecord::Summary(JsonSummary::from(summary)))
+    }
+
+    fn is_streaming(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod json_tests {
+    use super::super::test_support::{render, violation, SharedBuffer};
+    use super::super::ReporterRegistry;
+    use super::super::{OmittedRule, Truncation};
+    use super::*;
+    use crate::config::deprecations::find_deprecation;
+    use crate::core::Severity;
+
+    #[test]
+    fn test_json_document_structure() {
+        let violations = vec![violation("lib/user.ex", 42, Severity::Major)];
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &ReportContext::default(), &violations)).unwrap();
+
+        assert_eq!(output["violations"][0]["rule_id"], "test_rule");
+        assert_eq!(output["violations"][0]["severity"], "major");
+        assert_eq!(output["summary"]["total_violations"], 1);
+        assert!(output.get("sampling").is_none());
+        assert!(output.get("fixed_since_last_run").is_none());
+        assert!(output.get("skipped").is_none());
+        assert!(output.get("truncated").is_none());
+        assert!(output["summary"].get("skipped").is_none());
+
+        let progress = ReportContext { fixed_since_last_run: Some(0), ..Default::default() };
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &progress, &violations)).unwrap();
+        assert_eq!(output["fixed_since_last_run"], 0);
+
+        let truncated = ReportContext {
+            truncation: Some(Truncation { limit: 1, total: 2 }),
+            ..Default::default()
+        };
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &truncated, &violations)).unwrap();
+        assert_eq!(output["truncated"], true);
+
+        let skipped = ReportContext {
+            skipped: vec![SkippedViolation {
+                violation: violation("lib/a.ex", 3, Severity::Warning),
+                reason: SkipReason::InlineSuppression,
+            }],
+            ..Default::default()
+        };
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &skipped, &violations)).unwrap();
+        assert_eq!(output["skipped"][0]["line_number"], 3);
+        assert_eq!(output["skipped"][0]["reason"], "inline_suppression");
+        let mut summary = ReviewSummary::default();
+        summary.record_skip(SkipReason::OlderThan);
+        let summary = serde_json::to_value(JsonSummary::from(&summary)).unwrap();
+        assert_eq!(summary["skipped"]["older_than"], 1);
+
+        let empty: serde_json::Value =
+            serde_json::from_str(&render("json", &ReportContext::default(), &[])).unwrap();
+        assert_eq!(empty["violations"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_json_sampling_object() {
+        let context = ReportContext {
+            sampling: Some(SampleSummary {
+                per_rule: 2,
+                shown: 5,
+                total: 9,
+                omitted: vec![OmittedRule {
+                    rule_id: "noisy_warning".to_string(),
+                    severity: Severity::Warning,
+                    count: 3,
+                }],
+            }),
+            ..Default::default()
+        };
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &context, &[])).unwrap();
+
+        assert_eq!(output["sampling"]["total_violations"], 9);
+        assert_eq!(output["sampling"]["shown"], 5);
+        assert_eq!(output["sampling"]["omitted"]["noisy_warning"], 3);
+    }
+
+    #[test]
+    fn test_deprecation_notices() {
+        let context = ReportContext {
+            deprecations: vec![*find_deprecation("review", "--auto-fix").unwrap()],
+            ..Default::default()
+        };
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &context, &[])).unwrap();
+        assert_eq!(output["deprecations"][0]["flag"], "--auto-fix");
+        assert_eq!(output["deprecations"][0]["replacement"], "--fix");
+
+        let text = render("ndjson", &context, &[]);
+        let first: serde_json::Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(first["type"], "deprecation");
+        assert_eq!(first["removal_in"], "0.2.0");
+    }
+
+    #[test]
+    fn test_parse_warnings() {
+        let context = ReportContext {
+            parse_warnings: vec![ParseWarning {
+                path: Some("assets/logo.png".to_string()),
+                message: "binary file; not reviewed".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &context, &[])).unwrap();
+        assert_eq!(output["parse_warnings"][0]["path"], "assets/logo.png");
+        assert_eq!(output["parse_warnings"][0]["message"], "binary file; not reviewed");
+
+        let text = render("ndjson", &context, &[]);
+        let first: serde_json::Value = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(first["type"], "parse_warning");
+    }
+
+    #[test]
+    fn test_files_array() {
+        let context = ReportContext {
+            files: vec![
+                FileCoverage {
+                    path: "lib/user.ex".to_string(),
+                    language: Some(crate::core::Language::Elixir),
+                    status: FileStatus::Analyzed,
+                    violations: 0,
+                },
+                FileCoverage {
+                    path: "assets/logo.png".to_string(),
+                    language: None,
+                    status: FileStatus::Skipped(FileSkipReason::Unparsed),
+                    violations: 0,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &context, &[])).unwrap();
+        assert_eq!(output["files"][0]["status"], "analyzed");
+        assert_eq!(output["files"][0]["language"], "elixir");
+        assert_eq!(output["files"][0]["violations"], 0);
+        assert!(output["files"][0].get("reason").is_none());
+        assert_eq!(output["files"][1]["status"], "skipped");
+        assert_eq!(output["files"][1]["reason"], "unparsed");
+        assert!(output["files"][1]["language"].is_null());
+    }
+
+    #[test]
+    fn test_violations_name_their_rule_source() {
+        let violations = vec![violation("lib/user.ex", 42, Severity::Major)];
+        let mut context = ReportContext::default();
+        context.rule_sources.insert(&violations[0].rule, RuleSource::built_in("elixir"));
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &context, &violations)).unwrap();
+        let source = &output["violations"][0]["rule_source"];
+        assert_eq!(source["kind"], "built_in");
+        assert_eq!(source["pack"], "elixir");
+        assert_eq!(source["version"], env!("CARGO_PKG_VERSION"));
+
+        let text = render("ndjson", &context, &violations);
+        assert!(text.contains(r#""rule_source":{"kind":"built_in","pack":"elixir""#));
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &ReportContext::default(), &violations)).unwrap();
+        assert!(output["violations"][0].get("rule_source").is_none());
+    }
+
+    #[test]
+    fn test_blamed_violations_carry_their_commit() {
+        let mut blamed = violation("lib/user.ex", 42, Severity::Major);
+        blamed.blame = Some(LineBlame {
+            commit: "24ef6ea1c0ffee".to_string(),
+            author: "Ana".to_string(),
+            email: "ana@example.com".to_string(),
+            time: 1_700_000_000,
+        });
+        let violations = vec![blamed, violation("lib/user.ex", 43, Severity::Major)];
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &ReportContext::default(), &violations)).unwrap();
+        let blame = &output["violations"][0]["blame"];
+        assert_eq!(blame["commit"], "24ef6ea1c0ffee");
+        assert_eq!(blame["author"], "Ana");
+        assert_eq!(blame["time"], 1_700_000_000);
+        assert!(output["violations"][1].get("blame").is_none());
+    }
+
+    #[test]
+    fn test_violations_carry_their_owners() {
+        let mut owned = violation("lib/pay.ex", 3, Severity::Major);
+        owned.owners = vec!["@acme/payments".to_string(), "@jane".to_string()];
+        let mut also_owned = violation("lib/pay.ex", 9, Severity::Major);
+        also_owned.owners = vec!["@acme/payments".to_string()];
+        let violations = vec![owned, also_owned, violation("README.md", 1, Severity::Major)];
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &ReportContext::default(), &violations)).unwrap();
+        assert_eq!(
+            output["violations"][0]["owners"],
+            serde_json::json!(["@acme/payments", "@jane"])
+        );
+        assert!(output["violations"][2].get("owners").is_none());
+        assert_eq!(output["by_owner"], serde_json::json!({"@acme/payments": 2, "@jane": 1}));
+    }
+
+    #[test]
+    fn test_violations_carry_match_columns() {
+        let mut matched = violation("lib/user.ex", 42, Severity::Major);
+        matched.content = "  \"ü\" |> String.to_atom()".to_string();
+        let start = matched.content.find("String").unwrap();
+        matched.span = Some(start..start + "String.to_atom".len());
+        let violations = vec![matched, violation("lib/user.ex", 43, Severity::Major)];
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &ReportContext::default(), &violations)).unwrap();
+        assert_eq!(output["violations"][0]["column"], 10);
+        assert_eq!(output["violations"][0]["end_column"], 24);
+        assert!(output["violations"][1].get("column").is_none());
+
+        let context = ReportContext { column_unit: ColumnUnit::Utf8, ..ReportContext::default() };
+        let text = render("ndjson", &context, &violations);
+        assert!(text.contains(r#""column":11,"end_column":25"#));
+    }
+
+    #[test]
+    fn test_violations_carry_their_captures() {
+        let mut matched = violation("lib/user.ex", 42, Severity::Major);
+        matched.captures.insert("1".to_string(), "params".to_string());
+        let violations = vec![matched, violation("lib/user.ex", 43, Severity::Major)];
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &ReportContext::default(), &violations)).unwrap();
+        assert_eq!(output["violations"][0]["captures"]["1"], "params");
+        assert!(output["violations"][1].get("captures").is_none());
+    }
+
+    #[test]
+    fn test_acknowledged_array() {
+        let context = ReportContext {
+            acknowledged: vec![AcknowledgedViolation {
+                violation: violation("lib/a.ex", 3, Severity::Major),
+                risk: crate::core::baseline::AcceptedRisk {
+                    reason: "Internal tool only".to_string(),
+                    author: "Jane <jane@example.com>".to_string(),
+                    date: "2024-05-01".to_string(),
+                },
+            }],
+            ..Default::default()
+        };
+
+        let output: serde_json::Value =
+            serde_json::from_str(&render("json", &context, &[])).unwrap();
+        let acknowledged = &output["acknowledged"][0];
+        assert_eq!(acknowledged["file_path"], "lib/a.ex");
+        assert_eq!(acknowledged["reason"], "Internal tool only");
+        assert_eq!(acknowledged["accepted_by"], "Jane <jane@example.com>");
+        assert_eq!(acknowledged["accepted_on"], "2024-05-01");
+
+        let empty: serde_json::Value =
+            serde_json::from_str(&render("json", &ReportContext::default(), &[])).unwrap();
+        assert!(empty.get("acknowledged").is_none());
+    }
+
+    #[test]
+    fn test_ndjson_records_are_tagged_single_lines() {
+        let violations = vec![violation("lib/user.ex", 42, Severity::Major)];
+        let text = render("ndjson", &ReportContext::default(), &violations);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["type"], "violation");
+        assert_eq!(first["rule_id"], "test_rule");
+        assert_eq!(first["line_number"], 42);
+
+        let last: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(last["type"], "summary");
+        assert_eq!(last["total_violations"], 1);
+    }
+
+    #[test]
+    fn test_streamed_file_records() {
+        let buffer = SharedBuffer::default();
+        let mut reporter =
+            ReporterRegistry::new().create("jsonl", Box::new(buffer.clone())).unwrap();
+        reporter.on_start(&ReportContext::default()).unwrap();
+        reporter.on_violation(&violation("lib/user.ex", 42, Severity::Major)).unwrap();
+        let progress = |path: &str, violations, files_reviewed| FileProgress {
+            path: path.to_string(),
+            violations,
+            files_reviewed,
+            files_total: 2,
+        };
+        reporter.on_file(&progress("lib/user.ex", 1, 1)).unwrap();
+        reporter.on_file(&progress("lib/auth.ex", 0, 2)).unwrap();
+        reporter.on_summary(&ReviewSummary::default()).unwrap();
+
+        let records: Vec<serde_json::Value> =
+            buffer.contents().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        let types: Vec<&str> = records.iter().map(|r| r["type"].as_str().unwrap()).collect();
+        assert_eq!(types, ["violation", "file", "file", "summary"]);
+        assert_eq!(records[1]["path"], "lib/user.ex");
+        assert_eq!(records[1]["violations"], 1);
+        assert_eq!(records[2]["files_reviewed"], 2);
+        assert_eq!(records[2]["files_total"], 2);
+    }
+}
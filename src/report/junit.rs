@@ -0,0 +1,129 @@
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::io::Write;
+
+use super::html::escape_html as escape_xml;
+use super::{ReportContext, Reporter};
+use crate::core::coverage::{FileCoverage, FileStatus};
+use crate::core::review_engine::ReviewSummary;
+use crate::core::ReviewViolation;
+
+/// JUnit XML, which most CI systems show in a test report tab: a failing test case per
+/// violation, a passing one per clean file and a skipped one per file that was not reviewed
+pub struct JunitReporter {
+    out: Box<dyn Write>,
+    files: Vec<FileCoverage>,
+    violations: Vec<ReviewViolation>,
+}
+
+impl JunitReporter {
+    pub fn new(out: Box<dyn Write>) -> Self {
+        Self { out, files: Vec::new(), violations: Vec::new() }
+    }
+}
+
+impl Reporter for JunitReporter {
+    fn on_start(&mut self, context: &ReportContext) -> Result<()> {
+        self.files = context.files.clone();
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &ReviewViolation) -> Result<()> {
+        self.violations.push(violation.clone());
+        Ok(())
+    }
+
+    fn on_summary(&mut self, _summary: &ReviewSummary) -> Result<()> {
+        write!(self.out, "{}", render_junit(&self.violations, &self.files))?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Test cases are named after the file and classed by the rule, so CI test tabs group
+/// findings by file
+pub fn render_junit(violations: &[ReviewViolation], files: &[FileCoverage]) -> String {
+    let mut cases = String::new();
+    for violation in violations {
+        let _ = write!(
+            cases,
+            "    <testcase classname=\"{}\" name=\"{}:{}\">\n      \
+             <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>\n",
+            escape_xml(&violation.rule.id),
+            escape_xml(&violation.file_path),
+            violation.line_number,
+            violation.severity,
+            escape_xml(&violation.rule.name),
+            escape_xml(&format!(
+                "Line {}: {}\nFix: {}",
+                violation.line_number,
+                violation.content.trim(),
+                violation.fix_suggestion
+            )),
+        );
+    }
+
+    let mut skipped = 0;
+    for file in files.iter().filter(|file| file.violations == 0) {
+        let _ = write!(
+            cases,
+            "    <testcase classname=\"patingin\" name=\"{}\"",
+            escape_xml(&file.path)
+        );
+        match file.status {
+            FileStatus::Analyzed | FileStatus::PartiallyAnalyzed => cases.push_str("/>\n"),
+            FileStatus::Skipped(reason) => {
+                skipped += 1;
+                let _ = write!(
+                    cases,
+                    ">\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                    escape_xml(&reason.to_string())
+                );
+            }
+        }
+    }
+
+    let tests = violations.len() + files.iter().filter(|file| file.violations == 0).count();
+    let counts = format!(
+        "tests=\"{tests}\" failures=\"{}\" errors=\"0\" skipped=\"{skipped}\"",
+        violations.len()
+    );
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"patingin\" {counts}>\n  \
+         <testsuite name=\"patingin\" {counts}>\n{cases}  </testsuite>\n</testsuites>\n"
+    )
+}
+
+#[cfg(test)]
+mod junit_tests {
+    use super::*;
+    use crate::core::coverage::FileSkipReason;
+    use crate::core::Severity;
+    use crate::report::test_support::violation;
+
+    fn coverage(path: &str, status: FileStatus, violations: usize) -> FileCoverage {
+        FileCoverage { path: path.to_string(), language: None, status, violations }
+    }
+
+    #[test]
+    fn test_violations_fail_and_clean_files_pass() {
+        let mut found = violation("lib/a&b.ex", 7, Severity::Critical);
+        found.content = "  x < y".to_string();
+        let files = [
+            coverage("lib/a&b.ex", FileStatus::Analyzed, 1),
+            coverage("lib/clean.ex", FileStatus::Analyzed, 0),
+            coverage("logo.png", FileStatus::Skipped(FileSkipReason::Binary), 0),
+        ];
+
+        let xml = render_junit(&[found], &files);
+
+        assert!(xml.contains(
+            "<testsuite name=\"patingin\" tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\">"
+        ));
+        assert!(xml.contains("name=\"lib/a&amp;b.ex:7\">\n      <failure type=\"critical\""));
+        assert!(xml.contains(">Line 7: x &lt; y\nFix: "), "{xml}");
+        assert!(xml.contains("<testcase classname=\"patingin\" name=\"lib/clean.ex\"/>"));
+        assert!(xml.contains("<skipped message=\"binary file\"/>"));
+        assert_eq!(xml.matches("<testcase").count(), 3);
+    }
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use super::{severity_breakdown, ReportContext, Reporter, SampleSummary};
+use crate::core::large_hunks::PartialHunk;
+use crate::core::review_engine::ReviewSummary;
+use crate::core::{ReviewViolation, Severity};
+
+/// Markdown tables grouped by file, suitable for pull request comments
+pub struct MarkdownReporter {
+    out: Box<dyn Write>,
+    context: ReportContext,
+    violations: Vec<ReviewViolation>,
+}
+
+impl MarkdownReporter {
+    pub fn new(out: Box<dyn Write>) -> Self {
+        Self { out, context: ReportContext::default(), violations: Vec::new() }
+    }
+}
+
+impl Reporter for MarkdownReporter {
+    fn on_start(&mut self, context: &ReportContext) -> Result<()> {
+        self.context = context.clone();
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &ReviewViolation) -> Result<()> {
+        self.violations.push(violation.clone());
+        Ok(())
+    }
+
+    fn on_summary(&mut self, _summary: &ReviewSummary) -> Result<()> {
+        write!(self.out, "{}", render_markdown(&self.violations, &self.context.scope))?;
+        if let Some(sample) = &self.context.sampling {
+            write!(self.out, "{}", render_sample_note(sample))?;
+        }
+        if let Some(truncation) = self.context.truncation {
+            writeln!(
+                self.out,
+                "\n✂️ Showing the first {} of {} violations; {} more not shown.",
+                truncation.limit,
+                truncation.total,
+                truncation.total - truncation.limit
+            )?;
+        }
+        write!(self.out, "{}", render_partial_hunks_note(&self.context.partial_hunks))?;
+        if let Some(mentions) = self.context.mentions.render(&self.violations) {
+            writeln!(self.out, "\n{mentions}")?;
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Render violations as a markdown report headed by the reviewed scope
+pub fn render_markdown(violations: &[ReviewViolation], scope: &str) -> String {
+    let mut report = format!("## 🔍 patingin review: {scope}\n\n");
+
+    if violations.is_empty() {
+        report.push_str("✅ No anti-pattern violations found!\n");
+        return report;
+    }
+
+    let mut violations_by_file: BTreeMap<&str, Vec<&ReviewViolation>> = BTreeMap::new();
+    for violation in violations {
+        violations_by_file.entry(&violation.file_path).or_default().push(violation);
+    }
+
+    let count = |severity: Severity| violations.iter().filter(|v| v.severity == severity).count();
+    report.push_str(&format!(
+        "**{} violations** in {} files ({})\n",
+        violations.len(),
+        violations_by_file.len(),
+        severity_breakdown(count, true)
+    ));
+
+    for (file_path, file_violations) in violations_by_file {
+        report.push_str(&format!("\n### `{file_path}`\n\n"));
+        report.push_str("| Line | Severity | Rule | Fix |\n|---:|---|---|---|\n");
+        for violation in file_violations {
+            let severity_icon = violation.severity.icon();
+            report.push_str(&format!(
+                "| {} | {} {} | {} (`{}`) | {} |\n",
+                violation.line_number,
+                severity_icon,
+                violation.severity,
+                escape_markdown_cell(&violation.rule.name),
+                violation.rule.id,
+                escape_markdown_cell(&violation.fix_suggestion)
+            ));
+        }
+    }
+
+    report
+}
+
+pub fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn render_sample_note(sample: &SampleSummary) -> String {
+    if sample.omitted.is_empty() {
+        return String::new();
+    }
+    let mut note = format!(
+        "\n<details><summary>🔎 Sampled {} per rule: {} of {} violations shown</summary>\n\n",
+        sample.per_rule, sample.shown, sample.total
+    );
+    for omitted in &sample.omitted {
+        note.push_str(&format!(
+            "- `{}` ({}): {} more\n",
+            omitted.rule_id, omitted.severity, omitted.count
+        ));
+    }
+    note.push_str("\n</details>\n");
+    note
+}
+
+fn render_partial_hunks_note(partial_hunks: &[PartialHunk]) -> String {
+    if partial_hunks.is_empty() {
+        return String::new();
+    }
+    let mut note =
+        "\n⏩ **Partially analyzed** (large hunks, critical rules only):\n\n".to_string();
+    for hunk in partial_hunks {
+        note.push_str(&format!(
+            "- `{}` lines {}-{}: {} of {} added lines checked\n",
+            hunk.file_path, hunk.first_line, hunk.last_line, hunk.checked_lines, hunk.added_lines
+        ));
+    }
+    note
+}
+
+#[cfg(test)]
+mod markdown_tests {
+    use super::super::test_support::{render, violation};
+    use super::*;
+
+    #[test]
+    fn test_render_markdown_results() {
+        let report =
+            render_markdown(&[violation("test.ex", 42, Severity::Major)], "staged changes");
+
+        assert!(report.starts_with("## 🔍 patingin review: staged changes"));
+        assert!(report.contains("### `test.ex`"));
+        assert!(report.contains("| 42 | 🟡 major | Test Rule (`test_rule`) | Use a \\| b |"));
+
+        let empty = render_markdown(&[], "staged changes");
+        assert!(empty.contains("No anti-pattern violations found"));
+    }
+
+    #[test]
+    fn test_markdown_reporter_uses_context_scope() {
+        let context = ReportContext { scope: "main..feature".to_string(), ..Default::default() };
+        let report = render("markdown", &context, &[violation("test.ex", 1, Severity::Critical)]);
+        assert!(report.starts_with("## 🔍 patingin review: main..feature"));
+        assert!(report.contains("🔴 1 critical"));
+        assert!(!report.contains("👥"));
+
+        let context = ReportContext {
+            mentions: serde_yaml::from_str("severities:\n  critical: ['@leads']\n").unwrap(),
+            ..context
+        };
+        let report = render("markdown", &context, &[violation("test.ex", 1, Severity::Critical)]);
+        assert!(report.ends_with("\n👥 cc @leads (critical)\n"));
+    }
+}
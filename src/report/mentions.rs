@@ -0,0 +1,148 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::find_project_config;
+use crate::core::{ProjectDetector, ReviewViolation, Severity};
+
+/// The `mentions:` section of the project config: who markdown reports and pull request
+/// comments pull in when violations at or above a severity, or of a rule tag, are found.
+///
+/// ```yaml
+/// mentions:
+///   severities:
+///     critical: ["@acme/leads"]
+///   tags:
+///     security: ["@acme/security-team"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct MentionsConfig {
+    #[serde(default)]
+    pub severities: BTreeMap<Severity, Vec<String>>,
+    #[serde(default)]
+    pub tags: BTreeMap<String, Vec<String>>,
+}
+
+/// A handle to mention and the severities or tags that triggered it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mention {
+    pub handle: String,
+    pub reasons: Vec<String>,
+}
+
+impl MentionsConfig {
+    /// Read the `mentions:` section of a project config file; absent means no mentions
+    pub fn load(config_path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct MentionsSection {
+            #[serde(default)]
+            mentions: MentionsConfig,
+        }
+
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+        let section: Option<MentionsSection> = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+        Ok(section.map(|section| section.mentions).unwrap_or_default())
+    }
+
+    /// The detected project's mentions, or none outside a configured project
+    pub fn load_for_project() -> Result<Self> {
+        let Ok(project_info) = ProjectDetector::detect_project(None) else {
+            return Ok(Self::default());
+        };
+        match find_project_config(&project_info.root_path) {
+            Some(config_path) => Self::load(&config_path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Handles triggered by `violations`, severities (most severe first) before tags, each
+    /// listed once
+    pub fn mentions(&self, violations: &[ReviewViolation]) -> Vec<Mention> {
+        let mut mentions: Vec<Mention> = Vec::new();
+        let mut add = |handles: &[String], reason: String| {
+            for handle in handles {
+                match mentions.iter_mut().find(|mention| &mention.handle == handle) {
+                    Some(mention) if mention.reasons.contains(&reason) => {}
+                    Some(mention) => mention.reasons.push(reason.clone()),
+                    None => mentions
+                        .push(Mention { handle: handle.clone(), reasons: vec![reason.clone()] }),
+                }
+            }
+        };
+
+        for (severity, handles) in &self.severities {
+            if violations.iter().any(|v| v.severity.is_at_least(*severity)) {
+                add(handles, severity.to_string());
+            }
+        }
+        for (tag, handles) in &self.tags {
+            if violations.iter().any(|v| v.rule.tags.contains(tag)) {
+                add(handles, tag.clone());
+            }
+        }
+        mentions
+    }
+
+    /// Markdown line mentioning everyone `violations` trigger, e.g.
+    /// "👥 cc @acme/security-team (security), @acme/leads (critical)"
+    pub fn render(&self, violations: &[ReviewViolation]) -> Option<String> {
+        let mentions = self.mentions(violations);
+        if mentions.is_empty() {
+            return None;
+        }
+        let listed: Vec<String> = mentions
+            .iter()
+            .map(|mention| format!("{} ({})", mention.handle, mention.reasons.join(", ")))
+            .collect();
+        Some(format!("👥 cc {}", listed.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod mentions_tests {
+    use super::*;
+    use crate::report::test_support::violation;
+
+    fn config() -> MentionsConfig {
+        serde_yaml::from_str(
+            "severities:\n  major: ['@leads']\ntags:\n  security: ['@security', '@leads']\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mentions_follow_severity_and_tags() {
+        let mut critical = violation("lib/a.ex", 1, Severity::Critical);
+        critical.rule.tags = vec!["security".to_string()];
+
+        assert_eq!(
+            config().render(&[critical]).unwrap(),
+            "👥 cc @leads (major, security), @security (security)"
+        );
+        assert_eq!(
+            config().render(&[violation("a.ex", 1, Severity::Major)]).unwrap(),
+            "👥 cc @leads (major)"
+        );
+        assert!(config().render(&[violation("a.ex", 1, Severity::Warning)]).is_none());
+        assert!(MentionsConfig::default()
+            .render(&[violation("a.ex", 1, Severity::Critical)])
+            .is_none());
+    }
+
+    #[test]
+    fn test_load_mentions_section() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("patingin.yml");
+
+        std::fs::write(&config_path, "mentions:\n  tags:\n    security: ['@security']\n").unwrap();
+        let loaded = MentionsConfig::load(&config_path).unwrap();
+        assert_eq!(loaded.tags["security"], ["@security"]);
+        assert!(loaded.severities.is_empty());
+
+        std::fs::write(&config_path, "mentions:\n  severities:\n    urgent: ['@x']\n").unwrap();
+        assert!(MentionsConfig::load(&config_path).is_err());
+    }
+}
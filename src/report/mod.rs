@@ -0,0 +1,449 @@
+//! Output of review results.
+//!
+//! Every `--format` is backed by a [`Reporter`] looked up by name in a [`ReporterRegistry`].
+//! Binaries embedding patingin can register their own reporters next to the built-in ones
+//! and hand the registry to `cli::commands::review::run_with_reporters`:
+//!
+//! ```no_run
+//! use patingin::report::{ReportContext, Reporter, ReporterRegistry};
+//! use patingin::core::review_engine::ReviewSummary;
+//! use patingin::ReviewViolation;
+//! use std::io::Write;
+//!
+//! struct CountReporter(Box<dyn Write>, usize);
+//!
+//! impl Reporter for CountReporter {
+//!     fn on_violation(&mut self, _violation: &ReviewViolation) -> anyhow::Result<()> {
+//!         self.1 += 1;
+//!         Ok(())
+//!     }
+//!
+//!     fn on_summary(&mut self, _summary: &ReviewSummary) -> anyhow::Result<()> {
+//!         writeln!(self.0, "{} violations", self.1)?;
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut registry = ReporterRegistry::new();
+//! registry.register("count", "Number of violations", |out| Box::new(CountReporter(out, 0)));
+//! ```
+
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use crate::config::deprecations::Deprecation;
+use crate::core::baseline::AcknowledgedViolation;
+use crate::core::coverage::FileCoverage;
+use crate::core::grouping::GroupBy;
+use crate::core::large_hunks::PartialHunk;
+use crate::core::provenance::RuleSources;
+use crate::core::review_engine::{ReviewSummary, SkippedViolation};
+use crate::core::{ReviewViolation, Severity};
+use crate::git::ParseWarning;
+use accessibility::Accessibility;
+use mentions::MentionsConfig;
+use positions::ColumnUnit;
+
+pub mod accessibility;
+pub mod html;
+pub mod human;
+pub mod json;
+pub mod junit;
+pub mod markdown;
+pub mod mentions;
+pub mod positions;
+pub mod sarif;
+
+/// Receives the results of one review run.
+///
+/// `on_start` is called once, then `on_violation` for every reported violation in order,
+/// then `on_summary` once. Reporters that need the full set before writing anything (tables,
+/// grouped output, SARIF documents) buffer violations and render in `on_summary`. With
+/// `review --stream`, streaming reporters also get `on_file` after each file's violations.
+pub trait Reporter {
+    fn on_start(&mut self, _context: &ReportContext) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &ReviewViolation) -> Result<()>;
+
+    /// A file of the diff has been analyzed and its violations reported
+    fn on_file(&mut self, _progress: &FileProgress) -> Result<()> {
+        Ok(())
+    }
+
+    fn on_summary(&mut self, summary: &ReviewSummary) -> Result<()>;
+
+    /// Streaming reporters receive violations while the diff is still being reviewed.
+    /// They get no sampling information and the summary counts every violation found.
+    fn is_streaming(&self) -> bool {
+        false
+    }
+}
+
+/// How far a streamed review is, passed to `Reporter::on_file`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileProgress {
+    pub path: String,
+    /// Violations reported for the file
+    pub violations: usize,
+    /// Files analyzed so far, this one included
+    pub files_reviewed: usize,
+    pub files_total: usize,
+}
+
+/// What was reviewed and how, passed to `Reporter::on_start`
+#[derive(Debug, Clone, Default)]
+pub struct ReportContext {
+    /// Human description of the reviewed changes, e.g. "staged changes"
+    pub scope: String,
+    /// Whether fix suggestions were requested (`--suggest`, `--auto-fix`)
+    pub show_auto_fixable: bool,
+    /// Whether any fix mode is active, so hints about `--suggest`/`--fix` are redundant
+    pub fix_requested: bool,
+    /// Set when `--sample` trimmed the reported violations
+    pub sampling: Option<SampleSummary>,
+    /// Set when `output.max_reported_violations` cut the report short
+    pub truncation: Option<Truncation>,
+    /// Deprecated flags used for this run, so machine-readable output can flag them
+    pub deprecations: Vec<Deprecation>,
+    /// How the human report conveys severity (`output.accessibility`)
+    pub accessibility: Accessibility,
+    /// Oversized hunks that were only sampled against critical rules
+    pub partial_hunks: Vec<PartialHunk>,
+    /// Violations of the previous run on this branch and scope that are gone now; `None`
+    /// without an earlier run or with `--no-state`
+    pub fixed_since_last_run: Option<usize>,
+    /// Who markdown output mentions for the violations found (`mentions:`)
+    pub mentions: MentionsConfig,
+    /// Diff sections that could not be reviewed
+    pub parse_warnings: Vec<ParseWarning>,
+    /// Findings that were not reported, listed with `--show-skipped`; the summary counts
+    /// them either way
+    pub skipped: Vec<SkippedViolation>,
+    /// Every file of the diff and whether it was analyzed
+    pub files: Vec<FileCoverage>,
+    /// Violations triaged as accepted risks, listed instead of reported
+    pub acknowledged: Vec<AcknowledgedViolation>,
+    /// Where each rule came from; JSON output names it for every violation
+    pub rule_sources: RuleSources,
+    /// `--verbose`: human output adds each violation's rule source
+    pub verbose: bool,
+    /// What the columns of machine-readable output count (`output.column_unit`)
+    pub column_unit: ColumnUnit,
+    /// How the human report arranges violations (`--group-by`)
+    pub group_by: GroupBy,
+}
+
+/// Findings hidden by `--sample N-per-rule`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleSummary {
+    pub per_rule: usize,
+    pub shown: usize,
+    pub total: usize,
+    /// Rules with findings that were not shown, most severe first
+    pub omitted: Vec<OmittedRule>,
+}
+
+/// Violations left out of a report longer than `output.max_reported_violations`; the
+/// summary still counts them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Truncation {
+    pub limit: usize,
+    /// Violations there were to report before the cut
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OmittedRule {
+    pub rule_id: String,
+    pub severity: Severity,
+    pub count: usize,
+}
+
+/// Builds a reporter writing to the given output
+pub type ReporterFactory = Box<dyn Fn(Box<dyn Write>) -> Box<dyn Reporter>>;
+
+struct RegisteredReporter {
+    description: String,
+    factory: ReporterFactory,
+}
+
+/// Reporters available to `--format`, keyed by name
+pub struct ReporterRegistry {
+    reporters: BTreeMap<String, RegisteredReporter>,
+}
+
+impl Default for ReporterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReporterRegistry {
+    /// Registry with the built-in human, json, ndjson (also `jsonl`), markdown, sarif, junit
+    /// and html reporters
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register("human", "Colored terminal output grouped by file", |out| {
+                Box::new(human::HumanReporter::new(out))
+            })
+            .register("json", "One JSON document with violations and summary", |out| {
+                Box::new(json::JsonReporter::new(out))
+            })
+            .register("ndjson", "One JSON object per line, streamed as files are reviewed", |out| {
+                Box::new(json::NdjsonReporter::new(out))
+            })
+            .register("jsonl", "Same as ndjson", |out| Box::new(json::NdjsonReporter::new(out)))
+            .register("markdown", "Markdown tables for pull request comments", |out| {
+                Box::new(markdown::MarkdownReporter::new(out))
+            })
+            .register("sarif", "SARIF 2.1.0 for code scanning dashboards", |out| {
+                Box::new(sarif::SarifReporter::new(out))
+            })
+            .register("junit", "JUnit XML for CI test report tabs", |out| {
+                Box::new(junit::JunitReporter::new(out))
+            })
+            .register("html", "Standalone HTML page with charts, filters and snippets", |out| {
+                Box::new(html::HtmlReporter::new(out))
+            });
+        registry
+    }
+
+    pub fn empty() -> Self {
+        Self { reporters: BTreeMap::new() }
+    }
+
+    /// Add a reporter, replacing any existing one with the same name
+    pub fn register<F>(&mut self, name: &str, description: &str, factory: F) -> &mut Self
+    where
+        F: Fn(Box<dyn Write>) -> Box<dyn Reporter> + 'static,
+    {
+        self.reporters.insert(
+            name.to_string(),
+            RegisteredReporter { description: description.to_string(), factory: Box::new(factory) },
+        );
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.reporters.contains_key(name)
+    }
+
+    /// Registered names with their descriptions, alphabetically
+    pub fn list(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.reporters.iter().map(|(name, reporter)| (name.as_str(), reporter.description.as_str()))
+    }
+
+    pub fn create(&self, name: &str, out: Box<dyn Write>) -> Result<Box<dyn Reporter>> {
+        let reporter = self.reporters.get(name).ok_or_else(|| {
+            let available: Vec<&str> = self.reporters.keys().map(String::as_str).collect();
+            anyhow!("Unknown output format '{name}'; available formats: {}", available.join(", "))
+        })?;
+        Ok((reporter.factory)(out))
+    }
+}
+
+/// Levels a summary lists: critical, major and warning always, blocker and info only when
+/// `count` finds some, so reports without them read as they always have
+pub fn summary_severities(count: impl Fn(Severity) -> usize) -> Vec<Severity> {
+    Severity::ALL
+        .into_iter()
+        .filter(|severity| {
+            matches!(severity, Severity::Critical | Severity::Major | Severity::Warning)
+                || count(*severity) > 0
+        })
+        .collect()
+}
+
+/// "2 critical, 0 major, 1 warning", or "🔴 2 critical, ..." with `icons`
+pub fn severity_breakdown(count: impl Fn(Severity) -> usize, icons: bool) -> String {
+    summary_severities(&count)
+        .into_iter()
+        .map(|severity| match icons {
+            true => format!("{} {} {severity}", severity.icon(), count(severity)),
+            false => format!("{} {severity}", count(severity)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Feed a complete, already collected set of results through a reporter
+pub fn report(
+    reporter: &mut dyn Reporter,
+    context: &ReportContext,
+    violations: &[ReviewViolation],
+    summary: &ReviewSummary,
+) -> Result<()> {
+    reporter.on_start(context)?;
+    for violation in violations {
+        reporter.on_violation(violation)?;
+    }
+    reporter.on_summary(summary)
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::test_support::{violation, SharedBuffer};
+    use super::*;
+
+    struct CountReporter {
+        out: Box<dyn Write>,
+        count: usize,
+    }
+
+    impl Reporter for CountReporter {
+        fn on_violation(&mut self, _violation: &ReviewViolation) -> Result<()> {
+            self.count += 1;
+            Ok(())
+        }
+
+        fn on_summary(&mut self, _summary: &ReviewSummary) -> Result<()> {
+            writeln!(self.out, "count={}", self.count)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_builtin_reporters_are_registered() {
+        let registry = ReporterRegistry::new();
+        let names: Vec<&str> = registry.list().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            ["html", "human", "json", "jsonl", "junit", "markdown", "ndjson", "sarif"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_format_lists_available() {
+        let error = ReporterRegistry::new()
+            .create("xml", Box::new(std::io::sink()))
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("Unknown output format 'xml'"));
+        assert!(error.contains("human, json, jsonl, junit, markdown, ndjson, sarif"));
+    }
+
+    #[test]
+    fn test_custom_reporter_receives_results() {
+        let mut registry = ReporterRegistry::new();
+        registry
+            .register("count", "Violation count", |out| Box::new(CountReporter { out, count: 0 }));
+        let buffer = SharedBuffer::default();
+
+        let mut reporter = registry.create("count", Box::new(buffer.clone())).unwrap();
+        let violations = vec![violation("a.ex", 1, Severity::Major)];
+        report(
+            reporter.as_mut(),
+            &ReportContext::default(),
+            &violations,
+            &ReviewSummary::default(),
+        )
+        .unwrap();
+
+        assert_eq!(buffer.contents(), "count=1\n");
+    }
+
+    #[test]
+    fn test_severity_breakdown_lists_blocker_and_info_only_when_present() {
+        let violations = [violation("a.ex", 1, Severity::Major)];
+        let count = |severity| violations.iter().filter(|v| v.severity == severity).count();
+        assert_eq!(severity_breakdown(count, false), "0 critical, 1 major, 0 warning");
+
+        let violations =
+            [violation("a.ex", 1, Severity::Blocker), violation("a.ex", 2, Severity::Info)];
+        let count = |severity| violations.iter().filter(|v| v.severity == severity).count();
+        assert_eq!(
+            severity_breakdown(count, true),
+            "⛔ 1 blocker, 🔴 0 critical, 🟡 0 major, 🔵 0 warning, ⚪ 1 info"
+        );
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use crate::core::{AntiPattern, DetectionMethod, Language};
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// `Write` handle whose contents stay readable after the reporter consumed it
+    #[derive(Clone, Default)]
+    pub(crate) struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+    impl SharedBuffer {
+        pub(crate) fn contents(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn violation(
+        file_path: &str,
+        line_number: usize,
+        severity: Severity,
+    ) -> ReviewViolation {
+        ReviewViolation {
+            rule: AntiPattern {
+                id: "test_rule".to_string(),
+                name: "Test Rule".to_string(),
+                language: Language::Elixir,
+                severity,
+                description: "Test description".to_string(),
+                detection_method: DetectionMethod::Regex { pattern: "test".to_string() },
+                fix_suggestion: "Fix this test issue".to_string(),
+                source_url: Some("https://example.com/test_rule".to_string()),
+                claude_code_fixable: true,
+                examples: vec![],
+                tags: vec![],
+                ai_hint: None,
+                auto_fix: None,
+                skip_comments: false,
+                skip_strings: false,
+                enabled: true,
+            },
+            file_path: file_path.to_string(),
+            line_number,
+            content: "test_content()".to_string(),
+            severity,
+            language: Language::Elixir,
+            fix_suggestion: "Use a | b".to_string(),
+            auto_fixable: true,
+            context_before: vec![],
+            context_after: vec![],
+            confidence: 0.85,
+            span: None,
+            blame: None,
+            captures: BTreeMap::new(),
+            owners: Vec::new(),
+        }
+    }
+
+    /// Run a reporter from the registry over `violations` and return what it wrote
+    pub(crate) fn render(
+        format: &str,
+        context: &ReportContext,
+        violations: &[ReviewViolation],
+    ) -> String {
+        let buffer = SharedBuffer::default();
+        let mut reporter =
+            ReporterRegistry::new().create(format, Box::new(buffer.clone())).unwrap();
+        let mut summary = ReviewSummary::default();
+        violations.iter().for_each(|v| summary.record(v));
+        report(reporter.as_mut(), context, violations, &summary).unwrap();
+        buffer.contents()
+    }
+}
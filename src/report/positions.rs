@@ -0,0 +1,91 @@
+use serde::Deserialize;
+use std::ops::Range;
+
+use crate::core::ReviewViolation;
+
+/// What a column counts. Rules match on UTF-8 bytes, but editors and report formats count
+/// differently, so a byte offset must be converted before it is emitted. A tab is one unit
+/// in every kind; expanding it is the viewer's business.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColumnUnit {
+    /// UTF-16 code units: the SARIF default and the LSP default position encoding, also
+    /// used by VS Code and JetBrains editors
+    #[default]
+    #[serde(rename = "utf-16")]
+    Utf16,
+    /// Bytes, as Vim, Emacs and most compilers count
+    #[serde(rename = "utf-8")]
+    Utf8,
+    /// Unicode code points (SARIF `unicodeCodePoints`, LSP `utf-32`)
+    CodePoints,
+}
+
+/// 0-based column of `byte_offset` in `line`. An offset inside a multibyte character maps
+/// to that character's start, and one past the end to the end of the line.
+pub fn column(line: &str, byte_offset: usize, unit: ColumnUnit) -> usize {
+    let mut offset = byte_offset.min(line.len());
+    while !line.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let prefix = &line[..offset];
+    match unit {
+        ColumnUnit::Utf16 => prefix.encode_utf16().count(),
+        ColumnUnit::Utf8 => prefix.len(),
+        ColumnUnit::CodePoints => prefix.chars().count(),
+    }
+}
+
+/// 1-based, end-exclusive columns of the text a violation matched, as report formats and
+/// editor links number them; `None` when the rule did not match a specific part of the line
+pub fn violation_columns(violation: &ReviewViolation, unit: ColumnUnit) -> Option<Range<usize>> {
+    let span = violation.span.as_ref()?;
+    let start = column(&violation.content, span.start, unit) + 1;
+    let end = column(&violation.content, span.end, unit) + 1;
+    Some(start..end)
+}
+
+#[cfg(test)]
+mod positions_tests {
+    use super::*;
+    use crate::core::Severity;
+
+    #[test]
+    fn test_columns_count_in_the_requested_unit() {
+        // "é" is 2 bytes and 1 UTF-16 unit, "😀" is 4 bytes and 2 UTF-16 units
+        let line = "\tname = \"é😀\" <> String.to_atom(x)";
+        let offset = line.find("String").unwrap();
+        assert_eq!(offset, 20);
+        assert_eq!(column(line, offset, ColumnUnit::Utf8), 20);
+        assert_eq!(column(line, offset, ColumnUnit::Utf16), 17);
+        assert_eq!(column(line, offset, ColumnUnit::CodePoints), 16);
+
+        // Mid-character and out-of-range offsets are clamped
+        let emoji = line.find('😀').unwrap();
+        assert_eq!(
+            column(line, emoji + 2, ColumnUnit::Utf16),
+            column(line, emoji, ColumnUnit::Utf16)
+        );
+        assert_eq!(column(line, 500, ColumnUnit::CodePoints), line.chars().count());
+    }
+
+    #[test]
+    fn test_violation_columns_are_one_based() {
+        let mut violation = crate::report::test_support::violation("lib/a.ex", 3, Severity::Major);
+        assert_eq!(violation_columns(&violation, ColumnUnit::Utf16), None);
+
+        violation.content = "  \"ü\" |> String.to_atom()".to_string();
+        let start = violation.content.find("String").unwrap();
+        violation.span = Some(start..start + "String.to_atom".len());
+        assert_eq!(violation_columns(&violation, ColumnUnit::Utf16), Some(10..24));
+        assert_eq!(violation_columns(&violation, ColumnUnit::Utf8), Some(11..25));
+    }
+
+    #[test]
+    fn test_column_unit_names() {
+        let unit = |name: &str| serde_yaml::from_str::<ColumnUnit>(name).unwrap();
+        assert_eq!(unit("utf-16"), ColumnUnit::Utf16);
+        assert_eq!(unit("utf-8"), ColumnUnit::Utf8);
+        assert_eq!(unit("code-points"), ColumnUnit::CodePoints);
+    }
+}
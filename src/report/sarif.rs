@@ -0,0 +1,211 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::io::Write;
+
+use super::positions::{violation_columns, ColumnUnit};
+use super::{ReportContext, Reporter};
+use crate::core::review_engine::ReviewSummary;
+use crate::core::{ReviewViolation, Severity};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF 2.1.0 log for GitHub code scanning and other static analysis dashboards
+pub struct SarifReporter {
+    out: Box<dyn Write>,
+    violations: Vec<ReviewViolation>,
+    column_unit: ColumnUnit,
+}
+
+impl SarifReporter {
+    pub fn new(out: Box<dyn Write>) -> Self {
+        Self { out, violations: Vec::new(), column_unit: ColumnUnit::default() }
+    }
+}
+
+impl Reporter for SarifReporter {
+    fn on_start(&mut self, context: &ReportContext) -> Result<()> {
+        self.column_unit = context.column_unit;
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &ReviewViolation) -> Result<()> {
+        self.violations.push(violation.clone());
+        Ok(())
+    }
+
+    fn on_summary(&mut self, _summary: &ReviewSummary) -> Result<()> {
+        writeln!(
+            self.out,
+            "{}",
+            serde_json::to_string_pretty(&render_sarif(&self.violations, self.column_unit))?
+        )?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Blocker | Severity::Critical => "error",
+        Severity::Major => "warning",
+        Severity::Warning | Severity::Info => "note",
+    }
+}
+
+/// Build the SARIF log: each distinct rule once under `tool.driver.rules`, one result per
+/// violation pointing back to it by index, fingerprinted so dashboards track findings
+/// across runs. SARIF has no byte columns, so `column_unit` only chooses between UTF-16
+/// code units and code points.
+pub fn render_sarif(violations: &[ReviewViolation], column_unit: ColumnUnit) -> Value {
+    let (column_unit, column_kind) = match column_unit {
+        ColumnUnit::CodePoints => (ColumnUnit::CodePoints, "unicodeCodePoints"),
+        ColumnUnit::Utf16 | ColumnUnit::Utf8 => (ColumnUnit::Utf16, "utf16CodeUnits"),
+    };
+
+    let mut rule_ids: Vec<&str> = Vec::new();
+    let mut rules: Vec<Value> = Vec::new();
+    for violation in violations {
+        if rule_ids.contains(&violation.rule.id.as_str()) {
+            continue;
+        }
+        rule_ids.push(&violation.rule.id);
+
+        let rule = &violation.rule;
+        let mut descriptor = json!({
+            "id": rule.id,
+            "name": rule.name,
+            "shortDescription": { "text": rule.name },
+            "fullDescription": { "text": rule.description },
+            "help": { "text": rule.fix_suggestion },
+            "defaultConfiguration": { "level": sarif_level(rule.severity) },
+            "properties": { "tags": rule.tags },
+        });
+        if let Some(source_url) = &rule.source_url {
+            descriptor["helpUri"] = json!(source_url);
+        }
+        rules.push(descriptor);
+    }
+
+    let results: Vec<Value> = violations
+        .iter()
+        .map(|violation| {
+            let rule_index = rule_ids.iter().position(|id| *id == violation.rule.id);
+            let mut region = json!({
+                "startLine": violation.line_number,
+                "snippet": { "text": violation.content }
+            });
+            if let Some(columns) = violation_columns(violation, column_unit) {
+                region["startColumn"] = json!(columns.start);
+                region["endColumn"] = json!(columns.end);
+            }
+            let mut result = json!({
+                "ruleId": violation.rule.id,
+                "ruleIndex": rule_index,
+                "level": sarif_level(violation.severity),
+                "message": {
+                    "text": format!("{}: {}", violation.rule.name, violation.fix_suggestion)
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": violation.file_path },
+                        "region": region
+                    }
+                }],
+                "partialFingerprints": { "patingin/v1": violation.fingerprint() },
+            });
+            if !violation.captures.is_empty() {
+                result["properties"] = json!({ "captures": violation.captures });
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "patingin",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "columnKind": column_kind,
+            "results": results,
+        }]
+    })
+}
+
+#[cfg(test)]
+mod sarif_tests {
+    use super::super::test_support::{render, violation};
+    use super::super::ReportContext;
+    use super::*;
+
+    #[test]
+    fn test_sarif_rules_and_results() {
+        let mut other = violation("lib/auth.ex", 4, Severity::Critical);
+        other.rule.id = "other_rule".to_string();
+        other.rule.source_url = None;
+        let violations = vec![
+            violation("lib/user.ex", 10, Severity::Major),
+            other,
+            violation("lib/user.ex", 20, Severity::Warning),
+        ];
+
+        let sarif = render_sarif(&violations, ColumnUnit::Utf16);
+        let run = &sarif["runs"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["name"], "patingin");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["tool"]["driver"]["rules"][0]["helpUri"], "https://example.com/test_rule");
+        assert!(run["tool"]["driver"]["rules"][1].get("helpUri").is_none());
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[1]["ruleId"], "other_rule");
+        assert_eq!(results[1]["ruleIndex"], 1);
+        assert_eq!(results[1]["level"], "error");
+        assert_eq!(results[2]["level"], "note");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "lib/user.ex"
+        );
+        assert_eq!(results[0]["locations"][0]["physicalLocation"]["region"]["startLine"], 10);
+        assert_eq!(results[0]["partialFingerprints"]["patingin/v1"], violations[0].fingerprint());
+    }
+
+    #[test]
+    fn test_sarif_columns_follow_the_column_kind() {
+        let mut matched = violation("lib/user.ex", 10, Severity::Major);
+        matched.content = "\t\"😀\" <> String.to_atom(x)".to_string();
+        let start = matched.content.find("String").unwrap();
+        matched.span = Some(start..start + "String.to_atom".len());
+        let violations = vec![matched, violation("lib/user.ex", 20, Severity::Warning)];
+
+        let sarif = render_sarif(&violations, ColumnUnit::Utf8);
+        assert_eq!(sarif["runs"][0]["columnKind"], "utf16CodeUnits");
+        let region = |index: usize| {
+            sarif["runs"][0]["results"][index]["locations"][0]["physicalLocation"]["region"].clone()
+        };
+        // The emoji is two UTF-16 code units, and the tab one
+        assert_eq!(region(0)["startColumn"], 10);
+        assert_eq!(region(0)["endColumn"], 24);
+        assert!(region(1).get("startColumn").is_none());
+
+        let sarif = render_sarif(&violations, ColumnUnit::CodePoints);
+        assert_eq!(sarif["runs"][0]["columnKind"], "unicodeCodePoints");
+        let region = &sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startColumn"], 9);
+    }
+
+    #[test]
+    fn test_sarif_reporter_writes_valid_json() {
+        let output = render("sarif", &ReportContext::default(), &[]);
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(sarif["runs"][0]["results"].as_array().unwrap().len(), 0);
+    }
+}
@@ -5,7 +5,7 @@ use std::process::Command;
 fn test_actual_command_help_matches_docs() {
     // Test that our binary actually supports the commands we document
     let output = Command::new("cargo")
-        .args(&["run", "--", "--help"])
+        .args(["run", "--", "--help"])
         .output()
         .expect("Failed to run patingin --help");
 
@@ -20,7 +20,7 @@ fn test_actual_command_help_matches_docs() {
 #[test]
 fn test_review_command_options() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "review", "--help"])
+        .args(["run", "--", "review", "--help"])
         .output()
         .expect("Failed to run patingin review --help");
 
@@ -41,7 +41,7 @@ fn test_review_command_options() {
 #[test]
 fn test_rules_command_options() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "rules", "--help"])
+        .args(["run", "--", "rules", "--help"])
         .output()
         .expect("Failed to run patingin rules --help");
 
@@ -68,27 +68,31 @@ fn test_actual_builtin_rules_count() {
         "src/rules/builtin/rust.yml",
         "src/rules/builtin/zig.yml",
         "src/rules/builtin/sql.yml",
+        "src/rules/builtin/ruby.yml",
+        "src/rules/builtin/php.yml",
+        "src/rules/builtin/java.yml",
+        "src/rules/builtin/kotlin.yml",
     ];
 
     for file_path in rule_files.iter() {
-        let content =
-            fs::read_to_string(file_path).expect(&format!("Failed to read {}", file_path));
+        let content = fs::read_to_string(file_path)
+            .unwrap_or_else(|_| panic!("Failed to read {}", file_path));
         let rules: Vec<Value> = serde_yaml::from_str(&content)
-            .expect(&format!("Failed to parse YAML in {}", file_path));
+            .unwrap_or_else(|_| panic!("Failed to parse YAML in {}", file_path));
         total_rules += rules.len();
     }
 
     // Document the actual count we found
     println!("Actual built-in rules count: {}", total_rules);
     assert!(total_rules > 40, "Should have substantial number of rules");
-    assert!(total_rules < 60, "Sanity check on rule count");
+    assert!(total_rules < 100, "Sanity check on rule count");
 }
 
 #[test]
 fn test_test_count_matches_docs() {
     // Count actual test functions in the codebase
     let output = Command::new("find")
-        .args(&["src", "-name", "*.rs", "-exec", "grep", "-c", "fn test_", "{}", ";"])
+        .args(["src", "-name", "*.rs", "-exec", "grep", "-c", "fn test_", "{}", ";"])
         .output()
         .expect("Failed to count test functions");
 
@@ -124,7 +128,7 @@ mod claude_code_detection_tests {
 
         // Check npm installation status
         let npm_check =
-            Command::new("npm").args(&["list", "-g", "@anthropic-ai/claude-code"]).output();
+            Command::new("npm").args(["list", "-g", "@anthropic-ai/claude-code"]).output();
 
         if let Ok(output) = npm_check {
             let output_str = String::from_utf8_lossy(&output.stdout);
@@ -154,6 +158,10 @@ fn test_supported_languages_match_implementation() {
         "rust.yml",
         "zig.yml",
         "sql.yml",
+        "ruby.yml",
+        "php.yml",
+        "java.yml",
+        "kotlin.yml",
     ];
 
     for lang_file in expected_languages.iter() {
@@ -174,7 +182,7 @@ fn test_performance_claims_are_reasonable() {
     let start = Instant::now();
 
     let _output = Command::new("cargo")
-        .args(&["run", "--", "--help"])
+        .args(["run", "--", "--help"])
         .output()
         .expect("Failed to run patingin");
 
@@ -56,7 +56,13 @@ fn test_malformed_regex_patterns_in_custom_rules() {
         pattern: r"[invalid[regex".to_string(), // Invalid regex - unclosed bracket
         severity: "major".to_string(),
         fix: "Fix the issue".to_string(),
+        ai_hint: None,
+        auto_fix: None,
+        skip_comments: false,
+        skip_strings: false,
         enabled: true,
+        file_scope: None,
+        examples: vec![],
     };
 
     // Test that adding invalid regex pattern is handled gracefully
@@ -121,19 +127,19 @@ async fn test_empty_git_repository_handling() {
 
     // Initialize empty git repository (no commits)
     std::process::Command::new("git")
-        .args(&["init"])
+        .args(["init"])
         .current_dir(repo_path)
         .output()
         .expect("Should initialize git repo");
 
     std::process::Command::new("git")
-        .args(&["config", "user.email", "test@example.com"])
+        .args(["config", "user.email", "test@example.com"])
         .current_dir(repo_path)
         .output()
         .expect("Should set git user email");
 
     std::process::Command::new("git")
-        .args(&["config", "user.name", "Test User"])
+        .args(["config", "user.name", "Test User"])
         .current_dir(repo_path)
         .output()
         .expect("Should set git user name");
@@ -148,6 +154,8 @@ async fn test_empty_git_repository_handling() {
         uncommitted: false,
         since: None,
         severity: None,
+        min_confidence: None,
+        stream: false,
         language: None,
         json: false,
         no_color: true,
@@ -155,6 +163,7 @@ async fn test_empty_git_repository_handling() {
         fix: false,
         auto_fix: false,
         no_confirm: false,
+        ..Default::default()
     };
 
     let result = review::run(review_args).await;
@@ -203,10 +212,7 @@ fn test_claude_code_integration_with_missing_cli() {
 
     // Should return a valid ClaudeCodeIntegration regardless of CLI availability
     // The 'available' field indicates whether CLI is present
-    assert!(
-        integration.available == true || integration.available == false,
-        "Claude Code integration should have valid availability status"
-    );
+    let _available: bool = integration.available;
 
     // If not available, version should be None
     if !integration.available {
@@ -15,19 +15,19 @@ fn test_git_diff_with_empty_repository() {
 
     // Initialize empty git repository (no commits)
     Command::new("git")
-        .args(&["init"])
+        .args(["init"])
         .current_dir(repo_path)
         .output()
         .expect("Should initialize git repo");
 
     Command::new("git")
-        .args(&["config", "user.email", "test@example.com"])
+        .args(["config", "user.email", "test@example.com"])
         .current_dir(repo_path)
         .output()
         .expect("Should set git user email");
 
     Command::new("git")
-        .args(&["config", "user.name", "Test User"])
+        .args(["config", "user.name", "Test User"])
         .current_dir(repo_path)
         .output()
         .expect("Should set git user name");
@@ -175,16 +175,16 @@ fn test_git_diff_graceful_degradation() {
 
 fn setup_git_repo_with_commit(repo_path: &std::path::Path) -> Result<()> {
     // Initialize git repo
-    Command::new("git").args(&["init"]).current_dir(repo_path).output()?;
+    Command::new("git").args(["init"]).current_dir(repo_path).output()?;
 
     // Configure git user
     Command::new("git")
-        .args(&["config", "user.email", "test@example.com"])
+        .args(["config", "user.email", "test@example.com"])
         .current_dir(repo_path)
         .output()?;
 
     Command::new("git")
-        .args(&["config", "user.name", "Test User"])
+        .args(["config", "user.name", "Test User"])
         .current_dir(repo_path)
         .output()?;
 
@@ -192,12 +192,9 @@ fn setup_git_repo_with_commit(repo_path: &std::path::Path) -> Result<()> {
     let readme = repo_path.join("README.md");
     std::fs::write(readme, "# Test Repository\n")?;
 
-    Command::new("git").args(&["add", "README.md"]).current_dir(repo_path).output()?;
+    Command::new("git").args(["add", "README.md"]).current_dir(repo_path).output()?;
 
-    Command::new("git")
-        .args(&["commit", "-m", "Initial commit"])
-        .current_dir(repo_path)
-        .output()?;
+    Command::new("git").args(["commit", "-m", "Initial commit"]).current_dir(repo_path).output()?;
 
     Ok(())
 }
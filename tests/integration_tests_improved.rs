@@ -48,12 +48,12 @@ end
 
     // Add and commit the new file
     Command::new("git")
-        .args(&["add", "test_files/new_violations.ex"])
+        .args(["add", "test_files/new_violations.ex"])
         .current_dir(&project_root)
         .output()?;
 
     Command::new("git")
-        .args(&["commit", "-m", "Add test violations for integration test"])
+        .args(["commit", "-m", "Add test violations for integration test"])
         .current_dir(&project_root)
         .output()?;
 
@@ -79,7 +79,7 @@ end
 
     // Clean up - remove the test file and reset git
     fs::remove_file(&test_file)?;
-    Command::new("git").args(&["reset", "--hard", "HEAD~1"]).current_dir(&project_root).output()?;
+    Command::new("git").args(["reset", "--hard", "HEAD~1"]).current_dir(&project_root).output()?;
 
     Ok(())
 }
@@ -105,6 +105,8 @@ async fn test_review_existing_test_files() -> Result<()> {
         uncommitted: true, // Review unstaged changes
         since: None,
         severity: None,
+        min_confidence: None,
+        stream: false,
         language: Some(Language::Elixir),
         json: false,
         no_color: true,
@@ -112,6 +114,7 @@ async fn test_review_existing_test_files() -> Result<()> {
         fix: false,
         auto_fix: false,
         no_confirm: false,
+        ..Default::default()
     };
 
     // This should work without panicking and detect violations
@@ -150,7 +153,13 @@ async fn test_custom_rules_with_project_files() -> Result<()> {
         pattern: r"# Added violation for testing".to_string(),
         severity: "warning".to_string(),
         fix: "Remove test comment".to_string(),
+        ai_hint: None,
+        auto_fix: None,
+        skip_comments: false,
+        skip_strings: false,
         enabled: true,
+        file_scope: None,
+        examples: vec![],
     };
 
     custom_rules_manager.add_project_rule(